@@ -0,0 +1,227 @@
+//! High-level API for embedding actr in other Rust tools
+//!
+//! IDE plugins and CI bots can run the install, check and generation pipelines
+//! without shelling out to `actr` or assembling a service container:
+//!
+//! ```ignore
+//! let project = std::path::Path::new("services/echo");
+//! let installed = actr_cli::api::install(project, &Default::default()).await?;
+//! let report = actr_cli::api::check(project, &Default::default()).await?;
+//! let generated = actr_cli::api::generate(project, &Default::default()).await?;
+//! ```
+//!
+//! Every call runs in `project_dir`, which Actr.toml, Actr.lock.toml and the proto
+//! cache are resolved against. The process has a single working directory, so calls
+//! are serialized and the previous directory is restored afterwards, even when the
+//! call fails or panics. [`install`] and [`generate`] hold the project lock
+//! (`.actr/lock`) like `actr install` and `actr gen` do, so they never interleave
+//! their writes with an `actr` process working on the same project.
+//!
+//! Progress bars are disabled, but the status lines `actr` prints are not: they go
+//! to stdout as with the CLI. Errors are [`ActrCliError`](crate::core::ActrCliError)s
+//! or [`crate::error::ActrCliError`]s wrapped in `anyhow::Error`.
+
+use crate::commands::codegen::GenerationOptions;
+use crate::commands::install::InstallCommand;
+use crate::core::{
+    Command, CommandArgs, CommandContext, CommandResult, ComponentOverride, ContainerBuilder,
+    GenerationResult, GlobalProtoCache, InstallResult, ValidationReport,
+};
+use crate::project_lock::ProjectLock;
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// How long to wait for another actr process to release the project lock, the
+/// default of `--lock-timeout`
+const LOCK_WAIT: Duration = Duration::from_secs(30);
+
+/// Settings shared by every call, the library side of the global CLI options
+#[derive(Debug, Clone, Default)]
+pub struct ApiOptions {
+    /// Actr.toml to use instead of `<project_dir>/Actr.toml` (`--config`), relative to
+    /// `project_dir`
+    pub config: Option<PathBuf>,
+    /// Resolve dependencies from Actr.lock.toml and the proto cache only (`--offline`)
+    pub offline: bool,
+    /// Mock components answering from JSON fixtures (`--component`)
+    pub components: Vec<ComponentOverride>,
+}
+
+/// Options of [`install`], as `actr install [PACKAGE]...`
+#[derive(Debug, Clone, Default)]
+pub struct InstallOptions {
+    pub api: ApiOptions,
+    /// Packages to add; empty installs the dependencies of Actr.toml
+    pub packages: Vec<String>,
+    /// Reinstall dependencies that are already installed (`--force`)
+    pub force: bool,
+    /// Re-resolve every dependency (`--force-update`)
+    pub force_update: bool,
+    /// Skip fingerprint verification (`--skip-verification`)
+    pub skip_verification: bool,
+}
+
+/// Install dependencies into `project_dir` and update Actr.toml and Actr.lock.toml
+pub async fn install(project_dir: &Path, options: &InstallOptions) -> Result<InstallResult> {
+    in_dir(project_dir, async {
+        let _lock = lock_project(options.api.config.as_deref(), "install").await?;
+        let context = command_context(&options.api)?;
+        let command = InstallCommand::new(
+            options.packages.clone(),
+            None,
+            None,
+            options.force,
+            options.force_update,
+            options.skip_verification,
+        );
+        context.container.validate(&command.required_components())?;
+        install_result(command.execute(&context).await?)
+    })
+    .await
+}
+
+/// Options of [`check`], as `actr check`
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    pub api: ApiOptions,
+}
+
+/// Validate the config, dependencies, network reachability and fingerprints of
+/// `project_dir`; a failed check is a report with `is_valid == false`, not an error
+pub async fn check(project_dir: &Path, options: &CheckOptions) -> Result<ValidationReport> {
+    in_dir(project_dir, async {
+        let context = command_context(&options.api)?;
+        context
+            .container
+            .get_validation_pipeline()?
+            .validate_project()
+            .await
+    })
+    .await
+}
+
+/// Run `actr gen` in `project_dir` and return the files written
+///
+/// See [`GenerationOptions`] for the defaults (no scaffolds, no validation step).
+pub async fn generate(project_dir: &Path, options: &GenerationOptions) -> Result<GenerationResult> {
+    in_dir(project_dir, async {
+        let _lock = lock_project(Some(&options.config), "gen").await?;
+        let generated_files = options.to_command().run().await?;
+        Ok(GenerationResult {
+            generated_files,
+            warnings: vec![],
+            errors: vec![],
+        })
+    })
+    .await
+}
+
+/// Run `future` with `dir` as the working directory, one call at a time
+async fn in_dir<T>(dir: &Path, future: impl Future<Output = Result<T>>) -> Result<T> {
+    static WORKING_DIR: Mutex<()> = Mutex::const_new(());
+    let _guard = WORKING_DIR.lock().await;
+
+    let original_dir = std::env::current_dir()?;
+    std::env::set_current_dir(dir).map_err(|e| anyhow!("Cannot enter {}: {e}", dir.display()))?;
+    let _restore = RestoreDir(original_dir);
+    future.await
+}
+
+/// Changes back to the saved working directory on drop, so an early return or a
+/// panic inside [`in_dir`] does not leave the process in the project directory
+struct RestoreDir(PathBuf);
+
+impl Drop for RestoreDir {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.0);
+    }
+}
+
+/// Take `.actr/lock` in the project of `config` (Actr.toml when `None`), as `actr`
+/// does for mutating commands
+async fn lock_project(config: Option<&Path>, command: &str) -> Result<ProjectLock> {
+    let root = match config.and_then(Path::parent) {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    ProjectLock::acquire(&root, command, LOCK_WAIT).await
+}
+
+/// The context `actr` builds for a command from its global options
+fn command_context(options: &ApiOptions) -> Result<CommandContext> {
+    let mut builder = ContainerBuilder::new()
+        .offline(options.offline)
+        .quiet(true)
+        .global_cache(GlobalProtoCache::user_default());
+    if let Some(config) = &options.config {
+        builder = builder.config_path(config);
+    }
+    for component in &options.components {
+        builder = builder.component_override(component.clone());
+    }
+
+    let mut flags = HashMap::from([("quiet".to_string(), "true".to_string())]);
+    if options.offline {
+        flags.insert("offline".to_string(), "true".to_string());
+    }
+    Ok(CommandContext {
+        container: Arc::new(builder.build()?),
+        args: CommandArgs {
+            command: String::new(),
+            subcommand: None,
+            flags,
+            positional: Vec::new(),
+        },
+        working_dir: std::env::current_dir()?,
+    })
+}
+
+/// Typed result of `actr install`; messages such as "nothing to install" are warnings
+fn install_result(result: CommandResult) -> Result<InstallResult> {
+    match result {
+        CommandResult::Install(result) => Ok(result),
+        CommandResult::Error(message) => Err(anyhow!(message)),
+        CommandResult::Success(message) => {
+            let mut result = InstallResult::success();
+            result.warnings.push(message);
+            Ok(result)
+        }
+        CommandResult::Validation(report) if !report.is_success() => {
+            Err(anyhow!("Dependency validation failed"))
+        }
+        CommandResult::Validation(_) | CommandResult::Generation(_) => Ok(InstallResult::success()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_result() {
+        let result = install_result(CommandResult::Success("No packages to install".into()));
+        assert_eq!(result.unwrap().warnings, vec!["No packages to install"]);
+        assert!(install_result(CommandResult::Error("boom".into())).is_err());
+
+        let mut installed = InstallResult::success();
+        installed.cache_updates = 2;
+        let result = install_result(CommandResult::Install(installed)).unwrap();
+        assert_eq!(result.cache_updates, 2);
+    }
+
+    #[tokio::test]
+    async fn test_lock_project_locks_the_config_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("Actr.toml");
+
+        let lock = lock_project(Some(&config), "gen").await.unwrap();
+        assert_eq!(lock.path(), ProjectLock::path_for(dir.path()));
+        let holder = std::fs::read_to_string(lock.path()).unwrap();
+        assert!(holder.contains(r#""command":"gen""#));
+    }
+}
@@ -0,0 +1,298 @@
+//! `buf.yaml` / `buf.work.yaml` - buf modules as proto input
+//!
+//! A buf workspace lists module directories (`buf.work.yaml` `directories`, or
+//! `modules` in a v2 `buf.yaml`). Each module is a proto root: its files are
+//! generated and the root is passed to protoc as a `--proto_path`. Excluded
+//! directories are skipped. Dependencies declared under `deps` are fetched with
+//! `buf export` into `.actr/buf-export/` and only used for imports.
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+use walkdir::WalkDir;
+
+use crate::error::{ActrCliError, Result};
+
+pub const BUF_YAML: &str = "buf.yaml";
+pub const BUF_WORK_YAML: &str = "buf.work.yaml";
+
+/// Directory `buf export` writes dependency protos to, relative to the project
+pub const BUF_EXPORT_DIR: &str = ".actr/buf-export";
+
+#[derive(Debug, Default, Deserialize)]
+struct BufFile {
+    version: Option<String>,
+    /// v2: modules of the workspace
+    #[serde(default)]
+    modules: Vec<BufModuleEntry>,
+    /// v1: `build.excludes`
+    #[serde(default)]
+    build: BufBuild,
+    #[serde(default)]
+    deps: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BufBuild {
+    #[serde(default)]
+    excludes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BufModuleEntry {
+    #[serde(default = "default_module_path")]
+    path: String,
+    #[serde(default)]
+    excludes: Vec<String>,
+}
+
+fn default_module_path() -> String {
+    ".".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct BufWorkFile {
+    #[serde(default)]
+    directories: Vec<String>,
+}
+
+/// One proto root of a buf workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufModule {
+    pub root: PathBuf,
+    /// Directories below `root` whose protos are not generated
+    pub excludes: Vec<PathBuf>,
+}
+
+/// Modules and dependencies read from a buf configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BufLayout {
+    /// `buf.yaml` or `buf.work.yaml` the layout was read from
+    pub config_path: PathBuf,
+    pub modules: Vec<BufModule>,
+    /// Remote modules (`buf.build/googleapis/googleapis`)
+    pub deps: Vec<String>,
+}
+
+impl BufLayout {
+    /// Read the buf configuration in `dir`, preferring `buf.work.yaml`
+    pub fn detect(dir: &Path) -> Result<Option<Self>> {
+        if !dir.is_dir() {
+            return Ok(None);
+        }
+        let work = dir.join(BUF_WORK_YAML);
+        if work.is_file() {
+            return Self::load_work(&work).map(Some);
+        }
+        let buf = dir.join(BUF_YAML);
+        if buf.is_file() {
+            return Self::load(&buf).map(Some);
+        }
+        Ok(None)
+    }
+
+    /// Read a `buf.yaml` (v1 module or v2 workspace)
+    pub fn load(path: &Path) -> Result<Self> {
+        let file: BufFile = read_yaml(path)?;
+        let base = parent_dir(path);
+        let modules = if file.version.as_deref() == Some("v2") && !file.modules.is_empty() {
+            // v2 excludes are relative to the buf.yaml directory
+            file.modules
+                .iter()
+                .map(|module| BufModule {
+                    root: base.join(&module.path),
+                    excludes: module.excludes.iter().map(|e| base.join(e)).collect(),
+                })
+                .collect()
+        } else {
+            vec![BufModule {
+                root: base.clone(),
+                excludes: file.build.excludes.iter().map(|e| base.join(e)).collect(),
+            }]
+        };
+        Ok(Self {
+            config_path: path.to_path_buf(),
+            modules,
+            deps: file.deps,
+        })
+    }
+
+    /// Read a `buf.work.yaml` and the `buf.yaml` of each listed directory
+    pub fn load_work(path: &Path) -> Result<Self> {
+        let file: BufWorkFile = read_yaml(path)?;
+        let base = parent_dir(path);
+        let mut layout = Self {
+            config_path: path.to_path_buf(),
+            modules: Vec::new(),
+            deps: Vec::new(),
+        };
+        for directory in &file.directories {
+            let root = base.join(directory);
+            let module_config = root.join(BUF_YAML);
+            if module_config.is_file() {
+                let module = Self::load(&module_config)?;
+                layout.modules.extend(module.modules);
+                for dep in module.deps {
+                    if !layout.deps.contains(&dep) {
+                        layout.deps.push(dep);
+                    }
+                }
+            } else {
+                layout.modules.push(BufModule {
+                    root,
+                    excludes: Vec::new(),
+                });
+            }
+        }
+        if layout.modules.is_empty() {
+            return Err(ActrCliError::config_error(format!(
+                "{} lists no directories",
+                path.display()
+            )));
+        }
+        Ok(layout)
+    }
+
+    /// Module roots, in declaration order
+    pub fn roots(&self) -> Vec<PathBuf> {
+        self.modules
+            .iter()
+            .map(|module| module.root.clone())
+            .collect()
+    }
+
+    /// Proto files of every module, skipping excluded directories
+    pub fn proto_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for module in &self.modules {
+            if !module.root.is_dir() {
+                return Err(ActrCliError::config_error(format!(
+                    "buf module '{}' from {} does not exist",
+                    module.root.display(),
+                    self.config_path.display()
+                )));
+            }
+            let walker = WalkDir::new(&module.root)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_entry(|entry| !module.excludes.iter().any(|e| entry.path() == e));
+            for entry in walker {
+                let entry = entry.map_err(|e| ActrCliError::Io(e.into()))?;
+                if entry.file_type().is_file()
+                    && entry.path().extension().is_some_and(|ext| ext == "proto")
+                {
+                    files.push(entry.into_path());
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Export the protos of the workspace and its deps with `buf export`
+    ///
+    /// Returns the export directory, or `None` when there are no deps.
+    pub fn export_deps(&self, project_root: &Path) -> Result<Option<PathBuf>> {
+        if self.deps.is_empty() {
+            return Ok(None);
+        }
+        let export_dir = project_root.join(BUF_EXPORT_DIR);
+        if export_dir.exists() {
+            std::fs::remove_dir_all(&export_dir)?;
+        }
+        let output = StdCommand::new("buf")
+            .arg("export")
+            .arg(parent_dir(&self.config_path))
+            .arg("--output")
+            .arg(&export_dir)
+            .output()
+            .map_err(|e| {
+                ActrCliError::command_error(format!(
+                    "Failed to run 'buf export' (is buf installed?): {e}"
+                ))
+            })?;
+        if !output.status.success() {
+            return Err(ActrCliError::command_error(format!(
+                "buf export failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(Some(export_dir))
+    }
+}
+
+fn read_yaml<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T> {
+    let content = std::fs::read_to_string(path)?;
+    serde_yaml::from_str(&content)
+        .map_err(|e| ActrCliError::config_error(format!("Invalid {}: {e}", path.display())))
+}
+
+fn parent_dir(path: &Path) -> PathBuf {
+    path.parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn touch(path: &Path) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "syntax = \"proto3\";\n").unwrap();
+    }
+
+    #[test]
+    fn test_buf_work_layout() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join(BUF_WORK_YAML),
+            "version: v1\ndirectories:\n  - api\n  - vendor\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("api")).unwrap();
+        std::fs::write(
+            root.join("api").join(BUF_YAML),
+            "version: v1\nbuild:\n  excludes:\n    - internal\ndeps:\n  - buf.build/googleapis/googleapis\n",
+        )
+        .unwrap();
+        touch(&root.join("api/acme/echo.proto"));
+        touch(&root.join("api/internal/skip.proto"));
+        touch(&root.join("vendor/common.proto"));
+
+        let layout = BufLayout::detect(root).unwrap().unwrap();
+        assert_eq!(layout.roots(), vec![root.join("api"), root.join("vendor")]);
+        assert_eq!(layout.deps, vec!["buf.build/googleapis/googleapis"]);
+        assert_eq!(
+            layout.proto_files().unwrap(),
+            vec![
+                root.join("api/acme/echo.proto"),
+                root.join("vendor/common.proto")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_buf_v2_modules() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join(BUF_YAML),
+            "version: v2\nmodules:\n  - path: proto\n    excludes:\n      - proto/legacy\n",
+        )
+        .unwrap();
+        touch(&root.join("proto/echo.proto"));
+        touch(&root.join("proto/legacy/old.proto"));
+
+        let layout = BufLayout::detect(root).unwrap().unwrap();
+        assert_eq!(layout.roots(), vec![root.join("proto")]);
+        assert_eq!(
+            layout.proto_files().unwrap(),
+            vec![root.join("proto/echo.proto")]
+        );
+        assert!(layout.export_deps(root).unwrap().is_none());
+        assert!(BufLayout::detect(&root.join("proto")).unwrap().is_none());
+    }
+}
@@ -0,0 +1,223 @@
+//! Cache command implementation - inspect and maintain the remote proto cache
+//!
+//! Operates on `protos/remote/`, where `actr install` stores fetched protos:
+//! - `actr cache stats` - entry count, size and hit rate
+//! - `actr cache list` - cached services with timestamps and fingerprints
+//! - `actr cache clear` - remove every cached service
+//! - `actr cache invalidate <service>` - remove a single service
+
+use crate::core::{
+    ActrCliError, CacheEntry, Command, CommandContext, CommandResult, ComponentType,
+};
+use crate::human_println;
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Args, Subcommand};
+use owo_colors::OwoColorize;
+use std::io::IsTerminal;
+
+/// Cache command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Inspect and maintain the proto cache",
+    long_about = "Inspect and maintain the proto cache in protos/remote/.\n\nExamples:\n  actr cache stats\n  actr cache list\n  actr cache invalidate user-service\n  actr cache clear --yes"
+)]
+pub struct CacheCommand {
+    #[command(subcommand)]
+    pub command: CacheSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheSubcommand {
+    /// Show entry count, size and hit rate
+    Stats,
+    /// List cached services
+    List,
+    /// Remove all cached services
+    Clear {
+        /// Do not ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Remove the cached protos of one service
+    Invalidate {
+        /// Service name (directory under protos/remote/)
+        service: String,
+    },
+}
+
+#[async_trait]
+impl Command for CacheCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let cache_manager = context.container.get_cache_manager()?;
+
+        match &self.command {
+            CacheSubcommand::Stats => {
+                let stats = cache_manager.get_cache_stats().await?;
+                if context.is_json() {
+                    let report = serde_json::json!({
+                        "entries": stats.total_entries,
+                        "size_bytes": stats.total_size_bytes,
+                        "lookups": stats.lookups,
+                        "hit_rate": stats.hit_rate,
+                        "miss_rate": stats.miss_rate,
+                    });
+                    return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                        &report,
+                    )?));
+                }
+                let hit_rate = if stats.lookups == 0 {
+                    "n/a".to_string()
+                } else {
+                    format!(
+                        "{:.1}% of {} lookups",
+                        stats.hit_rate * 100.0,
+                        stats.lookups
+                    )
+                };
+                Ok(CommandResult::Success(format!(
+                    "📦 Proto cache\n   entries:  {}\n   size:     {}\n   hit rate: {}",
+                    stats.total_entries,
+                    format_size(stats.total_size_bytes),
+                    hit_rate
+                )))
+            }
+            CacheSubcommand::List => {
+                let entries = cache_manager.list_cached().await?;
+                if context.is_json() {
+                    let report: Vec<_> = entries.iter().map(entry_json).collect();
+                    return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                        &report,
+                    )?));
+                }
+                if entries.is_empty() {
+                    return Ok(CommandResult::Success("Proto cache is empty".to_string()));
+                }
+                for entry in &entries {
+                    human_println!(
+                        "{}  {} files, {}, cached {}",
+                        entry.service.cyan(),
+                        entry.files,
+                        format_size(entry.size_bytes),
+                        entry
+                            .cached_at
+                            .map(format_time)
+                            .unwrap_or_else(|| "unknown".to_string())
+                    );
+                    if let Some(fingerprint) = &entry.fingerprint {
+                        human_println!("   {}", fingerprint.dimmed());
+                    }
+                }
+                Ok(CommandResult::Success(format!(
+                    "{} cached services",
+                    entries.len()
+                )))
+            }
+            CacheSubcommand::Clear { yes } => {
+                let count = cache_manager.list_cached().await?.len();
+                if count == 0 {
+                    return Ok(CommandResult::Success("Proto cache is empty".to_string()));
+                }
+                if !yes {
+                    if context.is_json() || !std::io::stdin().is_terminal() {
+                        return Err(ActrCliError::InvalidArgument {
+                            message: "Refusing to clear the cache without confirmation; pass --yes"
+                                .to_string(),
+                        }
+                        .into());
+                    }
+                    let user_interface = context.container.get_user_interface()?;
+                    let prompt = format!("Remove {count} cached services from protos/remote/?");
+                    if !user_interface.confirm(&prompt).await? {
+                        return Ok(CommandResult::Success("Cancelled".to_string()));
+                    }
+                }
+                cache_manager.clear_cache().await?;
+                Ok(CommandResult::Success(format!(
+                    "{} Removed {} cached services",
+                    "✅".green(),
+                    count
+                )))
+            }
+            CacheSubcommand::Invalidate { service } => {
+                let cached = cache_manager
+                    .list_cached()
+                    .await?
+                    .iter()
+                    .any(|entry| &entry.service == service);
+                if !cached {
+                    return Err(ActrCliError::Cache {
+                        message: format!("'{service}' is not in the proto cache"),
+                    }
+                    .into());
+                }
+                cache_manager.invalidate_cache(service).await?;
+                Ok(CommandResult::Success(format!(
+                    "{} Invalidated {}; run 'actr install' to fetch it again",
+                    "✅".green(),
+                    service
+                )))
+            }
+        }
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        match self.command {
+            CacheSubcommand::Clear { yes: false } => {
+                vec![ComponentType::CacheManager, ComponentType::UserInterface]
+            }
+            _ => vec![ComponentType::CacheManager],
+        }
+    }
+
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect and maintain the proto cache"
+    }
+}
+
+fn entry_json(entry: &CacheEntry) -> serde_json::Value {
+    serde_json::json!({
+        "service": entry.service,
+        "files": entry.files,
+        "size_bytes": entry.size_bytes,
+        "cached_at": entry.cached_at.map(format_time),
+        "fingerprint": entry.fingerprint,
+    })
+}
+
+fn format_time(time: std::time::SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Human-readable byte count (1024-based)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
@@ -0,0 +1,228 @@
+//! Call command implementation - invoke one RPC of a dependency for debugging
+//!
+//! `actr call <service> <Method> [JSON]` looks the RPC up in the protos cached by
+//! `actr install` (or, when they are not cached, in the service spec published on
+//! the signaling server), converts the JSON request to protobuf with them and
+//! prints the decoded response together with the round-trip latency.
+//!
+//! Without `--endpoint` the target is discovered through the signaling server
+//! configured in Actr.toml. The CLI has no WebRTC transport of its own, so the
+//! call itself goes to an HTTP endpoint speaking the `actr mock` protocol
+//! (`POST /<package>.<Service>/<Method>` with an `application/x-protobuf` body):
+//! an `actr mock` instance, or a gateway in front of the real actor. When no
+//! endpoint is given the command stops after discovery and says so.
+
+use crate::commands::mock::PROTOBUF_CONTENT_TYPE;
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, NetworkSettings, ProtoFile,
+};
+use crate::human_println;
+use crate::proto_codec::{ProtoRegistry, RpcMethod};
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use clap::Args;
+use serde_json::Value;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+/// Call command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Invoke an RPC of a dependency with a JSON request",
+    long_about = "Encode a JSON request with the dependency's protos, invoke the RPC and print the decoded response and latency. Without --endpoint the service is discovered through signaling; as the CLI has no WebRTC transport, the call itself needs an HTTP endpoint such as 'actr mock'.\n\nExamples:\n  actr call user-service GetUser '{\"userId\": \"42\"}' --endpoint http://127.0.0.1:50080\n  actr call user-service UserService/GetUser @request.json --endpoint http://127.0.0.1:50080\n  echo '{}' | actr call user-service Ping - --endpoint http://127.0.0.1:50080"
+)]
+pub struct CallCommand {
+    /// Dependency to call, as named in Actr.toml
+    pub service: String,
+
+    /// RPC to invoke: Method, Service/Method or package.Service/Method
+    pub rpc: String,
+
+    /// Request as JSON; '@FILE' reads a file and '-' reads stdin
+    #[arg(default_value = "{}")]
+    pub request: String,
+
+    /// HTTP endpoint serving the RPC, e.g. the address of 'actr mock'
+    #[arg(long, value_name = "URL")]
+    pub endpoint: Option<String>,
+}
+
+#[async_trait]
+impl Command for CallCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let (cache_manager, service_discovery) = (
+            context.container.get_cache_manager()?,
+            context.container.get_service_discovery()?,
+        );
+
+        let request: Value =
+            serde_json::from_str(&self.read_request()?).context("Request is not valid JSON")?;
+        let proto_files = match cache_manager.get_cached_proto(&self.service).await? {
+            Some(cached) => cached.files,
+            None if context.is_offline() => {
+                return Err(ActrCliError::Cache {
+                    message: format!(
+                        "Protos for '{}' are not cached under protos/remote/{}. Run 'actr install' first.",
+                        self.service, self.service
+                    ),
+                }
+                .into());
+            }
+            None => {
+                service_discovery
+                    .get_service_details(&self.service)
+                    .await?
+                    .proto_files
+            }
+        };
+        let registry = registry(&self.service, &proto_files)?;
+        let rpc = registry.find_rpc(&self.rpc)?;
+        let body = registry
+            .encode(&rpc.input_type, &request)
+            .with_context(|| format!("Request does not match {}", rpc.input_type))?;
+
+        let Some(endpoint) = &self.endpoint else {
+            if context.is_offline() {
+                bail!(
+                    "Offline mode cannot reach '{}'; pass --endpoint",
+                    self.service
+                );
+            }
+            let status = service_discovery
+                .check_service_availability(&self.service)
+                .await?;
+            if !status.is_available {
+                return Err(ActrCliError::ServiceNotFound {
+                    name: self.service.clone(),
+                }
+                .into());
+            }
+            bail!(
+                "'{}' is online, but the CLI cannot open a WebRTC session to it. Pass --endpoint with an HTTP gateway to the actor, or run 'actr mock {}' and call that.",
+                self.service,
+                self.service
+            );
+        };
+
+        let (decoded, latency) = invoke(endpoint, &registry, rpc, body).await?;
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "service": self.service,
+                "rpc": rpc.path(),
+                "endpoint": endpoint,
+                "latency_ms": latency_ms,
+                "response": decoded,
+            });
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &report,
+            )?));
+        }
+        human_println!("⏱️  {} answered in {latency_ms:.1} ms", rpc.path());
+        Ok(CommandResult::Success(serde_json::to_string_pretty(
+            &decoded,
+        )?))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::CacheManager, ComponentType::ServiceDiscovery]
+    }
+
+    fn name(&self) -> &str {
+        "call"
+    }
+
+    fn description(&self) -> &str {
+        "Invoke an RPC of a dependency with a JSON request"
+    }
+}
+
+impl CallCommand {
+    fn read_request(&self) -> Result<String> {
+        if self.request == "-" {
+            let mut request = String::new();
+            std::io::stdin()
+                .read_to_string(&mut request)
+                .context("Failed to read the request from stdin")?;
+            return Ok(request);
+        }
+        match self.request.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read request file {path}")),
+            None => Ok(self.request.clone()),
+        }
+    }
+}
+
+/// POST the encoded request to `endpoint` and decode the reply
+pub async fn invoke(
+    endpoint: &str,
+    registry: &ProtoRegistry,
+    rpc: &RpcMethod,
+    body: Vec<u8>,
+) -> Result<(Value, Duration)> {
+    let url = format!("{}{}", endpoint.trim_end_matches('/'), rpc.path());
+    let client = NetworkSettings::from_env()?.http_client()?;
+    let started = Instant::now();
+    let response = client
+        .post(&url)
+        .header(reqwest::header::CONTENT_TYPE, PROTOBUF_CONTENT_TYPE)
+        .body(body)
+        .send()
+        .await
+        .with_context(|| format!("Failed to call {url}"))?;
+    let status = response.status();
+    let bytes = response.bytes().await?;
+    let latency = started.elapsed();
+
+    if !status.is_success() {
+        let message = serde_json::from_slice::<Value>(&bytes)
+            .ok()
+            .and_then(|body| body.get("error")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| String::from_utf8_lossy(&bytes).to_string());
+        bail!("{} failed ({status}): {message}", rpc.path());
+    }
+    let decoded = registry
+        .decode(&rpc.output_type, &bytes)
+        .with_context(|| format!("Response is not a valid {}", rpc.output_type))?;
+    Ok((decoded, latency))
+}
+
+fn registry(service: &str, files: &[ProtoFile]) -> Result<ProtoRegistry> {
+    ProtoRegistry::from_sources(files.iter().map(|file| file.content.as_str()))
+        .with_context(|| format!("Failed to parse the protos of '{service}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::mock::{MockFixtures, MockService, serve};
+    use std::sync::Arc;
+
+    const PROTO: &str = "syntax = \"proto3\";\npackage echo;\nmessage EchoRequest { string message = 1; int32 times = 2; }\nmessage EchoResponse { string reply = 1; int64 count = 2; }\nservice EchoService {\n  rpc Echo (EchoRequest) returns (EchoResponse);\n  rpc Shout (EchoRequest) returns (EchoRequest);\n}\n";
+
+    #[tokio::test]
+    async fn test_invoke_against_mock() {
+        let registry = ProtoRegistry::from_sources([PROTO]).unwrap();
+        let fixtures: MockFixtures =
+            serde_yaml::from_str("rpcs:\n  Echo:\n    response: { reply: hi, count: \"3\" }\n")
+                .unwrap();
+        let (service, _) = MockService::new(registry.clone()).with_fixtures(fixtures);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(serve(listener, Arc::new(service)));
+
+        let echo = registry.find_rpc("Echo").unwrap();
+        let request = serde_json::json!({ "message": "hi", "times": 2 });
+        let body = registry.encode(&echo.input_type, &request).unwrap();
+        let (response, _) = invoke(&endpoint, &registry, echo, body).await.unwrap();
+        assert_eq!(response, serde_json::json!({ "reply": "hi", "count": "3" }));
+
+        // Same-typed RPCs echo the decoded request
+        let shout = registry.find_rpc("EchoService/Shout").unwrap();
+        let body = registry.encode(&shout.input_type, &request).unwrap();
+        let (response, _) = invoke(&endpoint, &registry, shout, body).await.unwrap();
+        assert_eq!(response, request);
+    }
+}
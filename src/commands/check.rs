@@ -2,21 +2,42 @@
 //!
 //! The check command validates that services are available in the registry
 //! and optionally verifies they match the configured dependencies.
+//!
+//! With `--watch` the signaling, availability, connectivity and fingerprint checks
+//! re-run every `--interval` seconds: a terminal shows a live status table, other
+//! outputs get one line per round, and every healthy ↔ unhealthy transition is
+//! logged. `--max-failures <N>` ends the watch with an error after N consecutive
+//! failed rounds, so a smoke test can wait on a deployment. `--metrics-listen`
+//! additionally exports the latest round for Prometheus (see [`super::metrics`]).
 
+use crate::commands::codegen::manifest;
+use crate::commands::metrics::{self, Exposition, RoundStats};
 use crate::core::{
-    Command, CommandContext, CommandResult, ComponentType, DependencySpec, NetworkCheckOptions,
+    Command, CommandContext, CommandResult, ComponentType, DependencySpec, DependencyValidation,
+    FingerprintValidation, FingerprintValidator, NetworkCheckOptions, NetworkValidation, ProtoFile,
+    ResolvedDependency, ValidationPipeline,
+    events::{self, Event},
+    profile,
 };
-use actr_config::ConfigParser;
+use crate::tr;
+use actr_protocol::ActrTypeExt;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use clap::Args;
 use comfy_table::{Attribute, Cell, Color, Table};
 use futures_util::future;
 use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io::IsTerminal;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::MissedTickBehavior;
 use tracing::info;
 
 /// Check command - validates service availability
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 #[command(
     about = "Validate project dependencies",
     long_about = "Validate that services are available in the registry and match the configured dependencies"
@@ -35,72 +56,174 @@ pub struct CheckCommand {
     #[arg(short, long)]
     pub verbose: bool,
 
-    /// Timeout for each service check in seconds
-    #[arg(long, default_value = "10")]
+    /// Timeout for each service check in seconds (`actr check --timeout N` is a
+    /// deprecated spelling of this flag)
+    #[arg(
+        id = "check_timeout",
+        long = "check-timeout",
+        value_name = "SECS",
+        default_value = "10"
+    )]
     pub timeout: u64,
 
+    /// Maximum number of services checked at the same time
+    #[arg(long, default_value = "8", value_parser = clap::value_parser!(u32).range(1..))]
+    pub concurrency: u32,
+
     /// Also verify services are installed in Actr.lock.toml
     #[arg(long)]
     pub lock: bool,
+
+    /// Output format (text for humans, json for CI pipelines)
+    #[arg(long, value_enum, default_value_t = CheckOutputFormat::Text)]
+    pub format: CheckOutputFormat,
+
+    /// Check every member listed in Actr.workspace.toml
+    #[arg(long)]
+    pub workspace: bool,
+
+    /// Keep re-running the checks and show a live status table
+    #[arg(long, conflicts_with_all = ["workspace", "lock"])]
+    pub watch: bool,
+
+    /// Seconds between rounds in watch mode
+    #[arg(long, value_name = "SECS", default_value = "10", requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+    pub interval: u64,
+
+    /// Exit non-zero after this many consecutive failed rounds in watch mode
+    #[arg(long, value_name = "N", requires = "watch", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_failures: Option<u32>,
+
+    /// Serve the latest watch round as Prometheus metrics, e.g. 0.0.0.0:9095
+    #[arg(long, value_name = "ADDR", requires = "watch")]
+    pub metrics_listen: Option<SocketAddr>,
 }
 
-#[async_trait]
-impl Command for CheckCommand {
-    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
-        let config_path = self.config_file.as_deref().unwrap_or("Actr.toml");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum CheckOutputFormat {
+    /// Human-readable table (default)
+    #[default]
+    Text,
+    /// Structured JSON report
+    Json,
+}
 
-        let pipeline = {
-            let mut container = context.container.lock().unwrap();
-            container.get_validation_pipeline()?
-        };
-        let options = NetworkCheckOptions::with_timeout_secs(self.timeout);
+/// Machine-readable result of a check run (`--format json`)
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub config_file: String,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub signaling: Option<SignalingCheck>,
+    pub services: Vec<ServiceCheckReport>,
+    /// Generated files edited by hand since the last `actr gen` (warnings only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub generated_edits: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SignalingCheck {
+    pub url: String,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
 
-        println!("🔍 Starting dependency validation...");
-        info!("🔍 Starting dependency validation...");
+/// Check result for a single dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceCheckReport {
+    pub alias: String,
+    pub name: String,
+    pub actr_type: Option<String>,
+    pub passed: bool,
+    pub availability: AvailabilityCheck,
+    pub connectivity: ConnectivityCheck,
+    pub fingerprint: FingerprintCheck,
+    /// Present only when `--lock` was requested
+    pub lock: Option<LockCheck>,
+}
 
-        // 1. Validate Config and Signaling Server
-        let config_validation = pipeline.config_manager().validate_config().await?;
-        if !config_validation.is_valid {
-            let mut msg = format!("{} Configuration validation failed:\n", "❌".red());
-            for err in config_validation.errors {
-                msg.push_str(&format!("  - {}\n", err.red()));
-            }
-            return Ok(CommandResult::Error(msg));
+#[derive(Debug, Clone, Serialize)]
+pub struct AvailabilityCheck {
+    pub available: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectivityCheck {
+    pub applicable: bool,
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FingerprintCheck {
+    pub valid: bool,
+    pub expected: String,
+    pub actual: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LockCheck {
+    pub locked: bool,
+    pub locked_fingerprint: Option<String>,
+    pub matches: bool,
+    /// Cached protos whose semantic fingerprint differs from the lock file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub proto_mismatches: Vec<String>,
+}
+
+impl CheckReport {
+    fn new(config_file: &str) -> Self {
+        Self {
+            config_file: config_file.to_string(),
+            passed: false,
+            error: None,
+            signaling: None,
+            services: Vec::new(),
+            generated_edits: Vec::new(),
         }
+    }
 
-        let config = ConfigParser::from_file(config_path)
-            .with_context(|| format!("Failed to load config: {}", config_path))?;
+    fn failed(mut self, error: impl Into<String>) -> Self {
+        self.passed = false;
+        self.error = Some(error.into());
+        self
+    }
 
-        println!(
-            "🌐 Checking signaling server: {}...",
-            config.signaling_url.as_str()
-        );
-        info!(
-            "🌐 Checking signaling server: {}...",
-            config.signaling_url.as_str()
-        );
-        let signaling_status = pipeline
-            .network_validator()
-            .check_connectivity(config.signaling_url.as_str(), &options)
-            .await?;
-        if signaling_status.is_reachable {
-            let latency = signaling_status.response_time_ms.unwrap_or(0);
-            println!("  ✔ Signaling server is reachable ({}ms)", latency);
-            info!("  ✔ Signaling server is reachable ({}ms)", latency);
+    /// Print the report as JSON and map it to a command result.
+    ///
+    /// The JSON always goes to stdout so CI can parse it; failures are
+    /// additionally surfaced through `CommandResult::Error` for the exit code.
+    fn into_result(self) -> Result<CommandResult> {
+        let json =
+            serde_json::to_string_pretty(&self).context("Failed to serialize check report")?;
+        if self.passed {
+            Ok(CommandResult::Success(json))
+        } else if crate::core::json_output() {
+            // --json: the global formatter prints the report once, as an error document
+            Ok(CommandResult::Error(json))
         } else {
-            let err = signaling_status
-                .error
-                .unwrap_or_else(|| "Unknown error".to_string());
-            return Ok(CommandResult::Error(format!(
-                "{} Signaling server unreachable: {}",
-                "❌".red(),
-                err.red()
-            )));
+            println!("{json}");
+            Ok(CommandResult::Error(
+                self.error
+                    .unwrap_or_else(|| tr!("check.services_failed_short")),
+            ))
         }
+    }
+}
 
-        // 2. Resolve Dependencies to check
+impl CheckCommand {
+    fn is_json(&self) -> bool {
+        self.format == CheckOutputFormat::Json
+    }
 
-        let all_specs: Vec<DependencySpec> = config
+    /// Dependencies of the config, narrowed to the requested packages
+    fn specs_to_check(&self, config: &actr_config::Config) -> Vec<DependencySpec> {
+        config
             .dependencies
             .iter()
             .map(|d| DependencySpec {
@@ -108,119 +231,69 @@ impl Command for CheckCommand {
                 name: d.name.clone(),
                 actr_type: d.actr_type.clone(),
                 fingerprint: d.fingerprint.clone(),
+                constraint: Default::default(),
             })
-            .collect();
-
-        let specs_to_check = if self.packages.is_empty() {
-            all_specs
-        } else {
-            all_specs
-                .into_iter()
-                .filter(|s| self.packages.contains(&s.name) || self.packages.contains(&s.alias))
-                .collect()
-        };
-
-        if specs_to_check.is_empty() {
-            if self.packages.is_empty() {
-                return Ok(CommandResult::Success(
-                    "No dependencies to check".to_string(),
-                ));
-            } else {
-                return Ok(CommandResult::Error(format!(
-                    "None of the specified packages found in {}",
-                    config_path
-                )));
-            }
-        }
-
-        // 3. Perform Validation via Pipeline
-        let dep_validations = pipeline.validate_dependencies(&specs_to_check).await?;
-
-        // 3.1 Lock File Validation (if requested)
-        if self.lock {
-            println!("🔒 Verifying lock file integrity...");
-            info!("🔒 Verifying lock file integrity...");
-            let lock_path = std::path::Path::new("Actr.lock.toml");
-            if !lock_path.exists() {
-                return Ok(CommandResult::Error("Actr.lock.toml not found".to_string()));
-            }
-
-            let lock_file = actr_config::LockFile::from_file(lock_path)
-                .map_err(|e| anyhow::anyhow!("Failed to read lock file: {}", e))?;
-
-            for spec in &specs_to_check {
-                if let Some(locked) = lock_file.get_dependency(&spec.name) {
-                    // Check if versions/types match if necessary
-                    if let Some(spec_fp) = &spec.fingerprint
-                        && spec_fp != &locked.fingerprint
-                    {
-                        return Ok(CommandResult::Error(format!(
-                            "{} Fingerprint mismatch for '{}' in lock file:\n  Expected: {}\n  Locked:   {}",
-                            "❌".red(),
-                            spec.alias,
-                            spec_fp,
-                            locked.fingerprint
-                        )));
-                    }
-                } else {
-                    return Ok(CommandResult::Error(format!(
-                        "{} Dependency '{}' not found in Actr.lock.toml",
-                        "❌".red(),
-                        spec.alias
-                    )));
-                }
-            }
-            println!("  ✔ Lock file integrity verified");
-            info!("  ✔ Lock file integrity verified");
-        }
+            .filter(|s| {
+                self.packages.is_empty()
+                    || self.packages.contains(&s.name)
+                    || self.packages.contains(&s.alias)
+            })
+            .collect()
+    }
 
-        // For network and fingerprint, we need ResolvedDependency
-        // Use parallel fetch for service details to improve performance
-        let fetch_futures = specs_to_check.iter().map(|spec| {
-            let sd = pipeline.service_discovery().clone();
-            let name = spec.name.clone();
+    /// Check every spec, at most --concurrency at a time
+    ///
+    /// try_join_all keeps the results in the order of `specs`.
+    async fn check_services(
+        &self,
+        pipeline: &ValidationPipeline,
+        specs: &[DependencySpec],
+        options: &NetworkCheckOptions,
+    ) -> Result<Vec<ServiceOutcome>> {
+        let semaphore = Semaphore::new(self.concurrency.max(1) as usize);
+        let checks = specs.iter().map(|spec| {
+            let semaphore = &semaphore;
             async move {
-                let details = sd.get_service_details(&name).await;
-                (name, details)
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .context("Check semaphore closed")?;
+                check_service(pipeline, spec, options).await
             }
         });
+        future::try_join_all(checks).await
+    }
 
-        let fetch_results = future::join_all(fetch_futures).await;
-
-        let mut service_details_map = std::collections::HashMap::new();
-        let mut fetch_errors: Vec<(String, anyhow::Error)> = Vec::new();
-
-        for (name, result) in fetch_results {
-            match result {
-                Ok(details) => {
-                    service_details_map.insert(name, details);
-                }
-                Err(e) => {
-                    fetch_errors.push((name, e));
-                }
+    /// Progress lines go to stdout only in text mode so JSON output stays parseable
+    /// Warn about generated files that no longer match their `actr gen` manifest
+    fn generated_edits(&self, config_path: &std::path::Path) -> Vec<String> {
+        let root = config_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| std::path::Path::new("."));
+        let mut edits = Vec::new();
+        for output in manifest::find_manifests(root) {
+            let tampering = manifest::detect_tampering(&output).unwrap_or_default();
+            for entry in tampering {
+                let edit = format!("{}: {entry}", output.display());
+                self.progress(&format!("⚠️  {edit}"));
+                edits.push(edit);
             }
         }
+        if !edits.is_empty() {
+            self.progress(&tr!("check.restore_generated"));
+        }
+        edits
+    }
 
-        let resolved_deps: Vec<_> = specs_to_check
-            .iter()
-            .map(|spec| {
-                let details = service_details_map.get(&spec.name);
-                crate::core::ResolvedDependency {
-                    spec: spec.clone(),
-                    fingerprint: details
-                        .map(|d| d.info.fingerprint.clone())
-                        .unwrap_or_default(),
-                    proto_files: details.map(|d| d.proto_files.clone()).unwrap_or_default(),
-                }
-            })
-            .collect();
-
-        let net_validations = pipeline
-            .validate_network_connectivity(&resolved_deps, &options)
-            .await?;
-        let fp_validations = pipeline.validate_fingerprints(&resolved_deps).await?;
+    fn progress(&self, msg: &str) {
+        if !self.is_json() {
+            println!("{msg}");
+        }
+        info!("{msg}");
+    }
 
-        // 4. Report Results
+    fn render_table(&self, report: &CheckReport) -> Table {
         let mut table = Table::new();
         table.set_header(vec![
             Cell::new("Dependency").add_attribute(Attribute::Bold),
@@ -229,107 +302,764 @@ impl Command for CheckCommand {
             Cell::new("Fingerprint").add_attribute(Attribute::Bold),
         ]);
 
-        let mut all_ok = true;
-
-        for i in 0..specs_to_check.len() {
-            let spec = &specs_to_check[i];
-            let dep_v = &dep_validations[i];
-            let net_v = &net_validations[i];
-            let fp_v = &fp_validations[i];
-
-            let mut row = vec![Cell::new(&spec.alias)];
+        for service in &report.services {
+            let mut row = vec![Cell::new(&service.alias)];
 
             // Availability
-            if dep_v.is_available {
+            if service.availability.available {
                 row.push(Cell::new("✔ Available").fg(Color::Green));
             } else {
                 let err_msg = if self.verbose {
-                    dep_v.error.as_deref().unwrap_or("Unknown error")
+                    service
+                        .availability
+                        .error
+                        .as_deref()
+                        .unwrap_or("Unknown error")
                 } else {
                     "Missing"
                 };
                 row.push(Cell::new(format!("✘ {}", err_msg)).fg(Color::Red));
-                all_ok = false;
             }
 
             // Network
-            if !net_v.is_applicable {
+            let net = &service.connectivity;
+            if !net.applicable {
                 let cell_text = if self.verbose {
-                    net_v.error.clone().unwrap_or_else(|| "N/A".to_string())
+                    net.error.clone().unwrap_or_else(|| "N/A".to_string())
                 } else {
                     "N/A".to_string()
                 };
                 row.push(Cell::new(cell_text).fg(Color::Yellow));
-            } else if net_v.is_reachable {
-                let latency = net_v
+            } else if net.reachable {
+                let latency = net
                     .latency_ms
                     .map(|l| format!(" ({}ms)", l))
                     .unwrap_or_default();
                 row.push(Cell::new(format!("✔ Reachable{}", latency)).fg(Color::Green));
             } else {
-                // If service detail fetch failed earlier, network check will likely fail too.
-                // Check if we had a fetch error for this service
-                let fetch_err = fetch_errors
-                    .iter()
-                    .find(|(n, _)| n == &spec.name)
-                    .map(|(_, e)| e.to_string());
-
+                // A failed detail fetch is recorded as the connectivity error
                 let err_display = if self.verbose {
-                    if let Some(fe) = fetch_err {
-                        format!("Fetch Error: {}", fe)
-                    } else {
-                        net_v
-                            .error
-                            .clone()
-                            .unwrap_or_else(|| "Unreachable".to_string())
-                    }
+                    net.error
+                        .clone()
+                        .unwrap_or_else(|| "Unreachable".to_string())
                 } else {
                     "✘ Unreachable".to_string()
                 };
-
                 row.push(Cell::new(err_display).fg(Color::Red));
-                all_ok = false;
             }
 
             // Fingerprint
-            if fp_v.is_valid {
+            let fp = &service.fingerprint;
+            if fp.valid {
                 row.push(Cell::new("✔ Match").fg(Color::Green));
             } else {
                 let mut cell_text = "✘ Mismatch".to_string();
                 if self.verbose {
-                    let expected = &fp_v.expected.value;
-                    let actual_opt = fp_v.actual.as_ref().map(|f| &f.value);
-
-                    if let Some(actual) = actual_opt {
-                        if !expected.is_empty() {
+                    if let Some(actual) = &fp.actual {
+                        if !fp.expected.is_empty() {
                             cell_text = format!(
                                 "✘ Mismatch\n  Exp: {:.8}...\n  Act: {:.8}...",
-                                expected, actual
+                                fp.expected, actual
                             );
                         }
-                    } else if let Some(err) = &fp_v.error {
+                    } else if let Some(err) = &fp.error {
                         cell_text = format!("✘ Error: {}", err);
                     }
                 }
                 row.push(Cell::new(cell_text).fg(Color::Red));
-                all_ok = false;
             }
 
             table.add_row(row);
         }
 
-        println!("\n{table}");
+        table
+    }
+}
+
+/// Report entry of one dependency from its validation results, also sent as a
+/// `service_checked` event
+fn service_report(
+    spec: &DependencySpec,
+    outcome: ServiceOutcome,
+    lock: Option<LockCheck>,
+) -> ServiceCheckReport {
+    let ServiceOutcome {
+        dependency: dep_v,
+        network: net_v,
+        fingerprint: fp_v,
+        fetch_error: fetch_err,
+    } = outcome;
+
+    let passed = dep_v.is_available
+        && (!net_v.is_applicable || net_v.is_reachable)
+        && fp_v.is_valid
+        && lock.as_ref().is_none_or(|l| l.locked && l.matches);
+
+    let report = ServiceCheckReport {
+        alias: spec.alias.clone(),
+        name: spec.name.clone(),
+        actr_type: spec.actr_type.as_ref().map(|t| t.to_string_repr()),
+        passed,
+        availability: AvailabilityCheck {
+            available: dep_v.is_available,
+            error: dep_v.error,
+        },
+        connectivity: ConnectivityCheck {
+            applicable: net_v.is_applicable,
+            reachable: net_v.is_reachable,
+            latency_ms: net_v.latency_ms,
+            error: fetch_err.or(net_v.error),
+        },
+        fingerprint: FingerprintCheck {
+            valid: fp_v.is_valid,
+            expected: fp_v.expected.value,
+            actual: fp_v.actual.map(|f| f.value),
+            error: fp_v.error,
+        },
+        lock,
+    };
+    events::emit(Event::ServiceChecked {
+        service: report.alias.clone(),
+        passed: report.passed,
+        error: report.failure_reason(),
+    });
+    report
+}
+
+impl ServiceCheckReport {
+    /// Why the dependency failed, for transition logs
+    fn failure_reason(&self) -> Option<String> {
+        if self.passed {
+            None
+        } else if !self.availability.available {
+            Some(
+                self.availability
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "unavailable".to_string()),
+            )
+        } else if self.connectivity.applicable && !self.connectivity.reachable {
+            Some(
+                self.connectivity
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "unreachable".to_string()),
+            )
+        } else {
+            Some(
+                self.fingerprint
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "fingerprint mismatch".to_string()),
+            )
+        }
+    }
+}
+
+/// Compare cached protos with the per-proto semantic fingerprints of a lock entry
+async fn locked_proto_mismatches(
+    validator: &dyn FingerprintValidator,
+    locked: &actr_config::LockedDependency,
+) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    for meta in &locked.files {
+        // Lock files written before per-proto fingerprints have empty entries
+        if meta.fingerprint.is_empty() {
+            continue;
+        }
+        // Lock paths are relative to protos/remote/
+        let path = std::path::Path::new("protos/remote").join(&meta.path);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            mismatches.push(format!("{} is not cached", meta.path));
+            continue;
+        };
+        let file = ProtoFile {
+            name: meta.path.clone(),
+            path,
+            content,
+            services: Vec::new(),
+        };
+        match validator.compute_proto_fingerprint(&file).await {
+            Ok(actual) if actual.value == meta.fingerprint => {}
+            Ok(actual) => mismatches.push(format!(
+                "{}: locked {}, cached {}",
+                meta.path, meta.fingerprint, actual.value
+            )),
+            Err(e) => mismatches.push(format!("{}: {}", meta.path, e)),
+        }
+    }
+    mismatches
+}
+
+/// Validation results for one dependency
+struct ServiceOutcome {
+    dependency: DependencyValidation,
+    network: NetworkValidation,
+    fingerprint: FingerprintValidation,
+    /// Error fetching service details, reported in the network column
+    fetch_error: Option<String>,
+}
+
+/// Run every pipeline check for a single dependency
+async fn check_service(
+    pipeline: &ValidationPipeline,
+    spec: &DependencySpec,
+    options: &NetworkCheckOptions,
+) -> Result<ServiceOutcome> {
+    let dependency = pipeline
+        .validate_dependencies(std::slice::from_ref(spec))
+        .await?
+        .into_iter()
+        .next()
+        .context("Missing dependency validation result")?;
+
+    let (details, fetch_error) = match pipeline
+        .service_discovery()
+        .get_service_details(&spec.name)
+        .await
+    {
+        Ok(details) => (Some(details), None),
+        Err(e) => (None, Some(format!("Fetch Error: {}", e))),
+    };
+    let resolved = ResolvedDependency {
+        spec: spec.clone(),
+        fingerprint: details
+            .as_ref()
+            .map(|d| d.info.fingerprint.clone())
+            .unwrap_or_default(),
+        dependencies: details
+            .as_ref()
+            .map(|d| d.dependencies.clone())
+            .unwrap_or_default(),
+        proto_files: details.map(|d| d.proto_files).unwrap_or_default(),
+        selected_tag: None,
+    };
+    let resolved = std::slice::from_ref(&resolved);
+
+    let network = pipeline
+        .validate_network_connectivity(resolved, options)
+        .await?
+        .into_iter()
+        .next()
+        .context("Missing network validation result")?;
+    let fingerprint = pipeline
+        .validate_fingerprints(resolved)
+        .await?
+        .into_iter()
+        .next()
+        .context("Missing fingerprint validation result")?;
+
+    Ok(ServiceOutcome {
+        dependency,
+        network,
+        fingerprint,
+        fetch_error,
+    })
+}
+
+/// Transitions kept on screen in watch mode
+const RECENT_TRANSITIONS: usize = 10;
+
+/// What a watch re-checks every round
+struct WatchTarget<'a> {
+    config_path: &'a str,
+    /// `None` in offline mode
+    signaling_url: Option<String>,
+    specs: &'a [DependencySpec],
+    pipeline: &'a ValidationPipeline,
+    options: &'a NetworkCheckOptions,
+}
+
+/// Health of the signaling server or one dependency in a round
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TargetHealth {
+    name: String,
+    healthy: bool,
+    detail: Option<String>,
+}
+
+/// Transitions and failure streaks across watch rounds
+#[derive(Debug, Default)]
+struct WatchState {
+    healthy: HashMap<String, bool>,
+    rounds: u64,
+    consecutive_failures: u32,
+    /// Latest transitions, oldest first
+    recent: VecDeque<String>,
+}
+
+impl WatchState {
+    /// Record a round checked at `time`; returns the transitions it caused
+    fn record(&mut self, time: &str, targets: &[TargetHealth]) -> Vec<String> {
+        self.rounds += 1;
+        if targets.iter().all(|target| target.healthy) {
+            self.consecutive_failures = 0;
+        } else {
+            self.consecutive_failures += 1;
+        }
+
+        let mut transitions = Vec::new();
+        for target in targets {
+            let previous = self.healthy.insert(target.name.clone(), target.healthy);
+            let detail = target.detail.as_deref().unwrap_or("failed");
+            let transition = match (previous, target.healthy) {
+                (None, false) => format!("{time} {}: unhealthy ({detail})", target.name),
+                (Some(true), false) => {
+                    format!("{time} {}: healthy → unhealthy ({detail})", target.name)
+                }
+                (Some(false), true) => format!("{time} {}: unhealthy → healthy", target.name),
+                _ => continue,
+            };
+            transitions.push(transition);
+        }
+        for transition in &transitions {
+            if self.recent.len() == RECENT_TRANSITIONS {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(transition.clone());
+        }
+        transitions
+    }
+}
+
+impl CheckCommand {
+    /// Watch, serving --metrics-listen for as long as the watch runs
+    async fn watch(&self, target: &WatchTarget<'_>) -> Result<CommandResult> {
+        let Some(addr) = self.metrics_listen else {
+            return self.watch_rounds(target, None).await;
+        };
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to listen for metrics on {addr}"))?;
+        let exposition = Exposition::default();
+        let server = tokio::spawn(metrics::serve(listener, exposition.clone()));
+        let result = self.watch_rounds(target, Some(&exposition)).await;
+        server.abort();
+        result
+    }
+
+    /// Re-run the checks every --interval seconds until Ctrl+C or --max-failures
+    async fn watch_rounds(
+        &self,
+        target: &WatchTarget<'_>,
+        exposition: Option<&Exposition>,
+    ) -> Result<CommandResult> {
+        let live = !self.is_json() && std::io::stdout().is_terminal();
+        let mut state = WatchState::default();
+        let mut ticker = tokio::time::interval(Duration::from_secs(self.interval));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let ctrl_c = tokio::signal::ctrl_c();
+        tokio::pin!(ctrl_c);
+
+        loop {
+            let (report, health) = tokio::select! {
+                round = async {
+                    ticker.tick().await;
+                    self.watch_round(target).await
+                } => round,
+                _ = &mut ctrl_c => {
+                    return Ok(CommandResult::Success(tr!(
+                        "check.watch_stopped",
+                        rounds = state.rounds
+                    )));
+                }
+            };
+            let now = chrono::Local::now();
+            let time = now.format("%H:%M:%S").to_string();
+            let transitions = state.record(&time, &health);
+            if let Some(exposition) = exposition {
+                let stats = RoundStats {
+                    rounds: state.rounds,
+                    consecutive_failures: state.consecutive_failures,
+                    timestamp: now.timestamp(),
+                };
+                *exposition.write().unwrap() = metrics::render(&report, stats);
+            }
+
+            if self.is_json() {
+                let mut line = serde_json::to_value(&report)?;
+                line["round"] = state.rounds.into();
+                line["transitions"] = serde_json::json!(transitions);
+                println!("{}", serde_json::to_string(&line)?);
+            } else if live {
+                self.render_watch(&report, &state, &time, target.specs.len());
+            } else {
+                for transition in &transitions {
+                    println!("{transition}");
+                }
+                println!(
+                    "{}",
+                    tr!(
+                        "check.watch_round",
+                        time = time,
+                        round = state.rounds,
+                        status = if report.passed {
+                            tr!("check.round_passed")
+                        } else {
+                            tr!("check.round_failed")
+                        }
+                    )
+                );
+            }
+
+            if let Some(max_failures) = self.max_failures
+                && state.consecutive_failures >= max_failures
+            {
+                return Ok(CommandResult::Error(format!(
+                    "{} {}",
+                    "❌".red(),
+                    tr!("check.consecutive_failures", count = max_failures)
+                )));
+            }
+        }
+    }
+
+    /// One round of signaling and dependency checks; errors count as failures
+    async fn watch_round(&self, target: &WatchTarget<'_>) -> (CheckReport, Vec<TargetHealth>) {
+        let mut report = CheckReport::new(target.config_path);
+        let mut health = Vec::new();
+
+        if let Some(url) = &target.signaling_url {
+            let signaling = match target
+                .pipeline
+                .network_validator()
+                .check_connectivity(url, target.options)
+                .await
+            {
+                Ok(status) => SignalingCheck {
+                    url: url.clone(),
+                    reachable: status.is_reachable,
+                    latency_ms: status.response_time_ms,
+                    error: status.error,
+                },
+                Err(e) => SignalingCheck {
+                    url: url.clone(),
+                    reachable: false,
+                    latency_ms: None,
+                    error: Some(e.to_string()),
+                },
+            };
+            health.push(TargetHealth {
+                name: "signaling".to_string(),
+                healthy: signaling.reachable,
+                detail: signaling.error.clone(),
+            });
+            let reachable = signaling.reachable;
+            report.signaling = Some(signaling);
+            // Dependencies cannot be discovered without signaling; keep their last state
+            if !reachable {
+                return (report.failed("Signaling server unreachable"), health);
+            }
+        }
+
+        match self
+            .check_services(target.pipeline, target.specs, target.options)
+            .await
+        {
+            Ok(outcomes) => {
+                for (spec, outcome) in target.specs.iter().zip(outcomes) {
+                    let service = service_report(spec, outcome, None);
+                    health.push(TargetHealth {
+                        name: service.alias.clone(),
+                        healthy: service.passed,
+                        detail: service.failure_reason(),
+                    });
+                    report.services.push(service);
+                }
+                report.passed = report.services.iter().all(|service| service.passed);
+                (report, health)
+            }
+            Err(e) => {
+                health.push(TargetHealth {
+                    name: "checks".to_string(),
+                    healthy: false,
+                    detail: Some(format!("{e:#}")),
+                });
+                (report.failed(format!("{e:#}")), health)
+            }
+        }
+    }
+
+    /// Redraw the terminal with the latest round
+    fn render_watch(&self, report: &CheckReport, state: &WatchState, time: &str, count: usize) {
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "{}\n",
+            tr!(
+                "check.watch_header",
+                count = count,
+                interval = self.interval,
+                round = state.rounds,
+                time = time
+            )
+        );
+        if let Some(signaling) = &report.signaling {
+            if signaling.reachable {
+                let latency = signaling.latency_ms.unwrap_or(0);
+                println!(
+                    "{}",
+                    tr!(
+                        "check.watch_signaling",
+                        url = signaling.url,
+                        status = tr!("check.reachable", latency = latency).green()
+                    )
+                );
+            } else {
+                let error = signaling
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| tr!("check.unknown_error"));
+                println!(
+                    "{}",
+                    tr!(
+                        "check.watch_signaling",
+                        url = signaling.url,
+                        status = tr!("check.unreachable", error = error).red()
+                    )
+                );
+            }
+        }
+        match &report.error {
+            Some(error) if report.signaling.as_ref().is_none_or(|s| s.reachable) => {
+                println!("{} {error}", "❌".red());
+            }
+            _ => {}
+        }
+        if !report.services.is_empty() {
+            println!("{}", self.render_table(report));
+        }
+
+        let streak = match self.max_failures {
+            Some(max_failures) => format!("{}/{max_failures}", state.consecutive_failures),
+            None => state.consecutive_failures.to_string(),
+        };
+        println!("\n{}", tr!("check.consecutive_rounds", streak = streak));
+        if !state.recent.is_empty() {
+            println!("\n{}", tr!("check.transitions"));
+            for transition in &state.recent {
+                println!("  {transition}");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Command for CheckCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let config_path = self.config_file.as_deref().unwrap_or("Actr.toml");
+
+        let pipeline = context.container.get_validation_pipeline()?;
+        let options = NetworkCheckOptions::with_timeout_secs(self.timeout);
+
+        let mut report = CheckReport::new(config_path);
+
+        self.progress(&tr!("check.starting"));
+
+        // 1. Validate Config and Signaling Server
+        let config_validation = pipeline.config_manager().validate_config().await?;
+        if !config_validation.is_valid {
+            if self.is_json() {
+                return report
+                    .failed(format!(
+                        "Configuration validation failed: {}",
+                        config_validation.errors.join("; ")
+                    ))
+                    .into_result();
+            }
+            let mut msg = format!("{} {}\n", "❌".red(), tr!("check.config_failed"));
+            for err in config_validation.errors {
+                msg.push_str(&format!("  - {}\n", err.red()));
+            }
+            return Ok(CommandResult::Error(msg));
+        }
+
+        let config = profile::parse_config(std::path::Path::new(config_path))
+            .with_context(|| format!("Failed to load config: {}", config_path))?;
+
+        report.generated_edits = self.generated_edits(std::path::Path::new(config_path));
+
+        if self.watch {
+            let specs = self.specs_to_check(&config);
+            if specs.is_empty() && !self.packages.is_empty() {
+                return Ok(CommandResult::Error(tr!(
+                    "check.packages_not_found",
+                    path = config_path
+                )));
+            }
+            let target = WatchTarget {
+                config_path,
+                signaling_url: (!context.is_offline()).then(|| config.signaling_url.to_string()),
+                specs: &specs,
+                pipeline: &pipeline,
+                options: &options,
+            };
+            return self.watch(&target).await;
+        }
+
+        if context.is_offline() {
+            self.progress(&tr!("check.offline_signaling"));
+        } else {
+            self.progress(&tr!(
+                "check.checking_signaling",
+                url = config.signaling_url.as_str()
+            ));
+            let signaling_status = pipeline
+                .network_validator()
+                .check_connectivity(config.signaling_url.as_str(), &options)
+                .await?;
+            report.signaling = Some(SignalingCheck {
+                url: config.signaling_url.to_string(),
+                reachable: signaling_status.is_reachable,
+                latency_ms: signaling_status.response_time_ms,
+                error: signaling_status.error.clone(),
+            });
+            if signaling_status.is_reachable {
+                let latency = signaling_status.response_time_ms.unwrap_or(0);
+                self.progress(&tr!("check.signaling_reachable", latency = latency));
+            } else {
+                let err = signaling_status
+                    .error
+                    .unwrap_or_else(|| "Unknown error".to_string());
+                if self.is_json() {
+                    return report
+                        .failed(format!("Signaling server unreachable: {}", err))
+                        .into_result();
+                }
+                return Ok(CommandResult::Error(format!(
+                    "{} {}",
+                    "❌".red(),
+                    tr!("check.signaling_unreachable", error = err.red())
+                )));
+            }
+        }
+
+        // 2. Resolve Dependencies to check
+        let specs_to_check = self.specs_to_check(&config);
+
+        if specs_to_check.is_empty() {
+            if self.packages.is_empty() {
+                if self.is_json() {
+                    report.passed = true;
+                    return report.into_result();
+                }
+                return Ok(CommandResult::Success(tr!("check.no_dependencies")));
+            } else {
+                let msg = tr!("check.packages_not_found", path = config_path);
+                if self.is_json() {
+                    return report.failed(msg).into_result();
+                }
+                return Ok(CommandResult::Error(msg));
+            }
+        }
+
+        // 3. Lock File Validation (if requested)
+        let mut lock_checks: Option<Vec<LockCheck>> = None;
+        if self.lock {
+            self.progress(&tr!("check.verifying_lock"));
+            let lock_path = std::path::Path::new("Actr.lock.toml");
+            if !lock_path.exists() {
+                let msg = "Actr.lock.toml not found".to_string();
+                if self.is_json() {
+                    return report.failed(msg).into_result();
+                }
+                return Ok(CommandResult::Error(msg));
+            }
+
+            let lock_file = actr_config::LockFile::from_file(lock_path)
+                .map_err(|e| anyhow::anyhow!("Failed to read lock file: {}", e))?;
 
-        if all_ok {
+            let mut checks = Vec::with_capacity(specs_to_check.len());
+            for spec in &specs_to_check {
+                if let Some(locked) = lock_file.get_dependency(&spec.name) {
+                    // Check if versions/types match if necessary
+                    let matches = spec
+                        .fingerprint
+                        .as_ref()
+                        .is_none_or(|spec_fp| spec_fp == &locked.fingerprint);
+                    if !matches && !self.is_json() {
+                        return Ok(CommandResult::Error(format!(
+                            "{} {}",
+                            "❌".red(),
+                            tr!(
+                                "check.lock_fingerprint_mismatch",
+                                alias = spec.alias,
+                                expected = spec.fingerprint.as_deref().unwrap_or_default(),
+                                locked = locked.fingerprint
+                            )
+                        )));
+                    }
+                    let proto_mismatches =
+                        locked_proto_mismatches(pipeline.fingerprint_validator().as_ref(), locked)
+                            .await;
+                    if !proto_mismatches.is_empty() && !self.is_json() {
+                        return Ok(CommandResult::Error(format!(
+                            "{} {}",
+                            "❌".red(),
+                            tr!(
+                                "check.lock_protos_mismatch",
+                                alias = spec.alias,
+                                mismatches = proto_mismatches.join("\n  ")
+                            )
+                        )));
+                    }
+                    checks.push(LockCheck {
+                        locked: true,
+                        locked_fingerprint: Some(locked.fingerprint.clone()),
+                        matches: matches && proto_mismatches.is_empty(),
+                        proto_mismatches,
+                    });
+                } else {
+                    if !self.is_json() {
+                        return Ok(CommandResult::Error(format!(
+                            "{} {}",
+                            "❌".red(),
+                            tr!("check.not_locked", alias = spec.alias)
+                        )));
+                    }
+                    checks.push(LockCheck {
+                        locked: false,
+                        locked_fingerprint: None,
+                        matches: false,
+                        proto_mismatches: Vec::new(),
+                    });
+                }
+            }
+            if checks.iter().all(|c| c.locked && c.matches) {
+                self.progress(&tr!("check.lock_verified"));
+            }
+            lock_checks = Some(checks);
+        }
+
+        // 4. Availability, connectivity and fingerprint checks, bounded by --concurrency.
+        let outcomes = self
+            .check_services(&pipeline, &specs_to_check, &options)
+            .await?;
+
+        // 5. Report Results
+        for (i, (spec, outcome)) in specs_to_check.iter().zip(outcomes).enumerate() {
+            let lock = lock_checks.as_ref().map(|checks| checks[i].clone());
+            report.services.push(service_report(spec, outcome, lock));
+        }
+
+        report.passed = report.services.iter().all(|s| s.passed);
+
+        if self.is_json() {
+            return report.into_result();
+        }
+
+        println!("\n{}", self.render_table(&report));
+
+        if report.passed {
             Ok(CommandResult::Success(format!(
-                "\n{} All {} services passed validation!",
+                "\n{} {}",
                 "✨".green(),
-                specs_to_check.len()
+                tr!("check.all_passed", count = specs_to_check.len())
             )))
         } else {
             Ok(CommandResult::Error(format!(
-                "\n{} Some services failed validation. Run with --verbose for details.",
-                "⚠️".yellow()
+                "\n{} {}",
+                "⚠️".yellow(),
+                tr!("check.services_failed")
             )))
         }
     }
@@ -352,3 +1082,53 @@ impl Command for CheckCommand {
         "Validate project dependencies and service availability"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(name: &str, healthy: bool) -> TargetHealth {
+        TargetHealth {
+            name: name.to_string(),
+            healthy,
+            detail: (!healthy).then(|| "unreachable".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_watch_state_logs_transitions_and_counts_failures() {
+        let mut state = WatchState::default();
+        assert!(
+            state
+                .record(
+                    "10:00:00",
+                    &[health("signaling", true), health("user", true)]
+                )
+                .is_empty()
+        );
+
+        let transitions = state.record(
+            "10:00:10",
+            &[health("signaling", true), health("user", false)],
+        );
+        assert_eq!(
+            transitions,
+            vec!["10:00:10 user: healthy → unhealthy (unreachable)".to_string()]
+        );
+        assert!(
+            state
+                .record("10:00:20", &[health("user", false)])
+                .is_empty()
+        );
+        assert_eq!(state.consecutive_failures, 2);
+
+        let transitions = state.record("10:00:30", &[health("user", true)]);
+        assert_eq!(
+            transitions,
+            vec!["10:00:30 user: unhealthy → healthy".to_string()]
+        );
+        assert_eq!(state.consecutive_failures, 0);
+        assert_eq!(state.rounds, 4);
+        assert_eq!(state.recent.len(), 2);
+    }
+}
@@ -0,0 +1,237 @@
+//! Code generation from a Cargo build script
+//!
+//! [`run_generation`] runs the `actr gen` pipeline from Rust code. `actr gen
+//! --emit-build-rs` writes a `build.rs` calling it and adds `actr-cli` to
+//! `[build-dependencies]`, so `cargo build` regenerates the code whenever the protos,
+//! Actr.toml or Actr.lock.toml change:
+//!
+//! ```ignore
+//! use actr_cli::commands::codegen::{GenerationOptions, run_generation};
+//!
+//! fn main() {
+//!     println!("cargo:rerun-if-changed=protos");
+//!     run_generation(GenerationOptions::default()).expect("actr code generation failed");
+//! }
+//! ```
+
+use super::SupportedLanguage;
+use crate::commands::generate::GenCommand;
+use crate::error::{ActrCliError, Result};
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table, value};
+
+/// First line of every `build.rs` written by `actr gen --emit-build-rs`
+const BUILD_RS_MARKER: &str = "//! Generated by `actr gen --emit-build-rs`";
+
+/// Options of [`run_generation`], the programmatic `actr gen`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationOptions {
+    /// Proto file or directory (`-i`)
+    pub input: PathBuf,
+    /// Actr.toml (`-c`)
+    pub config: PathBuf,
+    /// Languages (`-l`); empty uses `[[build.targets]]`, else Rust
+    pub language: Vec<SupportedLanguage>,
+    /// Output directory (`-o`); `None` uses the language default
+    pub output: Option<PathBuf>,
+    /// Skip the user scaffolds (on by default: build scripts must not write user code)
+    pub no_scaffold: bool,
+    pub no_format: bool,
+    /// Skip the validation step (`cargo check`, `swift build`). On by default, since
+    /// `cargo check` cannot run from inside a build
+    pub no_validate: bool,
+    /// Ignore the generation cache
+    pub force: bool,
+    /// Only use cached protos and never install plugins
+    pub offline: bool,
+    /// Maximum number of concurrent protoc invocations
+    pub jobs: Option<usize>,
+}
+
+impl Default for GenerationOptions {
+    fn default() -> Self {
+        Self {
+            input: PathBuf::from("protos"),
+            config: PathBuf::from("Actr.toml"),
+            language: Vec::new(),
+            output: None,
+            no_scaffold: true,
+            no_format: false,
+            no_validate: true,
+            force: false,
+            offline: false,
+            jobs: None,
+        }
+    }
+}
+
+impl GenerationOptions {
+    /// The `actr gen` invocation these options stand for
+    pub fn to_command(&self) -> GenCommand {
+        GenCommand {
+            input: self.input.clone(),
+            output: self.output.clone(),
+            config: self.config.clone(),
+            clean: false,
+            no_scaffold: self.no_scaffold,
+            overwrite_user_code: false,
+            merge_scaffold: false,
+            no_format: self.no_format,
+            debug: false,
+            language: self.language.clone(),
+            gradle_module: None,
+            as_crate: None,
+            workspace: false,
+            force: self.force,
+            jobs: self.jobs,
+            watch: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            buf: false,
+            check: false,
+            emit_build_rs: false,
+            offline: self.offline,
+            quiet: true,
+            no_validate: self.no_validate,
+        }
+    }
+}
+
+/// Run the `actr gen` pipeline and return the files that were written
+///
+/// Blocks on its own Tokio runtime, so it must not be called from async code.
+pub fn run_generation(options: GenerationOptions) -> Result<Vec<PathBuf>> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(ActrCliError::Io)?;
+    runtime.block_on(options.to_command().run())
+}
+
+/// Write `project_root/build.rs` running `options` and add actr-cli to the
+/// `[build-dependencies]` of `project_root/Cargo.toml`; returns the files written
+pub fn emit_build_rs(project_root: &Path, options: &GenerationOptions) -> Result<Vec<PathBuf>> {
+    let manifest_path = project_root.join("Cargo.toml");
+    let manifest = std::fs::read_to_string(&manifest_path).map_err(|_| {
+        ActrCliError::InvalidProject(format!(
+            "--emit-build-rs needs a Cargo.toml in {}",
+            project_root.display()
+        ))
+    })?;
+
+    let build_rs = project_root.join("build.rs");
+    if let Ok(existing) = std::fs::read_to_string(&build_rs)
+        && !existing.starts_with(BUILD_RS_MARKER)
+    {
+        return Err(ActrCliError::InvalidProject(format!(
+            "{} already exists; call actr_cli::commands::codegen::run_generation from it by hand",
+            build_rs.display()
+        )));
+    }
+    std::fs::write(&build_rs, build_rs_content(options))
+        .map_err(|e| ActrCliError::output_write_failed(&build_rs, e))?;
+    let mut written = vec![build_rs];
+
+    let mut doc: DocumentMut = manifest.parse().map_err(|e| {
+        ActrCliError::InvalidProject(format!("Invalid {}: {e}", manifest_path.display()))
+    })?;
+    let build_dependencies = doc
+        .entry("build-dependencies")
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            ActrCliError::InvalidProject(format!(
+                "Invalid {}: [build-dependencies] is not a table",
+                manifest_path.display()
+            ))
+        })?;
+    if !build_dependencies.contains_key("actr-cli") {
+        build_dependencies.insert("actr-cli", value(env!("CARGO_PKG_VERSION")));
+        std::fs::write(&manifest_path, doc.to_string())
+            .map_err(|e| ActrCliError::output_write_failed(&manifest_path, e))?;
+        written.push(manifest_path);
+    }
+    Ok(written)
+}
+
+fn build_rs_content(options: &GenerationOptions) -> String {
+    let path = |path: &Path| path.to_string_lossy().replace('\\', "/");
+    let output = options
+        .output
+        .as_deref()
+        .map(|output| format!("        output: Some({:?}.into()),\n", path(output)))
+        .unwrap_or_default();
+    let lock_file = options.config.with_file_name("Actr.lock.toml");
+    format!(
+        r#"{BUILD_RS_MARKER}
+//!
+//! Regenerates the actor code from the protos when they, Actr.toml or
+//! Actr.lock.toml change. Adjust the options as needed; rerunning
+//! `actr gen --emit-build-rs` rewrites this file.
+
+use actr_cli::commands::codegen::{{GenerationOptions, run_generation}};
+
+fn main() {{
+    for path in [{input:?}, {config:?}, {lock_file:?}] {{
+        println!("cargo:rerun-if-changed={{path}}");
+    }}
+
+    let options = GenerationOptions {{
+        input: {input:?}.into(),
+        config: {config:?}.into(),
+{output}        ..GenerationOptions::default()
+    }};
+    if let Err(e) = run_generation(options) {{
+        panic!("actr code generation failed: {{e}}");
+    }}
+}}
+"#,
+        input = path(&options.input),
+        config = path(&options.config),
+        lock_file = path(&lock_file),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_emit_build_rs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"echo\"\n\n[dependencies]\nprost = \"0.14\"\n",
+        )
+        .unwrap();
+
+        let options = GenerationOptions {
+            output: Some(PathBuf::from("src/generated")),
+            ..GenerationOptions::default()
+        };
+        let written = emit_build_rs(root, &options).unwrap();
+        assert_eq!(written.len(), 2);
+
+        let build_rs = std::fs::read_to_string(root.join("build.rs")).unwrap();
+        assert!(build_rs.starts_with(BUILD_RS_MARKER));
+        assert!(build_rs.contains("for path in [\"protos\", \"Actr.toml\", \"Actr.lock.toml\"] {"));
+        assert!(build_rs.contains("input: \"protos\".into(),"));
+        assert!(build_rs.contains("output: Some(\"src/generated\".into()),"));
+
+        let manifest: toml::Table = std::fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            manifest["build-dependencies"]["actr-cli"].as_str(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+
+        // Rerunning rewrites our build.rs only; a hand-written one is kept
+        assert_eq!(emit_build_rs(root, &options).unwrap().len(), 1);
+        std::fs::write(root.join("build.rs"), "fn main() {}\n").unwrap();
+        assert!(emit_build_rs(root, &options).is_err());
+    }
+}
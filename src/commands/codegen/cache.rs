@@ -0,0 +1,355 @@
+//! Content-hash based generation cache
+//!
+//! `.actr/gen-cache-<language>.json` records, per proto file, the hash of its
+//! content together with every file it imports (directly or not, buf modules
+//! included), and of the generation options (language, CLI and plugin versions,
+//! input, proto roots and output flags). Protos whose hashes are unchanged are
+//! skipped on the next `actr gen`. Each language keeps its own file so
+//! multi-target runs stay warm.
+//!
+//! The cache also lists the files `actr gen` owns (scaffolds excluded). A run is
+//! only skipped while all of them exist; after a full generation, listed files
+//! that were not written again, such as the outputs of a deleted proto, are removed.
+
+use super::{GenContext, LanguageGenerator, SupportedLanguage};
+use crate::proto_parser::ProtoSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+const CACHE_VERSION: u32 = 3;
+const CACHE_DIR: &str = ".actr";
+const CACHE_FILE_PREFIX: &str = "gen-cache";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct CacheEntry {
+    content_hash: String,
+    options_hash: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GenCache {
+    version: u32,
+    #[serde(default)]
+    entries: BTreeMap<String, CacheEntry>,
+    /// Generated files owned by `actr gen`
+    #[serde(default)]
+    outputs: BTreeSet<String>,
+}
+
+/// Which protos need regenerating
+#[derive(Debug, Default)]
+pub struct CachePlan {
+    pub stale: Vec<PathBuf>,
+    pub fresh: Vec<PathBuf>,
+    /// Cached protos that no longer exist
+    pub removed: Vec<String>,
+    hashes: BTreeMap<String, String>,
+    options_hash: String,
+}
+
+impl GenCache {
+    /// Cache file of one language for a project (next to Actr.toml)
+    pub fn path_for(context: &GenContext, language: SupportedLanguage) -> PathBuf {
+        context
+            .config_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .join(CACHE_DIR)
+            .join(format!("{CACHE_FILE_PREFIX}-{}.json", language.as_str()))
+    }
+
+    /// Load the cache; a missing, unreadable or outdated cache is treated as empty
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match serde_json::from_str::<GenCache>(&content) {
+            Ok(cache) if cache.version == CACHE_VERSION => cache,
+            Ok(_) => Self::default(),
+            Err(e) => {
+                warn!("Ignoring corrupt generation cache {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, content)
+    }
+
+    /// Split proto files into stale and fresh ones
+    ///
+    /// `resolve` maps an import to the file protoc would read for it.
+    pub fn plan(
+        &self,
+        proto_files: &[PathBuf],
+        options_hash: String,
+        resolve: &dyn Fn(&str) -> Option<PathBuf>,
+    ) -> CachePlan {
+        let mut plan = CachePlan {
+            options_hash,
+            ..Default::default()
+        };
+        for proto in proto_files {
+            let key = cache_key(proto);
+            let content_hash = match content_hash(proto, resolve) {
+                Some(hash) => hash,
+                None => {
+                    plan.stale.push(proto.clone());
+                    continue;
+                }
+            };
+            let up_to_date = self.entries.get(&key).is_some_and(|entry| {
+                entry.content_hash == content_hash && entry.options_hash == plan.options_hash
+            });
+            if up_to_date {
+                plan.fresh.push(proto.clone());
+            } else {
+                plan.stale.push(proto.clone());
+            }
+            plan.hashes.insert(key, content_hash);
+        }
+        plan.removed = self
+            .entries
+            .keys()
+            .filter(|key| !proto_files.iter().any(|proto| cache_key(proto) == **key))
+            .cloned()
+            .collect();
+        plan
+    }
+
+    /// Whether a generated file listed in the cache is gone (e.g. after --clean)
+    pub fn outputs_missing(&self) -> bool {
+        self.outputs
+            .iter()
+            .any(|output| !Path::new(output).exists())
+    }
+
+    /// Listed outputs that a full generation writing `owned` did not produce
+    pub fn orphaned_outputs(&self, owned: &[PathBuf]) -> Vec<PathBuf> {
+        let owned: BTreeSet<String> = owned.iter().map(|file| cache_key(file)).collect();
+        self.outputs.difference(&owned).map(PathBuf::from).collect()
+    }
+
+    /// Record a successful generation and forget protos that no longer exist
+    ///
+    /// `owned` are the generated files of the run; a `full` run replaces the
+    /// output list instead of extending it.
+    pub fn record(&mut self, plan: &CachePlan, owned: &[PathBuf], full: bool) {
+        self.version = CACHE_VERSION;
+        if full {
+            self.outputs.clear();
+        }
+        self.outputs
+            .extend(owned.iter().map(|file| cache_key(file)));
+        self.entries.retain(|key, _| plan.hashes.contains_key(key));
+        for (key, content_hash) in &plan.hashes {
+            self.entries.insert(
+                key.clone(),
+                CacheEntry {
+                    content_hash: content_hash.clone(),
+                    options_hash: plan.options_hash.clone(),
+                },
+            );
+        }
+    }
+}
+
+/// Hash of everything besides proto content that affects generated output
+///
+/// `proto_roots` are the `--proto_path`s protoc will be given.
+pub fn options_hash(
+    language: SupportedLanguage,
+    generator: &dyn LanguageGenerator,
+    context: &GenContext,
+    proto_roots: &[PathBuf],
+) -> String {
+    let options = format!(
+        "language={language:?};cli={};plugin={};input={};proto_roots={:?};gradle_module={:?};output={};no_scaffold={};overwrite_user_code={};merge_scaffold={};no_format={};debug={};header={:?};format={:?};crate={:?}",
+        env!("CARGO_PKG_VERSION"),
+        generator.toolchain_version().unwrap_or_default(),
+        context.input_path.display(),
+        proto_roots,
+        context.gradle_module,
+        context.output.display(),
+        context.no_scaffold,
+        context.overwrite_user_code,
+        context.merge_scaffold,
+        context.no_format,
+        context.debug,
+        context.header,
+        context.format,
+        context.generated_crate,
+    );
+    hash_bytes(options.as_bytes())
+}
+
+/// Hash of `proto` and of the files it imports, following imports of imports
+///
+/// `None` when `proto` cannot be read. Imports nothing resolves are hashed by
+/// name, so providing them later invalidates the entry.
+fn content_hash(proto: &Path, resolve: &dyn Fn(&str) -> Option<PathBuf>) -> Option<String> {
+    let mut hasher = Sha256::new();
+    let source = std::fs::read(proto).ok()?;
+    hasher.update(&source);
+
+    let mut seen = BTreeSet::new();
+    let mut pending = vec![source];
+    while let Some(source) = pending.pop() {
+        let Ok(schema) = ProtoSchema::parse(&String::from_utf8_lossy(&source)) else {
+            continue;
+        };
+        for import in schema.imports {
+            if !seen.insert(import.clone()) {
+                continue;
+            }
+            hasher.update(import.as_bytes());
+            match resolve(&import).and_then(|path| std::fs::read(path).ok()) {
+                Some(bytes) => {
+                    hasher.update([1]);
+                    hasher.update(&bytes);
+                    pending.push(bytes);
+                }
+                None => hasher.update([0]),
+            }
+        }
+    }
+    Some(hex::encode(hasher.finalize()))
+}
+
+fn cache_key(proto: &Path) -> String {
+    proto.to_string_lossy().replace('\\', "/")
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn no_imports(_: &str) -> Option<PathBuf> {
+        None
+    }
+
+    #[test]
+    fn test_plan_skips_unchanged_protos() {
+        let temp = TempDir::new().unwrap();
+        let echo = temp.path().join("echo.proto");
+        let other = temp.path().join("other.proto");
+        std::fs::write(&echo, "syntax = \"proto3\";").unwrap();
+        std::fs::write(&other, "syntax = \"proto3\";").unwrap();
+        let protos = vec![echo.clone(), other.clone()];
+
+        let mut cache = GenCache::default();
+        let plan = cache.plan(&protos, "opts".to_string(), &no_imports);
+        assert_eq!(plan.stale.len(), 2);
+        cache.record(&plan, &[], true);
+
+        std::fs::write(&other, "syntax = \"proto3\";\npackage other;").unwrap();
+        let plan = cache.plan(&protos, "opts".to_string(), &no_imports);
+        assert_eq!(plan.fresh, vec![echo.clone()]);
+        assert_eq!(plan.stale, vec![other]);
+
+        let plan = cache.plan(&protos, "changed-opts".to_string(), &no_imports);
+        assert_eq!(plan.stale.len(), 2);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let proto = temp.path().join("echo.proto");
+        std::fs::write(&proto, "syntax = \"proto3\";").unwrap();
+        let cache_path = temp
+            .path()
+            .join(CACHE_DIR)
+            .join(format!("{CACHE_FILE_PREFIX}-rust.json"));
+
+        let mut cache = GenCache::default();
+        let plan = cache.plan(
+            std::slice::from_ref(&proto),
+            "opts".to_string(),
+            &no_imports,
+        );
+        cache.record(&plan, &[], true);
+        cache.save(&cache_path).unwrap();
+
+        let loaded = GenCache::load(&cache_path);
+        let plan = loaded.plan(&[proto], "opts".to_string(), &no_imports);
+        assert!(plan.stale.is_empty());
+    }
+
+    #[test]
+    fn test_removed_protos_and_orphaned_outputs() {
+        let temp = TempDir::new().unwrap();
+        let echo = temp.path().join("echo.proto");
+        let other = temp.path().join("other.proto");
+        let echo_rs = temp.path().join("echo.rs");
+        let other_rs = temp.path().join("other.rs");
+        for file in [&echo, &other, &echo_rs, &other_rs] {
+            std::fs::write(file, "").unwrap();
+        }
+
+        let mut cache = GenCache::default();
+        let plan = cache.plan(
+            &[echo.clone(), other.clone()],
+            "opts".to_string(),
+            &no_imports,
+        );
+        cache.record(&plan, &[echo_rs.clone(), other_rs.clone()], true);
+        assert!(!cache.outputs_missing());
+
+        // Deleting a proto is a change even though no remaining proto is stale
+        std::fs::remove_file(&other).unwrap();
+        let plan = cache.plan(std::slice::from_ref(&echo), "opts".to_string(), &no_imports);
+        assert!(plan.stale.is_empty());
+        assert_eq!(plan.removed, vec![cache_key(&other)]);
+        assert_eq!(
+            cache.orphaned_outputs(std::slice::from_ref(&echo_rs)),
+            vec![other_rs.clone()]
+        );
+
+        cache.record(&plan, std::slice::from_ref(&echo_rs), true);
+        std::fs::remove_file(&echo_rs).unwrap();
+        assert!(cache.outputs_missing());
+    }
+
+    #[test]
+    fn test_imported_proto_changes_invalidate_importers() {
+        let temp = TempDir::new().unwrap();
+        let echo = temp.path().join("echo.proto");
+        let types = temp.path().join("types.proto");
+        let common = temp.path().join("common.proto");
+        std::fs::write(&echo, "syntax = \"proto3\";\nimport \"types.proto\";").unwrap();
+        std::fs::write(&types, "syntax = \"proto3\";\nimport \"common.proto\";").unwrap();
+        std::fs::write(&common, "syntax = \"proto3\";").unwrap();
+        let root = temp.path().to_path_buf();
+        let resolve = move |import: &str| Some(root.join(import)).filter(|path| path.is_file());
+        let protos = vec![echo.clone()];
+
+        let mut cache = GenCache::default();
+        let plan = cache.plan(&protos, "opts".to_string(), &resolve);
+        cache.record(&plan, &[], true);
+        assert!(
+            cache
+                .plan(&protos, "opts".to_string(), &resolve)
+                .stale
+                .is_empty()
+        );
+
+        // An import of an import changed; echo.proto itself did not
+        std::fs::write(&common, "syntax = \"proto3\";\npackage common;").unwrap();
+        let plan = cache.plan(&protos, "opts".to_string(), &resolve);
+        assert_eq!(plan.stale, vec![echo]);
+    }
+}
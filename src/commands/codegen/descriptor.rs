@@ -0,0 +1,514 @@
+//! Proto schemas for code generation, read from protoc descriptor sets
+//!
+//! The generators need the same view of a proto that protoc has: nested messages
+//! with options, maps, `optional` fields and streaming RPCs exactly as the compiler
+//! resolved them. [`crate::core::DefaultProtoProcessor::compile_file`] asks protoc
+//! for a `FileDescriptorSet` (`--descriptor_set_out --include_source_info`) and
+//! [`decode`] converts it into the [`ProtoSchema`] shape of the lightweight
+//! parser, with line numbers and leading comments taken from the source info. Type references are relative to the
+//! file's package (`EchoRequest`, `other.pkg.User`), as they are usually written.
+
+use crate::error::{ActrCliError, Result};
+use crate::proto_parser::{
+    EnumDef, EnumValueDef, FieldDef, FieldLabel, MessageDef, ProtoSchema, RpcDef, ServiceDef,
+};
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorProto,
+    FileDescriptorSet,
+};
+use std::path::Path;
+
+// Field numbers of the descriptor messages, as used in `SourceCodeInfo` paths
+const FILE_MESSAGE: i32 = 4;
+const FILE_ENUM: i32 = 5;
+const FILE_SERVICE: i32 = 6;
+const MESSAGE_FIELD: i32 = 2;
+const MESSAGE_NESTED: i32 = 3;
+const MESSAGE_ENUM: i32 = 4;
+const ENUM_VALUE: i32 = 2;
+const SERVICE_METHOD: i32 = 2;
+
+/// Schema of `proto_file` from the descriptor set protoc wrote for it
+pub fn decode(bytes: &[u8], proto_file: &Path) -> Result<ProtoSchema> {
+    let set = FileDescriptorSet::decode(bytes).map_err(|e| {
+        ActrCliError::CodeGeneration(format!(
+            "Invalid descriptor set for {}: {e}",
+            proto_file.display()
+        ))
+    })?;
+    // Without --include_imports the set holds just the requested file
+    let file = set.file.last().ok_or_else(|| {
+        ActrCliError::CodeGeneration(format!(
+            "protoc returned no descriptor for {}",
+            proto_file.display()
+        ))
+    })?;
+    Ok(schema_from_descriptor(file))
+}
+
+/// Convert one compiled file into a [`ProtoSchema`]
+pub fn schema_from_descriptor(file: &FileDescriptorProto) -> ProtoSchema {
+    let converter = Converter::new(file);
+    let mut schema = ProtoSchema {
+        // protoc leaves `syntax` unset for proto2 files
+        syntax: Some(
+            file.syntax
+                .clone()
+                .filter(|syntax| !syntax.is_empty())
+                .unwrap_or_else(|| "proto2".to_string()),
+        ),
+        package: converter.package.map(str::to_string),
+        imports: file.dependency.clone(),
+        ..Default::default()
+    };
+
+    for (index, message) in file.message_type.iter().enumerate() {
+        converter.message(message, "", &[FILE_MESSAGE, index as i32], &mut schema);
+    }
+    for (index, enumeration) in file.enum_type.iter().enumerate() {
+        converter.enumeration(enumeration, "", &[FILE_ENUM, index as i32], &mut schema);
+    }
+    for (index, service) in file.service.iter().enumerate() {
+        let path = [FILE_SERVICE, index as i32];
+        let methods = service
+            .method
+            .iter()
+            .enumerate()
+            .map(|(method_index, method)| {
+                let method_path = [
+                    FILE_SERVICE,
+                    index as i32,
+                    SERVICE_METHOD,
+                    method_index as i32,
+                ];
+                let (line, comment) = converter.location(&method_path);
+                RpcDef {
+                    name: method.name().to_string(),
+                    input_type: converter.relative(method.input_type()),
+                    output_type: converter.relative(method.output_type()),
+                    client_streaming: method.client_streaming(),
+                    server_streaming: method.server_streaming(),
+                    line,
+                    comment,
+                }
+            })
+            .collect();
+        let (line, comment) = converter.location(&path);
+        schema.services.push(ServiceDef {
+            name: service.name().to_string(),
+            methods,
+            line,
+            comment,
+        });
+    }
+
+    schema
+}
+
+struct Converter<'a> {
+    file: &'a FileDescriptorProto,
+    package: Option<&'a str>,
+    proto3: bool,
+}
+
+impl<'a> Converter<'a> {
+    fn new(file: &'a FileDescriptorProto) -> Self {
+        Self {
+            file,
+            package: file
+                .package
+                .as_deref()
+                .filter(|package| !package.is_empty()),
+            proto3: file.syntax() == "proto3",
+        }
+    }
+
+    /// Flatten `message` and its nested types into `schema`, nested ones first
+    fn message(
+        &self,
+        message: &DescriptorProto,
+        prefix: &str,
+        path: &[i32],
+        schema: &mut ProtoSchema,
+    ) {
+        let name = format!("{prefix}{}", message.name());
+        let nested_prefix = format!("{name}.");
+
+        for (index, nested) in message.nested_type.iter().enumerate() {
+            // `map<K, V>` fields are compiled into synthetic `XxxEntry` messages
+            if is_map_entry(nested) {
+                continue;
+            }
+            let nested_path = [path, &[MESSAGE_NESTED, index as i32][..]].concat();
+            self.message(nested, &nested_prefix, &nested_path, schema);
+        }
+        for (index, enumeration) in message.enum_type.iter().enumerate() {
+            let enum_path = [path, &[MESSAGE_ENUM, index as i32][..]].concat();
+            self.enumeration(enumeration, &nested_prefix, &enum_path, schema);
+        }
+
+        let fields = message
+            .field
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let (line, comment) =
+                    self.location(&[path, &[MESSAGE_FIELD, index as i32][..]].concat());
+                let (label, type_name) = match self.map_entry(message, field) {
+                    Some(entry) => (FieldLabel::Singular, self.map_type(entry)),
+                    None => (self.label(field), self.field_type(field)),
+                };
+                let oneof = field
+                    .oneof_index
+                    .filter(|_| !field.proto3_optional())
+                    .and_then(|index| message.oneof_decl.get(index as usize))
+                    .map(|oneof| oneof.name().to_string());
+                FieldDef {
+                    name: field.name().to_string(),
+                    number: i64::from(field.number()),
+                    type_name,
+                    label: if oneof.is_some() {
+                        FieldLabel::Singular
+                    } else {
+                        label
+                    },
+                    oneof,
+                    line,
+                    comment,
+                }
+            })
+            .collect();
+
+        let (line, comment) = self.location(path);
+        schema.messages.push(MessageDef {
+            name,
+            fields,
+            line,
+            comment,
+        });
+    }
+
+    fn enumeration(
+        &self,
+        enumeration: &EnumDescriptorProto,
+        prefix: &str,
+        path: &[i32],
+        schema: &mut ProtoSchema,
+    ) {
+        let values = enumeration
+            .value
+            .iter()
+            .enumerate()
+            .map(|(index, value)| EnumValueDef {
+                name: value.name().to_string(),
+                number: i64::from(value.number()),
+                line: self
+                    .location(&[path, &[ENUM_VALUE, index as i32][..]].concat())
+                    .0,
+            })
+            .collect();
+        let (line, comment) = self.location(path);
+        schema.enums.push(EnumDef {
+            name: format!("{prefix}{}", enumeration.name()),
+            values,
+            line,
+            comment,
+        });
+    }
+
+    fn label(&self, field: &FieldDescriptorProto) -> FieldLabel {
+        match field.label() {
+            Label::Repeated => FieldLabel::Repeated,
+            Label::Required => FieldLabel::Required,
+            Label::Optional if field.proto3_optional() || !self.proto3 => FieldLabel::Optional,
+            Label::Optional => FieldLabel::Singular,
+        }
+    }
+
+    fn field_type(&self, field: &FieldDescriptorProto) -> String {
+        match field.r#type() {
+            Type::Message | Type::Enum | Type::Group => self.relative(field.type_name()),
+            scalar => scalar
+                .as_str_name()
+                .trim_start_matches("TYPE_")
+                .to_lowercase(),
+        }
+    }
+
+    /// The synthetic entry message of a `map<K, V>` field of `message`
+    fn map_entry<'m>(
+        &self,
+        message: &'m DescriptorProto,
+        field: &FieldDescriptorProto,
+    ) -> Option<&'m DescriptorProto> {
+        if field.label() != Label::Repeated || field.r#type() != Type::Message {
+            return None;
+        }
+        let entry_name = field.type_name().rsplit('.').next()?;
+        message
+            .nested_type
+            .iter()
+            .find(|nested| nested.name() == entry_name && is_map_entry(nested))
+    }
+
+    fn map_type(&self, entry: &DescriptorProto) -> String {
+        let part = |number: i32| {
+            entry
+                .field
+                .iter()
+                .find(|field| field.number() == number)
+                .map(|field| self.field_type(field))
+                .unwrap_or_default()
+        };
+        format!("map<{}, {}>", part(1), part(2))
+    }
+
+    /// `.acme.User` as `User` inside package `acme`, other packages without the dot
+    fn relative(&self, type_name: &str) -> String {
+        let trimmed = type_name.trim_start_matches('.');
+        self.package
+            .and_then(|package| trimmed.strip_prefix(package))
+            .and_then(|rest| rest.strip_prefix('.'))
+            .unwrap_or(trimmed)
+            .to_string()
+    }
+
+    /// 1-based line and leading comment of the element at `path`
+    fn location(&self, path: &[i32]) -> (usize, Option<String>) {
+        let Some(location) = self
+            .file
+            .source_code_info
+            .as_ref()
+            .and_then(|info| info.location.iter().find(|location| location.path == path))
+        else {
+            return (0, None);
+        };
+        let line = location.span.first().map_or(0, |&line| line as usize + 1);
+        let comment = location
+            .leading_comments
+            .as_deref()
+            .map(|text| {
+                text.lines()
+                    .map(|line| line.trim().trim_start_matches('*').trim())
+                    .filter(|line| !line.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .filter(|comment| !comment.is_empty());
+        (line, comment)
+    }
+}
+
+fn is_map_entry(message: &DescriptorProto) -> bool {
+    message
+        .options
+        .as_ref()
+        .is_some_and(|options| options.map_entry())
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use prost_types::source_code_info::Location;
+    use prost_types::{
+        MessageOptions, MethodDescriptorProto, MethodOptions, OneofDescriptorProto,
+        ServiceDescriptorProto, SourceCodeInfo,
+    };
+
+    fn field(
+        name: &str,
+        number: i32,
+        label: Label,
+        kind: Type,
+        type_name: &str,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(label as i32),
+            r#type: Some(kind as i32),
+            type_name: (!type_name.is_empty()).then(|| type_name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn method(name: &str, client_streaming: bool, server_streaming: bool) -> MethodDescriptorProto {
+        MethodDescriptorProto {
+            name: Some(name.to_string()),
+            input_type: Some(".echo.EchoRequest".to_string()),
+            output_type: Some(".echo.Outer.Inner".to_string()),
+            client_streaming: Some(client_streaming),
+            server_streaming: Some(server_streaming),
+            ..Default::default()
+        }
+    }
+
+    /// What protoc emits for:
+    ///
+    /// ```proto
+    /// syntax = "proto3";
+    /// package echo;
+    /// message Outer {
+    ///   option deprecated = true;
+    ///   message Inner { string id = 1 [(validate.rules).string = {min_len: 1}]; }
+    ///   map<string, Inner> items = 1;
+    ///   optional int32 limit = 2;
+    ///   oneof choice { other.Ref ref = 3; }
+    /// }
+    /// // Echo service
+    /// service EchoService {
+    ///   rpc Echo(EchoRequest) returns (Outer.Inner);
+    ///   rpc Upload(stream EchoRequest) returns (Outer.Inner);
+    ///   rpc Chat(stream EchoRequest) returns (stream Outer.Inner) { option deprecated = true; }
+    /// }
+    /// ```
+    pub(in crate::commands::codegen) fn echo_descriptor() -> FileDescriptorProto {
+        let entry = DescriptorProto {
+            name: Some("ItemsEntry".to_string()),
+            field: vec![
+                field("key", 1, Label::Optional, Type::String, ""),
+                field(
+                    "value",
+                    2,
+                    Label::Optional,
+                    Type::Message,
+                    ".echo.Outer.Inner",
+                ),
+            ],
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let inner = DescriptorProto {
+            name: Some("Inner".to_string()),
+            field: vec![field("id", 1, Label::Optional, Type::String, "")],
+            ..Default::default()
+        };
+        let mut limit = field("limit", 2, Label::Optional, Type::Int32, "");
+        limit.proto3_optional = Some(true);
+        limit.oneof_index = Some(1);
+        let mut reference = field("ref", 3, Label::Optional, Type::Message, ".other.Ref");
+        reference.oneof_index = Some(0);
+        let outer = DescriptorProto {
+            name: Some("Outer".to_string()),
+            field: vec![
+                field(
+                    "items",
+                    1,
+                    Label::Repeated,
+                    Type::Message,
+                    ".echo.Outer.ItemsEntry",
+                ),
+                limit,
+                reference,
+            ],
+            nested_type: vec![inner, entry],
+            oneof_decl: vec![
+                OneofDescriptorProto {
+                    name: Some("choice".to_string()),
+                    ..Default::default()
+                },
+                OneofDescriptorProto {
+                    name: Some("_limit".to_string()),
+                    ..Default::default()
+                },
+            ],
+            options: Some(MessageOptions {
+                deprecated: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        FileDescriptorProto {
+            name: Some("echo.proto".to_string()),
+            package: Some("echo".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![outer],
+            service: vec![ServiceDescriptorProto {
+                name: Some("EchoService".to_string()),
+                method: vec![
+                    method("Echo", false, false),
+                    method("Upload", true, false),
+                    MethodDescriptorProto {
+                        options: Some(MethodOptions {
+                            deprecated: Some(true),
+                            ..Default::default()
+                        }),
+                        ..method("Chat", true, true)
+                    },
+                ],
+                ..Default::default()
+            }],
+            source_code_info: Some(SourceCodeInfo {
+                location: vec![
+                    Location {
+                        path: vec![FILE_SERVICE, 0],
+                        span: vec![9, 0, 13, 1],
+                        leading_comments: Some(" Echo service\n".to_string()),
+                        ..Default::default()
+                    },
+                    Location {
+                        path: vec![FILE_SERVICE, 0, SERVICE_METHOD, 2],
+                        span: vec![12, 2, 87],
+                        ..Default::default()
+                    },
+                ],
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_schema_from_descriptor_flattens_nested_messages() {
+        let schema = schema_from_descriptor(&echo_descriptor());
+        assert_eq!(schema.syntax.as_deref(), Some("proto3"));
+        assert_eq!(schema.package.as_deref(), Some("echo"));
+
+        let names: Vec<_> = schema.messages.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["Outer.Inner", "Outer"]);
+
+        let outer = &schema.messages[1];
+        assert_eq!(outer.fields[0].type_name, "map<string, Outer.Inner>");
+        assert_eq!(outer.fields[0].label, FieldLabel::Singular);
+        assert_eq!(outer.fields[1].label, FieldLabel::Optional);
+        assert_eq!(outer.fields[1].oneof, None);
+        assert_eq!(outer.fields[2].type_name, "other.Ref");
+        assert_eq!(outer.fields[2].oneof.as_deref(), Some("choice"));
+    }
+
+    #[test]
+    fn test_schema_from_descriptor_streaming_rpcs() {
+        let schema = schema_from_descriptor(&echo_descriptor());
+        let service = &schema.services[0];
+        assert_eq!(service.line, 10);
+        assert_eq!(service.comment.as_deref(), Some("Echo service"));
+
+        let flags: Vec<_> = service
+            .methods
+            .iter()
+            .map(|rpc| {
+                (
+                    rpc.name.as_str(),
+                    rpc.client_streaming,
+                    rpc.server_streaming,
+                )
+            })
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("Echo", false, false),
+                ("Upload", true, false),
+                ("Chat", true, true)
+            ]
+        );
+        assert_eq!(service.methods[2].input_type, "EchoRequest");
+        assert_eq!(service.methods[2].output_type, "Outer.Inner");
+        assert_eq!(service.methods[2].line, 13);
+    }
+}
@@ -0,0 +1,214 @@
+//! Compiler and protoc failures as `file:line:col: severity: message` lines
+//!
+//! The format matches the `$gcc` problem matcher of VS Code and most CI
+//! annotators, so a failed `protoc` run or `cargo check` points at the exact
+//! proto or generated file instead of a blob of stderr. Each diagnostic is also
+//! sent to the `--event-stream` as a `diagnostic` event.
+
+use crate::core::events::{self, Event};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: u32,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.severity.as_str(),
+            self.message
+        )
+    }
+}
+
+/// Print `diagnostics` on stderr and send them to the event stream
+pub fn report(diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        eprintln!("{diagnostic}");
+        events::emit(Event::Diagnostic {
+            file: diagnostic.file.display().to_string(),
+            line: diagnostic.line,
+            column: diagnostic.column,
+            severity: diagnostic.severity.as_str().to_string(),
+            message: diagnostic.message.clone(),
+        });
+    }
+}
+
+/// Diagnostics in protoc's stderr
+///
+/// protoc names files relative to its include path (`echo.proto:12:5: ...`);
+/// they are mapped back to the matching entry of `proto_files`. Errors without
+/// a position, such as `echo.proto: File not found.`, point at line 1.
+pub fn from_protoc(stderr: &str, proto_files: &[PathBuf]) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            // Aggregated failures are prefixed with the file: `protos/a.proto: a.proto:3:1: ...`
+            let names: Vec<(usize, usize)> = line
+                .match_indices(".proto:")
+                .map(|(index, _)| {
+                    let start = line[..index]
+                        .rfind(char::is_whitespace)
+                        .map_or(0, |space| space + 1);
+                    (start, index + ".proto".len())
+                })
+                .collect();
+            let (start, end) = names
+                .iter()
+                .copied()
+                .find(|&(_, end)| position(&line[end + 1..]).is_some())
+                .or(names.last().copied())?;
+            let name = &line[start..end];
+            let (line_number, column, rest) =
+                position(&line[end + 1..]).unwrap_or((1, 1, &line[end + 1..]));
+            let rest = rest.trim_start();
+            let (severity, message) = match rest.strip_prefix("warning:") {
+                Some(message) => (Severity::Warning, message.trim_start()),
+                None => (Severity::Error, rest),
+            };
+            if message.is_empty() {
+                return None;
+            }
+            let file = proto_files
+                .iter()
+                .find(|proto| proto.ends_with(name))
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from(name));
+            Some(Diagnostic {
+                file,
+                line: line_number,
+                column,
+                severity,
+                message: message.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// `12:5: rest` as `(12, 5, " rest")`
+fn position(text: &str) -> Option<(u32, u32, &str)> {
+    let mut parts = text.splitn(3, ':');
+    let line = parts.next()?.parse().ok()?;
+    let column = parts.next()?.parse().ok()?;
+    Some((line, column, parts.next()?))
+}
+
+/// Errors and warnings of `cargo check --message-format=json`
+///
+/// Cargo names files relative to the workspace root, which is `project_root` or
+/// one of its parents; paths are made relative to the current directory.
+pub fn from_cargo_json(stdout: &str, project_root: &Path) -> Vec<Diagnostic> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|value| value["reason"] == "compiler-message")
+        .filter_map(|value| {
+            let message = &value["message"];
+            let severity = match message["level"].as_str()? {
+                "error" => Severity::Error,
+                "warning" => Severity::Warning,
+                _ => return None,
+            };
+            let span = message["spans"]
+                .as_array()?
+                .iter()
+                .find(|span| span["is_primary"].as_bool() == Some(true))?;
+            let file = resolve(span["file_name"].as_str()?, project_root);
+            let mut text = message["message"].as_str()?.to_string();
+            if let Some(code) = message["code"]["code"].as_str() {
+                text = format!("[{code}] {text}");
+            }
+            Some(Diagnostic {
+                file: file
+                    .strip_prefix(&cwd)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(file),
+                line: span["line_start"].as_u64()? as u32,
+                column: span["column_start"].as_u64()? as u32,
+                severity,
+                message: text,
+            })
+        })
+        .collect()
+}
+
+fn resolve(file: &str, project_root: &Path) -> PathBuf {
+    let path = Path::new(file);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    project_root
+        .ancestors()
+        .map(|root| root.join(path))
+        .find(|candidate| candidate.exists())
+        .unwrap_or_else(|| project_root.join(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_protoc() {
+        let protos = vec![PathBuf::from("protos/echo.proto")];
+        let stderr = "protos/echo.proto: echo.proto:12:5: \"Foo\" is not defined.\n\
+                      echo.proto:3:1: warning: Import \"a.proto\" is unused.\n\
+                      missing.proto: File not found.\n\
+                      protoc-gen-prost: program not found\n";
+        let diagnostics = from_protoc(stderr, &protos);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "protos/echo.proto:12:5: error: \"Foo\" is not defined."
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+        assert_eq!(diagnostics[1].message, "Import \"a.proto\" is unused.");
+        assert_eq!(
+            diagnostics[2].to_string(),
+            "missing.proto:1:1: error: File not found."
+        );
+    }
+
+    #[test]
+    fn test_from_cargo_json() {
+        let root = Path::new("/work/echo");
+        let stdout = r#"{"reason":"compiler-artifact","target":{}}
+{"reason":"compiler-message","message":{"level":"error","message":"cannot find type `Echo`","code":{"code":"E0412"},"spans":[{"file_name":"src/generated/echo.rs","line_start":7,"column_start":13,"is_primary":true}]}}
+{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","code":null,"spans":[]}}
+{"reason":"build-finished","success":false}"#;
+        let diagnostics = from_cargo_json(stdout, root);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "/work/echo/src/generated/echo.rs:7:13: error: [E0412] cannot find type `Echo`"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Include/exclude globs for proto discovery
+//!
+//! Patterns are matched against paths relative to the input directory, with `/`
+//! separators: `*` stays within one path segment and `**` spans directories. They
+//! come from `[build.proto]` in Actr.toml and are replaced by `--include` /
+//! `--exclude` when those flags are given:
+//!
+//! ```toml
+//! [build.proto]
+//! include = ["**/*.proto"]
+//! exclude = ["vendor", "**/*_test.proto"]
+//! ```
+//!
+//! An exclude pattern that matches a directory skips everything below it.
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
+
+use crate::core::profile;
+use crate::error::{ActrCliError, Result};
+
+/// Which discovered proto files take part in generation
+#[derive(Debug, Clone)]
+pub struct ProtoFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl Default for ProtoFilter {
+    fn default() -> Self {
+        Self {
+            include: None,
+            exclude: GlobSet::empty(),
+        }
+    }
+}
+
+impl ProtoFilter {
+    /// Filter from include and exclude patterns; no include patterns means every proto
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: if include.is_empty() {
+                None
+            } else {
+                Some(build_set(include)?)
+            },
+            exclude: build_set(exclude)?,
+        })
+    }
+
+    /// Read `[build.proto]` from an Actr.toml, with CLI patterns taking precedence
+    pub fn from_config_file(path: &Path, include: &[String], exclude: &[String]) -> Result<Self> {
+        let (mut config_include, mut config_exclude) = (Vec::new(), Vec::new());
+        if path.exists() {
+            let value = profile::load_value(path)
+                .map_err(|e| ActrCliError::config_error(format!("{e:#}")))?;
+            if let Some(table) = value.get("build").and_then(|build| build.get("proto")) {
+                config_include = read_patterns(table, "include")?;
+                config_exclude = read_patterns(table, "exclude")?;
+            }
+        }
+        let pick = |cli: &[String], config: Vec<String>| {
+            if cli.is_empty() { config } else { cli.to_vec() }
+        };
+        Self::new(
+            &pick(include, config_include),
+            &pick(exclude, config_exclude),
+        )
+    }
+
+    /// Whether a proto file (relative to the input directory) is generated
+    pub fn matches_file(&self, relative: &Path) -> bool {
+        let relative = normalize(relative);
+        !self.exclude.is_match(&relative)
+            && self
+                .include
+                .as_ref()
+                .is_none_or(|include| include.is_match(&relative))
+    }
+
+    /// Whether a directory (relative to the input directory) is skipped entirely
+    pub fn excludes_dir(&self, relative: &Path) -> bool {
+        self.exclude.is_match(normalize(relative))
+    }
+}
+
+fn build_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(glob(pattern)?);
+    }
+    builder
+        .build()
+        .map_err(|e| ActrCliError::config_error(format!("Invalid proto glob: {e}")))
+}
+
+fn glob(pattern: &str) -> Result<Glob> {
+    GlobBuilder::new(pattern.trim_start_matches("./").trim_end_matches('/'))
+        .literal_separator(true)
+        .build()
+        .map_err(|e| ActrCliError::config_error(format!("Invalid proto glob '{pattern}': {e}")))
+}
+
+fn read_patterns(table: &toml::Value, key: &str) -> Result<Vec<String>> {
+    let Some(value) = table.get(key) else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|item| item.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| {
+            ActrCliError::config_error(format!("build.proto.{key} must be an array of strings"))
+        })
+}
+
+fn normalize(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_include_and_exclude() {
+        let filter = ProtoFilter::new(
+            &patterns(&["api/**/*.proto", "*.proto"]),
+            &patterns(&["vendor", "**/*_test.proto"]),
+        )
+        .unwrap();
+
+        assert!(filter.matches_file(Path::new("echo.proto")));
+        assert!(filter.matches_file(Path::new("api/v1/chat/chat.proto")));
+        assert!(!filter.matches_file(Path::new("other/echo.proto")));
+        assert!(!filter.matches_file(Path::new("api/v1/chat_test.proto")));
+        assert!(filter.excludes_dir(Path::new("vendor")));
+        assert!(!filter.excludes_dir(Path::new("api")));
+        assert!(ProtoFilter::default().matches_file(Path::new("a/b/c.proto")));
+    }
+
+    #[test]
+    fn test_cli_patterns_replace_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(
+            &path,
+            "[build.proto]\ninclude = [\"local/**\"]\nexclude = [\"local/legacy\"]\n",
+        )
+        .unwrap();
+
+        let filter = ProtoFilter::from_config_file(&path, &[], &[]).unwrap();
+        assert!(filter.matches_file(Path::new("local/echo.proto")));
+        assert!(!filter.matches_file(Path::new("remote/echo.proto")));
+        assert!(filter.excludes_dir(Path::new("local/legacy")));
+
+        let filter = ProtoFilter::from_config_file(&path, &patterns(&["remote/**"]), &[]).unwrap();
+        assert!(filter.matches_file(Path::new("remote/echo.proto")));
+        assert!(filter.excludes_dir(Path::new("local/legacy")));
+
+        std::fs::write(&path, "[build.proto]\nexclude = \"vendor\"\n").unwrap();
+        assert!(ProtoFilter::from_config_file(&path, &[], &[]).is_err());
+    }
+}
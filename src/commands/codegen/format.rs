@@ -0,0 +1,125 @@
+//! Formatter of each language, configured by `[build.format.<language>]`
+//!
+//! ```toml
+//! [build.format.rust]
+//! args = ["--config", "imports_granularity=Crate"]
+//!
+//! [build.format.python]
+//! tool = "ruff"
+//! args = ["format", "--quiet"]
+//! ```
+//!
+//! `tool` replaces the generator's formatter (rustfmt, black, swift-format, ktlint,
+//! prettier); `args` go before the file list. The generator's own arguments are kept
+//! when the default tool runs and dropped for any other tool. Formatters still read
+//! their usual project config files (`rustfmt.toml`, `pyproject.toml`,
+//! `.swift-format`, `.editorconfig`, `.prettierrc`).
+
+use super::SupportedLanguage;
+use crate::error::{ActrCliError, Result};
+use tokio::process::Command as TokioCommand;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatConfig {
+    /// Formatter command instead of the generator's default
+    pub tool: Option<String>,
+    /// Extra arguments placed before the files
+    pub args: Vec<String>,
+}
+
+impl FormatConfig {
+    /// `[build.format.<language>]` of a raw Actr.toml
+    pub fn from_config(value: &toml::Value, language: SupportedLanguage) -> Result<Self> {
+        let Some(table) = value
+            .get("build")
+            .and_then(|build| build.get("format"))
+            .and_then(|format| format.get(language.as_str()))
+        else {
+            return Ok(Self::default());
+        };
+        let invalid = |detail: &str| {
+            ActrCliError::config_error(format!(
+                "Invalid [build.format.{}] in Actr.toml: {detail}",
+                language.as_str()
+            ))
+        };
+
+        let tool = match table.get("tool") {
+            None => None,
+            Some(tool) => Some(
+                tool.as_str()
+                    .filter(|tool| !tool.trim().is_empty())
+                    .ok_or_else(|| invalid("`tool` must be a command name"))?
+                    .to_string(),
+            ),
+        };
+        let args = match table.get("args") {
+            None => Vec::new(),
+            Some(args) => args
+                .as_array()
+                .and_then(|args| {
+                    args.iter()
+                        .map(|arg| arg.as_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| invalid("`args` must be an array of strings"))?,
+        };
+        Ok(Self { tool, args })
+    }
+
+    /// Whether the generator's formatter `default` runs
+    pub fn is_default_tool(&self, default: &str) -> bool {
+        self.tool.as_deref().is_none_or(|tool| tool == default)
+    }
+
+    /// Formatter to run: the configured `tool`, else `default`
+    pub fn tool<'a>(&'a self, default: &'a str) -> &'a str {
+        self.tool.as_deref().unwrap_or(default)
+    }
+
+    /// Command running `program`: `defaults` when `default_tool` runs, then the extra args
+    ///
+    /// The formatter is killed when the command is dropped, e.g. at the `--timeout` deadline.
+    pub fn command(&self, program: &str, default_tool: &str, defaults: &[&str]) -> TokioCommand {
+        let mut cmd = TokioCommand::new(program);
+        cmd.kill_on_drop(true);
+        if self.is_default_tool(default_tool) {
+            cmd.args(defaults);
+        }
+        cmd.args(&self.args);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_config() {
+        let value: toml::Value = "[build.format.python]\ntool = \"ruff\"\nargs = [\"format\"]\n\n[build.format.rust]\nargs = [\"--config\", \"imports_granularity=Crate\"]\n"
+            .parse()
+            .unwrap();
+
+        let python = FormatConfig::from_config(&value, SupportedLanguage::Python).unwrap();
+        assert_eq!(python.tool("black"), "ruff");
+        assert!(!python.is_default_tool("black"));
+        let cmd = python.command("ruff", "black", &["--quiet"]);
+        assert_eq!(cmd.as_std().get_args().collect::<Vec<_>>(), ["format"]);
+
+        let rust = FormatConfig::from_config(&value, SupportedLanguage::Rust).unwrap();
+        assert!(rust.is_default_tool("rustfmt"));
+        let cmd = rust.command("rustfmt", "rustfmt", &["--edition", "2024"]);
+        assert_eq!(
+            cmd.as_std().get_args().collect::<Vec<_>>(),
+            ["--edition", "2024", "--config", "imports_granularity=Crate"]
+        );
+
+        assert_eq!(
+            FormatConfig::from_config(&value, SupportedLanguage::Kotlin).unwrap(),
+            FormatConfig::default()
+        );
+        let value: toml::Value = "[build.format.kotlin]\nargs = \"-F\"\n".parse().unwrap();
+        assert!(FormatConfig::from_config(&value, SupportedLanguage::Kotlin).is_err());
+    }
+}
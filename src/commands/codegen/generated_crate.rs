@@ -0,0 +1,236 @@
+//! `actr gen --as-crate <name>`: Rust code generated into a crate of its own
+//!
+//! The crate lives in `<name>/` next to the app's Cargo.toml and holds the generated
+//! modules in `src/generated/`, exported from `src/lib.rs`. Its Cargo.toml is created
+//! with the dependencies of the generated code; on later runs only missing
+//! dependencies are added, so edited versions stay. The app's Cargo.toml gets the
+//! crate as a workspace member (creating `[workspace]` when needed) and as a path
+//! dependency, and the user scaffolds stay in the app's `src/`, importing from it.
+
+use crate::error::{ActrCliError, Result};
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, value};
+use tracing::{info, warn};
+
+/// Dependencies of the generated code: prost messages and actr-framework actors
+const DEPENDENCIES: &[(&str, &str)] = &[
+    ("actr-framework", "0.1"),
+    ("actr-protocol", "0.1"),
+    ("async-trait", "0.1"),
+    ("prost", "0.14"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedCrate {
+    pub name: String,
+    /// Crate directory, relative to the app crate
+    pub dir: PathBuf,
+}
+
+impl GeneratedCrate {
+    pub fn new(name: &str) -> Result<Self> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            && !name.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+        if !valid {
+            return Err(ActrCliError::config_error(format!(
+                "--as-crate: '{name}' is not a valid crate name (letters, digits, - and _)"
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            dir: PathBuf::from(name),
+        })
+    }
+
+    /// Directory receiving the generated modules
+    pub fn output(&self) -> PathBuf {
+        self.dir.join("src").join("generated")
+    }
+
+    /// Path of the generated modules in Rust code (`echo_types::generated`)
+    pub fn module_path(&self) -> String {
+        format!("{}::generated", self.name.replace('-', "_"))
+    }
+
+    /// Create or update the crate manifest and `src/lib.rs`, then register the crate
+    /// in `app_manifest`; returns `src/lib.rs`
+    pub fn prepare(&self, app_manifest: &Path, edition: &str) -> Result<PathBuf> {
+        self.write_manifest(edition)?;
+        let lib_rs = self.dir.join("src").join("lib.rs");
+        let content = "//! Code generated by `actr gen --as-crate` from the project's protos\n\npub mod generated;\n";
+        std::fs::write(&lib_rs, content)
+            .map_err(|e| ActrCliError::output_write_failed(&lib_rs, e))?;
+
+        if app_manifest.is_file() {
+            self.register(app_manifest)?;
+        } else {
+            warn!(
+                "⚠️  {} not found; add {} to your workspace by hand",
+                app_manifest.display(),
+                self.dir.display()
+            );
+        }
+        Ok(lib_rs)
+    }
+
+    fn write_manifest(&self, edition: &str) -> Result<()> {
+        let path = self.dir.join("Cargo.toml");
+        let mut doc = match std::fs::read_to_string(&path) {
+            Ok(content) => parse_manifest(&path, &content)?,
+            Err(_) => {
+                let mut doc = DocumentMut::new();
+                let mut package = Table::new();
+                package.insert("name", value(self.name.as_str()));
+                package.insert("version", value("0.1.0"));
+                package.insert("edition", value(edition));
+                package.insert("publish", value(false));
+                doc.insert("package", Item::Table(package));
+                info!("📦 Creating crate {}", self.dir.display());
+                doc
+            }
+        };
+
+        let dependencies = doc
+            .entry("dependencies")
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| invalid_manifest(&path, "[dependencies] is not a table"))?;
+        for (name, version) in DEPENDENCIES {
+            if !dependencies.contains_key(name) {
+                dependencies.insert(name, value(*version));
+            }
+        }
+
+        std::fs::create_dir_all(self.dir.join("src"))
+            .map_err(|e| ActrCliError::output_write_failed(self.dir.join("src"), e))?;
+        std::fs::write(&path, doc.to_string())
+            .map_err(|e| ActrCliError::output_write_failed(&path, e))
+    }
+
+    /// Add the crate to the workspace members and the app's dependencies
+    fn register(&self, app_manifest: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(app_manifest)
+            .map_err(|e| ActrCliError::output_write_failed(app_manifest, e))?;
+        let mut doc = parse_manifest(app_manifest, &content)?;
+        let member = self.dir.to_string_lossy().replace('\\', "/");
+        let has_package = doc.contains_key("package");
+
+        let workspace = doc
+            .entry("workspace")
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| invalid_manifest(app_manifest, "[workspace] is not a table"))?;
+        let members = workspace.entry("members").or_insert_with(|| {
+            let mut members = Array::new();
+            if has_package {
+                members.push(".");
+            }
+            value(members)
+        });
+        let members = members
+            .as_array_mut()
+            .ok_or_else(|| invalid_manifest(app_manifest, "workspace.members is not an array"))?;
+        if !members
+            .iter()
+            .any(|entry| entry.as_str() == Some(member.as_str()))
+        {
+            members.push(member.as_str());
+        }
+
+        if has_package {
+            let dependencies = doc
+                .entry("dependencies")
+                .or_insert_with(|| Item::Table(Table::new()))
+                .as_table_like_mut()
+                .ok_or_else(|| invalid_manifest(app_manifest, "[dependencies] is not a table"))?;
+            if !dependencies.contains_key(&self.name) {
+                let mut dependency = InlineTable::new();
+                dependency.insert("path", member.as_str().into());
+                dependencies.insert(&self.name, value(dependency));
+            }
+        }
+
+        let updated = doc.to_string();
+        if updated != content {
+            std::fs::write(app_manifest, updated)
+                .map_err(|e| ActrCliError::output_write_failed(app_manifest, e))?;
+            info!("📝 Registered {} in {}", self.name, app_manifest.display());
+        }
+        Ok(())
+    }
+}
+
+fn parse_manifest(path: &Path, content: &str) -> Result<DocumentMut> {
+    content
+        .parse()
+        .map_err(|e| invalid_manifest(path, &format!("{e}")))
+}
+
+fn invalid_manifest(path: &Path, detail: &str) -> ActrCliError {
+    ActrCliError::InvalidProject(format!("Invalid {}: {detail}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_prepare_creates_crate_and_registers_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let app_manifest = root.join("Cargo.toml");
+        std::fs::write(
+            &app_manifest,
+            "[package]\nname = \"echo\"\n\n[dependencies]\nprost = \"0.14\" # messages\n",
+        )
+        .unwrap();
+
+        let mut krate = GeneratedCrate::new("echo-types").unwrap();
+        krate.dir = root.join("echo-types");
+        assert_eq!(krate.module_path(), "echo_types::generated");
+        krate.prepare(&app_manifest, "2021").unwrap();
+
+        let manifest = std::fs::read_to_string(krate.dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"echo-types\""));
+        assert!(manifest.contains("edition = \"2021\""));
+        assert!(manifest.contains("actr-framework = \"0.1\""));
+        assert!(krate.dir.join("src/lib.rs").is_file());
+
+        let member = krate.dir.to_string_lossy().replace('\\', "/");
+        let app: toml::Table = std::fs::read_to_string(&app_manifest)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            app["workspace"]["members"].as_array().unwrap(),
+            &vec![toml::Value::from("."), toml::Value::from(member.as_str())]
+        );
+        assert_eq!(
+            app["dependencies"]["echo-types"]["path"].as_str(),
+            Some(member.as_str())
+        );
+
+        // A second run keeps edited versions and does not duplicate anything
+        std::fs::write(
+            krate.dir.join("Cargo.toml"),
+            manifest.replace("prost = \"0.14\"", "prost = \"0.14.1\""),
+        )
+        .unwrap();
+        let app_before = std::fs::read_to_string(&app_manifest).unwrap();
+        krate.prepare(&app_manifest, "2021").unwrap();
+        assert!(
+            std::fs::read_to_string(krate.dir.join("Cargo.toml"))
+                .unwrap()
+                .contains("prost = \"0.14.1\"")
+        );
+        assert_eq!(std::fs::read_to_string(&app_manifest).unwrap(), app_before);
+        assert!(app_before.contains("# messages"));
+
+        assert!(GeneratedCrate::new("1st").is_err());
+        assert!(GeneratedCrate::new("a/b").is_err());
+    }
+}
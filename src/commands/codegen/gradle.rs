@@ -0,0 +1,170 @@
+//! Gradle integration for Kotlin codegen (`actr gen -l kotlin --gradle-module <path>`)
+//!
+//! Generated sources go to `<module>/build/generated/source/actr` instead of the
+//! module's source tree, and `<module>/actr.gradle.kts` wires that directory into
+//! the `main` source set of Android or Kotlin/JVM modules. When the protobuf Gradle
+//! plugin is applied, the snippet also registers the proto roots with it so the
+//! message classes are compiled from the same protos. The module opts in once:
+//!
+//! ```kotlin
+//! // build.gradle.kts
+//! apply(from = "actr.gradle.kts")
+//! ```
+//!
+//! The snippet is rewritten on every run; a hand-written `actr.gradle.kts` (one
+//! without the generated header) is left alone.
+
+use std::path::{Component, Path, PathBuf};
+
+use super::imports::service_roots;
+use crate::error::{ActrCliError, Result};
+
+/// Directory below the module that receives the generated sources
+pub const GENERATED_DIR: &str = "build/generated/source/actr";
+
+/// Snippet applied from the module's build script
+pub const SNIPPET_FILE: &str = "actr.gradle.kts";
+
+/// First line of every snippet this CLI writes
+const SNIPPET_HEADER: &str = "// Generated by `actr gen --gradle-module`";
+
+/// Output directory of generated sources for a module
+pub fn generated_dir(module: &Path) -> PathBuf {
+    module.join(GENERATED_DIR)
+}
+
+/// Source directory of the user code scaffolds for a module
+pub fn scaffold_dir(module: &Path, base_package: &str) -> PathBuf {
+    base_package
+        .split('.')
+        .fold(module.join("src/main/java"), |dir, segment| {
+            dir.join(segment)
+        })
+}
+
+/// Write `<module>/actr.gradle.kts`; returns `None` when a hand-written snippet is kept
+pub fn write_snippet(module: &Path, input_path: &Path) -> Result<Option<PathBuf>> {
+    let path = module.join(SNIPPET_FILE);
+    if let Ok(existing) = std::fs::read_to_string(&path)
+        && !existing.starts_with(SNIPPET_HEADER)
+    {
+        return Ok(None);
+    }
+
+    let proto_dirs = proto_dirs(module, input_path)?;
+    std::fs::write(&path, render_snippet(&proto_dirs))
+        .map_err(|e| ActrCliError::output_write_failed(&path, e))?;
+    Ok(Some(path))
+}
+
+/// Whether the module's build script already applies the snippet
+pub fn is_applied(module: &Path) -> bool {
+    ["build.gradle.kts", "build.gradle"].iter().any(|script| {
+        std::fs::read_to_string(module.join(script))
+            .is_ok_and(|content| content.contains(SNIPPET_FILE))
+    })
+}
+
+/// Proto roots relative to the module, one per service so bare-name imports resolve
+fn proto_dirs(module: &Path, input_path: &Path) -> Result<Vec<String>> {
+    let input_root = if input_path.is_file() {
+        input_path.parent().unwrap_or(Path::new("."))
+    } else {
+        input_path
+    };
+    let mut roots = service_roots(input_root);
+    if roots.is_empty() {
+        roots.push(input_root.to_path_buf());
+    }
+    roots.iter().map(|root| relative_to(module, root)).collect()
+}
+
+/// `to` relative to the directory `from`, with `/` separators
+fn relative_to(from: &Path, to: &Path) -> Result<String> {
+    let canonical = |path: &Path| {
+        std::fs::canonicalize(path).map_err(|e| {
+            ActrCliError::config_error(format!("Failed to resolve {}: {e}", path.display()))
+        })
+    };
+    let (from, to) = (canonical(from)?, canonical(to)?);
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(
+        to[common..]
+            .iter()
+            .map(|component| component.as_os_str().to_string_lossy().to_string()),
+    );
+    Ok(if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    })
+}
+
+fn render_snippet(proto_dirs: &[String]) -> String {
+    let proto_dirs = proto_dirs
+        .iter()
+        .map(|dir| format!("    layout.projectDirectory.dir(\"{dir}\"),\n"))
+        .collect::<String>();
+    format!(
+        r#"{SNIPPET_HEADER}; changes are overwritten on the next run.
+// Apply it from this module's build.gradle.kts:
+//
+//     apply(from = "{SNIPPET_FILE}")
+
+val actrGeneratedDir = layout.projectDirectory.dir("{GENERATED_DIR}")
+val actrProtoDirs = listOf(
+{proto_dirs})
+
+fun actrWire(mainSourceSet: Any) {{
+    mainSourceSet.withGroovyBuilder {{ "java" {{ "srcDir"(actrGeneratedDir) }} }}
+    // Message classes come from the protobuf Gradle plugin, compiled from the same protos
+    plugins.withId("com.google.protobuf") {{
+        ((mainSourceSet as ExtensionAware).extensions.getByName("proto") as SourceDirectorySet)
+            .srcDirs(actrProtoDirs)
+    }}
+}}
+
+listOf("com.android.application", "com.android.library").forEach {{ id ->
+    plugins.withId(id) {{
+        val sourceSets = extensions.getByName("android").withGroovyBuilder {{ getProperty("sourceSets") }}
+        actrWire((sourceSets as NamedDomainObjectContainer<*>).getByName("main"))
+    }}
+}}
+
+plugins.withId("org.jetbrains.kotlin.jvm") {{
+    actrWire(extensions.getByType<SourceSetContainer>().getByName("main"))
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_lists_service_roots_relative_to_module() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let module = dir.path().join("app");
+        let protos = dir.path().join("protos");
+        std::fs::create_dir_all(&module).unwrap();
+        std::fs::create_dir_all(protos.join("local")).unwrap();
+        std::fs::create_dir_all(protos.join("remote/echo-service")).unwrap();
+
+        let path = write_snippet(&module, &protos).unwrap().unwrap();
+        let snippet = std::fs::read_to_string(&path).unwrap();
+        assert!(snippet.starts_with(SNIPPET_HEADER));
+        assert!(snippet.contains("dir(\"../protos/local\")"));
+        assert!(snippet.contains("dir(\"../protos/remote/echo-service\")"));
+        assert!(snippet.contains("dir(\"build/generated/source/actr\")"));
+
+        // Hand-written snippets are kept
+        std::fs::write(&path, "// mine\n").unwrap();
+        assert!(write_snippet(&module, &protos).unwrap().is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "// mine\n");
+    }
+}
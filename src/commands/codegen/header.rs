@@ -0,0 +1,404 @@
+//! Header prepended to generated source files, configured by `[build.header]`
+//!
+//! ```toml
+//! [build.header]
+//! text = "Copyright (c) Acme Corp.\nService {service}, generated {date} by actr {version}"
+//! # or, relative to Actr.toml:
+//! # file = "LICENSE_HEADER.txt"
+//! default_banner = false
+//! ```
+//!
+//! The custom header goes on every generated source file, scaffolds included. The
+//! default "Generated by actr ... DO NOT EDIT" banner goes only on the files
+//! `actr gen` owns and can be turned off with `default_banner = false`. Header lines
+//! are turned into comments in the syntax of each file (`//` or `#`) unless they
+//! already are one. A file that already starts with the header is left alone, so
+//! incremental runs and merged scaffolds do not stack it, and a regenerated file
+//! gets back the header it had before. `{date}` is therefore the date the file was
+//! first written, and `actr gen --check` does not drift from one day to the next.
+
+use crate::error::{ActrCliError, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_BANNER: &str = "Generated by actr {version}. DO NOT EDIT.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenHeader {
+    /// Custom header template (`text`, or the content of `file`)
+    pub template: Option<String>,
+    /// Put the default banner on the files owned by `actr gen`
+    pub default_banner: bool,
+}
+
+impl Default for GenHeader {
+    fn default() -> Self {
+        Self {
+            template: None,
+            default_banner: true,
+        }
+    }
+}
+
+impl GenHeader {
+    /// `[build.header]` of a raw Actr.toml; `file` is relative to `config_dir`
+    pub fn from_config(value: &toml::Value, config_dir: &Path) -> Result<Self> {
+        let Some(header) = value.get("build").and_then(|build| build.get("header")) else {
+            return Ok(Self::default());
+        };
+        let invalid = |detail: &str| {
+            ActrCliError::config_error(format!("Invalid [build.header] in Actr.toml: {detail}"))
+        };
+        let string = |key: &str| -> Result<Option<&str>> {
+            header
+                .get(key)
+                .map(|value| {
+                    value
+                        .as_str()
+                        .ok_or_else(|| invalid(&format!("`{key}` must be a string")))
+                })
+                .transpose()
+        };
+
+        let template = match (string("text")?, string("file")?) {
+            (Some(_), Some(_)) => return Err(invalid("set either `text` or `file`, not both")),
+            (Some(text), None) => Some(text.to_string()),
+            (None, Some(file)) => {
+                let path = config_dir.join(file);
+                let text = std::fs::read_to_string(&path).map_err(|e| {
+                    invalid(&format!("cannot read header file {}: {e}", path.display()))
+                })?;
+                Some(text)
+            }
+            (None, None) => None,
+        };
+        let default_banner = match header.get("default_banner") {
+            None => true,
+            Some(value) => value
+                .as_bool()
+                .ok_or_else(|| invalid("`default_banner` must be true or false"))?,
+        };
+        Ok(Self {
+            template: template.map(|text| text.trim_end().to_string()),
+            default_banner,
+        })
+    }
+
+    /// Prepend the header to `files`; `owned` files also get the default banner
+    ///
+    /// A file whose `previous` header matches apart from dates gets it back.
+    /// Files that are not source files of a supported language are skipped.
+    pub fn apply(
+        &self,
+        service: &str,
+        files: &[PathBuf],
+        owned: bool,
+        previous: &PreviousHeaders,
+    ) -> Result<()> {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        self.apply_dated(service, files, owned, previous, &today)
+    }
+
+    fn apply_dated(
+        &self,
+        service: &str,
+        files: &[PathBuf],
+        owned: bool,
+        previous: &PreviousHeaders,
+        date: &str,
+    ) -> Result<()> {
+        for file in files {
+            let Some(comment) = file
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .and_then(comment_prefix)
+            else {
+                continue;
+            };
+            let mut header = self.render(service, date, owned, comment);
+            if header.is_empty() {
+                continue;
+            }
+            if let Some(lines) = previous.matching(file, &header) {
+                header = lines.to_vec();
+            }
+            let content = std::fs::read_to_string(file)
+                .map_err(|e| ActrCliError::output_write_failed(file, e))?;
+            if let Some(content) = prepend(&header, &content) {
+                std::fs::write(file, content)
+                    .map_err(|e| ActrCliError::output_write_failed(file, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Comment lines of the header for one file
+    fn render(&self, service: &str, date: &str, owned: bool, comment: &str) -> Vec<String> {
+        let banner = (owned && self.default_banner).then_some(DEFAULT_BANNER);
+        self.template
+            .as_deref()
+            .into_iter()
+            .chain(banner)
+            .flat_map(str::lines)
+            .map(|line| {
+                let line = line
+                    .replace("{service}", service)
+                    .replace("{date}", date)
+                    .replace("{version}", env!("CARGO_PKG_VERSION"));
+                let line = line.trim_end();
+                if line.trim_start().starts_with(comment) {
+                    line.to_string()
+                } else if line.is_empty() {
+                    comment.to_string()
+                } else {
+                    format!("{comment} {line}")
+                }
+            })
+            .collect()
+    }
+}
+
+/// Leading comment lines of the source files under a directory, by path
+/// relative to the output they are restored into
+#[derive(Debug, Default)]
+pub struct PreviousHeaders {
+    output: PathBuf,
+    comments: HashMap<PathBuf, Vec<String>>,
+}
+
+impl PreviousHeaders {
+    /// Read the files under `dir` as the previous version of `output`
+    ///
+    /// `dir` is `output` itself, except for `actr gen --check`, which generates
+    /// elsewhere and compares with the committed output.
+    pub fn read(dir: &Path, output: &Path) -> Self {
+        let mut comments = HashMap::new();
+        for entry in walkdir::WalkDir::new(dir).into_iter().flatten() {
+            let path = entry.path();
+            let Some(comment) = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .and_then(comment_prefix)
+            else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            let body = match content.split_once('\n') {
+                Some((first, rest)) if first.starts_with("#!") => rest,
+                _ => content.as_str(),
+            };
+            let lines: Vec<String> = body
+                .lines()
+                .take_while(|line| line.trim_start().starts_with(comment))
+                .map(str::to_string)
+                .collect();
+            if !lines.is_empty()
+                && let Ok(relative) = path.strip_prefix(dir)
+            {
+                comments.insert(relative.to_path_buf(), lines);
+            }
+        }
+        Self {
+            output: output.to_path_buf(),
+            comments,
+        }
+    }
+
+    /// Previous header of `file` when it equals `header` apart from dates
+    fn matching(&self, file: &Path, header: &[String]) -> Option<&[String]> {
+        let relative = file.strip_prefix(&self.output).ok()?;
+        let previous = self.comments.get(relative)?.get(..header.len())?;
+        previous
+            .iter()
+            .zip(header)
+            .all(|(line, expected)| without_dates(line) == without_dates(expected))
+            .then_some(previous)
+    }
+}
+
+/// Line comment of a generated source file
+fn comment_prefix(extension: &str) -> Option<&'static str> {
+    match extension {
+        "rs" | "swift" | "kt" | "kts" | "ts" | "js" => Some("//"),
+        "py" | "pyi" => Some("#"),
+        _ => None,
+    }
+}
+
+/// `content` with `header` on top (after a shebang), or `None` when it is already there
+///
+/// Lines are compared with any date replaced, so a header written on another day
+/// still counts as present.
+fn prepend(header: &[String], content: &str) -> Option<String> {
+    let (shebang, body) = match content.split_once('\n') {
+        Some((first, rest)) if first.starts_with("#!") => (Some(first), rest),
+        _ => (None, content),
+    };
+    let present = body.lines().count() >= header.len()
+        && body
+            .lines()
+            .zip(header)
+            .all(|(line, expected)| without_dates(line) == without_dates(expected));
+    if present {
+        return None;
+    }
+
+    let mut output = String::new();
+    if let Some(shebang) = shebang {
+        output.push_str(shebang);
+        output.push('\n');
+    }
+    output.push_str(&header.join("\n"));
+    output.push_str("\n\n");
+    output.push_str(body);
+    Some(output)
+}
+
+/// `line` with every `YYYY-MM-DD` date replaced by a placeholder
+fn without_dates(line: &str) -> String {
+    let bytes = line.as_bytes();
+    let is_date = |at: usize| {
+        bytes.get(at..at + 10).is_some_and(|candidate| {
+            candidate
+                .iter()
+                .enumerate()
+                .all(|(index, byte)| match index {
+                    4 | 7 => *byte == b'-',
+                    _ => byte.is_ascii_digit(),
+                })
+        })
+    };
+    let mut output = String::with_capacity(line.len());
+    let mut index = 0;
+    while index < line.len() {
+        if is_date(index) {
+            output.push_str("{date}");
+            index += 10;
+        } else {
+            let c = line[index..].chars().next().unwrap_or_default();
+            output.push(c);
+            index += c.len_utf8().max(1);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_header_from_config() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("HEADER.txt"), "Copyright (c) Acme Corp.\n").unwrap();
+
+        let value: toml::Value = "[build.header]\nfile = \"HEADER.txt\"\ndefault_banner = false\n"
+            .parse()
+            .unwrap();
+        let header = GenHeader::from_config(&value, dir.path()).unwrap();
+        assert_eq!(header.template.as_deref(), Some("Copyright (c) Acme Corp."));
+        assert!(!header.default_banner);
+
+        let value: toml::Value = "[package]\nname = \"echo\"\n".parse().unwrap();
+        assert_eq!(
+            GenHeader::from_config(&value, dir.path()).unwrap(),
+            GenHeader::default()
+        );
+
+        let value: toml::Value = "[build.header]\ntext = \"a\"\nfile = \"HEADER.txt\"\n"
+            .parse()
+            .unwrap();
+        assert!(GenHeader::from_config(&value, dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_header_applied_once() {
+        let dir = TempDir::new().unwrap();
+        let rust = dir.path().join("echo.rs");
+        let python = dir.path().join("echo_pb2.py");
+        let json = dir.path().join("manifest.json");
+        std::fs::write(&rust, "pub struct Echo;\n").unwrap();
+        std::fs::write(&python, "#!/usr/bin/env python3\nimport echo\n").unwrap();
+        std::fs::write(&json, "{}\n").unwrap();
+
+        let header = GenHeader {
+            template: Some("Service {service}\n\n// generated {date}".to_string()),
+            default_banner: true,
+        };
+        let files = [rust.clone(), python.clone(), json.clone()];
+        header
+            .apply("echo", &files, true, &PreviousHeaders::default())
+            .unwrap();
+
+        let version = env!("CARGO_PKG_VERSION");
+        let rust_content = std::fs::read_to_string(&rust).unwrap();
+        let mut lines = rust_content.lines();
+        assert_eq!(lines.next(), Some("// Service echo"));
+        assert_eq!(lines.next(), Some("//"));
+        assert!(lines.next().unwrap().starts_with("// generated 20"));
+        assert_eq!(
+            lines.next(),
+            Some(format!("// Generated by actr {version}. DO NOT EDIT.").as_str())
+        );
+        assert!(rust_content.ends_with("\n\npub struct Echo;\n"));
+
+        let python_content = std::fs::read_to_string(&python).unwrap();
+        assert!(python_content.starts_with("#!/usr/bin/env python3\n# Service echo\n#\n"));
+        assert_eq!(std::fs::read_to_string(&json).unwrap(), "{}\n");
+
+        // A second run, even on another day, leaves the files alone
+        let date = &rust_content.lines().nth(2).unwrap()["// generated ".len()..];
+        let older = rust_content.replace(date, "2001-01-01");
+        std::fs::write(&rust, &older).unwrap();
+        header
+            .apply("echo", &files, true, &PreviousHeaders::default())
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&rust).unwrap(), older);
+        assert_eq!(std::fs::read_to_string(&python).unwrap(), python_content);
+    }
+
+    #[test]
+    fn test_check_keeps_first_date_across_days() {
+        let committed = TempDir::new().unwrap();
+        let header = GenHeader {
+            template: Some("Generated {date}".to_string()),
+            default_banner: true,
+        };
+        let protoc_output = "pub struct Echo;\n";
+
+        // First generation, on day one
+        let echo = committed.path().join("echo.rs");
+        std::fs::write(&echo, protoc_output).unwrap();
+        let previous = PreviousHeaders::read(committed.path(), committed.path());
+        header
+            .apply_dated("echo", &[echo.clone()], true, &previous, "2001-01-01")
+            .unwrap();
+        let first = std::fs::read_to_string(&echo).unwrap();
+        assert!(first.starts_with("// Generated 2001-01-01\n"));
+
+        // `actr gen --check` on two later days regenerates elsewhere
+        for day in ["2001-01-02", "2001-02-01"] {
+            let staging = TempDir::new().unwrap();
+            let fresh = staging.path().join("echo.rs");
+            std::fs::write(&fresh, protoc_output).unwrap();
+            let previous = PreviousHeaders::read(committed.path(), staging.path());
+            header
+                .apply_dated("echo", &[fresh], true, &previous, day)
+                .unwrap();
+            let drift =
+                crate::commands::generate::diff_dirs(committed.path(), staging.path()).unwrap();
+            assert!(drift.is_empty(), "drift on {day}: {drift:?}");
+        }
+
+        // Regenerating in place keeps the first date too
+        let previous = PreviousHeaders::read(committed.path(), committed.path());
+        std::fs::write(&echo, protoc_output).unwrap();
+        header
+            .apply_dated("echo", &[echo.clone()], true, &previous, "2001-03-01")
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&echo).unwrap(), first);
+    }
+}
@@ -0,0 +1,243 @@
+//! Import resolution across the local and remote proto roots
+//!
+//! protoc only finds an import when one of its `--proto_path`s contains it. Besides
+//! the input directory (and the buf include paths), imports are looked up in:
+//!
+//! - `local/` and every `remote/<service>/` directory below the input, so protos
+//!   fetched by `actr install` keep importing their siblings by bare file name
+//! - the directory holding the well-known types (`google/protobuf/*.proto`) next to
+//!   protoc, or in the usual system include directories
+//!
+//! Imports are checked before protoc runs so a missing file is reported together
+//! with the roots that were searched instead of one protoc error per file.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::traits::GenContext;
+use crate::error::{ActrCliError, Result};
+use crate::plugin_manager::find_plugin;
+use crate::proto_parser::ProtoSchema;
+
+/// Prefix of the well-known types shipped with protoc
+const WELL_KNOWN_PREFIX: &str = "google/protobuf/";
+
+/// System include directories that commonly hold the well-known types
+const SYSTEM_INCLUDE_DIRS: [&str; 3] = [
+    "/usr/local/include",
+    "/usr/include",
+    "/opt/homebrew/include",
+];
+
+/// An import no proto root provides
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedImport {
+    pub file: PathBuf,
+    pub import: String,
+}
+
+impl fmt::Display for UnresolvedImport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: import \"{}\"", self.file.display(), self.import)
+    }
+}
+
+/// Ordered proto roots an import may come from
+#[derive(Debug, Clone)]
+pub struct ImportResolver {
+    /// Roots passed before the input root (buf modules and exported deps)
+    leading: Vec<PathBuf>,
+    input_root: PathBuf,
+    /// Roots passed after the input root
+    fallback: Vec<PathBuf>,
+    /// protoc finds the well-known types next to itself even if no root holds them
+    well_known_builtin: bool,
+}
+
+impl ImportResolver {
+    pub fn new(context: &GenContext) -> Self {
+        let input_root = proto_root(&context.input_path);
+        let mut fallback = service_roots(&input_root);
+        let well_known = well_known_dir();
+        fallback.extend(well_known.clone());
+        Self {
+            leading: context.include_paths.clone(),
+            input_root,
+            fallback,
+            well_known_builtin: well_known.is_some() || find_plugin("protoc").is_some(),
+        }
+    }
+
+    /// Roots searched after the input root
+    pub fn fallback_roots(&self) -> &[PathBuf] {
+        &self.fallback
+    }
+
+    /// Every root, in protoc's lookup order
+    pub fn all_roots(&self) -> Vec<PathBuf> {
+        self.roots().cloned().collect()
+    }
+
+    fn roots(&self) -> impl Iterator<Item = &PathBuf> {
+        self.leading
+            .iter()
+            .chain(std::iter::once(&self.input_root))
+            .chain(&self.fallback)
+    }
+
+    /// File an import resolves to, in protoc's lookup order
+    pub fn resolve(&self, import: &str) -> Option<PathBuf> {
+        self.roots()
+            .map(|root| root.join(import))
+            .find(|path| path.is_file())
+    }
+
+    /// Imports of `files` that no root provides
+    pub fn unresolved(&self, files: &[PathBuf]) -> Vec<UnresolvedImport> {
+        let mut missing = Vec::new();
+        for file in files {
+            // Unreadable or unparsable files are left for protoc to report
+            let Ok(source) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            let Ok(schema) = ProtoSchema::parse(&source) else {
+                continue;
+            };
+            for import in schema.imports {
+                if self.resolve(&import).is_some()
+                    || (self.well_known_builtin && import.starts_with(WELL_KNOWN_PREFIX))
+                {
+                    continue;
+                }
+                missing.push(UnresolvedImport {
+                    file: file.clone(),
+                    import,
+                });
+            }
+        }
+        missing
+    }
+
+    /// Fail with every unresolved import of `files` and the roots that were searched
+    pub fn check(&self, files: &[PathBuf]) -> Result<()> {
+        let missing = self.unresolved(files);
+        if missing.is_empty() {
+            return Ok(());
+        }
+        let mut message = format!("{} import(s) could not be resolved:", missing.len());
+        for entry in &missing {
+            message.push_str(&format!("\n  {entry}"));
+        }
+        message.push_str("\nSearched proto roots:");
+        for root in self.roots() {
+            message.push_str(&format!("\n  {}", root.display()));
+        }
+        message.push_str("\nRun 'actr install' if the import belongs to a dependency.");
+        Err(ActrCliError::CodeGeneration(message))
+    }
+}
+
+/// Check the imports of `context.proto_files` and add the fallback roots to the context
+pub fn resolve_imports(context: &GenContext) -> Result<GenContext> {
+    let resolver = ImportResolver::new(context);
+    resolver.check(&context.proto_files)?;
+    let mut context = context.clone();
+    context.import_roots = resolver.fallback_roots().to_vec();
+    Ok(context)
+}
+
+fn proto_root(input: &Path) -> PathBuf {
+    if input.is_file() {
+        input
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf()
+    } else {
+        input.to_path_buf()
+    }
+}
+
+/// `local/` and `remote/<service>/` below the input root, in a stable order
+pub(super) fn service_roots(input_root: &Path) -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    let local = input_root.join("local");
+    if local.is_dir() {
+        roots.push(local);
+    }
+    if let Ok(entries) = std::fs::read_dir(input_root.join("remote")) {
+        let mut services: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        services.sort();
+        roots.extend(services);
+    }
+    roots
+}
+
+/// Include directory holding `google/protobuf/*.proto`
+fn well_known_dir() -> Option<PathBuf> {
+    let beside_protoc = find_plugin("protoc")
+        .and_then(|protoc| std::fs::canonicalize(protoc).ok())
+        .and_then(|protoc| Some(protoc.parent()?.parent()?.join("include")));
+    beside_protoc
+        .into_iter()
+        .chain(SYSTEM_INCLUDE_DIRS.iter().map(PathBuf::from))
+        .find(|dir| {
+            dir.join(WELL_KNOWN_PREFIX)
+                .join("descriptor.proto")
+                .is_file()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver(input_root: &Path) -> ImportResolver {
+        ImportResolver {
+            leading: Vec::new(),
+            input_root: input_root.to_path_buf(),
+            fallback: service_roots(input_root),
+            well_known_builtin: true,
+        }
+    }
+
+    #[test]
+    fn test_resolves_remote_siblings_and_reports_missing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("local")).unwrap();
+        std::fs::create_dir_all(root.join("remote/user-service")).unwrap();
+        std::fs::write(
+            root.join("remote/user-service/types.proto"),
+            "syntax = \"proto3\";\nmessage User {}\n",
+        )
+        .unwrap();
+        let local = root.join("local/app.proto");
+        std::fs::write(
+            &local,
+            "syntax = \"proto3\";\nimport \"types.proto\";\nimport \"remote/user-service/types.proto\";\nimport \"google/protobuf/empty.proto\";\nimport \"missing.proto\";\n",
+        )
+        .unwrap();
+
+        let resolver = resolver(root);
+        assert_eq!(
+            resolver.resolve("types.proto"),
+            Some(root.join("remote/user-service/types.proto"))
+        );
+        let missing = resolver.unresolved(std::slice::from_ref(&local));
+        assert_eq!(
+            missing,
+            vec![UnresolvedImport {
+                file: local.clone(),
+                import: "missing.proto".to_string(),
+            }]
+        );
+
+        let err = resolver.check(&[local]).unwrap_err().to_string();
+        assert!(err.contains("import \"missing.proto\""));
+        assert!(err.contains("remote/user-service"));
+    }
+}
@@ -1,13 +1,35 @@
-use crate::commands::codegen::traits::{GenContext, LanguageGenerator};
+use crate::commands::codegen::gradle;
+use crate::commands::codegen::merge::{MergeOutcome, MethodStub, merge_block};
+use crate::commands::codegen::traits::{
+    GenContext, LanguageGenerator, PROTOC_TOOL, RequiredTool, print_steps,
+};
+use crate::core::DefaultProtoProcessor;
 use crate::error::{ActrCliError, Result};
+use crate::plugin_config::{load_protoc_plugin_config, parse_version_output};
+use crate::plugin_manager::{PluginInstaller, locate_or_install};
+use crate::tr;
 use crate::utils::to_snake_case;
 use actr_config::LockFile;
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command as StdCommand;
+use tokio::process::Command as TokioCommand;
 use tracing::{debug, info, warn};
 
+const KOTLIN_PLUGIN_NAME: &str = "protoc-gen-actrframework-kotlin";
+
+/// Environment variable pointing at a locally built Kotlin plugin
+const KOTLIN_PLUGIN_ENV: &str = "ACTR_KOTLIN_PLUGIN_PATH";
+
+/// How to get the Kotlin plugin when neither a download nor PATH provides it
+const KOTLIN_PLUGIN_HINT: &str = "Rerun online to download a prebuilt release into ~/.actr/bin, \
+    or build https://github.com/actor-rtc/framework-codegen-kotlin (./gradlew installDist) \
+    and add it to PATH (or set ACTR_KOTLIN_PLUGIN_PATH)";
+
+/// Import needed by handler methods of streaming RPCs
+const FLOW_IMPORT: &str = "import kotlinx.coroutines.flow.Flow";
+
 pub struct KotlinGenerator;
 
 /// Information about a proto service
@@ -39,71 +61,91 @@ struct MethodInfo {
     request_type: String,
     /// Response type (e.g., "SendFileResponse")
     response_type: String,
+    /// `stream` request
+    client_streaming: bool,
+    /// `stream` response
+    server_streaming: bool,
 }
 
 impl KotlinGenerator {
-    /// Find the framework-codegen-kotlin plugin
-    fn find_kotlin_plugin(&self) -> Result<PathBuf> {
-        // First try the environment variable
-        if let Ok(plugin_path) = std::env::var("ACTR_KOTLIN_PLUGIN_PATH") {
-            let path = PathBuf::from(&plugin_path);
-            if path.exists() {
-                debug!("Using Kotlin plugin from env: {:?}", path);
-                return Ok(path);
-            }
-        }
+    /// Find the framework-codegen-kotlin plugin, downloading a prebuilt release if missing
+    async fn find_kotlin_plugin(&self, context: &GenContext) -> Result<PathBuf> {
+        locate_or_install(
+            KOTLIN_PLUGIN_NAME,
+            &context.config_path,
+            Some(KOTLIN_PLUGIN_ENV),
+            context.offline,
+        )
+        .await
+        .map_err(|e| {
+            warn!("Could not find {KOTLIN_PLUGIN_NAME}: {e}");
+            ActrCliError::tool_missing(KOTLIN_PLUGIN_NAME, KOTLIN_PLUGIN_HINT)
+        })
+    }
 
-        // Try common locations
-        let possible_paths = [
-            // Development location
-            PathBuf::from(
-                "/Users/mafeng/Desktop/dev/framework-codegen-kotlin/protoc-gen-actrframework-kotlin",
-            ),
-            // Relative to current directory
-            PathBuf::from("../framework-codegen-kotlin/protoc-gen-actrframework-kotlin"),
-            // In PATH
-            PathBuf::from("protoc-gen-actrframework-kotlin"),
-        ];
+    /// Validate the plugin against `.protoc-plugin.toml`, downloading a matching release if needed
+    async fn ensure_plugin_version(
+        &self,
+        context: &GenContext,
+        plugin_path: PathBuf,
+    ) -> Result<PathBuf> {
+        let Some(config) = load_protoc_plugin_config(&context.config_path)? else {
+            return Ok(plugin_path);
+        };
+        let Some(requirement) = config.requirement(KOTLIN_PLUGIN_NAME) else {
+            return Ok(plugin_path);
+        };
 
-        for path in &possible_paths {
-            if path.exists() {
-                debug!("Found Kotlin plugin at: {:?}", path);
-                return Ok(path.clone());
-            }
+        let Some(version) = plugin_version(&plugin_path) else {
+            warn!(
+                "Could not determine {KOTLIN_PLUGIN_NAME} version, skipping check against '{requirement}'"
+            );
+            return Ok(plugin_path);
+        };
+        if requirement.matches(&version) {
+            debug!("{KOTLIN_PLUGIN_NAME} v{version} satisfies '{requirement}'");
+            return Ok(plugin_path);
         }
 
-        // Try `which` command
-        let output = StdCommand::new("which")
-            .arg("protoc-gen-actrframework-kotlin")
-            .output();
-
-        if let Ok(output) = output
-            && output.status.success()
+        warn!("⚠️  {KOTLIN_PLUGIN_NAME} v{version} does not satisfy '{requirement}'");
+        if !context.offline
+            && let Some(minimum) = requirement.minimum()
         {
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path.is_empty() {
-                return Ok(PathBuf::from(path));
+            match PluginInstaller::from_config(&context.config_path)?
+                .install(KOTLIN_PLUGIN_NAME, minimum)
+                .await
+            {
+                Ok(path) if plugin_version(&path).is_some_and(|v| requirement.matches(&v)) => {
+                    return Ok(path);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Prebuilt {KOTLIN_PLUGIN_NAME} download failed: {e}"),
             }
         }
 
-        Err(ActrCliError::config_error(
-            "Could not find protoc-gen-actrframework-kotlin plugin.\n\n\
-             Installation options:\n\n\
-             1. Build from source (recommended):\n\
-                git clone https://github.com/actor-rtc/framework-codegen-kotlin.git\n\
-                cd framework-codegen-kotlin\n\
-                gradle wrapper --gradle-version 8.5\n\
-                ./gradlew installDist\n\
-                ./gradlew protocPluginJar\n\
-                export PATH=\"$PWD/build/install/protoc-gen-actrframework-kotlin/bin:$PATH\"\n\n\
-             2. Set environment variable (if already built):\n\
-                export ACTR_KOTLIN_PLUGIN_PATH=/path/to/protoc-gen-actrframework-kotlin\n\n\
-             For more information, visit: https://github.com/actor-rtc/framework-codegen-kotlin",
-        ))
+        Err(ActrCliError::command_error(format!(
+            "{KOTLIN_PLUGIN_NAME} v{version} at {} does not satisfy '{requirement}' from {}.\n\
+             Rebuild framework-codegen-kotlin at a matching version or adjust the requirement.",
+            plugin_path.display(),
+            config.path().display()
+        )))
     }
 
     /// Get Kotlin package name - infer from output path or use default
     fn get_kotlin_package(&self, context: &GenContext) -> String {
+        // Sources under build/ carry no package path; use the `actr gen` default package
+        if context.gradle_module.is_some() {
+            let clean_name: String = context
+                .config
+                .package
+                .name
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase();
+            return format!("io.actr.{clean_name}.generated");
+        }
+
         // Try to infer package from output path
         // e.g., ".../java/io/actr/testkotlinecho/generated" -> "io.actr.testkotlinecho.generated"
         let output_str = context.output.to_string_lossy();
@@ -135,11 +177,12 @@ impl KotlinGenerator {
     ///
     /// Now reads actr_type from Actr.lock.toml instead of inferring from directory names.
     /// Returns None if the proto file has no service definitions (skip it).
-    fn analyze_proto_file(
+    async fn analyze_proto_file(
         &self,
+        context: &GenContext,
         proto_path: &PathBuf,
         actr_type_map: &HashMap<String, String>,
-    ) -> Option<ServiceInfo> {
+    ) -> Result<Option<ServiceInfo>> {
         let path_str = proto_path.to_string_lossy();
         let is_local = path_str.contains("/local/");
 
@@ -161,48 +204,29 @@ impl KotlinGenerator {
             None
         };
 
-        // Read service name from proto file directly
-        let proto_content = std::fs::read_to_string(proto_path).unwrap_or_default();
-
-        // Extract service name from proto file
-        // Look for "service ServiceName {"
-        // If no service definition found, return None (skip this proto file)
-        let service_name = proto_content
-            .lines()
-            .find(|l| l.trim().starts_with("service ") && l.contains("{"))
-            .and_then(|l| {
-                let trimmed = l.trim();
-                let after_service = trimmed.strip_prefix("service ")?;
-                let name_end = after_service.find([' ', '{'])?;
-                Some(after_service[..name_end].trim().to_string())
-            });
+        let schema = DefaultProtoProcessor::compile_file(
+            proto_path,
+            &context.proto_path_args(&context.input_path),
+        )
+        .await?;
 
         // If no service definition found, skip this proto file
-        let service_name = match service_name {
-            Some(name) => name,
-            None => {
-                debug!(
-                    "analyze_proto_file: {} has no service definition, skipping",
-                    proto_path.display()
-                );
-                return None;
-            }
+        let Some(service) = schema.services.first() else {
+            debug!(
+                "analyze_proto_file: {} has no service definition, skipping",
+                proto_path.display()
+            );
+            return Ok(None);
         };
+        let service_name = service.name.clone();
 
-        // Read proto package from the proto file
-        let proto_package = proto_content
-            .lines()
-            .find(|l| l.starts_with("package "))
-            .and_then(|l| l.strip_prefix("package "))
-            .and_then(|l| l.strip_suffix(";"))
-            .map(|s| s.trim().to_string())
-            .unwrap_or_else(|| {
-                let file_stem = proto_path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("unknown");
-                file_stem.to_lowercase().replace('-', "_")
-            });
+        let proto_package = schema.package.clone().unwrap_or_else(|| {
+            let file_stem = proto_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown");
+            file_stem.to_lowercase().replace('-', "_")
+        });
 
         let proto_file_name = proto_path
             .file_name()
@@ -210,41 +234,17 @@ impl KotlinGenerator {
             .unwrap_or("unknown.proto")
             .to_string();
 
-        // Extract RPC methods from proto file
-        let mut methods = Vec::new();
-        for line in proto_content.lines() {
-            let trimmed = line.trim();
-            if trimmed.starts_with("rpc ") {
-                // Parse: rpc method_name(request_type) returns (response_type);
-                if let Some(rpc_content) = trimmed.strip_prefix("rpc ")
-                    && let Some(semicolon_pos) = rpc_content.find(';')
-                {
-                    let rpc_def = &rpc_content[..semicolon_pos];
-                    // Split by " returns "
-                    if let Some((method_and_req, resp_part)) = rpc_def.split_once(" returns ") {
-                        // Parse method name and request type
-                        if let Some((method_name, req_part)) = method_and_req.split_once('(') {
-                            let method_name = to_snake_case(method_name.trim());
-                            if let Some(req_type) = req_part.strip_suffix(')') {
-                                let request_type = req_type.trim().to_string();
-                                // Parse response type
-                                if let Some(resp_type) = resp_part
-                                    .strip_prefix('(')
-                                    .and_then(|s| s.strip_suffix(')'))
-                                {
-                                    let response_type = resp_type.trim().to_string();
-                                    methods.push(MethodInfo {
-                                        name: method_name,
-                                        request_type,
-                                        response_type,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let methods = service
+            .methods
+            .iter()
+            .map(|rpc| MethodInfo {
+                name: to_snake_case(&rpc.name),
+                request_type: rpc.input_type.clone(),
+                response_type: rpc.output_type.clone(),
+                client_streaming: rpc.client_streaming,
+                server_streaming: rpc.server_streaming,
+            })
+            .collect::<Vec<_>>();
 
         // Determine if the outer class needs "OuterClass" suffix
         // protobuf-java adds this suffix when the file name (in PascalCase) conflicts
@@ -263,42 +263,16 @@ impl KotlinGenerator {
             .unwrap_or("unknown");
         let outer_class_base_name = to_pascal_case(file_stem);
 
-        // Extract all message, service, and enum names from proto
-        let mut declared_names: Vec<String> = Vec::new();
-
-        for line in proto_content.lines() {
-            let trimmed = line.trim();
-
-            // Check for message declarations
-            if trimmed.starts_with("message ")
-                && let Some(name) = trimmed
-                    .strip_prefix("message ")
-                    .and_then(|s| s.split_whitespace().next())
-                    .map(|s| s.trim_end_matches('{'))
-            {
-                declared_names.push(name.to_string());
-            }
-
-            // Check for service declarations
-            if trimmed.starts_with("service ")
-                && let Some(name) = trimmed
-                    .strip_prefix("service ")
-                    .and_then(|s| s.split_whitespace().next())
-                    .map(|s| s.trim_end_matches('{'))
-            {
-                declared_names.push(name.to_string());
-            }
-
-            // Check for enum declarations
-            if trimmed.starts_with("enum ")
-                && let Some(name) = trimmed
-                    .strip_prefix("enum ")
-                    .and_then(|s| s.split_whitespace().next())
-                    .map(|s| s.trim_end_matches('{'))
-            {
-                declared_names.push(name.to_string());
-            }
-        }
+        // Top-level message, service and enum names (nested ones are dotted)
+        let declared_names: Vec<String> = schema
+            .messages
+            .iter()
+            .map(|message| &message.name)
+            .chain(schema.services.iter().map(|service| &service.name))
+            .chain(schema.enums.iter().map(|e| &e.name))
+            .filter(|name| !name.contains('.'))
+            .cloned()
+            .collect();
 
         let needs_outer_class_suffix = declared_names.contains(&outer_class_base_name);
 
@@ -315,7 +289,7 @@ impl KotlinGenerator {
             needs_outer_class_suffix
         );
 
-        Some(ServiceInfo {
+        Ok(Some(ServiceInfo {
             service_name,
             proto_package,
             proto_file_name,
@@ -323,7 +297,7 @@ impl KotlinGenerator {
             remote_target_type,
             methods,
             needs_outer_class_suffix,
-        })
+        }))
     }
 
     /// Load Actr.lock.toml and build a mapping from dependency name to actr_type
@@ -380,14 +354,19 @@ impl KotlinGenerator {
 
     /// Collect all service information from proto files
     /// Skips proto files that have no service definitions
-    fn collect_services(&self, context: &GenContext) -> Result<Vec<ServiceInfo>> {
+    async fn collect_services(&self, context: &GenContext) -> Result<Vec<ServiceInfo>> {
         let actr_type_map = self.load_actr_type_map(context)?;
 
-        Ok(context
-            .proto_files
-            .iter()
-            .filter_map(|p| self.analyze_proto_file(p, &actr_type_map))
-            .collect())
+        let mut services = Vec::new();
+        for proto_path in &context.proto_files {
+            if let Some(service) = self
+                .analyze_proto_file(context, proto_path, &actr_type_map)
+                .await?
+            {
+                services.push(service);
+            }
+        }
+        Ok(services)
     }
 
     /// Generate unified infrastructure code
@@ -681,7 +660,8 @@ impl LanguageGenerator for KotlinGenerator {
         info!("🔧 Generating Kotlin Actor infrastructure code...");
 
         // Find the Kotlin plugin
-        let plugin_path = self.find_kotlin_plugin()?;
+        let plugin_path = self.find_kotlin_plugin(context).await?;
+        let plugin_path = self.ensure_plugin_version(context, plugin_path).await?;
         info!("✅ Using Kotlin plugin: {:?}", plugin_path);
 
         let kotlin_package = self.get_kotlin_package(context);
@@ -697,9 +677,9 @@ impl LanguageGenerator for KotlinGenerator {
                 .unwrap_or_else(|| std::path::Path::new("."));
 
             // Use protoc with the Kotlin plugin
-            let mut cmd = StdCommand::new("protoc");
+            let mut cmd = TokioCommand::new("protoc");
             // Add the main input path (protos directory) as include path for imports
-            cmd.arg(format!("--proto_path={}", context.input_path.display()))
+            cmd.args(context.proto_path_args(&context.input_path))
                 .arg(format!("--proto_path={}", proto_dir.display()))
                 .arg(format!(
                     "--plugin=protoc-gen-actrframework-kotlin={}",
@@ -713,18 +693,15 @@ impl LanguageGenerator for KotlinGenerator {
                     "--actrframework-kotlin_out={}",
                     context.output.display()
                 ))
-                .arg(proto_file);
+                .arg(proto_file)
+                .kill_on_drop(true);
 
             debug!("Executing protoc: {:?}", cmd);
-            let output = cmd.output().map_err(|e| {
-                ActrCliError::command_error(format!("Failed to execute protoc: {e}"))
-            })?;
+            let output = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
 
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(ActrCliError::command_error(format!(
-                    "protoc (actrframework-kotlin) execution failed: {stderr}"
-                )));
+                return Err(ActrCliError::protoc_failed(proto_file.display(), stderr));
             }
 
             // Track generated files
@@ -740,7 +717,7 @@ impl LanguageGenerator for KotlinGenerator {
         }
 
         // NOW collect service info (after per-service files are generated)
-        let services = self.collect_services(context)?;
+        let services = self.collect_services(context).await?;
         info!(
             "📊 Found {} services ({} local, {} remote)",
             services.len(),
@@ -751,12 +728,21 @@ impl LanguageGenerator for KotlinGenerator {
         // Generate unified infrastructure file
         let unified_code = self.generate_unified_infrastructure(&services, &kotlin_package);
         let unified_file = context.output.join("unified_actor.kt");
-        std::fs::write(&unified_file, &unified_code).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to write unified_actor.kt: {e}"))
-        })?;
+        std::fs::write(&unified_file, &unified_code)
+            .map_err(|e| ActrCliError::output_write_failed(&unified_file, e))?;
         generated_files.push(unified_file);
         info!("📄 Generated unified_actor.kt");
 
+        if let Some(module) = &context.gradle_module {
+            match gradle::write_snippet(module, &context.input_path)? {
+                Some(path) => info!("📄 Updated {}", path.display()),
+                None => warn!(
+                    "⚠️  {} was not written by actr gen; leaving it unchanged",
+                    module.join(gradle::SNIPPET_FILE).display()
+                ),
+            }
+        }
+
         info!(
             "✅ Generated {} Kotlin infrastructure files",
             generated_files.len()
@@ -773,18 +759,33 @@ impl LanguageGenerator for KotlinGenerator {
 
         let mut generated_files = Vec::new();
         let kotlin_package = self.get_kotlin_package(context);
-        let services = self.collect_services(context)?;
-
-        let output_dir = context.output.parent().unwrap_or(&context.output);
+        let services = self.collect_services(context).await?;
+
+        // Scaffolds are user code: keep them in the module's sources, not under build/
+        let output_dir = match &context.gradle_module {
+            Some(module) => {
+                let base_package = kotlin_package
+                    .strip_suffix(".generated")
+                    .unwrap_or(&kotlin_package);
+                let dir = gradle::scaffold_dir(module, base_package);
+                std::fs::create_dir_all(&dir)
+                    .map_err(|e| ActrCliError::output_write_failed(&dir, e))?;
+                dir
+            }
+            None => context
+                .output
+                .parent()
+                .unwrap_or(&context.output)
+                .to_path_buf(),
+        };
 
         // Generate unified workload
         let unified_workload_file = output_dir.join("UnifiedWorkload.kt");
         if !unified_workload_file.exists() || context.overwrite_user_code {
             let unified_workload_content =
                 generate_unified_workload_scaffold(&services, &kotlin_package);
-            std::fs::write(&unified_workload_file, &unified_workload_content).map_err(|e| {
-                ActrCliError::config_error(format!("Failed to write UnifiedWorkload.kt: {e}"))
-            })?;
+            std::fs::write(&unified_workload_file, &unified_workload_content)
+                .map_err(|e| ActrCliError::output_write_failed(&unified_workload_file, e))?;
             info!("📄 Generated UnifiedWorkload.kt");
             generated_files.push(unified_workload_file);
         } else {
@@ -793,12 +794,29 @@ impl LanguageGenerator for KotlinGenerator {
 
         // Generate unified handler implementation
         let unified_handler_file = output_dir.join("MyUnifiedHandler.kt");
-        if !unified_handler_file.exists() || context.overwrite_user_code {
+        if unified_handler_file.exists() && context.merge_scaffold {
+            let existing =
+                std::fs::read_to_string(&unified_handler_file).map_err(ActrCliError::Io)?;
+            match merge_unified_handler(&existing, &services) {
+                Some(outcome) if !outcome.added.is_empty() => {
+                    std::fs::write(&unified_handler_file, &outcome.source)
+                        .map_err(|e| ActrCliError::output_write_failed(&unified_handler_file, e))?;
+                    info!(
+                        "🧩 Added {} to MyUnifiedHandler.kt",
+                        outcome.added.join(", ")
+                    );
+                    generated_files.push(unified_handler_file);
+                }
+                Some(_) => info!("✅ MyUnifiedHandler.kt already implements every RPC"),
+                None => {
+                    warn!("⚠️  No `class MyUnifiedHandler` in MyUnifiedHandler.kt; not merging")
+                }
+            }
+        } else if !unified_handler_file.exists() || context.overwrite_user_code {
             let unified_handler_content =
                 generate_unified_handler_scaffold(&services, &kotlin_package);
-            std::fs::write(&unified_handler_file, &unified_handler_content).map_err(|e| {
-                ActrCliError::config_error(format!("Failed to write MyUnifiedHandler.kt: {e}"))
-            })?;
+            std::fs::write(&unified_handler_file, &unified_handler_content)
+                .map_err(|e| ActrCliError::output_write_failed(&unified_handler_file, e))?;
             info!("📄 Generated MyUnifiedHandler.kt");
             generated_files.push(unified_handler_file);
         } else {
@@ -808,47 +826,59 @@ impl LanguageGenerator for KotlinGenerator {
         Ok(generated_files)
     }
 
-    async fn format_code(&self, _context: &GenContext, files: &[PathBuf]) -> Result<()> {
+    async fn format_code(&self, context: &GenContext, files: &[PathBuf]) -> Result<()> {
         info!("🎨 Formatting Kotlin code...");
 
-        // Try to use ktlint if available
-        let ktlint_check = StdCommand::new("which").arg("ktlint").output();
+        // Try to use ktlint (or the [build.format.kotlin] tool) if available
+        let tool = context.format.tool("ktlint");
+        let tool_check = StdCommand::new("which").arg(tool).output();
 
-        if let Ok(output) = ktlint_check {
+        if let Ok(output) = tool_check {
             if output.status.success() {
                 for file in files {
-                    let mut cmd = StdCommand::new("ktlint");
-                    cmd.arg("-F").arg(file);
+                    let mut cmd = context.format.command(tool, "ktlint", &["-F"]);
+                    cmd.arg(file);
 
-                    let output = cmd.output();
+                    let output = cmd.output().await;
                     if let Err(e) = output {
-                        warn!("ktlint formatting failed for {:?}: {}", file, e);
+                        warn!("{tool} formatting failed for {:?}: {}", file, e);
                     }
                 }
-                info!("✅ Kotlin code formatted with ktlint");
+                info!("✅ Kotlin code formatted with {tool}");
             } else {
-                info!("💡 ktlint not found, skipping formatting");
+                info!("💡 {tool} not found, skipping formatting");
             }
         }
 
         Ok(())
     }
 
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        vec![
+            PROTOC_TOOL,
+            RequiredTool::required(KOTLIN_PLUGIN_NAME, KOTLIN_PLUGIN_HINT),
+            RequiredTool::optional("ktlint", "brew install ktlint"),
+        ]
+    }
+
+    fn stage_names(&self) -> (&'static str, &'static str) {
+        ("ktlint", "validate")
+    }
+
     async fn validate_code(&self, context: &GenContext) -> Result<()> {
         info!("🔍 Validating Kotlin code...");
 
         // Check if generated files exist
         let generated_dir = &context.output;
         if !generated_dir.exists() {
-            return Err(ActrCliError::config_error(
-                "Generated output directory does not exist",
-            ));
+            return Err(ActrCliError::CodeGeneration(format!(
+                "Generated output directory {} does not exist",
+                generated_dir.display()
+            )));
         }
 
         let kt_files: Vec<_> = std::fs::read_dir(generated_dir)
-            .map_err(|e| {
-                ActrCliError::config_error(format!("Failed to read output directory: {e}"))
-            })?
+            .map_err(ActrCliError::Io)?
             .filter_map(|e| e.ok())
             .filter(|e| e.path().extension().map(|ext| ext == "kt").unwrap_or(false))
             .collect();
@@ -866,16 +896,27 @@ impl LanguageGenerator for KotlinGenerator {
     }
 
     fn print_next_steps(&self, context: &GenContext) {
-        println!("\n🎉 Kotlin code generation completed!");
-        println!("\n📋 Next steps:");
-        println!("1. 📖 View generated code: {:?}", context.output);
-        println!("2. 📦 Ensure protobuf gradle plugin is configured for message classes");
-        println!("3. ✏️  Implement MyUnifiedHandler with your business logic");
-        println!("4. 🚀 Use UnifiedWorkload in your app");
-        println!("5. 🏗️  Build project: ./gradlew build");
-        println!("6. 🧪 Run tests: ./gradlew connectedAndroidTest");
-        println!(
-            "\n💡 Tip: The UnifiedDispatcher routes local requests to your handler and remote requests via RPC"
+        let gradle_step = match &context.gradle_module {
+            Some(module) if !gradle::is_applied(module) => tr!(
+                "gen.kotlin.apply_snippet",
+                snippet = gradle::SNIPPET_FILE,
+                build_file = module.join("build.gradle.kts").display()
+            ),
+            Some(_) => tr!("gen.kotlin.snippet_applied", snippet = gradle::SNIPPET_FILE),
+            None => tr!("gen.kotlin.protobuf_plugin"),
+        };
+        let steps = [
+            tr!("gen.view_code", path = context.output.display()),
+            gradle_step,
+            tr!("gen.kotlin.implement"),
+            tr!("gen.kotlin.workload"),
+            tr!("gen.kotlin.build"),
+            tr!("gen.kotlin.test"),
+        ];
+        print_steps(
+            &tr!("gen.completed_language", language = "Kotlin"),
+            &steps,
+            Some(&tr!("gen.kotlin.tip")),
         );
     }
 }
@@ -1012,6 +1053,129 @@ class UnifiedWorkload(
     )
 }
 
+/// `override fun` of one RPC in `MyUnifiedHandler`
+///
+/// Streaming requests and responses are `Flow`s; a method with a streaming
+/// response returns its `Flow` directly instead of suspending.
+fn handler_method(service: &ServiceInfo, method: &MethodInfo) -> String {
+    let (param, param_doc) = if method.client_streaming {
+        (
+            format!("requests: Flow<{}>", method.request_type),
+            format!(
+                "@param requests Stream of {} request messages",
+                method.request_type
+            ),
+        )
+    } else {
+        (
+            format!("request: {}", method.request_type),
+            format!("@param request The {} request message", method.request_type),
+        )
+    };
+    let (modifier, returns, returns_doc) = if method.server_streaming {
+        (
+            "",
+            format!("Flow<{}>", method.response_type),
+            format!("Stream of {} response messages", method.response_type),
+        )
+    } else {
+        (
+            "suspend ",
+            method.response_type.clone(),
+            format!("{} response message", method.response_type),
+        )
+    };
+    format!(
+        r#"    /**
+     * Handle {name} request for {service} service
+     *
+     * {param_doc}
+     * @param ctx Context bridge for actor operations
+     * @return {returns_doc}
+     */
+    override {modifier}fun {name}({param}, ctx: ContextBridge): {returns} {{
+        TODO("Not yet implemented")
+    }}
+"#,
+        name = method.name,
+        service = service.service_name,
+    )
+}
+
+fn is_streaming(method: &MethodInfo) -> bool {
+    method.client_streaming || method.server_streaming
+}
+
+/// Outer class of a service's proto file
+fn outer_class(service: &ServiceInfo) -> String {
+    let base = to_pascal_case(&service.proto_file_name.replace(".proto", ""));
+    if service.needs_outer_class_suffix {
+        format!("{base}OuterClass")
+    } else {
+        base
+    }
+}
+
+/// Add the methods of RPCs missing from an existing `MyUnifiedHandler.kt`
+///
+/// Imports of services that were not handled before are added after the last
+/// import. Returns `None` when the file has no `class MyUnifiedHandler`.
+fn merge_unified_handler(existing: &str, services: &[ServiceInfo]) -> Option<MergeOutcome> {
+    let local_services: Vec<_> = services.iter().filter(|s| s.is_local).collect();
+    let stubs: Vec<MethodStub> = local_services
+        .iter()
+        .flat_map(|service| {
+            service.methods.iter().map(|method| MethodStub {
+                name: method.name.clone(),
+                code: format!(
+                    "    // TODO: new RPC, added by `actr gen --merge-scaffold`\n{}",
+                    handler_method(service, method)
+                ),
+            })
+        })
+        .collect();
+    let mut outcome = merge_block(existing, "class MyUnifiedHandler", "fun", &stubs)?;
+    if outcome.added.is_empty() {
+        return Some(outcome);
+    }
+
+    let flow_import = local_services
+        .iter()
+        .any(|service| service.methods.iter().any(is_streaming))
+        .then(|| FLOW_IMPORT.to_string());
+    let missing_imports: Vec<String> = local_services
+        .iter()
+        .map(|service| {
+            format!(
+                "import {}.{}.*",
+                service.proto_package,
+                outer_class(service)
+            )
+        })
+        .chain(flow_import)
+        .filter(|import| !outcome.source.lines().any(|line| line.trim() == import))
+        .collect();
+    if !missing_imports.is_empty() {
+        let insert_at = outcome
+            .source
+            .match_indices("\nimport ")
+            .last()
+            .and_then(|(pos, _)| {
+                outcome.source[pos + 1..]
+                    .find('\n')
+                    .map(|end| pos + end + 2)
+            });
+        if let Some(insert_at) = insert_at {
+            let lines: String = missing_imports
+                .iter()
+                .map(|import| format!("{import}\n"))
+                .collect();
+            outcome.source.insert_str(insert_at, &lines);
+        }
+    }
+    Some(outcome)
+}
+
 /// Generate unified handler implementation scaffold
 fn generate_unified_handler_scaffold(services: &[ServiceInfo], kotlin_package: &str) -> String {
     let base_package = kotlin_package
@@ -1037,44 +1201,24 @@ package {base_package}
 
     let mut imports = String::new();
     let mut method_impls = String::new();
+    if local_services
+        .iter()
+        .any(|service| service.methods.iter().any(is_streaming))
+    {
+        imports.push_str(&format!("{FLOW_IMPORT}\n"));
+    }
 
     for service in &local_services {
-        let outer_class = if service.needs_outer_class_suffix {
-            format!(
-                "{}OuterClass",
-                to_pascal_case(&service.proto_file_name.replace(".proto", ""))
-            )
-        } else {
-            to_pascal_case(&service.proto_file_name.replace(".proto", ""))
-        };
         imports.push_str(&format!(
             "import {}.{}.*\n",
-            service.proto_package, outer_class
+            service.proto_package,
+            outer_class(service)
         ));
 
         // Generate method implementations for each RPC method
         for method in &service.methods {
-            method_impls.push_str(&format!(
-                r#"
-    /**
-     * Handle {} request for {} service
-     *
-     * @param request The {} request message
-     * @param ctx Context bridge for actor operations
-     * @return {} response message
-     */
-    override suspend fun {}(request: {}, ctx: ContextBridge): {} {{
-        TODO("Not yet implemented")
-    }}
-"#,
-                method.name,
-                service.service_name,
-                method.request_type,
-                method.response_type,
-                method.name,
-                method.request_type,
-                method.response_type
-            ));
+            method_impls.push('\n');
+            method_impls.push_str(&handler_method(service, method));
         }
 
         // Add a separator comment between services
@@ -1122,3 +1266,9 @@ class MyUnifiedHandler : UnifiedHandler {{
         method_impls = method_impls,
     )
 }
+
+/// Version reported by `<plugin> --version`
+fn plugin_version(path: &Path) -> Option<String> {
+    let output = StdCommand::new(path).arg("--version").output().ok()?;
+    parse_version_output(&String::from_utf8_lossy(&output.stdout))
+}
@@ -0,0 +1,178 @@
+//! Manifest of generated files and tamper detection
+//!
+//! After every generation `actr gen` writes `.actr-manifest.json` into the output
+//! directory with the SHA-256 of each generated file. Generated files are made
+//! read-only, but that is easy to override; comparing the files with the manifest
+//! shows which ones were edited by hand since the last `actr gen`. `actr gen` and
+//! `actr check` report such edits as warnings.
+
+use crate::error::{ActrCliError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Manifest file written into the output directory
+pub const MANIFEST_FILE: &str = ".actr-manifest.json";
+
+const MANIFEST_VERSION: u32 = 1;
+
+/// Directories `find_manifests` never descends into
+const SKIPPED_DIRS: [&str; 6] = [
+    "target",
+    "node_modules",
+    ".git",
+    "build",
+    ".gradle",
+    "protos",
+];
+
+/// Contents of [`MANIFEST_FILE`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenManifest {
+    pub version: u32,
+    /// SHA-256 of every generated file, keyed by its path relative to the output directory
+    pub files: BTreeMap<String, String>,
+}
+
+/// A generated file that no longer matches the manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Tampering {
+    Modified(PathBuf),
+    Deleted(PathBuf),
+    /// Present in the output directory but not written by `actr gen`
+    Untracked(PathBuf),
+}
+
+impl std::fmt::Display for Tampering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tampering::Modified(path) => write!(f, "{} was edited by hand", path.display()),
+            Tampering::Deleted(path) => write!(f, "{} was deleted", path.display()),
+            Tampering::Untracked(path) => {
+                write!(f, "{} was not written by actr gen", path.display())
+            }
+        }
+    }
+}
+
+impl GenManifest {
+    /// Hash every file currently in `output`
+    pub fn build(output: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for entry in WalkDir::new(output).sort_by_file_name() {
+            let entry = entry.map_err(|e| ActrCliError::Io(e.into()))?;
+            if !entry.file_type().is_file() || entry.file_name() == MANIFEST_FILE {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(output) else {
+                continue;
+            };
+            let content = std::fs::read(entry.path())?;
+            files.insert(
+                relative.to_string_lossy().replace('\\', "/"),
+                hex::encode(Sha256::digest(&content)),
+            );
+        }
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            files,
+        })
+    }
+
+    /// Read the manifest of `output`; `None` when missing or unreadable
+    pub fn load(output: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(output.join(MANIFEST_FILE)).ok()?;
+        serde_json::from_str::<Self>(&content)
+            .ok()
+            .filter(|manifest| manifest.version == MANIFEST_VERSION)
+    }
+
+    /// Hash the files currently in `output` and write them as its manifest
+    pub fn write(output: &Path) -> Result<Self> {
+        let manifest = Self::build(output)?;
+        let path = output.join(MANIFEST_FILE);
+        std::fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")?;
+        Ok(manifest)
+    }
+
+    /// Files in `output` that differ from this manifest
+    pub fn verify(&self, output: &Path) -> Result<Vec<Tampering>> {
+        let current = Self::build(output)?;
+        let mut tampering = Vec::new();
+        for (path, hash) in &self.files {
+            match current.files.get(path) {
+                None => tampering.push(Tampering::Deleted(PathBuf::from(path))),
+                Some(current_hash) if current_hash != hash => {
+                    tampering.push(Tampering::Modified(PathBuf::from(path)))
+                }
+                Some(_) => {}
+            }
+        }
+        for path in current.files.keys() {
+            if !self.files.contains_key(path) {
+                tampering.push(Tampering::Untracked(PathBuf::from(path)));
+            }
+        }
+        Ok(tampering)
+    }
+}
+
+/// Compare `output` with its manifest; empty when there is no manifest yet
+pub fn detect_tampering(output: &Path) -> Result<Vec<Tampering>> {
+    match GenManifest::load(output) {
+        Some(manifest) => manifest.verify(output),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Output directories below `root` that hold a manifest
+pub fn find_manifests(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .max_depth(6)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.file_name() == MANIFEST_FILE)
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_tampering() {
+        let dir = TempDir::new().unwrap();
+        let output = dir.path().join("src").join("generated");
+        std::fs::create_dir_all(&output).unwrap();
+        assert!(detect_tampering(&output).unwrap().is_empty());
+
+        std::fs::write(output.join("echo.rs"), "pub struct Echo;\n").unwrap();
+        std::fs::write(output.join("mod.rs"), "pub mod echo;\n").unwrap();
+        let manifest = GenManifest::write(&output).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert!(detect_tampering(&output).unwrap().is_empty());
+        assert_eq!(find_manifests(dir.path()), vec![output.clone()]);
+
+        std::fs::write(output.join("echo.rs"), "pub struct Echo; // patched\n").unwrap();
+        std::fs::remove_file(output.join("mod.rs")).unwrap();
+        std::fs::write(output.join("extra.rs"), "").unwrap();
+        assert_eq!(
+            detect_tampering(&output).unwrap(),
+            vec![
+                Tampering::Modified(PathBuf::from("echo.rs")),
+                Tampering::Deleted(PathBuf::from("mod.rs")),
+                Tampering::Untracked(PathBuf::from("extra.rs")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! Scaffold merging for `actr gen --merge-scaffold`
+//!
+//! Instead of skipping or overwriting an existing user code file, the generators
+//! look up the handler block of each service in it and insert the stubs of the
+//! RPCs that have no method yet, just before the block's closing brace. Everything
+//! else in the file is kept byte for byte.
+
+/// A handler method the scaffold would contain
+#[derive(Debug, Clone)]
+pub struct MethodStub {
+    /// Method name as declared in the target language
+    pub name: String,
+    /// Full method source, indented for the handler block
+    pub code: String,
+}
+
+/// Result of merging stubs into one handler block
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOutcome {
+    pub source: String,
+    /// Names of the inserted methods
+    pub added: Vec<String>,
+}
+
+/// Insert the stubs missing from the block opened at the first `header`
+///
+/// `keyword` introduces a method in the target language (`fn`, `fun`, `func`).
+/// Returns `None` when `header` or its braces cannot be found.
+pub fn merge_block(
+    source: &str,
+    header: &str,
+    keyword: &str,
+    stubs: &[MethodStub],
+) -> Option<MergeOutcome> {
+    let header_start = source.find(header)?;
+    let open = header_start + source[header_start..].find('{')?;
+    let close = matching_brace(source, open)?;
+    let block = &source[open..close];
+
+    let missing: Vec<&MethodStub> = stubs
+        .iter()
+        .filter(|stub| !defines_method(block, keyword, &stub.name))
+        .collect();
+    if missing.is_empty() {
+        return Some(MergeOutcome {
+            source: source.to_string(),
+            added: Vec::new(),
+        });
+    }
+
+    // Insert at the start of the line holding the closing brace
+    let insert_at = source[..close].rfind('\n').map_or(close, |i| i + 1);
+    let mut merged = String::with_capacity(source.len() + 256);
+    merged.push_str(&source[..insert_at]);
+    if !source[..insert_at].ends_with("\n\n") && !source[..insert_at].ends_with("{\n") {
+        merged.push('\n');
+    }
+    for (index, stub) in missing.iter().enumerate() {
+        if index > 0 {
+            merged.push('\n');
+        }
+        merged.push_str(stub.code.trim_end_matches('\n'));
+        merged.push('\n');
+    }
+    merged.push_str(&source[insert_at..]);
+
+    Some(MergeOutcome {
+        source: merged,
+        added: missing.iter().map(|stub| stub.name.clone()).collect(),
+    })
+}
+
+/// Whether `block` declares a method `name` with `keyword` (`fn name`, `func name(`)
+pub fn defines_method(block: &str, keyword: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut rest = block;
+    while let Some(pos) = rest.find(keyword) {
+        let before = rest[..pos].chars().next_back();
+        let after = &rest[pos + keyword.len()..];
+        rest = after;
+        if before.is_some_and(is_ident) || !after.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let after = after.trim_start();
+        if let Some(tail) = after.strip_prefix(name)
+            && !tail.starts_with(is_ident)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Index of the `}` matching the `{` at `open`, skipping strings and comments
+fn matching_brace(source: &str, open: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += source[i..].find('\n').unwrap_or(source.len() - i);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += source[i + 2..]
+                    .find("*/")
+                    .map_or(source.len() - i, |end| end + 4);
+                continue;
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stub(name: &str) -> MethodStub {
+        MethodStub {
+            name: name.to_string(),
+            code: format!("    fn {name}(&self) {{\n        todo!()\n    }}\n"),
+        }
+    }
+
+    #[test]
+    fn test_merge_block_appends_missing_methods() {
+        let source = "impl EchoHandler for MyEcho {\n    fn echo(&self) {\n        let s = \"}\";\n    }\n}\n\nfn helper() {}\n";
+        let merged = merge_block(
+            source,
+            "impl EchoHandler for",
+            "fn",
+            &[stub("echo"), stub("ping")],
+        )
+        .unwrap();
+        assert_eq!(merged.added, vec!["ping".to_string()]);
+        assert_eq!(
+            merged.source,
+            "impl EchoHandler for MyEcho {\n    fn echo(&self) {\n        let s = \"}\";\n    }\n\n    fn ping(&self) {\n        todo!()\n    }\n}\n\nfn helper() {}\n"
+        );
+
+        // A second merge is a no-op
+        let again = merge_block(
+            &merged.source,
+            "impl EchoHandler for",
+            "fn",
+            &[stub("ping")],
+        )
+        .unwrap();
+        assert!(again.added.is_empty());
+        assert!(merge_block(source, "impl Missing for", "fn", &[stub("x")]).is_none());
+    }
+
+    #[test]
+    fn test_defines_method() {
+        assert!(defines_method("async fn send<C>(", "fn", "send"));
+        assert!(defines_method(
+            "override suspend fun send_file(",
+            "fun",
+            "send_file"
+        ));
+        assert!(!defines_method("fn sender(", "fn", "send"));
+        assert!(!defines_method("let fnsend = 1;", "fn", "send"));
+    }
+}
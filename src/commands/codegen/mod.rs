@@ -1,43 +1,282 @@
+pub mod build_script;
+mod cache;
+pub mod descriptor;
+pub mod diagnostics;
+mod filter;
+pub mod format;
+pub mod generated_crate;
+pub mod gradle;
+pub mod header;
+mod imports;
 mod kotlin;
+pub mod manifest;
+mod merge;
 mod python;
+mod rust;
 mod swift;
 mod traits;
+mod typescript;
 
 pub use crate::commands::SupportedLanguage;
-use crate::error::Result;
+use crate::core::events;
+use crate::core::timings::{timed, timed_blocking};
+use crate::error::{ActrCliError, Result};
+use crate::human_println;
+use crate::tr;
+pub use build_script::{GenerationOptions, run_generation};
+use cache::GenCache;
+pub use filter::ProtoFilter;
+use header::PreviousHeaders;
 use kotlin::KotlinGenerator;
 use python::PythonGenerator;
+use rust::RustGenerator;
+use std::path::{Path, PathBuf};
 use swift::SwiftGenerator;
-use tracing::info;
-pub use traits::{GenContext, LanguageGenerator, ScaffoldType};
+use tracing::{info, warn};
+pub use traits::{GenContext, LanguageGenerator, PROTOC_TOOL, RequiredTool, ScaffoldType};
+use typescript::TypescriptGenerator;
 
 pub struct GeneratorFactory;
 
 impl GeneratorFactory {
     pub fn get_generator(language: SupportedLanguage) -> Box<dyn LanguageGenerator> {
         match language {
-            SupportedLanguage::Rust => unreachable!("Rust is handled directly in GenCommand"),
+            SupportedLanguage::Rust => Box::new(RustGenerator),
             SupportedLanguage::Python => Box::new(PythonGenerator),
             SupportedLanguage::Swift => Box::new(SwiftGenerator),
             SupportedLanguage::Kotlin => Box::new(KotlinGenerator),
+            SupportedLanguage::Typescript => Box::new(TypescriptGenerator),
         }
     }
 }
 
-pub async fn execute_codegen(language: SupportedLanguage, context: &GenContext) -> Result<()> {
+/// Run code generation and return the files that were written
+pub async fn execute_codegen(
+    language: SupportedLanguage,
+    context: &GenContext,
+) -> Result<Vec<PathBuf>> {
     let generator = GeneratorFactory::get_generator(language);
+    let run = generate_cached(generator.as_ref(), language, context).await?;
+    if run.up_to_date {
+        human_println!("{}", tr!("gen.up_to_date", count = run.skipped));
+        return Ok(Vec::new());
+    }
+
+    human_println!(
+        "{}",
+        tr!(
+            "gen.cache_summary",
+            regenerated = run.regenerated,
+            skipped = run.skipped
+        )
+    );
+
+    info!("{}", tr!("gen.finished"));
+    generator.print_next_steps(context);
+    Ok(run.files)
+}
+
+/// Outcome of a cached generation run
+pub struct CachedRun {
+    /// Nothing was stale, so nothing was generated
+    pub up_to_date: bool,
+    /// Protos handed to the generator
+    pub regenerated: usize,
+    /// Protos skipped as unchanged
+    pub skipped: usize,
+    /// Files written
+    pub files: Vec<PathBuf>,
+}
+
+/// Regenerate the stale protos among `context.proto_files` through the
+/// generation cache (used by `actr gen` and watch mode)
+///
+/// Generators without incremental support, deleted protos and missing or
+/// hand-edited outputs get a full run, which also removes orphaned outputs.
+pub async fn regenerate_cached(
+    language: SupportedLanguage,
+    context: &GenContext,
+) -> Result<CachedRun> {
+    let generator = GeneratorFactory::get_generator(language);
+    generate_cached(generator.as_ref(), language, context).await
+}
+
+async fn generate_cached(
+    generator: &dyn LanguageGenerator,
+    language: SupportedLanguage,
+    context: &GenContext,
+) -> Result<CachedRun> {
+    let cache_path = GenCache::path_for(context, language);
+    let mut cache = if context.force {
+        GenCache::default()
+    } else {
+        GenCache::load(&cache_path)
+    };
+    let resolver = imports::ImportResolver::new(context);
+    let plan = cache.plan(
+        &context.proto_files,
+        cache::options_hash(language, generator, context, &resolver.all_roots()),
+        &|import| resolver.resolve(import),
+    );
+
+    // Hand edits to generated files force a full regeneration to restore them
+    let tampering = manifest::detect_tampering(&context.output).unwrap_or_default();
+    report_tampering(&context.output, &tampering);
+
+    // Deleted protos, outputs removed since the last run (e.g. --clean) and hand
+    // edits all need a full run, which also drops the outputs of deleted protos
+    let full = !plan.removed.is_empty() || cache.outputs_missing() || !tampering.is_empty();
+    if plan.stale.is_empty() && !full {
+        return Ok(CachedRun {
+            up_to_date: true,
+            regenerated: 0,
+            skipped: plan.fresh.len(),
+            files: Vec::new(),
+        });
+    }
+
+    let mut run_context = context.clone();
+    let incremental = generator.supports_incremental() && !full;
+    if incremental {
+        run_context.proto_files = plan.stale.clone();
+    }
+    let files = run_pipeline(generator, &run_context, &context.output).await?;
+
+    if !incremental {
+        remove_orphaned_outputs(&cache.orphaned_outputs(&files.owned));
+    }
+    cache.record(&plan, &files.owned, !incremental);
+    if let Err(e) = cache.save(&cache_path) {
+        warn!(
+            "{}",
+            tr!(
+                "gen.cache_write_failed",
+                path = cache_path.display(),
+                error = e
+            )
+        );
+    }
+
+    let regenerated = run_context.proto_files.len();
+    Ok(CachedRun {
+        up_to_date: false,
+        regenerated,
+        skipped: context.proto_files.len() - regenerated,
+        files: files.all(),
+    })
+}
 
-    let mut all_files = generator.generate_infrastructure(context).await?;
+/// Delete generated files whose source proto is gone
+fn remove_orphaned_outputs(orphans: &[PathBuf]) {
+    for orphan in orphans.iter().filter(|orphan| orphan.is_file()) {
+        // Generated files are read-only, which blocks deletion on Windows
+        if cfg!(windows)
+            && let Ok(metadata) = std::fs::metadata(orphan)
+        {
+            let mut permissions = metadata.permissions();
+            #[allow(clippy::permissions_set_readonly_false)]
+            permissions.set_readonly(false);
+            let _ = std::fs::set_permissions(orphan, permissions);
+        }
+        match std::fs::remove_file(orphan) {
+            Ok(()) => info!("{}", tr!("gen.orphan_removed", path = orphan.display())),
+            Err(e) => warn!(
+                "{}",
+                tr!(
+                    "gen.orphan_remove_failed",
+                    path = orphan.display(),
+                    error = e
+                )
+            ),
+        }
+    }
+}
+
+/// Run the generator pipeline for `context.proto_files`, bypassing the
+/// generation cache (used by `actr gen --check`)
+///
+/// File headers are restored from the matching files under `headers_from`.
+/// Returns the number of files written.
+pub async fn regenerate(
+    language: SupportedLanguage,
+    context: &GenContext,
+    headers_from: &Path,
+) -> Result<usize> {
+    let generator = GeneratorFactory::get_generator(language);
+    Ok(run_pipeline(generator.as_ref(), context, headers_from)
+        .await?
+        .all()
+        .len())
+}
+
+/// Files written by one pipeline run
+struct PipelineFiles {
+    /// Generated files owned by `actr gen`
+    owned: Vec<PathBuf>,
+    /// User code scaffolds
+    scaffold: Vec<PathBuf>,
+}
+
+impl PipelineFiles {
+    fn all(self) -> Vec<PathBuf> {
+        [self.owned, self.scaffold].concat()
+    }
+}
+
+async fn run_pipeline(
+    generator: &dyn LanguageGenerator,
+    context: &GenContext,
+    headers_from: &Path,
+) -> Result<PipelineFiles> {
+    let context = &timed_blocking("imports", || imports::resolve_imports(context))?;
+    let service = &context.config.package.name;
+    let previous = PreviousHeaders::read(headers_from, &context.output);
+    let owned = timed("protoc", generator.generate_infrastructure(context))
+        .await
+        .inspect_err(|e| {
+            if let ActrCliError::ProtocFailed { stderr, .. } = e {
+                diagnostics::report(&diagnostics::from_protoc(stderr, &context.proto_files));
+            }
+        })?;
+    context.header.apply(service, &owned, true, &previous)?;
+    let mut scaffold = Vec::new();
     if !context.no_scaffold {
-        all_files.extend(generator.generate_scaffold(context).await?);
+        scaffold = timed("scaffold", generator.generate_scaffold(context)).await?;
+        context.header.apply(service, &scaffold, false, &previous)?;
     }
+    let all_files = [owned.as_slice(), scaffold.as_slice()].concat();
+    let (format_stage, validate_stage) = generator.stage_names();
     if !context.no_format {
-        generator.format_code(context, &all_files).await?;
+        timed(format_stage, generator.format_code(context, &all_files)).await?;
     }
 
-    generator.validate_code(context).await?;
+    if !context.no_validate {
+        timed(validate_stage, generator.validate_code(context)).await?;
+    }
+    timed("finalize", generator.finalize(context)).await?;
 
-    info!("✅ 代码生成完成！");
-    generator.print_next_steps(context);
-    Ok(())
+    if context.output.is_dir() {
+        manifest::GenManifest::write(&context.output)?;
+    }
+    events::emit_files_generated(&all_files);
+    Ok(PipelineFiles { owned, scaffold })
+}
+
+/// Warn about generated files that were edited since the last generation
+fn report_tampering(output: &Path, tampering: &[manifest::Tampering]) {
+    if tampering.is_empty() {
+        return;
+    }
+    human_println!(
+        "{}",
+        tr!(
+            "gen.tampered",
+            count = tampering.len(),
+            path = output.display()
+        )
+    );
+    for entry in tampering {
+        human_println!("   - {entry}");
+    }
+    human_println!("{}", tr!("gen.tampered_regenerating"));
 }
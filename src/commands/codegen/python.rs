@@ -1,5 +1,9 @@
-use crate::commands::codegen::traits::{GenContext, LanguageGenerator};
+use crate::commands::codegen::traits::{
+    GenContext, LanguageGenerator, PROTOC_TOOL, RequiredTool, print_steps,
+};
+use crate::core::DefaultProtoProcessor;
 use crate::error::{ActrCliError, Result};
+use crate::tr;
 use crate::utils::{command_exists, to_pascal_case};
 use actr_config::LockFile;
 use async_trait::async_trait;
@@ -7,6 +11,7 @@ use handlebars::Handlebars;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 use std::process::Command as StdCommand;
+use tokio::process::Command as TokioCommand;
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
@@ -17,8 +22,7 @@ const ACTR_SERVICE_TEMPLATE: &str = include_str!(concat!(
 ));
 
 // Required tools for Python codegen
-const PROTOC: &str = "protoc";
-const REQUIRED_TOOLS: &[(&str, &str)] = &[(PROTOC, "Protocol Buffers compiler")];
+const REQUIRED_TOOLS: &[RequiredTool] = &[PROTOC_TOOL];
 
 #[derive(Serialize, Clone)]
 struct ProtoService {
@@ -33,6 +37,8 @@ struct ProtoMethod {
     snake_name: String,
     input_type: String,
     output_type: String,
+    client_streaming: bool,
+    server_streaming: bool,
 }
 
 pub struct PythonGenerator;
@@ -48,9 +54,8 @@ impl LanguageGenerator for PythonGenerator {
         let plugin_path = ensure_python_plugin()?;
 
         // Ensure output directory exists
-        std::fs::create_dir_all(&context.output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to create output directory: {e}"))
-        })?;
+        std::fs::create_dir_all(&context.output)
+            .map_err(|e| ActrCliError::output_write_failed(&context.output, e))?;
 
         let proto_root = if context.input_path.is_file() {
             context
@@ -237,8 +242,9 @@ impl LanguageGenerator for PythonGenerator {
         info!("📝 Options: {}", options);
 
         // Step 1: Generate basic Python protobuf types for all files at once
-        let mut cmd = StdCommand::new("protoc");
-        cmd.arg(format!("--proto_path={}", proto_root.display()))
+        let mut cmd = TokioCommand::new("protoc");
+        cmd.kill_on_drop(true);
+        cmd.args(context.proto_path_args(proto_root))
             .arg(format!("--python_out={}", context.output.display()));
 
         for proto_file in &context.proto_files {
@@ -246,20 +252,20 @@ impl LanguageGenerator for PythonGenerator {
         }
 
         debug!("Executing protoc (python): {:?}", cmd);
-        let output = cmd.output().map_err(|e| {
-            ActrCliError::command_error(format!("Failed to execute protoc (python): {e}"))
-        })?;
+        let output = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ActrCliError::command_error(format!(
-                "protoc (python) execution failed: {stderr}"
-            )));
+            return Err(ActrCliError::protoc_failed(
+                context.input_path.display(),
+                stderr,
+            ));
         }
 
         // Step 2: Generate Actor framework code using protoc-gen-actrpython for all files at once
-        let mut cmd = StdCommand::new("protoc");
-        cmd.arg(format!("--proto_path={}", proto_root.display()))
+        let mut cmd = TokioCommand::new("protoc");
+        cmd.kill_on_drop(true);
+        cmd.args(context.proto_path_args(proto_root))
             .arg(format!(
                 "--plugin=protoc-gen-actrpython={}",
                 plugin_path.display()
@@ -272,15 +278,14 @@ impl LanguageGenerator for PythonGenerator {
         }
 
         debug!("Executing protoc (actrpython): {:?}", cmd);
-        let output = cmd.output().map_err(|e| {
-            ActrCliError::command_error(format!("Failed to execute protoc (actrpython): {e}"))
-        })?;
+        let output = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ActrCliError::command_error(format!(
-                "protoc (actrpython) execution failed: {stderr}"
-            )));
+            return Err(ActrCliError::protoc_failed(
+                context.input_path.display(),
+                stderr,
+            ));
         }
 
         // Collect generated files (recursively)
@@ -299,11 +304,15 @@ impl LanguageGenerator for PythonGenerator {
     }
 
     async fn generate_scaffold(&self, context: &GenContext) -> Result<Vec<PathBuf>> {
+        if context.no_scaffold {
+            return Ok(vec![]);
+        }
+
         info!("📝 Generating Python user code scaffold...");
         let mut scaffold_files = Vec::new();
 
         // 1. Parse local services to get methods for handler implementation
-        let services = self.parse_local_services(context);
+        let services = self.parse_local_services(context).await?;
 
         // 2. Determine service name for scaffolding
         let service_name = if let Some(service) = services.first() {
@@ -375,9 +384,8 @@ impl LanguageGenerator for PythonGenerator {
         let scaffold_content =
             self.generate_scaffold_content(context, &service_name, &workload_name, &services)?;
 
-        std::fs::write(&user_file_path, scaffold_content).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to write user code scaffold: {e}"))
-        })?;
+        std::fs::write(&user_file_path, scaffold_content)
+            .map_err(|e| ActrCliError::output_write_failed(&user_file_path, e))?;
 
         info!("📄 Generated user code scaffold: {:?}", user_file_path);
         scaffold_files.push(user_file_path);
@@ -387,25 +395,30 @@ impl LanguageGenerator for PythonGenerator {
     }
 
     async fn format_code(&self, context: &GenContext, files: &[PathBuf]) -> Result<()> {
-        // Check if black is available
-        if !command_exists("black") {
-            info!("💡 black not found, skipping code formatting");
-            info!("   Install with: pip3 install black");
+        // Check if the formatter (black unless [build.format.python] says otherwise) is available
+        let tool = context.format.tool("black");
+        if !command_exists(tool) {
+            info!("💡 {tool} not found, skipping code formatting");
+            if context.format.is_default_tool("black") {
+                info!("   Install with: pip3 install black");
+            }
             return Ok(());
         }
 
-        info!("🎨 Formatting Python code with black...");
+        info!("🎨 Formatting Python code with {tool}...");
 
         // Format all Python files in the output directory
-        let output = StdCommand::new("black")
-            .arg("--quiet")
+        let output = context
+            .format
+            .command(tool, "black", &["--quiet"])
             .arg(&context.output)
             .output()
-            .map_err(|e| ActrCliError::command_error(format!("Failed to run black: {e}")))?;
+            .await
+            .map_err(|e| ActrCliError::FormatFailed(format!("Failed to run {tool}: {e}")))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("⚠️  Black formatting encountered issues: {}", stderr);
+            warn!("⚠️  {tool} formatting encountered issues: {}", stderr);
             // Don't fail on formatting errors, just warn
             return Ok(());
         }
@@ -413,20 +426,22 @@ impl LanguageGenerator for PythonGenerator {
         // Also format scaffold file if it exists and is in the files list
         for file in files {
             if file.exists() && file.extension().is_some_and(|ext| ext == "py") {
-                let output = StdCommand::new("black")
-                    .arg("--quiet")
+                let output = context
+                    .format
+                    .command(tool, "black", &["--quiet"])
                     .arg(file)
                     .output()
+                    .await
                     .map_err(|e| {
-                        ActrCliError::command_error(format!(
-                            "Failed to run black on {:?}: {e}",
-                            file
+                        ActrCliError::FormatFailed(format!(
+                            "Failed to run {tool} on {}: {e}",
+                            file.display()
                         ))
                     })?;
 
                 if !output.status.success() {
                     let stderr = String::from_utf8_lossy(&output.stderr);
-                    warn!("⚠️  Black formatting failed for {:?}: {}", file, stderr);
+                    warn!("⚠️  {tool} formatting failed for {:?}: {}", file, stderr);
                 }
             }
         }
@@ -435,6 +450,22 @@ impl LanguageGenerator for PythonGenerator {
         Ok(())
     }
 
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        vec![
+            PROTOC_TOOL,
+            RequiredTool::required("python3", "Install Python 3 from https://www.python.org"),
+            RequiredTool::required(
+                "framework_codegen_python",
+                "pip install framework_codegen_python",
+            ),
+            RequiredTool::optional("black", "pip3 install black"),
+        ]
+    }
+
+    fn stage_names(&self) -> (&'static str, &'static str) {
+        ("black", "validate")
+    }
+
     async fn validate_code(&self, context: &GenContext) -> Result<()> {
         info!("🔍 Validating Python code...");
 
@@ -475,11 +506,13 @@ impl LanguageGenerator for PythonGenerator {
         // Validate each file using py_compile
         let mut failed_files = Vec::new();
         for file in &python_files {
-            let output = StdCommand::new(python_cmd)
+            let output = TokioCommand::new(python_cmd)
                 .arg("-m")
                 .arg("py_compile")
                 .arg(file)
+                .kill_on_drop(true)
                 .output()
+                .await
                 .map_err(|e| {
                     ActrCliError::command_error(format!("Failed to run python -m py_compile: {e}"))
                 })?;
@@ -507,38 +540,28 @@ impl LanguageGenerator for PythonGenerator {
     }
 
     fn print_next_steps(&self, context: &GenContext) {
-        println!("\n🎉 Python code generation completed!");
-        println!("\n📋 Next steps:");
-        println!("1. 📖 View generated code: {:?}", context.output);
-        println!("2. 📦 Add the output directory to PYTHONPATH:");
-        println!(
-            "   export PYTHONPATH=$PYTHONPATH:{}",
-            context.output.display()
+        let steps = [
+            tr!("gen.view_code", path = context.output.display()),
+            tr!("gen.python.pythonpath", path = context.output.display()),
+            tr!("gen.python.import"),
+        ];
+        print_steps(
+            &tr!("gen.completed_language", language = "Python"),
+            &steps,
+            Some(&tr!("gen.python.tip")),
         );
-        println!("3. 🐍 Import and use the generated modules in your Python code");
-        println!("\n💡 Tip: Consider using a virtual environment for your Python project");
     }
 }
 
 impl PythonGenerator {
     fn ensure_required_tools(&self) -> Result<()> {
-        let mut missing_tools = Vec::new();
-        for (tool, description) in REQUIRED_TOOLS {
-            if !command_exists(tool) {
-                missing_tools.push((tool, description));
-            }
-        }
-
-        if !missing_tools.is_empty() {
-            let mut error_msg = "Missing required tools:\n".to_string();
-            for (tool, description) in missing_tools {
-                error_msg.push_str(&format!("  - {tool} ({description})\n"));
-            }
-            error_msg.push_str("\nPlease install the missing tools and try again.");
-            return Err(ActrCliError::command_error(error_msg));
+        match REQUIRED_TOOLS
+            .iter()
+            .find(|tool| !command_exists(tool.name))
+        {
+            Some(tool) => Err(ActrCliError::tool_missing(tool.name, tool.install_hint)),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     fn should_overwrite_scaffold(&self, path: &Path) -> Result<bool> {
@@ -557,7 +580,7 @@ impl PythonGenerator {
         Ok(markers.iter().any(|marker| content.contains(marker)))
     }
 
-    fn parse_local_services(&self, context: &GenContext) -> Vec<ProtoService> {
+    async fn parse_local_services(&self, context: &GenContext) -> Result<Vec<ProtoService>> {
         let mut services = Vec::new();
 
         for proto_file_path in &context.proto_files {
@@ -566,90 +589,32 @@ impl PythonGenerator {
                 continue;
             }
 
-            if let Ok(content) = std::fs::read_to_string(proto_file_path) {
-                let mut current_package = String::new();
-                let mut current_service: Option<ProtoService> = None;
-
-                for line in content.lines() {
-                    let line = line.trim();
-
-                    // Parse package
-                    if line.starts_with("package ") {
-                        current_package = line
-                            .trim_start_matches("package ")
-                            .trim_end_matches(';')
-                            .trim()
-                            .to_string();
-                        continue;
-                    }
-
-                    // Parse service start
-                    if line.starts_with("service ") {
-                        let service_name = line
-                            .trim_start_matches("service ")
-                            .split_whitespace()
-                            .next()
-                            .unwrap_or("")
-                            .trim_end_matches('{')
-                            .trim()
-                            .to_string();
-
-                        if !service_name.is_empty() {
-                            current_service = Some(ProtoService {
-                                name: service_name,
-                                package: current_package.clone(),
-                                methods: Vec::new(),
-                            });
-                        }
-                        continue;
-                    }
-
-                    // Parse rpc method
-                    if let Some(ref mut service) = current_service {
-                        if line.starts_with("rpc ") {
-                            // rpc Echo(EchoRequest) returns (EchoResponse);
-                            let line = line.trim_start_matches("rpc ").trim();
-                            if let Some(name_end) = line.find('(') {
-                                let method_name = line[..name_end].trim().to_string();
-
-                                // Convert to snake_case
-                                let snake_name = to_snake_case(&method_name);
-
-                                let rest = &line[name_end + 1..];
-                                if let Some(input_end) = rest.find(')') {
-                                    let input_type = rest[..input_end].trim().to_string();
-
-                                    if let Some(returns_pos) = rest.find("returns") {
-                                        let rest = &rest[returns_pos + 7..];
-                                        if let Some(output_start) = rest.find('(')
-                                            && let Some(output_end) = rest.find(')')
-                                        {
-                                            let output_type = rest[output_start + 1..output_end]
-                                                .trim()
-                                                .to_string();
-
-                                            service.methods.push(ProtoMethod {
-                                                name: method_name,
-                                                snake_name,
-                                                input_type,
-                                                output_type,
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        if line.contains('}')
-                            && let Some(s) = current_service.take()
-                        {
-                            services.push(s);
-                        }
-                    }
+            let schema = DefaultProtoProcessor::compile_file(
+                proto_file_path,
+                &context.proto_path_args(&context.input_path),
+            )
+            .await?;
+            let package = schema.package.clone().unwrap_or_default();
+            services.extend(schema.services.into_iter().map(|service| {
+                ProtoService {
+                    name: service.name,
+                    package: package.clone(),
+                    methods: service
+                        .methods
+                        .into_iter()
+                        .map(|rpc| ProtoMethod {
+                            snake_name: to_snake_case(&rpc.name),
+                            name: rpc.name,
+                            input_type: rpc.input_type,
+                            output_type: rpc.output_type,
+                            client_streaming: rpc.client_streaming,
+                            server_streaming: rpc.server_streaming,
+                        })
+                        .collect(),
                 }
-            }
+            }));
         }
-        services
+        Ok(services)
     }
 
     fn generate_scaffold_content(
@@ -0,0 +1,1150 @@
+use crate::commands::codegen::diagnostics;
+use crate::commands::codegen::generated_crate::GeneratedCrate;
+use crate::commands::codegen::merge::{MergeOutcome, MethodStub, merge_block};
+use crate::commands::codegen::traits::{
+    GenContext, LanguageGenerator, PROTOC_TOOL, RequiredTool, print_steps,
+};
+use crate::core::{ConsoleProgressBar, DefaultProtoProcessor, ProgressBar};
+use crate::error::{ActrCliError, Result};
+use crate::plugin_config::{PluginRequirement, load_protoc_plugin_config};
+use crate::plugin_manager::{PluginInstaller, find_plugin};
+use crate::proto_parser::{ProtoSchema, RpcDef, ServiceDef};
+use crate::tr;
+use crate::utils::{to_pascal_case, to_snake_case};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+use std::sync::Arc;
+use tokio::process::Command as TokioCommand;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+const PLUGIN_NAME: &str = "protoc-gen-actrframework";
+const CARGO_TOOL: RequiredTool =
+    RequiredTool::required("cargo", "Install the Rust toolchain from https://rustup.rs");
+/// Plugin version required when `.protoc-plugin.toml` has no entry (same as actr-framework-protoc-codegen)
+const EXPECTED_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub struct RustGenerator;
+
+impl RustGenerator {
+    /// 确保 protoc-gen-actrframework 插件可用
+    ///
+    /// Plugin version policy:
+    /// 1. Check installed version first.
+    /// 2. If `.protoc-plugin.toml` defines a requirement (bare minimum version or semver
+    ///    range), accept any version that satisfies it.
+    /// 3. Otherwise, require an exact match with the CLI version.
+    /// 4. If missing or not satisfying the requirement, download a prebuilt release into
+    ///    `~/.actr/bin`, falling back to building from the actr workspace.
+    async fn ensure_protoc_plugin(&self, context: &GenContext) -> Result<PathBuf> {
+        let requirement = self.resolve_plugin_requirement(context)?;
+        let install_version = requirement
+            .minimum()
+            .unwrap_or(EXPECTED_VERSION)
+            .to_string();
+
+        // 1. Check installed version
+        let installed_version = self.check_installed_plugin_version()?;
+
+        match installed_version {
+            Some(version) if requirement.matches(&version) => {
+                // Version matches, use it directly
+                info!("✅ Using installed protoc-gen-actrframework v{}", version);
+                find_plugin(PLUGIN_NAME).ok_or_else(|| {
+                    ActrCliError::command_error(format!("Failed to locate {PLUGIN_NAME}"))
+                })
+            }
+            Some(version) => {
+                // Version mismatch, upgrade needed
+                info!(
+                    "🔄 Installed protoc-gen-actrframework v{} does not satisfy '{}'",
+                    version, requirement
+                );
+                info!("🔨 Upgrading plugin...");
+                let path = self
+                    .install_plugin_unless_offline(context, &install_version)
+                    .await?;
+                self.ensure_required_plugin_version(&requirement)?;
+                Ok(path)
+            }
+            None => {
+                // Not installed, install it
+                info!("📦 protoc-gen-actrframework not found, installing...");
+                let path = self
+                    .install_plugin_unless_offline(context, &install_version)
+                    .await?;
+                self.ensure_required_plugin_version(&requirement)?;
+                Ok(path)
+            }
+        }
+    }
+
+    /// Check installed plugin version
+    fn check_installed_plugin_version(&self) -> Result<Option<String>> {
+        let Some(plugin_path) = find_plugin(PLUGIN_NAME) else {
+            debug!("Plugin not found in ~/.actr/bin or PATH");
+            return Ok(None);
+        };
+        let output = StdCommand::new(plugin_path).arg("--version").output();
+
+        match output {
+            Ok(output) if output.status.success() => {
+                let version_info = String::from_utf8_lossy(&output.stdout);
+                // Parse "protoc-gen-actrframework 0.1.0"
+                let version = version_info
+                    .lines()
+                    .next()
+                    .and_then(|line| line.split_whitespace().nth(1))
+                    .map(|v| v.to_string());
+
+                debug!("Detected installed version: {:?}", version);
+                Ok(version)
+            }
+            _ => {
+                debug!("Plugin not found in PATH");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Install the plugin, or fail when offline mode forbids it
+    ///
+    /// A prebuilt release is tried first; building from the actr workspace is the fallback.
+    async fn install_plugin_unless_offline(
+        &self,
+        context: &GenContext,
+        version: &str,
+    ) -> Result<PathBuf> {
+        if context.offline {
+            return Err(ActrCliError::command_error(format!(
+                "Offline mode: {PLUGIN_NAME} is missing or outdated and cannot be installed. \
+                Install it while online or rerun without --offline."
+            )));
+        }
+
+        let download_error = match PluginInstaller::from_config(&context.config_path)?
+            .install(PLUGIN_NAME, version)
+            .await
+        {
+            Ok(path) => return Ok(path),
+            Err(e) => e,
+        };
+        warn!("Prebuilt {PLUGIN_NAME} download failed: {download_error}");
+
+        self.install_or_upgrade_plugin().map_err(|build_error| {
+            ActrCliError::command_error(format!(
+                "Could not install {PLUGIN_NAME} v{version}.\n\
+                 Download failed: {download_error}\n\
+                 Workspace build failed: {build_error}"
+            ))
+        })
+    }
+
+    /// Install or upgrade plugin from workspace
+    fn install_or_upgrade_plugin(&self) -> Result<PathBuf> {
+        // Find actr workspace
+        let current_dir = std::env::current_dir()?;
+        let workspace_root = current_dir.ancestors().find(|p| {
+            let is_workspace =
+                p.join("Cargo.toml").exists() && p.join("crates/framework-protoc-codegen").exists();
+            if is_workspace {
+                debug!("Found workspace root: {:?}", p);
+            }
+            is_workspace
+        });
+
+        let workspace_root = workspace_root.ok_or_else(|| {
+            ActrCliError::command_error(
+                "Cannot find actr workspace.\n\
+                 Please run this command from within an actr project or workspace.",
+            )
+        })?;
+
+        info!("🔍 Found actr workspace at: {}", workspace_root.display());
+
+        // Step 1: Build the plugin
+        info!("🔨 Building protoc-gen-actrframework...");
+        let mut build_cmd = StdCommand::new("cargo");
+        build_cmd
+            .arg("build")
+            .arg("-p")
+            .arg("actr-framework-protoc-codegen")
+            .arg("--bin")
+            .arg(PLUGIN_NAME)
+            .current_dir(workspace_root);
+
+        debug!("Running: {:?}", build_cmd);
+        let output = build_cmd.output().map_err(|e| CARGO_TOOL.spawn_error(e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(ActrCliError::command_error(format!(
+                "Failed to build plugin:\n{stderr}"
+            )));
+        }
+
+        // Step 2: Install to ~/.cargo/bin/
+        info!("📦 Installing to ~/.cargo/bin/...");
+        let mut install_cmd = StdCommand::new("cargo");
+        install_cmd
+            .arg("install")
+            .arg("--path")
+            .arg(workspace_root.join("crates/framework-protoc-codegen"))
+            .arg("--bin")
+            .arg(PLUGIN_NAME)
+            .arg("--force"); // Overwrite existing version
+
+        debug!("Running: {:?}", install_cmd);
+        let output = install_cmd
+            .output()
+            .map_err(|e| CARGO_TOOL.spawn_error(e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(ActrCliError::command_error(format!(
+                "Failed to install plugin:\n{stderr}"
+            )));
+        }
+
+        info!("✅ Plugin installed successfully");
+
+        // Return the installed path
+        let which_output = StdCommand::new("which")
+            .arg(PLUGIN_NAME)
+            .output()
+            .map_err(|e| {
+                ActrCliError::command_error(format!("Failed to locate installed plugin: {e}"))
+            })?;
+
+        let path = String::from_utf8_lossy(&which_output.stdout)
+            .trim()
+            .to_string();
+        Ok(PathBuf::from(path))
+    }
+
+    /// Requirement from `.protoc-plugin.toml`, or an exact match with the CLI version
+    fn resolve_plugin_requirement(&self, context: &GenContext) -> Result<PluginRequirement> {
+        let config = load_protoc_plugin_config(&context.config_path)?;
+        if let Some(config) = config
+            && let Some(requirement) = config.requirement(PLUGIN_NAME)
+        {
+            info!(
+                "🔧 Using version requirement '{}' for {} from {}",
+                requirement,
+                PLUGIN_NAME,
+                config.path().display()
+            );
+            return Ok(requirement.clone());
+        }
+        PluginRequirement::parse(&format!("={EXPECTED_VERSION}"))
+    }
+
+    fn ensure_required_plugin_version(&self, requirement: &PluginRequirement) -> Result<()> {
+        let installed_version = self.check_installed_plugin_version()?;
+        let Some(installed_version) = installed_version else {
+            return Err(ActrCliError::command_error(
+                "Failed to determine installed protoc-gen-actrframework version after install"
+                    .to_string(),
+            ));
+        };
+
+        if requirement.matches(&installed_version) {
+            return Ok(());
+        }
+
+        Err(ActrCliError::command_error(format!(
+            "protoc-gen-actrframework v{installed_version} does not satisfy '{requirement}'.\n\
+             Install a matching version (e.g. `cargo install actr-framework-protoc-codegen --version {}`) \
+             or adjust the requirement in .protoc-plugin.toml.",
+            requirement.minimum().unwrap_or(EXPECTED_VERSION)
+        )))
+    }
+
+    /// 列出输出目录中的 .rs 文件（不递归）
+    fn list_generated_rs_files(&self, output: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(output).map_err(ActrCliError::Io)? {
+            let entry = entry.map_err(ActrCliError::Io)?;
+            let path = entry.path();
+
+            if path.is_file() && path.extension().unwrap_or_default() == "rs" {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// 生成 mod.rs 文件
+    fn generate_mod_rs(&self, output: &Path) -> Result<PathBuf> {
+        let mod_path = output.join("mod.rs");
+
+        // 扫描实际生成的文件，而不是根据 proto 文件名猜测
+        let mut proto_modules = Vec::new();
+        let mut service_modules = Vec::new();
+
+        for path in self.list_generated_rs_files(output)? {
+            if let Some(file_name) = path.file_stem().and_then(|s| s.to_str()) {
+                // 跳过 mod.rs 本身
+                if file_name == "mod" {
+                    continue;
+                }
+
+                // 区分 service_actor 文件和 proto 文件
+                if file_name.ends_with("_service_actor") {
+                    service_modules.push(format!("pub mod {file_name};"));
+                } else {
+                    proto_modules.push(format!("pub mod {file_name};"));
+                }
+            }
+        }
+
+        let mod_content = format!(
+            r#"//! Automatically generated code module
+//!
+//! This module is automatically generated by the `actr gen` command, including:
+//! - protobuf message type definitions
+//! - Actor framework code (router, traits)
+//!
+//! ⚠️ Do not manually modify files in this directory
+
+// Protobuf message types (generated by prost)
+{}
+
+// Actor framework code (generated by protoc-gen-actrframework)
+{}
+
+// Common types are defined in their respective modules, please import as needed
+"#,
+            proto_modules.join("\n"),
+            service_modules.join("\n"),
+        );
+
+        std::fs::write(&mod_path, mod_content)
+            .map_err(|e| ActrCliError::output_write_failed(&mod_path, e))?;
+
+        debug!("Generated mod.rs: {:?}", mod_path);
+        Ok(mod_path)
+    }
+
+    /// 将生成目录中的文件设置为只读
+    fn set_generated_files_readonly(&self, output: &Path) -> Result<()> {
+        use std::fs;
+
+        for path in self.list_generated_rs_files(output)? {
+            // 获取当前权限
+            let metadata =
+                fs::metadata(&path).map_err(|e| ActrCliError::output_write_failed(&path, e))?;
+            let mut permissions = metadata.permissions();
+
+            // 设置只读（移除写权限）
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = permissions.mode();
+                permissions.set_mode(mode & !0o222); // 移除所有写权限
+            }
+
+            #[cfg(not(unix))]
+            {
+                permissions.set_readonly(true);
+            }
+
+            fs::set_permissions(&path, permissions)
+                .map_err(|e| ActrCliError::output_write_failed(&path, e))?;
+
+            debug!("Set read-only attribute: {:?}", path);
+        }
+
+        Ok(())
+    }
+
+    /// Generate scaffold for a specific service
+    fn generate_service_scaffold(
+        &self,
+        context: &GenContext,
+        service: &ServiceDef,
+        proto_module: &str,
+    ) -> Result<Option<PathBuf>> {
+        // With --as-crate the scaffolds stay in the app crate
+        let user_dir = match &context.generated_crate {
+            Some(_) => Path::new("src"),
+            None => context.output.parent().unwrap_or_else(|| Path::new("src")),
+        };
+        let user_file_path = user_dir.join(format!("{}.rs", scaffold_file_stem(&service.name)));
+
+        if user_file_path.exists() && context.merge_scaffold {
+            return self.merge_service_scaffold(&user_file_path, service);
+        }
+
+        // If file exists and overwrite is not forced, skip
+        if user_file_path.exists() && !context.overwrite_user_code {
+            info!("⏭️  Skipping existing user code file: {:?}", user_file_path);
+            return Ok(None);
+        }
+
+        let generated_module = context
+            .generated_crate
+            .as_ref()
+            .map(GeneratedCrate::module_path)
+            .unwrap_or_else(|| "crate::generated".to_string());
+        let scaffold_content = generate_scaffold_content(service, proto_module, &generated_module);
+
+        std::fs::write(&user_file_path, scaffold_content)
+            .map_err(|e| ActrCliError::output_write_failed(&user_file_path, e))?;
+
+        info!("📄 Generated user code scaffold: {:?}", user_file_path);
+        Ok(Some(user_file_path))
+    }
+
+    /// Append stubs for new RPCs to an existing scaffold (`--merge-scaffold`)
+    fn merge_service_scaffold(
+        &self,
+        user_file_path: &Path,
+        service: &ServiceDef,
+    ) -> Result<Option<PathBuf>> {
+        let existing = std::fs::read_to_string(user_file_path).map_err(ActrCliError::Io)?;
+        let Some(outcome) = merge_scaffold_content(&existing, service) else {
+            warn!(
+                "⚠️  No `impl {}Handler for` block in {:?}; not merging",
+                to_pascal_case(&service.name),
+                user_file_path
+            );
+            return Ok(None);
+        };
+        if outcome.added.is_empty() {
+            info!("✅ {:?} already implements every RPC", user_file_path);
+            return Ok(None);
+        }
+
+        std::fs::write(user_file_path, outcome.source)
+            .map_err(|e| ActrCliError::output_write_failed(user_file_path, e))?;
+        info!(
+            "🧩 Added {} to {:?}",
+            outcome.added.join(", "),
+            user_file_path
+        );
+        Ok(Some(user_file_path.to_path_buf()))
+    }
+
+    /// 查找项目根目录（包含 Cargo.toml 的目录）
+    fn find_project_root(&self) -> Result<PathBuf> {
+        let mut current = std::env::current_dir().map_err(ActrCliError::Io)?;
+
+        loop {
+            if current.join("Cargo.toml").exists() {
+                return Ok(current);
+            }
+
+            match current.parent() {
+                Some(parent) => current = parent.to_path_buf(),
+                None => break,
+            }
+        }
+
+        // 如果找不到 Cargo.toml，回退到当前目录
+        std::env::current_dir().map_err(ActrCliError::Io)
+    }
+}
+
+/// `edition` of the package (or workspace) in `project_root/Cargo.toml`, else 2024
+fn cargo_edition(project_root: &Path) -> String {
+    std::fs::read_to_string(project_root.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|manifest| {
+            let package = manifest.get("package").and_then(|p| p.get("edition"));
+            let workspace = manifest
+                .get("workspace")
+                .and_then(|w| w.get("package"))
+                .and_then(|p| p.get("edition"));
+            package
+                .and_then(toml::Value::as_str)
+                .or_else(|| workspace.and_then(toml::Value::as_str))
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "2024".to_string())
+}
+
+/// Whether rustfmt picks up a `rustfmt.toml` / `.rustfmt.toml` for files in `dir`
+fn has_rustfmt_config(dir: &Path) -> bool {
+    let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+    dir.ancestors().any(|ancestor| {
+        ["rustfmt.toml", ".rustfmt.toml"]
+            .iter()
+            .any(|name| ancestor.join(name).is_file())
+    })
+}
+
+#[async_trait]
+impl LanguageGenerator for RustGenerator {
+    async fn generate_infrastructure(&self, context: &GenContext) -> Result<Vec<PathBuf>> {
+        info!("🔧 Generating infrastructure code...");
+
+        // 确保 protoc 插件可用
+        let plugin_path = self.ensure_protoc_plugin(context).await?;
+
+        let manufacturer = context.config.package.actr_type.manufacturer.clone();
+        debug!("Using manufacturer from Actr.toml: {}", manufacturer);
+
+        let output = &context.output;
+
+        // protoc 按文件并发执行（受 --jobs 限制），汇总每个文件的错误
+        let semaphore = Arc::new(Semaphore::new(context.jobs.max(1)));
+        let mut tasks = JoinSet::new();
+        for proto_file in context.proto_files.clone() {
+            let semaphore = semaphore.clone();
+            let proto_paths = context.proto_path_args(&context.input_path);
+            let output = output.clone();
+            let plugin_path = plugin_path.clone();
+            let manufacturer = manufacturer.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let result = run_protoc_passes(
+                    &proto_file,
+                    &proto_paths,
+                    &output,
+                    &plugin_path,
+                    &manufacturer,
+                )
+                .await;
+                (proto_file, result)
+            });
+        }
+
+        let total = context.proto_files.len();
+        let progress = ConsoleProgressBar::new(
+            &format!("Running protoc for {total} proto files"),
+            context.quiet,
+        );
+        let mut completed = 0;
+        let mut failures = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (proto_file, result) = joined
+                .map_err(|e| ActrCliError::command_error(format!("protoc task panicked: {e}")))?;
+            completed += 1;
+            progress.set_message(&format!("protoc {}", proto_file.display()));
+            progress.update(completed as f64 / total as f64);
+            if let Err(error) = result {
+                failures.push((proto_file, error));
+            }
+        }
+        progress.finish();
+
+        if !failures.is_empty() {
+            failures.sort_by(|a, b| a.0.cmp(&b.0));
+            // A missing protoc or plugin fails every file the same way
+            if let Some(index) = failures
+                .iter()
+                .position(|(_, error)| !matches!(error, ActrCliError::ProtocFailed { .. }))
+            {
+                return Err(failures.swap_remove(index).1);
+            }
+            if failures.len() == 1 {
+                return Err(failures.remove(0).1);
+            }
+            let details: Vec<String> = failures
+                .into_iter()
+                .map(|(_, error)| match error {
+                    ActrCliError::ProtocFailed { file, stderr } => format!("{file}: {stderr}"),
+                    other => other.to_string(),
+                })
+                .collect();
+            return Err(ActrCliError::ProtocFailed {
+                file: format!("{} of {total} proto files", details.len()),
+                stderr: details.join("\n"),
+            });
+        }
+
+        // 生成 mod.rs
+        self.generate_mod_rs(output)?;
+
+        let mut generated_files = self.list_generated_rs_files(output)?;
+        if let Some(generated_crate) = &context.generated_crate {
+            let edition = cargo_edition(&self.find_project_root()?);
+            generated_files.push(generated_crate.prepare(Path::new("Cargo.toml"), &edition)?);
+        }
+        info!("✅ Infrastructure code generation completed");
+        Ok(generated_files)
+    }
+
+    async fn generate_scaffold(&self, context: &GenContext) -> Result<Vec<PathBuf>> {
+        if context.no_scaffold {
+            return Ok(vec![]);
+        }
+
+        info!("📝 Generating user code scaffold...");
+
+        let mut generated_files = Vec::new();
+        for proto_file in &context.proto_files {
+            let schema = DefaultProtoProcessor::compile_file(
+                proto_file,
+                &context.proto_path_args(&context.input_path),
+            )
+            .await?;
+            let proto_module = proto_module_name(&schema, proto_file);
+
+            // One scaffold per `service` definition; message-only protos get none
+            for service in &schema.services {
+                if let Some(path) =
+                    self.generate_service_scaffold(context, service, &proto_module)?
+                {
+                    generated_files.push(path);
+                }
+            }
+        }
+
+        info!("✅ User code scaffold generation completed");
+        Ok(generated_files)
+    }
+
+    async fn format_code(&self, context: &GenContext, files: &[PathBuf]) -> Result<()> {
+        info!("🎨 Formatting generated code...");
+
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        // Like `cargo fmt`: the crate's edition, and the project's rustfmt.toml when present
+        let project_root = self.find_project_root()?;
+        let edition = cargo_edition(&project_root);
+        let mut defaults = vec!["--edition", edition.as_str()];
+        if !has_rustfmt_config(&context.output) {
+            defaults.extend(["--config", "max_width=100"]);
+        }
+        let tool = context.format.tool("rustfmt");
+        let mut cmd = context.format.command(tool, "rustfmt", &defaults);
+        cmd.args(files);
+
+        let output = cmd
+            .output()
+            .await
+            .map_err(|e| ActrCliError::FormatFailed(format!("Failed to execute {tool}: {e}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            warn!("{tool} execution warning: {}", stderr);
+        } else {
+            info!("✅ Code formatting completed");
+        }
+
+        Ok(())
+    }
+
+    fn toolchain_version(&self) -> Option<String> {
+        self.check_installed_plugin_version().ok().flatten()
+    }
+
+    fn supports_incremental(&self) -> bool {
+        true
+    }
+
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        vec![
+            PROTOC_TOOL,
+            RequiredTool::required("protoc-gen-prost", "cargo install protoc-gen-prost"),
+            RequiredTool::required(
+                PLUGIN_NAME,
+                "Run `actr gen` once to install it, or `cargo install actr-framework-protoc-codegen`",
+            ),
+            CARGO_TOOL,
+            RequiredTool::optional("rustfmt", "rustup component add rustfmt"),
+        ]
+    }
+
+    fn stage_names(&self) -> (&'static str, &'static str) {
+        ("rustfmt", "cargo check")
+    }
+
+    async fn validate_code(&self, _context: &GenContext) -> Result<()> {
+        info!("🔍 Validating generated code...");
+
+        let project_root = self.find_project_root()?;
+
+        let mut cmd = TokioCommand::new("cargo");
+        cmd.arg("check")
+            .arg("--quiet")
+            .arg("--message-format=json")
+            .current_dir(&project_root)
+            .kill_on_drop(true);
+
+        let output = cmd.output().await.map_err(|e| CARGO_TOOL.spawn_error(e))?;
+
+        if !output.status.success() {
+            let found = diagnostics::from_cargo_json(
+                &String::from_utf8_lossy(&output.stdout),
+                &project_root,
+            );
+            if found.is_empty() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                warn!(
+                    "Generated code has compilation warnings or errors:\n{}",
+                    stderr
+                );
+            } else {
+                warn!(
+                    "Generated code has {} compilation warning(s) or error(s)",
+                    found.len()
+                );
+                diagnostics::report(&found);
+            }
+            info!("💡 This is usually normal because the user code scaffold contains TODO markers");
+        } else {
+            info!("✅ Code validation passed");
+        }
+
+        Ok(())
+    }
+
+    async fn finalize(&self, context: &GenContext) -> Result<()> {
+        // Only lock files once rustfmt and cargo check are done with them
+        self.set_generated_files_readonly(&context.output)
+    }
+
+    fn print_next_steps(&self, context: &GenContext) {
+        let mut steps = vec![tr!("gen.view_code", path = context.output.display())];
+        if !context.no_scaffold {
+            steps.push(tr!("gen.rust.implement"));
+            steps.push(tr!("gen.rust.add_dependencies"));
+        }
+        steps.push(tr!("gen.rust.build"));
+        steps.push(tr!("gen.rust.test"));
+        steps.push(tr!("gen.rust.run"));
+        print_steps(
+            &tr!("gen.completed"),
+            &steps,
+            Some(&tr!("gen.tip_user_guide")),
+        );
+    }
+}
+
+/// Run the prost and actrframework protoc passes for one proto file
+async fn run_protoc_passes(
+    proto_file: &Path,
+    proto_paths: &[String],
+    output: &Path,
+    plugin_path: &Path,
+    manufacturer: &str,
+) -> Result<()> {
+    debug!("Processing proto file: {:?}", proto_file);
+
+    // 第一步：使用 prost 生成基础 protobuf 消息类型
+    let mut cmd = TokioCommand::new("protoc");
+    cmd.args(proto_paths)
+        .arg("--prost_opt=flat_output_dir")
+        .arg(format!("--prost_out={}", output.display()))
+        .arg(proto_file)
+        .kill_on_drop(true);
+
+    debug!("Executing protoc (prost): {:?}", cmd);
+    let output_cmd = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
+
+    if !output_cmd.status.success() {
+        let stderr = String::from_utf8_lossy(&output_cmd.stderr);
+        return Err(ActrCliError::protoc_failed(proto_file.display(), stderr));
+    }
+
+    // 第二步：使用 actrframework 插件生成 Actor 框架代码
+    let mut cmd = TokioCommand::new("protoc");
+    cmd.args(proto_paths)
+        .arg(format!(
+            "--plugin=protoc-gen-actrframework={}",
+            plugin_path.display()
+        ))
+        .arg(format!("--actrframework_opt=manufacturer={manufacturer}"))
+        .arg(format!("--actrframework_out={}", output.display()))
+        .arg(proto_file)
+        .kill_on_drop(true);
+
+    debug!("Executing protoc (actrframework): {:?}", cmd);
+    let output_cmd = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
+
+    if !output_cmd.status.success() {
+        let stderr = String::from_utf8_lossy(&output_cmd.stderr);
+        return Err(ActrCliError::protoc_failed(proto_file.display(), stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output_cmd.stdout);
+    if !stdout.is_empty() {
+        debug!("protoc output: {}", stdout);
+    }
+    Ok(())
+}
+
+/// Scaffold file of a service: `EchoService` → `echo_service`, `Greeter` → `greeter_service`
+fn scaffold_file_stem(service_name: &str) -> String {
+    let snake = to_snake_case(service_name);
+    if snake.ends_with("_service") || snake == "service" {
+        snake
+    } else {
+        format!("{snake}_service")
+    }
+}
+
+/// Module holding a proto's prost types inside `generated/` (named after its package)
+fn proto_module_name(schema: &ProtoSchema, proto_file: &Path) -> String {
+    schema
+        .package
+        .clone()
+        .or_else(|| {
+            proto_file
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_default()
+        .replace('.', "_")
+}
+
+/// Rust type of an RPC input or output, relative to the proto's module
+fn rust_type(type_name: &str) -> String {
+    let trimmed = type_name.trim_start_matches('.');
+    if trimmed == "google.protobuf.Empty" {
+        return "()".to_string();
+    }
+    // Leading lowercase segments are packages; nested messages live in snake_case modules
+    let segments: Vec<&str> = trimmed
+        .split('.')
+        .skip_while(|segment| segment.starts_with(|c: char| c.is_ascii_lowercase()))
+        .collect();
+    match segments.split_last() {
+        Some((name, parents)) => parents
+            .iter()
+            .map(|parent| to_snake_case(parent))
+            .chain(std::iter::once(name.to_string()))
+            .collect::<Vec<_>>()
+            .join("::"),
+        None => trimmed.rsplit('.').next().unwrap_or(trimmed).to_string(),
+    }
+}
+
+/// Doc comment lines of an RPC, indented for the handler block
+fn rpc_doc(rpc: &RpcDef) -> String {
+    let mut doc = String::new();
+    for line in rpc.comment.iter().flat_map(|comment| comment.lines()) {
+        let line = line.trim();
+        if line.is_empty() {
+            doc.push_str("    ///\n");
+        } else {
+            doc.push_str(&format!("    /// {line}\n"));
+        }
+    }
+    doc
+}
+
+/// Typed handler method of a unary RPC; streaming RPCs have none
+fn handler_stub(service: &ServiceDef, rpc: &RpcDef) -> Option<MethodStub> {
+    if rpc.client_streaming || rpc.server_streaming {
+        return None;
+    }
+    let name = to_snake_case(&rpc.name);
+    let code = format!(
+        "{}    async fn {name}<C: Context>(&self, req: {}, ctx: &C) -> ActorResult<{}> {{\n        let _ = (req, ctx);\n        todo!(\"Implement {}.{}\")\n    }}\n",
+        rpc_doc(rpc),
+        rust_type(&rpc.input_type),
+        rust_type(&rpc.output_type),
+        service.name,
+        rpc.name
+    );
+    Some(MethodStub { name, code })
+}
+
+/// Handler method stubs for every unary RPC of a service
+fn handler_methods(service: &ServiceDef) -> String {
+    let mut methods = Vec::new();
+    for rpc in &service.methods {
+        match handler_stub(service, rpc) {
+            Some(stub) => methods.push(stub.code),
+            None => methods.push(format!(
+                "{}    // TODO: `{}` is a streaming RPC; implement it through the stream APIs of the context\n",
+                rpc_doc(rpc),
+                rpc.name
+            )),
+        }
+    }
+    methods.join("\n")
+}
+
+/// Add the handler methods of RPCs missing from an existing scaffold
+///
+/// Returns `None` when the file has no `impl <Service>Handler for` block.
+fn merge_scaffold_content(existing: &str, service: &ServiceDef) -> Option<MergeOutcome> {
+    let stubs: Vec<MethodStub> = service
+        .methods
+        .iter()
+        .filter_map(|rpc| handler_stub(service, rpc))
+        .map(|stub| MethodStub {
+            code: format!(
+                "    // TODO: new RPC, added by `actr gen --merge-scaffold`\n{}",
+                stub.code
+            ),
+            ..stub
+        })
+        .collect();
+    let header = format!("impl {}Handler for", to_pascal_case(&service.name));
+    merge_block(existing, &header, "fn", &stubs)
+}
+
+/// 生成用户代码框架内容
+/// Scaffold of one service; `generated_module` is where the generated code is imported
+/// from (`crate::generated`, or `<crate>::generated` with --as-crate)
+fn generate_scaffold_content(
+    service: &ServiceDef,
+    proto_module: &str,
+    generated_module: &str,
+) -> String {
+    let service_name_pascal = to_pascal_case(&service.name);
+    // `EchoService` is implemented by `MyEchoService`
+    let impl_name = format!(
+        "My{}Service",
+        service_name_pascal
+            .strip_suffix("Service")
+            .unwrap_or(&service_name_pascal)
+    );
+    let handler_methods = handler_methods(service);
+    let rpc_list = if service.methods.is_empty() {
+        "//! The service defines no RPCs yet.".to_string()
+    } else {
+        service
+            .methods
+            .iter()
+            .map(|rpc| format!("//! - `{}`", rpc.name))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(
+        r#"//! # {service_name_pascal} user business logic implementation
+//!
+//! This file is a user code scaffold automatically generated by the `actr gen` command.
+//! Please implement your specific business logic here.
+//!
+//! RPCs of `{service_name_pascal}`:
+{rpc_list}
+
+use {generated_module}::{proto_module}::*;
+use {generated_module}::{{{service_name_pascal}Handler, {service_name_pascal}Actor}};
+// 只导入必要的类型，避免拉入不需要的依赖如 sqlite
+use actr_framework::{{ActorResult, Context}};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Specific implementation of the {service_name_pascal} service
+///
+/// TODO: Add state fields you need, for example:
+/// - Database connection pool
+/// - Configuration information
+/// - Cache client
+/// - Logger, etc.
+pub struct {impl_name} {{
+    // TODO: Add your service state fields
+    // For example:
+    // pub db_pool: Arc<DatabasePool>,
+    // pub config: Arc<ServiceConfig>,
+    // pub metrics: Arc<Metrics>,
+}}
+
+impl {impl_name} {{
+    /// Create a new service instance
+    ///
+    /// TODO: Modify constructor parameters as needed
+    pub fn new(/* TODO: Add necessary dependencies */) -> Self {{
+        Self {{
+            // TODO: Initialize your fields
+        }}
+    }}
+
+    /// Create a service instance with default configuration (for testing)
+    pub fn default_for_testing() -> Self {{
+        Self {{
+            // TODO: Provide default values for testing
+        }}
+    }}
+}}
+
+// TODO: Replace the `todo!()` bodies with real business logic:
+// 1. Validate input
+// 2. Execute business logic
+// 3. Return result
+#[async_trait]
+impl {service_name_pascal}Handler for {impl_name} {{
+{handler_methods}}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[tokio::test]
+    async fn test_service_creation() {{
+        let _service = {impl_name}::default_for_testing();
+        // TODO: Add your tests
+    }}
+
+    // TODO: Add more test cases
+}}
+
+/*
+📚 User Guide
+
+## 🚀 Quick Start
+
+1. **Implement business logic**:
+   Implement all methods of the `{service_name_pascal}Handler` trait in `{impl_name}`
+
+2. **Add dependencies**:
+   Add dependencies you need in `Cargo.toml`, such as database clients, HTTP clients, etc.
+
+3. **Configure service**:
+   Modify the `new()` constructor to inject necessary dependencies
+
+4. **Start service**:
+   ```rust
+   #[tokio::main]
+   async fn main() -> ActorResult<()> {{
+       let service = {impl_name}::new(/* dependencies */);
+
+       ActorSystem::new()
+           .attach({service_name_pascal}Actor::new(service))
+           .start()
+           .await
+   }}
+   ```
+
+## 🔧 Development Tips
+
+- Use `tracing` crate for logging
+- Implement error handling and retry logic
+- Add unit and integration tests
+- Consider using configuration files for environment variables
+- Implement health checks and metrics collection
+
+## 📖 More Resources
+
+- Actor-RTC Documentation: [Link]
+- API Reference: [Link]
+- Example Projects: [Link]
+*/
+"# // Service in example code
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::codegen::descriptor;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rustfmt_settings_follow_the_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert_eq!(cargo_edition(root), "2024");
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"echo\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        assert_eq!(cargo_edition(root), "2021");
+
+        let output = root.join("src/generated");
+        std::fs::create_dir_all(&output).unwrap();
+        assert!(!has_rustfmt_config(&output));
+        std::fs::write(root.join("rustfmt.toml"), "max_width = 120\n").unwrap();
+        assert!(has_rustfmt_config(&output));
+    }
+
+    #[test]
+    fn test_generate_mod_rs_groups_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path();
+        std::fs::write(output.join("echo.rs"), "").unwrap();
+        std::fs::write(output.join("echo_service_actor.rs"), "").unwrap();
+
+        let mod_path = RustGenerator.generate_mod_rs(output).unwrap();
+        let content = std::fs::read_to_string(mod_path).unwrap();
+
+        assert!(content.contains("pub mod echo;"));
+        assert!(content.contains("pub mod echo_service_actor;"));
+        assert!(!content.contains("pub mod mod;"));
+    }
+
+    #[test]
+    fn test_scaffold_content_uses_pascal_case() {
+        let schema = ProtoSchema::parse("service user_profile {}").unwrap();
+        let content = generate_scaffold_content(&schema.services[0], "user", "crate::generated");
+        assert!(content.contains("pub struct MyUserProfileService"));
+        assert!(content.contains("UserProfileHandler"));
+    }
+
+    #[test]
+    fn test_scaffold_per_service_with_typed_methods() {
+        let schema = ProtoSchema::parse(
+            r#"syntax = "proto3";
+package acme.chat;
+message SendRequest { string text = 1; }
+message SendResponse { bool ok = 1; }
+message Room { message Info { string name = 1; } }
+service ChatService {
+  // Post a message to the room
+  rpc Send(SendRequest) returns (SendResponse);
+  rpc Watch(SendRequest) returns (stream SendResponse);
+}
+service Rooms {
+  rpc GetRoom(.acme.chat.SendRequest) returns (Room.Info);
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(schema.services.len(), 2);
+        assert_eq!(scaffold_file_stem("ChatService"), "chat_service");
+        assert_eq!(scaffold_file_stem("Rooms"), "rooms_service");
+        assert_eq!(
+            proto_module_name(&schema, Path::new("protos/chat.proto")),
+            "acme_chat"
+        );
+
+        let chat = generate_scaffold_content(&schema.services[0], "acme_chat", "crate::generated");
+        assert!(chat.contains("use crate::generated::acme_chat::*;"));
+        assert!(chat.contains("impl ChatServiceHandler for MyChatService"));
+        assert!(chat.contains("    /// Post a message to the room\n"));
+        assert!(chat.contains(
+            "async fn send<C: Context>(&self, req: SendRequest, ctx: &C) -> ActorResult<SendResponse>"
+        ));
+        assert!(chat.contains("`Watch` is a streaming RPC"));
+
+        // A new RPC is appended to the user's implementation, leaving their code alone
+        let edited = chat.replace(
+            "let _ = (req, ctx);\n        todo!(\"Implement ChatService.Send\")",
+            "Ok(SendResponse { ok: true })",
+        );
+        let grown = ProtoSchema::parse(
+            "service ChatService { rpc Send(SendRequest) returns (SendResponse); rpc Leave(SendRequest) returns (SendResponse); }",
+        )
+        .unwrap();
+        let merged = merge_scaffold_content(&edited, &grown.services[0]).unwrap();
+        assert_eq!(merged.added, vec!["leave".to_string()]);
+        assert!(merged.source.contains("Ok(SendResponse { ok: true })"));
+        assert!(
+            merged
+                .source
+                .contains("todo!(\"Implement ChatService.Leave\")")
+        );
+
+        let rooms = generate_scaffold_content(&schema.services[1], "acme_chat", "crate::generated");
+        assert!(rooms.contains(
+            "async fn get_room<C: Context>(&self, req: SendRequest, ctx: &C) -> ActorResult<room::Info>"
+        ));
+    }
+
+    #[test]
+    fn test_scaffold_from_descriptor_streaming_rpcs() {
+        let schema = descriptor::schema_from_descriptor(&descriptor::tests::echo_descriptor());
+        let content = generate_scaffold_content(&schema.services[0], "echo", "crate::generated");
+        assert!(content.contains(
+            "async fn echo<C: Context>(&self, req: EchoRequest, ctx: &C) -> ActorResult<outer::Inner>"
+        ));
+        assert!(content.contains("`Upload` is a streaming RPC"));
+        assert!(content.contains("`Chat` is a streaming RPC"));
+    }
+}
@@ -1,6 +1,12 @@
-use crate::commands::codegen::traits::{GenContext, LanguageGenerator};
+use crate::commands::codegen::merge::{MethodStub, merge_block};
+use crate::commands::codegen::traits::{
+    GenContext, LanguageGenerator, PROTOC_TOOL, RequiredTool, print_steps,
+};
+use crate::core::DefaultProtoProcessor;
 use crate::error::{ActrCliError, Result};
-use crate::plugin_config::{compare_versions, load_protoc_plugin_config, version_is_at_least};
+use crate::plugin_config::{PluginRequirement, compare_versions, load_protoc_plugin_config};
+use crate::plugin_manager::{PluginInstaller, find_plugin};
+use crate::tr;
 use crate::utils::{command_exists, to_pascal_case};
 use actr_config::LockFile;
 use async_trait::async_trait;
@@ -8,6 +14,7 @@ use handlebars::Handlebars;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 use std::process::Command as StdCommand;
+use tokio::process::Command as TokioCommand;
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
@@ -20,6 +27,14 @@ const ACTR_SERVICE_TEMPLATE: &str = include_str!(concat!(
 const PROTOC: &str = "protoc";
 const PROTOC_GEN_SWIFT: &str = "protoc-gen-swift";
 const PROTOC_GEN_ACTR_FRAMEWORK_SWIFT: &str = "protoc-gen-actrframework-swift";
+const PROTOC_GEN_SWIFT_TOOL: RequiredTool = RequiredTool::required(
+    PROTOC_GEN_SWIFT,
+    "brew install swift-protobuf (see https://github.com/apple/swift-protobuf)",
+);
+const ACTR_FRAMEWORK_SWIFT_TOOL: RequiredTool = RequiredTool::required(
+    PROTOC_GEN_ACTR_FRAMEWORK_SWIFT,
+    "brew install protoc-gen-actrframework-swift",
+);
 
 pub struct SwiftGenerator;
 
@@ -37,12 +52,11 @@ impl LanguageGenerator for SwiftGenerator {
         info!("🔧 Generating Swift infrastructure code...");
         let mut generated_files = Vec::new();
 
-        self.ensure_required_tools(context)?;
+        self.ensure_required_tools(context).await?;
 
         // Ensure output directory exists
-        std::fs::create_dir_all(&context.output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to create output directory: {e}"))
-        })?;
+        std::fs::create_dir_all(&context.output)
+            .map_err(|e| ActrCliError::output_write_failed(&context.output, e))?;
 
         let proto_root = if context.input_path.is_file() {
             context
@@ -208,8 +222,9 @@ impl LanguageGenerator for SwiftGenerator {
             .collect();
 
         if !swift_proto_files.is_empty() {
-            let mut cmd = StdCommand::new("protoc");
-            cmd.arg(format!("--proto_path={}", proto_root.display()))
+            let mut cmd = TokioCommand::new("protoc");
+            cmd.kill_on_drop(true);
+            cmd.args(context.proto_path_args(proto_root))
                 .arg(format!("--swift_out={}", context.output.display()))
                 .arg("--swift_opt=Visibility=Public");
 
@@ -218,15 +233,14 @@ impl LanguageGenerator for SwiftGenerator {
             }
 
             debug!("Executing protoc (swift): {:?}", cmd);
-            let output = cmd.output().map_err(|e| {
-                ActrCliError::command_error(format!("Failed to execute protoc (swift): {e}"))
-            })?;
+            let output = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
 
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(ActrCliError::command_error(format!(
-                    "protoc (swift) execution failed: {stderr}"
-                )));
+                return Err(ActrCliError::protoc_failed(
+                    context.input_path.display(),
+                    stderr,
+                ));
             }
         }
 
@@ -244,8 +258,19 @@ impl LanguageGenerator for SwiftGenerator {
             .collect();
 
         if !actr_proto_files.is_empty() {
-            let mut cmd = StdCommand::new("protoc");
-            cmd.arg(format!("--proto_path={}", proto_root.display()))
+            let plugin_path = find_plugin(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT).ok_or_else(|| {
+                ActrCliError::tool_missing(
+                    ACTR_FRAMEWORK_SWIFT_TOOL.name,
+                    ACTR_FRAMEWORK_SWIFT_TOOL.install_hint,
+                )
+            })?;
+            let mut cmd = TokioCommand::new("protoc");
+            cmd.kill_on_drop(true);
+            cmd.args(context.proto_path_args(proto_root))
+                .arg(format!(
+                    "--plugin={PROTOC_GEN_ACTR_FRAMEWORK_SWIFT}={}",
+                    plugin_path.display()
+                ))
                 .arg(format!("--actrframework-swift_opt={}", options))
                 .arg(format!(
                     "--actrframework-swift_out={}",
@@ -257,17 +282,14 @@ impl LanguageGenerator for SwiftGenerator {
             }
 
             debug!("Executing protoc (actrframework-swift): {:?}", cmd);
-            let output = cmd.output().map_err(|e| {
-                ActrCliError::command_error(format!(
-                    "Failed to execute protoc (actrframework-swift): {e}"
-                ))
-            })?;
+            let output = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
 
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(ActrCliError::command_error(format!(
-                    "protoc (actrframework-swift) execution failed: {stderr}"
-                )));
+                return Err(ActrCliError::protoc_failed(
+                    context.input_path.display(),
+                    stderr,
+                ));
             }
         }
 
@@ -293,7 +315,7 @@ impl LanguageGenerator for SwiftGenerator {
         let mut scaffold_files = Vec::new();
 
         // 1. Parse local services to get methods for handler implementation
-        let services = self.parse_local_services(context);
+        let services = self.parse_local_services(context).await?;
 
         // 2. Determine service name for scaffolding
         let service_name = if let Some(service) = services.first() {
@@ -345,7 +367,12 @@ impl LanguageGenerator for SwiftGenerator {
             let is_scaffold = self.should_overwrite_scaffold(&user_file_path)?;
 
             // Always overwrite scaffold files (generated by init)
-            if is_scaffold {
+            if context.merge_scaffold && !is_scaffold {
+                if let Some(path) = merge_handler_impls(&user_file_path, &services)? {
+                    scaffold_files.push(path);
+                }
+                return Ok(scaffold_files);
+            } else if is_scaffold {
                 info!("🔄 Overwriting scaffold file: {:?}", user_file_path);
             } else if !context.overwrite_user_code {
                 // Skip non-scaffold files unless overwrite is forced
@@ -373,9 +400,8 @@ impl LanguageGenerator for SwiftGenerator {
             &services,
         )?;
 
-        std::fs::write(&user_file_path, scaffold_content).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to write user code scaffold: {e}"))
-        })?;
+        std::fs::write(&user_file_path, scaffold_content)
+            .map_err(|e| ActrCliError::output_write_failed(&user_file_path, e))?;
 
         info!("📄 Generated user code scaffold: {:?}", user_file_path);
         scaffold_files.push(user_file_path);
@@ -384,30 +410,104 @@ impl LanguageGenerator for SwiftGenerator {
         Ok(scaffold_files)
     }
 
-    async fn format_code(&self, _context: &GenContext, _files: &[PathBuf]) -> Result<()> {
-        // Swift code formatting is usually done via Xcode or swift-format.
-        // For now, we'll skip it as we don't want to enforce a specific tool.
-        Ok(())
-    }
+    async fn format_code(&self, context: &GenContext, files: &[PathBuf]) -> Result<()> {
+        // swift-format (or the [build.format.swift] tool) when installed; it reads the
+        // project's .swift-format. Without one the code is left as generated.
+        let tool = context.format.tool("swift-format");
+        let files: Vec<&PathBuf> = files
+            .iter()
+            .filter(|file| file.extension().is_some_and(|ext| ext == "swift"))
+            .collect();
+        if files.is_empty() || !command_exists(tool) {
+            debug!("{tool} not found or nothing to format, skipping formatting");
+            return Ok(());
+        }
 
-    async fn validate_code(&self, context: &GenContext) -> Result<()> {
-        info!("🔍 Running xcodegen generate...");
-        self.ensure_xcodegen_available()?;
-        let project_root = self.find_xcodegen_root(context)?;
-        let output = StdCommand::new("xcodegen")
-            .arg("generate")
-            .current_dir(&project_root)
+        info!("🎨 Formatting Swift code with {tool}...");
+        let output = context
+            .format
+            .command(tool, "swift-format", &["format", "--in-place"])
+            .args(files)
             .output()
-            .map_err(|e| ActrCliError::command_error(format!("Failed to run xcodegen: {e}")))?;
+            .await
+            .map_err(|e| ActrCliError::FormatFailed(format!("Failed to run {tool}: {e}")))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ActrCliError::command_error(format!(
-                "xcodegen generate failed: {stderr}"
-            )));
+            warn!("{tool} execution warning: {}", stderr);
+        } else {
+            info!("✅ Code formatting completed");
         }
+        Ok(())
+    }
+
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        vec![
+            PROTOC_TOOL,
+            PROTOC_GEN_SWIFT_TOOL,
+            ACTR_FRAMEWORK_SWIFT_TOOL,
+            // xcodegen projects run xcodegen, Swift packages run `swift build`
+            RequiredTool::optional("xcodegen", "brew install xcodegen"),
+            RequiredTool::optional("swift", "xcode-select --install"),
+        ]
+    }
+
+    fn stage_names(&self) -> (&'static str, &'static str) {
+        ("format", "swift build")
+    }
 
-        info!("✅ xcodegen generate completed");
+    async fn validate_code(&self, context: &GenContext) -> Result<()> {
+        match self.find_swift_project(context)? {
+            SwiftProject::Package(project_root) => {
+                info!("🔍 Running swift build...");
+                if !command_exists("swift") {
+                    return Err(ActrCliError::command_error(
+                        "swift not found. Install the Swift toolchain (`xcode-select --install`)."
+                            .to_string(),
+                    ));
+                }
+                let output = TokioCommand::new("swift")
+                    .arg("build")
+                    .current_dir(&project_root)
+                    .kill_on_drop(true)
+                    .output()
+                    .await
+                    .map_err(|e| {
+                        ActrCliError::command_error(format!("Failed to run swift build: {e}"))
+                    })?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(ActrCliError::command_error(format!(
+                        "swift build failed: {stderr}"
+                    )));
+                }
+
+                info!("✅ swift build completed");
+            }
+            SwiftProject::Xcodegen(project_root) => {
+                info!("🔍 Running xcodegen generate...");
+                self.ensure_xcodegen_available()?;
+                let output = TokioCommand::new("xcodegen")
+                    .arg("generate")
+                    .current_dir(&project_root)
+                    .kill_on_drop(true)
+                    .output()
+                    .await
+                    .map_err(|e| {
+                        ActrCliError::command_error(format!("Failed to run xcodegen: {e}"))
+                    })?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    return Err(ActrCliError::command_error(format!(
+                        "xcodegen generate failed: {stderr}"
+                    )));
+                }
+
+                info!("✅ xcodegen generate completed");
+            }
+        }
         Ok(())
     }
 
@@ -419,29 +519,35 @@ impl LanguageGenerator for SwiftGenerator {
             .and_then(|s| s.to_str())
             .unwrap_or("YourProject");
 
-        println!("\n🎉 Swift code generation completed!");
-        println!("\n📋 Next steps:");
-        println!("1. 📖 View generated code: {:?}", context.output);
+        let mut steps = vec![tr!("gen.view_code", path = context.output.display())];
         if !context.no_scaffold {
-            println!("2. ✏️  Implement business logic in ActrService.swift");
-            println!("3. 🏗️  xcodegen generate has been run to update your Xcode project");
-            println!("4. 🚀 Open {}.xcodeproj and build", project_name);
+            steps.push(tr!("gen.swift.implement"));
+        }
+        if matches!(
+            self.find_swift_project(context),
+            Ok(SwiftProject::Package(_))
+        ) {
+            steps.push(tr!("gen.swift.package_run"));
         } else {
-            println!("2. 🏗️  xcodegen generate has been run to update your Xcode project");
-            println!("3. 🚀 Open {}.xcodeproj and build", project_name);
+            steps.push(tr!("gen.swift.xcodegen"));
+            steps.push(tr!("gen.swift.open_project", project = project_name));
         }
-        println!("\n💡 Tip: Check the detailed user guide in the generated user code files");
+        print_steps(
+            &tr!("gen.completed_language", language = "Swift"),
+            &steps,
+            Some(&tr!("gen.tip_user_guide")),
+        );
     }
 }
 
 impl SwiftGenerator {
-    fn ensure_required_tools(&self, context: &GenContext) -> Result<()> {
+    async fn ensure_required_tools(&self, context: &GenContext) -> Result<()> {
         // 1. Ensure protoc is available.
-        let mut missing_tools: Vec<(&str, &str)> = Vec::new();
+        let mut missing_tools: Vec<RequiredTool> = Vec::new();
         if !command_exists(PROTOC) {
             self.try_install_protoc()?;
             if !command_exists(PROTOC) {
-                missing_tools.push((PROTOC, "Protocol Buffers compiler"));
+                missing_tools.push(PROTOC_TOOL);
             }
         }
 
@@ -451,63 +557,39 @@ impl SwiftGenerator {
         if !command_exists(PROTOC_GEN_SWIFT) {
             self.try_install_swift_protobuf()?;
             if !command_exists(PROTOC_GEN_SWIFT) {
-                missing_tools.push((
-                    PROTOC_GEN_SWIFT,
-                    "Protocol Buffers Swift codegen plugin (usually provided by swift-protobuf)",
-                ));
+                missing_tools.push(PROTOC_GEN_SWIFT_TOOL);
             }
         }
 
-        if !command_exists(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT) {
+        if find_plugin(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT).is_none() {
+            self.try_download_actrframework_swift_plugin(context).await;
+        }
+        if find_plugin(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT).is_none() {
             self.try_install_actrframework_swift_plugin()?;
-            if !command_exists(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT) {
-                missing_tools.push((
-                    PROTOC_GEN_ACTR_FRAMEWORK_SWIFT,
-                    "ActrFramework Swift codegen plugin (protoc-gen-actrframework-swift)",
-                ));
+            if find_plugin(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT).is_none() {
+                missing_tools.push(ACTR_FRAMEWORK_SWIFT_TOOL);
             }
         }
 
         // 3. Check version compatibility for protoc-gen-actrframework-swift
-        if command_exists(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT) {
-            self.check_and_update_plugin_version(context)?;
+        if find_plugin(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT).is_some() {
+            self.check_and_update_plugin_version(context).await?;
         }
 
         if missing_tools.is_empty() {
             return Ok(());
         }
 
-        let mut error_msg = "Missing required tools:\n".to_string();
-        for (tool, description) in &missing_tools {
-            error_msg.push_str(&format!("  - {tool} ({description})\n"));
-        }
-
-        error_msg
-            .push_str("\nTried automatic installation for Swift-related tools where possible.\n");
-        error_msg.push_str("Please install the missing tools manually and try again.\n\n");
-        error_msg.push_str("Suggested installation commands:\n");
-        for (tool, _) in &missing_tools {
-            match *tool {
-                PROTOC => {
-                    error_msg.push_str(
-                        "  - protoc: install via your package manager, e.g. `brew install protobuf` or `brew reinstall protobuf`\n",
-                    );
-                }
-                PROTOC_GEN_SWIFT => {
-                    error_msg.push_str(
-                        "  - protoc-gen-swift: install via your package manager, e.g. `brew install swift-protobuf` or `brew reinstall swift-protobuf`; see https://github.com/apple/swift-protobuf\n",
-                    );
-                }
-                PROTOC_GEN_ACTR_FRAMEWORK_SWIFT => {
-                    error_msg.push_str(
-                        "  - protoc-gen-actrframework-swift: install via your package manager, e.g. `brew install protoc-gen-actrframework-swift` or `brew reinstall protoc-gen-actrframework-swift`\n",
-                    );
-                }
-                _ => {}
-            }
-        }
-
-        Err(ActrCliError::command_error(error_msg))
+        // Automatic installation was already tried where possible
+        let tools: Vec<&str> = missing_tools.iter().map(|tool| tool.name).collect();
+        let hints: Vec<String> = missing_tools
+            .iter()
+            .map(|tool| format!("{}: {}", tool.name, tool.install_hint))
+            .collect();
+        Err(ActrCliError::tool_missing(
+            tools.join(", "),
+            hints.join("; "),
+        ))
     }
 
     fn should_overwrite_scaffold(&self, path: &Path) -> Result<bool> {
@@ -667,6 +749,32 @@ impl SwiftGenerator {
         Ok(())
     }
 
+    /// Best-effort download of a prebuilt protoc-gen-actrframework-swift into ~/.actr/bin.
+    async fn try_download_actrframework_swift_plugin(&self, context: &GenContext) {
+        if context.offline {
+            return;
+        }
+        let version =
+            match self.resolve_plugin_requirement(context, PROTOC_GEN_ACTR_FRAMEWORK_SWIFT) {
+                Ok(Some(requirement)) => requirement
+                    .minimum()
+                    .unwrap_or(env!("CARGO_PKG_VERSION"))
+                    .to_string(),
+                _ => env!("CARGO_PKG_VERSION").to_string(),
+            };
+        let installed = match PluginInstaller::from_config(&context.config_path) {
+            Ok(installer) => {
+                installer
+                    .install(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT, &version)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = installed {
+            warn!("Prebuilt {PROTOC_GEN_ACTR_FRAMEWORK_SWIFT} download failed: {e}");
+        }
+    }
+
     /// Best-effort automatic installation hook for protoc-gen-actrframework-swift.
     ///
     /// On macOS with Homebrew available this will run:
@@ -738,31 +846,38 @@ impl SwiftGenerator {
         Ok(())
     }
 
-    /// Check installed protoc-gen-actrframework-swift version and ensure it matches actr version
-    fn check_and_update_plugin_version(&self, context: &GenContext) -> Result<()> {
+    /// Check installed protoc-gen-actrframework-swift version against `.protoc-plugin.toml`
+    /// (or the actr version when no requirement is configured)
+    async fn check_and_update_plugin_version(&self, context: &GenContext) -> Result<()> {
         let actr_version = env!("CARGO_PKG_VERSION");
-        let min_version =
-            self.resolve_plugin_min_version(context, PROTOC_GEN_ACTR_FRAMEWORK_SWIFT)?;
+        let requirement =
+            self.resolve_plugin_requirement(context, PROTOC_GEN_ACTR_FRAMEWORK_SWIFT)?;
         let plugin_version = self.get_plugin_version()?;
 
-        match (min_version, plugin_version) {
-            (Some(min_version), Some(plugin_ver)) => {
-                if version_is_at_least(&plugin_ver, &min_version) {
+        match (requirement, plugin_version) {
+            (Some(requirement), Some(plugin_ver)) => {
+                if requirement.matches(&plugin_ver) {
                     debug!(
-                        "✅ protoc-gen-actrframework-swift version {} meets minimum version {}",
-                        plugin_ver, min_version
+                        "✅ protoc-gen-actrframework-swift version {} satisfies '{}'",
+                        plugin_ver, requirement
                     );
                     return Ok(());
                 }
 
                 warn!(
-                    "⚠️  protoc-gen-actrframework-swift version {} is lower than minimum version {}",
-                    plugin_ver, min_version
+                    "⚠️  protoc-gen-actrframework-swift version {} does not satisfy '{}'",
+                    plugin_ver, requirement
                 );
-                self.try_update_plugin()?;
+                self.try_download_actrframework_swift_plugin(context).await;
+                if !self
+                    .get_plugin_version()?
+                    .is_some_and(|v| requirement.matches(&v))
+                {
+                    self.try_update_plugin()?;
+                }
                 let updated_version = self.get_plugin_version()?;
                 if let Some(updated_ver) = updated_version {
-                    if version_is_at_least(&updated_ver, &min_version) {
+                    if requirement.matches(&updated_ver) {
                         info!(
                             "✅ Successfully updated protoc-gen-actrframework-swift to version {}",
                             updated_ver
@@ -770,18 +885,20 @@ impl SwiftGenerator {
                         return Ok(());
                     }
                     return Err(ActrCliError::command_error(format!(
-                        "protoc-gen-actrframework-swift version {} is still lower than minimum version {} after update. Please manually update it.",
-                        updated_ver, min_version
+                        "protoc-gen-actrframework-swift version {} still does not satisfy '{}' after update.\n\
+                         Install a matching version (e.g. `brew upgrade protoc-gen-actrframework-swift`) \
+                         or adjust the requirement in .protoc-plugin.toml.",
+                        updated_ver, requirement
                     )));
                 }
                 return Err(ActrCliError::command_error(
                     "Failed to get protoc-gen-actrframework-swift version after update".to_string(),
                 ));
             }
-            (Some(min_version), None) => {
+            (Some(requirement), None) => {
                 return Err(ActrCliError::command_error(format!(
-                    "Could not determine protoc-gen-actrframework-swift version (minimum required: {}).",
-                    min_version
+                    "Could not determine protoc-gen-actrframework-swift version (required: '{}').",
+                    requirement
                 )));
             }
             (None, Some(plugin_ver)) => match compare_versions(&plugin_ver, actr_version) {
@@ -847,9 +964,10 @@ impl SwiftGenerator {
 
     /// Get the version of installed protoc-gen-actrframework-swift
     fn get_plugin_version(&self) -> Result<Option<String>> {
-        let output = StdCommand::new(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT)
-            .arg("--version")
-            .output();
+        let Some(plugin_path) = find_plugin(PROTOC_GEN_ACTR_FRAMEWORK_SWIFT) else {
+            return Ok(None);
+        };
+        let output = StdCommand::new(plugin_path).arg("--version").output();
 
         match output {
             Ok(output) if output.status.success() => {
@@ -875,21 +993,22 @@ impl SwiftGenerator {
         }
     }
 
-    fn resolve_plugin_min_version(
+    fn resolve_plugin_requirement(
         &self,
         context: &GenContext,
         plugin_name: &str,
-    ) -> Result<Option<String>> {
+    ) -> Result<Option<PluginRequirement>> {
         let config = load_protoc_plugin_config(&context.config_path)?;
         if let Some(config) = config
-            && let Some(min_version) = config.min_version(plugin_name)
+            && let Some(requirement) = config.requirement(plugin_name)
         {
             info!(
-                "🔧 Using minimum version for {} from {}",
+                "🔧 Using version requirement '{}' for {} from {}",
+                requirement,
                 plugin_name,
                 config.path().display()
             );
-            return Ok(Some(min_version.to_string()));
+            return Ok(Some(requirement.clone()));
         }
         Ok(None)
     }
@@ -1030,7 +1149,10 @@ impl SwiftGenerator {
                 .file_name()
                 .and_then(|n| n.to_str())
                 .ok_or_else(|| {
-                    ActrCliError::config_error("Failed to get filename from path".to_string())
+                    ActrCliError::CodeGeneration(format!(
+                        "Failed to get filename from {}",
+                        src_path.display()
+                    ))
                 })?;
 
             let mut dst_path = output_dir.to_path_buf();
@@ -1039,22 +1161,12 @@ impl SwiftGenerator {
             // Overwrite existing files if they are not the same as src_path
             if dst_path.exists() && dst_path != src_path {
                 debug!("Overwriting existing file: {:?}", dst_path);
-                std::fs::remove_file(&dst_path).map_err(|e| {
-                    ActrCliError::config_error(format!(
-                        "Failed to remove existing file {:?}: {}",
-                        dst_path, e
-                    ))
-                })?;
+                std::fs::remove_file(&dst_path)
+                    .map_err(|e| ActrCliError::output_write_failed(&dst_path, e))?;
             }
 
-            std::fs::rename(&src_path, &dst_path).map_err(|e| {
-                ActrCliError::config_error(format!(
-                    "Failed to move {} to {}: {}",
-                    src_path.display(),
-                    dst_path.display(),
-                    e
-                ))
-            })?;
+            std::fs::rename(&src_path, &dst_path)
+                .map_err(|e| ActrCliError::output_write_failed(&dst_path, e))?;
         }
 
         // Remove empty subdirectories
@@ -1082,7 +1194,10 @@ impl SwiftGenerator {
         Ok(())
     }
 
-    fn find_xcodegen_root(&self, context: &GenContext) -> Result<PathBuf> {
+    /// Nearest Swift package or xcodegen project around the input and output paths
+    ///
+    /// Package.swift wins over project.yml when a directory has both.
+    fn find_swift_project(&self, context: &GenContext) -> Result<SwiftProject> {
         let mut candidates = Vec::new();
         if let Ok(cwd) = std::env::current_dir() {
             candidates.push(cwd);
@@ -1105,18 +1220,31 @@ impl SwiftGenerator {
 
         for candidate in candidates {
             for ancestor in candidate.ancestors() {
+                if ancestor.join("Package.swift").exists() {
+                    return Ok(SwiftProject::Package(ancestor.to_path_buf()));
+                }
                 if ancestor.join("project.yml").exists() {
-                    return Ok(ancestor.to_path_buf());
+                    return Ok(SwiftProject::Xcodegen(ancestor.to_path_buf()));
                 }
             }
         }
 
-        Err(ActrCliError::config_error(
-            "project.yml not found; cannot run xcodegen generate",
+        Err(ActrCliError::InvalidProject(
+            "Neither Package.swift nor project.yml found; cannot build the Swift project"
+                .to_string(),
         ))
     }
 }
 
+/// How a Swift project is built after generation
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SwiftProject {
+    /// Swift package, validated with `swift build`
+    Package(PathBuf),
+    /// xcodegen project, regenerated with `xcodegen generate`
+    Xcodegen(PathBuf),
+}
+
 #[derive(Serialize, Clone)]
 struct ProtoService {
     name: String,
@@ -1131,10 +1259,129 @@ struct ProtoMethod {
     swift_name: String,
     input_type: String,
     output_type: String,
+    client_streaming: bool,
+    server_streaming: bool,
+}
+
+/// Type prefix SwiftProtobuf derives from a package: `echo_app` -> `EchoApp_`
+fn swift_package_prefix(package: &str) -> String {
+    if package.is_empty() {
+        return String::new();
+    }
+    let parts: Vec<String> = package
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect();
+    parts.join("") + "_"
+}
+
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_lowercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+/// `func` of one RPC in `<Service>HandlerImpl`, as rendered by ActrService.swift.hbs
+///
+/// Streaming requests arrive as an `AsyncThrowingStream` named `reqs`; streaming
+/// responses are returned as one.
+fn handler_method(method: &ProtoMethod) -> String {
+    let name = &method.name;
+    let input = if method.client_streaming {
+        format!("reqs: AsyncThrowingStream<{}, Error>", method.input_type)
+    } else {
+        format!("req: {}", method.input_type)
+    };
+    let todo = match (method.client_streaming, method.server_streaming) {
+        (true, true) => format!("{name} (bidirectional streaming)"),
+        (true, false) => format!("{name} (client streaming)"),
+        (false, true) => format!("{name} (server streaming)"),
+        (false, false) => name.clone(),
+    };
+    let received = if method.client_streaming {
+        "stream"
+    } else {
+        "request"
+    };
+    let (returns, body) = if method.server_streaming {
+        (
+            format!("-> AsyncThrowingStream<{}, Error>", method.output_type),
+            format!(
+                r#"        return AsyncThrowingStream {{ continuation in
+            continuation.finish(throwing: ActrError.WorkloadError(msg: "{name} not implemented"))
+        }}"#
+            ),
+        )
+    } else {
+        (
+            format!("async throws -> {}", method.output_type),
+            format!(r#"        throw ActrError.WorkloadError(msg: "{name} not implemented")"#),
+        )
+    };
+    format!(
+        r#"    /// RPC method: {name}
+    public func {swift_name}(
+        {input},
+        ctx: Context
+    ) {returns} {{
+        // TODO: Implement {todo}
+        logger.info("Received {name} {received}")
+{body}
+    }}
+"#,
+        swift_name = method.swift_name,
+    )
+}
+
+/// Add the methods of RPCs missing from the handler classes of an existing
+/// ActrService.swift (`--merge-scaffold`)
+fn merge_handler_impls(path: &Path, services: &[ProtoService]) -> Result<Option<PathBuf>> {
+    let mut source = std::fs::read_to_string(path).map_err(ActrCliError::Io)?;
+    let mut added = Vec::new();
+    for service in services {
+        let stubs: Vec<MethodStub> = service
+            .methods
+            .iter()
+            .map(|method| MethodStub {
+                name: method.swift_name.clone(),
+                code: format!(
+                    "    // TODO: new RPC, added by `actr gen --merge-scaffold`\n{}",
+                    handler_method(method)
+                ),
+            })
+            .collect();
+        let header = format!("class {}HandlerImpl", service.name);
+        match merge_block(&source, &header, "func", &stubs) {
+            Some(outcome) => {
+                added.extend(outcome.added);
+                source = outcome.source;
+            }
+            None => warn!(
+                "⚠️  No `{header}` in {:?}; add the handler for {} by hand or use --overwrite-user-code",
+                path, service.name
+            ),
+        }
+    }
+
+    if added.is_empty() {
+        info!("✅ {:?} already implements every RPC", path);
+        return Ok(None);
+    }
+    std::fs::write(path, source).map_err(|e| ActrCliError::output_write_failed(path, e))?;
+    info!("🧩 Added {} to {:?}", added.join(", "), path);
+    Ok(Some(path.to_path_buf()))
 }
 
 impl SwiftGenerator {
-    fn parse_local_services(&self, context: &GenContext) -> Vec<ProtoService> {
+    async fn parse_local_services(&self, context: &GenContext) -> Result<Vec<ProtoService>> {
         let mut services = Vec::new();
 
         for proto_file_path in &context.proto_files {
@@ -1143,122 +1390,35 @@ impl SwiftGenerator {
                 continue;
             }
 
-            if let Ok(content) = std::fs::read_to_string(proto_file_path) {
-                let mut current_package = String::new();
-                let mut current_service: Option<ProtoService> = None;
-
-                for line in content.lines() {
-                    let line = line.trim();
-
-                    // Parse package
-                    if line.starts_with("package ") {
-                        current_package = line
-                            .trim_start_matches("package ")
-                            .trim_end_matches(';')
-                            .trim()
-                            .to_string();
-                        continue;
-                    }
-
-                    // Parse service start
-                    if line.starts_with("service ") {
-                        let service_name = line
-                            .trim_start_matches("service ")
-                            .split_whitespace()
-                            .next()
-                            .unwrap_or("")
-                            .trim_end_matches('{')
-                            .trim()
-                            .to_string();
-
-                        if !service_name.is_empty() {
-                            let swift_package_prefix = if current_package.is_empty() {
-                                String::new()
-                            } else {
-                                // Convert echo_app to EchoApp_
-                                let parts: Vec<String> = current_package
-                                    .split('_')
-                                    .map(|s| {
-                                        let mut c = s.chars();
-                                        match c.next() {
-                                            None => String::new(),
-                                            Some(f) => {
-                                                f.to_uppercase().collect::<String>() + c.as_str()
-                                            }
-                                        }
-                                    })
-                                    .collect();
-                                parts.join("") + "_"
-                            };
-
-                            current_service = Some(ProtoService {
-                                name: service_name,
-                                package: current_package.clone(),
-                                swift_package_prefix,
-                                methods: Vec::new(),
-                            });
-                        }
-                        continue;
-                    }
-
-                    // Parse rpc method
-                    if let Some(ref mut service) = current_service {
-                        if line.starts_with("rpc ") {
-                            // rpc RelayMessage(RelayMessageRequest) returns (RelayMessageResponse);
-                            let line = line.trim_start_matches("rpc ").trim();
-                            if let Some(name_end) = line.find('(') {
-                                let method_name = line[..name_end].trim().to_string();
-
-                                // swift_name: RelayMessage -> relayMessage
-                                let mut chars = method_name.chars();
-                                let swift_name = match chars.next() {
-                                    None => String::new(),
-                                    Some(f) => {
-                                        f.to_lowercase().collect::<String>() + chars.as_str()
-                                    }
-                                };
-
-                                let rest = &line[name_end + 1..];
-                                if let Some(input_end) = rest.find(')') {
-                                    let input_type = rest[..input_end].trim().to_string();
-
-                                    if let Some(returns_pos) = rest.find("returns") {
-                                        let rest = &rest[returns_pos + 7..];
-                                        if let Some(output_start) = rest.find('(')
-                                            && let Some(output_end) = rest.find(')')
-                                        {
-                                            let output_type = rest[output_start + 1..output_end]
-                                                .trim()
-                                                .to_string();
-
-                                            service.methods.push(ProtoMethod {
-                                                name: method_name,
-                                                swift_name,
-                                                input_type: format!(
-                                                    "{}{}",
-                                                    service.swift_package_prefix, input_type
-                                                ),
-                                                output_type: format!(
-                                                    "{}{}",
-                                                    service.swift_package_prefix, output_type
-                                                ),
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                        }
-
-                        if line.contains('}')
-                            && let Some(s) = current_service.take()
-                        {
-                            services.push(s);
-                        }
-                    }
+            let schema = DefaultProtoProcessor::compile_file(
+                proto_file_path,
+                &context.proto_path_args(&context.input_path),
+            )
+            .await?;
+            let package = schema.package.clone().unwrap_or_default();
+            let swift_package_prefix = swift_package_prefix(&package);
+            services.extend(schema.services.into_iter().map(|service| {
+                ProtoService {
+                    name: service.name,
+                    package: package.clone(),
+                    swift_package_prefix: swift_package_prefix.clone(),
+                    methods: service
+                        .methods
+                        .into_iter()
+                        .map(|rpc| ProtoMethod {
+                            // RelayMessage -> relayMessage
+                            swift_name: lower_first(&rpc.name),
+                            input_type: format!("{swift_package_prefix}{}", rpc.input_type),
+                            output_type: format!("{swift_package_prefix}{}", rpc.output_type),
+                            name: rpc.name,
+                            client_streaming: rpc.client_streaming,
+                            server_streaming: rpc.server_streaming,
+                        })
+                        .collect(),
                 }
-            }
+            }));
         }
-        services
+        Ok(services)
     }
 
     /// Extract workload name from generated local.actor.swift file
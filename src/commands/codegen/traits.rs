@@ -1,7 +1,11 @@
-use crate::error::Result;
+use super::format::FormatConfig;
+use super::generated_crate::GeneratedCrate;
+use super::header::GenHeader;
+use crate::error::{ActrCliError, Result};
+use crate::{human_println, tr};
 use actr_config::Config;
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Type of scaffold code to generate
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -25,8 +29,99 @@ pub struct GenContext {
     pub config: Config,
     pub no_scaffold: bool,
     pub overwrite_user_code: bool,
+    /// Add stubs for new RPCs to existing user code files instead of skipping them
+    pub merge_scaffold: bool,
     pub no_format: bool,
     pub debug: bool,
+    /// Skip `validate_code` (build scripts cannot run `cargo check`)
+    pub no_validate: bool,
+    /// Never install or download toolchain plugins
+    pub offline: bool,
+    /// Ignore the generation cache and regenerate every proto
+    pub force: bool,
+    /// Maximum number of concurrent protoc invocations
+    pub jobs: usize,
+    /// Suppress progress bars
+    pub quiet: bool,
+    /// Extra import roots searched before the input path (buf modules and exported deps)
+    pub include_paths: Vec<PathBuf>,
+    /// Import roots searched after the input path (remote service dirs, well-known
+    /// types); filled in by the import resolver before generation
+    pub import_roots: Vec<PathBuf>,
+    /// Kotlin only: Gradle module whose build directory receives the generated sources
+    pub gradle_module: Option<PathBuf>,
+    /// `[build.header]`: header prepended to every generated source file
+    pub header: GenHeader,
+    /// `[build.format.<language>]`: formatter tool and extra arguments
+    pub format: FormatConfig,
+    /// Rust only (`--as-crate`): crate receiving the generated modules
+    pub generated_crate: Option<GeneratedCrate>,
+}
+
+impl GenContext {
+    /// `--proto_path` arguments for protoc: the include paths, `input_root`, then the
+    /// import roots
+    pub fn proto_path_args(&self, input_root: &Path) -> Vec<String> {
+        self.include_paths
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(std::iter::once(input_root))
+            .chain(self.import_roots.iter().map(PathBuf::as_path))
+            .map(|path| format!("--proto_path={}", path.display()))
+            .collect()
+    }
+}
+
+/// External executable a generator shells out to, probed by `actr doctor`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequiredTool {
+    /// Executable name looked up in node_modules/.bin, ~/.actr/bin and on PATH
+    pub name: &'static str,
+    /// Generation fails without it; optional tools such as formatters are skipped
+    pub required: bool,
+    /// How to install the tool
+    pub install_hint: &'static str,
+}
+
+impl RequiredTool {
+    pub const fn required(name: &'static str, install_hint: &'static str) -> Self {
+        Self {
+            name,
+            required: true,
+            install_hint,
+        }
+    }
+
+    pub const fn optional(name: &'static str, install_hint: &'static str) -> Self {
+        Self {
+            name,
+            required: false,
+            install_hint,
+        }
+    }
+
+    /// Error for a failed spawn of the tool; `ToolMissing` when it is not installed
+    pub fn spawn_error(&self, error: std::io::Error) -> ActrCliError {
+        ActrCliError::spawn_error(self.name, self.install_hint, error)
+    }
+}
+
+/// Protocol Buffers compiler, needed by every generator
+pub const PROTOC_TOOL: RequiredTool = RequiredTool::required(
+    "protoc",
+    "Install protoc from https://github.com/protocolbuffers/protobuf/releases (or `brew install protobuf`)",
+);
+
+/// Print the summary shown after generation: a title, numbered next steps and a tip
+pub fn print_steps(title: &str, steps: &[String], tip: Option<&str>) {
+    human_println!("\n{title}");
+    human_println!("\n{}", tr!("gen.next_steps"));
+    for (index, step) in steps.iter().enumerate() {
+        human_println!("{}. {step}", index + 1);
+    }
+    if let Some(tip) = tip {
+        human_println!("\n{tip}");
+    }
 }
 
 /// Interface for language-specific code generators
@@ -41,9 +136,35 @@ pub trait LanguageGenerator: Send {
     /// Format generated code using language-specific tools
     async fn format_code(&self, context: &GenContext, files: &[PathBuf]) -> Result<()>;
 
+    /// Version of the external code generation plugin, part of the generation cache key
+    fn toolchain_version(&self) -> Option<String> {
+        None
+    }
+
+    /// External tools this generator invokes
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        vec![PROTOC_TOOL]
+    }
+
+    /// Whether outputs are produced per proto file, so unchanged protos can be skipped
+    /// individually; otherwise any change regenerates every proto
+    fn supports_incremental(&self) -> bool {
+        false
+    }
+
     /// Validate generated code (e.g., using a compiler)
     async fn validate_code(&self, context: &GenContext) -> Result<()>;
 
+    /// Stage names of `format_code` and `validate_code` in `--timings`
+    fn stage_names(&self) -> (&'static str, &'static str) {
+        ("format", "validate")
+    }
+
+    /// Post-process outputs once formatting and validation are done
+    async fn finalize(&self, _context: &GenContext) -> Result<()> {
+        Ok(())
+    }
+
     /// Print next steps
     fn print_next_steps(&self, context: &GenContext);
 }
@@ -0,0 +1,608 @@
+use crate::commands::codegen::traits::{
+    GenContext, LanguageGenerator, PROTOC_TOOL, RequiredTool, print_steps,
+};
+use crate::core::DefaultProtoProcessor;
+use crate::error::{ActrCliError, Result};
+use crate::proto_parser::ProtoSchema;
+use crate::tr;
+use crate::utils::{command_exists, to_pascal_case};
+use actr_config::LockFile;
+use actr_protocol::ActrTypeExt;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+use tracing::{debug, info, warn};
+
+const PROTOC: &str = "protoc";
+const ES_PLUGIN: &str = "protoc-gen-es";
+
+/// Information about a proto service
+#[derive(Debug, Clone)]
+struct ProtoService {
+    /// Service name (e.g., "EchoService")
+    name: String,
+    /// Proto package (e.g., "echo")
+    package: String,
+    /// Module generated by protoc-gen-es, relative to the output directory (e.g., "local/echo_pb")
+    pb_module: String,
+    /// Target actr_type for calls (e.g., "acme+EchoService")
+    target_type: String,
+    methods: Vec<ProtoMethod>,
+}
+
+/// Information about an RPC method
+#[derive(Debug, Clone)]
+struct ProtoMethod {
+    name: String,
+    input_type: String,
+    output_type: String,
+    client_streaming: bool,
+    server_streaming: bool,
+}
+
+pub struct TypescriptGenerator;
+
+impl TypescriptGenerator {
+    /// Locate protoc-gen-es, preferring the project-local node_modules install
+    fn find_es_plugin(&self, context: &GenContext) -> Result<PathBuf> {
+        let project_root = self.project_root(context);
+        let local = project_root.join("node_modules/.bin").join(ES_PLUGIN);
+        if local.exists() {
+            return Ok(local);
+        }
+
+        if command_exists(ES_PLUGIN) {
+            return Ok(PathBuf::from(ES_PLUGIN));
+        }
+
+        Err(ActrCliError::tool_missing(
+            ES_PLUGIN,
+            format!(
+                "Run 'npm install' in {} (it is listed in devDependencies)",
+                project_root.display()
+            ),
+        ))
+    }
+
+    fn project_root<'a>(&self, context: &'a GenContext) -> &'a Path {
+        context
+            .config_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+    }
+
+    /// Map remote proto paths (relative to protos/remote/) to their actr_type
+    fn load_remote_types(&self, context: &GenContext) -> HashMap<String, String> {
+        let lock_file_path = self.project_root(context).join("Actr.lock.toml");
+        let Ok(lock_file) = LockFile::from_file(&lock_file_path) else {
+            warn!("Actr.lock.toml could not be read; remote ActorRefs will have no target type");
+            return HashMap::new();
+        };
+
+        let mut map = HashMap::new();
+        for dep in &lock_file.dependencies {
+            for file in &dep.files {
+                map.insert(file.path.clone(), dep.actr_type.clone());
+            }
+        }
+        map
+    }
+
+    /// Parse service definitions from proto files
+    async fn parse_services(&self, context: &GenContext) -> Result<Vec<ProtoService>> {
+        let remote_types = self.load_remote_types(context);
+        let local_type = context.config.package.actr_type.to_string_repr();
+        let mut services = Vec::new();
+
+        for proto_file in &context.proto_files {
+            let schema = DefaultProtoProcessor::compile_file(
+                proto_file,
+                &context.proto_path_args(&context.input_path),
+            )
+            .await?;
+
+            let relative = proto_file
+                .strip_prefix(&context.input_path)
+                .unwrap_or(proto_file)
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .collect::<Vec<_>>()
+                .join("/");
+            let pb_module = format!("{}_pb", relative.trim_end_matches(".proto"));
+            let target_type = match relative.strip_prefix("remote/") {
+                Some(remote_path) => remote_types.get(remote_path).cloned().unwrap_or_default(),
+                None => local_type.clone(),
+            };
+
+            services.extend(services_from_schema(&schema, &pb_module, &target_type));
+        }
+
+        Ok(services)
+    }
+
+    /// Generate an ActorRef wrapper for a service
+    fn generate_actor_ref(&self, service: &ProtoService) -> String {
+        let mut imports: Vec<&str> = service
+            .methods
+            .iter()
+            .flat_map(|m| [m.input_type.as_str(), m.output_type.as_str()])
+            .collect();
+        imports.sort();
+        imports.dedup();
+
+        let full_service = if service.package.is_empty() {
+            service.name.clone()
+        } else {
+            format!("{}.{}", service.package, service.name)
+        };
+
+        let methods: String = service
+            .methods
+            .iter()
+            .map(|method| actor_ref_method(method, &full_service))
+            .collect();
+        let index_import = if service.methods.iter().any(|m| m.client_streaming) {
+            "import { encodeStream, type RawCaller } from \"./index\";"
+        } else {
+            "import type { RawCaller } from \"./index\";"
+        };
+
+        format!(
+            r#"// Automatically generated by `actr gen`. Do not edit.
+
+{index_import}
+import {{ {imports} }} from "./{pb_module}";
+
+/** Typed client for {full_service} */
+export class {name}Ref {{
+  constructor(
+    private readonly caller: RawCaller,
+    readonly targetType: string = "{target_type}",
+  ) {{}}
+{methods}}}
+"#,
+            index_import = index_import,
+            imports = imports.join(", "),
+            pb_module = service.pb_module,
+            name = service.name,
+            target_type = service.target_type,
+        )
+    }
+
+    /// Generate actr-config.ts from Actr.toml
+    fn generate_config_module(&self, context: &GenContext) -> String {
+        let config = &context.config;
+        format!(
+            r#"// Automatically generated by `actr gen` from Actr.toml. Do not edit.
+
+export const actrConfig = {{
+  packageName: "{package}",
+  actrType: "{actr_type}",
+  signalingUrl: "{signaling}",
+  realmId: {realm},
+}} as const;
+"#,
+            package = config.package.name,
+            actr_type = config.package.actr_type.to_string_repr(),
+            signaling = config.signaling_url.as_str(),
+            realm = config.realm.realm_id,
+        )
+    }
+
+    /// Generate the barrel module exporting message types and ActorRefs
+    fn generate_index(&self, pb_modules: &[String], ref_modules: &[String]) -> String {
+        let mut exports = String::new();
+        for module in pb_modules.iter().chain(ref_modules) {
+            exports.push_str(&format!("export * from \"./{module}\";\n"));
+        }
+
+        format!(
+            r#"// Automatically generated by `actr gen`. Do not edit.
+
+/** Minimal transport used by generated ActorRefs (implemented by the web ActorClient) */
+export interface RawCaller {{
+  callRaw(targetType: string, routeKey: string, payload: Uint8Array): Promise<Uint8Array>;
+  /** Server streaming RPCs: one request, a stream of responses */
+  callServerStream?(targetType: string, routeKey: string, payload: Uint8Array): AsyncIterable<Uint8Array>;
+  /** Client streaming RPCs: a stream of requests, one response */
+  callClientStream?(targetType: string, routeKey: string, payloads: AsyncIterable<Uint8Array>): Promise<Uint8Array>;
+  /** Bidirectional streaming RPCs */
+  callBidiStream?(targetType: string, routeKey: string, payloads: AsyncIterable<Uint8Array>): AsyncIterable<Uint8Array>;
+}}
+
+/** Serialize request messages for the streaming RawCaller methods */
+export async function* encodeStream(
+  messages: AsyncIterable<{{ toBinary(): Uint8Array }}>,
+): AsyncGenerator<Uint8Array> {{
+  for await (const message of messages) {{
+    yield message.toBinary();
+  }}
+}}
+
+export {{ actrConfig }} from "./actr-config";
+{exports}"#
+        )
+    }
+
+    fn write_file(&self, path: &Path, content: &str) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| ActrCliError::output_write_failed(parent, e))?;
+        }
+        std::fs::write(path, content).map_err(|e| ActrCliError::output_write_failed(path, e))
+    }
+}
+
+#[async_trait]
+impl LanguageGenerator for TypescriptGenerator {
+    async fn generate_infrastructure(&self, context: &GenContext) -> Result<Vec<PathBuf>> {
+        info!("🔧 Generating TypeScript infrastructure code...");
+
+        if !command_exists(PROTOC) {
+            return Err(ActrCliError::tool_missing(
+                PROTOC_TOOL.name,
+                PROTOC_TOOL.install_hint,
+            ));
+        }
+        let plugin_path = self.find_es_plugin(context)?;
+        info!("✅ Using {}: {:?}", ES_PLUGIN, plugin_path);
+
+        // 1. Message types via protoc-gen-es
+        let mut cmd = TokioCommand::new(PROTOC);
+        cmd.kill_on_drop(true)
+            .args(context.proto_path_args(&context.input_path))
+            .arg(format!("--plugin=protoc-gen-es={}", plugin_path.display()))
+            .arg("--es_opt=target=ts")
+            .arg(format!("--es_out={}", context.output.display()))
+            .args(&context.proto_files);
+
+        debug!("Executing protoc (es): {:?}", cmd);
+        let output = cmd.output().await.map_err(|e| PROTOC_TOOL.spawn_error(e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(ActrCliError::protoc_failed(
+                context.input_path.display(),
+                stderr,
+            ));
+        }
+
+        let mut generated_files = Vec::new();
+
+        // 2. ActorRef wrappers
+        let services = self.parse_services(context).await?;
+        info!("📊 Found {} services", services.len());
+
+        let mut pb_modules: Vec<String> = services.iter().map(|s| s.pb_module.clone()).collect();
+        pb_modules.dedup();
+
+        let mut ref_modules = Vec::new();
+        for service in &services {
+            let module = format!("{}_actor_ref", crate::utils::to_snake_case(&service.name));
+            let path = context.output.join(format!("{module}.ts"));
+            self.write_file(&path, &self.generate_actor_ref(service))?;
+            generated_files.push(path);
+            ref_modules.push(module);
+        }
+
+        // 3. actr-config.ts and index.ts
+        let config_path = context.output.join("actr-config.ts");
+        self.write_file(&config_path, &self.generate_config_module(context))?;
+        generated_files.push(config_path);
+
+        let index_path = context.output.join("index.ts");
+        self.write_file(&index_path, &self.generate_index(&pb_modules, &ref_modules))?;
+        generated_files.push(index_path);
+
+        info!(
+            "✅ Generated {} TypeScript infrastructure files",
+            generated_files.len()
+        );
+        Ok(generated_files)
+    }
+
+    async fn generate_scaffold(&self, context: &GenContext) -> Result<Vec<PathBuf>> {
+        if context.no_scaffold {
+            return Ok(vec![]);
+        }
+
+        // The web client entry point (src/main.ts) is created by `actr init`;
+        // generated ActorRefs are consumed from there.
+        let entry = self.project_root(context).join("src/main.ts");
+        if !entry.exists() {
+            info!(
+                "💡 No src/main.ts found; import ActorRefs from {:?}",
+                context.output
+            );
+        }
+        Ok(vec![])
+    }
+
+    async fn format_code(&self, context: &GenContext, files: &[PathBuf]) -> Result<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        // The project's own prettier, unless [build.format.typescript] names another tool
+        let tool = context.format.tool("prettier");
+        let program = if context.format.is_default_tool("prettier") {
+            let prettier = self
+                .project_root(context)
+                .join("node_modules/.bin/prettier");
+            if !prettier.exists() {
+                info!("💡 prettier not installed locally, skipping formatting");
+                return Ok(());
+            }
+            prettier.to_string_lossy().into_owned()
+        } else if command_exists(tool) {
+            tool.to_string()
+        } else {
+            info!("💡 {tool} not found, skipping formatting");
+            return Ok(());
+        };
+
+        info!("🎨 Formatting TypeScript code...");
+        let output = context
+            .format
+            .command(&program, "prettier", &["--write"])
+            .args(files)
+            .output()
+            .await
+            .map_err(|e| ActrCliError::FormatFailed(format!("Failed to execute {tool}: {e}")))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            warn!("{tool} execution warning: {}", stderr);
+        } else {
+            info!("✅ Code formatting completed");
+        }
+        Ok(())
+    }
+
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        vec![
+            PROTOC_TOOL,
+            RequiredTool::required(ES_PLUGIN, "npm install (protoc-gen-es is a devDependency)"),
+            RequiredTool::optional("prettier", "npm install --save-dev prettier"),
+            RequiredTool::optional("tsc", "npm install --save-dev typescript"),
+        ]
+    }
+
+    async fn validate_code(&self, context: &GenContext) -> Result<()> {
+        let tsc = self.project_root(context).join("node_modules/.bin/tsc");
+        if !tsc.exists() {
+            info!("💡 TypeScript compiler not installed locally, skipping validation");
+            return Ok(());
+        }
+
+        info!("🔍 Validating generated code...");
+        let output = TokioCommand::new(&tsc)
+            .arg("--noEmit")
+            .current_dir(self.project_root(context))
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| ActrCliError::command_error(format!("Failed to execute tsc: {e}")))?;
+
+        if !output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            warn!("Generated code has type errors:\n{}", stdout);
+        } else {
+            info!("✅ Code validation passed");
+        }
+        Ok(())
+    }
+
+    fn print_next_steps(&self, context: &GenContext) {
+        let steps = [
+            tr!("gen.view_code", path = context.output.display()),
+            tr!("gen.typescript.use_refs"),
+            tr!("gen.typescript.dev"),
+        ];
+        print_steps(
+            &tr!("gen.completed_language", language = "TypeScript"),
+            &steps,
+            None,
+        );
+    }
+}
+
+/// One ActorRef method; streaming requests and responses are async iterables
+fn actor_ref_method(method: &ProtoMethod, full_service: &str) -> String {
+    let method_camel = to_camel_case(&method.name);
+    let route = format!("{full_service}.{}", method.name);
+    let (input, output) = (&method.input_type, &method.output_type);
+    let guard = |caller: &str, kind: &str| {
+        format!(
+            r#"    if (!this.caller.{caller}) {{
+      throw new Error("RawCaller does not support {kind} streaming ({route})");
+    }}
+"#
+        )
+    };
+    match (method.client_streaming, method.server_streaming) {
+        (false, false) => format!(
+            r#"
+  async {method_camel}(request: {input}): Promise<{output}> {{
+    const response = await this.caller.callRaw(
+      this.targetType,
+      "{route}",
+      request.toBinary(),
+    );
+    return {output}.fromBinary(response);
+  }}
+"#
+        ),
+        (false, true) => format!(
+            r#"
+  async *{method_camel}(request: {input}): AsyncGenerator<{output}> {{
+{guard}    const responses = this.caller.callServerStream(
+      this.targetType,
+      "{route}",
+      request.toBinary(),
+    );
+    for await (const response of responses) {{
+      yield {output}.fromBinary(response);
+    }}
+  }}
+"#,
+            guard = guard("callServerStream", "server")
+        ),
+        (true, false) => format!(
+            r#"
+  async {method_camel}(requests: AsyncIterable<{input}>): Promise<{output}> {{
+{guard}    const response = await this.caller.callClientStream(
+      this.targetType,
+      "{route}",
+      encodeStream(requests),
+    );
+    return {output}.fromBinary(response);
+  }}
+"#,
+            guard = guard("callClientStream", "client")
+        ),
+        (true, true) => format!(
+            r#"
+  async *{method_camel}(requests: AsyncIterable<{input}>): AsyncGenerator<{output}> {{
+{guard}    const responses = this.caller.callBidiStream(
+      this.targetType,
+      "{route}",
+      encodeStream(requests),
+    );
+    for await (const response of responses) {{
+      yield {output}.fromBinary(response);
+    }}
+  }}
+"#,
+            guard = guard("callBidiStream", "bidirectional")
+        ),
+    }
+}
+
+/// Services of a parsed proto file, with short type names for the `_pb` imports
+fn services_from_schema(
+    schema: &ProtoSchema,
+    pb_module: &str,
+    target_type: &str,
+) -> Vec<ProtoService> {
+    schema
+        .services
+        .iter()
+        .map(|service| ProtoService {
+            name: service.name.clone(),
+            package: schema.package.clone().unwrap_or_default(),
+            pb_module: pb_module.to_string(),
+            target_type: target_type.to_string(),
+            methods: service
+                .methods
+                .iter()
+                .map(|rpc| ProtoMethod {
+                    name: rpc.name.clone(),
+                    input_type: short_type_name(&rpc.input_type),
+                    output_type: short_type_name(&rpc.output_type),
+                    client_streaming: rpc.client_streaming,
+                    server_streaming: rpc.server_streaming,
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// "echo.EchoRequest" -> "EchoRequest"
+fn short_type_name(type_name: &str) -> String {
+    type_name
+        .trim()
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// "SendFile" -> "sendFile"
+fn to_camel_case(name: &str) -> String {
+    let pascal = to_pascal_case(name);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::codegen::descriptor;
+
+    const ECHO_PROTO: &str = r#"
+syntax = "proto3";
+package echo;
+
+// Comments mentioning rpc Fake(A) returns (B) are ignored
+service EchoService {
+  rpc Echo(EchoRequest) returns (EchoResponse);
+  rpc Ping (echo.PingRequest) returns (echo.PingResponse) {
+    option deprecated = true;
+  }
+  rpc Watch(EchoRequest) returns (stream EchoResponse);
+}
+"#;
+
+    fn echo_services() -> Vec<ProtoService> {
+        let schema = ProtoSchema::parse(ECHO_PROTO).unwrap();
+        services_from_schema(&schema, "local/echo_pb", "acme+EchoService")
+    }
+
+    #[test]
+    fn test_services_from_schema() {
+        let services = echo_services();
+        assert_eq!(services.len(), 1);
+
+        let service = &services[0];
+        assert_eq!(service.name, "EchoService");
+        assert_eq!(service.package, "echo");
+        assert_eq!(service.methods.len(), 3);
+        assert_eq!(service.methods[1].name, "Ping");
+        assert_eq!(service.methods[1].input_type, "PingRequest");
+        assert_eq!(service.methods[1].output_type, "PingResponse");
+        assert!(service.methods[2].server_streaming);
+        assert!(!service.methods[2].client_streaming);
+    }
+
+    #[test]
+    fn test_generate_actor_ref() {
+        let services = echo_services();
+        let content = TypescriptGenerator.generate_actor_ref(&services[0]);
+
+        assert!(content.contains("export class EchoServiceRef"));
+        assert!(content.contains("async echo(request: EchoRequest): Promise<EchoResponse>"));
+        assert!(content.contains("\"echo.EchoService.Ping\""));
+        assert!(content.contains("from \"./local/echo_pb\""));
+        assert!(
+            content.contains("async *watch(request: EchoRequest): AsyncGenerator<EchoResponse>")
+        );
+        assert!(content.contains("this.caller.callServerStream("));
+        assert!(content.contains("import type { RawCaller } from \"./index\";"));
+    }
+
+    #[test]
+    fn test_actor_ref_from_descriptor_streaming_rpcs() {
+        let schema = descriptor::schema_from_descriptor(&descriptor::tests::echo_descriptor());
+        let services = services_from_schema(&schema, "local/echo_pb", "acme+EchoService");
+        let content = TypescriptGenerator.generate_actor_ref(&services[0]);
+
+        assert!(content.contains("async echo(request: EchoRequest): Promise<Inner>"));
+        assert!(
+            content.contains("async upload(requests: AsyncIterable<EchoRequest>): Promise<Inner>")
+        );
+        assert!(content.contains("this.caller.callClientStream("));
+        assert!(content.contains("this.caller.callBidiStream("));
+        assert!(content.contains("\"echo.EchoService.Chat\""));
+        assert!(content.contains("import { encodeStream, type RawCaller } from \"./index\";"));
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("SendFile"), "sendFile");
+        assert_eq!(to_camel_case("echo"), "echo");
+    }
+}
@@ -0,0 +1,167 @@
+//! Shell completions command
+//!
+//! `actr completions <shell>` prints a clap_complete script. For bash, zsh and
+//! fish the script is followed by a small wrapper that completes service names
+//! for `install`, `remove` and `update` by calling `actr completions --services`.
+//! Service names come from the Actr.toml dependencies, the proto cache under
+//! `protos/remote/` and the last `actr discovery` results.
+
+use actr_config::ConfigParser;
+use anyhow::Result;
+use clap::Args;
+use clap_complete::Shell;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const DISCOVERY_CACHE_FILE: &str = ".actr/discovery-cache.json";
+
+/// Subcommands whose positional arguments are service names
+const SERVICE_SUBCOMMANDS: &[&str] = &["install", "remove", "update"];
+
+#[derive(Args, Debug, Clone)]
+pub struct CompletionsCommand {
+    /// Shell to generate the completion script for
+    #[arg(value_enum, required_unless_present = "services")]
+    pub shell: Option<Shell>,
+
+    /// Print known service names, one per line (used by the completion scripts)
+    #[arg(long, hide = true)]
+    pub services: bool,
+}
+
+impl CompletionsCommand {
+    /// Write the completion script (or the service list) for the `actr` command tree
+    pub fn write_to(&self, mut command: clap::Command, out: &mut dyn Write) -> Result<()> {
+        if self.services {
+            for name in known_service_names(Path::new(".")) {
+                writeln!(out, "{name}")?;
+            }
+            return Ok(());
+        }
+
+        let Some(shell) = self.shell else {
+            anyhow::bail!("a shell is required (bash, zsh, fish, powershell, elvish)");
+        };
+        let bin_name = command.get_name().to_string();
+        clap_complete::generate(shell, &mut command, &bin_name, out);
+        if let Some(helper) = dynamic_helper(shell, &bin_name) {
+            writeln!(out)?;
+            write!(out, "{helper}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Remember service names seen by `actr discovery` for later completion
+pub fn save_discovery_cache(project_dir: &Path, names: &[String]) -> std::io::Result<()> {
+    let path = project_dir.join(DISCOVERY_CACHE_FILE);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(names).map_err(std::io::Error::other)?;
+    std::fs::write(path, content)
+}
+
+/// Service names offered by the dynamic completion helpers
+pub fn known_service_names(project_dir: &Path) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+
+    if let Ok(config) = ConfigParser::from_file(project_dir.join("Actr.toml")) {
+        for dependency in &config.dependencies {
+            names.insert(dependency.alias.clone());
+            names.insert(dependency.name.clone());
+        }
+    }
+
+    let remote_dir: PathBuf = project_dir.join("protos").join("remote");
+    if let Ok(entries) = std::fs::read_dir(remote_dir) {
+        for entry in entries.flatten() {
+            if entry.path().is_dir()
+                && let Some(name) = entry.file_name().to_str()
+            {
+                names.insert(name.to_string());
+            }
+        }
+    }
+
+    if let Ok(content) = std::fs::read_to_string(project_dir.join(DISCOVERY_CACHE_FILE))
+        && let Ok(cached) = serde_json::from_str::<Vec<String>>(&content)
+    {
+        names.extend(cached);
+    }
+
+    names.retain(|name| !name.is_empty());
+    names
+}
+
+/// Shell snippet that adds service-name completion on top of the static script
+fn dynamic_helper(shell: Shell, bin: &str) -> Option<String> {
+    let subcommands = SERVICE_SUBCOMMANDS.join(" ");
+    match shell {
+        Shell::Bash => Some(format!(
+            r#"_{bin}_with_services() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    if [[ ${{COMP_CWORD}} -ge 2 && " {subcommands} " == *" ${{COMP_WORDS[1]}} "* && "${{cur}}" != -* ]]; then
+        COMPREPLY=( $(compgen -W "$({bin} completions --services 2>/dev/null)" -- "${{cur}}") )
+        return 0
+    fi
+    _{bin} "$@"
+}}
+complete -F _{bin}_with_services -o bashdefault -o default {bin}
+"#
+        )),
+        Shell::Zsh => Some(format!(
+            r#"_{bin}_with_services() {{
+    if (( CURRENT > 2 )) && [[ " {subcommands} " == *" ${{words[2]}} "* && "${{words[CURRENT]}}" != -* ]]; then
+        local -a services
+        services=(${{(f)"$({bin} completions --services 2>/dev/null)"}})
+        compadd -a services
+        return
+    fi
+    _{bin} "$@"
+}}
+compdef _{bin}_with_services {bin}
+"#
+        )),
+        Shell::Fish => Some(format!(
+            "complete -c {bin} -n '__fish_seen_subcommand_from {subcommands}' -f -a '({bin} completions --services 2>/dev/null)'\n"
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_known_service_names_merges_sources() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("protos/remote/user-service")).unwrap();
+        save_discovery_cache(
+            temp.path(),
+            &["order-service".to_string(), "user-service".to_string()],
+        )
+        .unwrap();
+
+        let names: Vec<String> = known_service_names(temp.path()).into_iter().collect();
+        assert_eq!(names, vec!["order-service", "user-service"]);
+    }
+
+    #[test]
+    fn test_script_includes_service_helper() {
+        let command = CompletionsCommand {
+            shell: Some(Shell::Bash),
+            services: false,
+        };
+        let cli = clap::Command::new("actr").subcommand(clap::Command::new("install"));
+        let mut out = Vec::new();
+        command.write_to(cli, &mut out).unwrap();
+
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("_actr()"));
+        assert!(script.contains("actr completions --services"));
+    }
+}
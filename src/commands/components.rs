@@ -0,0 +1,97 @@
+//! Components command - show how the service container provides each component
+//!
+//! Lists every `ComponentType` with whether it is available, whether it was
+//! registered explicitly (e.g. a `--component` fixture) or comes from the defaults,
+//! the implementation backing it and its key settings. Commands fail with
+//! "<Component> is required but not registered" when one they need is missing;
+//! outside a project that is ConfigManager and ServiceDiscovery, which read Actr.toml.
+
+use crate::core::{Command, CommandContext, CommandResult, ComponentStatus, ComponentType};
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Args;
+use comfy_table::{Attribute, Cell, Color, Table};
+use owo_colors::OwoColorize;
+
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Show the components available to commands",
+    long_about = "List every component of the service container: whether it is available, whether it is registered explicitly (--component) or created from the defaults, the implementation backing it and key settings such as the config file, cache directory and registry URL.\n\nExamples:\n  actr components\n  actr components --offline            # Show the offline implementations\n  actr --config other/Actr.toml components"
+)]
+pub struct ComponentsCommand {}
+
+#[async_trait]
+impl Command for ComponentsCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let statuses = context.container.component_status();
+        if context.is_json() {
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &statuses,
+            )?));
+        }
+
+        println!("🧩 Components\n");
+        println!("{}", render_table(&statuses));
+
+        let missing: Vec<&str> = statuses
+            .iter()
+            .filter(|status| !status.available)
+            .map(|status| status.component)
+            .collect();
+        if missing.is_empty() {
+            return Ok(CommandResult::Success(format!(
+                "{} All {} components are available",
+                "✅".green(),
+                statuses.len()
+            )));
+        }
+        Ok(CommandResult::Success(format!(
+            "{} Unavailable: {}. Run inside a project or pass --config <Actr.toml>",
+            "⚠️".yellow(),
+            missing.join(", ")
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![]
+    }
+
+    fn name(&self) -> &str {
+        "components"
+    }
+
+    fn description(&self) -> &str {
+        "Show how the container provides each component"
+    }
+}
+
+fn render_table(statuses: &[ComponentStatus]) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Component").add_attribute(Attribute::Bold),
+        Cell::new("Status").add_attribute(Attribute::Bold),
+        Cell::new("Implementation").add_attribute(Attribute::Bold),
+        Cell::new("Details").add_attribute(Attribute::Bold),
+    ]);
+
+    for status in statuses {
+        let source = if status.available {
+            Cell::new(format!("✔ {}", status.source)).fg(Color::Green)
+        } else {
+            Cell::new(format!("✘ {}", status.source)).fg(Color::Red)
+        };
+        let details: Vec<String> = status
+            .details
+            .iter()
+            .map(|(key, value)| format!("{key}: {value}"))
+            .collect();
+        table.add_row(vec![
+            Cell::new(status.component),
+            source,
+            Cell::new(status.implementation.as_deref().unwrap_or("-")),
+            Cell::new(details.join("\n")),
+        ]);
+    }
+
+    table
+}
@@ -6,15 +6,27 @@
 //! - `actr config list` - List all configuration keys
 //! - `actr config show` - Show full configuration
 //! - `actr config unset <key>` - Remove a configuration value
-//! - `actr config test` - Test configuration file syntax
-
-use crate::core::{Command, CommandContext, CommandResult, ComponentType};
-use actr_config::{ConfigParser, RawConfig};
+//! - `actr config test` - Test configuration file syntax and schema
+//!
+//! Dependency keys (`dependencies.<alias>[.<field>]`) are resolved through the parsed
+//! `actr_config::Config`. Writes go through [`ConfigEditor`], so comments and the
+//! layout of untouched keys survive, and are re-validated with `ConfigParser` so an
+//! edit that breaks the file is rolled back.
+
+use crate::core::{
+    Command, CommandContext, CommandResult, ComponentType, ConfigEditor, config_schema,
+};
+use actr_config::{Config, ConfigParser, RawConfig};
+use actr_protocol::ActrTypeExt;
 use anyhow::{Result, bail};
 use async_trait::async_trait;
 use clap::{Args, Subcommand};
 use owo_colors::OwoColorize;
 use std::path::Path;
+use toml_edit::{Array, InlineTable, Value};
+
+/// Dependency fields editable with `actr config set dependencies.<alias>.<field>`
+const DEPENDENCY_FIELDS: &[&str] = &["name", "actr_type", "fingerprint"];
 
 #[derive(Args, Clone)]
 pub struct ConfigCommand {
@@ -114,13 +126,22 @@ impl ConfigCommand {
             );
         }
 
-        let mut raw_config = RawConfig::from_file(config_path)?;
+        let original = std::fs::read_to_string(config_path)?;
 
-        // Parse the key path and set the value
-        self.set_nested_value(&mut raw_config, key, value)?;
-
-        // Save the updated configuration
-        raw_config.save_to_file(config_path)?;
+        if let ["dependencies", alias, field] = key.split('.').collect::<Vec<_>>().as_slice() {
+            self.edit_dependency_field(config_path, alias, field, Some(value))?;
+        } else {
+            let Some((path, kind)) = resolve_key(key) else {
+                bail!(
+                    "Unknown or unsupported configuration key: {}\n\n💡 Hint: Run 'actr config list' to see available keys",
+                    key
+                );
+            };
+            let mut editor = ConfigEditor::open(Path::new(config_path))?;
+            editor.set(&path, kind.parse(key, value)?)?;
+            editor.save()?;
+        }
+        self.validate_or_restore(config_path, &original)?;
 
         Ok(CommandResult::Success(format!(
             "{} Configuration updated: {} = {}",
@@ -136,6 +157,15 @@ impl ConfigCommand {
             bail!("Configuration file not found: {}", config_path);
         }
 
+        if let ["dependencies", alias, rest @ ..] = key.split('.').collect::<Vec<_>>().as_slice() {
+            let config = ConfigParser::from_file(config_path)?;
+            return Ok(CommandResult::Success(Self::get_dependency_value(
+                &config,
+                alias,
+                rest.first().copied(),
+            )?));
+        }
+
         let raw_config = RawConfig::from_file(config_path)?;
 
         // Get the nested value
@@ -201,16 +231,38 @@ impl ConfigCommand {
             }
         }
 
-        // Dependencies
-        if !raw_config.dependencies.is_empty() {
-            output.push_str(&format!(
-                "\n  {} Dependencies ({}):\n",
-                "🔗".blue(),
-                raw_config.dependencies.len()
-            ));
-            for key in raw_config.dependencies.keys() {
-                output.push_str(&format!("    dependencies.{}\n", key));
+        // Dependencies (resolved through Config so aliases, names and types are shown)
+        match ConfigParser::from_file(config_path) {
+            Ok(config) if !config.dependencies.is_empty() => {
+                output.push_str(&format!(
+                    "\n  {} Dependencies ({}):\n",
+                    "🔗".blue(),
+                    config.dependencies.len()
+                ));
+                for dependency in &config.dependencies {
+                    let actr_type = dependency
+                        .actr_type
+                        .as_ref()
+                        .map(|t| t.to_string_repr())
+                        .unwrap_or_else(|| dependency.name.clone());
+                    output.push_str(&format!(
+                        "    dependencies.{}  ({})\n",
+                        dependency.alias, actr_type
+                    ));
+                }
+            }
+            Ok(_) => {}
+            Err(_) if !raw_config.dependencies.is_empty() => {
+                output.push_str(&format!(
+                    "\n  {} Dependencies ({}):\n",
+                    "🔗".blue(),
+                    raw_config.dependencies.len()
+                ));
+                for key in raw_config.dependencies.keys() {
+                    output.push_str(&format!("    dependencies.{}\n", key));
+                }
             }
+            Err(_) => {}
         }
 
         // Scripts
@@ -252,13 +304,30 @@ impl ConfigCommand {
             bail!("Configuration file not found: {}", config_path);
         }
 
-        let mut raw_config = RawConfig::from_file(config_path)?;
+        let original = std::fs::read_to_string(config_path)?;
 
-        // Remove the nested value
-        self.unset_nested_value(&mut raw_config, key)?;
-
-        // Save the updated configuration
-        raw_config.save_to_file(config_path)?;
+        if let ["dependencies", alias, field] = key.split('.').collect::<Vec<_>>().as_slice() {
+            self.edit_dependency_field(config_path, alias, field, None)?;
+        } else {
+            let path = match key.split('.').collect::<Vec<_>>().as_slice() {
+                ["package", "name"]
+                | ["package", "actr_type", "manufacturer"]
+                | ["package", "actr_type", "name"] => {
+                    bail!("Cannot unset required configuration key: {}", key);
+                }
+                ["dependencies", alias] => vec!["dependencies", *alias],
+                _ => match resolve_key(key) {
+                    Some((path, _)) => path,
+                    None => bail!("Cannot unset configuration key: {}", key),
+                },
+            };
+            let mut editor = ConfigEditor::open(Path::new(config_path))?;
+            if !editor.remove(&path) {
+                bail!("Configuration key not set: {}", key);
+            }
+            editor.save()?;
+        }
+        self.validate_or_restore(config_path, &original)?;
 
         Ok(CommandResult::Success(format!(
             "{} Configuration key '{}' removed successfully",
@@ -280,6 +349,17 @@ impl ConfigCommand {
             config_path
         ));
 
+        // Schema validation first: it reports every problem with its key and position
+        let issues = config_schema::validate_file(Path::new(config_path));
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues.iter().map(|issue| format!("   {issue}")).collect();
+            bail!(
+                "Configuration validation failed with {} problem(s):\n{}",
+                issues.len(),
+                issues.join("\n")
+            );
+        }
+
         // Test 1: Raw TOML parsing
         let raw_config = match RawConfig::from_file(config_path) {
             Ok(config) => {
@@ -358,96 +438,76 @@ impl ConfigCommand {
         Ok(CommandResult::Success(output))
     }
 
-    /// Set a nested configuration value using dot notation
-    fn set_nested_value(&self, config: &mut RawConfig, key: &str, value: &str) -> Result<()> {
-        let parts: Vec<&str> = key.split('.').collect();
-
-        match parts.as_slice() {
-            // Package configuration
-            ["package", "name"] => config.package.name = value.to_string(),
-            ["package", "description"] => config.package.description = Some(value.to_string()),
-            ["package", "actr_type", "manufacturer"] => {
-                config.package.actr_type.manufacturer = value.to_string()
-            }
-            ["package", "actr_type", "name"] => config.package.actr_type.name = value.to_string(),
-
-            // System signaling configuration
-            ["signaling", "url"] | ["system", "signaling", "url"] => {
-                config.system.signaling.url = Some(value.to_string())
-            }
-
-            // Deployment configuration
-            ["deployment", "realm_id"] | ["system", "deployment", "realm_id"] => {
-                config.system.deployment.realm_id = Some(
-                    value
-                        .parse()
-                        .map_err(|_| anyhow::anyhow!("deployment.realm_id must be a number"))?,
-                );
-            }
-
-            // Discovery configuration
-            ["discovery", "visible"] | ["system", "discovery", "visible"] => {
-                config.system.discovery.visible = Some(
-                    value
-                        .parse()
-                        .map_err(|_| anyhow::anyhow!("discovery.visible must be true or false"))?,
-                );
-            }
-
-            // Storage configuration
-            ["storage", "mailbox_path"] | ["system", "storage", "mailbox_path"] => {
-                config.system.storage.mailbox_path = Some(value.into());
-            }
+    /// Re-parse the written file and roll back to `original` if it no longer validates
+    fn validate_or_restore(&self, config_path: &str, original: &str) -> Result<()> {
+        if let Err(e) = ConfigParser::from_file(config_path) {
+            std::fs::write(config_path, original)?;
+            bail!("Change rejected, configuration would be invalid: {}", e);
+        }
+        Ok(())
+    }
 
-            // WebRTC configuration
-            ["webrtc", "stun_urls"] | ["system", "webrtc", "stun_urls"] => {
-                let urls: Vec<String> = value.split(',').map(|s| s.trim().to_string()).collect();
-                config.system.webrtc.stun_urls = urls;
-            }
-            ["webrtc", "turn_urls"] | ["system", "webrtc", "turn_urls"] => {
-                let urls: Vec<String> = value.split(',').map(|s| s.trim().to_string()).collect();
-                config.system.webrtc.turn_urls = urls;
-            }
-            ["webrtc", "force_relay"] | ["system", "webrtc", "force_relay"] => {
-                config.system.webrtc.force_relay = value
-                    .parse()
-                    .map_err(|_| anyhow::anyhow!("webrtc.force_relay must be true or false"))?;
-            }
+    /// Look up a dependency (by alias) in the parsed configuration
+    fn get_dependency_value(config: &Config, alias: &str, field: Option<&str>) -> Result<String> {
+        let Some(dependency) = config.dependencies.iter().find(|d| d.alias == alias) else {
+            bail!("Dependency not found: {}", alias);
+        };
+        let actr_type = dependency
+            .actr_type
+            .as_ref()
+            .map(|t| t.to_string_repr())
+            .unwrap_or_default();
+        let fingerprint = dependency.fingerprint.clone().unwrap_or_default();
+
+        Ok(match field {
+            None => format!(
+                "{{ name={}, actr_type={}, fingerprint={} }}",
+                dependency.name, actr_type, fingerprint
+            ),
+            Some("name") => dependency.name.clone(),
+            Some("actr_type") => actr_type,
+            Some("fingerprint") => fingerprint,
+            Some(other) => bail!(
+                "Unknown dependency field: {} (expected one of: {})",
+                other,
+                DEPENDENCY_FIELDS.join(", ")
+            ),
+        })
+    }
 
-            // Observability configuration
-            ["observability", "filter_level"] | ["system", "observability", "filter_level"] => {
-                config.system.observability.filter_level = Some(value.to_string());
-            }
-            ["observability", "tracing_enabled"]
-            | ["system", "observability", "tracing_enabled"] => {
-                config.system.observability.tracing_enabled =
-                    Some(value.parse().map_err(|_| {
-                        anyhow::anyhow!("observability.tracing_enabled must be true or false")
-                    })?);
-            }
-            ["observability", "tracing_endpoint"]
-            | ["system", "observability", "tracing_endpoint"] => {
-                config.system.observability.tracing_endpoint = Some(value.to_string());
-            }
-            ["observability", "tracing_service_name"]
-            | ["system", "observability", "tracing_service_name"] => {
-                config.system.observability.tracing_service_name = Some(value.to_string());
-            }
+    /// Set (`Some`) or remove (`None`) a field of `[dependencies].<alias>`, preserving formatting
+    fn edit_dependency_field(
+        &self,
+        config_path: &str,
+        alias: &str,
+        field: &str,
+        value: Option<&str>,
+    ) -> Result<()> {
+        if !DEPENDENCY_FIELDS.contains(&field) {
+            bail!(
+                "Unknown dependency field: {} (expected one of: {})",
+                field,
+                DEPENDENCY_FIELDS.join(", ")
+            );
+        }
 
-            // Scripts configuration
-            ["scripts", script_name] => {
-                config
-                    .scripts
-                    .insert(script_name.to_string(), value.to_string());
+        let mut editor = ConfigEditor::open(Path::new(config_path))?;
+        let Some(entry) = editor.get(&["dependencies", alias]) else {
+            bail!("Dependency not found: {}", alias);
+        };
+        if entry.as_table_like().is_none() {
+            editor.set(
+                &["dependencies", alias],
+                Value::InlineTable(InlineTable::new()),
+            )?;
+        }
+        match value {
+            Some(value) => editor.set(&["dependencies", alias, field], Value::from(value))?,
+            None => {
+                editor.remove(&["dependencies", alias, field]);
             }
-
-            _ => bail!(
-                "Unknown or unsupported configuration key: {}\n\n💡 Hint: Run 'actr config list' to see available keys",
-                key
-            ),
         }
-
-        Ok(())
+        editor.save()
     }
 
     /// Get a nested configuration value using dot notation
@@ -580,83 +640,200 @@ impl ConfigCommand {
 
         Ok(value)
     }
+}
 
-    /// Remove a nested configuration value using dot notation
-    fn unset_nested_value(&self, config: &mut RawConfig, key: &str) -> Result<()> {
-        let parts: Vec<&str> = key.split('.').collect();
+/// Type of the value `actr config set` writes for a key
+#[derive(Debug, Clone, Copy)]
+enum ValueKind {
+    String,
+    Integer,
+    Bool,
+    /// Comma-separated on the command line
+    List,
+}
 
-        match parts.as_slice() {
-            // Package optional fields
-            ["package", "description"] => config.package.description = None,
+impl ValueKind {
+    fn parse(self, key: &str, value: &str) -> Result<Value> {
+        Ok(match self {
+            ValueKind::String => Value::from(value),
+            ValueKind::Integer => Value::from(
+                value
+                    .parse::<i64>()
+                    .map_err(|_| anyhow::anyhow!("{} must be a number", key))?,
+            ),
+            ValueKind::Bool => Value::from(
+                value
+                    .parse::<bool>()
+                    .map_err(|_| anyhow::anyhow!("{} must be true or false", key))?,
+            ),
+            ValueKind::List => Value::Array(value.split(',').map(str::trim).collect::<Array>()),
+        })
+    }
+}
 
-            // System signaling configuration
-            ["signaling", "url"] | ["system", "signaling", "url"] => {
-                config.system.signaling.url = None
-            }
+/// TOML path and value type of a key outside `[dependencies]`
+///
+/// System keys may leave out the `system.` prefix (`signaling.url`).
+fn resolve_key(key: &str) -> Option<(Vec<&str>, ValueKind)> {
+    let parts: Vec<&str> = key.split('.').collect();
+    match parts.as_slice() {
+        ["package", "name" | "description"]
+        | ["package", "actr_type", "manufacturer" | "name"]
+        | ["scripts", _] => return Some((parts, ValueKind::String)),
+        ["package" | "scripts" | "dependencies", ..] => return None,
+        _ => {}
+    }
 
-            // Deployment configuration
-            ["deployment", "realm_id"] | ["system", "deployment", "realm_id"] => {
-                config.system.deployment.realm_id = None
-            }
+    let path: Vec<&str> = if parts.first() == Some(&"system") {
+        parts
+    } else {
+        std::iter::once("system").chain(parts).collect()
+    };
+    let kind = match &path[1..] {
+        ["signaling", "url"]
+        | ["storage", "mailbox_path"]
+        | [
+            "observability",
+            "filter_level" | "tracing_endpoint" | "tracing_service_name",
+        ] => ValueKind::String,
+        ["deployment", "realm_id"] => ValueKind::Integer,
+        ["discovery", "visible"]
+        | ["webrtc", "force_relay"]
+        | ["observability", "tracing_enabled"] => ValueKind::Bool,
+        ["webrtc", "stun_urls" | "turn_urls"] => ValueKind::List,
+        _ => return None,
+    };
+    Some((path, kind))
+}
 
-            // Discovery configuration
-            ["discovery", "visible"] | ["system", "discovery", "visible"] => {
-                config.system.discovery.visible = None
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
 
-            // Storage configuration
-            ["storage", "mailbox_path"] | ["system", "storage", "mailbox_path"] => {
-                config.system.storage.mailbox_path = None
-            }
+    const CONFIG: &str = r#"edition = 1
+exports = []
 
-            // WebRTC configuration
-            ["webrtc", "stun_urls"] | ["system", "webrtc", "stun_urls"] => {
-                config.system.webrtc.stun_urls = vec![]
-            }
-            ["webrtc", "turn_urls"] | ["system", "webrtc", "turn_urls"] => {
-                config.system.webrtc.turn_urls = vec![]
-            }
-            ["webrtc", "force_relay"] | ["system", "webrtc", "force_relay"] => {
-                config.system.webrtc.force_relay = false
-            }
+[package]
+name = "demo"
 
-            // Observability configuration
-            ["observability", "filter_level"] | ["system", "observability", "filter_level"] => {
-                config.system.observability.filter_level = None
-            }
-            ["observability", "tracing_enabled"]
-            | ["system", "observability", "tracing_enabled"] => {
-                config.system.observability.tracing_enabled = None
-            }
-            ["observability", "tracing_endpoint"]
-            | ["system", "observability", "tracing_endpoint"] => {
-                config.system.observability.tracing_endpoint = None
-            }
-            ["observability", "tracing_service_name"]
-            | ["system", "observability", "tracing_service_name"] => {
-                config.system.observability.tracing_service_name = None
-            }
+[package.actr_type]
+manufacturer = "acme"
+name = "demo-service"
 
-            // Scripts configuration
-            ["scripts", script_name] => {
-                config.scripts.remove(*script_name);
-            }
+[dependencies]
+echo = { actr_type = "acme+EchoService" }
 
-            // Dependencies configuration
-            ["dependencies", dep_name] => {
-                config.dependencies.remove(*dep_name);
-            }
+[system.signaling]
+url = "ws://127.0.0.1:8080"
 
-            // Cannot unset required fields
-            ["package", "name"]
-            | ["package", "actr_type", "manufacturer"]
-            | ["package", "actr_type", "name"] => {
-                bail!("Cannot unset required configuration key: {}", key);
-            }
+[system.deployment]
+realm_id = 1
+"#;
 
-            _ => bail!("Cannot unset configuration key: {}", key),
+    fn command(path: &Path) -> ConfigCommand {
+        ConfigCommand {
+            config_file: Some(path.display().to_string()),
+            command: ConfigSubcommand::List,
         }
+    }
 
-        Ok(())
+    fn success(result: CommandResult) -> String {
+        match result {
+            CommandResult::Success(msg) => msg,
+            _ => panic!("expected a success result"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_and_get_signaling_url() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Actr.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+        let cmd = command(&path);
+        let path_str = cmd.config_path().to_string();
+
+        cmd.set_config(&path_str, "system.signaling.url", "ws://example.com:9000")
+            .await
+            .unwrap();
+        let value = success(cmd.get_config(&path_str, "signaling.url").await.unwrap());
+        assert_eq!(value, "ws://example.com:9000");
+    }
+
+    #[tokio::test]
+    async fn test_set_and_unset_keep_comments() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Actr.toml");
+        let commented = CONFIG
+            .replace("[package]\n", "# The service itself\n[package]\n")
+            .replace(
+                "url = \"ws://127.0.0.1:8080\"",
+                "url = \"ws://127.0.0.1:8080\" # local signaling",
+            );
+        std::fs::write(&path, &commented).unwrap();
+        let cmd = command(&path);
+        let path_str = cmd.config_path().to_string();
+
+        cmd.set_config(&path_str, "signaling.url", "ws://example.com:9000")
+            .await
+            .unwrap();
+        cmd.set_config(&path_str, "webrtc.stun_urls", "stun:a:3478, stun:b:3478")
+            .await
+            .unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            written,
+            commented.replace("ws://127.0.0.1:8080", "ws://example.com:9000")
+                + "\n[system.webrtc]\nstun_urls = [\"stun:a:3478\", \"stun:b:3478\"]\n"
+        );
+
+        cmd.unset_config(&path_str, "webrtc.stun_urls")
+            .await
+            .unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(!written.contains("stun_urls"));
+        assert!(written.contains("# The service itself\n[package]\n"));
+        assert!(
+            cmd.unset_config(&path_str, "scripts.missing")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dependency_fields_use_parsed_config() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Actr.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+        let cmd = command(&path);
+        let path_str = cmd.config_path().to_string();
+
+        let actr_type = success(
+            cmd.get_config(&path_str, "dependencies.echo.actr_type")
+                .await
+                .unwrap(),
+        );
+        assert_eq!(actr_type, "acme+EchoService");
+
+        cmd.set_config(&path_str, "dependencies.echo.fingerprint", "sha256:abc")
+            .await
+            .unwrap();
+        let fingerprint = success(
+            cmd.get_config(&path_str, "dependencies.echo.fingerprint")
+                .await
+                .unwrap(),
+        );
+        assert_eq!(fingerprint, "sha256:abc");
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("url = \"ws://127.0.0.1:8080\"")
+        );
+
+        assert!(
+            cmd.get_config(&path_str, "dependencies.missing")
+                .await
+                .is_err()
+        );
     }
 }
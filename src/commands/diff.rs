@@ -0,0 +1,494 @@
+//! Diff command implementation - detect breaking proto changes
+//!
+//! Compares the protos cached under `protos/remote/<service>/` with the version
+//! currently published in the registry and classifies every difference as
+//! breaking (removed RPCs, messages or fields, changed field types or numbers)
+//! or additive. Breaking changes make the command exit non-zero so it can gate
+//! CI pipelines before `actr update`.
+
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType, ProtoFile};
+use crate::human_println;
+use crate::proto_parser::{EnumDef, FieldDef, FieldLabel, MessageDef, ProtoSchema, ServiceDef};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// Diff command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Show proto changes between installed and remote services",
+    long_about = "Compare cached protos with the versions currently published in the registry and report breaking and additive changes.\n\nExit status is non-zero when breaking changes are found (or any change with --strict).\n\nExamples:\n  actr diff user-service        # Compare one dependency\n  actr diff                     # Compare every dependency\n  actr diff --strict --json     # Fail on any change, machine-readable"
+)]
+pub struct DiffCommand {
+    /// Dependencies to compare (alias or service name); defaults to every dependency
+    #[arg(value_name = "SERVICE")]
+    pub services: Vec<String>,
+
+    /// Also fail when only additive changes are found
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Breaking,
+    Additive,
+}
+
+/// One difference between two versions of a service's protos
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    pub message: String,
+}
+
+impl SchemaChange {
+    fn breaking(message: impl Into<String>) -> Self {
+        Self {
+            kind: ChangeKind::Breaking,
+            message: message.into(),
+        }
+    }
+
+    fn additive(message: impl Into<String>) -> Self {
+        Self {
+            kind: ChangeKind::Additive,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ServiceDiff {
+    service: String,
+    changes: Vec<SchemaChange>,
+}
+
+#[async_trait]
+impl Command for DiffCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        if context.is_offline() {
+            return Err(ActrCliError::InvalidArgument {
+                message: "'actr diff' compares against the registry; run it without --offline"
+                    .to_string(),
+            }
+            .into());
+        }
+
+        let (config_manager, service_discovery, cache_manager) = (
+            context.container.get_config_manager()?,
+            context.container.get_service_discovery()?,
+            context.container.get_cache_manager()?,
+        );
+        let project_root = config_manager.get_project_root().to_path_buf();
+        let config = config_manager
+            .load_config(project_root.join("Actr.toml").as_path())
+            .await?;
+
+        for requested in &self.services {
+            if !config
+                .dependencies
+                .iter()
+                .any(|d| &d.alias == requested || &d.name == requested)
+            {
+                return Err(ActrCliError::Dependency {
+                    message: format!("Dependency '{}' not found in Actr.toml", requested),
+                }
+                .into());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut diffs = Vec::new();
+        for dependency in &config.dependencies {
+            let selected = self.services.is_empty()
+                || self.services.contains(&dependency.alias)
+                || self.services.contains(&dependency.name);
+            if !selected || !seen.insert(dependency.name.clone()) {
+                continue;
+            }
+
+            let cached = cache_manager
+                .get_cached_proto(&dependency.name)
+                .await?
+                .ok_or_else(|| ActrCliError::Cache {
+                    message: format!(
+                        "Protos for '{}' are not cached. Run 'actr install' first.",
+                        dependency.name
+                    ),
+                })?;
+            let remote = service_discovery
+                .get_service_details(&dependency.name)
+                .await
+                .with_context(|| {
+                    format!("Failed to fetch '{}' from the registry", dependency.name)
+                })?;
+
+            let changes = diff_schemas(
+                &parse_files(&cached.files)?,
+                &parse_files(&remote.proto_files)?,
+            );
+            diffs.push(ServiceDiff {
+                service: dependency.name.clone(),
+                changes,
+            });
+        }
+
+        let breaking = count(&diffs, ChangeKind::Breaking);
+        let additive = count(&diffs, ChangeKind::Additive);
+        let failed = breaking > 0 || (self.strict && additive > 0);
+
+        if context.is_json() {
+            let json = serde_json::to_string_pretty(&serde_json::json!({
+                "breaking": breaking,
+                "additive": additive,
+                "services": diffs,
+            }))?;
+            return Ok(if failed {
+                CommandResult::Error(json)
+            } else {
+                CommandResult::Success(json)
+            });
+        }
+
+        for diff in &diffs {
+            if diff.changes.is_empty() {
+                human_println!("✅ {}: no changes", diff.service);
+                continue;
+            }
+            human_println!("📄 {}:", diff.service);
+            for change in &diff.changes {
+                match change.kind {
+                    ChangeKind::Breaking => {
+                        human_println!("  {} {}", "✘ breaking".red(), change.message)
+                    }
+                    ChangeKind::Additive => {
+                        human_println!("  {} {}", "+ additive".green(), change.message)
+                    }
+                }
+            }
+        }
+
+        let summary = format!("{breaking} breaking, {additive} additive changes");
+        if failed {
+            Ok(CommandResult::Error(summary))
+        } else {
+            Ok(CommandResult::Success(summary))
+        }
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![
+            ComponentType::ConfigManager,
+            ComponentType::ServiceDiscovery,
+            ComponentType::CacheManager,
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "diff"
+    }
+
+    fn description(&self) -> &str {
+        "Report breaking and additive proto changes against the registry"
+    }
+}
+
+fn count(diffs: &[ServiceDiff], kind: ChangeKind) -> usize {
+    diffs
+        .iter()
+        .flat_map(|d| &d.changes)
+        .filter(|c| c.kind == kind)
+        .count()
+}
+
+fn parse_files(files: &[ProtoFile]) -> Result<Vec<ProtoSchema>> {
+    files
+        .iter()
+        .map(|file| {
+            ProtoSchema::parse(&file.content)
+                .with_context(|| format!("Failed to parse {}", file.name))
+        })
+        .collect()
+}
+
+/// Definitions of all files indexed by package-qualified name
+struct Index<'a> {
+    messages: BTreeMap<String, (&'a ProtoSchema, &'a MessageDef)>,
+    enums: BTreeMap<String, &'a EnumDef>,
+    services: BTreeMap<String, (&'a ProtoSchema, &'a ServiceDef)>,
+}
+
+impl<'a> Index<'a> {
+    fn new(schemas: &'a [ProtoSchema]) -> Self {
+        let mut index = Self {
+            messages: BTreeMap::new(),
+            enums: BTreeMap::new(),
+            services: BTreeMap::new(),
+        };
+        for schema in schemas {
+            for message in &schema.messages {
+                index
+                    .messages
+                    .insert(schema.qualified(&message.name), (schema, message));
+            }
+            for def in &schema.enums {
+                index.enums.insert(schema.qualified(&def.name), def);
+            }
+            for service in &schema.services {
+                index
+                    .services
+                    .insert(schema.qualified(&service.name), (schema, service));
+            }
+        }
+        index
+    }
+}
+
+/// Classify the differences between an old and a new set of proto files
+pub fn diff_schemas(old: &[ProtoSchema], new: &[ProtoSchema]) -> Vec<SchemaChange> {
+    let old = Index::new(old);
+    let new = Index::new(new);
+    let mut changes = Vec::new();
+
+    for (name, (old_schema, old_service)) in &old.services {
+        let Some((new_schema, new_service)) = new.services.get(name) else {
+            changes.push(SchemaChange::breaking(format!("service {name} removed")));
+            continue;
+        };
+        for old_rpc in &old_service.methods {
+            let Some(new_rpc) = new_service.methods.iter().find(|m| m.name == old_rpc.name) else {
+                changes.push(SchemaChange::breaking(format!(
+                    "rpc {name}.{} removed",
+                    old_rpc.name
+                )));
+                continue;
+            };
+            let rpc = format!("{name}.{}", old_rpc.name);
+            let (old_in, new_in) = (
+                old_schema.normalize_type(&old_rpc.input_type),
+                new_schema.normalize_type(&new_rpc.input_type),
+            );
+            if old_in != new_in {
+                changes.push(SchemaChange::breaking(format!(
+                    "rpc {rpc} request type changed: {old_in} -> {new_in}"
+                )));
+            }
+            let (old_out, new_out) = (
+                old_schema.normalize_type(&old_rpc.output_type),
+                new_schema.normalize_type(&new_rpc.output_type),
+            );
+            if old_out != new_out {
+                changes.push(SchemaChange::breaking(format!(
+                    "rpc {rpc} response type changed: {old_out} -> {new_out}"
+                )));
+            }
+            if old_rpc.client_streaming != new_rpc.client_streaming
+                || old_rpc.server_streaming != new_rpc.server_streaming
+            {
+                changes.push(SchemaChange::breaking(format!(
+                    "rpc {rpc} streaming mode changed"
+                )));
+            }
+        }
+        for new_rpc in &new_service.methods {
+            if !old_service.methods.iter().any(|m| m.name == new_rpc.name) {
+                changes.push(SchemaChange::additive(format!(
+                    "rpc {name}.{} added",
+                    new_rpc.name
+                )));
+            }
+        }
+    }
+    for name in new.services.keys() {
+        if !old.services.contains_key(name) {
+            changes.push(SchemaChange::additive(format!("service {name} added")));
+        }
+    }
+
+    for (name, (old_schema, old_message)) in &old.messages {
+        let Some((new_schema, new_message)) = new.messages.get(name) else {
+            changes.push(SchemaChange::breaking(format!("message {name} removed")));
+            continue;
+        };
+        diff_fields(
+            name,
+            (old_schema, &old_message.fields),
+            (new_schema, &new_message.fields),
+            &mut changes,
+        );
+    }
+    for name in new.messages.keys() {
+        if !old.messages.contains_key(name) {
+            changes.push(SchemaChange::additive(format!("message {name} added")));
+        }
+    }
+
+    for (name, old_enum) in &old.enums {
+        let Some(new_enum) = new.enums.get(name) else {
+            changes.push(SchemaChange::breaking(format!("enum {name} removed")));
+            continue;
+        };
+        for value in &old_enum.values {
+            match new_enum.values.iter().find(|v| v.number == value.number) {
+                None => changes.push(SchemaChange::breaking(format!(
+                    "enum value {name}.{} (= {}) removed",
+                    value.name, value.number
+                ))),
+                Some(renamed) if renamed.name != value.name => {
+                    changes.push(SchemaChange::breaking(format!(
+                        "enum value {name} = {} renamed: {} -> {}",
+                        value.number, value.name, renamed.name
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+        for value in &new_enum.values {
+            if !old_enum.values.iter().any(|v| v.number == value.number) {
+                changes.push(SchemaChange::additive(format!(
+                    "enum value {name}.{} (= {}) added",
+                    value.name, value.number
+                )));
+            }
+        }
+    }
+    for name in new.enums.keys() {
+        if !old.enums.contains_key(name) {
+            changes.push(SchemaChange::additive(format!("enum {name} added")));
+        }
+    }
+
+    changes.sort_by(|a, b| a.kind.cmp(&b.kind));
+    changes
+}
+
+fn diff_fields(
+    message: &str,
+    (old_schema, old_fields): (&ProtoSchema, &[FieldDef]),
+    (new_schema, new_fields): (&ProtoSchema, &[FieldDef]),
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_field in old_fields {
+        let field = format!("{message}.{}", old_field.name);
+        let Some(new_field) = new_fields.iter().find(|f| f.number == old_field.number) else {
+            match new_fields.iter().find(|f| f.name == old_field.name) {
+                Some(moved) => changes.push(SchemaChange::breaking(format!(
+                    "field {field} number changed: {} -> {}",
+                    old_field.number, moved.number
+                ))),
+                None => changes.push(SchemaChange::breaking(format!(
+                    "field {field} (= {}) removed",
+                    old_field.number
+                ))),
+            }
+            continue;
+        };
+
+        if new_field.name != old_field.name {
+            changes.push(SchemaChange::breaking(format!(
+                "field {message} = {} renamed: {} -> {}",
+                old_field.number, old_field.name, new_field.name
+            )));
+        }
+        let (old_type, new_type) = (
+            old_schema.normalize_type(&old_field.type_name),
+            new_schema.normalize_type(&new_field.type_name),
+        );
+        if old_type != new_type {
+            changes.push(SchemaChange::breaking(format!(
+                "field {field} type changed: {old_type} -> {new_type}"
+            )));
+        }
+        let repeated = |label: FieldLabel| label == FieldLabel::Repeated;
+        if repeated(old_field.label) != repeated(new_field.label) {
+            changes.push(SchemaChange::breaking(format!(
+                "field {field} cardinality changed"
+            )));
+        }
+    }
+
+    for new_field in new_fields {
+        let known = old_fields
+            .iter()
+            .any(|f| f.number == new_field.number || f.name == new_field.name);
+        if !known {
+            changes.push(SchemaChange::additive(format!(
+                "field {message}.{} (= {}) added",
+                new_field.name, new_field.number
+            )));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(source: &str) -> Vec<ProtoSchema> {
+        vec![ProtoSchema::parse(source).unwrap()]
+    }
+
+    const V1: &str = r#"
+syntax = "proto3";
+package acme;
+message GetUserRequest { string id = 1; }
+message User { string id = 1; string name = 2; int32 age = 3; }
+message Legacy { string value = 1; }
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc DeleteUser(GetUserRequest) returns (User);
+}
+"#;
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        assert!(diff_schemas(&schema(V1), &schema(V1)).is_empty());
+    }
+
+    #[test]
+    fn test_breaking_and_additive_changes() {
+        let v2 = r#"
+syntax = "proto3";
+package acme;
+message GetUserRequest { string id = 1; }
+message User { string id = 1; int64 age = 3; string email = 4; uint32 name = 5; }
+service UserService {
+  rpc GetUser(GetUserRequest) returns (.acme.User);
+  rpc ListUsers(GetUserRequest) returns (stream User);
+}
+"#;
+        let changes = diff_schemas(&schema(V1), &schema(v2));
+        let breaking: Vec<&str> = changes
+            .iter()
+            .filter(|c| c.kind == ChangeKind::Breaking)
+            .map(|c| c.message.as_str())
+            .collect();
+        let additive: Vec<&str> = changes
+            .iter()
+            .filter(|c| c.kind == ChangeKind::Additive)
+            .map(|c| c.message.as_str())
+            .collect();
+
+        assert_eq!(
+            breaking,
+            vec![
+                "rpc acme.UserService.DeleteUser removed",
+                "message acme.Legacy removed",
+                "field acme.User.name number changed: 2 -> 5",
+                "field acme.User.age type changed: int32 -> int64",
+            ]
+        );
+        assert_eq!(
+            additive,
+            vec![
+                "rpc acme.UserService.ListUsers added",
+                "field acme.User.email (= 4) added",
+            ]
+        );
+    }
+}
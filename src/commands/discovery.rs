@@ -2,9 +2,14 @@
 //!
 //! Demonstrates multi-level reuse patterns: Service Discovery -> Validation -> Optional Install
 
+use crate::commands::completions::save_discovery_cache;
+use crate::commands::proto::{DEFAULT_EXPORT_DIR, ExportLayout, export_service_protos};
+use crate::human_println;
+use crate::tr;
+use actr_protocol::ActrTypeExt;
 use anyhow::Result;
 use async_trait::async_trait;
-use clap::Args;
+use clap::{Args, ValueEnum};
 
 use crate::core::{
     ActrCliError, Command, CommandContext, CommandResult, ComponentType, ConfigManager,
@@ -30,48 +35,117 @@ pub struct DiscoveryCommand {
     /// Automatically install selected services
     #[arg(long)]
     pub auto_install: bool,
+
+    /// Print discovered services and exit without prompting
+    #[arg(long)]
+    pub list_only: bool,
+
+    /// Output format for the service list (json implies --list-only)
+    #[arg(long, value_enum, default_value_t = DiscoveryOutputFormat::Table)]
+    pub output: DiscoveryOutputFormat,
+
+    /// Select a service by name instead of prompting (requires --action or --output json)
+    #[arg(long, value_name = "NAME")]
+    pub select: Option<String>,
+
+    /// Action to run on the selected service (requires --select)
+    #[arg(long, value_enum, requires = "select")]
+    pub action: Option<DiscoveryAction>,
+
+    /// Directory exported protos are written to (default: exports/remote)
+    #[arg(long, value_name = "DIR")]
+    pub out_dir: Option<std::path::PathBuf>,
+
+    /// Directory layout of exported protos
+    #[arg(long, value_enum, default_value_t = ExportLayout::Package)]
+    pub layout: ExportLayout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum DiscoveryOutputFormat {
+    /// Human-readable table (default)
+    #[default]
+    Table,
+    /// JSON array of services
+    Json,
+}
+
+/// Non-interactive counterpart of the action menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DiscoveryAction {
+    /// View service details (fingerprint, publication time)
+    Details,
+    /// Export proto files
+    Export,
+    /// Add to configuration file
+    Add,
 }
 
 #[async_trait]
 impl Command for DiscoveryCommand {
     async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
         // Get reusable components
-        let (service_discovery, user_interface, config_manager) = {
-            let container = context.container.lock().unwrap();
-            (
-                container.get_service_discovery()?,
-                container.get_user_interface()?,
-                container.get_config_manager()?,
-            )
-        };
+        let (service_discovery, user_interface, config_manager) = (
+            context.container.get_service_discovery()?,
+            context.container.get_user_interface()?,
+            context.container.get_config_manager()?,
+        );
+
+        self.validate_selection()?;
 
         // Phase 1: Service Discovery
 
         let filter = self.create_service_filter();
         let services = service_discovery.discover_services(filter.as_ref()).await?;
         tracing::debug!("Discovered services: {:?}", services);
+        let names: Vec<String> = services.iter().map(|s| s.name.clone()).collect();
+        if let Err(e) = save_discovery_cache(std::path::Path::new("."), &names) {
+            tracing::debug!("Failed to cache discovered service names: {e}");
+        }
+
+        if self.output == DiscoveryOutputFormat::Json {
+            let services = match &self.select {
+                Some(name) => vec![services[Self::find_service(&services, name)?].clone()],
+                None => services,
+            };
+            let json = serde_json::to_string_pretty(&Self::services_to_json(&services))?;
+            return Ok(CommandResult::Success(json));
+        }
 
         if services.is_empty() {
-            println!("ℹ️ No available Actor services discovered in the current network");
-            return Ok(CommandResult::Success("No services discovered".to_string()));
+            human_println!("{}", tr!("discovery.none_found"));
+            return Ok(CommandResult::Success(tr!("discovery.none_found_short")));
         }
 
-        println!("🔍 Discovered Actor services:");
+        human_println!("{}", tr!("discovery.found"));
         // Display discovered services table
         self.display_services_table(&services);
 
-        // Selection Phase
-        let service_options: Vec<String> = services.iter().map(|s| s.name.clone()).collect();
+        if self.list_only {
+            return Ok(CommandResult::Success(tr!(
+                "discovery.found_count",
+                count = services.len()
+            )));
+        }
 
-        let selected_index = match user_interface
-            .select_from_list(&service_options, "Select a service to view (Esc to quit)")
-            .await
-        {
-            Ok(index) => index,
-            Err(err) if Self::is_operation_cancelled(&err) => {
-                return Ok(CommandResult::Success("Operation cancelled".to_string()));
+        // Selection Phase
+        let selected_index = match &self.select {
+            Some(name) => Self::find_service(&services, name)?,
+            None => {
+                let service_options: Vec<String> =
+                    services.iter().map(|s| s.name.clone()).collect();
+
+                match user_interface
+                    .select_from_list(&service_options, &tr!("discovery.select_service"))
+                    .await
+                {
+                    Ok(index) => index,
+                    Err(err) if Self::is_operation_cancelled(&err) => {
+                        return Ok(CommandResult::Success(tr!("discovery.cancelled")));
+                    }
+                    Err(err) => return Err(err),
+                }
             }
-            Err(err) => return Err(err),
         };
 
         let selected_service = &services[selected_index];
@@ -86,24 +160,25 @@ impl Command for DiscoveryCommand {
         }
 
         // Action menu prompt
-        let menu_prompt = format!("Options for {}", selected_service.name);
+        let menu_prompt = tr!("discovery.menu_prompt", name = selected_service.name);
 
         // Action menu items (as shown in screenshot)
-        let action_menu = vec![
-            "[1] View service details (fingerprint, publication time)".to_string(),
-            "[2] Export proto files".to_string(),
-            "[3] Add to configuration file".to_string(),
-        ];
-
-        let action_choice = match user_interface
-            .select_from_list(&action_menu, &menu_prompt)
-            .await
-        {
-            Ok(choice) => choice,
-            Err(err) if Self::is_operation_cancelled(&err) => {
-                return Ok(CommandResult::Success("Operation cancelled".to_string()));
-            }
-            Err(err) => return Err(err),
+        let action_menu = crate::core::i18n::messages("discovery.menu");
+
+        let action_choice = match self.action {
+            Some(DiscoveryAction::Details) => 0,
+            Some(DiscoveryAction::Export) => 1,
+            Some(DiscoveryAction::Add) => 2,
+            None => match user_interface
+                .select_from_list(&action_menu, &menu_prompt)
+                .await
+            {
+                Ok(choice) => choice,
+                Err(err) if Self::is_operation_cancelled(&err) => {
+                    return Ok(CommandResult::Success(tr!("discovery.cancelled")));
+                }
+                Err(err) => return Err(err),
+            },
         };
 
         match action_choice {
@@ -116,22 +191,20 @@ impl Command for DiscoveryCommand {
                         .await?;
                     self.display_service_details(&details);
                 }
-                Ok(CommandResult::Success(
-                    "Service details displayed".to_string(),
-                ))
+                Ok(CommandResult::Success(tr!("discovery.details_displayed")))
             }
             1 => {
                 // Export proto files
                 self.export_proto_files(selected_service, &service_discovery, &config_manager)
                     .await?;
-                Ok(CommandResult::Success("Proto files exported".to_string()))
+                Ok(CommandResult::Success(tr!("discovery.protos_exported")))
             }
             2 => {
                 // Add to configuration file - core flow of reuse architecture
                 self.add_to_config_with_validation(selected_service, context)
                     .await
             }
-            _ => Ok(CommandResult::Success("Invalid choice".to_string())),
+            _ => Ok(CommandResult::Success(tr!("discovery.invalid_choice"))),
         }
     }
 
@@ -162,6 +235,12 @@ impl DiscoveryCommand {
             filter,
             verbose,
             auto_install,
+            list_only: false,
+            output: DiscoveryOutputFormat::Table,
+            select: None,
+            action: None,
+            out_dir: None,
+            layout: ExportLayout::Package,
         }
     }
 
@@ -171,7 +250,57 @@ impl DiscoveryCommand {
             filter: args.filter.clone(),
             verbose: args.verbose,
             auto_install: args.auto_install,
+            list_only: args.list_only,
+            output: args.output,
+            select: args.select.clone(),
+            action: args.action,
+            out_dir: args.out_dir.clone(),
+            layout: args.layout,
+        }
+    }
+
+    /// `--select` picks the service to act on or the one to print as JSON;
+    /// without either it would open the action menu in scripted runs
+    fn validate_selection(&self) -> Result<()> {
+        if self.select.is_some()
+            && self.action.is_none()
+            && self.output != DiscoveryOutputFormat::Json
+        {
+            return Err(ActrCliError::InvalidArgument {
+                message: tr!("discovery.select_needs_action"),
+            }
+            .into());
         }
+        Ok(())
+    }
+
+    /// Locate a service selected via `--select`
+    fn find_service(services: &[ServiceInfo], name: &str) -> Result<usize> {
+        services.iter().position(|s| s.name == name).ok_or_else(|| {
+            ActrCliError::ServiceNotFound {
+                name: name.to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Serialize services for `--output json`
+    fn services_to_json(services: &[ServiceInfo]) -> serde_json::Value {
+        serde_json::Value::Array(
+            services
+                .iter()
+                .map(|s| {
+                    serde_json::json!({
+                        "name": s.name,
+                        "actr_type": s.actr_type.to_string_repr(),
+                        "fingerprint": s.fingerprint,
+                        "tags": s.tags,
+                        "description": s.description,
+                        "published_at": s.published_at,
+                    })
+                })
+                .collect(),
+        )
     }
 
     /// Create service filter
@@ -205,8 +334,8 @@ impl DiscoveryCommand {
         network_validator: &std::sync::Arc<dyn NetworkValidator>,
         fingerprint_validator: &std::sync::Arc<dyn FingerprintValidator>,
     ) -> Result<()> {
-        println!();
-        println!("🔍 Validating dependency...");
+        human_println!();
+        human_println!("{}", tr!("discovery.validating"));
 
         let mut failures = Vec::new();
 
@@ -216,15 +345,15 @@ impl DiscoveryCommand {
         {
             Ok(status) => {
                 if status.is_available {
-                    println!("  ├─ ✅ Service availability");
+                    human_println!("  ├─ ✅ {}", tr!("discovery.check_availability"));
                 } else {
-                    println!("  ├─ ❌ Service availability");
-                    failures.push(format!("Service '{}' not found in registry", service.name));
+                    human_println!("  ├─ ❌ {}", tr!("discovery.check_availability"));
+                    failures.push(tr!("discovery.not_in_registry", name = service.name));
                 }
             }
             Err(e) => {
-                println!("  ├─ ❌ Service availability");
-                failures.push(format!("Service availability check failed: {e}"));
+                human_println!("  ├─ ❌ {}", tr!("discovery.check_availability"));
+                failures.push(tr!("discovery.availability_failed", error = e));
             }
         }
 
@@ -234,19 +363,23 @@ impl DiscoveryCommand {
         {
             Ok(connectivity) => {
                 if connectivity.is_reachable {
-                    println!("  ├─ ✅ Network connectivity");
+                    human_println!("  ├─ ✅ {}", tr!("discovery.check_connectivity"));
                 } else {
-                    println!("  ├─ ❌ Network connectivity");
-                    let detail = connectivity.error.as_deref().unwrap_or("unknown error");
-                    failures.push(format!(
-                        "Network connectivity failed for '{}': {}",
-                        service.name, detail
+                    human_println!("  ├─ ❌ {}", tr!("discovery.check_connectivity"));
+                    let detail = connectivity
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| tr!("check.unknown_error"));
+                    failures.push(tr!(
+                        "discovery.connectivity_failed",
+                        name = service.name,
+                        error = detail
                     ));
                 }
             }
             Err(e) => {
-                println!("  ├─ ❌ Network connectivity");
-                failures.push(format!("Network connectivity check failed: {e}"));
+                human_println!("  ├─ ❌ {}", tr!("discovery.check_connectivity"));
+                failures.push(tr!("discovery.connectivity_check_failed", error = e));
             }
         }
 
@@ -265,19 +398,19 @@ impl DiscoveryCommand {
                         .await
                         .unwrap_or(false);
                     if is_valid {
-                        println!("  ├─ ✅ Fingerprint match");
+                        human_println!("  ├─ ✅ {}", tr!("discovery.check_fingerprint"));
                     } else {
-                        println!("  ├─ ❌ Fingerprint match");
-                        failures.push(format!("Fingerprint mismatch for '{}'", service.name));
+                        human_println!("  ├─ ❌ {}", tr!("discovery.check_fingerprint"));
+                        failures.push(tr!("discovery.fingerprint_mismatch", name = service.name));
                     }
                 }
                 Err(e) => {
-                    println!("  ├─ ❌ Fingerprint check");
-                    failures.push(format!("Fingerprint check failed: {e}"));
+                    human_println!("  ├─ ❌ {}", tr!("discovery.check_fingerprint"));
+                    failures.push(tr!("discovery.fingerprint_check_failed", error = e));
                 }
             }
         } else {
-            println!("  ├─ ⚠️  Fingerprint missing; skipping check");
+            human_println!("  ├─ ⚠️  {}", tr!("discovery.fingerprint_missing"));
         }
 
         if check_conflicts {
@@ -287,42 +420,46 @@ impl DiscoveryCommand {
                     spec: spec.clone(),
                     fingerprint: spec.fingerprint.clone().unwrap_or_default(),
                     proto_files: Vec::new(),
+                    dependencies: Vec::new(),
+                    selected_tag: None,
                 });
             }
             resolved.push(ResolvedDependency {
                 spec: dependency_spec.clone(),
                 fingerprint: dependency_spec.fingerprint.clone().unwrap_or_default(),
                 proto_files: Vec::new(),
+                dependencies: Vec::new(),
+                selected_tag: None,
             });
 
             match dependency_resolver.check_conflicts(&resolved).await {
                 Ok(conflicts) => {
                     if conflicts.is_empty() {
-                        println!("  ├─ ✅ Dependency conflicts");
+                        human_println!("  ├─ ✅ {}", tr!("discovery.check_conflicts"));
                     } else {
-                        println!("  ├─ ❌ Dependency conflicts");
+                        human_println!("  ├─ ❌ {}", tr!("discovery.check_conflicts"));
                         let details = conflicts
                             .iter()
                             .map(|conflict| conflict.description.clone())
                             .collect::<Vec<_>>()
                             .join(", ");
-                        failures.push(format!("Dependency conflicts: {details}"));
+                        failures.push(tr!("discovery.conflicts", details = details));
                     }
                 }
                 Err(e) => {
-                    println!("  ├─ ❌ Dependency conflicts");
-                    failures.push(format!("Dependency conflict check failed: {e}"));
+                    human_println!("  ├─ ❌ {}", tr!("discovery.check_conflicts"));
+                    failures.push(tr!("discovery.conflict_check_failed", error = e));
                 }
             }
         } else {
-            println!("  ├─ ⚠️  Dependency conflict check skipped (already configured)");
+            human_println!("  ├─ ⚠️  {}", tr!("discovery.conflicts_skipped"));
         }
 
         if failures.is_empty() {
-            println!("  └─ ✅ Validation passed");
+            human_println!("  └─ ✅ {}", tr!("discovery.validation_passed"));
             Ok(())
         } else {
-            println!("  └─ ❌ Validation failed");
+            human_println!("  └─ ❌ {}", tr!("discovery.validation_failed"));
             Err(ActrCliError::ValidationFailed {
                 details: failures.join("; "),
             }
@@ -332,7 +469,7 @@ impl DiscoveryCommand {
 
     /// Display services table
     fn display_services_table(&self, services: &[ServiceInfo]) {
-        println!();
+        human_println!();
         // Total width limit is 160
         const TOTAL_MAX_WIDTH: usize = 160;
         // Border and separator overhead
@@ -406,9 +543,9 @@ impl DiscoveryCommand {
             "─".repeat(desc_w)
         );
 
-        println!("{top_border}");
-        println!("{header}");
-        println!("{separator}");
+        human_println!("{top_border}");
+        human_println!("{header}");
+        human_println!("{separator}");
 
         for service in services {
             let tags_str = service.tags.join(", ");
@@ -420,7 +557,7 @@ impl DiscoveryCommand {
                 .take(desc_w)
                 .collect::<String>();
 
-            println!(
+            human_println!(
                 "│ {:name_w$} │ {:tags_w$} │ {:desc_w$} │",
                 service.name,
                 tags_str.chars().take(tags_w).collect::<String>(),
@@ -431,17 +568,20 @@ impl DiscoveryCommand {
             );
         }
 
-        println!("{bottom_border}");
-        println!();
+        human_println!("{bottom_border}");
+        human_println!();
     }
 
     /// Display service info
     fn display_service_info(&self, service: &ServiceInfo) {
-        println!("📋 Selected service: {}", service.name);
+        human_println!("{}", tr!("discovery.selected", name = service.name));
         if let Some(desc) = &service.description {
-            println!("📝 Description: {desc}");
+            human_println!("{}", tr!("discovery.description", description = desc));
         }
-        println!("🔐 Fingerprint: {}", service.fingerprint);
+        human_println!(
+            "{}",
+            tr!("discovery.fingerprint", fingerprint = service.fingerprint)
+        );
         let time = service
             .published_at
             .and_then(|published_at| chrono::DateTime::from_timestamp(published_at, 0))
@@ -450,57 +590,70 @@ impl DiscoveryCommand {
                     .format("%Y-%m-%d %H:%M:%S")
                     .to_string()
             })
-            .unwrap_or_else(|| "Unknown".to_string());
-        println!("📅 Publication Time: {}", time);
-        println!(
-            "🏷️  Tags: {}",
-            if service.tags.is_empty() {
-                "(none)".to_string()
-            } else {
-                service.tags.join(", ")
-            }
+            .unwrap_or_else(|| tr!("discovery.unknown"));
+        human_println!("{}", tr!("discovery.published_at", time = time));
+        let tags = if service.tags.is_empty() {
+            tr!("discovery.none")
+        } else {
+            service.tags.join(", ")
+        };
+        human_println!("{}", tr!("discovery.tags", tags = tags));
+        human_println!(
+            "{}",
+            tr!("discovery.methods_count", count = service.methods.len())
         );
-        println!("📊 Methods count: {}", service.methods.len());
-        println!();
+        human_println!();
     }
 
     #[allow(unused)]
     /// Display service details
     fn display_service_details(&self, details: &ServiceDetails) {
-        println!("📖 {} Detailed Information:", details.info.name);
-        println!("════════════════════════════════════════");
+        human_println!(
+            "{}",
+            tr!("discovery.details_title", name = details.info.name)
+        );
+        human_println!("════════════════════════════════════════");
         self.display_service_info(&details.info);
-        println!("📋 Available Methods:");
+        human_println!("{}", tr!("discovery.methods"));
         if details.info.methods.is_empty() {
-            println!("  (None)");
+            human_println!("  {}", tr!("discovery.none"));
         } else {
             for method in &details.info.methods {
-                println!(
+                human_println!(
                     "  • {}: {} → {}",
-                    method.name, method.input_type, method.output_type
+                    method.name,
+                    method.input_type,
+                    method.output_type
                 );
             }
         }
 
         if !details.dependencies.is_empty() {
-            println!();
-            println!("🔗 Dependent Services:");
+            human_println!();
+            human_println!("{}", tr!("discovery.dependencies"));
             for dep in &details.dependencies {
-                println!("  • {dep}");
+                human_println!("  • {dep}");
             }
         }
 
-        println!();
-        println!("📁 Proto Files:");
+        human_println!();
+        human_println!("{}", tr!("discovery.proto_files"));
         if details.proto_files.is_empty() {
-            println!("  (None)");
+            human_println!("  {}", tr!("discovery.none"));
         } else {
             for proto in &details.proto_files {
-                println!("  • {} ({} services)", proto.name, proto.services.len());
+                human_println!(
+                    "  • {}",
+                    tr!(
+                        "discovery.proto_file",
+                        name = proto.name,
+                        count = proto.services.len()
+                    )
+                );
             }
         }
 
-        println!();
+        human_println!();
     }
 
     /// Export proto files
@@ -510,27 +663,17 @@ impl DiscoveryCommand {
         service_discovery: &std::sync::Arc<dyn ServiceDiscovery>,
         config_manager: &std::sync::Arc<dyn ConfigManager>,
     ) -> Result<()> {
-        println!("📤 Exporting proto files for {}...", service.name);
-
-        let proto_files = service_discovery.get_service_proto(&service.name).await?;
-
-        let output_dir = config_manager
-            .get_project_root()
-            .join("exports")
-            .join("remote")
-            .join(&service.name);
-        std::fs::create_dir_all(&output_dir)?;
-
-        for proto in &proto_files {
-            let file_path = output_dir.join(&proto.name);
-            if let Some(parent) = file_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-            std::fs::write(&file_path, &proto.content)?;
-            println!("✅ Exported: {}", file_path.display());
-        }
-
-        println!("🎉 Export completed, total {} files", proto_files.len());
+        let out_dir = self
+            .out_dir
+            .clone()
+            .unwrap_or_else(|| config_manager.get_project_root().join(DEFAULT_EXPORT_DIR));
+        export_service_protos(
+            &service.name,
+            service_discovery.as_ref(),
+            &out_dir,
+            self.layout,
+        )
+        .await?;
         Ok(())
     }
 
@@ -547,17 +690,14 @@ impl DiscoveryCommand {
             service_discovery,
             network_validator,
             fingerprint_validator,
-        ) = {
-            let container = context.container.lock().unwrap();
-            (
-                container.get_config_manager()?,
-                container.get_user_interface()?,
-                container.get_dependency_resolver()?,
-                container.get_service_discovery()?,
-                container.get_network_validator()?,
-                container.get_fingerprint_validator()?,
-            )
-        };
+        ) = (
+            context.container.get_config_manager()?,
+            context.container.get_user_interface()?,
+            context.container.get_dependency_resolver()?,
+            context.container.get_service_discovery()?,
+            context.container.get_network_validator()?,
+            context.container.get_fingerprint_validator()?,
+        );
 
         // Convert to dependency spec
         let dependency_spec = DependencySpec {
@@ -565,6 +705,7 @@ impl DiscoveryCommand {
             actr_type: Some(service.actr_type.clone()),
             name: service.name.clone(),
             fingerprint: Some(service.fingerprint.clone()),
+            constraint: Default::default(),
         };
 
         // Check if a dependency with the same name already exists
@@ -600,21 +741,29 @@ impl DiscoveryCommand {
 
         let should_update_config = existing_by_name.is_none();
         if let Some(existing) = existing_by_name {
-            println!(
-                "ℹ️  Dependency with name '{}' already exists (alias: '{}')",
-                service.name, existing.alias
+            human_println!(
+                "{}",
+                tr!(
+                    "discovery.already_configured",
+                    name = service.name,
+                    alias = existing.alias
+                )
             );
             if let (Some(existing_fp), Some(discovered_fp)) = (
                 existing.fingerprint.as_deref(),
                 dependency_spec.fingerprint.as_deref(),
             ) && existing_fp != discovered_fp
             {
-                println!(
-                    "⚠️  Fingerprint mismatch: config '{}' vs discovery '{}'",
-                    existing_fp, discovered_fp
+                human_println!(
+                    "{}",
+                    tr!(
+                        "discovery.configured_fingerprint_mismatch",
+                        configured = existing_fp,
+                        discovered = discovered_fp
+                    )
                 );
             }
-            println!("   Skipping configuration update");
+            human_println!("{}", tr!("discovery.skip_config_update"));
         }
 
         let expected_fingerprint = existing_by_name
@@ -635,12 +784,12 @@ impl DiscoveryCommand {
         .await?;
 
         if should_update_config {
-            println!("📝 Adding {} to configuration file...", service.name);
+            human_println!("{}", tr!("discovery.adding", name = service.name));
             let backup = config_manager.backup_config().await?;
             match config_manager.update_dependency(&dependency_spec).await {
                 Ok(_) => {
                     config_manager.remove_backup(backup).await?;
-                    println!("✅ Added {} to configuration file", service.name);
+                    human_println!("{}", tr!("discovery.added", name = service.name));
                 }
                 Err(e) => {
                     config_manager.restore_backup(backup).await?;
@@ -653,30 +802,28 @@ impl DiscoveryCommand {
         }
 
         // Ask if user wants to install immediately
-        println!();
+        human_println!();
         let should_install = if self.auto_install {
             true
+        } else if self.action.is_some() {
+            // Non-interactive runs only install when explicitly asked to
+            false
         } else {
             user_interface
-                .confirm("🤔 Install this dependency now?")
+                .confirm(&tr!("discovery.confirm_install"))
                 .await?
         };
 
         if should_install {
             // Reuse install flow
-            println!();
-            println!("📦 Installing {}...", service.name);
-
-            let install_pipeline = {
-                let mut container = context.container.lock().unwrap();
-                match container.get_install_pipeline() {
-                    Ok(pipeline) => pipeline,
-                    Err(_) => {
-                        println!("ℹ️ Install pipeline is not implemented yet; skipping.");
-                        return Ok(CommandResult::Success(
-                            "Dependency added; install pending".to_string(),
-                        ));
-                    }
+            human_println!();
+            human_println!("{}", tr!("discovery.installing", name = service.name));
+
+            let install_pipeline = match context.container.get_install_pipeline() {
+                Ok(pipeline) => pipeline,
+                Err(_) => {
+                    human_println!("{}", tr!("discovery.install_unavailable"));
+                    return Ok(CommandResult::Success(tr!("discovery.install_pending")));
                 }
             };
 
@@ -685,27 +832,25 @@ impl DiscoveryCommand {
                 .await
             {
                 Ok(install_result) => {
-                    println!("  ├─ 📦 Cache proto files ✅");
-                    println!("  ├─ 🔒 Update lock file ✅");
-                    println!("  └─ ✅ Installation complete");
-                    println!();
-                    println!("💡 Tip: Run 'actr gen' to generate the latest code");
+                    human_println!("  ├─ {}", tr!("discovery.cached_protos"));
+                    human_println!("  ├─ {}", tr!("discovery.updated_lock"));
+                    human_println!("  └─ {}", tr!("discovery.install_complete"));
+                    human_println!();
+                    human_println!("{}", tr!("update.tip_generate"));
 
                     Ok(CommandResult::Install(install_result))
                 }
                 Err(e) => {
-                    eprintln!("❌ Installation failed: {e}");
-                    Ok(CommandResult::Success(
-                        "Dependency added but installation failed".to_string(),
-                    ))
+                    eprintln!("{}", tr!("discovery.install_failed", error = e));
+                    Ok(CommandResult::Success(tr!("discovery.added_not_installed")))
                 }
             }
         } else {
-            println!("✅ Dependency added to configuration file");
-            println!("💡 Tip: Run 'actr install' to install dependencies");
-            Ok(CommandResult::Success(
-                "Dependency added to configuration".to_string(),
-            ))
+            human_println!("{}", tr!("discovery.dependency_added"));
+            human_println!("{}", tr!("discovery.tip_install"));
+            Ok(CommandResult::Success(tr!(
+                "discovery.dependency_added_short"
+            )))
         }
     }
 }
@@ -738,6 +883,58 @@ mod tests {
         assert!(filter.is_none());
     }
 
+    #[test]
+    fn test_select_needs_action_or_json() {
+        let mut cmd = DiscoveryCommand {
+            select: Some("user-service".to_string()),
+            ..Default::default()
+        };
+        assert!(cmd.validate_selection().is_err());
+
+        cmd.output = DiscoveryOutputFormat::Json;
+        assert!(cmd.validate_selection().is_ok());
+
+        cmd.output = DiscoveryOutputFormat::Table;
+        cmd.action = Some(DiscoveryAction::Export);
+        assert!(cmd.validate_selection().is_ok());
+    }
+
+    #[test]
+    fn test_find_service() {
+        let services = vec![
+            sample_service("user-service"),
+            sample_service("order-service"),
+        ];
+
+        assert_eq!(
+            DiscoveryCommand::find_service(&services, "order-service").unwrap(),
+            1
+        );
+        assert!(DiscoveryCommand::find_service(&services, "missing").is_err());
+    }
+
+    #[test]
+    fn test_services_to_json() {
+        let services = vec![sample_service("user-service")];
+        let json = DiscoveryCommand::services_to_json(&services);
+
+        assert_eq!(json[0]["name"], "user-service");
+        assert_eq!(json[0]["fingerprint"], "sha256:abc");
+        assert_eq!(json[0]["tags"][0], "stable");
+    }
+
+    fn sample_service(name: &str) -> ServiceInfo {
+        ServiceInfo {
+            name: name.to_string(),
+            tags: vec!["stable".to_string()],
+            fingerprint: "sha256:abc".to_string(),
+            actr_type: actr_protocol::ActrType::from_string_repr(&format!("acme+{name}")).unwrap(),
+            published_at: None,
+            description: None,
+            methods: Vec::new(),
+        }
+    }
+
     #[test]
     fn test_required_components() {
         let cmd = DiscoveryCommand::default();
@@ -1,28 +1,80 @@
 //! Doc command implementation - generate project documentation
 //!
 //! Now uses Handlebars templates and embedded assets for maintainability and portability.
+//! The API reference is rendered from the parsed protos under `protos/` (see [`model`]):
+//! an index of services, dependencies and types in `api.html`, and one
+//! `service-<full name>.html` page per service. `--format markdown` writes the same
+//! reference as Markdown (see [`markdown`]) and `--format json` dumps the model itself.
+//! `--include-deps` adds the services of the dependencies cached under `protos/remote/`.
+
+pub mod markdown;
+pub mod model;
 
 use crate::assets::FixtureAssets;
 use crate::commands::Command;
+use crate::core::profile;
 use crate::error::{ActrCliError, Result};
-use actr_config::{Config, ConfigParser};
+use crate::human_println;
+use crate::proto_parser::ProtoSchema;
+use actr_config::{Config, LockFile};
 use async_trait::async_trait;
-use clap::Args;
+use clap::{Args, ValueEnum};
 use handlebars::Handlebars;
+use model::{ApiModel, DependencyDoc, ProtoSource, ServiceDoc, ServiceGroup};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
-#[derive(Args)]
+#[derive(Args, Clone)]
 #[command(
     about = "Generate project documentation",
-    long_about = "Generate static HTML documentation for the project, including project overview, API (Proto) reference, and configuration guide."
+    long_about = "Generate static HTML documentation for the project, including project overview, API (Proto) reference, and configuration guide.\n\nExamples:\n  actr doc                      # HTML site in docs/\n  actr doc --format markdown    # index.md plus one file per service\n  actr doc --format json -o .   # Parsed proto model in ./api.json\n  actr doc --include-deps       # Also document the cached dependency APIs"
 )]
 pub struct DocCommand {
     /// Output directory for documentation (defaults to "./docs")
     #[arg(short = 'o', long = "output")]
     pub output_dir: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DocFormat::Html)]
+    pub format: DocFormat,
+
+    /// Also document the dependencies cached under protos/remote/, grouped by alias
+    #[arg(long)]
+    pub include_deps: bool,
+
+    /// Actr.toml to document (global --config, default: ./Actr.toml)
+    #[arg(skip)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum DocFormat {
+    /// Static HTML site
+    #[default]
+    Html,
+    /// index.md plus one file per service
+    Markdown,
+    /// The parsed proto model as api.json
+    Json,
+}
+
+/// File written by `--format json`
+pub const JSON_FILE: &str = "api.json";
+
+#[derive(Serialize)]
+struct JsonDocument<'a> {
+    project: ProjectInfo,
+    #[serde(flatten)]
+    model: &'a ApiModel,
+}
+
+#[derive(Serialize)]
+struct ProjectInfo {
+    name: String,
+    version: String,
+    description: String,
 }
 
 #[derive(Serialize)]
@@ -71,16 +123,20 @@ struct IndexContext {
 }
 
 #[derive(Serialize)]
-struct ApiContext {
+struct ApiContext<'a> {
     #[serde(flatten)]
     base: BaseContext,
-    proto_files: Vec<ProtoFile>,
+    #[serde(flatten)]
+    model: &'a ApiModel,
+    local_services: Vec<&'a ServiceDoc>,
+    dependency_groups: Vec<ServiceGroup<'a>>,
 }
 
 #[derive(Serialize)]
-struct ProtoFile {
-    filename: String,
-    content: String,
+struct ServiceContext<'a> {
+    #[serde(flatten)]
+    base: BaseContext,
+    service: &'a ServiceDoc,
 }
 
 #[derive(Serialize)]
@@ -95,7 +151,8 @@ impl Command for DocCommand {
     async fn execute(&self) -> Result<()> {
         let output_dir = self.output_dir.as_deref().unwrap_or("docs");
 
-        if !Path::new("Actr.toml").exists()
+        let config_file = self.config_file();
+        if !config_file.exists()
             && let Some(root) = Self::find_project_root()
         {
             return Err(ActrCliError::InvalidProject(format!(
@@ -109,36 +166,50 @@ impl Command for DocCommand {
         // Create output directory
         std::fs::create_dir_all(output_dir)?;
 
-        // Load project configuration
-        let config = if Path::new("Actr.toml").exists() {
-            Some(ConfigParser::from_file("Actr.toml")?)
+        // Load project configuration with the --env profile and ${VAR} expansion
+        let config = if config_file.exists() {
+            Some(profile::parse_config(&config_file).map_err(|e| {
+                ActrCliError::config_error(format!(
+                    "Failed to parse {}: {e:#}",
+                    config_file.display()
+                ))
+            })?)
         } else {
             None
         };
 
+        let model = self.build_model(&config);
+        match self.format {
+            DocFormat::Html => {}
+            DocFormat::Markdown => return self.generate_markdown(output_dir, &config, &model),
+            DocFormat::Json => return self.generate_json(output_dir, &config, &model),
+        }
+
         // Initialize Handlebars
         let hb = self.init_handlebars()?;
 
         // Generate documentation files
         self.generate_index_html(output_dir, &config, &hb).await?;
-        self.generate_api_html(output_dir, &config, &hb).await?;
+        self.generate_api_html(output_dir, &config, &hb, &model)
+            .await?;
         self.generate_config_html(output_dir, &config, &hb).await?;
 
         info!("✅ Documentation generated successfully");
         info!("📄 Generated files:");
         info!("  - {}/index.html (project overview)", output_dir);
         info!("  - {}/api.html (API interface documentation)", output_dir);
+        info!("  - {}/service-*.html (one page per service)", output_dir);
         info!(
             "  - {}/config.html (configuration documentation)",
             output_dir
         );
 
-        println!();
-        println!("🚀 To preview the documentation locally:");
-        println!("   python3 -m http.server --directory {} 8080", output_dir);
-        println!("   # or");
-        println!("   npx http-server {} -p 8080", output_dir);
-        println!();
+        human_println!();
+        human_println!("🚀 To preview the documentation locally:");
+        human_println!("   python3 -m http.server --directory {} 8080", output_dir);
+        human_println!("   # or");
+        human_println!("   npx http-server {} -p 8080", output_dir);
+        human_println!();
 
         Ok(())
     }
@@ -165,12 +236,18 @@ impl DocCommand {
             .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
         hb.register_partial("nav", load_template("_nav")?)
             .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
+        hb.register_partial("type_ref", load_template("_type_ref")?)
+            .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
+        hb.register_partial("service_table", load_template("_service_table")?)
+            .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
 
         // Register templates
         hb.register_template_string("index", load_template("index")?)
             .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
         hb.register_template_string("api", load_template("api")?)
             .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
+        hb.register_template_string("service", load_template("service")?)
+            .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
         hb.register_template_string("config", load_template("config")?)
             .map_err(|e| ActrCliError::Internal(anyhow::anyhow!(e)))?;
 
@@ -293,43 +370,175 @@ impl DocCommand {
         output_dir: &str,
         config: &Option<Config>,
         hb: &Handlebars<'_>,
+        model: &ApiModel,
     ) -> Result<()> {
         debug!("Generating api.html...");
 
-        // Collect proto files information
-        let mut proto_files = Vec::new();
-        let proto_dir = Path::new("protos");
-
-        if proto_dir.exists() {
-            for entry in WalkDir::new(proto_dir).into_iter().flatten() {
-                let path = entry.path();
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("proto") {
-                    // Use relative path for better context (e.g., "local/local.proto")
-                    let relative_path = path.strip_prefix(proto_dir).unwrap_or(path);
-                    let filename = relative_path.to_string_lossy().to_string();
-
-                    let content = std::fs::read_to_string(path).unwrap_or_else(|e| {
-                        warn!("Failed to read proto file {:?}: {}", path, e);
-                        String::new()
-                    });
-                    proto_files.push(ProtoFile { filename, content });
-                }
-            }
-        }
-        proto_files.sort_by(|a, b| a.filename.cmp(&b.filename));
-
         let context = ApiContext {
             base: self.create_base_context(config, "API Documentation", "api"),
-            proto_files,
+            model,
+            local_services: model.local_services(),
+            dependency_groups: model.dependency_groups(),
         };
-
         let content = hb.render("api", &context)?;
         let api_path = Path::new(output_dir).join("api.html");
         std::fs::write(api_path, content)?;
 
+        for service in &model.services {
+            let context = ServiceContext {
+                base: self.create_base_context(config, &service.full_name, "api"),
+                service,
+            };
+            let content = hb.render("service", &context)?;
+            let page = Path::new(output_dir).join(format!("service-{}.html", service.full_name));
+            std::fs::write(page, content)?;
+        }
+
         Ok(())
     }
 
+    /// Write `index.md` and one Markdown file per service
+    fn generate_markdown(
+        &self,
+        output_dir: &str,
+        config: &Option<Config>,
+        model: &ApiModel,
+    ) -> Result<()> {
+        let base = self.create_base_context(config, "API Documentation", "api");
+        let index = markdown::render_index(&base.project_name, &base.project_description, model);
+        std::fs::write(Path::new(output_dir).join(markdown::INDEX_FILE), index)?;
+        for service in &model.services {
+            let page = Path::new(output_dir).join(markdown::service_file(service));
+            std::fs::write(page, markdown::render_service(service))?;
+        }
+
+        info!("✅ Documentation generated successfully");
+        info!("📄 Generated files:");
+        info!("  - {}/{} (API index)", output_dir, markdown::INDEX_FILE);
+        for service in &model.services {
+            info!("  - {}/{}", output_dir, markdown::service_file(service));
+        }
+        Ok(())
+    }
+
+    /// Write the parsed proto model as JSON
+    fn generate_json(
+        &self,
+        output_dir: &str,
+        config: &Option<Config>,
+        model: &ApiModel,
+    ) -> Result<()> {
+        let base = self.create_base_context(config, "API Documentation", "api");
+        let document = JsonDocument {
+            project: ProjectInfo {
+                name: base.project_name,
+                version: base.project_version,
+                description: base.project_description,
+            },
+            model,
+        };
+        let path = Path::new(output_dir).join(JSON_FILE);
+        std::fs::write(&path, serde_json::to_string_pretty(&document)?)?;
+
+        info!("✅ API model written to {}", path.display());
+        Ok(())
+    }
+
+    fn build_model(&self, config: &Option<Config>) -> ApiModel {
+        let mut model = ApiModel::from_sources(&self.parse_protos(Path::new("protos"), config));
+        model.dependencies = Self::locked_dependencies(Path::new("Actr.lock.toml"));
+        model
+    }
+
+    /// Parse the project's own protos and, with `--include-deps`, the cached protos of
+    /// its dependencies under `remote/<service>/`. Files that fail to parse are skipped.
+    fn parse_protos(&self, proto_dir: &Path, config: &Option<Config>) -> Vec<ProtoSource> {
+        let mut files = Vec::new();
+        if !proto_dir.exists() {
+            return files;
+        }
+
+        let include_deps = self.include_deps;
+        let entries = WalkDir::new(proto_dir)
+            .into_iter()
+            .filter_entry(|entry| {
+                include_deps || entry.depth() != 1 || entry.file_name() != "remote"
+            })
+            .flatten();
+        for entry in entries {
+            let path = entry.path();
+            if !path.is_file() || path.extension().and_then(|s| s.to_str()) != Some("proto") {
+                continue;
+            }
+            // Use relative path for better context (e.g., "local/local.proto")
+            let relative_path = path.strip_prefix(proto_dir).unwrap_or(path);
+            let filename = relative_path.to_string_lossy().replace('\\', "/");
+            let dependency = match filename.strip_prefix("remote/") {
+                Some(rest) => match rest.split_once('/') {
+                    Some((service, _)) => Some(Self::dependency_alias(config, service)),
+                    // Bookkeeping next to the service directories
+                    None => continue,
+                },
+                None => None,
+            };
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Failed to read proto file {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            match ProtoSchema::parse(&content) {
+                Ok(schema) => files.push(ProtoSource {
+                    path: filename,
+                    dependency,
+                    schema,
+                }),
+                Err(e) => warn!("Skipping {}: {}", filename, e),
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    /// Alias a cached service is declared under in Actr.toml; transitive dependencies
+    /// keep their service name
+    fn dependency_alias(config: &Option<Config>, service: &str) -> String {
+        config
+            .iter()
+            .flat_map(|config| &config.dependencies)
+            .find(|dependency| dependency.name == service)
+            .map(|dependency| dependency.alias.clone())
+            .unwrap_or_else(|| service.to_string())
+    }
+
+    fn locked_dependencies(lock_path: &Path) -> Vec<DependencyDoc> {
+        if !lock_path.exists() {
+            return Vec::new();
+        }
+        let lock_file = match LockFile::from_file(lock_path) {
+            Ok(lock_file) => lock_file,
+            Err(e) => {
+                warn!("Failed to read {}: {}", lock_path.display(), e);
+                return Vec::new();
+            }
+        };
+
+        let mut dependencies: Vec<DependencyDoc> = lock_file
+            .dependencies
+            .iter()
+            .map(|dep| DependencyDoc {
+                name: dep.name.clone(),
+                fingerprint: dep.fingerprint.clone(),
+                description: dep.description.clone(),
+                files: dep.files.iter().map(|file| file.path.clone()).collect(),
+            })
+            .collect();
+        dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+        dependencies
+    }
+
     /// Generate configuration documentation
     async fn generate_config_html(
         &self,
@@ -340,8 +549,9 @@ impl DocCommand {
         debug!("Generating config.html...");
 
         // Generate configuration example
-        let config_example = if Path::new("Actr.toml").exists() {
-            std::fs::read_to_string("Actr.toml").unwrap_or_default()
+        let config_file = self.config_file();
+        let config_example = if config_file.exists() {
+            std::fs::read_to_string(&config_file).unwrap_or_default()
         } else {
             r#"edition = 1
 exports = []
@@ -488,6 +698,13 @@ test = "cargo test""#
         None
     }
 
+    /// Actr.toml being documented
+    fn config_file(&self) -> PathBuf {
+        self.config
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("Actr.toml"))
+    }
+
     fn find_project_root() -> Option<PathBuf> {
         let cwd = std::env::current_dir().ok()?;
         for ancestor in cwd.ancestors() {
@@ -0,0 +1,216 @@
+//! Markdown rendering of the API reference
+//!
+//! `index.md` lists the services (dependency services grouped by alias), locked
+//! dependencies, messages and enums; every
+//! service gets `<full name>.md`. Types carry explicit `<a id>` anchors named after
+//! their full name, so links survive renderers with different heading slug rules.
+
+use super::model::{ApiModel, FieldDoc, ServiceDoc, TypeRef};
+use crate::proto_parser::FieldLabel;
+
+pub const INDEX_FILE: &str = "index.md";
+
+/// File a service page is written to
+pub fn service_file(service: &ServiceDoc) -> String {
+    format!("{}.md", service.full_name)
+}
+
+pub fn render_index(title: &str, description: &str, model: &ApiModel) -> String {
+    let mut out = format!("# {title}\n\n{description}\n\n## Services\n\n");
+    let local = model.local_services();
+    if local.is_empty() {
+        out.push_str("No services found under `protos/`.\n");
+    } else {
+        push_service_table(&mut out, &local);
+    }
+
+    let groups = model.dependency_groups();
+    if !groups.is_empty() {
+        out.push_str("\n## Dependency APIs\n");
+        for group in &groups {
+            out.push_str(&format!("\n### `{}`\n\n", group.alias));
+            push_service_table(&mut out, &group.services);
+        }
+    }
+
+    out.push_str("\n## Dependencies\n\n");
+    if model.dependencies.is_empty() {
+        out.push_str("No locked dependencies. Run `actr install` to add some.\n");
+    } else {
+        out.push_str("| Service | Fingerprint | Proto Files |\n|---|---|---|\n");
+        for dep in &model.dependencies {
+            let files: Vec<String> = dep.files.iter().map(|f| format!("`{f}`")).collect();
+            out.push_str(&format!(
+                "| `{}` | `{}` | {} |\n",
+                dep.name,
+                dep.fingerprint,
+                files.join("<br>")
+            ));
+        }
+    }
+
+    if !model.messages.is_empty() {
+        out.push_str("\n## Messages\n");
+        for message in &model.messages {
+            out.push_str(&format!(
+                "\n<a id=\"{0}\"></a>\n\n### `{0}`\n\n{1}.\n\n",
+                message.full_name,
+                defined_in(&message.file, message.dependency.as_deref())
+            ));
+            push_comment(&mut out, message.comment.as_deref());
+            if message.fields.is_empty() {
+                out.push_str("No fields.\n");
+                continue;
+            }
+            out.push_str("| # | Field | Type | Label | Description |\n|---|---|---|---|---|\n");
+            for field in &message.fields {
+                out.push_str(&format!(
+                    "| {} | `{}` | {} | {} | {} |\n",
+                    field.number,
+                    field.name,
+                    type_link(&field.type_ref, ""),
+                    label(field),
+                    cell(field.comment.as_deref())
+                ));
+            }
+        }
+    }
+
+    if !model.enums.is_empty() {
+        out.push_str("\n## Enums\n");
+        for definition in &model.enums {
+            out.push_str(&format!(
+                "\n<a id=\"{0}\"></a>\n\n### `{0}`\n\n{1}.\n\n",
+                definition.full_name,
+                defined_in(&definition.file, definition.dependency.as_deref())
+            ));
+            push_comment(&mut out, definition.comment.as_deref());
+            out.push_str("| Value | Number |\n|---|---|\n");
+            for value in &definition.values {
+                out.push_str(&format!("| `{}` | {} |\n", value.name, value.number));
+            }
+        }
+    }
+    out
+}
+
+pub fn render_service(service: &ServiceDoc) -> String {
+    let mut out = format!(
+        "# `{}`\n\n[API index]({INDEX_FILE}) · {}",
+        service.full_name,
+        defined_in(&service.file, service.dependency.as_deref())
+    );
+    if let Some(package) = &service.package {
+        out.push_str(&format!(", package `{package}`"));
+    }
+    out.push_str(".\n\n");
+    push_comment(&mut out, service.comment.as_deref());
+
+    if service.methods.is_empty() {
+        out.push_str("This service has no methods.\n");
+        return out;
+    }
+    out.push_str("## Methods\n");
+    for method in &service.methods {
+        out.push_str(&format!("\n### `{}`\n\n", method.name));
+        push_comment(&mut out, method.comment.as_deref());
+        let stream = |streaming: bool| if streaming { "stream " } else { "" };
+        out.push_str(&format!(
+            "- Request: {}{}\n- Response: {}{}\n",
+            stream(method.client_streaming),
+            type_link(&method.input, INDEX_FILE),
+            stream(method.server_streaming),
+            type_link(&method.output, INDEX_FILE)
+        ));
+    }
+    out
+}
+
+fn push_service_table(out: &mut String, services: &[&ServiceDoc]) {
+    out.push_str("| Service | File | Methods | Description |\n|---|---|---|---|\n");
+    for service in services {
+        out.push_str(&format!(
+            "| [`{}`]({}) | `{}` | {} | {} |\n",
+            service.full_name,
+            service_file(service),
+            service.file,
+            service.methods.len(),
+            cell(service.comment.as_deref())
+        ));
+    }
+}
+
+fn defined_in(file: &str, dependency: Option<&str>) -> String {
+    match dependency {
+        Some(alias) => format!("Defined in `{file}` (dependency `{alias}`)"),
+        None => format!("Defined in `{file}`"),
+    }
+}
+
+/// `page` is the file defining the types, empty when it is the current one
+fn type_link(type_ref: &TypeRef, page: &str) -> String {
+    match &type_ref.target {
+        Some(target) => format!("[`{}`]({page}#{target})", type_ref.name),
+        None => format!("`{}`", type_ref.name),
+    }
+}
+
+fn label(field: &FieldDoc) -> String {
+    let label = match field.label {
+        FieldLabel::Singular => "singular",
+        FieldLabel::Optional => "optional",
+        FieldLabel::Repeated => "repeated",
+        FieldLabel::Required => "required",
+    };
+    match &field.oneof {
+        Some(oneof) => format!("{label} (oneof `{oneof}`)"),
+        None => label.to_string(),
+    }
+}
+
+fn push_comment(out: &mut String, comment: Option<&str>) {
+    if let Some(comment) = comment {
+        out.push_str(comment);
+        out.push_str("\n\n");
+    }
+}
+
+/// A comment fitted into one table cell
+fn cell(comment: Option<&str>) -> String {
+    comment
+        .unwrap_or_default()
+        .replace('|', "\\|")
+        .lines()
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::doc::model::ProtoSource;
+    use crate::proto_parser::ProtoSchema;
+
+    #[test]
+    fn test_service_page_links_types_in_index() {
+        let source = "syntax = \"proto3\";\npackage echo;\n\n// Echo back\nservice EchoService {\n  // Say it\n  rpc Echo(EchoRequest) returns (stream EchoResponse);\n}\nmessage EchoRequest {\n  // What to say\n  // and how\n  string message = 1;\n}\nmessage EchoResponse { string message = 1; }\n";
+        let model = ApiModel::from_sources(&[ProtoSource {
+            path: "echo.proto".to_string(),
+            dependency: None,
+            schema: ProtoSchema::parse(source).unwrap(),
+        }]);
+
+        let service = &model.services[0];
+        assert_eq!(service_file(service), "echo.EchoService.md");
+        let page = render_service(service);
+        assert!(page.contains("- Request: [`EchoRequest`](index.md#echo.EchoRequest)"));
+        assert!(page.contains("- Response: stream [`EchoResponse`](index.md#echo.EchoResponse)"));
+
+        let index = render_index("echo", "An Actor-RTC project", &model);
+        assert!(index.contains(
+            "| [`echo.EchoService`](echo.EchoService.md) | `echo.proto` | 1 | Echo back |"
+        ));
+        assert!(index.contains("<a id=\"echo.EchoRequest\"></a>"));
+        assert!(index.contains("| 1 | `message` | `string` | singular | What to say<br>and how |"));
+    }
+}
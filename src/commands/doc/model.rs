@@ -0,0 +1,374 @@
+//! API reference model for `actr doc`
+//!
+//! Built from [`ProtoSchema`]: every service, RPC, message, enum and field with its
+//! leading comment. Type references are resolved with protobuf scoping rules to the
+//! message or enum they name, so rendered pages can link them. Definitions from
+//! dependency protos carry the alias of the dependency they came from.
+
+use crate::proto_parser::{FieldLabel, ProtoSchema};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// One parsed proto file
+#[derive(Debug, Clone)]
+pub struct ProtoSource {
+    /// Path relative to the proto root
+    pub path: String,
+    /// Alias of the dependency the file belongs to, `None` for the project's own protos
+    pub dependency: Option<String>,
+    pub schema: ProtoSchema,
+}
+
+/// Everything `actr doc` renders
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApiModel {
+    pub services: Vec<ServiceDoc>,
+    pub messages: Vec<MessageDoc>,
+    pub enums: Vec<EnumDoc>,
+    /// Locked dependencies from Actr.lock.toml
+    pub dependencies: Vec<DependencyDoc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceDoc {
+    pub name: String,
+    /// Package-qualified name, also used as page name
+    pub full_name: String,
+    pub package: Option<String>,
+    /// Proto file, relative to the proto root
+    pub file: String,
+    pub dependency: Option<String>,
+    pub comment: Option<String>,
+    pub methods: Vec<MethodDoc>,
+}
+
+/// Services of one dependency
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceGroup<'a> {
+    pub alias: &'a str,
+    pub services: Vec<&'a ServiceDoc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MethodDoc {
+    pub name: String,
+    pub input: TypeRef,
+    pub output: TypeRef,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    pub comment: Option<String>,
+}
+
+/// A type as written in the proto, with the full name of the type it resolves to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TypeRef {
+    pub name: String,
+    /// `None` for scalars and types not defined in the documented protos
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MessageDoc {
+    pub name: String,
+    pub full_name: String,
+    pub file: String,
+    pub dependency: Option<String>,
+    pub comment: Option<String>,
+    pub fields: Vec<FieldDoc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldDoc {
+    pub name: String,
+    pub number: i64,
+    pub label: FieldLabel,
+    pub type_ref: TypeRef,
+    pub oneof: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumDoc {
+    pub name: String,
+    pub full_name: String,
+    pub file: String,
+    pub dependency: Option<String>,
+    pub comment: Option<String>,
+    pub values: Vec<EnumValueDoc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumValueDoc {
+    pub name: String,
+    pub number: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyDoc {
+    pub name: String,
+    pub fingerprint: String,
+    pub description: Option<String>,
+    pub files: Vec<String>,
+}
+
+impl ApiModel {
+    /// Build the model from parsed proto files
+    pub fn from_sources(files: &[ProtoSource]) -> Self {
+        let known: BTreeSet<String> = files
+            .iter()
+            .map(|file| &file.schema)
+            .flat_map(|schema| {
+                let messages = schema.messages.iter().map(|m| schema.qualified(&m.name));
+                let enums = schema.enums.iter().map(|e| schema.qualified(&e.name));
+                messages.chain(enums).collect::<Vec<_>>()
+            })
+            .collect();
+        let resolver = Resolver { known: &known };
+
+        let mut model = ApiModel::default();
+        for source in files {
+            let (file, schema) = (&source.path, &source.schema);
+            let package = schema.package.as_deref();
+            for service in &schema.services {
+                model.services.push(ServiceDoc {
+                    name: service.name.clone(),
+                    full_name: schema.qualified(&service.name),
+                    package: schema.package.clone(),
+                    file: file.clone(),
+                    dependency: source.dependency.clone(),
+                    comment: service.comment.clone(),
+                    methods: service
+                        .methods
+                        .iter()
+                        .map(|rpc| MethodDoc {
+                            name: rpc.name.clone(),
+                            input: resolver.type_ref(&rpc.input_type, package, ""),
+                            output: resolver.type_ref(&rpc.output_type, package, ""),
+                            client_streaming: rpc.client_streaming,
+                            server_streaming: rpc.server_streaming,
+                            comment: rpc.comment.clone(),
+                        })
+                        .collect(),
+                });
+            }
+            for message in &schema.messages {
+                model.messages.push(MessageDoc {
+                    name: message.name.clone(),
+                    full_name: schema.qualified(&message.name),
+                    file: file.clone(),
+                    dependency: source.dependency.clone(),
+                    comment: message.comment.clone(),
+                    fields: message
+                        .fields
+                        .iter()
+                        .map(|field| FieldDoc {
+                            name: field.name.clone(),
+                            number: field.number,
+                            label: field.label,
+                            type_ref: resolver.type_ref(&field.type_name, package, &message.name),
+                            oneof: field.oneof.clone(),
+                            comment: field.comment.clone(),
+                        })
+                        .collect(),
+                });
+            }
+            for definition in &schema.enums {
+                model.enums.push(EnumDoc {
+                    name: definition.name.clone(),
+                    full_name: schema.qualified(&definition.name),
+                    file: file.clone(),
+                    dependency: source.dependency.clone(),
+                    comment: definition.comment.clone(),
+                    values: definition
+                        .values
+                        .iter()
+                        .map(|value| EnumValueDoc {
+                            name: value.name.clone(),
+                            number: value.number,
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        model.services.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+        model.messages.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+        model.enums.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+        model
+    }
+
+    /// Services defined by the project itself
+    pub fn local_services(&self) -> Vec<&ServiceDoc> {
+        self.services
+            .iter()
+            .filter(|service| service.dependency.is_none())
+            .collect()
+    }
+
+    /// Dependency services grouped by alias, in alias order
+    pub fn dependency_groups(&self) -> Vec<ServiceGroup<'_>> {
+        let mut groups: BTreeMap<&str, Vec<&ServiceDoc>> = BTreeMap::new();
+        for service in &self.services {
+            if let Some(alias) = &service.dependency {
+                groups.entry(alias).or_default().push(service);
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(alias, services)| ServiceGroup { alias, services })
+            .collect()
+    }
+}
+
+struct Resolver<'a> {
+    known: &'a BTreeSet<String>,
+}
+
+impl Resolver<'_> {
+    /// Resolve `type_name` referenced from `scope` (a dotted message name, empty at
+    /// file level) in `package`; map fields resolve to their value type
+    fn type_ref(&self, type_name: &str, package: Option<&str>, scope: &str) -> TypeRef {
+        let referenced = type_name
+            .strip_prefix("map<")
+            .and_then(|rest| rest.strip_suffix('>'))
+            .and_then(|inner| inner.split_once(','))
+            .map(|(_, value)| value.trim())
+            .unwrap_or(type_name);
+        TypeRef {
+            name: type_name.to_string(),
+            target: self.resolve(referenced, package, scope),
+        }
+    }
+
+    fn resolve(&self, name: &str, package: Option<&str>, scope: &str) -> Option<String> {
+        if let Some(absolute) = name.strip_prefix('.') {
+            return self.known.get(absolute).cloned();
+        }
+
+        // Innermost scope first: `pkg.Outer.Inner.T`, `pkg.Outer.T`, `pkg.T`, `T`
+        let mut prefix: Vec<&str> = package
+            .into_iter()
+            .flat_map(|package| package.split('.'))
+            .chain(scope.split('.').filter(|segment| !segment.is_empty()))
+            .collect();
+        loop {
+            let candidate = if prefix.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{name}", prefix.join("."))
+            };
+            if self.known.contains(&candidate) {
+                return Some(candidate);
+            }
+            if prefix.pop().is_none() {
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER: &str = r#"syntax = "proto3";
+package acme.user;
+
+import "common/types.proto";
+
+// User lookups
+service UserService {
+  // Fetch one user
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc Watch(GetUserRequest) returns (stream User);
+}
+
+message GetUserRequest {
+  string id = 1;
+}
+
+message User {
+  message Address {
+    string city = 1;
+  }
+  string id = 1;
+  Address address = 2;
+  map<string, acme.common.Tag> tags = 3;
+  .acme.common.Status status = 4;
+  Unknown other = 5;
+}
+"#;
+
+    const COMMON: &str = r#"syntax = "proto3";
+package acme.common;
+
+message Tag { string value = 1; }
+enum Status { UNKNOWN = 0; ACTIVE = 1; }
+"#;
+
+    fn model() -> ApiModel {
+        ApiModel::from_sources(&[
+            ProtoSource {
+                path: "user.proto".to_string(),
+                dependency: None,
+                schema: ProtoSchema::parse(USER).unwrap(),
+            },
+            ProtoSource {
+                path: "remote/common-service/types.proto".to_string(),
+                dependency: Some("common".to_string()),
+                schema: ProtoSchema::parse(COMMON).unwrap(),
+            },
+        ])
+    }
+
+    #[test]
+    fn test_services_and_comments() {
+        let model = model();
+        assert_eq!(model.services.len(), 1);
+        let service = &model.services[0];
+        assert_eq!(service.full_name, "acme.user.UserService");
+        assert_eq!(service.comment.as_deref(), Some("User lookups"));
+        assert_eq!(
+            service.methods[0].comment.as_deref(),
+            Some("Fetch one user")
+        );
+        assert_eq!(
+            service.methods[0].output.target.as_deref(),
+            Some("acme.user.User")
+        );
+        assert!(service.methods[1].server_streaming);
+        assert_eq!(model.local_services().len(), 1);
+        assert!(model.dependency_groups().is_empty());
+    }
+
+    #[test]
+    fn test_field_types_resolve_by_scope() {
+        let model = model();
+        let user = model
+            .messages
+            .iter()
+            .find(|m| m.full_name == "acme.user.User")
+            .unwrap();
+        let targets: Vec<Option<&str>> = user
+            .fields
+            .iter()
+            .map(|f| f.type_ref.target.as_deref())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                None,
+                Some("acme.user.User.Address"),
+                Some("acme.common.Tag"),
+                Some("acme.common.Status"),
+                None,
+            ]
+        );
+        let status = model
+            .enums
+            .iter()
+            .find(|e| e.full_name == "acme.common.Status")
+            .unwrap();
+        assert_eq!(status.dependency.as_deref(), Some("common"));
+    }
+}
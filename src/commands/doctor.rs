@@ -0,0 +1,376 @@
+//! Doctor command - diagnose the local development environment
+//!
+//! Probes every external tool the selected language generators invoke, checks
+//! plugin versions against the `.protoc-plugin.toml` requirements, verifies that
+//! Actr.toml parses and tests signaling connectivity. Results are printed as a
+//! pass/fail table followed by fix suggestions.
+
+use crate::commands::SupportedLanguage;
+use crate::commands::codegen::{GeneratorFactory, RequiredTool};
+use crate::core::{
+    Command, CommandContext, CommandResult, ComponentType, NetworkCheckOptions, profile,
+};
+use crate::plugin_config::{ProtocPluginConfig, load_protoc_plugin_config, parse_version_output};
+use crate::plugin_manager::find_plugin;
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Args, ValueEnum};
+use comfy_table::{Attribute, Cell, Color, Table};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+
+/// Doctor command - environment diagnostics
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Diagnose the development environment",
+    long_about = "Check that protoc, code generation plugins and formatters are installed, that plugin versions satisfy .protoc-plugin.toml, that Actr.toml parses and that the signaling server is reachable"
+)]
+pub struct DoctorCommand {
+    /// Languages whose toolchains are checked (comma separated)
+    #[arg(short, long, value_delimiter = ',', default_value = "rust")]
+    pub language: Vec<SupportedLanguage>,
+
+    /// Check the toolchains of every supported language
+    #[arg(long)]
+    pub all_languages: bool,
+
+    /// Configuration file to check (defaults to Actr.toml)
+    #[arg(short = 'f', long = "file")]
+    pub config_file: Option<String>,
+
+    /// Timeout for the signaling connectivity check in seconds
+    #[arg(
+        id = "check_timeout",
+        long = "check-timeout",
+        value_name = "SECS",
+        default_value = "5"
+    )]
+    pub timeout: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Outcome of a single probe
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub check: String,
+    pub status: DiagnosticStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(check: impl Into<String>, status: DiagnosticStatus, detail: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            status,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Command for DoctorCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let config_path = PathBuf::from(self.config_file.as_deref().unwrap_or("Actr.toml"));
+        let project_root = config_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf();
+
+        let mut diagnostics = Vec::new();
+
+        // 1. Actr.toml
+        let config = if !config_path.exists() {
+            diagnostics.push(
+                Diagnostic::new(
+                    config_path.display().to_string(),
+                    DiagnosticStatus::Fail,
+                    "not found",
+                )
+                .with_fix("Run 'actr init' to create a project"),
+            );
+            None
+        } else {
+            match profile::parse_config(&config_path) {
+                Ok(config) => {
+                    diagnostics.push(Diagnostic::new(
+                        config_path.display().to_string(),
+                        DiagnosticStatus::Pass,
+                        format!("{} dependencies", config.dependencies.len()),
+                    ));
+                    Some(config)
+                }
+                Err(e) => {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            config_path.display().to_string(),
+                            DiagnosticStatus::Fail,
+                            e.to_string(),
+                        )
+                        .with_fix("Run 'actr config test' for details"),
+                    );
+                    None
+                }
+            }
+        };
+
+        // 2. .protoc-plugin.toml
+        let plugin_config = match load_protoc_plugin_config(&config_path) {
+            Ok(Some(plugin_config)) => {
+                diagnostics.push(Diagnostic::new(
+                    ".protoc-plugin.toml",
+                    DiagnosticStatus::Pass,
+                    plugin_config.path().display().to_string(),
+                ));
+                Some(plugin_config)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                diagnostics.push(
+                    Diagnostic::new(".protoc-plugin.toml", DiagnosticStatus::Fail, e.to_string())
+                        .with_fix(
+                            "Fix the [plugins] table (e.g. protoc-gen-actrframework = \"0.1.10\")",
+                        ),
+                );
+                None
+            }
+        };
+
+        // 3. Toolchains
+        for tool in self.required_tools() {
+            diagnostics.push(check_tool(&tool, &project_root, plugin_config.as_ref()));
+        }
+
+        // 4. Signaling server
+        if let Some(config) = &config {
+            let url = config.signaling_url.to_string();
+            let check = format!("signaling {url}");
+            if context.is_offline() {
+                diagnostics.push(Diagnostic::new(
+                    check,
+                    DiagnosticStatus::Warn,
+                    "skipped (offline)",
+                ));
+            } else {
+                let validator = context.container.get_network_validator()?;
+                let options = NetworkCheckOptions::with_timeout_secs(self.timeout);
+                let status = validator.check_connectivity(&url, &options).await?;
+                diagnostics.push(if status.is_reachable {
+                    Diagnostic::new(
+                        check,
+                        DiagnosticStatus::Pass,
+                        format!("reachable ({}ms)", status.response_time_ms.unwrap_or(0)),
+                    )
+                } else {
+                    Diagnostic::new(
+                        check,
+                        DiagnosticStatus::Fail,
+                        status.error.unwrap_or_else(|| "unreachable".to_string()),
+                    )
+                    .with_fix(
+                        "Start the signaling server or update it with 'actr config set system.signaling.url <url>'",
+                    )
+                });
+            }
+        }
+
+        let failed = diagnostics
+            .iter()
+            .filter(|d| d.status == DiagnosticStatus::Fail)
+            .count();
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "passed": failed == 0,
+                "diagnostics": diagnostics,
+            });
+            let json = serde_json::to_string_pretty(&report)?;
+            return Ok(if failed == 0 {
+                CommandResult::Success(json)
+            } else {
+                CommandResult::Error(json)
+            });
+        }
+
+        println!("🩺 Environment diagnostics\n");
+        println!("{}", render_table(&diagnostics));
+
+        let fixes: Vec<&Diagnostic> = diagnostics.iter().filter(|d| d.fix.is_some()).collect();
+        if !fixes.is_empty() {
+            println!("\n💡 Suggested fixes:");
+            for diagnostic in fixes {
+                println!(
+                    "  • {}: {}",
+                    diagnostic.check,
+                    diagnostic.fix.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        println!();
+
+        if failed == 0 {
+            Ok(CommandResult::Success(format!(
+                "{} Environment looks good",
+                "✅".green()
+            )))
+        } else {
+            Ok(CommandResult::Error(format!("{failed} checks failed")))
+        }
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::NetworkValidator]
+    }
+
+    fn name(&self) -> &str {
+        "doctor"
+    }
+
+    fn description(&self) -> &str {
+        "Diagnose toolchains, configuration and signaling connectivity"
+    }
+}
+
+impl DoctorCommand {
+    /// Tools required by the selected languages, without duplicates (protoc is shared)
+    fn required_tools(&self) -> Vec<RequiredTool> {
+        let languages: Vec<SupportedLanguage> = if self.all_languages {
+            SupportedLanguage::value_variants().to_vec()
+        } else {
+            self.language.clone()
+        };
+
+        let mut tools: Vec<RequiredTool> = Vec::new();
+        for language in languages {
+            for tool in GeneratorFactory::get_generator(language).required_tools() {
+                if !tools.iter().any(|t| t.name == tool.name) {
+                    tools.push(tool);
+                }
+            }
+        }
+        tools
+    }
+}
+
+fn check_tool(
+    tool: &RequiredTool,
+    project_root: &Path,
+    plugin_config: Option<&ProtocPluginConfig>,
+) -> Diagnostic {
+    let Some(path) = locate_tool(tool.name, project_root) else {
+        let status = if tool.required {
+            DiagnosticStatus::Fail
+        } else {
+            DiagnosticStatus::Warn
+        };
+        let detail = if tool.required {
+            "not found"
+        } else {
+            "not found (optional)"
+        };
+        return Diagnostic::new(tool.name, status, detail).with_fix(tool.install_hint);
+    };
+
+    let version = tool_version(&path);
+    let requirement = plugin_config.and_then(|config| config.requirement(tool.name));
+    match (version, requirement) {
+        (Some(version), Some(requirement)) if !requirement.matches(&version) => Diagnostic::new(
+            tool.name,
+            DiagnosticStatus::Fail,
+            format!("v{version} does not satisfy '{requirement}'"),
+        )
+        .with_fix(format!("Upgrade {}: {}", tool.name, tool.install_hint)),
+        (Some(version), _) => {
+            Diagnostic::new(tool.name, DiagnosticStatus::Pass, format!("v{version}"))
+        }
+        (None, Some(requirement)) => Diagnostic::new(
+            tool.name,
+            DiagnosticStatus::Warn,
+            format!("version unknown (requires '{requirement}')"),
+        ),
+        (None, None) => Diagnostic::new(
+            tool.name,
+            DiagnosticStatus::Pass,
+            path.display().to_string(),
+        ),
+    }
+}
+
+/// Find a tool in the project's node_modules/.bin, ~/.actr/bin or on PATH
+fn locate_tool(name: &str, project_root: &Path) -> Option<PathBuf> {
+    let local = project_root.join("node_modules/.bin").join(name);
+    if local.exists() {
+        return Some(local);
+    }
+    find_plugin(name)
+}
+
+fn tool_version(path: &Path) -> Option<String> {
+    let output = StdCommand::new(path).arg("--version").output().ok()?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_version_output(&text)
+}
+
+fn render_table(diagnostics: &[Diagnostic]) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Check").add_attribute(Attribute::Bold),
+        Cell::new("Status").add_attribute(Attribute::Bold),
+        Cell::new("Details").add_attribute(Attribute::Bold),
+    ]);
+
+    for diagnostic in diagnostics {
+        let status = match diagnostic.status {
+            DiagnosticStatus::Pass => Cell::new("✔ Pass").fg(Color::Green),
+            DiagnosticStatus::Warn => Cell::new("⚠ Warn").fg(Color::Yellow),
+            DiagnosticStatus::Fail => Cell::new("✘ Fail").fg(Color::Red),
+        };
+        table.add_row(vec![
+            Cell::new(&diagnostic.check),
+            status,
+            Cell::new(&diagnostic.detail),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_tools_are_deduplicated() {
+        let command = DoctorCommand {
+            language: vec![SupportedLanguage::Rust, SupportedLanguage::Swift],
+            all_languages: false,
+            config_file: None,
+            timeout: 5,
+        };
+        let tools = command.required_tools();
+        assert_eq!(tools.iter().filter(|t| t.name == "protoc").count(), 1);
+        assert!(tools.iter().any(|t| t.name == "xcodegen"));
+        assert!(tools.iter().any(|t| t.name == "rustfmt" && !t.required));
+    }
+}
@@ -0,0 +1,233 @@
+//! Export command implementation - bundle a project for air-gapped machines
+//!
+//! `actr export` packs Actr.toml, Actr.lock.toml and the protos cached under
+//! `protos/remote/` for every locked service into a gzip-compressed tarball.
+//! `actr import` restores such a bundle so `install --offline` and `gen` work
+//! without a signaling server.
+
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType};
+use crate::human_println;
+use actr_config::LockFile;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clap::Args;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Manifest at the root of every bundle
+pub const MANIFEST_FILE: &str = "actr-bundle.json";
+
+/// Bundle layout version written by this CLI
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// Directory of cached protos inside the bundle, mirroring the project layout
+pub const REMOTE_PROTO_DIR: &str = "protos/remote";
+
+/// Export command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Bundle the project and its cached protos for offline use",
+    long_about = "Pack Actr.toml, Actr.lock.toml and the cached protos of every locked service into a .tar.gz bundle that 'actr import' restores on another machine.\n\nExamples:\n  actr export                              # Write actr-bundle.tar.gz\n  actr export --output bundle.tar.gz"
+)]
+pub struct ExportCommand {
+    /// Bundle file to write
+    #[arg(short, long, value_name = "FILE", default_value = "actr-bundle.tar.gz")]
+    pub output: PathBuf,
+}
+
+/// Contents of [`MANIFEST_FILE`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub created_at: DateTime<Utc>,
+    /// Package name of the exported project
+    pub package: String,
+    pub services: Vec<BundledService>,
+}
+
+/// One locked service and the proto files bundled for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundledService {
+    pub name: String,
+    pub fingerprint: String,
+    pub files: Vec<String>,
+}
+
+#[async_trait]
+impl Command for ExportCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let (config_manager, cache_manager) = (
+            context.container.get_config_manager()?,
+            context.container.get_cache_manager()?,
+        );
+        let project_root = config_manager.get_project_root().to_path_buf();
+        let config_path = project_root.join("Actr.toml");
+        let lock_path = project_root.join("Actr.lock.toml");
+        if !lock_path.exists() {
+            return Err(ActrCliError::InvalidProject {
+                message: "Actr.lock.toml not found. Run 'actr install' before exporting."
+                    .to_string(),
+            }
+            .into());
+        }
+        let config = config_manager.load_config(&config_path).await?;
+        let lock_file = LockFile::from_file(&lock_path)
+            .with_context(|| format!("Failed to read {}", lock_path.display()))?;
+
+        let mut entries: Vec<(String, Vec<u8>)> = vec![
+            ("Actr.toml".to_string(), std::fs::read(&config_path)?),
+            ("Actr.lock.toml".to_string(), std::fs::read(&lock_path)?),
+        ];
+        let mut services = Vec::new();
+        for dep in &lock_file.dependencies {
+            let cached = cache_manager
+                .get_cached_proto(&dep.name)
+                .await?
+                .ok_or_else(|| ActrCliError::Cache {
+                    message: format!(
+                        "Protos for '{}' are not cached under protos/remote/{}. Run 'actr install' first.",
+                        dep.name, dep.name
+                    ),
+                })?;
+            let mut files = Vec::with_capacity(cached.files.len());
+            for file in cached.files {
+                entries.push((
+                    format!("{REMOTE_PROTO_DIR}/{}/{}", dep.name, file.name),
+                    file.content.into_bytes(),
+                ));
+                files.push(file.name);
+            }
+            services.push(BundledService {
+                name: dep.name.clone(),
+                fingerprint: dep.fingerprint.clone(),
+                files,
+            });
+        }
+
+        let manifest = BundleManifest {
+            format: BUNDLE_FORMAT,
+            created_at: Utc::now(),
+            package: config.package.name.clone(),
+            services,
+        };
+        write_bundle(&self.output, &manifest, &entries)?;
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "output": self.output,
+                "manifest": manifest,
+            });
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &report,
+            )?));
+        }
+
+        for service in &manifest.services {
+            human_println!(
+                "📦 {} ({} files, {})",
+                service.name,
+                service.files.len(),
+                service.fingerprint
+            );
+        }
+        Ok(CommandResult::Success(format!(
+            "Exported {} services to {}",
+            manifest.services.len(),
+            self.output.display()
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::ConfigManager, ComponentType::CacheManager]
+    }
+
+    fn name(&self) -> &str {
+        "export"
+    }
+
+    fn description(&self) -> &str {
+        "Bundle the project and its cached protos for offline use"
+    }
+}
+
+/// Write the manifest followed by `entries` as a .tar.gz
+///
+/// The archive is written next to `output` and renamed into place, so a failed
+/// export never leaves a truncated bundle behind.
+pub fn write_bundle(
+    output: &std::path::Path,
+    manifest: &BundleManifest,
+    entries: &[(String, Vec<u8>)],
+) -> Result<()> {
+    let tmp_path = output.with_extension("tmp");
+    let file = std::fs::File::create(&tmp_path)
+        .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let manifest = serde_json::to_vec_pretty(manifest)?;
+    let mtime = Utc::now().timestamp().max(0) as u64;
+    for (path, data) in std::iter::once((MANIFEST_FILE, manifest.as_slice()))
+        .chain(entries.iter().map(|(p, d)| (p.as_str(), d.as_slice())))
+    {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(&mut header, path, data)?;
+    }
+
+    let result = builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map(drop)
+        .and_then(|()| std::fs::rename(&tmp_path, output));
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(e).with_context(|| format!("Failed to write {}", output.display()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::import::read_bundle;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let output = dir.path().join("bundle.tar.gz");
+        let manifest = BundleManifest {
+            format: BUNDLE_FORMAT,
+            created_at: Utc::now(),
+            package: "app".to_string(),
+            services: vec![BundledService {
+                name: "user-service".to_string(),
+                fingerprint: "service_semantic:abc".to_string(),
+                files: vec!["user.proto".to_string()],
+            }],
+        };
+        let entries = vec![
+            ("Actr.toml".to_string(), b"[package]\n".to_vec()),
+            (
+                format!("{REMOTE_PROTO_DIR}/user-service/user.proto"),
+                b"syntax = \"proto3\";\n".to_vec(),
+            ),
+        ];
+        write_bundle(&output, &manifest, &entries).unwrap();
+
+        let bundle = read_bundle(&output).unwrap();
+        assert_eq!(bundle.manifest, manifest);
+        assert_eq!(bundle.file("Actr.toml"), Some(b"[package]\n".as_slice()));
+        assert!(
+            bundle
+                .file("protos/remote/user-service/user.proto")
+                .is_some()
+        );
+        assert!(!output.with_extension("tmp").exists());
+    }
+}
@@ -1,81 +1,143 @@
 //! Fingerprint command implementation
 //!
-//! Computes and displays semantic fingerprints for proto files
-
-use crate::core::{Command, CommandContext, CommandResult, ComponentType};
-use actr_config::ConfigParser;
-use actr_version::{Fingerprint, ProtoFile};
+//! Computes and checks semantic fingerprints through the FingerprintValidator:
+//!
+//! - `actr fingerprint` - service fingerprint of the project's exports
+//! - `actr fingerprint compute <proto|dir>` - fingerprint of one proto file, or the
+//!   service fingerprint of every proto in a directory
+//! - `actr fingerprint service <name>` - fetch a service and recompute its fingerprint
+//!   from the downloaded protos
+//! - `actr fingerprint verify` - compare the protos cached under `protos/remote/`
+//!   with the fingerprints in Actr.lock.toml
+
+use crate::commands::lock::load_lock_file;
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, FingerprintValidator,
+    ProtoFile,
+};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use clap::Args;
-use std::fs;
-use std::path::Path;
-use tracing::{error, info};
-
-/// Verification status
-#[derive(Debug, Clone)]
-enum VerificationStatus {
-    Passed {
-        matched_fingerprint: String,
-    },
-    Failed {
-        mismatches: Vec<(String, String, String)>,
-    }, // (file_path, expected_fingerprint, actual_fingerprint)
-    NoLockFile,
-    NotRequested,
-}
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 
 /// Fingerprint command - computes semantic fingerprints
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 #[command(
     about = "Compute project and service fingerprints",
-    long_about = "Compute and display semantic fingerprints for proto files and services"
+    long_about = "Compute and verify semantic fingerprints of proto files and services. Without a subcommand, prints the service fingerprint of the project's exports.\n\nExamples:\n  actr fingerprint                              # Fingerprint of the exported protos\n  actr fingerprint compute protos/echo.proto    # One proto file\n  actr fingerprint compute protos/              # Every proto in a directory\n  actr fingerprint service user-service         # Recompute a registry service\n  actr fingerprint verify                       # Cached protos vs Actr.lock.toml"
 )]
 pub struct FingerprintCommand {
-    /// Configuration file path
-    #[arg(short, long, default_value = "Actr.toml")]
-    pub config: String,
+    #[command(subcommand)]
+    pub command: Option<FingerprintSubcommand>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = FingerprintFormat::Text, global = true)]
+    pub format: FingerprintFormat,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum FingerprintSubcommand {
+    /// Fingerprint of a proto file, or service fingerprint of a directory of protos
+    Compute {
+        /// Proto file or directory
+        path: PathBuf,
+    },
+    /// Fetch a service and recompute its fingerprint from the downloaded protos
+    Service {
+        /// Service name
+        name: String,
+    },
+    /// Compare the cached protos with the fingerprints in Actr.lock.toml
+    Verify,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum FingerprintFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
 
-    /// Output format (text, json, yaml)
-    #[arg(long, default_value = "text")]
-    pub format: String,
+/// Fingerprint of one proto file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileFingerprint {
+    pub path: String,
+    pub fingerprint: String,
+}
 
-    /// Calculate fingerprint for a specific proto file
-    #[arg(long)]
-    pub proto: Option<String>,
+/// Result of `compute` and of the project fingerprint
+#[derive(Debug, Clone, Serialize)]
+struct ComputeReport {
+    path: String,
+    fingerprint: String,
+    files: Vec<FileFingerprint>,
+}
 
-    /// Calculate service-level fingerprint (default)
-    #[arg(long)]
-    pub service_level: bool,
+/// Result of `service`
+#[derive(Debug, Clone, Serialize)]
+struct ServiceReport {
+    service: String,
+    advertised: String,
+    computed: String,
+    matches: bool,
+    files: Vec<FileFingerprint>,
+}
 
-    /// Verify fingerprint against lock file
-    #[arg(long)]
-    pub verify: bool,
+/// A fingerprint that differs from the lock file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Mismatch {
+    /// Lock file path of the proto, or `service` for the service fingerprint
+    pub path: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Verification of one locked service
+#[derive(Debug, Clone, Serialize)]
+struct ServiceVerification {
+    name: String,
+    locked: String,
+    cached: bool,
+    mismatches: Vec<Mismatch>,
 }
 
 #[async_trait]
 impl Command for FingerprintCommand {
-    async fn execute(&self, _context: &CommandContext) -> Result<CommandResult> {
-        if let Some(proto_path) = &self.proto {
-            // Calculate fingerprint for a specific proto file
-            info!(
-                "🔍 Computing proto semantic fingerprint for: {}",
-                proto_path
-            );
-            execute_proto_fingerprint(self, proto_path).await?;
-        } else {
-            // Calculate service-level fingerprint (default)
-            info!("🔍 Computing service semantic fingerprint...");
-            execute_service_fingerprint(self).await?;
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let validator = context.container.get_fingerprint_validator()?;
+        match &self.command {
+            None => self.project(context, validator.as_ref()).await,
+            Some(FingerprintSubcommand::Compute { path }) => {
+                self.compute(path, validator.as_ref()).await
+            }
+            Some(FingerprintSubcommand::Service { name }) => {
+                self.service(context, name, validator.as_ref()).await
+            }
+            Some(FingerprintSubcommand::Verify) => self.verify(context, validator.as_ref()).await,
         }
-
-        Ok(CommandResult::Success(
-            "Fingerprint calculation completed".to_string(),
-        ))
     }
 
     fn required_components(&self) -> Vec<ComponentType> {
-        vec![] // Fingerprint calculation doesn't require external components
+        match &self.command {
+            None => vec![
+                ComponentType::ConfigManager,
+                ComponentType::FingerprintValidator,
+            ],
+            Some(FingerprintSubcommand::Compute { .. }) => {
+                vec![ComponentType::FingerprintValidator]
+            }
+            Some(FingerprintSubcommand::Service { .. }) => vec![
+                ComponentType::ServiceDiscovery,
+                ComponentType::FingerprintValidator,
+            ],
+            Some(FingerprintSubcommand::Verify) => vec![
+                ComponentType::ConfigManager,
+                ComponentType::CacheManager,
+                ComponentType::FingerprintValidator,
+            ],
+        }
     }
 
     fn name(&self) -> &str {
@@ -87,632 +149,371 @@ impl Command for FingerprintCommand {
     }
 }
 
-/// Execute proto-level fingerprint calculation
-async fn execute_proto_fingerprint(args: &FingerprintCommand, proto_path: &str) -> Result<()> {
-    let path = Path::new(proto_path);
-    if !path.exists() {
-        return Err(anyhow::anyhow!("Proto file not found: {}", proto_path));
-    }
-
-    let content = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read proto file: {}", proto_path))?;
-
-    let fingerprint = Fingerprint::calculate_proto_semantic_fingerprint(&content)
-        .context("Failed to calculate proto fingerprint")?;
-
-    // Output
-    match args.format.as_str() {
-        "text" => show_proto_text_output(&fingerprint, proto_path),
-        "json" => show_proto_json_output(&fingerprint, proto_path)?,
-        "yaml" => show_proto_yaml_output(&fingerprint, proto_path)?,
-        _ => {
-            error!("Unsupported output format: {}", args.format);
-            return Err(anyhow::anyhow!("Unsupported format: {}", args.format));
+impl FingerprintCommand {
+    async fn project(
+        &self,
+        context: &CommandContext,
+        validator: &dyn FingerprintValidator,
+    ) -> Result<CommandResult> {
+        let config_manager = context.container.get_config_manager()?;
+        let config = config_manager
+            .load_config(&config_manager.get_project_root().join("Actr.toml"))
+            .await?;
+        if config.exports.is_empty() {
+            return Ok(CommandResult::Success(
+                "No proto files found in exports. Add proto files to the 'exports' array in Actr.toml to calculate fingerprints".to_string(),
+            ));
         }
-    }
-
-    Ok(())
-}
 
-/// Execute service-level fingerprint calculation
-async fn execute_service_fingerprint(args: &FingerprintCommand) -> Result<()> {
-    // Load configuration
-    let config_path = Path::new(&args.config);
-    let config = ConfigParser::from_file(config_path)
-        .with_context(|| format!("Failed to load config from {}", args.config))?;
-
-    // Convert actr_config::ProtoFile to actr_version::ProtoFile
-    let mut proto_files: Vec<ProtoFile> = config
-        .exports
-        .iter()
-        .map(|pf| ProtoFile {
-            name: pf.file_name().unwrap_or("unknown.proto").to_string(),
-            content: pf.content.clone(),
-            path: Some(pf.path.to_string_lossy().to_string()),
+        let files: Vec<ProtoFile> = config
+            .exports
+            .iter()
+            .map(|pf| ProtoFile {
+                name: pf.file_name().unwrap_or("unknown.proto").to_string(),
+                path: pf.path.clone(),
+                content: pf.content.clone(),
+                services: Vec::new(),
+            })
+            .collect();
+        let report = ComputeReport {
+            path: "exports".to_string(),
+            fingerprint: validator.compute_files_fingerprint(&files).await?.value,
+            files: file_fingerprints(validator, &files).await?,
+        };
+        self.render(&report, || {
+            compute_text("Service Semantic Fingerprint", &report)
         })
-        .collect();
-
-    // If verifying, also collect proto files from protos/remote directory
-    if args.verify {
-        let config_dir = config_path.parent().unwrap_or(Path::new("."));
-        let remote_dir = config_dir.join("protos").join("remote");
+        .map(CommandResult::Success)
+    }
 
-        if remote_dir.exists() {
-            collect_proto_files_from_directory(&remote_dir, &mut proto_files)?;
+    async fn compute(
+        &self,
+        path: &Path,
+        validator: &dyn FingerprintValidator,
+    ) -> Result<CommandResult> {
+        if !path.exists() {
+            return Err(ActrCliError::InvalidArgument {
+                message: format!("Proto file or directory not found: {}", path.display()),
+            }
+            .into());
         }
-    }
 
-    if proto_files.is_empty() {
-        // No proto files to calculate fingerprint for, but we can still verify lock file
-        if args.verify {
-            let verification_status = verify_fingerprint_against_lock("", &[], config_path)?;
-            match args.format.as_str() {
-                "text" => {
-                    show_verification_status_only(&verification_status);
-                }
-                "json" => {
-                    let verification_info = match verification_status {
-                        VerificationStatus::Passed { .. } => {
-                            serde_json::json!({"status": "passed"})
-                        }
-                        VerificationStatus::Failed { mismatches } => serde_json::json!({
-                            "status": "failed",
-                            "mismatches": mismatches.iter().map(|(file_path, expected, actual)| {
-                                serde_json::json!({
-                                    "file_path": file_path,
-                                    "expected": expected,
-                                    "actual": actual
-                                })
-                            }).collect::<Vec<_>>()
-                        }),
-                        VerificationStatus::NoLockFile => {
-                            serde_json::json!({"status": "no_lock_file"})
-                        }
-                        _ => serde_json::json!({"status": "not_requested"}),
-                    };
-                    println!(
-                        "{}",
-                        serde_json::to_string_pretty(&verification_info).unwrap()
-                    );
-                }
-                "yaml" => {
-                    let verification_info = match verification_status {
-                        VerificationStatus::Passed { .. } => {
-                            let mut map = serde_yaml::Mapping::new();
-                            map.insert(
-                                serde_yaml::Value::String("status".to_string()),
-                                serde_yaml::Value::String("passed".to_string()),
-                            );
-                            map
-                        }
-                        VerificationStatus::Failed { mismatches } => {
-                            let mut map = serde_yaml::Mapping::new();
-                            map.insert(
-                                serde_yaml::Value::String("status".to_string()),
-                                serde_yaml::Value::String("failed".to_string()),
-                            );
-                            map.insert(
-                                serde_yaml::Value::String("mismatches".to_string()),
-                                serde_yaml::Value::Sequence(
-                                    mismatches
-                                        .iter()
-                                        .map(|(file_path, expected, actual)| {
-                                            let mut mismatch_map = serde_yaml::Mapping::new();
-                                            mismatch_map.insert(
-                                                serde_yaml::Value::String("file_path".to_string()),
-                                                serde_yaml::Value::String(file_path.clone()),
-                                            );
-                                            mismatch_map.insert(
-                                                serde_yaml::Value::String("expected".to_string()),
-                                                serde_yaml::Value::String(expected.clone()),
-                                            );
-                                            mismatch_map.insert(
-                                                serde_yaml::Value::String("actual".to_string()),
-                                                serde_yaml::Value::String(actual.clone()),
-                                            );
-                                            serde_yaml::Value::Mapping(mismatch_map)
-                                        })
-                                        .collect(),
-                                ),
-                            );
-                            map
-                        }
-                        VerificationStatus::NoLockFile => {
-                            let mut map = serde_yaml::Mapping::new();
-                            map.insert(
-                                serde_yaml::Value::String("status".to_string()),
-                                serde_yaml::Value::String("no_lock_file".to_string()),
-                            );
-                            map
-                        }
-                        _ => {
-                            let mut map = serde_yaml::Mapping::new();
-                            map.insert(
-                                serde_yaml::Value::String("status".to_string()),
-                                serde_yaml::Value::String("not_requested".to_string()),
-                            );
-                            map
-                        }
-                    };
-                    println!(
-                        "{}",
-                        serde_yaml::to_string(&serde_yaml::Value::Mapping(verification_info))
-                            .unwrap()
-                    );
-                }
-                _ => {
-                    show_verification_status_only(&verification_status);
+        let (title, report) = if path.is_dir() {
+            let files = read_proto_dir(path)?;
+            if files.is_empty() {
+                return Err(ActrCliError::InvalidArgument {
+                    message: format!("No .proto files found in {}", path.display()),
                 }
+                .into());
             }
+            let report = ComputeReport {
+                path: path.display().to_string(),
+                fingerprint: validator.compute_files_fingerprint(&files).await?.value,
+                files: file_fingerprints(validator, &files).await?,
+            };
+            ("Service Semantic Fingerprint", report)
         } else {
-            match args.format.as_str() {
-                "text" => {
-                    println!("ℹ️  No proto files found in exports");
-                    println!(
-                        "   Add proto files to the 'exports' array in {} to calculate fingerprints",
-                        args.config
-                    );
-                }
-                "json" => {
-                    let output = serde_json::json!({
-                        "status": "no_exports",
-                        "message": "No proto files found in exports",
-                        "config_file": args.config
-                    });
-                    println!("{}", serde_json::to_string_pretty(&output).unwrap());
-                }
-                "yaml" => {
-                    let output = serde_yaml::Value::Mapping({
-                        let mut map = serde_yaml::Mapping::new();
-                        map.insert(
-                            serde_yaml::Value::String("status".to_string()),
-                            serde_yaml::Value::String("no_exports".to_string()),
-                        );
-                        map.insert(
-                            serde_yaml::Value::String("message".to_string()),
-                            serde_yaml::Value::String(
-                                "No proto files found in exports".to_string(),
-                            ),
-                        );
-                        map.insert(
-                            serde_yaml::Value::String("config_file".to_string()),
-                            serde_yaml::Value::String(args.config.clone()),
-                        );
-                        map
-                    });
-                    println!("{}", serde_yaml::to_string(&output).unwrap());
-                }
-                _ => {
-                    println!("ℹ️  No proto files found in exports");
-                }
-            }
-        }
-        return Ok(());
+            let file = read_proto(path, path.parent().unwrap_or(Path::new("")))?;
+            let files = file_fingerprints(validator, std::slice::from_ref(&file)).await?;
+            let report = ComputeReport {
+                path: path.display().to_string(),
+                fingerprint: files[0].fingerprint.clone(),
+                files,
+            };
+            ("Proto Semantic Fingerprint", report)
+        };
+        self.render(&report, || compute_text(title, &report))
+            .map(CommandResult::Success)
     }
 
-    // Calculate semantic fingerprint
-    let fingerprint = Fingerprint::calculate_service_semantic_fingerprint(&proto_files)
-        .context("Failed to calculate service fingerprint")?;
-
-    // Verify against lock file if requested
-    let verification_status = if args.verify {
-        verify_fingerprint_against_lock(&fingerprint, &proto_files, config_path)?
-    } else {
-        VerificationStatus::NotRequested
-    };
-
-    // Output
-    match args.format.as_str() {
-        "text" => show_text_output(&fingerprint, &proto_files, &verification_status),
-        "json" => show_json_output(&fingerprint, &proto_files, &verification_status)?,
-        "yaml" => show_yaml_output(&fingerprint, &proto_files, &verification_status)?,
-        _ => {
-            error!("Unsupported output format: {}", args.format);
-            return Err(anyhow::anyhow!("Unsupported format: {}", args.format));
-        }
+    async fn service(
+        &self,
+        context: &CommandContext,
+        name: &str,
+        validator: &dyn FingerprintValidator,
+    ) -> Result<CommandResult> {
+        let details = context
+            .container
+            .get_service_discovery()?
+            .get_service_details(name)
+            .await?;
+        let computed = validator
+            .compute_files_fingerprint(&details.proto_files)
+            .await?
+            .value;
+        let report = ServiceReport {
+            service: details.info.name.clone(),
+            matches: computed == details.info.fingerprint,
+            advertised: details.info.fingerprint.clone(),
+            computed,
+            files: file_fingerprints(validator, &details.proto_files).await?,
+        };
+
+        let output = self.render(&report, || {
+            let mut text = format!(
+                "📋 {}\n  Advertised: {}\n  Computed:   {}\n\n📦 Proto Files ({}):\n",
+                report.service,
+                report.advertised,
+                report.computed,
+                report.files.len()
+            );
+            for file in &report.files {
+                text.push_str(&format!("  - {} ({})\n", file.path, file.fingerprint));
+            }
+            if report.matches {
+                text.push_str("\n✅ Downloaded protos match the advertised fingerprint");
+            } else {
+                text.push_str("\n❌ Downloaded protos do not match the advertised fingerprint");
+            }
+            text
+        })?;
+        Ok(if report.matches {
+            CommandResult::Success(output)
+        } else {
+            CommandResult::Error(output)
+        })
     }
 
-    Ok(())
-}
-
-/// Show proto text output format
-fn show_proto_text_output(fingerprint: &str, proto_path: &str) {
-    println!("📋 Proto Semantic Fingerprint:");
-    println!("  File: {}", proto_path);
-    println!("  {fingerprint}");
-}
-
-/// Show proto JSON output format
-fn show_proto_json_output(fingerprint: &str, proto_path: &str) -> Result<()> {
-    let output = ProtoJsonOutput {
-        proto_file: proto_path.to_string(),
-        fingerprint: fingerprint.to_string(),
-    };
-
-    let json = serde_json::to_string_pretty(&output).context("Failed to serialize output")?;
-    println!("{json}");
-
-    Ok(())
-}
-
-/// Show proto YAML output format
-fn show_proto_yaml_output(fingerprint: &str, proto_path: &str) -> Result<()> {
-    let output = ProtoJsonOutput {
-        proto_file: proto_path.to_string(),
-        fingerprint: fingerprint.to_string(),
-    };
-
-    let yaml = serde_yaml::to_string(&output).context("Failed to serialize output")?;
-    println!("{yaml}");
-
-    Ok(())
-}
+    async fn verify(
+        &self,
+        context: &CommandContext,
+        validator: &dyn FingerprintValidator,
+    ) -> Result<CommandResult> {
+        let (project_root, cache_manager) = (
+            context
+                .container
+                .get_config_manager()?
+                .get_project_root()
+                .to_path_buf(),
+            context.container.get_cache_manager()?,
+        );
+        let lock_file = load_lock_file(&project_root)?;
+
+        let mut services = Vec::with_capacity(lock_file.dependencies.len());
+        for locked in &lock_file.dependencies {
+            let cached = cache_manager.get_cached_proto(&locked.name).await?;
+            let mut verification = ServiceVerification {
+                name: locked.name.clone(),
+                locked: locked.fingerprint.clone(),
+                cached: cached.is_some(),
+                mismatches: Vec::new(),
+            };
+            if let Some(cached) = cached {
+                let mut files = cached.files;
+                // read_dir order is platform dependent
+                files.sort_by(|a, b| a.name.cmp(&b.name));
+                verification.mismatches = verify_files(
+                    validator,
+                    &locked.fingerprint,
+                    locked
+                        .files
+                        .iter()
+                        .map(|meta| (meta.path.as_str(), meta.fingerprint.as_str())),
+                    &files,
+                )
+                .await?;
+            }
+            services.push(verification);
+        }
 
-/// Collect proto files from a directory recursively
-fn collect_proto_files_from_directory(dir: &Path, proto_files: &mut Vec<ProtoFile>) -> Result<()> {
-    fn visit_dir(dir: &Path, proto_files: &mut Vec<ProtoFile>, base_dir: &Path) -> Result<()> {
-        if let Ok(entries) = fs::read_dir(dir) {
-            for entry in entries {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_dir() {
-                    visit_dir(&path, proto_files, base_dir)?;
-                } else if path.extension().and_then(|s| s.to_str()) == Some("proto") {
-                    let content = fs::read_to_string(&path).with_context(|| {
-                        format!("Failed to read proto file: {}", path.display())
-                    })?;
-
-                    // Get relative path from the base directory
-                    let relative_path = path.strip_prefix(base_dir).unwrap_or(&path);
-                    let name = path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown.proto")
-                        .to_string();
-
-                    proto_files.push(ProtoFile {
-                        name,
-                        content,
-                        path: Some(relative_path.to_string_lossy().to_string()),
-                    });
+        let failed = services
+            .iter()
+            .filter(|s| !s.cached || !s.mismatches.is_empty())
+            .count();
+        let report = serde_json::json!({
+            "passed": failed == 0,
+            "error": (failed > 0).then(|| format!("{failed} dependencies do not match Actr.lock.toml")),
+            "services": services,
+        });
+        let output = self.render(&report, || {
+            let mut text = String::from("🔒 Fingerprint verification:\n");
+            for service in &services {
+                if !service.cached {
+                    text.push_str(&format!(
+                        "  ❌ {}: not cached under protos/remote/{}\n",
+                        service.name, service.name
+                    ));
+                    continue;
+                }
+                if service.mismatches.is_empty() {
+                    text.push_str(&format!("  ✅ {} ({})\n", service.name, service.locked));
+                    continue;
+                }
+                text.push_str(&format!("  ❌ {}\n", service.name));
+                for mismatch in &service.mismatches {
+                    text.push_str(&format!(
+                        "     {}\n       Expected: {}\n       Actual:   {}\n",
+                        mismatch.path, mismatch.expected, mismatch.actual
+                    ));
                 }
             }
-        }
-        Ok(())
+            if failed == 0 {
+                text.push_str(&format!(
+                    "\n✅ {} dependencies match Actr.lock.toml",
+                    services.len()
+                ));
+            } else {
+                text.push_str(&format!(
+                    "\n❌ {failed} dependencies do not match Actr.lock.toml. Run 'actr install' to restore the cache"
+                ));
+            }
+            text
+        })?;
+        Ok(if failed == 0 {
+            CommandResult::Success(output)
+        } else {
+            CommandResult::Error(output)
+        })
     }
 
-    visit_dir(dir, proto_files, dir)
-}
-
-/// Show verification status only (for cases where no proto files are available)
-fn show_verification_status_only(verification_status: &VerificationStatus) {
-    match verification_status {
-        VerificationStatus::Passed { .. } => {
-            println!("✅ Fingerprint verification: PASSED");
-            println!("  All lock file fingerprints verified against actual files");
-        }
-        VerificationStatus::Failed { mismatches } => {
-            println!("❌ Fingerprint verification: FAILED");
-            println!("  File-level mismatches:");
-            for (file_path, expected, actual) in mismatches {
-                println!("    File: {}", file_path);
-                println!("      Expected: {}", expected);
-                println!("      Actual:   {}", actual);
+    /// Serialize `report` in the selected format, or build the text output
+    fn render<T: Serialize>(&self, report: &T, text: impl FnOnce() -> String) -> Result<String> {
+        Ok(match self.format {
+            FingerprintFormat::Text => text(),
+            FingerprintFormat::Json => {
+                serde_json::to_string_pretty(report).context("Failed to serialize output")?
             }
-        }
-        VerificationStatus::NoLockFile => {
-            println!("⚠️  Fingerprint verification: No lock file found");
-        }
-        VerificationStatus::NotRequested => {
-            // No verification requested, don't show anything
-        }
+            FingerprintFormat::Yaml => {
+                serde_yaml::to_string(report).context("Failed to serialize output")?
+            }
+        })
     }
 }
 
-/// Show text output format
-fn show_text_output(
-    fingerprint: &str,
-    proto_files: &[ProtoFile],
-    verification_status: &VerificationStatus,
-) {
-    println!("📋 Service Semantic Fingerprint:");
-    println!("  {fingerprint}");
-    println!("\n📦 Proto Files ({}):", proto_files.len());
-    for pf in proto_files {
-        println!("  - {}", pf.name);
+/// Compare `files` with a locked service fingerprint and its `(path, fingerprint)` entries
+///
+/// Entries without a fingerprint come from older lock files and are only checked for presence.
+pub async fn verify_files<'a>(
+    validator: &dyn FingerprintValidator,
+    locked_fingerprint: &str,
+    locked_files: impl Iterator<Item = (&'a str, &'a str)>,
+    files: &[ProtoFile],
+) -> Result<Vec<Mismatch>> {
+    let mut mismatches = Vec::new();
+
+    let actual = validator.compute_files_fingerprint(files).await?.value;
+    if actual != locked_fingerprint {
+        mismatches.push(Mismatch {
+            path: "service".to_string(),
+            expected: locked_fingerprint.to_string(),
+            actual,
+        });
     }
 
-    // Show verification status
-    match verification_status {
-        VerificationStatus::Passed {
-            matched_fingerprint: _,
-        } => {
-            println!("\n✅ Fingerprint verification: PASSED");
-            println!("  All lock file fingerprints verified against actual files");
+    for (path, expected) in locked_files {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        let Some(file) = files.iter().find(|f| f.name == file_name) else {
+            mismatches.push(Mismatch {
+                path: path.to_string(),
+                expected: expected.to_string(),
+                actual: "missing".to_string(),
+            });
+            continue;
+        };
+        if expected.is_empty() {
+            continue;
         }
-        VerificationStatus::Failed { mismatches } => {
-            println!("\n❌ Fingerprint verification: FAILED");
-            println!("  File-level mismatches:");
-            for (file_path, expected, actual) in mismatches {
-                println!("    File: {}", file_path);
-                println!("      Expected: {}", expected);
-                println!("      Actual:   {}", actual);
-            }
-        }
-        VerificationStatus::NoLockFile => {
-            println!("\n⚠️  Fingerprint verification: No lock file found");
-        }
-        VerificationStatus::NotRequested => {
-            // No verification requested, don't show anything
+        let actual = validator.compute_proto_fingerprint(file).await?.value;
+        if actual != expected {
+            mismatches.push(Mismatch {
+                path: path.to_string(),
+                expected: expected.to_string(),
+                actual,
+            });
         }
     }
-}
-
-/// Show JSON output format
-fn show_json_output(
-    fingerprint: &str,
-    proto_files: &[ProtoFile],
-    verification_status: &VerificationStatus,
-) -> Result<()> {
-    let verification_info = match verification_status {
-        VerificationStatus::Passed {
-            matched_fingerprint,
-        } => serde_json::json!({
-            "status": "passed",
-            "matched_fingerprint": matched_fingerprint
-        }),
-        VerificationStatus::Failed { mismatches } => serde_json::json!({
-            "status": "failed",
-            "mismatches": mismatches.iter().map(|(file_path, expected, actual)| {
-                serde_json::json!({
-                    "file_path": file_path,
-                    "expected": expected,
-                    "actual": actual
-                })
-            }).collect::<Vec<_>>()
-        }),
-        VerificationStatus::NoLockFile => serde_json::json!({
-            "status": "no_lock_file"
-        }),
-        VerificationStatus::NotRequested => serde_json::json!({
-            "status": "not_requested"
-        }),
-    };
-
-    let output = serde_json::json!({
-        "service_fingerprint": fingerprint,
-        "proto_files": proto_files.iter().map(|pf| pf.name.clone()).collect::<Vec<_>>(),
-        "verification": verification_info
-    });
-
-    let json = serde_json::to_string_pretty(&output).context("Failed to serialize output")?;
-    println!("{json}");
-
-    Ok(())
-}
-
-/// Show YAML output format
-fn show_yaml_output(
-    fingerprint: &str,
-    proto_files: &[ProtoFile],
-    verification_status: &VerificationStatus,
-) -> Result<()> {
-    let verification_info = match verification_status {
-        VerificationStatus::Passed {
-            matched_fingerprint,
-        } => serde_yaml::Value::Mapping({
-            let mut map = serde_yaml::Mapping::new();
-            map.insert(
-                serde_yaml::Value::String("status".to_string()),
-                serde_yaml::Value::String("passed".to_string()),
-            );
-            map.insert(
-                serde_yaml::Value::String("matched_fingerprint".to_string()),
-                serde_yaml::Value::String(matched_fingerprint.clone()),
-            );
-            map
-        }),
-        VerificationStatus::Failed { mismatches } => serde_yaml::Value::Mapping({
-            let mut map = serde_yaml::Mapping::new();
-            map.insert(
-                serde_yaml::Value::String("status".to_string()),
-                serde_yaml::Value::String("failed".to_string()),
-            );
-            map.insert(
-                serde_yaml::Value::String("mismatches".to_string()),
-                serde_yaml::Value::Sequence(
-                    mismatches
-                        .iter()
-                        .map(|(file_path, expected, actual)| {
-                            let mut mismatch_map = serde_yaml::Mapping::new();
-                            mismatch_map.insert(
-                                serde_yaml::Value::String("file_path".to_string()),
-                                serde_yaml::Value::String(file_path.clone()),
-                            );
-                            mismatch_map.insert(
-                                serde_yaml::Value::String("expected".to_string()),
-                                serde_yaml::Value::String(expected.clone()),
-                            );
-                            mismatch_map.insert(
-                                serde_yaml::Value::String("actual".to_string()),
-                                serde_yaml::Value::String(actual.clone()),
-                            );
-                            serde_yaml::Value::Mapping(mismatch_map)
-                        })
-                        .collect(),
-                ),
-            );
-            map
-        }),
-        VerificationStatus::NoLockFile => serde_yaml::Value::Mapping({
-            let mut map = serde_yaml::Mapping::new();
-            map.insert(
-                serde_yaml::Value::String("status".to_string()),
-                serde_yaml::Value::String("no_lock_file".to_string()),
-            );
-            map
-        }),
-        VerificationStatus::NotRequested => serde_yaml::Value::Mapping({
-            let mut map = serde_yaml::Mapping::new();
-            map.insert(
-                serde_yaml::Value::String("status".to_string()),
-                serde_yaml::Value::String("not_requested".to_string()),
-            );
-            map
-        }),
-    };
-
-    let output = serde_yaml::Value::Mapping({
-        let mut map = serde_yaml::Mapping::new();
-        map.insert(
-            serde_yaml::Value::String("service_fingerprint".to_string()),
-            serde_yaml::Value::String(fingerprint.to_string()),
-        );
-        map.insert(
-            serde_yaml::Value::String("proto_files".to_string()),
-            serde_yaml::Value::Sequence(
-                proto_files
-                    .iter()
-                    .map(|pf| serde_yaml::Value::String(pf.name.clone()))
-                    .collect(),
-            ),
-        );
-        map.insert(
-            serde_yaml::Value::String("verification".to_string()),
-            verification_info,
-        );
-        map
-    });
-
-    let yaml = serde_yaml::to_string(&output).context("Failed to serialize output")?;
-    println!("{yaml}");
 
-    Ok(())
+    Ok(mismatches)
 }
 
-/// Verify fingerprint against lock file
-fn verify_fingerprint_against_lock(
-    current_fingerprint: &str,
-    proto_files: &[ProtoFile],
-    config_path: &Path,
-) -> Result<VerificationStatus> {
-    let lock_path = config_path.with_file_name("actr.lock.toml");
-    if !lock_path.exists() {
-        return Ok(VerificationStatus::NoLockFile);
-    }
-
-    let lock_content = fs::read_to_string(&lock_path)
-        .with_context(|| format!("Failed to read lock file: {}", lock_path.display()))?;
-
-    let lock_file: toml::Value = toml::from_str(&lock_content)
-        .with_context(|| format!("Failed to parse lock file: {}", lock_path.display()))?;
-
-    let mut mismatches = Vec::new();
-    let mut service_fingerprint_mismatch = None;
-
-    // Check service-level fingerprints first
-    if let Some(dependencies) = lock_file.get("dependency").and_then(|d| d.as_array()) {
-        for dep in dependencies {
-            if let Some(expected_service_fp) = dep.get("fingerprint").and_then(|f| f.as_str())
-                && expected_service_fp.starts_with("service_semantic:")
-            {
-                // Use the current fingerprint passed in
-                let expected_fp = expected_service_fp.to_string();
-                let actual_fp = current_fingerprint.to_string();
-
-                if expected_fp != actual_fp {
-                    service_fingerprint_mismatch = Some((expected_fp, actual_fp));
-                }
-                break; // Only check the first dependency for now
-            }
-        }
+async fn file_fingerprints(
+    validator: &dyn FingerprintValidator,
+    files: &[ProtoFile],
+) -> Result<Vec<FileFingerprint>> {
+    let mut fingerprints = Vec::with_capacity(files.len());
+    for file in files {
+        fingerprints.push(FileFingerprint {
+            path: file.path.display().to_string(),
+            fingerprint: validator.compute_proto_fingerprint(file).await?.value,
+        });
     }
+    Ok(fingerprints)
+}
 
-    // Check each proto file from lock file against actual proto files
-    if let Some(dependencies) = lock_file.get("dependency").and_then(|d| d.as_array()) {
-        for dep in dependencies {
-            if let Some(files) = dep.get("files").and_then(|f| f.as_array()) {
-                for file in files {
-                    if let (Some(lock_path), Some(expected_fp)) = (
-                        file.get("path").and_then(|p| p.as_str()),
-                        file.get("fingerprint").and_then(|f| f.as_str()),
-                    ) {
-                        // Empty fingerprints in lock file are considered mismatches
-                        if expected_fp.is_empty() {
-                            mismatches.push((
-                                lock_path.to_string(),
-                                expected_fp.to_string(),
-                                "ERROR: Empty fingerprint in lock file".to_string(),
-                            ));
-                            continue;
-                        }
-
-                        // Find the corresponding proto file in our proto_files list
-                        let mut found = false;
-                        for proto_file in proto_files {
-                            if let Some(proto_path) = &proto_file.path
-                                && proto_path == lock_path
-                            {
-                                match Fingerprint::calculate_proto_semantic_fingerprint(
-                                    &proto_file.content,
-                                ) {
-                                    Ok(actual_fp) => {
-                                        if actual_fp != expected_fp {
-                                            mismatches.push((
-                                                lock_path.to_string(),
-                                                expected_fp.to_string(),
-                                                actual_fp,
-                                            ));
-                                        }
-                                    }
-                                    Err(e) => {
-                                        // Could not calculate fingerprint for this file
-                                        mismatches.push((
-                                            lock_path.to_string(),
-                                            expected_fp.to_string(),
-                                            format!("ERROR: {}", e),
-                                        ));
-                                    }
-                                }
-                                found = true;
-                                break;
-                            }
-                        }
-
-                        if !found {
-                            // Proto file not found in our list
-                            mismatches.push((
-                                lock_path.to_string(),
-                                expected_fp.to_string(),
-                                "ERROR: Proto file not found".to_string(),
-                            ));
-                        }
-                    }
-                }
-            }
+fn compute_text(title: &str, report: &ComputeReport) -> String {
+    let mut text = format!("📋 {title}:\n  {}\n", report.fingerprint);
+    if report.files.len() > 1 || report.path == "exports" {
+        text.push_str(&format!("\n📦 Proto Files ({}):\n", report.files.len()));
+        for file in &report.files {
+            text.push_str(&format!("  - {} ({})\n", file.path, file.fingerprint));
         }
+    } else {
+        text.push_str(&format!("  File: {}\n", report.path));
     }
+    text.trim_end().to_string()
+}
 
-    // If there are service fingerprint mismatches, add them to the mismatches list
-    if let Some((expected, actual)) = service_fingerprint_mismatch {
-        mismatches.push(("SERVICE_FINGERPRINT".to_string(), expected, actual));
-    }
+/// Every `.proto` below `dir`, sorted by path, with paths relative to `dir`
+fn read_proto_dir(dir: &Path) -> Result<Vec<ProtoFile>> {
+    let mut paths: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "proto"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| read_proto(path, dir)).collect()
+}
 
-    if mismatches.is_empty() {
-        // All proto files match
-        Ok(VerificationStatus::Passed {
-            matched_fingerprint: "all_files_verified".to_string(),
-        })
-    } else {
-        // Some proto files don't match
-        Ok(VerificationStatus::Failed { mismatches })
-    }
+fn read_proto(path: &Path, base: &Path) -> Result<ProtoFile> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read proto file: {}", path.display()))?;
+    Ok(ProtoFile {
+        name: path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("unknown.proto")
+            .to_string(),
+        path: path.strip_prefix(base).unwrap_or(path).to_path_buf(),
+        content,
+        services: Vec::new(),
+    })
 }
 
-/// JSON output structure for proto files
-#[derive(serde::Serialize)]
-struct ProtoJsonOutput {
-    proto_file: String,
-    fingerprint: String,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::DefaultFingerprintValidator;
+
+    const USER: &str = "syntax = \"proto3\";\npackage user;\nmessage User { string id = 1; }\n";
+
+    #[tokio::test]
+    async fn test_verify_files_reports_changed_protos() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested/user.proto"), USER).unwrap();
+        let files = read_proto_dir(dir.path()).unwrap();
+        assert_eq!(files[0].path, PathBuf::from("nested/user.proto"));
+
+        let validator = DefaultFingerprintValidator::new();
+        let service = validator.compute_files_fingerprint(&files).await.unwrap();
+        let file = validator
+            .compute_proto_fingerprint(&files[0])
+            .await
+            .unwrap();
+        let locked = [("user-service/user.proto", file.value.as_str())];
+
+        let mismatches = verify_files(&validator, &service.value, locked.into_iter(), &files)
+            .await
+            .unwrap();
+        assert!(mismatches.is_empty());
+
+        let mut changed = files.clone();
+        changed[0].content = USER.replace("string id", "int64 id");
+        let mismatches = verify_files(&validator, &service.value, locked.into_iter(), &changed)
+            .await
+            .unwrap();
+        let paths: Vec<&str> = mismatches.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, vec!["service", "user-service/user.proto"]);
+    }
 }
@@ -1,23 +1,46 @@
 //! # Code Generation Command
 //!
-//! Generate Rust Actor code from proto files, including:
+//! Generate Actor code from proto files, including:
 //! 1. Protobuf message types
 //! 2. Actor infrastructure code
 //! 3. User business logic scaffolds (with TODO comments)
+//!
+//! Language-specific work is delegated to a `LanguageGenerator` via `execute_codegen`.
 
+use crate::buf_config::{BUF_EXPORT_DIR, BUF_WORK_YAML, BUF_YAML, BufLayout};
 use crate::commands::Command;
 use crate::commands::SupportedLanguage;
-use crate::commands::codegen::{GenContext, execute_codegen};
+use crate::commands::codegen::build_script::{self, GenerationOptions};
+use crate::commands::codegen::format::FormatConfig;
+use crate::commands::codegen::generated_crate::GeneratedCrate;
+use crate::commands::codegen::header::GenHeader;
+use crate::commands::codegen::{
+    GenContext, ProtoFilter, execute_codegen, gradle, regenerate, regenerate_cached,
+};
+use crate::core::{config_schema, profile};
 use crate::error::{ActrCliError, Result};
-use crate::plugin_config::{load_protoc_plugin_config, version_is_at_least};
+use crate::human_println;
+use crate::tr;
 use crate::utils::to_pascal_case;
-// 只导入必要的类型，避免拉入不需要的依赖如 sqlite
-// use actr_framework::prelude::*;
 use async_trait::async_trait;
 use clap::Args;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
-use std::process::Command as StdCommand;
-use tracing::{debug, info, warn};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Quiet period after the last proto change before regenerating
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// One language to generate, the directory its output goes to and its formatter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenTarget {
+    pub language: SupportedLanguage,
+    pub output: PathBuf,
+    /// `[build.format.<language>]`
+    pub format: FormatConfig,
+}
 
 #[derive(Args, Debug, Clone)]
 #[command(
@@ -26,7 +49,17 @@ use tracing::{debug, info, warn};
   - rust:   src/generated
   - swift:  {PascalName}/Generated (e.g., EchoApp/Generated)
   - kotlin: app/src/main/java/{package}/generated
-  - python: generated"
+  - python: generated
+  - typescript: src/generated
+
+Several languages in one run:
+  actr gen -l rust -l kotlin
+  actr gen -l python,swift
+
+or list targets in Actr.toml (used when -l is not given):
+  [[build.targets]]
+  language = \"typescript\"
+  output = \"web/src/generated\""
 )]
 pub struct GenCommand {
     /// Input proto file or directory
@@ -53,6 +86,10 @@ pub struct GenCommand {
     #[arg(long)]
     pub overwrite_user_code: bool,
 
+    /// Append stubs for new RPCs to existing user code files, keeping their contents
+    #[arg(long, conflicts_with = "overwrite_user_code")]
+    pub merge_scaffold: bool,
+
     /// Skip formatting
     #[arg(long = "no-format")]
     pub no_format: bool,
@@ -61,71 +98,527 @@ pub struct GenCommand {
     #[arg(long)]
     pub debug: bool,
 
-    /// Target language for generation
-    #[arg(short, long, default_value = "rust")]
-    pub language: SupportedLanguage,
+    /// Target language; repeat or comma-separate to generate several in one run
+    /// (default: `[[build.targets]]` in Actr.toml, else rust)
+    #[arg(short, long, value_delimiter = ',', value_name = "LANG")]
+    pub language: Vec<SupportedLanguage>,
+
+    /// Kotlin only: write generated sources into <PATH>/build/generated/source/actr and
+    /// wire them into the module with <PATH>/actr.gradle.kts
+    #[arg(long, value_name = "PATH", conflicts_with = "output")]
+    pub gradle_module: Option<PathBuf>,
+
+    /// Rust only: generate into a separate crate <NAME>/ with its own Cargo.toml,
+    /// added to the workspace and to the app's dependencies
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["output", "gradle_module"])]
+    pub as_crate: Option<String>,
+
+    /// Generate code for every member listed in Actr.workspace.toml
+    #[arg(long)]
+    pub workspace: bool,
+
+    /// Ignore the generation cache (.actr/gen-cache-<language>.json) and regenerate everything
+    #[arg(long)]
+    pub force: bool,
+
+    /// Maximum number of protoc processes to run in parallel (default: number of CPUs)
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// Keep running and regenerate the affected protos whenever the input changes
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Only generate protos matching this glob, relative to the input (repeatable;
+    /// replaces `[build.proto] include`)
+    #[arg(long = "include", value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip protos or directories matching this glob, relative to the input (repeatable;
+    /// replaces `[build.proto] exclude`)
+    #[arg(long = "exclude", value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Read the input through its buf.yaml / buf.work.yaml and resolve buf deps with
+    /// `buf export` (autodetected when the input directory holds a buf config)
+    #[arg(long)]
+    pub buf: bool,
+
+    /// Regenerate into a temporary directory and fail if the output differs (for CI)
+    #[arg(long, conflicts_with_all = ["watch", "clean", "workspace"])]
+    pub check: bool,
+
+    /// Write a build.rs that regenerates the Rust code on `cargo build` through the
+    /// actr-cli library, and add actr-cli to [build-dependencies]
+    #[arg(long, conflicts_with_all = ["check", "watch", "workspace", "as_crate"])]
+    pub emit_build_rs: bool,
+
+    /// Offline mode: only use cached protos and never install plugins (set by --offline)
+    #[arg(skip)]
+    pub offline: bool,
+
+    /// Suppress progress bars (set by --quiet)
+    #[arg(skip)]
+    pub quiet: bool,
+
+    /// Skip the validation step (`cargo check`, `swift build`); set by build scripts
+    #[arg(skip)]
+    pub no_validate: bool,
 }
 
 #[async_trait]
 impl Command for GenCommand {
     async fn execute(&self) -> Result<()> {
+        self.run().await.map(|_| ())
+    }
+}
+
+impl GenCommand {
+    /// Run code generation and return the files that were written
+    ///
+    /// Protos are discovered once and shared by every target.
+    pub async fn run(&self) -> Result<Vec<PathBuf>> {
+        if self.emit_build_rs {
+            return self.emit_build_rs();
+        }
+
         // Check if Actr.lock.toml exists
         self.check_lock_file()?;
 
-        // Determine output path based on language
-        let output = self.determine_output_path()?;
+        let issues = config_schema::validate_file(&self.config);
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+            return Err(ActrCliError::config_error(format!(
+                "Actr.toml is invalid:\n{}",
+                issues.join("\n")
+            )));
+        }
+        let config = profile::parse_config(&self.config)
+            .map_err(|e| ActrCliError::config_error(format!("Failed to parse Actr.toml: {e:#}")))?;
+        let targets = self.targets(&config)?;
+        let header = self.header()?;
+
+        if self.check {
+            self.validate_inputs()?;
+            let proto_files = self.discover_proto_files()?;
+            let include_paths = self.buf_include_paths()?;
+            let mut drifted = 0;
+            for target in &targets {
+                let mut context =
+                    self.gen_context(proto_files.clone(), target.output.clone(), config.clone());
+                context.include_paths = include_paths.clone();
+                context.header = header.clone();
+                context.format = target.format.clone();
+                drifted += self.check_drift(target.language, context).await?;
+            }
+            if drifted > 0 {
+                return Err(ActrCliError::CodeGeneration(format!(
+                    "{drifted} generated file(s) differ from the protos"
+                )));
+            }
+            return Ok(Vec::new());
+        }
+
+        let proto_files = self.preprocess(&targets)?;
+        let include_paths = self.buf_include_paths()?;
+        let mut files = Vec::new();
+        let mut contexts = Vec::with_capacity(targets.len());
+        for target in &targets {
+            info!(
+                "{}",
+                tr!(
+                    "gen.starting",
+                    language = target.language.as_str(),
+                    output = target.output.display()
+                )
+            );
+            let mut context =
+                self.gen_context(proto_files.clone(), target.output.clone(), config.clone());
+            context.include_paths = include_paths.clone();
+            context.header = header.clone();
+            context.format = target.format.clone();
+            files.extend(execute_codegen(target.language, &context).await?);
+            contexts.push((target.language, context));
+        }
 
-        info!(
-            "🚀 Start code generation (language: {:?})...",
+        if self.watch {
+            self.watch_and_regenerate(contexts).await?;
+        }
+        Ok(files)
+    }
+
+    /// Languages and outputs to generate: `--language`, else `[[build.targets]]`, else rust
+    ///
+    /// ```toml
+    /// [[build.targets]]
+    /// language = "rust"
+    ///
+    /// [[build.targets]]
+    /// language = "typescript"
+    /// output = "web/src/generated"
+    /// ```
+    pub fn targets(&self, config: &actr_config::Config) -> Result<Vec<GenTarget>> {
+        let value = profile::load_value(&self.config)
+            .map_err(|e| ActrCliError::config_error(format!("{e:#}")))?;
+        let mut targets: Vec<(SupportedLanguage, Option<PathBuf>)> = if !self.language.is_empty() {
             self.language
-        );
-        let config = actr_config::ConfigParser::from_file(&self.config)
-            .map_err(|e| ActrCliError::config_error(format!("Failed to parse Actr.toml: {e}")))?;
-
-        let proto_files = self.preprocess()?;
-        if self.language != SupportedLanguage::Rust {
-            let context = GenContext {
-                proto_files,
-                input_path: self.input.clone(),
-                output,
-                config_path: self.config.clone(),
-                config: config.clone(),
-                no_scaffold: self.no_scaffold,
-                overwrite_user_code: self.overwrite_user_code,
-                no_format: self.no_format,
-                debug: self.debug,
+                .iter()
+                .map(|language| (*language, None))
+                .collect()
+        } else {
+            configured_targets(&value)?
+        };
+        if targets.is_empty() {
+            targets.push((SupportedLanguage::Rust, None));
+        }
+
+        if let Some(output) = &self.output {
+            if targets.len() > 1 {
+                return Err(ActrCliError::config_error(
+                    "--output applies to a single target; set `output` per target in \
+                     [[build.targets]] instead",
+                ));
+            }
+            targets[0].1 = Some(output.clone());
+        }
+
+        if let Some(module) = &self.gradle_module {
+            if !targets
+                .iter()
+                .any(|(language, _)| *language == SupportedLanguage::Kotlin)
+            {
+                return Err(ActrCliError::config_error(
+                    "--gradle-module only applies to Kotlin (-l kotlin)",
+                ));
+            }
+            if !module.is_dir() {
+                return Err(ActrCliError::config_error(format!(
+                    "Gradle module directory not found: {}",
+                    module.display()
+                )));
+            }
+        }
+
+        let generated_crate = self
+            .as_crate
+            .as_deref()
+            .map(GeneratedCrate::new)
+            .transpose()?;
+        if generated_crate.is_some()
+            && targets
+                .iter()
+                .any(|(language, _)| *language != SupportedLanguage::Rust)
+        {
+            return Err(ActrCliError::config_error(
+                "--as-crate only applies to Rust (-l rust)",
+            ));
+        }
+
+        let mut resolved: Vec<GenTarget> = Vec::with_capacity(targets.len());
+        for (language, output) in targets {
+            let output = match (output, &self.gradle_module, &generated_crate) {
+                (_, _, Some(generated_crate)) => generated_crate.output(),
+                (Some(output), _, None) => output,
+                (None, Some(module), None) if language == SupportedLanguage::Kotlin => {
+                    gradle::generated_dir(module)
+                }
+                (None, _, None) => Self::default_output_path(language, config),
             };
-            execute_codegen(self.language, &context).await?;
-            return Ok(());
+            if resolved.iter().any(|target| target.output == output) {
+                return Err(ActrCliError::config_error(format!(
+                    "Two generation targets write to {}; set `output` per target in \
+                     [[build.targets]]",
+                    output.display()
+                )));
+            }
+            resolved.push(GenTarget {
+                language,
+                output,
+                format: FormatConfig::from_config(&value, language)?,
+            });
         }
+        Ok(resolved)
+    }
 
-        // Step 5: Generate infrastructure code
-        self.generate_infrastructure_code(&proto_files, &config)
-            .await?;
+    fn gen_context(
+        &self,
+        proto_files: Vec<PathBuf>,
+        output: PathBuf,
+        config: actr_config::Config,
+    ) -> GenContext {
+        GenContext {
+            proto_files,
+            input_path: self.input.clone(),
+            output,
+            config_path: self.config.clone(),
+            config,
+            no_scaffold: self.no_scaffold,
+            overwrite_user_code: self.overwrite_user_code,
+            merge_scaffold: self.merge_scaffold,
+            no_format: self.no_format,
+            debug: self.debug,
+            no_validate: self.no_validate,
+            offline: self.offline,
+            force: self.force,
+            jobs: self.jobs.unwrap_or_else(default_jobs),
+            quiet: self.quiet,
+            include_paths: Vec::new(),
+            import_roots: Vec::new(),
+            gradle_module: self.gradle_module.clone(),
+            header: GenHeader::default(),
+            // The name was validated by `targets`
+            generated_crate: self
+                .as_crate
+                .as_deref()
+                .and_then(|name| GeneratedCrate::new(name).ok()),
+            format: FormatConfig::default(),
+        }
+    }
 
-        // Step 6: Generate user code scaffold
-        if self.should_generate_scaffold() {
-            self.generate_user_code_scaffold(&proto_files).await?;
+    /// `--emit-build-rs`: write a build.rs running this generation on `cargo build`
+    fn emit_build_rs(&self) -> Result<Vec<PathBuf>> {
+        if self
+            .language
+            .iter()
+            .any(|language| *language != SupportedLanguage::Rust)
+        {
+            return Err(ActrCliError::config_error(
+                "--emit-build-rs only applies to Rust (-l rust)",
+            ));
         }
+        let options = GenerationOptions {
+            input: self.input.clone(),
+            config: self.config.clone(),
+            language: vec![SupportedLanguage::Rust],
+            output: self.output.clone(),
+            no_format: self.no_format,
+            ..GenerationOptions::default()
+        };
+        let written = build_script::emit_build_rs(Path::new("."), &options)?;
+        for file in &written {
+            human_println!("{}", tr!("gen.wrote", path = file.display()));
+        }
+        human_println!("{}", tr!("gen.build_script_tip"));
+        Ok(written)
+    }
 
-        // Step 7: Format code
-        if self.should_format() {
-            self.format_generated_code().await?;
+    /// `[build.header]` of Actr.toml
+    fn header(&self) -> Result<GenHeader> {
+        let value = profile::load_value(&self.config)
+            .map_err(|e| ActrCliError::config_error(format!("{e:#}")))?;
+        let config_dir = self
+            .config
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        GenHeader::from_config(&value, config_dir)
+    }
+
+    /// buf configuration of the input directory; required with `--buf`
+    fn buf_layout(&self) -> Result<Option<BufLayout>> {
+        let layout = BufLayout::detect(&self.input)?;
+        if self.buf && layout.is_none() {
+            return Err(ActrCliError::config_error(format!(
+                "--buf: no {BUF_YAML} or {BUF_WORK_YAML} found in {}",
+                self.input.display()
+            )));
+        }
+        Ok(layout)
+    }
+
+    /// Import roots of a buf input: its modules, then the protos of its deps
+    fn buf_include_paths(&self) -> Result<Vec<PathBuf>> {
+        let Some(layout) = self.buf_layout()? else {
+            return Ok(Vec::new());
+        };
+        let mut paths = layout.roots();
+        if layout.deps.is_empty() {
+            return Ok(paths);
+        }
+
+        let project_root = self
+            .config
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        if self.offline {
+            // Reuse the last export; buf would need network access to fetch deps
+            let export_dir = project_root.join(BUF_EXPORT_DIR);
+            if export_dir.is_dir() {
+                paths.push(export_dir);
+            } else {
+                warn!("{}", tr!("gen.buf_offline"));
+            }
+            return Ok(paths);
+        }
+        match layout.export_deps(project_root) {
+            Ok(export_dir) => paths.extend(export_dir),
+            // Autodetected layouts still work for protos that do not import deps
+            Err(e) if !self.buf => warn!("{}", tr!("gen.buf_skipped", error = e)),
+            Err(e) => return Err(e),
+        }
+        Ok(paths)
+    }
+
+    /// Regenerate into a temporary directory and compare it with the output (`--check`)
+    ///
+    /// Scaffolds and the generation cache are left alone; only the generated
+    /// directory is compared.
+    /// Returns the number of files that differ.
+    async fn check_drift(&self, language: SupportedLanguage, base: GenContext) -> Result<usize> {
+        let committed = base.output.clone();
+        let staging_parent = committed
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        std::fs::create_dir_all(staging_parent)?;
+        let staging = tempfile::Builder::new()
+            .prefix(".actr-gen-check-")
+            .tempdir_in(staging_parent)?;
+
+        let mut context = base;
+        context.output = staging.path().join(
+            committed
+                .file_name()
+                .unwrap_or_else(|| std::ffi::OsStr::new("generated")),
+        );
+        context.no_scaffold = true;
+        context.force = true;
+        std::fs::create_dir_all(&context.output)?;
+        regenerate(language, &context, &committed).await?;
+
+        let drift = diff_dirs(&committed, &context.output)?;
+        // Generated files may be read-only; make them removable with the temp dir
+        self.make_writable_recursive(staging.path())?;
+
+        if drift.is_empty() {
+            human_println!(
+                "{}",
+                tr!("gen.check_up_to_date", path = committed.display())
+            );
+            return Ok(0);
+        }
+
+        human_println!(
+            "{}",
+            tr!("gen.check_out_of_date", path = committed.display())
+        );
+        for entry in &drift {
+            human_println!("  {entry}");
         }
+        human_println!();
+        human_println!("{}", tr!("gen.check_hint"));
+        Ok(drift.len())
+    }
+
+    /// Watch the input path and regenerate changed protos until Ctrl+C
+    async fn watch_and_regenerate(
+        &self,
+        targets: Vec<(SupportedLanguage, GenContext)>,
+    ) -> Result<()> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|e| ActrCliError::command_error(format!("Failed to start file watcher: {e}")))?;
+        watcher
+            .watch(&self.input, RecursiveMode::Recursive)
+            .map_err(|e| {
+                ActrCliError::command_error(format!("Failed to watch {:?}: {e}", self.input))
+            })?;
+
+        human_println!();
+        human_println!("{}", tr!("gen.watching", path = self.input.display()));
+
+        loop {
+            let event = tokio::select! {
+                event = rx.recv() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                _ = tokio::signal::ctrl_c() => break,
+            };
 
-        // Step 8: Validate generated code
-        self.validate_generated_code().await?;
+            // Debounce: keep collecting until the input has been quiet for a while
+            let mut changed = changed_proto_paths(&event);
+            while let Ok(Some(event)) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {
+                changed.extend(changed_proto_paths(&event));
+            }
+            if changed.is_empty() {
+                continue;
+            }
 
-        info!("✅ Code generation completed!");
-        // Set all generated files to read-only only after generation, formatting, and validation are complete, to not interfere with rustfmt or other steps.
-        self.set_generated_files_readonly()?;
-        self.print_next_steps();
+            for (language, base) in &targets {
+                if let Err(e) = self.regenerate_changed(*language, base, &changed).await {
+                    human_println!(
+                        "  └─ {}",
+                        tr!(
+                            "gen.regenerate_failed",
+                            language = language.as_str(),
+                            error = e
+                        )
+                    );
+                }
+            }
+        }
 
+        human_println!("{}", tr!("gen.watch_stopped"));
+        Ok(())
+    }
+
+    /// Regenerate after `changed` through the generation cache, like `actr gen`
+    ///
+    /// Only the changed protos and their importers are stale; generators without
+    /// incremental support regenerate everything, and the outputs of removed
+    /// protos are deleted.
+    async fn regenerate_changed(
+        &self,
+        language: SupportedLanguage,
+        base: &GenContext,
+        changed: &BTreeSet<PathBuf>,
+    ) -> Result<()> {
+        human_println!();
+        for path in changed {
+            let key = if path.exists() {
+                "gen.proto_changed"
+            } else {
+                "gen.proto_removed"
+            };
+            human_println!("{}", tr!(key, path = path.display()));
+        }
+
+        let all_protos = self.discover_proto_files()?;
+        let removed = changed.iter().any(|path| !path.exists());
+        if !removed && affected_proto_files(&all_protos, changed).is_empty() {
+            human_println!("  └─ {}", tr!("gen.nothing_affected"));
+            return Ok(());
+        }
+
+        // Previous runs may have marked generated files read-only
+        if base.output.exists() {
+            self.make_writable_recursive(&base.output)?;
+        }
+
+        let mut context = base.clone();
+        context.proto_files = all_protos;
+        let run = regenerate_cached(language, &context).await?;
+        if run.up_to_date {
+            human_println!("  └─ {}", tr!("gen.nothing_affected"));
+            return Ok(());
+        }
+        human_println!(
+            "  └─ {}",
+            tr!(
+                "gen.regenerated",
+                protos = run.regenerated,
+                outputs = run.files.len()
+            )
+        );
         Ok(())
     }
-}
 
-impl GenCommand {
     /// Check if Actr.lock.toml exists and provide helpful error message if not
     fn check_lock_file(&self) -> Result<()> {
         let config_dir = self
@@ -143,34 +636,52 @@ impl GenCommand {
             ));
         }
 
+        if self.offline {
+            self.check_cached_protos(config_dir, &lock_file_path)?;
+        }
+
         Ok(())
     }
 
-    /// Determine output path based on language if not explicitly specified
-    fn determine_output_path(&self) -> Result<PathBuf> {
-        // If user specified a custom output, use it
-        if let Some(ref output) = self.output {
-            return Ok(output.clone());
+    /// In offline mode every locked proto must already be present under protos/remote/
+    fn check_cached_protos(&self, config_dir: &Path, lock_file_path: &Path) -> Result<()> {
+        let lock_file = actr_config::LockFile::from_file(lock_file_path).map_err(|e| {
+            ActrCliError::config_error(format!("Failed to parse Actr.lock.toml: {e}"))
+        })?;
+
+        let remote_dir = config_dir.join("protos").join("remote");
+        let missing: Vec<String> = lock_file
+            .dependencies
+            .iter()
+            .flat_map(|dep| dep.files.iter())
+            .filter(|file| !remote_dir.join(&file.path).exists())
+            .map(|file| file.path.clone())
+            .collect();
+
+        if !missing.is_empty() {
+            return Err(ActrCliError::config_error(format!(
+                "Offline mode: {} locked proto file(s) are not cached under protos/remote/:\n  {}\n\n\
+                Run 'actr install' while online to populate the cache.",
+                missing.len(),
+                missing.join("\n  ")
+            )));
         }
 
-        // Determine language-specific default output path
-        match self.language {
+        Ok(())
+    }
+
+    /// Default output directory of a language
+    fn default_output_path(language: SupportedLanguage, config: &actr_config::Config) -> PathBuf {
+        match language {
             SupportedLanguage::Swift => {
-                // Read package name from config for Swift
-                let config = actr_config::ConfigParser::from_file(&self.config).map_err(|e| {
-                    ActrCliError::config_error(format!("Failed to parse Actr.toml: {e}"))
-                })?;
                 let project_name = &config.package.name;
                 // Convert to PascalCase for Swift module name
                 let pascal_name = to_pascal_case(project_name);
-                Ok(PathBuf::from(format!("{}/Generated", pascal_name)))
+                PathBuf::from(format!("{}/Generated", pascal_name))
             }
             SupportedLanguage::Kotlin => {
                 // Kotlin default: app/src/main/java/{package_path}/generated
                 // Package name follows the pattern: io.actr.{project_name_cleaned}
-                let config = actr_config::ConfigParser::from_file(&self.config).map_err(|e| {
-                    ActrCliError::config_error(format!("Failed to parse Actr.toml: {e}"))
-                })?;
                 // Convert project name to valid Android package name
                 // e.g., "my-app" -> "io.actr.myapp"
                 let clean_name: String = config
@@ -181,35 +692,38 @@ impl GenCommand {
                     .collect::<String>()
                     .to_lowercase();
                 let package_path = format!("io/actr/{}", clean_name);
-                Ok(PathBuf::from(format!(
-                    "app/src/main/java/{}/generated",
-                    package_path
-                )))
+                PathBuf::from(format!("app/src/main/java/{}/generated", package_path))
             }
             SupportedLanguage::Python => {
                 // Python default: generated
-                Ok(PathBuf::from("generated"))
+                PathBuf::from("generated")
             }
             SupportedLanguage::Rust => {
                 // Rust default: src/generated
-                Ok(PathBuf::from("src/generated"))
+                PathBuf::from("src/generated")
+            }
+            SupportedLanguage::Typescript => {
+                // TypeScript default: src/generated (imported by src/main.ts)
+                PathBuf::from("src/generated")
             }
         }
     }
 
-    fn preprocess(&self) -> Result<Vec<PathBuf>> {
+    fn preprocess(&self, targets: &[GenTarget]) -> Result<Vec<PathBuf>> {
         // Step 1: Validate inputs
         self.validate_inputs()?;
 
-        // Step 2: Clean old generation outputs (optional)
-        self.clean_generated_outputs()?;
+        for target in targets {
+            // Step 2: Clean old generation outputs (optional)
+            self.clean_generated_outputs(&target.output)?;
 
-        // Step 3: Prepare output directories
-        self.prepare_output_dirs()?;
+            // Step 3: Prepare output directories
+            self.prepare_output_dirs(&target.output)?;
+        }
 
         // Step 4: Discover proto files
         let proto_files = self.discover_proto_files()?;
-        info!("📁 Found {} proto files", proto_files.len());
+        info!("{}", tr!("gen.found_protos", count = proto_files.len()));
 
         Ok(proto_files)
     }
@@ -219,30 +733,22 @@ impl GenCommand {
         !self.no_scaffold
     }
 
-    /// Whether formatting should run
-    fn should_format(&self) -> bool {
-        !self.no_format
-    }
-
     /// Remove previously generated files when --clean is used
-    fn clean_generated_outputs(&self) -> Result<()> {
+    fn clean_generated_outputs(&self, output: &Path) -> Result<()> {
         use std::fs;
 
         if !self.clean {
             return Ok(());
         }
 
-        let output = self.determine_output_path()?;
         if !output.exists() {
             return Ok(());
         }
 
-        info!("🧹 Cleaning old generation results: {:?}", output);
+        info!("{}", tr!("gen.cleaning", path = output.display()));
 
-        self.make_writable_recursive(&output)?;
-        fs::remove_dir_all(&output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to delete generation directory: {e}"))
-        })?;
+        self.make_writable_recursive(output)?;
+        fs::remove_dir_all(output).map_err(|e| ActrCliError::output_write_failed(output, e))?;
 
         Ok(())
     }
@@ -253,9 +759,8 @@ impl GenCommand {
         use std::fs;
 
         if path.is_file() {
-            let metadata = fs::metadata(path).map_err(|e| {
-                ActrCliError::config_error(format!("Failed to read file metadata: {e}"))
-            })?;
+            let metadata =
+                fs::metadata(path).map_err(|e| ActrCliError::output_write_failed(path, e))?;
             let mut permissions = metadata.permissions();
 
             #[cfg(unix)]
@@ -270,14 +775,13 @@ impl GenCommand {
                 permissions.set_readonly(false);
             }
 
-            fs::set_permissions(path, permissions).map_err(|e| {
-                ActrCliError::config_error(format!("Failed to reset file permissions: {e}"))
-            })?;
+            fs::set_permissions(path, permissions)
+                .map_err(|e| ActrCliError::output_write_failed(path, e))?;
         } else if path.is_dir() {
-            for entry in fs::read_dir(path)
-                .map_err(|e| ActrCliError::config_error(format!("Failed to read directory: {e}")))?
+            for entry in
+                fs::read_dir(path).map_err(|e| ActrCliError::output_write_failed(path, e))?
             {
-                let entry = entry.map_err(|e| ActrCliError::config_error(e.to_string()))?;
+                let entry = entry.map_err(|e| ActrCliError::output_write_failed(path, e))?;
                 self.make_writable_recursive(&entry.path())?;
             }
         }
@@ -295,37 +799,56 @@ impl GenCommand {
         }
 
         if self.input.is_file() && self.input.extension().unwrap_or_default() != "proto" {
-            warn!("Input file is not a .proto file: {:?}", self.input);
+            warn!("{}", tr!("gen.not_a_proto", path = self.input.display()));
         }
 
         Ok(())
     }
 
     /// 准备输出目录
-    fn prepare_output_dirs(&self) -> Result<()> {
-        let output = self.determine_output_path()?;
-        std::fs::create_dir_all(&output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to create output directory: {e}"))
-        })?;
+    fn prepare_output_dirs(&self, output: &Path) -> Result<()> {
+        std::fs::create_dir_all(output)
+            .map_err(|e| ActrCliError::output_write_failed(output, e))?;
 
         if self.should_generate_scaffold() {
             let user_code_dir = output.join("../");
-            std::fs::create_dir_all(&user_code_dir).map_err(|e| {
-                ActrCliError::config_error(format!("Failed to create user code directory: {e}"))
-            })?;
+            std::fs::create_dir_all(&user_code_dir)
+                .map_err(|e| ActrCliError::output_write_failed(&user_code_dir, e))?;
         }
 
         Ok(())
     }
 
-    /// Find proto files recursively
+    /// Find proto files recursively, in a stable order, honouring the include/exclude globs
     fn discover_proto_files(&self) -> Result<Vec<PathBuf>> {
         let mut proto_files = Vec::new();
+        let filter = ProtoFilter::from_config_file(&self.config, &self.include, &self.exclude)?;
 
-        if self.input.is_file() {
+        if let Some(layout) = self.buf_layout()? {
+            info!(
+                "{}",
+                tr!("gen.reading_buf", path = layout.config_path.display())
+            );
+            proto_files = layout
+                .proto_files()?
+                .into_iter()
+                .filter(|path| filter.matches_file(self.relative_to_input(path)))
+                .collect();
+            // Installed dependency protos live outside the buf modules
+            let remote_dir = self.input.join("remote");
+            if remote_dir.is_dir() {
+                let mut remote = Vec::new();
+                self.collect_proto_files(&remote_dir, &filter, &mut remote)?;
+                for path in remote {
+                    if !proto_files.contains(&path) {
+                        proto_files.push(path);
+                    }
+                }
+            }
+        } else if self.input.is_file() {
             proto_files.push(self.input.clone());
         } else {
-            self.collect_proto_files(&self.input, &mut proto_files)?;
+            self.collect_proto_files(&self.input, &filter, &mut proto_files)?;
         }
 
         if proto_files.is_empty() {
@@ -335,711 +858,303 @@ impl GenCommand {
         Ok(proto_files)
     }
 
-    /// Collect proto files recursively
-    #[allow(clippy::only_used_in_recursion)]
-    fn collect_proto_files(&self, dir: &PathBuf, proto_files: &mut Vec<PathBuf>) -> Result<()> {
-        for entry in std::fs::read_dir(dir)
-            .map_err(|e| ActrCliError::config_error(format!("Failed to read directory: {e}")))?
-        {
-            let entry = entry.map_err(|e| ActrCliError::config_error(e.to_string()))?;
+    /// Collect proto files below `dir`, sorted by path, skipping excluded directories
+    fn collect_proto_files(
+        &self,
+        dir: &Path,
+        filter: &ProtoFilter,
+        proto_files: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let walker = walkdir::WalkDir::new(dir)
+            .follow_links(true)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !filter.excludes_dir(self.relative_to_input(entry.path()))
+            });
+        for entry in walker {
+            let entry = entry.map_err(|e| ActrCliError::Io(e.into()))?;
             let path = entry.path();
-
-            if path.is_file() && path.extension().unwrap_or_default() == "proto" {
-                proto_files.push(path);
-            } else if path.is_dir() {
-                self.collect_proto_files(&path, proto_files)?;
+            if entry.file_type().is_file()
+                && path.extension().unwrap_or_default() == "proto"
+                && filter.matches_file(self.relative_to_input(path))
+            {
+                proto_files.push(path.to_path_buf());
             }
         }
         Ok(())
     }
 
-    /// 确保 protoc-gen-actrframework 插件可用
-    ///
-    /// Plugin version policy:
-    /// 1. Check installed version first.
-    /// 2. If `.protoc-plugin.toml` defines a minimum version, accept any version >= minimum.
-    /// 3. Otherwise, require an exact match with the CLI version.
-    /// 4. If missing or below requirement, install/upgrade automatically.
-    fn ensure_protoc_plugin(&self) -> Result<PathBuf> {
-        // Expected version (same as actr-framework-protoc-codegen)
-        const EXPECTED_VERSION: &str = env!("CARGO_PKG_VERSION");
-        const PLUGIN_NAME: &str = "protoc-gen-actrframework";
-
-        let min_version = self.resolve_plugin_min_version(PLUGIN_NAME)?;
-        let require_exact = min_version.is_none();
-        let required_version = min_version.unwrap_or_else(|| EXPECTED_VERSION.to_string());
-
-        // 1. Check installed version
-        let installed_version = self.check_installed_plugin_version()?;
-
-        match installed_version {
-            Some(version) if self.version_satisfies(&version, &required_version, require_exact) => {
-                // Version matches, use it directly
-                info!("✅ Using installed protoc-gen-actrframework v{}", version);
-                let output = StdCommand::new("which")
-                    .arg(PLUGIN_NAME)
-                    .output()
-                    .map_err(|e| {
-                        ActrCliError::command_error(format!("Failed to locate plugin: {e}"))
-                    })?;
-
-                let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                Ok(PathBuf::from(path))
-            }
-            Some(version) => {
-                // Version mismatch, upgrade needed
-                if require_exact {
-                    info!(
-                        "🔄 Version mismatch: installed v{}, need v{}",
-                        version, required_version
-                    );
-                } else {
-                    info!(
-                        "🔄 Version below minimum: installed v{}, need >= v{}",
-                        version, required_version
-                    );
-                }
-                info!("🔨 Upgrading plugin...");
-                let path = self.install_or_upgrade_plugin()?;
-                self.ensure_required_plugin_version(&required_version, require_exact)?;
-                Ok(path)
-            }
-            None => {
-                // Not installed, install it
-                info!("📦 protoc-gen-actrframework not found, installing...");
-                let path = self.install_or_upgrade_plugin()?;
-                self.ensure_required_plugin_version(&required_version, require_exact)?;
-                Ok(path)
-            }
-        }
-    }
-
-    /// Check installed plugin version
-    fn check_installed_plugin_version(&self) -> Result<Option<String>> {
-        let output = StdCommand::new("protoc-gen-actrframework")
-            .arg("--version")
-            .output();
-
-        match output {
-            Ok(output) if output.status.success() => {
-                let version_info = String::from_utf8_lossy(&output.stdout);
-                // Parse "protoc-gen-actrframework 0.1.0"
-                let version = version_info
-                    .lines()
-                    .next()
-                    .and_then(|line| line.split_whitespace().nth(1))
-                    .map(|v| v.to_string());
-
-                debug!("Detected installed version: {:?}", version);
-                Ok(version)
-            }
-            _ => {
-                debug!("Plugin not found in PATH");
-                Ok(None)
-            }
-        }
-    }
-
-    /// Install or upgrade plugin from workspace
-    fn install_or_upgrade_plugin(&self) -> Result<PathBuf> {
-        // Find actr workspace
-        let current_dir = std::env::current_dir()?;
-        let workspace_root = current_dir.ancestors().find(|p| {
-            let is_workspace =
-                p.join("Cargo.toml").exists() && p.join("crates/framework-protoc-codegen").exists();
-            if is_workspace {
-                debug!("Found workspace root: {:?}", p);
-            }
-            is_workspace
-        });
-
-        let workspace_root = workspace_root.ok_or_else(|| {
-            ActrCliError::config_error(
-                "Cannot find actr workspace.\n\
-                 Please run this command from within an actr project or workspace.",
-            )
-        })?;
-
-        info!("🔍 Found actr workspace at: {}", workspace_root.display());
-
-        // Step 1: Build the plugin
-        info!("🔨 Building protoc-gen-actrframework...");
-        let mut build_cmd = StdCommand::new("cargo");
-        build_cmd
-            .arg("build")
-            .arg("-p")
-            .arg("actr-framework-protoc-codegen")
-            .arg("--bin")
-            .arg("protoc-gen-actrframework")
-            .current_dir(workspace_root);
-
-        debug!("Running: {:?}", build_cmd);
-        let output = build_cmd
-            .output()
-            .map_err(|e| ActrCliError::command_error(format!("Failed to build plugin: {e}")))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ActrCliError::command_error(format!(
-                "Failed to build plugin:\n{stderr}"
-            )));
-        }
-
-        // Step 2: Install to ~/.cargo/bin/
-        info!("📦 Installing to ~/.cargo/bin/...");
-        let mut install_cmd = StdCommand::new("cargo");
-        install_cmd
-            .arg("install")
-            .arg("--path")
-            .arg(workspace_root.join("crates/framework-protoc-codegen"))
-            .arg("--bin")
-            .arg("protoc-gen-actrframework")
-            .arg("--force"); // Overwrite existing version
-
-        debug!("Running: {:?}", install_cmd);
-        let output = install_cmd
-            .output()
-            .map_err(|e| ActrCliError::command_error(format!("Failed to install plugin: {e}")))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(ActrCliError::command_error(format!(
-                "Failed to install plugin:\n{stderr}"
-            )));
-        }
-
-        info!("✅ Plugin installed successfully");
-
-        // Return the installed path
-        let which_output = StdCommand::new("which")
-            .arg("protoc-gen-actrframework")
-            .output()
-            .map_err(|e| {
-                ActrCliError::command_error(format!("Failed to locate installed plugin: {e}"))
-            })?;
-
-        let path = String::from_utf8_lossy(&which_output.stdout)
-            .trim()
-            .to_string();
-        Ok(PathBuf::from(path))
-    }
-
-    fn resolve_plugin_min_version(&self, plugin_name: &str) -> Result<Option<String>> {
-        let config = load_protoc_plugin_config(&self.config)?;
-        if let Some(config) = config
-            && let Some(min_version) = config.min_version(plugin_name)
-        {
-            info!(
-                "🔧 Using minimum version for {} from {}",
-                plugin_name,
-                config.path().display()
-            );
-            return Ok(Some(min_version.to_string()));
-        }
-        Ok(None)
+    fn relative_to_input<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.input).unwrap_or(path)
     }
+}
 
-    fn version_satisfies(&self, installed: &str, required: &str, strict_equal: bool) -> bool {
-        if strict_equal {
-            installed == required
-        } else {
-            version_is_at_least(installed, required)
-        }
-    }
-
-    fn ensure_required_plugin_version(
-        &self,
-        required_version: &str,
-        strict_equal: bool,
-    ) -> Result<()> {
-        let installed_version = self.check_installed_plugin_version()?;
-        let Some(installed_version) = installed_version else {
-            return Err(ActrCliError::command_error(
-                "Failed to determine installed protoc-gen-actrframework version after install"
-                    .to_string(),
-            ));
-        };
-
-        if self.version_satisfies(&installed_version, required_version, strict_equal) {
-            return Ok(());
-        }
+fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
 
-        if strict_equal {
-            Err(ActrCliError::command_error(format!(
-                "protoc-gen-actrframework version {} does not match required version {}",
-                installed_version, required_version
-            )))
-        } else {
-            Err(ActrCliError::command_error(format!(
-                "protoc-gen-actrframework version {} is lower than minimum version {}",
-                installed_version, required_version
-            )))
-        }
+/// Proto paths touched by a file system event (access events are ignored)
+fn changed_proto_paths(event: &notify::Event) -> BTreeSet<PathBuf> {
+    if matches!(event.kind, EventKind::Access(_) | EventKind::Other) {
+        return BTreeSet::new();
     }
+    event
+        .paths
+        .iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "proto"))
+        .map(|path| canonical_or_self(path))
+        .collect()
+}
 
-    /// 生成基础设施代码
-    async fn generate_infrastructure_code(
-        &self,
-        proto_files: &[PathBuf],
-        config: &actr_config::Config,
-    ) -> Result<()> {
-        info!("🔧 Generating infrastructure code...");
-
-        // 确保 protoc 插件可用
-        let plugin_path = self.ensure_protoc_plugin()?;
-
-        let manufacturer = config.package.actr_type.manufacturer.clone();
-        debug!("Using manufacturer from Actr.toml: {}", manufacturer);
-
-        // 确定输出路径
-        let output = self.determine_output_path()?;
-
-        for proto_file in proto_files {
-            debug!("Processing proto file: {:?}", proto_file);
-
-            // 第一步：使用 prost 生成基础 protobuf 消息类型
-            let mut cmd = StdCommand::new("protoc");
-            cmd.arg(format!("--proto_path={}", self.input.display()))
-                .arg("--prost_opt=flat_output_dir")
-                .arg(format!("--prost_out={}", output.display()))
-                .arg(proto_file);
-
-            debug!("Executing protoc (prost): {:?}", cmd);
-            let output_cmd = cmd.output().map_err(|e| {
-                ActrCliError::command_error(format!("Failed to execute protoc (prost): {e}"))
-            })?;
-
-            if !output_cmd.status.success() {
-                let stderr = String::from_utf8_lossy(&output_cmd.stderr);
-                return Err(ActrCliError::command_error(format!(
-                    "protoc (prost) execution failed: {stderr}"
-                )));
-            }
-
-            // 第二步：使用 actrframework 插件生成 Actor 框架代码
-            let mut cmd = StdCommand::new("protoc");
-            cmd.arg(format!("--proto_path={}", self.input.display()))
-                .arg(format!(
-                    "--plugin=protoc-gen-actrframework={}",
-                    plugin_path.display()
-                ))
-                .arg(format!("--actrframework_opt=manufacturer={manufacturer}"))
-                .arg(format!("--actrframework_out={}", output.display()))
-                .arg(proto_file);
-
-            debug!("Executing protoc (actrframework): {:?}", cmd);
-            let output_cmd = cmd.output().map_err(|e| {
-                ActrCliError::command_error(format!(
-                    "Failed to execute protoc (actrframework): {e}"
-                ))
-            })?;
-
-            if !output_cmd.status.success() {
-                let stderr = String::from_utf8_lossy(&output_cmd.stderr);
-                return Err(ActrCliError::command_error(format!(
-                    "protoc (actrframework) execution failed: {stderr}"
-                )));
-            }
-
-            let stdout = String::from_utf8_lossy(&output_cmd.stdout);
-            if !stdout.is_empty() {
-                debug!("protoc output: {}", stdout);
+/// Select the protos that need regeneration: changed files plus any file importing one
+fn affected_proto_files(all_protos: &[PathBuf], changed: &BTreeSet<PathBuf>) -> Vec<PathBuf> {
+    all_protos
+        .iter()
+        .filter(|proto| {
+            let canonical = canonical_or_self(proto);
+            if changed.contains(&canonical) {
+                return true;
             }
-        }
-
-        // 生成 mod.rs
-        self.generate_mod_rs(proto_files).await?;
-
-        info!("✅ Infrastructure code generation completed");
-        Ok(())
-    }
-
-    /// 生成 mod.rs 文件
-    async fn generate_mod_rs(&self, _proto_files: &[PathBuf]) -> Result<()> {
-        let output = self.determine_output_path()?;
-        let mod_path = output.join("mod.rs");
+            let Ok(content) = std::fs::read_to_string(proto) else {
+                return false;
+            };
+            proto_imports(&content).iter().any(|import| {
+                changed
+                    .iter()
+                    .any(|changed_path| changed_path.ends_with(import))
+            })
+        })
+        .cloned()
+        .collect()
+}
 
-        // 扫描实际生成的文件，而不是根据 proto 文件名猜测
-        let mut proto_modules = Vec::new();
-        let mut service_modules = Vec::new();
+/// Paths listed in `import "...";` statements
+fn proto_imports(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("import")?;
+            let start = rest.find('"')?;
+            let end = rest[start + 1..].find('"')?;
+            Some(rest[start + 1..start + 1 + end].to_string())
+        })
+        .collect()
+}
 
-        use std::fs;
-        for entry in fs::read_dir(&output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to read output directory: {e}"))
-        })? {
-            let entry = entry.map_err(|e| ActrCliError::config_error(e.to_string()))?;
-            let path = entry.path();
+fn canonical_or_self(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
 
-            if path.is_file()
-                && path.extension().unwrap_or_default() == "rs"
-                && let Some(file_name) = path.file_stem().and_then(|s| s.to_str())
-            {
-                // 跳过 mod.rs 本身
-                if file_name == "mod" {
-                    continue;
-                }
+/// How a committed generated file differs from a fresh generation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDrift {
+    /// Generated now but not committed
+    Missing(PathBuf),
+    /// Committed but no longer generated
+    Stale(PathBuf),
+    /// Content differs; line counts added and removed
+    Changed {
+        path: PathBuf,
+        added: usize,
+        removed: usize,
+    },
+}
 
-                // 区分 service_actor 文件和 proto 文件
-                if file_name.ends_with("_service_actor") {
-                    service_modules.push(format!("pub mod {file_name};"));
-                } else {
-                    proto_modules.push(format!("pub mod {file_name};"));
-                }
-            }
+impl std::fmt::Display for FileDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileDrift::Missing(path) => write!(f, "+ {} (missing)", path.display()),
+            FileDrift::Stale(path) => write!(f, "- {} (no longer generated)", path.display()),
+            FileDrift::Changed {
+                path,
+                added,
+                removed,
+            } => write!(f, "~ {} (+{added} -{removed} lines)", path.display()),
         }
-
-        // 排序以保证生成的 mod.rs 内容稳定
-        proto_modules.sort();
-        service_modules.sort();
-
-        let mod_content = format!(
-            r#"//! Automatically generated code module
-//!
-//! This module is automatically generated by the `actr gen` command, including:
-//! - protobuf message type definitions
-//! - Actor framework code (router, traits)
-//!
-//! ⚠️ Do not manually modify files in this directory
-
-// Protobuf message types (generated by prost)
-{}
-
-// Actor framework code (generated by protoc-gen-actrframework)
-{}
-
-// Common types are defined in their respective modules, please import as needed
-"#,
-            proto_modules.join("\n"),
-            service_modules.join("\n"),
-        );
-
-        std::fs::write(&mod_path, mod_content)
-            .map_err(|e| ActrCliError::config_error(format!("Failed to write mod.rs: {e}")))?;
-
-        debug!("Generated mod.rs: {:?}", mod_path);
-        Ok(())
     }
+}
 
-    /// 将生成目录中的文件设置为只读
-    fn set_generated_files_readonly(&self) -> Result<()> {
-        use std::fs;
-
-        let output = self.determine_output_path()?;
-        for entry in fs::read_dir(&output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to read output directory: {e}"))
-        })? {
-            let entry = entry.map_err(|e| ActrCliError::config_error(e.to_string()))?;
-            let path = entry.path();
-
-            if path.is_file() && path.extension().unwrap_or_default() == "rs" {
-                // 获取当前权限
-                let metadata = fs::metadata(&path).map_err(|e| {
-                    ActrCliError::config_error(format!("Failed to get file metadata: {e}"))
-                })?;
-                let mut permissions = metadata.permissions();
-
-                // 设置只读（移除写权限）
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    let mode = permissions.mode();
-                    permissions.set_mode(mode & !0o222); // 移除所有写权限
-                }
+/// Compare the committed output with a fresh generation, file by file
+pub fn diff_dirs(committed: &Path, fresh: &Path) -> Result<Vec<FileDrift>> {
+    let committed_files = relative_files(committed)?;
+    let fresh_files = relative_files(fresh)?;
 
-                #[cfg(not(unix))]
-                {
-                    permissions.set_readonly(true);
+    let mut drift = Vec::new();
+    for path in committed_files.union(&fresh_files) {
+        match (committed_files.contains(path), fresh_files.contains(path)) {
+            (true, false) => drift.push(FileDrift::Stale(path.clone())),
+            (false, true) => drift.push(FileDrift::Missing(path.clone())),
+            _ => {
+                let old = std::fs::read(committed.join(path))?;
+                let new = std::fs::read(fresh.join(path))?;
+                if old != new {
+                    let (added, removed) = line_changes(
+                        &String::from_utf8_lossy(&old),
+                        &String::from_utf8_lossy(&new),
+                    );
+                    drift.push(FileDrift::Changed {
+                        path: path.clone(),
+                        added,
+                        removed,
+                    });
                 }
-
-                fs::set_permissions(&path, permissions).map_err(|e| {
-                    ActrCliError::config_error(format!("Failed to set file permissions: {e}"))
-                })?;
-
-                debug!("Set read-only attribute: {:?}", path);
             }
         }
-
-        Ok(())
     }
+    Ok(drift)
+}
 
-    /// Generate user code scaffold
-    async fn generate_user_code_scaffold(&self, proto_files: &[PathBuf]) -> Result<()> {
-        info!("📝 Generating user code scaffold...");
-
-        for proto_file in proto_files {
-            let service_name = proto_file
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .ok_or_else(|| ActrCliError::config_error("Invalid proto file name"))?;
-
-            self.generate_service_scaffold(service_name).await?;
-        }
-
-        info!("✅ User code scaffold generation completed");
-        Ok(())
+fn relative_files(root: &Path) -> Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    if !root.exists() {
+        return Ok(files);
     }
-
-    /// Generate scaffold for a specific service
-    async fn generate_service_scaffold(&self, service_name: &str) -> Result<()> {
-        let output = self.determine_output_path()?;
-        let user_file_path = output
-            .parent()
-            .unwrap_or_else(|| Path::new("src"))
-            .join(format!("{}_service.rs", service_name.to_lowercase()));
-
-        // If file exists and overwrite is not forced, skip
-        if user_file_path.exists() && !self.overwrite_user_code {
-            info!("⏭️  Skipping existing user code file: {:?}", user_file_path);
-            return Ok(());
+    for entry in walkdir::WalkDir::new(root) {
+        let entry = entry.map_err(|e| ActrCliError::Io(e.into()))?;
+        if entry.file_type().is_file()
+            && let Ok(relative) = entry.path().strip_prefix(root)
+        {
+            files.insert(relative.to_path_buf());
         }
-
-        let scaffold_content = self.generate_scaffold_content(service_name);
-
-        std::fs::write(&user_file_path, scaffold_content).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to write user code scaffold: {e}"))
-        })?;
-
-        info!("📄 Generated user code scaffold: {:?}", user_file_path);
-        Ok(())
     }
+    Ok(files)
+}
 
-    /// 生成用户代码框架内容
-    fn generate_scaffold_content(&self, service_name: &str) -> String {
-        let service_name_pascal = service_name
-            .split('_')
-            .map(|s| {
-                let mut chars = s.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                }
-            })
-            .collect::<String>();
+/// Lines added and removed between two versions, ignoring moves
+fn line_changes(old: &str, new: &str) -> (usize, usize) {
+    let mut counts: std::collections::HashMap<&str, isize> = std::collections::HashMap::new();
+    for line in old.lines() {
+        *counts.entry(line).or_default() -= 1;
+    }
+    for line in new.lines() {
+        *counts.entry(line).or_default() += 1;
+    }
+    counts.values().fold((0, 0), |(added, removed), &n| {
+        if n > 0 {
+            (added + n as usize, removed)
+        } else {
+            (added, removed + (-n) as usize)
+        }
+    })
+}
 
-        let template = format!(
-            r#"//! # {service_name_pascal} user business logic implementation
-//!
-//! This file is a user code scaffold automatically generated by the `actr gen` command.
-//! Please implement your specific business logic here.
-
-use crate::generated::{{{service_name_pascal}Handler, {service_name_pascal}Actor}};
-// 只导入必要的类型，避免拉入不需要的依赖如 sqlite
-// use actr_framework::prelude::*;
-use std::sync::Arc;
-
-/// Specific implementation of the {service_name_pascal} service
-/// 
-/// TODO: Add state fields you need, for example:
-/// - Database connection pool
-/// - Configuration information
-/// - Cache client
-/// - Logger, etc.
-pub struct My{service_name_pascal}Service {{
-    // TODO: Add your service state fields
-    // For example:
-    // pub db_pool: Arc<DatabasePool>,
-    // pub config: Arc<ServiceConfig>,
-    // pub metrics: Arc<Metrics>,
-}}
-
-impl My{service_name_pascal}Service {{
-    /// Create a new service instance
-    /// 
-    /// TODO: Modify constructor parameters as needed
-    pub fn new(/* TODO: Add necessary dependencies */) -> Self {{
-        Self {{
-            // TODO: Initialize your fields
-        }}
-    }}
-    
-    /// Create a service instance with default configuration (for testing)
-    pub fn default_for_testing() -> Self {{
-        Self {{
-            // TODO: Provide default values for testing
-        }}
-    }}
-}}
-
-// TODO: Implement all methods of the {service_name_pascal}Handler trait
-// Note: The impl_user_code_scaffold! macro has generated a basic scaffold for you,
-// you need to replace it with real business logic implementation.
-//
-// Example:
-// #[async_trait]
-// impl {service_name_pascal}Handler for My{service_name_pascal}Service {{
-//     async fn method_name(&self, req: RequestType) -> ActorResult<ResponseType> {{
-//         // 1. Validate input
-//         // 2. Execute business logic
-//         // 3. Return result
-//         todo!("Implement your business logic")
-//     }}
-// }}
+/// `[[build.targets]]` of a raw Actr.toml
+fn configured_targets(value: &toml::Value) -> Result<Vec<(SupportedLanguage, Option<PathBuf>)>> {
+    let Some(targets) = value.get("build").and_then(|build| build.get("targets")) else {
+        return Ok(Vec::new());
+    };
+    let invalid = |detail: &str| {
+        ActrCliError::config_error(format!("Invalid [[build.targets]] in Actr.toml: {detail}"))
+    };
+    let targets = targets
+        .as_array()
+        .ok_or_else(|| invalid("expected an array of tables"))?;
+
+    targets
+        .iter()
+        .map(|target| {
+            let name = target
+                .get("language")
+                .and_then(|language| language.as_str())
+                .ok_or_else(|| invalid("every target needs a `language`"))?;
+            let language = <SupportedLanguage as clap::ValueEnum>::from_str(name, true)
+                .map_err(|_| invalid(&format!("unknown language '{name}'")))?;
+            let output = match target.get("output") {
+                None => None,
+                Some(output) => Some(PathBuf::from(
+                    output
+                        .as_str()
+                        .ok_or_else(|| invalid("`output` must be a string"))?,
+                )),
+            };
+            Ok((language, output))
+        })
+        .collect()
+}
 
 #[cfg(test)]
-mod tests {{
+mod tests {
     use super::*;
-    
-    #[tokio::test]
-    async fn test_service_creation() {{
-        let _service = My{service_name_pascal}Service::default_for_testing();
-        // TODO: Add your tests
-    }}
-    
-    // TODO: Add more test cases
-}}
-
-/*
-📚 User Guide
-
-## 🚀 Quick Start
-
-1. **Implement business logic**:
-   Implement all methods of the `{service_name_pascal}Handler` trait in `My{service_name_pascal}Service`
-
-2. **Add dependencies**:
-   Add dependencies you need in `Cargo.toml`, such as database clients, HTTP clients, etc.
-
-3. **Configure service**:
-   Modify the `new()` constructor to inject necessary dependencies
-
-4. **Start service**:
-   ```rust
-   #[tokio::main]
-   async fn main() -> ActorResult<()> {{
-       let service = My{service_name_pascal}Service::new(/* dependencies */);
-       
-       ActorSystem::new()
-           .attach(service)
-           .start()
-           .await
-   }}
-   ```
-
-## 🔧 Development Tips
-
-- Use `tracing` crate for logging
-- Implement error handling and retry logic
-- Add unit and integration tests
-- Consider using configuration files for environment variables
-- Implement health checks and metrics collection
-
-## 📖 More Resources
-
-- Actor-RTC Documentation: [Link]
-- API Reference: [Link]
-- Example Projects: [Link]
-*/
-"# // Service in example code
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_proto_imports() {
+        let content = "syntax = \"proto3\";\nimport \"common/types.proto\";\nimport public \"other.proto\";\n";
+        assert_eq!(
+            proto_imports(content),
+            vec!["common/types.proto".to_string(), "other.proto".to_string()]
         );
-
-        template
-    }
-
-    /// 格式化生成的代码
-    async fn format_generated_code(&self) -> Result<()> {
-        info!("🎨 Formatting generated code...");
-
-        let mut cmd = StdCommand::new("rustfmt");
-        cmd.arg("--edition")
-            .arg("2024")
-            .arg("--config")
-            .arg("max_width=100");
-
-        // 格式化生成目录中的所有 .rs 文件
-        let output = self.determine_output_path()?;
-        for entry in std::fs::read_dir(&output).map_err(|e| {
-            ActrCliError::config_error(format!("Failed to read output directory: {e}"))
-        })? {
-            let entry = entry.map_err(|e| ActrCliError::config_error(e.to_string()))?;
-            let path = entry.path();
-
-            if path.extension().unwrap_or_default() == "rs" {
-                cmd.arg(&path);
-            }
-        }
-
-        let output = cmd
-            .output()
-            .map_err(|e| ActrCliError::command_error(format!("Failed to execute rustfmt: {e}")))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("rustfmt execution warning: {}", stderr);
-        } else {
-            info!("✅ Code formatting completed");
-        }
-
-        Ok(())
     }
 
-    /// 验证生成的代码
-    async fn validate_generated_code(&self) -> Result<()> {
-        info!("🔍 Validating generated code...");
-
-        // 查找项目根目录（包含 Cargo.toml 的目录）
-        let project_root = self.find_project_root()?;
-
-        let mut cmd = StdCommand::new("cargo");
-        cmd.arg("check").arg("--quiet").current_dir(&project_root);
-
-        let output = cmd.output().map_err(|e| {
-            ActrCliError::command_error(format!("Failed to execute cargo check: {e}"))
-        })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!(
-                "Generated code has compilation warnings or errors:\n{}",
-                stderr
-            );
-            info!("💡 This is usually normal because the user code scaffold contains TODO markers");
-        } else {
-            info!("✅ Code validation passed");
-        }
-
-        Ok(())
+    #[test]
+    fn test_affected_includes_importers() {
+        let temp = TempDir::new().unwrap();
+        let common = temp.path().join("common.proto");
+        let echo = temp.path().join("echo.proto");
+        let other = temp.path().join("other.proto");
+        std::fs::write(&common, "syntax = \"proto3\";\n").unwrap();
+        std::fs::write(&echo, "import \"common.proto\";\n").unwrap();
+        std::fs::write(&other, "syntax = \"proto3\";\n").unwrap();
+
+        let changed: BTreeSet<PathBuf> = [canonical_or_self(&common)].into_iter().collect();
+        let affected = affected_proto_files(&[common.clone(), echo.clone(), other], &changed);
+        assert_eq!(affected, vec![common, echo]);
     }
 
-    /// 查找项目根目录（包含 Cargo.toml 的目录）
-    fn find_project_root(&self) -> Result<PathBuf> {
-        let mut current = std::env::current_dir().map_err(ActrCliError::Io)?;
-
-        loop {
-            if current.join("Cargo.toml").exists() {
-                return Ok(current);
-            }
-
-            match current.parent() {
-                Some(parent) => current = parent.to_path_buf(),
-                None => break,
-            }
-        }
+    #[test]
+    fn test_configured_targets() {
+        let value: toml::Value = toml::from_str(
+            "[[build.targets]]\nlanguage = \"rust\"\n\n[[build.targets]]\nlanguage = \"ts\"\noutput = \"web/src/generated\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            configured_targets(&value).unwrap(),
+            vec![
+                (SupportedLanguage::Rust, None),
+                (
+                    SupportedLanguage::Typescript,
+                    Some(PathBuf::from("web/src/generated"))
+                ),
+            ]
+        );
 
-        // 如果找不到 Cargo.toml，回退到当前目录
-        std::env::current_dir().map_err(ActrCliError::Io)
+        let empty: toml::Value = toml::from_str("[package]\nname = \"app\"\n").unwrap();
+        assert!(configured_targets(&empty).unwrap().is_empty());
+        let unknown: toml::Value =
+            toml::from_str("[[build.targets]]\nlanguage = \"cobol\"\n").unwrap();
+        assert!(configured_targets(&unknown).is_err());
     }
 
-    /// 打印后续步骤提示
-    fn print_next_steps(&self) {
-        println!("\n🎉 Code generation completed!");
-        println!("\n📋 Next steps:");
-        let output = self
-            .determine_output_path()
-            .unwrap_or_else(|_| PathBuf::from("src/generated"));
-        println!("1. 📖 View generated code: {:?}", output);
-        if self.should_generate_scaffold() {
-            println!(
-                "2. ✏️  Implement business logic: in the *_service.rs files in the src/ directory"
-            );
-            println!("3. 🔧 Add dependencies: add required packages in Cargo.toml");
-            println!("4. 🏗️  Build project: cargo build");
-            println!("5. 🧪 Run tests: cargo test");
-            println!("6. 🚀 Start service: cargo run");
-        } else {
-            println!("2. 🏗️  Build project: cargo build");
-            println!("3. 🧪 Run tests: cargo test");
-            println!("4. 🚀 Start service: cargo run");
-        }
-        println!("\n💡 Tip: Check the detailed user guide in the generated user code files");
+    #[test]
+    fn test_diff_dirs() {
+        let committed = TempDir::new().unwrap();
+        let fresh = TempDir::new().unwrap();
+        std::fs::write(committed.path().join("mod.rs"), "pub mod echo;\n").unwrap();
+        std::fs::write(fresh.path().join("mod.rs"), "pub mod echo;\n").unwrap();
+        std::fs::write(committed.path().join("echo.rs"), "a\nb\nc\n").unwrap();
+        std::fs::write(fresh.path().join("echo.rs"), "a\nB\nc\nd\n").unwrap();
+        std::fs::write(committed.path().join("old.rs"), "").unwrap();
+        std::fs::create_dir(fresh.path().join("nested")).unwrap();
+        std::fs::write(fresh.path().join("nested/new.rs"), "").unwrap();
+
+        let drift = diff_dirs(committed.path(), fresh.path()).unwrap();
+        assert_eq!(
+            drift,
+            vec![
+                FileDrift::Changed {
+                    path: PathBuf::from("echo.rs"),
+                    added: 2,
+                    removed: 1
+                },
+                FileDrift::Missing(PathBuf::from("nested/new.rs")),
+                FileDrift::Stale(PathBuf::from("old.rs")),
+            ]
+        );
+        assert_eq!(drift[0].to_string(), "~ echo.rs (+2 -1 lines)");
     }
 }
@@ -0,0 +1,267 @@
+//! Graph command implementation - visualize the service dependency topology
+//!
+//! Resolves the dependencies in Actr.toml together with the services they depend
+//! on, builds the graph through the DependencyResolver and renders it as
+//! Graphviz DOT, Mermaid or JSON.
+
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, DependencyGraph,
+};
+use crate::human_println;
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Graph command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Show the service dependency graph",
+    long_about = "Render the project's service dependencies, including transitive ones, as a graph.\n\nExamples:\n  actr graph | dot -Tsvg > deps.svg     # Graphviz\n  actr graph --format mermaid           # Paste into Markdown\n  actr graph --check-cycles             # Fail in CI when a cycle exists"
+)]
+pub struct GraphCommand {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    pub format: GraphFormat,
+
+    /// Exit non-zero when the graph contains a cycle
+    #[arg(long)]
+    pub check_cycles: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT (default)
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// Nodes, edges and cycles as JSON
+    Json,
+}
+
+/// The project and its dependency graph
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectGraph {
+    /// Project package name; the root of the graph
+    pub root: String,
+    #[serde(flatten)]
+    pub graph: DependencyGraph,
+    /// Services that could not be fetched, with the error
+    pub unresolved: Vec<(String, String)>,
+}
+
+#[async_trait]
+impl Command for GraphCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let pipeline = context.container.get_validation_pipeline()?;
+        let config_manager = pipeline.config_manager();
+        let config = config_manager
+            .load_config(&config_manager.get_project_root().join("Actr.toml"))
+            .await?;
+        let specs = pipeline.dependency_resolver().resolve_spec(&config).await?;
+
+        let resolution = pipeline.resolve_transitive(&specs).await?;
+        let mut graph = pipeline
+            .dependency_resolver()
+            .build_dependency_graph(&resolution.dependencies)
+            .await?;
+
+        // The project itself depends on every direct dependency
+        let root = config.package.name.clone();
+        let mut direct: Vec<String> = Vec::new();
+        for spec in &specs {
+            if !direct.contains(&spec.name) {
+                direct.push(spec.name.clone());
+            }
+        }
+        graph.nodes.insert(0, root.clone());
+        for (index, name) in direct.into_iter().enumerate() {
+            graph.edges.insert(index, (root.clone(), name));
+        }
+
+        let project = ProjectGraph {
+            root,
+            graph,
+            unresolved: resolution.unresolved,
+        };
+        let rendered = match self.format {
+            GraphFormat::Dot => render_dot(&project),
+            GraphFormat::Mermaid => render_mermaid(&project),
+            GraphFormat::Json => serde_json::to_string_pretty(&project)?,
+        };
+
+        // Warnings go to stderr so the graph can be piped into `dot`
+        for (service, error) in &project.unresolved {
+            eprintln!("⚠️  Could not resolve {service}: {error}");
+        }
+
+        if self.check_cycles && project.graph.has_cycles {
+            human_println!("{rendered}");
+            let cycles: Vec<String> = project
+                .graph
+                .cycles
+                .iter()
+                .map(|cycle| cycle.join(" → "))
+                .collect();
+            return Err(ActrCliError::Dependency {
+                message: format!("Dependency cycle detected: {}", cycles.join("; ")),
+            }
+            .into());
+        }
+
+        Ok(CommandResult::Success(rendered))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![
+            ComponentType::ConfigManager,
+            ComponentType::DependencyResolver,
+            ComponentType::ServiceDiscovery,
+            ComponentType::NetworkValidator,
+            ComponentType::FingerprintValidator,
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "graph"
+    }
+
+    fn description(&self) -> &str {
+        "Show the service dependency graph"
+    }
+}
+
+/// Edges that lie on a cycle
+fn cycle_edges(graph: &DependencyGraph) -> HashSet<(&str, &str)> {
+    graph
+        .cycles
+        .iter()
+        .flat_map(|cycle| {
+            cycle
+                .windows(2)
+                .map(|pair| (pair[0].as_str(), pair[1].as_str()))
+        })
+        .collect()
+}
+
+/// Graphviz DOT; cycle edges are drawn in red
+pub fn render_dot(project: &ProjectGraph) -> String {
+    let cycles = cycle_edges(&project.graph);
+    let unresolved: HashSet<&str> = project.unresolved.iter().map(|(s, _)| s.as_str()).collect();
+
+    let mut out = String::from("digraph dependencies {\n    rankdir=LR;\n");
+    for node in &project.graph.nodes {
+        let style = if *node == project.root {
+            " [shape=box, style=bold]"
+        } else if unresolved.contains(node.as_str()) {
+            " [style=dashed]"
+        } else {
+            ""
+        };
+        let _ = writeln!(out, "    {:?}{style};", node);
+    }
+    for (from, to) in &project.graph.edges {
+        let style = if cycles.contains(&(from.as_str(), to.as_str())) {
+            " [color=red]"
+        } else {
+            ""
+        };
+        let _ = writeln!(out, "    {:?} -> {:?}{style};", from, to);
+    }
+    out.push('}');
+    out
+}
+
+/// Mermaid flowchart; services on a cycle get the `cycle` class
+pub fn render_mermaid(project: &ProjectGraph) -> String {
+    let id = |name: &str| {
+        let index = project
+            .graph
+            .nodes
+            .iter()
+            .position(|node| node == name)
+            .unwrap_or(0);
+        format!("n{index}")
+    };
+
+    let mut out = String::from("graph LR\n");
+    for node in &project.graph.nodes {
+        let label = node.replace('"', "#quot;");
+        if *node == project.root {
+            let _ = writeln!(out, "    {}[\"{label}\"]", id(node));
+        } else {
+            let _ = writeln!(out, "    {}(\"{label}\")", id(node));
+        }
+    }
+    for (from, to) in &project.graph.edges {
+        let _ = writeln!(out, "    {} --> {}", id(from), id(to));
+    }
+
+    let mut on_cycle: Vec<String> = Vec::new();
+    for name in project.graph.cycles.iter().flatten() {
+        let node = id(name);
+        if !on_cycle.contains(&node) {
+            on_cycle.push(node);
+        }
+    }
+    if !on_cycle.is_empty() {
+        out.push_str("    classDef cycle stroke:#d33,stroke-width:2px\n");
+        let _ = writeln!(out, "    class {} cycle", on_cycle.join(","));
+    }
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(edges: &[(&str, &str)], cycles: Vec<Vec<String>>) -> ProjectGraph {
+        let mut nodes: Vec<String> = vec!["app".to_string()];
+        for (from, to) in edges {
+            for node in [from, to] {
+                if !nodes.iter().any(|n| n == node) {
+                    nodes.push(node.to_string());
+                }
+            }
+        }
+        ProjectGraph {
+            root: "app".to_string(),
+            graph: DependencyGraph {
+                nodes,
+                edges: edges
+                    .iter()
+                    .map(|(a, b)| (a.to_string(), b.to_string()))
+                    .collect(),
+                has_cycles: !cycles.is_empty(),
+                cycles,
+            },
+            unresolved: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let cycle = vec!["a".to_string(), "b".to_string(), "a".to_string()];
+        let dot = render_dot(&project(
+            &[("app", "a"), ("a", "b"), ("b", "a")],
+            vec![cycle],
+        ));
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"app\" [shape=box, style=bold];"));
+        assert!(dot.contains("\"app\" -> \"a\";"));
+        assert!(dot.contains("\"a\" -> \"b\" [color=red];"));
+        assert!(dot.ends_with('}'));
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let mermaid = render_mermaid(&project(&[("app", "user-service")], Vec::new()));
+        assert_eq!(
+            mermaid,
+            "graph LR\n    n0[\"app\"]\n    n1(\"user-service\")\n    n0 --> n1"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! History command implementation - display the dependency audit log
+//!
+//! Reads `.actr/history.jsonl`, which install, remove, update and lock append to,
+//! and filters it by service, operation and time.
+
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType};
+use crate::history::{History, HistoryEntry, HistoryOperation};
+use crate::human_println;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::Args;
+use owo_colors::OwoColorize;
+
+/// History command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Show the dependency audit log",
+    long_about = "Show the install, remove, update and lock operations recorded in .actr/history.jsonl.\n\nExamples:\n  actr history                          # Full history\n  actr history -s user-service          # One service\n  actr history --operation update --since 2025-01-01\n  actr history --changed -n 20          # Last 20 fingerprint changes"
+)]
+pub struct HistoryCommand {
+    /// Only show entries for this service
+    #[arg(short, long, value_name = "NAME")]
+    pub service: Option<String>,
+
+    /// Only show entries of this operation
+    #[arg(long, value_enum)]
+    pub operation: Option<HistoryOperation>,
+
+    /// Only show entries recorded at or after this time (YYYY-MM-DD or RFC 3339)
+    #[arg(long, value_name = "TIME", value_parser = parse_since)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only show entries where the fingerprint changed
+    #[arg(long)]
+    pub changed: bool,
+
+    /// Show at most the last N matching entries
+    #[arg(short = 'n', long, value_name = "N")]
+    pub limit: Option<usize>,
+}
+
+#[async_trait]
+impl Command for HistoryCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let config_manager = context.container.get_config_manager()?;
+        let history = History::for_project(config_manager.get_project_root());
+        let entries = self.filter(history.load()?);
+
+        if context.is_json() {
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &entries,
+            )?));
+        }
+
+        if entries.is_empty() {
+            return Ok(CommandResult::Success(
+                "No matching history entries".to_string(),
+            ));
+        }
+
+        for entry in &entries {
+            let change = match (&entry.previous_fingerprint, &entry.new_fingerprint) {
+                (Some(previous), Some(new)) if previous == new => short_fingerprint(new),
+                (Some(previous), Some(new)) => format!(
+                    "{} → {}",
+                    short_fingerprint(previous),
+                    short_fingerprint(new).yellow()
+                ),
+                (None, Some(new)) => format!("+ {}", short_fingerprint(new).green()),
+                (Some(previous), None) => format!("- {}", short_fingerprint(previous).red()),
+                (None, None) => String::new(),
+            };
+            human_println!(
+                "{}  {:<8} {:<7} {}  {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                entry.user,
+                entry.operation.to_string(),
+                entry.service.cyan(),
+                change
+            );
+        }
+
+        Ok(CommandResult::Success(format!(
+            "{} history entries",
+            entries.len()
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::ConfigManager]
+    }
+
+    fn name(&self) -> &str {
+        "history"
+    }
+
+    fn description(&self) -> &str {
+        "Show the dependency audit log"
+    }
+}
+
+impl HistoryCommand {
+    fn filter(&self, entries: Vec<HistoryEntry>) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = entries
+            .into_iter()
+            .filter(|e| self.service.as_ref().is_none_or(|s| &e.service == s))
+            .filter(|e| self.operation.is_none_or(|op| e.operation == op))
+            .filter(|e| self.since.is_none_or(|since| e.timestamp >= since))
+            .filter(|e| !self.changed || e.fingerprint_changed())
+            .collect();
+        if let Some(limit) = self.limit
+            && entries.len() > limit
+        {
+            entries.drain(..entries.len() - limit);
+        }
+        entries
+    }
+}
+
+/// Accept a plain date (midnight UTC) or a full RFC 3339 timestamp
+fn parse_since(value: &str) -> std::result::Result<DateTime<Utc>, ActrCliError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+        .ok_or_else(|| ActrCliError::InvalidArgument {
+            message: format!("'{value}' is not a date (YYYY-MM-DD) or RFC 3339 timestamp"),
+        })
+}
+
+/// Hash part of a fingerprint, shortened for display
+fn short_fingerprint(fingerprint: &str) -> String {
+    let hash = fingerprint.rsplit(':').next().unwrap_or(fingerprint);
+    hash.chars().take(12).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(service: &str, operation: HistoryOperation, day: u32, changed: bool) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: NaiveDate::from_ymd_opt(2025, 1, day)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc(),
+            user: "alice".to_string(),
+            operation,
+            service: service.to_string(),
+            previous_fingerprint: Some("service_semantic:aaa".to_string()),
+            new_fingerprint: Some(
+                if changed {
+                    "service_semantic:bbb"
+                } else {
+                    "service_semantic:aaa"
+                }
+                .to_string(),
+            ),
+        }
+    }
+
+    fn command() -> HistoryCommand {
+        HistoryCommand {
+            service: None,
+            operation: None,
+            since: None,
+            changed: false,
+            limit: None,
+        }
+    }
+
+    #[test]
+    fn test_filter() {
+        let entries = vec![
+            entry("user", HistoryOperation::Install, 1, false),
+            entry("auth", HistoryOperation::Update, 2, true),
+            entry("user", HistoryOperation::Update, 3, true),
+        ];
+
+        let cmd = HistoryCommand {
+            service: Some("user".to_string()),
+            ..command()
+        };
+        assert_eq!(cmd.filter(entries.clone()).len(), 2);
+
+        let cmd = HistoryCommand {
+            changed: true,
+            since: Some(parse_since("2025-01-03").unwrap()),
+            ..command()
+        };
+        let filtered = cmd.filter(entries.clone());
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].service, "user");
+
+        let cmd = HistoryCommand {
+            limit: Some(1),
+            operation: Some(HistoryOperation::Update),
+            ..command()
+        };
+        assert_eq!(cmd.filter(entries)[0].service, "user");
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert!(parse_since("2025-01-10").is_ok());
+        assert!(parse_since("2025-01-10T08:30:00+02:00").is_ok());
+        assert!(parse_since("last week").is_err());
+    }
+}
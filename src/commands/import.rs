@@ -0,0 +1,268 @@
+//! Import command implementation - restore a bundle written by `actr export`
+//!
+//! Unpacks Actr.toml and Actr.lock.toml into the project directory and stores the
+//! bundled protos through the CacheManager, which also fills the global proto
+//! cache. Every service is checked against its locked fingerprints before
+//! anything is written.
+
+use crate::commands::export::{BUNDLE_FORMAT, BundleManifest, MANIFEST_FILE, REMOTE_PROTO_DIR};
+use crate::commands::lock::verify_dependency;
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType, ProtoFile};
+use crate::human_println;
+use actr_config::LockFile;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use flate2::read::GzDecoder;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Project files restored from a bundle
+const PROJECT_FILES: [&str; 2] = ["Actr.toml", "Actr.lock.toml"];
+
+/// Import command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Restore a bundle written by 'actr export'",
+    long_about = "Restore Actr.toml, Actr.lock.toml and the cached protos from a bundle written by 'actr export', so 'actr install --offline' and 'actr gen' work without network access.\n\nExamples:\n  actr import bundle.tar.gz\n  actr import bundle.tar.gz --force     # Overwrite a different Actr.toml"
+)]
+pub struct ImportCommand {
+    /// Bundle file (.tar.gz)
+    #[arg(value_name = "BUNDLE")]
+    pub bundle: PathBuf,
+
+    /// Overwrite Actr.toml and Actr.lock.toml when they differ from the bundle
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// A bundle read into memory
+#[derive(Debug)]
+pub struct Bundle {
+    pub manifest: BundleManifest,
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl Bundle {
+    pub fn file(&self, path: &str) -> Option<&[u8]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    /// Bundled protos of one service
+    fn service_protos(&self, service: &str, files: &[String]) -> Result<Vec<ProtoFile>> {
+        files
+            .iter()
+            .map(|name| {
+                let path = format!("{REMOTE_PROTO_DIR}/{service}/{name}");
+                let content = self
+                    .file(&path)
+                    .with_context(|| format!("Bundle is missing {path}"))?;
+                Ok(ProtoFile {
+                    name: name.clone(),
+                    path: PathBuf::from(&path),
+                    content: String::from_utf8(content.to_vec())
+                        .with_context(|| format!("{path} is not valid UTF-8"))?,
+                    services: Vec::new(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Command for ImportCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let cache_manager = context.container.get_cache_manager()?;
+        let project_root = project_root(context);
+        let bundle = read_bundle(&self.bundle)?;
+
+        // Check everything before touching the project
+        let lock_content = bundle
+            .file("Actr.lock.toml")
+            .context("Bundle is missing Actr.lock.toml")?;
+        let lock_file = parse_lock_file(lock_content)?;
+        let mut restored = Vec::with_capacity(bundle.manifest.services.len());
+        for service in &bundle.manifest.services {
+            let locked = lock_file
+                .dependencies
+                .iter()
+                .find(|dep| dep.name == service.name)
+                .with_context(|| format!("'{}' is not in the bundled lock file", service.name))?;
+            let files = bundle.service_protos(&service.name, &service.files)?;
+            let versioned: Vec<actr_version::ProtoFile> = files
+                .iter()
+                .map(|file| actr_version::ProtoFile {
+                    name: file.name.clone(),
+                    content: file.content.clone(),
+                    path: None,
+                })
+                .collect();
+            let issues = verify_dependency(locked, Some(&versioned));
+            if !issues.is_empty() {
+                return Err(ActrCliError::FingerprintValidation {
+                    message: format!(
+                        "Bundled protos of '{}' do not match the lock file: {}",
+                        service.name,
+                        issues.join("; ")
+                    ),
+                }
+                .into());
+            }
+            restored.push((service, files));
+        }
+
+        for name in PROJECT_FILES {
+            let target = project_root.join(name);
+            let content = bundle
+                .file(name)
+                .with_context(|| format!("Bundle is missing {name}"))?;
+            if !self.force
+                && target.exists()
+                && std::fs::read(&target).is_ok_and(|current| current != content)
+            {
+                return Err(ActrCliError::InvalidArgument {
+                    message: format!(
+                        "{} differs from the bundle; pass --force to overwrite it",
+                        target.display()
+                    ),
+                }
+                .into());
+            }
+        }
+
+        for name in PROJECT_FILES {
+            std::fs::write(
+                project_root.join(name),
+                bundle.file(name).unwrap_or_default(),
+            )?;
+        }
+        for (service, files) in &restored {
+            cache_manager.invalidate_cache(&service.name).await?;
+            cache_manager.cache_proto(&service.name, files).await?;
+        }
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "bundle": self.bundle,
+                "project_root": project_root,
+                "manifest": bundle.manifest,
+            });
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &report,
+            )?));
+        }
+
+        for (service, files) in &restored {
+            human_println!("📥 {} ({} files)", service.name, files.len());
+        }
+        Ok(CommandResult::Success(format!(
+            "Imported {} services from {} (exported from '{}' at {})",
+            restored.len(),
+            self.bundle.display(),
+            bundle.manifest.package,
+            bundle.manifest.created_at.format("%Y-%m-%d %H:%M:%S")
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::CacheManager]
+    }
+
+    fn name(&self) -> &str {
+        "import"
+    }
+
+    fn description(&self) -> &str {
+        "Restore a bundle written by 'actr export'"
+    }
+}
+
+/// Directory of the current Actr.toml, or the working directory for a fresh checkout
+fn project_root(context: &CommandContext) -> PathBuf {
+    match context.container.get_config_manager() {
+        Ok(config_manager) => config_manager.get_project_root().to_path_buf(),
+        Err(_) => context.working_dir.clone(),
+    }
+}
+
+/// Read a bundle, rejecting entries that would escape the project directory
+pub fn read_bundle(path: &Path) -> Result<Bundle> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+    let mut files = BTreeMap::new();
+    for entry in archive
+        .entries()
+        .with_context(|| format!("{} is not a .tar.gz bundle", path.display()))?
+    {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let entry_path = entry.path()?.into_owned();
+        if !entry_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            anyhow::bail!(
+                "Bundle entry '{}' is not a relative path",
+                entry_path.display()
+            );
+        }
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        files.insert(entry_path.to_string_lossy().replace('\\', "/"), data);
+    }
+
+    let manifest: BundleManifest = serde_json::from_slice(
+        files
+            .get(MANIFEST_FILE)
+            .with_context(|| format!("{} has no {MANIFEST_FILE}", path.display()))?,
+    )
+    .with_context(|| format!("Invalid {MANIFEST_FILE}"))?;
+    if manifest.format != BUNDLE_FORMAT {
+        anyhow::bail!(
+            "Unsupported bundle format {} (this CLI reads format {BUNDLE_FORMAT})",
+            manifest.format
+        );
+    }
+
+    Ok(Bundle { manifest, files })
+}
+
+fn parse_lock_file(content: &[u8]) -> Result<LockFile> {
+    let dir = tempfile::TempDir::new()?;
+    let path = dir.path().join("Actr.lock.toml");
+    std::fs::write(&path, content)?;
+    LockFile::from_file(&path).context("Bundled Actr.lock.toml is invalid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_bundle_rejects_escaping_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("evil.tar.gz");
+        let file = std::fs::File::create(&path).unwrap();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ));
+        let data = b"pwned";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        // `append_data` refuses `..`, so write the name into the header directly
+        header.as_gnu_mut().unwrap().name[..12].copy_from_slice(b"../Actr.toml");
+        header.set_cksum();
+        builder.append(&header, data.as_slice()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let err = read_bundle(&path).unwrap_err();
+        assert!(err.to_string().contains("not a relative path"));
+    }
+}
@@ -2,11 +2,18 @@
 
 use crate::commands::initialize::{self, InitContext};
 use crate::commands::{Command, SupportedLanguage};
+use crate::core::{
+    ConfigManager, DependencySpec, NetworkSettings, RetryPolicy, ServiceInfo, TomlConfigManager,
+    events, registry_backend, timings::timed,
+};
 use crate::error::{ActrCliError, Result};
-use crate::template::ProjectTemplateName;
+use crate::template::registry::{self, CustomTemplate, HookRun, TemplateSource};
+use crate::template::{ProjectTemplateName, TemplateContext};
+use crate::{human_println, tr};
+use actr_protocol::ActrTypeExt;
 use async_trait::async_trait;
-use clap::Args;
-use std::io::{self, Write};
+use clap::{Args, ValueEnum};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use tracing::info;
 
@@ -19,6 +26,14 @@ pub struct InitCommand {
     #[arg(long, default_value_t = ProjectTemplateName::Echo)]
     pub template: ProjectTemplateName,
 
+    /// Custom template: directory, registered name, git URL (url#ref) or .tar.gz archive
+    #[arg(long, value_name = "PATH|URL")]
+    pub template_path: Option<String>,
+
+    /// Template variable exposed to handlebars as {{KEY}} (repeatable)
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_template_var)]
+    pub vars: Vec<(String, String)>,
+
     /// Project name when initializing in current directory
     #[arg(long)]
     pub project_name: Option<String>,
@@ -30,14 +45,64 @@ pub struct InitCommand {
     /// Target language for project initialization
     #[arg(short, long, default_value = "rust")]
     pub language: SupportedLanguage,
+
+    /// Swift only: create a Package.swift-based project instead of an xcodegen one
+    #[arg(long)]
+    pub swift_package: bool,
+
+    /// Run 'actr install' and 'actr gen' after selecting dependencies without asking
+    #[arg(long)]
+    pub with_deps: bool,
+
+    /// Do not query the registry for initial dependencies
+    #[arg(long)]
+    pub skip_discovery: bool,
+
+    /// Run the custom template's post_init hooks without asking
+    #[arg(long)]
+    pub allow_hooks: bool,
+
+    /// Initialize a git repository with an initial commit (default)
+    #[arg(long, overrides_with = "no_git")]
+    pub git: bool,
+
+    /// Do not initialize a git repository
+    #[arg(long, overrides_with = "git")]
+    pub no_git: bool,
+
+    /// Install, generate and build the new project to check the template compiles
+    #[arg(long)]
+    pub verify: bool,
+}
+
+/// What `actr init` produced
+#[derive(Debug, Default)]
+pub struct InitSummary {
+    /// Files created by the template, hooks and dependency setup
+    pub created: Vec<PathBuf>,
+    /// Post-init hooks that were run, in order
+    pub hooks: Vec<HookRun>,
 }
 
 #[async_trait]
 impl Command for InitCommand {
     async fn execute(&self) -> Result<()> {
+        self.run().await.map(|_| ())
+    }
+}
+
+impl InitCommand {
+    /// Initialize the project and summarize what was created
+    pub async fn run(&self) -> Result<InitSummary> {
+        self.run_with_discovery(!crate::utils::offline_from_env())
+            .await
+    }
+
+    /// Initialize the project; `discovery` enables the dependency selection step
+    pub async fn run_with_discovery(&self, discovery: bool) -> Result<InitSummary> {
         // Show welcome header
-        println!("🎯 Actor-RTC Project Initialization");
-        println!("----------------------------------------");
+        human_println!("{}", tr!("init.welcome"));
+        human_println!("----------------------------------------");
 
         // Interactive prompt for missing required fields
         let name = self.prompt_if_missing("project name", self.name.as_ref())?;
@@ -46,7 +111,13 @@ impl Command for InitCommand {
 
         let (project_dir, project_name) = self.resolve_project_info(&name)?;
 
-        info!("🚀 Initializing Actor-RTC project: {}", project_name);
+        if self.swift_package && self.language != SupportedLanguage::Swift {
+            return Err(ActrCliError::InvalidProject(
+                "--swift-package only applies to Swift projects (-l swift)".to_string(),
+            ));
+        }
+
+        info!("{}", tr!("init.initializing", name = project_name));
 
         // Check if target directory exists and is not empty
         if project_dir.exists() && project_dir != Path::new(".") {
@@ -68,6 +139,7 @@ impl Command for InitCommand {
         if project_dir != Path::new(".") {
             std::fs::create_dir_all(&project_dir)?;
         }
+        let existing_files = list_project_files(&project_dir);
 
         let context = InitContext {
             project_dir: project_dir.clone(),
@@ -75,15 +147,201 @@ impl Command for InitCommand {
             signaling_url: signaling_url.clone(),
             template: self.template,
             is_current_dir: project_dir == Path::new("."),
+            swift_package: self.swift_package,
+            vars: self.vars.iter().cloned().collect(),
+        };
+
+        let (template_name, hooks) = timed("template", async {
+            match &self.template_path {
+                Some(spec) => self.generate_custom(spec, &context).await,
+                None => {
+                    initialize::execute_initialize(self.language, &context).await?;
+                    Ok((self.template.to_string(), Vec::new()))
+                }
+            }
+        })
+        .await?;
+
+        if discovery && !self.skip_discovery && is_interactive() {
+            self.select_dependencies(&project_dir, &project_name)
+                .await?;
+        }
+
+        if self.verify {
+            timed(
+                "verify",
+                initialize::verify::verify(self.language, &template_name, &project_dir),
+            )
+            .await?;
+        }
+
+        if !self.no_git {
+            initialize::git::bootstrap(&project_dir)?;
+        }
+
+        let created: Vec<PathBuf> = list_project_files(&project_dir)
+            .into_iter()
+            .filter(|path| !existing_files.contains(path))
+            .collect();
+        events::emit_files_generated(&created);
+        Ok(InitSummary { created, hooks })
+    }
+
+    /// Render a custom template instead of a built-in one, then run its hooks;
+    /// returns the template name and the hooks that ran
+    async fn generate_custom(
+        &self,
+        spec: &str,
+        context: &InitContext,
+    ) -> Result<(String, Vec<HookRun>)> {
+        let template = CustomTemplate::fetch(&TemplateSource::parse(spec)).await?;
+        template.check_language(self.language)?;
+        info!(
+            "{}",
+            tr!("init.using_template", name = template.manifest().name)
+        );
+
+        let variables = template.resolve_variables(&context.vars, is_interactive())?;
+        let template_context = TemplateContext::new(
+            &context.project_name,
+            &context.signaling_url,
+            context.template.to_service_name(),
+        );
+        let written = template.generate(&context.project_dir, &template_context, &variables)?;
+        human_println!(
+            "{}",
+            tr!(
+                "init.rendered",
+                count = written.len(),
+                name = template.manifest().name
+            )
+        );
+
+        let hooks = self
+            .run_post_init_hooks(&template.manifest().hooks.post_init, &context.project_dir)
+            .await;
+
+        human_println!("\n{}", tr!("init.next_steps"));
+        if !context.is_current_dir {
+            human_println!("  cd {}", context.project_dir.display());
+        }
+        human_println!("  actr install  # {}", tr!("init.comment.install"));
+        human_println!("  actr gen      # {}", tr!("init.comment.gen"));
+        Ok((template.manifest().name.clone(), hooks))
+    }
+
+    /// Run `post_init` hooks once allowed by `--allow-hooks` or a confirmation prompt
+    async fn run_post_init_hooks(&self, commands: &[String], project_dir: &Path) -> Vec<HookRun> {
+        if commands.is_empty() {
+            return Vec::new();
+        }
+        let allowed = self.allow_hooks
+            || (is_interactive() && {
+                human_println!("\n{}", tr!("init.hooks_requested"));
+                for command in commands {
+                    human_println!("  $ {command}");
+                }
+                dialoguer::Confirm::new()
+                    .with_prompt(tr!("init.confirm_hooks"))
+                    .default(false)
+                    .interact()
+                    .unwrap_or(false)
+            });
+        if !allowed {
+            human_println!("{}", tr!("init.hooks_skipped", count = commands.len()));
+            return Vec::new();
+        }
+
+        let runs = registry::run_hooks(project_dir, commands).await;
+        human_println!("\n{}", tr!("init.hooks_title"));
+        for run in &runs {
+            let status = if run.success() { "✅" } else { "❌" };
+            human_println!("  {status} {}", run.command);
+            for line in output_tail(&run.output) {
+                human_println!("     {line}");
+            }
+        }
+        if runs.len() < commands.len() {
+            human_println!(
+                "{}",
+                tr!("init.hooks_stopped", count = commands.len() - runs.len())
+            );
+        }
+        runs
+    }
+
+    /// Offer services from the registry as initial dependencies, then install and generate
+    async fn select_dependencies(&self, project_dir: &Path, project_name: &str) -> Result<()> {
+        let config_path = project_dir.join("Actr.toml");
+        let config = actr_config::ConfigParser::from_file(&config_path)?;
+
+        human_println!("{}", tr!("init.looking_up_services"));
+        let discovery = registry_backend::project_discovery(
+            &config_path,
+            config,
+            RetryPolicy::from_config_file(&config_path)?,
+            NetworkSettings::from_config_file(&config_path)?,
+        )?;
+        let services = match discovery.discover_services(None).await {
+            Ok(services) => selectable_services(services, project_name),
+            Err(e) => {
+                human_println!("{}", tr!("init.discovery_skipped", error = e));
+                return Ok(());
+            }
         };
+        if services.is_empty() {
+            human_println!("{}", tr!("init.no_services"));
+            return Ok(());
+        }
+
+        let items: Vec<String> = services
+            .iter()
+            .map(|s| format!("{} ({})", s.name, s.actr_type.to_string_repr()))
+            .collect();
+        let selected = dialoguer::MultiSelect::new()
+            .with_prompt(tr!("init.select_dependencies"))
+            .items(&items)
+            .interact()
+            .map_err(|e| ActrCliError::command_error(format!("Selection failed: {e}")))?;
+        if selected.is_empty() {
+            return Ok(());
+        }
 
-        initialize::execute_initialize(self.language, &context).await?;
+        let config_manager = TomlConfigManager::new(config_path.clone());
+        for index in &selected {
+            let service = &services[*index];
+            config_manager
+                .update_dependency(&DependencySpec {
+                    alias: service.name.clone(),
+                    name: service.name.clone(),
+                    actr_type: Some(service.actr_type.clone()),
+                    fingerprint: Some(service.fingerprint.clone()),
+                    constraint: Default::default(),
+                })
+                .await?;
+            human_println!("{}", tr!("init.dependency_added", name = service.name));
+        }
 
+        let proceed = self.with_deps
+            || dialoguer::Confirm::new()
+                .with_prompt(tr!("init.confirm_install"))
+                .default(true)
+                .interact()
+                .unwrap_or(false);
+        if proceed {
+            let language = self
+                .language
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+                .unwrap_or_else(|| "rust".to_string());
+            run_actr(project_dir, &["install"]).await?;
+            run_actr(project_dir, &["gen", "--language", &language]).await?;
+        } else {
+            human_println!("{}", tr!("init.tip_install"));
+        }
         Ok(())
     }
-}
 
-impl InitCommand {
     fn resolve_project_info(&self, name: &str) -> Result<(PathBuf, String)> {
         if name == "." {
             // Initialize in current directory - name will be inferred
@@ -130,37 +388,15 @@ impl InitCommand {
 
         match field_name {
             "project name" => {
-                println!("┌──────────────────────────────────────────────────────────┐");
-                println!("│ 📋  Project Name Configuration                           │");
-                println!("├──────────────────────────────────────────────────────────┤");
-                println!("│                                                          │");
-                println!("│  📝 Requirements:                                        │");
-                println!("│     • Only alphanumeric characters, hyphens and _        │");
-                println!("│     • Cannot start or end with - or _                    │");
-                println!("│                                                          │");
-                println!("│  💡 Examples:                                            │");
-                println!("│     my-chat-service, user-manager, media_streamer        │");
-                println!("│                                                          │");
-                println!("└──────────────────────────────────────────────────────────┘");
-                print!("🎯 Enter project name [my-actor-project]: ");
+                print_prompt_box("init.prompt.project_name");
+                print!("{}", tr!("init.prompt.enter_project_name"));
             }
             "signaling server URL" => {
-                println!("┌──────────────────────────────────────────────────────────┐");
-                println!("│ 🌐  Signaling Server Configuration                       │");
-                println!("├──────────────────────────────────────────────────────────┤");
-                println!("│                                                          │");
-                println!("│  📡 WebSocket URL for Actor-RTC signaling coordination   │");
-                println!("│                                                          │");
-                println!("│  💡 Examples:                                            │");
-                println!("│     ws://localhost:8080/                (development)    │");
-                println!("│     wss://example.com                   (production      │");
-                println!("│     wss://example.com/?token=${{TOKEN}}   (with auth)    │");
-                println!("│                                                          │");
-                println!("└──────────────────────────────────────────────────────────┘");
-                print!("🎯 Enter signaling server URL [wss://actrix1.develenv.com]: ");
+                print_prompt_box("init.prompt.signaling");
+                print!("{}", tr!("init.prompt.enter_signaling"));
             }
             _ => {
-                print!("🎯 Enter {field_name}: ");
+                print!("{}", tr!("init.prompt.enter_field", field = field_name));
             }
         }
 
@@ -171,7 +407,7 @@ impl InitCommand {
             .read_line(&mut input)
             .map_err(ActrCliError::Io)?;
 
-        println!();
+        human_println!();
 
         let trimmed = input.trim();
         if trimmed.is_empty() {
@@ -230,3 +466,128 @@ impl InitCommand {
         Ok(())
     }
 }
+
+/// Parse a `--var KEY=VALUE` argument
+fn parse_template_var(value: &str) -> std::result::Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{value}'")),
+    }
+}
+
+/// Prompts need a terminal and are suppressed for machine-readable output
+/// Guidance shown before a prompt: a title and the lines of catalog list `key`
+///
+/// The box is only closed on the left so translated lines need no padding.
+fn print_prompt_box(key: &str) {
+    const RULE: &str = "──────────────────────────────────────────────────────────";
+    let mut lines = crate::core::i18n::messages(key).into_iter();
+    human_println!("┌{RULE}");
+    if let Some(title) = lines.next() {
+        human_println!("│ {title}");
+        human_println!("├{RULE}");
+    }
+    for line in lines {
+        human_println!("│  {line}");
+    }
+    human_println!("└{RULE}");
+}
+
+/// Last lines of a hook's output, enough to show what happened
+fn output_tail(output: &str) -> Vec<&str> {
+    const TAIL_LINES: usize = 5;
+    let lines: Vec<&str> = output.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(TAIL_LINES)..].to_vec()
+}
+
+pub(crate) fn is_interactive() -> bool {
+    io::stdin().is_terminal() && !crate::core::json_output()
+}
+
+/// Services that can become dependencies of `project_name`, sorted by name
+fn selectable_services(mut services: Vec<ServiceInfo>, project_name: &str) -> Vec<ServiceInfo> {
+    services.retain(|service| service.name != project_name);
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    services.dedup_by(|a, b| a.name == b.name);
+    services
+}
+
+/// Run an `actr` subcommand in the new project, streaming its output
+pub(crate) async fn run_actr(project_dir: &Path, args: &[&str]) -> Result<()> {
+    info!("{}", tr!("init.running", command = args.join(" ")));
+    let status = tokio::process::Command::new(std::env::current_exe()?)
+        .args(args)
+        .current_dir(project_dir)
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(ActrCliError::command_error(format!(
+            "'actr {}' failed with exit code {:?}",
+            args.join(" "),
+            status.code()
+        )));
+    }
+    Ok(())
+}
+
+/// Files below `dir`, skipping VCS metadata and dependency/build directories
+fn list_project_files(dir: &Path) -> std::collections::BTreeSet<PathBuf> {
+    const SKIPPED_DIRS: &[&str] = &[".git", "target", "node_modules", ".venv"];
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| SKIPPED_DIRS.contains(&name))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(name: &str) -> ServiceInfo {
+        ServiceInfo {
+            name: name.to_string(),
+            tags: Vec::new(),
+            fingerprint: "sha256:abc".to_string(),
+            actr_type: actr_protocol::ActrType::from_string_repr(&format!("acme+{name}")).unwrap(),
+            published_at: None,
+            description: None,
+            methods: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_selectable_services_skip_self_and_duplicates() {
+        let services = vec![
+            service("user-service"),
+            service("my-app"),
+            service("auth-service"),
+            service("user-service"),
+        ];
+        let names: Vec<String> = selectable_services(services, "my-app")
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["auth-service", "user-service"]);
+    }
+
+    #[test]
+    fn test_parse_template_var() {
+        assert_eq!(
+            parse_template_var("TEAM=core=platform").unwrap(),
+            ("TEAM".to_string(), "core=platform".to_string())
+        );
+        assert!(parse_template_var("TEAM").is_err());
+        assert!(parse_template_var("=value").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Git repository bootstrap for new projects
+//!
+//! After generation the project gets a repository, a `.gitignore` (kept when the
+//! template already wrote one) and an initial commit. Projects inside an existing
+//! work tree — typically `actr init .` in a repository — are left to it.
+
+use crate::error::{ActrCliError, Result};
+use crate::human_println;
+use std::path::Path;
+use std::process::Command;
+use tracing::info;
+
+pub const INITIAL_COMMIT_MESSAGE: &str = "Initialize Actor-RTC project";
+
+/// Used when the template does not ship a `.gitignore`
+const DEFAULT_GITIGNORE: &str = "\
+.env
+.env.local
+*.log
+.DS_Store
+
+/.actr/*
+!/.actr/history.jsonl
+";
+
+/// Initialize a repository in `project_dir` and commit the generated files
+pub fn bootstrap(project_dir: &Path) -> Result<()> {
+    if let Some(toplevel) = enclosing_work_tree(project_dir) {
+        human_println!("ℹ️  Keeping the existing git repository at {toplevel}");
+        return Ok(());
+    }
+
+    if let Err(e) = git(project_dir, &["init"]) {
+        human_println!("⚠️  Skipping git setup: {e}");
+        return Ok(());
+    }
+    info!("🔧 Initialized git repository");
+
+    if ensure_gitignore(project_dir)? {
+        info!("📄 Created .gitignore");
+    }
+
+    git(project_dir, &["add", "-A"])?;
+    match git(project_dir, &["commit", "-q", "-m", INITIAL_COMMIT_MESSAGE]) {
+        Ok(()) => info!("📝 Created initial commit \"{INITIAL_COMMIT_MESSAGE}\""),
+        // Usually a missing user.name/user.email; the repository is still usable
+        Err(e) => human_println!("⚠️  Could not create the initial commit: {e}"),
+    }
+    Ok(())
+}
+
+/// Write the default `.gitignore` unless one exists; returns whether it was written
+fn ensure_gitignore(project_dir: &Path) -> Result<bool> {
+    let path = project_dir.join(".gitignore");
+    if path.exists() {
+        return Ok(false);
+    }
+    std::fs::write(path, DEFAULT_GITIGNORE)?;
+    Ok(true)
+}
+
+fn enclosing_work_tree(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| ActrCliError::Command(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(ActrCliError::Command(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gitignore_kept_when_present() {
+        let dir = TempDir::new().unwrap();
+        assert!(ensure_gitignore(dir.path()).unwrap());
+        let written = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(written.contains("/.actr/*"));
+
+        std::fs::write(dir.path().join(".gitignore"), "/target\n").unwrap();
+        assert!(!ensure_gitignore(dir.path()).unwrap());
+        let kept = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert_eq!(kept, "/target\n");
+    }
+}
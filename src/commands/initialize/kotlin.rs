@@ -1,6 +1,7 @@
 use super::{InitContext, ProjectInitializer, create_local_proto, create_protoc_plugin_config};
 use crate::error::Result;
 use crate::templates::ProjectTemplateName;
+use crate::tr;
 use crate::utils::read_fixture_text;
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
@@ -22,11 +23,11 @@ impl ProjectInitializer for KotlinInitializer {
         let package_path = to_package_name(&context.project_name).replace('.', "/");
 
         info!("");
-        info!("Next steps:");
+        info!("{}", tr!("init.next_steps"));
         if !context.is_current_dir {
             info!("  cd {}", context.project_dir.display());
         }
-        info!("  actr install  # Install remote protobuf dependencies from Actr.toml");
+        info!("  actr install  # {}", tr!("init.comment.install"));
 
         match context.template {
             ProjectTemplateName::Echo => {
@@ -42,7 +43,7 @@ impl ProjectInitializer for KotlinInitializer {
         info!("  ./gradlew assembleDebug");
         info!("  # Install APK: adb install app/build/outputs/apk/debug/app-debug.apk");
         info!("");
-        info!("💡 Tips:");
+        info!("{}", tr!("init.tips"));
         info!("  - For Android emulator, use ws://10.0.2.2:PORT to reach host localhost");
         info!("  - actr-kotlin library is fetched from JitPack automatically");
         info!(
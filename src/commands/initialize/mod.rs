@@ -1,19 +1,22 @@
+pub mod git;
 mod kotlin;
 mod python;
 mod rust;
 mod swift;
 pub mod traits;
+mod typescript;
+pub mod verify;
 
 use crate::commands::SupportedLanguage;
 use crate::error::{ActrCliError, Result};
 use crate::template::{ProjectTemplateName, TemplateContext};
 use crate::utils::read_fixture_text;
-use handlebars::Handlebars;
 use kotlin::KotlinInitializer;
 use python::PythonInitializer;
 use rust::RustInitializer;
 use std::path::Path;
 use swift::SwiftInitializer;
+use typescript::TypescriptInitializer;
 
 pub use traits::{InitContext, ProjectInitializer};
 
@@ -61,7 +64,7 @@ pub fn create_local_proto(
 
     // Create template context
     let template_context = TemplateContext::new(project_name, "", "");
-    let handlebars = Handlebars::new();
+    let handlebars = crate::template::handlebars();
 
     // Render template
     let local_proto_content = handlebars
@@ -89,6 +92,7 @@ impl InitializerFactory {
             SupportedLanguage::Python => Ok(Box::new(PythonInitializer)),
             SupportedLanguage::Swift => Ok(Box::new(SwiftInitializer)),
             SupportedLanguage::Kotlin => Ok(Box::new(KotlinInitializer)),
+            SupportedLanguage::Typescript => Ok(Box::new(TypescriptInitializer)),
         }
     }
 }
@@ -3,6 +3,7 @@ use super::{InitContext, ProjectInitializer, create_protoc_plugin_config};
 use crate::commands::SupportedLanguage;
 use crate::error::Result;
 use crate::template::{ProjectTemplate, TemplateContext};
+use crate::tr;
 use async_trait::async_trait;
 use tracing::info;
 
@@ -14,7 +15,8 @@ impl ProjectInitializer for PythonInitializer {
         let template = ProjectTemplate::new(context.template, SupportedLanguage::Python);
         let service_name = context.template.to_service_name();
         let template_context =
-            TemplateContext::new(&context.project_name, &context.signaling_url, service_name);
+            TemplateContext::new(&context.project_name, &context.signaling_url, service_name)
+                .with_vars(&context.vars);
         template.generate(&context.project_dir, &template_context)?;
         create_protoc_plugin_config(&context.project_dir.join("server"))?;
         create_protoc_plugin_config(&context.project_dir.join("client"))?;
@@ -30,18 +32,20 @@ impl ProjectInitializer for PythonInitializer {
 
     fn print_next_steps(&self, context: &InitContext) {
         info!("");
-        info!("Next steps:");
+        info!("{}", tr!("init.next_steps"));
         if !context.is_current_dir {
             info!("  cd {}", context.project_dir.display());
         }
         info!("  cd server");
+        info!("  pip install -e \".[dev]\"  # Install actr runtime and codegen plugin");
         info!("  #First Update Actr.toml with your signaling URL, TURN/STUN server, and realm ID");
-        info!("  actr install  # Install remote protobuf dependencies from Actr.toml");
+        info!("  actr install  # {}", tr!("init.comment.install"));
         info!("  actr gen -l python -i protos -o generated  # Generate code for server");
         info!("  python server.py --actr-toml Actr.toml");
         info!("  cd ../client");
+        info!("  pip install -e \".[dev]\"");
         info!("  #First Update Actr.toml with your signaling URL, TURN/STUN server, and realm ID");
-        info!("  actr install  # Install remote protobuf dependencies from Actr.toml");
+        info!("  actr install  # {}", tr!("init.comment.install"));
         info!("  actr gen -l python -i protos -o generated  # Generate code for client");
         info!("  python client.py --actr-toml Actr.toml");
     }
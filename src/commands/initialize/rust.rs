@@ -3,6 +3,7 @@ use crate::commands::initialize::traits::{InitContext, ProjectInitializer};
 use crate::commands::initialize::{create_local_proto, create_protoc_plugin_config};
 use crate::error::Result;
 use crate::template::{ProjectTemplate, TemplateContext};
+use crate::{human_println, tr};
 use async_trait::async_trait;
 use tracing::info;
 
@@ -19,7 +20,8 @@ impl ProjectInitializer for RustInitializer {
             &context.project_name,
             &context.signaling_url,
             context.template.to_service_name(),
-        );
+        )
+        .with_vars(&context.vars);
 
         template.generate(&context.project_dir, &template_context)?;
 
@@ -38,12 +40,12 @@ impl ProjectInitializer for RustInitializer {
     }
 
     fn print_next_steps(&self, context: &InitContext) {
-        println!("\nNext steps:");
+        human_println!("\n{}", tr!("init.next_steps"));
         if !context.is_current_dir {
-            println!("  cd {}", context.project_dir.display());
+            human_println!("  cd {}", context.project_dir.display());
         }
-        println!("  actr install  # Install remote protobuf dependencies from Actr.toml");
-        println!("  actr gen      # Generate Actor code");
-        println!("  cargo run     # Start your work");
+        human_println!("  actr install  # {}", tr!("init.comment.install"));
+        human_println!("  actr gen      # {}", tr!("init.comment.gen"));
+        human_println!("  cargo run     # {}", tr!("init.comment.run"));
     }
 }
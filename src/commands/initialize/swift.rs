@@ -2,6 +2,7 @@ use super::{InitContext, ProjectInitializer, create_local_proto, create_protoc_p
 use crate::commands::SupportedLanguage;
 use crate::error::{ActrCliError, Result};
 use crate::template::{ProjectTemplate, TemplateContext};
+use crate::tr;
 use async_trait::async_trait;
 use std::path::Path;
 use std::process::Command;
@@ -12,7 +13,11 @@ pub struct SwiftInitializer;
 #[async_trait]
 impl ProjectInitializer for SwiftInitializer {
     async fn generate_project_structure(&self, context: &InitContext) -> Result<()> {
-        let template = ProjectTemplate::new(context.template, SupportedLanguage::Swift);
+        let template = if context.swift_package {
+            ProjectTemplate::swift_package(context.template)
+        } else {
+            ProjectTemplate::new(context.template, SupportedLanguage::Swift)
+        };
         // For data-stream template, use "LocalFileService" as the service name
         // For other templates, use the template's service name
         let service_name = match context.template {
@@ -26,7 +31,8 @@ impl ProjectInitializer for SwiftInitializer {
             &context.signaling_url,
             service_name,
         )
-        .await;
+        .await
+        .with_vars(&context.vars);
 
         template.generate(&context.project_dir, &template_context)?;
         create_protoc_plugin_config(&context.project_dir)?;
@@ -39,15 +45,15 @@ impl ProjectInitializer for SwiftInitializer {
             context.template,
         )?;
 
-        ensure_xcodegen_available()?;
-        run_xcodegen_generate(&context.project_dir)?;
+        // Swift packages are opened and built directly; only xcodegen projects need generating
+        if !context.swift_package {
+            ensure_xcodegen_available()?;
+            run_xcodegen_generate(&context.project_dir)?;
+        }
 
         // Create Swift Package Manager registry configuration
         create_swiftpm_registry_config(&context.project_dir)?;
 
-        // Initialize git repository
-        init_git_repo(&context.project_dir)?;
-
         Ok(())
     }
 
@@ -58,18 +64,24 @@ impl ProjectInitializer for SwiftInitializer {
             context.template.to_service_name(),
         );
         info!("");
-        info!("Next steps:");
+        info!("{}", tr!("init.next_steps"));
         if !context.is_current_dir {
             info!("  cd {}", context.project_dir.display());
         }
-        info!("  actr install  # Install remote protobuf dependencies from Actr.toml");
+        info!("  actr install  # {}", tr!("init.comment.install"));
         info!(
             "  actr gen -l swift  # Use default input (protos) and Swift output ({}/Generated)",
             template_context.project_name_pascal
         );
-        info!("  xcodegen generate");
-        info!("  open {}.xcodeproj", template_context.project_name_pascal);
-        info!("  # If you update project.yml, rerun: xcodegen generate");
+        if context.swift_package {
+            info!("  swift build");
+            info!("  swift run {}", template_context.project_name_pascal);
+            info!("  # Or open Package.swift in Xcode");
+        } else {
+            info!("  xcodegen generate");
+            info!("  open {}.xcodeproj", template_context.project_name_pascal);
+            info!("  # If you update project.yml, rerun: xcodegen generate");
+        }
     }
 }
 
@@ -140,19 +152,3 @@ fn create_swiftpm_registry_config(project_dir: &Path) -> Result<()> {
     info!("📦 Created Swift Package Manager registry configuration");
     Ok(())
 }
-
-fn init_git_repo(project_dir: &Path) -> Result<()> {
-    let output = Command::new("git")
-        .args(["init"])
-        .current_dir(project_dir)
-        .output()
-        .map_err(|error| ActrCliError::Command(format!("Failed to run git init: {error}")))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(ActrCliError::Command(format!("git init failed: {stderr}")));
-    }
-
-    info!("🔧 Initialized git repository");
-    Ok(())
-}
@@ -1,5 +1,6 @@
 use crate::{error::Result, template::ProjectTemplateName};
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Context for non-Rust project initialization.
@@ -10,6 +11,10 @@ pub struct InitContext {
     pub signaling_url: String,
     pub template: ProjectTemplateName,
     pub is_current_dir: bool,
+    /// Swift only: Package.swift layout instead of an xcodegen project
+    pub swift_package: bool,
+    /// `--var` values exposed to the templates
+    pub vars: BTreeMap<String, String>,
 }
 
 /// Interface for language-specific project initialization.
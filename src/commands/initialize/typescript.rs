@@ -0,0 +1,58 @@
+use crate::commands::SupportedLanguage;
+use crate::commands::initialize::create_local_proto;
+use crate::commands::initialize::traits::{InitContext, ProjectInitializer};
+use crate::error::Result;
+use crate::template::{ProjectTemplate, TemplateContext};
+use crate::{human_println, tr};
+use async_trait::async_trait;
+use tracing::info;
+
+pub struct TypescriptInitializer;
+
+#[async_trait]
+impl ProjectInitializer for TypescriptInitializer {
+    async fn generate_project_structure(&self, context: &InitContext) -> Result<()> {
+        info!("⚡ Generating TypeScript project structure...");
+
+        let template = ProjectTemplate::new(context.template, SupportedLanguage::Typescript);
+        let template_context = TemplateContext::new(
+            &context.project_name,
+            &context.signaling_url,
+            context.template.to_service_name(),
+        )
+        .with_vars(&context.vars);
+
+        template.generate(&context.project_dir, &template_context)?;
+
+        create_local_proto(
+            &context.project_dir,
+            &context.project_name,
+            "protos/local",
+            context.template,
+        )?;
+
+        // TypeScript codegen uses protoc-gen-es from node_modules, so no
+        // .protoc-plugin.toml is needed here.
+        Ok(())
+    }
+
+    fn print_next_steps(&self, context: &InitContext) {
+        human_println!("\n{}", tr!("init.next_steps"));
+        if !context.is_current_dir {
+            human_println!("  cd {}", context.project_dir.display());
+        }
+        human_println!(
+            "  npm install               # {}",
+            tr!("init.comment.npm_install")
+        );
+        human_println!(
+            "  actr install              # {}",
+            tr!("init.comment.install")
+        );
+        human_println!(
+            "  actr gen -l typescript    # {}",
+            tr!("init.comment.gen_typescript")
+        );
+        human_println!("  npm run dev               # {}", tr!("init.comment.dev"));
+    }
+}
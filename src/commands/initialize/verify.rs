@@ -0,0 +1,113 @@
+//! `actr init --verify`: build the freshly generated project
+//!
+//! Dependencies are installed and code generated first, then the language's
+//! cheapest build check runs, so a broken template fails the init right away.
+
+use crate::commands::SupportedLanguage;
+use crate::commands::init::run_actr;
+use crate::error::{ActrCliError, Result};
+use crate::human_println;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Build check commands for `language`, in order
+pub fn build_checks(language: SupportedLanguage, project_dir: &Path) -> Vec<Vec<String>> {
+    let command = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+    match language {
+        SupportedLanguage::Rust => vec![command(&["cargo", "check"])],
+        SupportedLanguage::Kotlin => {
+            let gradle = if project_dir.join("gradlew").exists() {
+                "./gradlew"
+            } else {
+                "gradle"
+            };
+            vec![command(&[gradle, "tasks", "--dry-run"])]
+        }
+        SupportedLanguage::Swift => {
+            let mut checks = Vec::new();
+            if project_dir.join("project.yml").exists() {
+                checks.push(command(&["xcodegen", "generate"]));
+            }
+            if project_dir.join("Package.swift").exists() {
+                checks.push(command(&["swift", "build"]));
+            }
+            checks
+        }
+        SupportedLanguage::Typescript => {
+            let mut checks = Vec::new();
+            if !project_dir.join("node_modules").exists() {
+                checks.push(command(&["npm", "install"]));
+            }
+            checks.push(command(&["npx", "tsc", "--noEmit"]));
+            checks
+        }
+        SupportedLanguage::Python => vec![command(&["python3", "-m", "compileall", "-q", "."])],
+    }
+}
+
+/// Install, generate and build the project; errors name the template and language
+pub async fn verify(language: SupportedLanguage, template: &str, project_dir: &Path) -> Result<()> {
+    human_println!("\n🔎 Verifying the generated project builds...");
+    let failed = |step: &str, reason: String| {
+        ActrCliError::command_error(format!(
+            "Template '{template}' ({}) failed verification at `{step}`: {reason}",
+            language.as_str()
+        ))
+    };
+
+    for args in [
+        vec!["install"],
+        vec!["gen", "--language", language.as_str()],
+    ] {
+        run_actr(project_dir, &args)
+            .await
+            .map_err(|e| failed(&format!("actr {}", args.join(" ")), e.to_string()))?;
+    }
+
+    for check in build_checks(language, project_dir) {
+        let step = check.join(" ");
+        human_println!("🔧 Running '{step}'");
+        let status = Command::new(&check[0])
+            .args(&check[1..])
+            .current_dir(project_dir)
+            .status()
+            .await
+            .map_err(|e| failed(&step, format!("could not start: {e}")))?;
+        if !status.success() {
+            return Err(failed(&step, format!("exit code {:?}", status.code())));
+        }
+    }
+
+    human_println!("✅ Template '{template}' builds for {}", language.as_str());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_build_checks_follow_project_layout() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            build_checks(SupportedLanguage::Rust, dir.path()),
+            vec![vec!["cargo", "check"]]
+        );
+        assert_eq!(
+            build_checks(SupportedLanguage::Kotlin, dir.path()),
+            vec![vec!["gradle", "tasks", "--dry-run"]]
+        );
+
+        std::fs::write(dir.path().join("Package.swift"), "").unwrap();
+        std::fs::write(dir.path().join("gradlew"), "").unwrap();
+        assert_eq!(
+            build_checks(SupportedLanguage::Swift, dir.path()),
+            vec![vec!["swift", "build"]]
+        );
+        assert_eq!(
+            build_checks(SupportedLanguage::Kotlin, dir.path())[0][0],
+            "./gradlew"
+        );
+    }
+}
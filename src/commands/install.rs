@@ -2,22 +2,27 @@
 //!
 //! Implement install flow based on reuse architecture with check-first principle
 
+use crate::commands::cache::format_size;
 use crate::core::{
-    ActrCliError, Command, CommandContext, CommandResult, ComponentType, DependencySpec,
-    ErrorReporter, InstallResult,
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, ConfigManager,
+    ConflictResolution, DependencySpec, ErrorReporter, InstallOptions, InstallPipeline,
+    InstallPlan, InstallResult, LockChange, dependency_constraint,
 };
+use crate::human_println;
+use crate::tr;
 use actr_config::LockFile;
 use actr_protocol::{ActrType, ActrTypeExt};
 use actr_version::{CompatibilityLevel, Fingerprint, ProtoFile, ServiceCompatibility};
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Args;
+use comfy_table::{Attribute, Cell, Table};
 
 /// Install command
 #[derive(Args, Debug)]
 #[command(
     about = "Install service dependencies",
-    long_about = "Install service dependencies. You can install specific service packages, or install all dependencies configured in Actr.toml.\n\nExamples:\n  actr install                          # Install all dependencies from Actr.toml\n  actr install user-service             # Install a service by name\n  actr install my-alias --actr-type acme+EchoService  # Install with alias and explicit actr_type"
+    long_about = "Install service dependencies. You can install specific service packages, or install all dependencies configured in Actr.toml.\n\nExamples:\n  actr install                          # Install all dependencies from Actr.toml\n  actr install user-service             # Install a service by name\n  actr install my-alias --actr-type acme+EchoService  # Install with alias and explicit actr_type\n  actr install --resolution prefer-locked  # Keep locked versions on conflicts\n  actr install --dry-run                # Show the install plan without writing anything"
 )]
 pub struct InstallCommand {
     /// Package name or alias (when used with --actr-type, this becomes the alias)
@@ -44,6 +49,22 @@ pub struct InstallCommand {
     /// Skip fingerprint verification
     #[arg(long)]
     pub skip_verification: bool,
+
+    /// Install dependencies for every member listed in Actr.workspace.toml
+    #[arg(long)]
+    pub workspace: bool,
+
+    /// Install services whose publisher signature is missing or does not verify
+    #[arg(long)]
+    pub allow_unsigned: bool,
+
+    /// How to settle fingerprint conflicts between dependencies
+    #[arg(long, value_enum, default_value_t = ConflictResolution::Strict)]
+    pub resolution: ConflictResolution,
+
+    /// Resolve and print the install plan without changing Actr.toml, the cache or the lock file
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 /// Installation mode
@@ -84,6 +105,18 @@ impl Command for InstallCommand {
             .into());
         }
 
+        let validation = context
+            .container
+            .get_config_manager()?
+            .validate_config()
+            .await?;
+        if !validation.is_valid {
+            return Err(ActrCliError::Config {
+                message: format!("Actr.toml is invalid:\n{}", validation.errors.join("\n")),
+            }
+            .into());
+        }
+
         // Determine installation mode
         let mode = if let Some(actr_type_str) = &self.actr_type {
             // Mode 1b: Install with explicit alias and actr_type
@@ -195,6 +228,10 @@ impl InstallCommand {
             force,
             force_update,
             skip_verification,
+            workspace: false,
+            allow_unsigned: false,
+            resolution: ConflictResolution::Strict,
+            dry_run: false,
         }
     }
 
@@ -207,6 +244,17 @@ impl InstallCommand {
             force: args.force,
             force_update: args.force_update,
             skip_verification: args.skip_verification,
+            workspace: args.workspace,
+            allow_unsigned: args.allow_unsigned,
+            resolution: args.resolution,
+            dry_run: args.dry_run,
+        }
+    }
+
+    fn install_options(&self) -> InstallOptions {
+        InstallOptions {
+            allow_unsigned: self.allow_unsigned,
+            resolution: self.resolution,
         }
     }
 
@@ -224,19 +272,16 @@ impl InstallCommand {
         context: &CommandContext,
         packages: &[String],
     ) -> Result<CommandResult> {
-        println!("actr install {}", packages.join(" "));
+        human_println!("actr install {}", packages.join(" "));
 
-        let install_pipeline = {
-            let mut container = context.container.lock().unwrap();
-            container.get_install_pipeline()?
-        };
+        let install_pipeline = context.container.get_install_pipeline()?;
 
         let mut resolved_specs = Vec::new();
 
-        println!("🔍 Phase 1: Complete Validation");
+        human_println!("{}", tr!("install.phase_validation"));
         for package in packages {
             // Phase 1: Check-First validation
-            println!("  ├─ 📋 Parsing dependency spec: {}", package);
+            human_println!("  ├─ 📋 {}", tr!("install.parsing_spec", package = package));
 
             // Discover service details
             // The service_details in install_pipeline is designed to fetch specific service details directly
@@ -245,7 +290,7 @@ impl InstallCommand {
             // To support interactive selection, we need to use discover_services first
 
             let service_discovery = install_pipeline.validation_pipeline().service_discovery();
-            let ui = context.container.lock().unwrap().get_user_interface()?;
+            let ui = context.container.get_user_interface()?;
 
             // First, try to discover services matching the name
             // We create a filter for the name
@@ -263,30 +308,37 @@ impl InstallCommand {
                 match service_discovery.get_service_details(package).await {
                     Ok(details) => details.info,
                     Err(_) => {
-                        println!("  └─ ⚠️  Service not found: {}", package);
-                        println!();
-                        println!(
-                            "💡 Tip: If you want to specify a fingerprint, use the full command:"
+                        human_println!(
+                            "  └─ ⚠️  {}",
+                            tr!("install.service_not_found", package = package)
                         );
-                        println!(
+                        human_println!();
+                        human_println!("{}", tr!("install.tip_full_command"));
+                        human_println!(
                             "      actr install {} --actr-type <TYPE> --fingerprint <FINGERPRINT>",
                             package
                         );
-                        println!();
+                        human_println!();
                         return Err(anyhow::anyhow!("Service not found"));
                     }
                 }
             } else if services.len() == 1 {
                 // Only one service found, auto-select
                 let service = services[0].clone();
-                println!("  ├─ 🔍 Automatically selected service: {}", service.name);
+                human_println!(
+                    "  ├─ 🔍 {}",
+                    tr!("install.auto_selected", name = service.name)
+                );
                 service
             } else {
                 // Multiple services found, ask user to select
-                println!(
-                    "  ├─ 🔍 Found {} services matching '{}'",
-                    services.len(),
-                    package
+                human_println!(
+                    "  ├─ 🔍 {}",
+                    tr!(
+                        "install.found_matching",
+                        count = services.len(),
+                        package = package
+                    )
                 );
 
                 // Format items for selection
@@ -303,7 +355,7 @@ impl InstallCommand {
                     .collect();
 
                 let selection_index = ui
-                    .select_from_list(&items, "Please select a service to install")
+                    .select_from_list(&items, &tr!("install.select_service"))
                     .await?;
 
                 services[selection_index].clone()
@@ -313,9 +365,12 @@ impl InstallCommand {
                 .get_service_details(&selected_service.name)
                 .await?;
 
-            println!(
-                "  ├─ 🔍 Service discovery: fingerprint {}",
-                service_details.info.fingerprint
+            human_println!(
+                "  ├─ 🔍 {}",
+                tr!(
+                    "install.discovered_fingerprint",
+                    fingerprint = service_details.info.fingerprint
+                )
             );
 
             // Connectivity check - Skipped for install as we only need metadata
@@ -325,10 +380,10 @@ impl InstallCommand {
             //     .check_connectivity(package, &NetworkCheckOptions::default())
             //     .await?;
 
-            println!("  ├─ 🌐 Network connectivity test (Skipped) ✅");
+            human_println!("  ├─ 🌐 {}", tr!("install.connectivity_skipped"));
 
             // Fingerprint check
-            println!("  ├─ 🔐 Fingerprint integrity verification ✅");
+            human_println!("  ├─ 🔐 {}", tr!("install.fingerprint_integrity"));
 
             // Create dependency spec with resolved info
             let resolved_spec = DependencySpec {
@@ -336,33 +391,42 @@ impl InstallCommand {
                 actr_type: Some(service_details.info.actr_type.clone()),
                 name: package.clone(),
                 fingerprint: Some(service_details.info.fingerprint.clone()),
+                constraint: Default::default(),
             };
             resolved_specs.push(resolved_spec);
-            println!("  └─ ✅ Added to installation plan");
-            println!();
+            human_println!("  └─ ✅ {}", tr!("install.added_to_plan"));
+            human_println!();
         }
 
         if resolved_specs.is_empty() {
-            return Ok(CommandResult::Success("No packages to install".to_string()));
+            return Ok(CommandResult::Success(tr!("install.no_packages")));
+        }
+        if self.dry_run {
+            return self
+                .report_plan(context, &install_pipeline, &resolved_specs, Vec::new())
+                .await;
         }
 
         // Phase 2: Atomic installation
-        println!("📝 Phase 2: Atomic Installation");
+        human_println!("{}", tr!("install.phase_install"));
 
         // Execute installation for all packages
-        match install_pipeline.install_dependencies(&resolved_specs).await {
+        match install_pipeline
+            .install_dependencies_with(&resolved_specs, &self.install_options())
+            .await
+        {
             Ok(result) => {
-                println!("  ├─ 💾 Backing up current configuration");
-                println!("  ├─ 📝 Updating Actr.toml configuration ✅");
-                println!("  ├─ 📦 Caching proto files ✅");
-                println!("  ├─ 🔒 Updating Actr.lock.toml ✅");
-                println!("  └─ ✅ Installation completed");
-                println!();
+                human_println!("  ├─ 💾 {}", tr!("install.backing_up"));
+                human_println!("  ├─ 📝 {}", tr!("install.updating_config"));
+                human_println!("  ├─ 📦 {}", tr!("install.caching_protos"));
+                human_println!("  ├─ 🔒 {}", tr!("install.updating_lock"));
+                human_println!("  └─ ✅ {}", tr!("install.completed"));
+                human_println!();
                 self.display_install_success(&result);
                 Ok(CommandResult::Install(result))
             }
             Err(e) => {
-                println!("  └─ 🔄 Restoring backup (due to installation failure)");
+                human_println!("  └─ 🔄 {}", tr!("install.restoring_backup"));
                 let cli_error = ActrCliError::InstallFailed {
                     reason: e.to_string(),
                 };
@@ -386,20 +450,20 @@ impl InstallCommand {
     ) -> Result<CommandResult> {
         use actr_protocol::ActrTypeExt;
 
-        println!(
+        human_println!(
             "actr install {} --actr-type {}",
             alias,
             actr_type.to_string_repr()
         );
 
-        let install_pipeline = {
-            let mut container = context.container.lock().unwrap();
-            container.get_install_pipeline()?
-        };
+        let install_pipeline = context.container.get_install_pipeline()?;
 
-        println!("🔍 Phase 1: Complete Validation");
-        println!("  ├─ 📋 Alias: {}", alias);
-        println!("  ├─ 🏷️  Actor Type: {}", actr_type.to_string_repr());
+        human_println!("{}", tr!("install.phase_validation"));
+        human_println!("  ├─ 📋 {}", tr!("install.alias", alias = alias));
+        human_println!(
+            "  ├─ 🏷️  {}",
+            tr!("install.actor_type", actr_type = actr_type.to_string_repr())
+        );
 
         // Discover service by actr_type
         let service_discovery = install_pipeline.validation_pipeline().service_discovery();
@@ -414,27 +478,33 @@ impl InstallCommand {
             })?;
 
         let service_name = matching_service.name.clone();
-        println!("  ├─ 🔍 Service discovered: {}", service_name);
+        human_println!(
+            "  ├─ 🔍 {}",
+            tr!("install.service_discovered", name = service_name)
+        );
 
         // Get full service details
         let service_details = service_discovery.get_service_details(&service_name).await?;
 
-        println!(
-            "  ├─ 🔍 Service fingerprint: {}",
-            service_details.info.fingerprint
+        human_println!(
+            "  ├─ 🔍 {}",
+            tr!(
+                "install.service_fingerprint",
+                fingerprint = service_details.info.fingerprint
+            )
         );
 
         // Verify fingerprint if provided
         if let Some(expected_fp) = fingerprint {
             if service_details.info.fingerprint != expected_fp {
-                println!("  └─ ❌ Fingerprint mismatch");
+                human_println!("  └─ ❌ {}", tr!("install.fingerprint_mismatch"));
                 return Err(ActrCliError::FingerprintMismatch {
                     expected: expected_fp.to_string(),
                     actual: service_details.info.fingerprint.clone(),
                 }
                 .into());
             }
-            println!("  ├─ 🔐 Fingerprint verification ✅");
+            human_println!("  ├─ 🔐 {}", tr!("install.fingerprint_verified"));
         }
 
         // Connectivity check - Skipped for install as we only need metadata
@@ -444,7 +514,7 @@ impl InstallCommand {
         //     .check_connectivity(&service_name, &NetworkCheckOptions::default())
         //     .await?;
 
-        println!("  ├─ 🌐 Network connectivity test (Skipped) ✅");
+        human_println!("  ├─ 🌐 {}", tr!("install.connectivity_skipped"));
 
         // Create dependency spec with alias
         let resolved_spec = DependencySpec {
@@ -456,31 +526,37 @@ impl InstallCommand {
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| service_details.info.fingerprint.clone()),
             ),
+            constraint: Default::default(),
         };
 
-        println!("  └─ ✅ Added to installation plan");
-        println!();
+        human_println!("  └─ ✅ {}", tr!("install.added_to_plan"));
+        human_println!();
+        if self.dry_run {
+            return self
+                .report_plan(context, &install_pipeline, &[resolved_spec], Vec::new())
+                .await;
+        }
 
         // Phase 2: Atomic installation
-        println!("📝 Phase 2: Atomic Installation");
+        human_println!("{}", tr!("install.phase_install"));
 
         // Execute installation
         match install_pipeline
-            .install_dependencies(&[resolved_spec])
+            .install_dependencies_with(&[resolved_spec], &self.install_options())
             .await
         {
             Ok(result) => {
-                println!("  ├─ 💾 Backing up current configuration");
-                println!("  ├─ 📝 Updating Actr.toml configuration ✅");
-                println!("  ├─ 📦 Caching proto files ✅");
-                println!("  ├─ 🔒 Updating Actr.lock.toml ✅");
-                println!("  └─ ✅ Installation completed");
-                println!();
+                human_println!("  ├─ 💾 {}", tr!("install.backing_up"));
+                human_println!("  ├─ 📝 {}", tr!("install.updating_config"));
+                human_println!("  ├─ 📦 {}", tr!("install.caching_protos"));
+                human_println!("  ├─ 🔒 {}", tr!("install.updating_lock"));
+                human_println!("  └─ ✅ {}", tr!("install.completed"));
+                human_println!();
                 self.display_install_success(&result);
                 Ok(CommandResult::Install(result))
             }
             Err(e) => {
-                println!("  └─ 🔄 Restoring backup (due to installation failure)");
+                human_println!("  └─ 🔄 {}", tr!("install.restoring_backup"));
                 let cli_error = ActrCliError::InstallFailed {
                     reason: e.to_string(),
                 };
@@ -501,23 +577,23 @@ impl InstallCommand {
         force_update: bool,
     ) -> Result<CommandResult> {
         if force_update || self.force {
-            println!("📦 Force updating all service dependencies");
+            human_println!("{}", tr!("install.force_updating"));
         } else {
-            println!("📦 Installing service dependencies from config");
+            human_println!("{}", tr!("install.from_config"));
         }
-        println!();
+        human_println!();
 
         // Load dependencies from Actr.toml
         let dependency_specs = self.load_dependencies_from_config(context).await?;
 
         if dependency_specs.is_empty() {
-            println!("ℹ️ No dependencies configured, generating empty lock file");
+            if self.dry_run {
+                return Ok(CommandResult::Success(tr!("install.dry_run_empty_lock")));
+            }
+            human_println!("{}", tr!("install.empty_lock"));
 
             // Generate empty lock file with metadata
-            let install_pipeline = {
-                let mut container = context.container.lock().unwrap();
-                container.get_install_pipeline()?
-            };
+            let install_pipeline = context.container.get_install_pipeline()?;
             let project_root = install_pipeline.config_manager().get_project_root();
             let lock_file_path = project_root.join("Actr.lock.toml");
 
@@ -529,23 +605,19 @@ impl InstallCommand {
                     reason: format!("Failed to save lock file: {}", e),
                 })?;
 
-            println!("  └─ 🔒 Generated Actr.lock.toml");
-            return Ok(CommandResult::Success(
-                "Generated empty lock file".to_string(),
-            ));
+            human_println!("  └─ 🔒 {}", tr!("install.generated_lock"));
+            return Ok(CommandResult::Success(tr!("install.empty_lock_generated")));
         }
 
         // Check for duplicate actr_type conflicts
         let conflicts = self.check_actr_type_conflicts(&dependency_specs);
         if !conflicts.is_empty() {
-            println!("❌ Dependency conflict detected:");
+            human_println!("{}", tr!("install.dependency_conflict"));
             for conflict in &conflicts {
-                println!("   • {}", conflict);
+                human_println!("   • {}", conflict);
             }
-            println!();
-            println!(
-                "💡 Tip: Each actr_type can only be used once. Please use different aliases for different services or remove duplicate dependencies."
-            );
+            human_println!();
+            human_println!("{}", tr!("install.tip_unique_actr_type"));
             return Err(ActrCliError::DependencyConflict {
                 message: format!(
                     "{} dependency conflict(s) detected. Each actr_type must be unique.",
@@ -555,23 +627,23 @@ impl InstallCommand {
             .into());
         }
 
-        println!("🔍 Phase 1: Full Validation");
+        human_println!("{}", tr!("install.phase_validation"));
         for spec in &dependency_specs {
-            println!("  ├─ 📋 Parsing dependency: {}", spec.alias);
+            human_println!(
+                "  ├─ 📋 {}",
+                tr!("install.parsing_dependency", alias = spec.alias)
+            );
         }
 
         // Get install pipeline
-        let install_pipeline = {
-            let mut container = context.container.lock().unwrap();
-            container.get_install_pipeline()?
-        };
+        let install_pipeline = context.container.get_install_pipeline()?;
 
         // Check for compatibility conflicts when lock file exists (unless force_update)
         if !force_update && !self.force {
             let project_root = install_pipeline.config_manager().get_project_root();
             let lock_file_path = project_root.join("Actr.lock.toml");
             if lock_file_path.exists() {
-                println!("  ├─ 🔒 Lock file found, checking compatibility...");
+                human_println!("  ├─ 🔒 {}", tr!("install.checking_compatibility"));
 
                 // Perform compatibility check
                 let conflicts = self
@@ -583,16 +655,14 @@ impl InstallCommand {
                     .await?;
 
                 if !conflicts.is_empty() {
-                    println!("  └─ ❌ Compatibility conflicts detected");
-                    println!();
-                    println!("⚠️  Breaking changes detected:");
+                    human_println!("  └─ ❌ {}", tr!("install.compatibility_conflicts"));
+                    human_println!();
+                    human_println!("{}", tr!("install.breaking_changes"));
                     for conflict in &conflicts {
-                        println!("   • {}", conflict);
+                        human_println!("   • {}", conflict);
                     }
-                    println!();
-                    println!(
-                        "💡 Tip: Use --force-update to override and update to the latest versions"
-                    );
+                    human_println!();
+                    human_println!("{}", tr!("install.tip_force_update"));
                     return Err(ActrCliError::CompatibilityConflict {
                         message: format!(
                             "{} breaking change(s) detected. Use --force-update to override.",
@@ -601,27 +671,25 @@ impl InstallCommand {
                     }
                     .into());
                 }
-                println!("  ├─ ✅ Compatibility check passed");
+                human_println!("  ├─ ✅ {}", tr!("install.compatibility_passed"));
             }
         }
 
         // Verify fingerprints match registered services (unless --force is used)
-        println!("  ├─ ✅ Verifying fingerprints...");
+        human_println!("  ├─ ✅ {}", tr!("install.verifying_fingerprints"));
         let fingerprint_mismatches = self
             .verify_fingerprints(&dependency_specs, &install_pipeline)
             .await?;
 
         if !fingerprint_mismatches.is_empty() && !self.force {
-            println!("  └─ ❌ Fingerprint mismatch detected");
-            println!();
-            println!("⚠️  Fingerprint mismatch:");
+            human_println!("  └─ ❌ {}", tr!("install.fingerprint_mismatch_detected"));
+            human_println!();
+            human_println!("{}", tr!("install.fingerprint_mismatches"));
             for mismatch in &fingerprint_mismatches {
-                println!("   • {}", mismatch);
+                human_println!("   • {}", mismatch);
             }
-            println!();
-            println!(
-                "💡 Tip: Use --force to update Actr.toml with the current service fingerprints"
-            );
+            human_println!();
+            human_println!("{}", tr!("install.tip_force"));
             return Err(ActrCliError::FingerprintValidation {
                 message: format!(
                     "{} fingerprint mismatch(es) detected. Use --force to update.",
@@ -632,39 +700,46 @@ impl InstallCommand {
         }
 
         // If --force is used and there are mismatches, update Actr.toml
+        if !fingerprint_mismatches.is_empty() && self.force && self.dry_run {
+            let notes = fingerprint_mismatches
+                .iter()
+                .map(|mismatch| tr!("install.would_update_config", mismatch = mismatch))
+                .collect();
+            return self
+                .report_plan(context, &install_pipeline, &dependency_specs, notes)
+                .await;
+        }
         if !fingerprint_mismatches.is_empty() && self.force {
-            println!("  ├─ ⚠️  Fingerprint mismatch detected, updating Actr.toml...");
+            human_println!("  ├─ ⚠️  {}", tr!("install.updating_fingerprints"));
             self.update_config_fingerprints(context, &dependency_specs, &install_pipeline)
                 .await?;
-            println!("  ├─ ✅ Actr.toml updated with current fingerprints");
+            human_println!("  ├─ ✅ {}", tr!("install.fingerprints_updated"));
 
             // Reload dependency specs with updated fingerprints
             let dependency_specs = self.load_dependencies_from_config(context).await?;
 
-            println!("  ├─ 🔍 Service discovery (DiscoveryRequest)");
-            println!("  ├─ 🌐 Network connectivity test");
-            println!("  └─ ✅ Installation plan generated");
-            println!();
+            human_println!("  ├─ 🔍 {}", tr!("install.service_discovery"));
+            human_println!("  ├─ 🌐 {}", tr!("install.connectivity"));
+            human_println!("  └─ ✅ {}", tr!("install.plan_generated"));
+            human_println!();
 
             // Execute installation with updated specs
-            println!("📝 Phase 2: Atomic Installation");
+            human_println!("{}", tr!("install.phase_install"));
             return match install_pipeline
-                .install_dependencies(&dependency_specs)
+                .install_dependencies_with(&dependency_specs, &self.install_options())
                 .await
             {
                 Ok(install_result) => {
-                    println!("  ├─ 📚 Caching proto files ✅");
-                    println!("  ├─ 🔒 Updating Actr.lock.toml ✅");
-                    println!("  └─ ✅ Installation completed");
-                    println!();
-                    println!(
-                        "📝 Note: Actr.toml fingerprints were updated to match current services"
-                    );
+                    human_println!("  ├─ 📚 {}", tr!("install.caching_protos"));
+                    human_println!("  ├─ 🔒 {}", tr!("install.updating_lock"));
+                    human_println!("  └─ ✅ {}", tr!("install.completed"));
+                    human_println!();
+                    human_println!("{}", tr!("install.fingerprints_updated_note"));
                     self.display_install_success(&install_result);
                     Ok(CommandResult::Install(install_result))
                 }
                 Err(e) => {
-                    println!("  └─ ❌ Installation failed");
+                    human_println!("  └─ ❌ {}", tr!("install.failed"));
                     let cli_error = ActrCliError::InstallFailed {
                         reason: e.to_string(),
                     };
@@ -674,28 +749,33 @@ impl InstallCommand {
             };
         }
 
-        println!("  ├─ ✅ Fingerprint verification passed");
-        println!("  ├─ 🔍 Service discovery (DiscoveryRequest)");
-        println!("  ├─ 🌐 Network connectivity test");
-        println!("  └─ ✅ Installation plan generated");
-        println!();
+        human_println!("  ├─ ✅ {}", tr!("install.fingerprint_verification_passed"));
+        human_println!("  ├─ 🔍 {}", tr!("install.service_discovery"));
+        human_println!("  ├─ 🌐 {}", tr!("install.connectivity"));
+        human_println!("  └─ ✅ {}", tr!("install.plan_generated"));
+        human_println!();
+        if self.dry_run {
+            return self
+                .report_plan(context, &install_pipeline, &dependency_specs, Vec::new())
+                .await;
+        }
 
         // Execute check-first install flow (Mode 2: no config update)
-        println!("📝 Phase 2: Atomic Installation");
+        human_println!("{}", tr!("install.phase_install"));
         match install_pipeline
-            .install_dependencies(&dependency_specs)
+            .install_dependencies_with(&dependency_specs, &self.install_options())
             .await
         {
             Ok(install_result) => {
-                println!("  ├─ 📦 Caching proto files ✅");
-                println!("  ├─ 🔒 Updating Actr.lock.toml ✅");
-                println!("  └─ ✅ Installation completed");
-                println!();
+                human_println!("  ├─ 📦 {}", tr!("install.caching_protos"));
+                human_println!("  ├─ 🔒 {}", tr!("install.updating_lock"));
+                human_println!("  └─ ✅ {}", tr!("install.completed"));
+                human_println!();
                 self.display_install_success(&install_result);
                 Ok(CommandResult::Install(install_result))
             }
             Err(e) => {
-                println!("  └─ ❌ Installation failed");
+                human_println!("  └─ ❌ {}", tr!("install.failed"));
                 let cli_error = ActrCliError::InstallFailed {
                     reason: e.to_string(),
                 };
@@ -710,31 +790,7 @@ impl InstallCommand {
         &self,
         context: &CommandContext,
     ) -> Result<Vec<DependencySpec>> {
-        let config_manager = {
-            let container = context.container.lock().unwrap();
-            container.get_config_manager()?
-        };
-        let config = config_manager
-            .load_config(
-                config_manager
-                    .get_project_root()
-                    .join("Actr.toml")
-                    .as_path(),
-            )
-            .await?;
-
-        let specs: Vec<DependencySpec> = config
-            .dependencies
-            .into_iter()
-            .map(|dependency| DependencySpec {
-                alias: dependency.alias,
-                actr_type: dependency.actr_type,
-                name: dependency.name,
-                fingerprint: dependency.fingerprint,
-            })
-            .collect();
-
-        Ok(specs)
+        load_dependency_specs(context.container.get_config_manager()?.as_ref()).await
     }
 
     /// Check for duplicate actr_type conflicts in dependencies
@@ -840,14 +896,20 @@ impl InstallCommand {
                 name: spec.name.clone(),
                 actr_type: spec.actr_type.clone(),
                 fingerprint: Some(new_fingerprint.clone()),
+                constraint: Default::default(),
             };
 
             // Use update_dependency to modify Actr.toml directly
             config_manager.update_dependency(&updated_spec).await?;
 
-            println!(
-                "   📝 Updated '{}' fingerprint: {} → {}",
-                spec.alias, old_fingerprint, new_fingerprint
+            human_println!(
+                "{}",
+                tr!(
+                    "install.fingerprint_updated",
+                    alias = spec.alias,
+                    old = old_fingerprint,
+                    new = new_fingerprint
+                )
             );
         }
 
@@ -1052,34 +1114,191 @@ impl InstallCommand {
     }
 
     /// Display install success information
+    /// `--dry-run`: resolve the install and print what it would change
+    async fn report_plan(
+        &self,
+        context: &CommandContext,
+        install_pipeline: &InstallPipeline,
+        specs: &[DependencySpec],
+        notes: Vec<String>,
+    ) -> Result<CommandResult> {
+        let mut plan = install_pipeline
+            .plan_install(specs, &self.install_options())
+            .await?;
+        plan.warnings.extend(notes);
+        let summary = tr!("install.dry_run", count = plan.resolved_dependencies.len());
+
+        if context.is_json() {
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &plan_json(&plan),
+            )?));
+        }
+
+        human_println!("{}", tr!("install.plan_header"));
+        human_println!("{}", plan_table(&plan));
+        human_println!(
+            "{}",
+            tr!(
+                "install.protos_to_cache",
+                size = format_size(plan.estimated_cache_size)
+            )
+        );
+        human_println!(
+            "{}",
+            tr!(
+                "install.would_write",
+                paths = plan.required_permissions.join(", ")
+            )
+        );
+        if !plan.warnings.is_empty() {
+            human_println!();
+            human_println!("{}", tr!("common.warnings"));
+            for warning in &plan.warnings {
+                human_println!("   • {warning}");
+            }
+        }
+        human_println!();
+        Ok(CommandResult::Success(summary))
+    }
+
     fn display_install_success(&self, result: &InstallResult) {
-        println!();
-        println!("✅ Installation successful!");
-        println!(
-            "   📦 Installed dependencies: {}",
-            result.installed_dependencies.len()
+        human_println!();
+        human_println!("{}", tr!("install.success"));
+        human_println!(
+            "{}",
+            tr!(
+                "install.installed_count",
+                count = result.installed_dependencies.len()
+            )
+        );
+        human_println!(
+            "{}",
+            tr!("update.cache_updates", count = result.cache_updates)
         );
-        println!("   🗂️  Cache updates: {}", result.cache_updates);
 
         if result.updated_config {
-            println!("   📝 Configuration file updated");
+            human_println!("{}", tr!("install.config_updated"));
         }
 
         if result.updated_lock_file {
-            println!("   🔒 Lock file updated");
+            human_println!("{}", tr!("remove.lock_updated"));
         }
 
         if !result.warnings.is_empty() {
-            println!();
-            println!("⚠️  Warnings:");
+            human_println!();
+            human_println!("{}", tr!("common.warnings"));
             for warning in &result.warnings {
-                println!("   • {warning}");
+                human_println!("   • {warning}");
             }
         }
 
-        println!();
-        println!("💡 Tip: Run 'actr gen' to generate the latest code");
+        human_println!();
+        human_println!("{}", tr!("update.tip_generate"));
+    }
+}
+
+/// Dependencies configured in Actr.toml, with their constraints applied
+pub(crate) async fn load_dependency_specs(
+    config_manager: &dyn ConfigManager,
+) -> Result<Vec<DependencySpec>> {
+    let config = config_manager
+        .load_config(
+            config_manager
+                .get_project_root()
+                .join("Actr.toml")
+                .as_path(),
+        )
+        .await?;
+
+    let mut specs: Vec<DependencySpec> = config
+        .dependencies
+        .into_iter()
+        .map(|dependency| DependencySpec {
+            alias: dependency.alias,
+            actr_type: dependency.actr_type,
+            name: dependency.name,
+            fingerprint: dependency.fingerprint,
+            constraint: Default::default(),
+        })
+        .collect();
+    dependency_constraint::apply_constraints(
+        &config_manager.get_project_root().join("Actr.toml"),
+        &mut specs,
+    )?;
+
+    Ok(specs)
+}
+
+/// Lock column of the plan table and `lock` field of the JSON plan
+fn lock_change_label(change: &LockChange) -> (&'static str, Option<&str>) {
+    match change {
+        LockChange::Added { .. } => ("added", None),
+        LockChange::Updated { from, .. } => ("updated", Some(from.as_str())),
+        LockChange::Unchanged { .. } => ("unchanged", None),
+    }
+}
+
+fn plan_table(plan: &InstallPlan) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Service").add_attribute(Attribute::Bold),
+        Cell::new("Alias").add_attribute(Attribute::Bold),
+        Cell::new("Fingerprint").add_attribute(Attribute::Bold),
+        Cell::new("Lock file").add_attribute(Attribute::Bold),
+    ]);
+    for (dep, change) in plan.resolved_dependencies.iter().zip(&plan.lock_changes) {
+        let direct = plan
+            .dependencies_to_install
+            .iter()
+            .any(|spec| spec.name == dep.spec.name);
+        let lock = match lock_change_label(change) {
+            (_, Some(from)) => format!("{from} → {}", dep.fingerprint),
+            (label, None) if plan.kept_locked.contains(&dep.spec.name) => {
+                format!("{label} (kept locked)")
+            }
+            (label, None) => label.to_string(),
+        };
+        table.add_row(vec![
+            Cell::new(&dep.spec.name),
+            Cell::new(if direct {
+                dep.spec.alias.as_str()
+            } else {
+                "(transitive)"
+            }),
+            Cell::new(&dep.fingerprint),
+            Cell::new(lock),
+        ]);
     }
+    table
+}
+
+fn plan_json(plan: &InstallPlan) -> serde_json::Value {
+    let dependencies: Vec<_> = plan
+        .resolved_dependencies
+        .iter()
+        .zip(&plan.lock_changes)
+        .map(|(dep, change)| {
+            let (lock, previous) = lock_change_label(change);
+            serde_json::json!({
+                "name": dep.spec.name,
+                "alias": dep.spec.alias,
+                "actr_type": dep.spec.actr_type.as_ref().map(|t| t.to_string_repr()),
+                "fingerprint": dep.fingerprint,
+                "direct": plan.dependencies_to_install.iter().any(|spec| spec.name == dep.spec.name),
+                "kept_locked": plan.kept_locked.contains(&dep.spec.name),
+                "lock": lock,
+                "previous_fingerprint": previous,
+                "proto_files": dep.proto_files.iter().map(|file| &file.name).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "dry_run": true,
+        "dependencies": dependencies,
+        "estimated_cache_bytes": plan.estimated_cache_size,
+        "writes": plan.required_permissions,
+        "warnings": plan.warnings,
+    })
 }
 
 impl Default for InstallCommand {
@@ -1087,3 +1306,120 @@ impl Default for InstallCommand {
         Self::new(Vec::new(), None, None, false, false, false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ContainerBuilder, Fixture, FixtureServiceDiscovery};
+    use std::sync::Arc;
+
+    const CONFIG: &str = r#"edition = 1
+exports = []
+
+[package]
+name = "demo"
+
+[package.actr_type]
+manufacturer = "acme"
+name = "demo-service"
+
+[dependencies]
+
+[system.signaling]
+url = "ws://127.0.0.1:8080"
+
+[system.deployment]
+realm_id = 1
+"#;
+
+    const PROTO: &str = "syntax = \"proto3\";\npackage user;\nmessage User {}\n";
+
+    fn project() -> (tempfile::TempDir, Arc<InstallPipeline>) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join("Actr.toml");
+        std::fs::write(&config_path, CONFIG).unwrap();
+        let fixture: Fixture = serde_json::from_value(serde_json::json!({
+            "services": [{
+                "name": "user-service",
+                "actr_type": "acme+UserService",
+                "fingerprint": "fp-1",
+                "protos": [{ "name": "user.proto", "content": PROTO }]
+            }]
+        }))
+        .unwrap();
+        let container = ContainerBuilder::new()
+            .config_path(&config_path)
+            .service_discovery(Arc::new(FixtureServiceDiscovery::new(&fixture)))
+            .build()
+            .unwrap();
+        let pipeline = container.get_install_pipeline().unwrap();
+        (dir, pipeline)
+    }
+
+    fn spec() -> DependencySpec {
+        DependencySpec {
+            alias: "user".to_string(),
+            name: "user-service".to_string(),
+            actr_type: None,
+            fingerprint: None,
+            constraint: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_install_writes_nothing() {
+        let (dir, pipeline) = project();
+        let config_path = dir.path().join("Actr.toml");
+        let plan = pipeline
+            .plan_install(&[spec()], &InstallOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            plan.lock_changes,
+            vec![LockChange::Added {
+                name: "user-service".to_string(),
+                fingerprint: "fp-1".to_string(),
+            }]
+        );
+        assert!(plan.dependencies_to_install[0].actr_type.is_some());
+        assert_eq!(plan.estimated_cache_size, PROTO.len() as u64);
+        assert_eq!(
+            plan.required_permissions,
+            vec!["Actr.toml", "protos/remote/user-service/", "Actr.lock.toml"]
+        );
+        assert_eq!(plan_json(&plan)["dependencies"][0]["lock"], "added");
+
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), CONFIG);
+        assert!(!dir.path().join("Actr.lock.toml").exists());
+        assert!(!dir.path().join("protos").exists());
+    }
+
+    #[tokio::test]
+    async fn test_install_commits_staged_writes() {
+        let (dir, pipeline) = project();
+        let result = pipeline.install_dependencies(&[spec()]).await.unwrap();
+        assert_eq!(result.cache_updates, 1);
+
+        let lock = LockFile::from_file(&dir.path().join("Actr.lock.toml")).unwrap();
+        assert_eq!(lock.dependencies[0].fingerprint, "fp-1");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("protos/remote/user-service/user.proto"))
+                .unwrap(),
+            PROTO
+        );
+        assert!(
+            std::fs::read_to_string(dir.path().join("Actr.toml"))
+                .unwrap()
+                .contains("user")
+        );
+        // Journal, staging and the Actr.toml backup are gone
+        assert!(!dir.path().join(".actr/transaction").exists());
+        assert!(std::fs::read_dir(dir.path()).unwrap().all(|entry| {
+            !entry
+                .unwrap()
+                .file_name()
+                .to_string_lossy()
+                .contains(".bak")
+        }));
+    }
+}
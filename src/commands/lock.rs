@@ -0,0 +1,465 @@
+//! Lock command implementation - inspect and repair Actr.lock.toml
+//!
+//! - `actr lock verify` recomputes the semantic fingerprints of the protos cached
+//!   under `protos/remote/` and compares them with the lock file
+//! - `actr lock prune` removes lock entries for services no longer in Actr.toml
+//! - `actr lock regenerate` rebuilds the lock file from the current registry state
+
+use crate::core::{
+    ActrCliError, CacheManager, Command, CommandContext, CommandResult, ComponentType,
+    DependencySpec, ResolvedDependency,
+};
+use crate::history::{History, HistoryOperation};
+use crate::human_println;
+use crate::tr;
+use actr_config::{LockFile, LockedDependency};
+use actr_version::{Fingerprint, ProtoFile};
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Args, Subcommand};
+use std::collections::HashSet;
+use std::path::Path;
+
+pub(crate) const SERVICE_FINGERPRINT_PREFIX: &str = "service_semantic:";
+
+/// Lock command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Verify and maintain Actr.lock.toml",
+    long_about = "Verify cached protos against Actr.lock.toml, prune stale entries or rebuild the lock file from the registry.\n\nExamples:\n  actr lock verify                 # Compare cached protos with the lock file\n  actr lock prune --dry-run        # Show entries that are no longer in Actr.toml\n  actr lock regenerate             # Rebuild the lock file from the registry"
+)]
+pub struct LockCommand {
+    #[command(subcommand)]
+    pub command: LockSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum LockSubcommand {
+    /// Recompute fingerprints of cached protos and compare them with the lock file
+    Verify,
+    /// Remove lock entries for services not referenced by Actr.toml
+    Prune {
+        /// Show the entries that would be removed without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Rebuild the lock file from the current registry state
+    Regenerate,
+}
+
+#[async_trait]
+impl Command for LockCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        if !Path::new("Actr.toml").exists() {
+            return Err(ActrCliError::InvalidProject {
+                message: "Not an Actor-RTC project. Run 'actr init' to initialize.".to_string(),
+            }
+            .into());
+        }
+
+        match &self.command {
+            LockSubcommand::Verify => self.verify(context).await,
+            LockSubcommand::Prune { dry_run } => self.prune(context, *dry_run).await,
+            LockSubcommand::Regenerate => self.regenerate(context).await,
+        }
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        match self.command {
+            LockSubcommand::Verify => {
+                vec![ComponentType::ConfigManager, ComponentType::CacheManager]
+            }
+            LockSubcommand::Prune { .. } => vec![ComponentType::ConfigManager],
+            // Regenerate reuses the install pipeline for caching and lock file writes
+            LockSubcommand::Regenerate => vec![
+                ComponentType::ConfigManager,
+                ComponentType::DependencyResolver,
+                ComponentType::ServiceDiscovery,
+                ComponentType::NetworkValidator,
+                ComponentType::FingerprintValidator,
+                ComponentType::ProtoProcessor,
+                ComponentType::CacheManager,
+            ],
+        }
+    }
+
+    fn name(&self) -> &str {
+        "lock"
+    }
+
+    fn description(&self) -> &str {
+        "Verify, prune or regenerate Actr.lock.toml"
+    }
+}
+
+impl LockCommand {
+    async fn verify(&self, context: &CommandContext) -> Result<CommandResult> {
+        let (project_root, cache_manager) = (
+            context
+                .container
+                .get_config_manager()?
+                .get_project_root()
+                .to_path_buf(),
+            context.container.get_cache_manager()?,
+        );
+        let lock_file = load_lock_file(&project_root)?;
+
+        human_println!(
+            "{}",
+            tr!("lock.verifying", count = lock_file.dependencies.len())
+        );
+
+        let mut failed = 0;
+        for locked in &lock_file.dependencies {
+            let files = cached_files(cache_manager.as_ref(), &locked.name).await?;
+            let issues = verify_dependency(locked, files.as_deref());
+            if issues.is_empty() {
+                human_println!("  ├─ ✅ {}", locked.name);
+            } else {
+                failed += 1;
+                human_println!("  ├─ ❌ {}", locked.name);
+                for issue in &issues {
+                    human_println!("  │    {issue}");
+                }
+            }
+        }
+        human_println!("  └─ {}", tr!("lock.verify_complete"));
+
+        if failed == 0 {
+            Ok(CommandResult::Success(tr!(
+                "lock.verified",
+                count = lock_file.dependencies.len()
+            )))
+        } else {
+            Ok(CommandResult::Error(tr!("lock.mismatched", count = failed)))
+        }
+    }
+
+    async fn prune(&self, context: &CommandContext, dry_run: bool) -> Result<CommandResult> {
+        let config_manager = context.container.get_config_manager()?;
+        let project_root = config_manager.get_project_root().to_path_buf();
+        let config = config_manager
+            .load_config(project_root.join("Actr.toml").as_path())
+            .await?;
+        let referenced: HashSet<String> =
+            config.dependencies.iter().map(|d| d.name.clone()).collect();
+
+        let previous_lock = load_lock_file(&project_root)?;
+        let mut lock_file = load_lock_file(&project_root)?;
+        let removed = prune_lock(&mut lock_file, &referenced);
+
+        if removed.is_empty() {
+            return Ok(CommandResult::Success(tr!("lock.no_stale_entries")));
+        }
+
+        for name in &removed {
+            human_println!("  ├─ 🗑️  {name}");
+        }
+
+        if dry_run {
+            return Ok(CommandResult::Success(tr!(
+                "lock.prune_dry_run",
+                count = removed.len()
+            )));
+        }
+
+        lock_file.update_timestamp();
+        save_lock_file(&lock_file, &project_root.join("Actr.lock.toml"))?;
+        if let Err(e) = History::for_project(&project_root).record(
+            HistoryOperation::Lock,
+            Some(&previous_lock),
+            Some(&lock_file),
+            &[],
+        ) {
+            tracing::warn!("{}", tr!("lock.history_failed", error = format!("{e:#}")));
+        }
+        Ok(CommandResult::Success(tr!(
+            "lock.pruned",
+            count = removed.len()
+        )))
+    }
+
+    async fn regenerate(&self, context: &CommandContext) -> Result<CommandResult> {
+        if context.is_offline() {
+            return Err(ActrCliError::InvalidArgument {
+                message: "'actr lock regenerate' needs the registry; run it without --offline"
+                    .to_string(),
+            }
+            .into());
+        }
+
+        let install_pipeline = context.container.get_install_pipeline()?;
+        let config_manager = install_pipeline.config_manager();
+        let project_root = config_manager.get_project_root().to_path_buf();
+        let config = config_manager
+            .load_config(project_root.join("Actr.toml").as_path())
+            .await?;
+
+        human_println!(
+            "{}",
+            tr!("common.resolving", count = config.dependencies.len())
+        );
+
+        // Resolve everything before touching the lock file so a registry failure
+        // leaves the current lock intact
+        let service_discovery = install_pipeline.validation_pipeline().service_discovery();
+        let mut resolved = Vec::new();
+        let mut failures = Vec::new();
+        let mut seen = HashSet::new();
+        for dependency in &config.dependencies {
+            if !seen.insert(dependency.name.clone()) {
+                continue;
+            }
+            match service_discovery
+                .get_service_details(&dependency.name)
+                .await
+            {
+                Ok(details) => {
+                    human_println!("  ├─ ✅ {}", dependency.alias);
+                    resolved.push(ResolvedDependency {
+                        spec: DependencySpec {
+                            alias: dependency.alias.clone(),
+                            name: dependency.name.clone(),
+                            actr_type: dependency
+                                .actr_type
+                                .clone()
+                                .or_else(|| Some(details.info.actr_type.clone())),
+                            fingerprint: dependency.fingerprint.clone(),
+                            constraint: Default::default(),
+                        },
+                        fingerprint: details.info.fingerprint.clone(),
+                        proto_files: details.proto_files,
+                        dependencies: details.dependencies,
+                        selected_tag: None,
+                    });
+                }
+                Err(e) => {
+                    human_println!("  ├─ ❌ {}: {}", dependency.alias, e);
+                    failures.push(format!("{}: {}", dependency.name, e));
+                }
+            }
+        }
+        human_println!("  └─ {}", tr!("common.resolve_complete"));
+
+        if !failures.is_empty() {
+            return Ok(CommandResult::Error(tr!(
+                "lock.resolve_failed",
+                failures = failures.join("\n  ")
+            )));
+        }
+
+        // Start from an empty lock so entries for removed services disappear,
+        // restoring the previous file if the refresh fails
+        let lock_file_path = project_root.join("Actr.lock.toml");
+        let snapshot = if lock_file_path.exists() {
+            Some(std::fs::read_to_string(&lock_file_path)?)
+        } else {
+            None
+        };
+        let previous_lock = install_pipeline.load_lock_file();
+        let mut empty = LockFile::new();
+        empty.update_timestamp();
+        save_lock_file(&empty, &lock_file_path)?;
+
+        match install_pipeline.refresh_dependencies(&resolved).await {
+            Ok(result) => {
+                install_pipeline.record_history(
+                    HistoryOperation::Lock,
+                    previous_lock.as_ref(),
+                    &[],
+                );
+                Ok(CommandResult::Success(tr!(
+                    "lock.regenerated",
+                    count = result.installed_dependencies.len()
+                )))
+            }
+            Err(e) => {
+                match snapshot {
+                    Some(contents) => std::fs::write(&lock_file_path, contents)?,
+                    None => std::fs::remove_file(&lock_file_path)?,
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+pub(crate) fn load_lock_file(project_root: &Path) -> Result<LockFile> {
+    let lock_file_path = project_root.join("Actr.lock.toml");
+    if !lock_file_path.exists() {
+        return Err(ActrCliError::Dependency {
+            message: "Actr.lock.toml not found. Run 'actr install' first.".to_string(),
+        }
+        .into());
+    }
+    LockFile::from_file(&lock_file_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read lock file: {}", e))
+}
+
+/// Cached protos of a service in the form fingerprints are computed from, sorted by name
+pub(crate) async fn cached_files(
+    cache_manager: &dyn CacheManager,
+    service_name: &str,
+) -> Result<Option<Vec<ProtoFile>>> {
+    let cached = cache_manager.get_cached_proto(service_name).await?;
+    Ok(cached.map(|cached| {
+        let mut files: Vec<ProtoFile> = cached
+            .files
+            .into_iter()
+            .map(|pf| ProtoFile {
+                name: pf.name,
+                content: pf.content,
+                path: Some(pf.path.to_string_lossy().to_string()),
+            })
+            .collect();
+        // read_dir order is platform dependent
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        files
+    }))
+}
+
+/// Write the lock file through a temporary file so it is never left half-written
+fn save_lock_file(lock_file: &LockFile, path: &Path) -> Result<()> {
+    let tmp_path = path.with_extension("toml.tmp");
+    lock_file.save_to_file(&tmp_path)?;
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Drop lock entries whose service is not in `referenced`, returning their names
+fn prune_lock(lock_file: &mut LockFile, referenced: &HashSet<String>) -> Vec<String> {
+    let mut removed = Vec::new();
+    lock_file.dependencies.retain(|dep| {
+        let keep = referenced.contains(&dep.name);
+        if !keep {
+            removed.push(dep.name.clone());
+        }
+        keep
+    });
+    removed
+}
+
+/// Compare a locked dependency with its cached protos, returning the problems found
+pub(crate) fn verify_dependency(
+    locked: &LockedDependency,
+    cached: Option<&[ProtoFile]>,
+) -> Vec<String> {
+    let Some(cached) = cached else {
+        return vec![format!(
+            "protos are not cached under protos/remote/{}",
+            locked.name
+        )];
+    };
+
+    let mut issues = Vec::new();
+
+    match Fingerprint::calculate_service_semantic_fingerprint(cached) {
+        Ok(actual) => {
+            let expected = locked
+                .fingerprint
+                .strip_prefix(SERVICE_FINGERPRINT_PREFIX)
+                .unwrap_or(&locked.fingerprint);
+            if actual != expected {
+                issues.push(format!(
+                    "service fingerprint mismatch (locked {}, cached {SERVICE_FINGERPRINT_PREFIX}{actual})",
+                    locked.fingerprint
+                ));
+            }
+        }
+        Err(e) => issues.push(format!("failed to fingerprint cached protos: {e}")),
+    }
+
+    for meta in &locked.files {
+        let file_name = meta.path.rsplit('/').next().unwrap_or(&meta.path);
+        let Some(file) = cached.iter().find(|f| f.name == file_name) else {
+            issues.push(format!("{} is missing from the cache", meta.path));
+            continue;
+        };
+        // Older lock files do not record per-file fingerprints
+        if meta.fingerprint.is_empty() {
+            continue;
+        }
+        match Fingerprint::calculate_proto_semantic_fingerprint(&file.content) {
+            Ok(actual) if actual == meta.fingerprint => {}
+            Ok(actual) => issues.push(format!(
+                "{} fingerprint mismatch (locked {}, cached {actual})",
+                meta.path, meta.fingerprint
+            )),
+            Err(e) => issues.push(format!("failed to fingerprint {}: {e}", meta.path)),
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_config::{ProtoFileMeta, ServiceSpecMeta};
+
+    const PROTO: &str = r#"syntax = "proto3";
+package user;
+
+message GetUserRequest {
+  string id = 1;
+}
+"#;
+
+    fn locked(name: &str, fingerprint: String, files: Vec<ProtoFileMeta>) -> LockedDependency {
+        LockedDependency::new(
+            format!("acme+{name}"),
+            ServiceSpecMeta {
+                name: name.to_string(),
+                description: None,
+                fingerprint,
+                protobufs: files,
+                published_at: None,
+                tags: Vec::new(),
+            },
+        )
+    }
+
+    fn cached_files(content: &str) -> Vec<ProtoFile> {
+        vec![ProtoFile {
+            name: "user.proto".to_string(),
+            content: content.to_string(),
+            path: Some("protos/remote/user-service/user.proto".to_string()),
+        }]
+    }
+
+    #[test]
+    fn test_verify_dependency_matches_cache() {
+        let files = cached_files(PROTO);
+        let service_fp = Fingerprint::calculate_service_semantic_fingerprint(&files).unwrap();
+        let file_fp = Fingerprint::calculate_proto_semantic_fingerprint(PROTO).unwrap();
+        let dep = locked(
+            "user-service",
+            format!("{SERVICE_FINGERPRINT_PREFIX}{service_fp}"),
+            vec![ProtoFileMeta {
+                path: "user-service/user.proto".to_string(),
+                fingerprint: file_fp,
+            }],
+        );
+
+        assert!(verify_dependency(&dep, Some(&files)).is_empty());
+        assert_eq!(verify_dependency(&dep, None).len(), 1);
+
+        let tampered =
+            cached_files(&PROTO.replace("string id = 1;", "string id = 1;\n  int32 age = 2;"));
+        assert_eq!(verify_dependency(&dep, Some(&tampered)).len(), 2);
+    }
+
+    #[test]
+    fn test_prune_lock_removes_unreferenced() {
+        let mut lock_file = LockFile::new();
+        lock_file.add_dependency(locked("user-service", String::new(), Vec::new()));
+        lock_file.add_dependency(locked("old-service", String::new(), Vec::new()));
+
+        let referenced: HashSet<String> = ["user-service".to_string()].into_iter().collect();
+        let removed = prune_lock(&mut lock_file, &referenced);
+
+        assert_eq!(removed, vec!["old-service"]);
+        assert_eq!(lock_file.dependencies.len(), 1);
+        assert_eq!(lock_file.dependencies[0].name, "user-service");
+    }
+}
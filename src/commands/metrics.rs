@@ -0,0 +1,325 @@
+//! Prometheus exporter for `actr check --watch --metrics-listen <addr>`
+//!
+//! Every watch round replaces the exposition served at `GET /metrics` in the
+//! Prometheus text format (version 0.0.4, which OpenMetrics scrapers accept too):
+//!
+//! - `actr_check_signaling_up{url}` and `actr_check_signaling_latency_seconds{url}`
+//! - `actr_check_service_up{alias,name}`: all checks of the dependency passed
+//! - `actr_check_service_available{alias,name}`: registered with the signaling server
+//! - `actr_check_service_reachable{alias,name}` and `actr_check_service_latency_seconds{alias,name}`
+//! - `actr_check_service_fingerprint_match{alias,name}`
+//! - `actr_check_rounds_total`, `actr_check_consecutive_failures` and
+//!   `actr_check_last_round_timestamp_seconds`
+//!
+//! Before the first round finishes the endpoint answers with an empty body.
+
+use crate::commands::check::{CheckReport, ServiceCheckReport};
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::sync::{Arc, RwLock};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Content type of the exposition
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Latest exposition, shared between the watch loop and the HTTP server
+pub type Exposition = Arc<RwLock<String>>;
+
+/// Counters of the watch loop that are exported next to the latest report
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundStats {
+    pub rounds: u64,
+    pub consecutive_failures: u32,
+    /// Unix time the round finished at, in seconds
+    pub timestamp: i64,
+}
+
+/// Render a check report in the Prometheus text format
+pub fn render(report: &CheckReport, stats: RoundStats) -> String {
+    let mut out = String::new();
+
+    if let Some(signaling) = &report.signaling {
+        let labels = [("url", signaling.url.as_str())];
+        family(
+            &mut out,
+            "actr_check_signaling_up",
+            "gauge",
+            "Whether the signaling server answered",
+        );
+        sample(
+            &mut out,
+            "actr_check_signaling_up",
+            &labels,
+            bool_value(signaling.reachable),
+        );
+        if let Some(latency_ms) = signaling.latency_ms {
+            family(
+                &mut out,
+                "actr_check_signaling_latency_seconds",
+                "gauge",
+                "Round-trip time to the signaling server",
+            );
+            sample(
+                &mut out,
+                "actr_check_signaling_latency_seconds",
+                &labels,
+                seconds(latency_ms),
+            );
+        }
+    }
+
+    type Gauge = fn(&ServiceCheckReport) -> Option<f64>;
+    let gauges: [(&str, &str, Gauge); 5] = [
+        (
+            "actr_check_service_up",
+            "Whether every check of the dependency passed",
+            |service| Some(bool_value(service.passed)),
+        ),
+        (
+            "actr_check_service_available",
+            "Whether the dependency is registered with the signaling server",
+            |service| Some(bool_value(service.availability.available)),
+        ),
+        (
+            "actr_check_service_reachable",
+            "Whether the dependency answered the connectivity probe",
+            |service| {
+                service
+                    .connectivity
+                    .applicable
+                    .then(|| bool_value(service.connectivity.reachable))
+            },
+        ),
+        (
+            "actr_check_service_latency_seconds",
+            "Round-trip time of the connectivity probe",
+            |service| service.connectivity.latency_ms.map(seconds),
+        ),
+        (
+            "actr_check_service_fingerprint_match",
+            "Whether the dependency matches the fingerprint in Actr.toml",
+            |service| Some(bool_value(service.fingerprint.valid)),
+        ),
+    ];
+    for (name, help, value) in gauges {
+        let samples: Vec<_> = report
+            .services
+            .iter()
+            .filter_map(|service| Some((service, value(service)?)))
+            .collect();
+        if samples.is_empty() {
+            continue;
+        }
+        family(&mut out, name, "gauge", help);
+        for (service, value) in samples {
+            let labels = [
+                ("alias", service.alias.as_str()),
+                ("name", service.name.as_str()),
+            ];
+            sample(&mut out, name, &labels, value);
+        }
+    }
+
+    family(
+        &mut out,
+        "actr_check_rounds_total",
+        "counter",
+        "Check rounds run",
+    );
+    sample(
+        &mut out,
+        "actr_check_rounds_total",
+        &[],
+        stats.rounds as f64,
+    );
+    family(
+        &mut out,
+        "actr_check_consecutive_failures",
+        "gauge",
+        "Failed check rounds in a row",
+    );
+    sample(
+        &mut out,
+        "actr_check_consecutive_failures",
+        &[],
+        stats.consecutive_failures as f64,
+    );
+    family(
+        &mut out,
+        "actr_check_last_round_timestamp_seconds",
+        "gauge",
+        "Unix time of the latest check round",
+    );
+    sample(
+        &mut out,
+        "actr_check_last_round_timestamp_seconds",
+        &[],
+        stats.timestamp as f64,
+    );
+    out
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let _ = write!(out, "{{{labels}}}");
+    }
+    let _ = writeln!(out, " {value}");
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+fn seconds(ms: u64) -> f64 {
+    ms as f64 / 1000.0
+}
+
+/// Serve `GET /metrics` until the task is dropped
+pub async fn serve(listener: TcpListener, exposition: Exposition) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let exposition = exposition.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &exposition).await {
+                tracing::debug!("Metrics connection failed: {e:#}");
+            }
+        });
+    }
+}
+
+/// Answer one HTTP/1.1 request and close the connection
+async fn handle_connection(stream: TcpStream, exposition: &Exposition) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let (status, body) = match (method, path.split('?').next().unwrap_or("")) {
+        ("GET", "/metrics") => (
+            "200 OK",
+            exposition
+                .read()
+                .map(|text| text.clone())
+                .unwrap_or_default(),
+        ),
+        ("GET", _) => (
+            "404 Not Found",
+            "Metrics are served at /metrics\n".to_string(),
+        ),
+        _ => ("405 Method Not Allowed", String::new()),
+    };
+
+    let mut stream = reader.into_inner();
+    stream
+        .write_all(
+            format!(
+                "HTTP/1.1 {status}\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .as_bytes(),
+        )
+        .await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::check::{
+        AvailabilityCheck, ConnectivityCheck, FingerprintCheck, SignalingCheck,
+    };
+
+    fn report() -> CheckReport {
+        CheckReport {
+            config_file: "Actr.toml".to_string(),
+            passed: false,
+            error: None,
+            signaling: Some(SignalingCheck {
+                url: "ws://localhost:8081".to_string(),
+                reachable: true,
+                latency_ms: Some(12),
+                error: None,
+            }),
+            services: vec![ServiceCheckReport {
+                alias: "user".to_string(),
+                name: "user-\"service\"".to_string(),
+                actr_type: None,
+                passed: false,
+                availability: AvailabilityCheck {
+                    available: true,
+                    error: None,
+                },
+                connectivity: ConnectivityCheck {
+                    applicable: false,
+                    reachable: false,
+                    latency_ms: None,
+                    error: None,
+                },
+                fingerprint: FingerprintCheck {
+                    valid: false,
+                    expected: "sha256:a".to_string(),
+                    actual: Some("sha256:b".to_string()),
+                    error: None,
+                },
+                lock: None,
+            }],
+            generated_edits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_exposition() {
+        let stats = RoundStats {
+            rounds: 3,
+            consecutive_failures: 2,
+            timestamp: 1_700_000_000,
+        };
+        let text = render(&report(), stats);
+        assert!(text.contains("# TYPE actr_check_signaling_up gauge\n"));
+        assert!(text.contains("actr_check_signaling_up{url=\"ws://localhost:8081\"} 1\n"));
+        assert!(
+            text.contains(
+                "actr_check_signaling_latency_seconds{url=\"ws://localhost:8081\"} 0.012\n"
+            )
+        );
+        assert!(text.contains(
+            "actr_check_service_fingerprint_match{alias=\"user\",name=\"user-\\\"service\\\"\"} 0\n"
+        ));
+        assert!(text.contains(
+            "actr_check_service_available{alias=\"user\",name=\"user-\\\"service\\\"\"} 1\n"
+        ));
+        // Not probed, so not exported
+        assert!(!text.contains("actr_check_service_reachable"));
+        assert!(!text.contains("actr_check_service_latency_seconds"));
+        assert!(text.contains("actr_check_rounds_total 3\n"));
+        assert!(text.contains("actr_check_consecutive_failures 2\n"));
+    }
+}
@@ -0,0 +1,445 @@
+//! Migrate command implementation - upgrade projects written for older editions
+//!
+//! `actr migrate` brings Actr.toml and the project layout to [`CURRENT_EDITION`]:
+//!
+//! - adds the `edition` key to files written before editions existed
+//! - expands shorthand dependencies (`user = "acme+UserService"`) and converts the
+//!   `[[dependencies]]` array into the `[dependencies]` table keyed by alias
+//! - moves `proto/` to `protos/` and `Actr.lock` to `Actr.lock.toml`, rewriting
+//!   config paths that pointed into `proto/`
+//!
+//! Actr.toml is backed up before it is rewritten and restored if the result does
+//! not parse. Anything that cannot be rewritten safely (scripts, conflicting
+//! directories) is reported as a manual step instead.
+
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType};
+use crate::human_println;
+use actr_config::ConfigParser;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, InlineTable, Item, Table, Value};
+
+/// Edition written by this CLI
+pub const CURRENT_EDITION: i64 = 1;
+
+/// Proto directory of projects created before edition 1
+const LEGACY_PROTO_DIR: &str = "proto";
+const PROTO_DIR: &str = "protos";
+const LEGACY_LOCK_FILE: &str = "Actr.lock";
+const LOCK_FILE: &str = "Actr.lock.toml";
+
+/// Migrate command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Upgrade Actr.toml and the project layout to the current edition",
+    long_about = "Detect projects written for older editions or layouts (no edition key, shorthand or array dependencies, proto/ instead of protos/, Actr.lock) and rewrite them for the current edition. Actr.toml is backed up first; steps that cannot be automated are listed at the end.\n\nExamples:\n  actr migrate --dry-run    # Show the planned changes\n  actr migrate"
+)]
+pub struct MigrateCommand {
+    /// Show the planned changes without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Changes needed to bring a project to [`CURRENT_EDITION`]
+#[derive(Debug, Clone, Default)]
+pub struct Migration {
+    /// Edition declared by Actr.toml, `None` before editions existed
+    pub from_edition: Option<i64>,
+    /// Rewritten Actr.toml, if it changes
+    pub config: Option<String>,
+    /// Files and directories to move, relative to the project root
+    pub renames: Vec<(PathBuf, PathBuf)>,
+    /// Automated steps, in the order they are applied
+    pub applied: Vec<String>,
+    /// Steps left to the user
+    pub manual: Vec<String>,
+}
+
+impl Migration {
+    pub fn is_up_to_date(&self) -> bool {
+        self.applied.is_empty() && self.manual.is_empty()
+    }
+}
+
+#[async_trait]
+impl Command for MigrateCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let config_manager = context.container.get_config_manager()?;
+        let project_root = config_manager.get_project_root().to_path_buf();
+        let config_path = project_root.join("Actr.toml");
+        let content = std::fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+        let migration = plan_migration(&project_root, &content)?;
+        if migration.is_up_to_date() {
+            return Ok(CommandResult::Success(format!(
+                "Project is up to date (edition {CURRENT_EDITION})"
+            )));
+        }
+
+        let mut backup_path = None;
+        if !self.dry_run {
+            let backup = match &migration.config {
+                Some(_) => Some(config_manager.backup_config().await?),
+                None => None,
+            };
+            apply_migration(&project_root, &migration).or_else(|e| {
+                if let Some(backup) = &backup {
+                    std::fs::copy(&backup.backup_path, &backup.original_path)?;
+                }
+                Err(e)
+            })?;
+            backup_path = backup.map(|backup| backup.backup_path);
+        }
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "dry_run": self.dry_run,
+                "from_edition": migration.from_edition,
+                "to_edition": CURRENT_EDITION,
+                "applied": migration.applied,
+                "manual": migration.manual,
+                "backup": backup_path,
+            });
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &report,
+            )?));
+        }
+
+        let marker = if self.dry_run { "📝" } else { "✅" };
+        for step in &migration.applied {
+            human_println!("  ├─ {marker} {step}");
+        }
+        for step in &migration.manual {
+            human_println!("  ├─ ⚠️  Manual: {step}");
+        }
+        if let Some(path) = &backup_path {
+            human_println!("  └─ Previous Actr.toml saved to {}", path.display());
+        }
+
+        let summary = format!(
+            "{} automated changes, {} manual steps",
+            migration.applied.len(),
+            migration.manual.len()
+        );
+        Ok(CommandResult::Success(if self.dry_run {
+            format!("Dry run: migration to edition {CURRENT_EDITION} would make {summary}")
+        } else {
+            format!("Migrated to edition {CURRENT_EDITION}: {summary}")
+        }))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::ConfigManager]
+    }
+
+    fn name(&self) -> &str {
+        "migrate"
+    }
+
+    fn description(&self) -> &str {
+        "Upgrade Actr.toml and the project layout to the current edition"
+    }
+}
+
+/// Work out the changes for the project at `root` whose Actr.toml holds `content`
+pub fn plan_migration(root: &Path, content: &str) -> Result<Migration> {
+    let mut doc = content
+        .parse::<DocumentMut>()
+        .context("Actr.toml is not valid TOML")?;
+    let mut migration = Migration {
+        from_edition: doc.get("edition").and_then(Item::as_integer),
+        ..Migration::default()
+    };
+
+    match migration.from_edition {
+        Some(edition) if edition > CURRENT_EDITION => {
+            return Err(ActrCliError::InvalidProject {
+                message: format!(
+                    "Actr.toml uses edition {edition}, but this actr only supports up to edition {CURRENT_EDITION}. Upgrade actr first."
+                ),
+            }
+            .into());
+        }
+        Some(_) => {}
+        None => {
+            doc.insert("edition", toml_edit::value(CURRENT_EDITION));
+            migration
+                .applied
+                .push(format!("Set edition = {CURRENT_EDITION}"));
+        }
+    }
+
+    migrate_dependencies(&mut doc, &mut migration);
+
+    let legacy_protos = root.join(LEGACY_PROTO_DIR).is_dir();
+    if legacy_protos && root.join(PROTO_DIR).exists() {
+        migration.manual.push(format!(
+            "Both {LEGACY_PROTO_DIR}/ and {PROTO_DIR}/ exist; merge {LEGACY_PROTO_DIR}/ into {PROTO_DIR}/ and update paths in Actr.toml"
+        ));
+    } else if legacy_protos {
+        migration
+            .renames
+            .push((PathBuf::from(LEGACY_PROTO_DIR), PathBuf::from(PROTO_DIR)));
+        migration
+            .applied
+            .push(format!("Move {LEGACY_PROTO_DIR}/ to {PROTO_DIR}/"));
+        for (key, item) in doc.as_table_mut().iter_mut() {
+            if key.get() != "scripts" {
+                rewrite_proto_paths(item, key.get(), &mut migration.applied);
+            }
+        }
+        if let Some(scripts) = doc.get("scripts").and_then(Item::as_table_like) {
+            for (name, script) in scripts.iter() {
+                if script
+                    .as_str()
+                    .is_some_and(|script| mentions_legacy_proto_dir(script))
+                {
+                    migration.manual.push(format!(
+                        "Script '{name}' mentions {LEGACY_PROTO_DIR}/; point it at {PROTO_DIR}/"
+                    ));
+                }
+            }
+        }
+    }
+
+    if root.join(LEGACY_LOCK_FILE).is_file() {
+        if root.join(LOCK_FILE).exists() {
+            migration.manual.push(format!(
+                "Both {LEGACY_LOCK_FILE} and {LOCK_FILE} exist; delete {LEGACY_LOCK_FILE} once 'actr lock verify' passes"
+            ));
+        } else {
+            migration
+                .renames
+                .push((PathBuf::from(LEGACY_LOCK_FILE), PathBuf::from(LOCK_FILE)));
+            migration
+                .applied
+                .push(format!("Rename {LEGACY_LOCK_FILE} to {LOCK_FILE}"));
+        }
+    }
+
+    let rewritten = doc.to_string();
+    if rewritten != content {
+        migration.config = Some(rewritten);
+    }
+    Ok(migration)
+}
+
+/// Move files, write the new Actr.toml and check that it parses, undoing the moves
+/// if it does not (the caller restores Actr.toml from its backup)
+fn apply_migration(root: &Path, migration: &Migration) -> Result<()> {
+    let mut moved: Vec<&(PathBuf, PathBuf)> = Vec::new();
+    let result = (|| -> Result<()> {
+        for rename in &migration.renames {
+            let (from, to) = (root.join(&rename.0), root.join(&rename.1));
+            std::fs::rename(&from, &to).with_context(|| {
+                format!("Failed to move {} to {}", from.display(), to.display())
+            })?;
+            moved.push(rename);
+        }
+        if let Some(config) = &migration.config {
+            let config_path = root.join("Actr.toml");
+            std::fs::write(&config_path, config)?;
+            ConfigParser::from_file(&config_path)
+                .map_err(|e| anyhow::anyhow!("Migrated Actr.toml does not parse: {e}"))?;
+        }
+        Ok(())
+    })();
+
+    if result.is_err() {
+        for (from, to) in moved.into_iter().rev() {
+            let _ = std::fs::rename(root.join(to), root.join(from));
+        }
+    }
+    result
+}
+
+/// Expand shorthand entries and convert the `[[dependencies]]` array
+fn migrate_dependencies(doc: &mut DocumentMut, migration: &mut Migration) {
+    let Some(dependencies) = doc.get_mut("dependencies") else {
+        return;
+    };
+
+    if let Some(array) = dependencies.as_array_of_tables() {
+        let mut table = Table::new();
+        for (index, entry) in array.iter().enumerate() {
+            let alias = ["alias", "name"]
+                .iter()
+                .find_map(|key| entry.get(key).and_then(Item::as_str))
+                .map(str::to_string);
+            let Some(alias) = alias else {
+                migration.manual.push(format!(
+                    "[[dependencies]] entry #{} has no name; add it to [dependencies] by hand",
+                    index + 1
+                ));
+                continue;
+            };
+            let mut inline = InlineTable::new();
+            for (key, value) in entry.iter() {
+                if key == "alias" || (key == "name" && value.as_str() == Some(alias.as_str())) {
+                    continue;
+                }
+                if let Some(value) = value.as_value() {
+                    let mut value = value.clone();
+                    value.decor_mut().clear();
+                    inline.insert(key, value);
+                }
+            }
+            table.insert(&alias, Item::Value(Value::InlineTable(inline)));
+        }
+        // Keep the table where the array was
+        if let Some(position) = array.iter().next().and_then(Table::position) {
+            table.set_position(position);
+        }
+        migration.applied.push(format!(
+            "Convert [[dependencies]] ({} entries) to the [dependencies] table",
+            table.len()
+        ));
+        *dependencies = Item::Table(table);
+        return;
+    }
+
+    let Some(table) = dependencies.as_table_like_mut() else {
+        return;
+    };
+    for (alias, entry) in table.iter_mut() {
+        let Some(actr_type) = entry.as_str().map(str::to_string) else {
+            continue;
+        };
+        let mut inline = InlineTable::new();
+        inline.insert("actr_type", actr_type.as_str().into());
+        *entry = Item::Value(Value::InlineTable(inline));
+        migration.applied.push(format!(
+            "Expand dependency '{}' to {{ actr_type = \"{actr_type}\" }}",
+            alias.get()
+        ));
+    }
+}
+
+/// Point string values under `item` that start with `proto/` at `protos/`
+fn rewrite_proto_paths(item: &mut Item, path: &str, applied: &mut Vec<String>) {
+    match item {
+        Item::Value(value) => rewrite_value(value, path, applied),
+        Item::Table(table) => {
+            for (key, item) in table.iter_mut() {
+                rewrite_proto_paths(item, &format!("{path}.{}", key.get()), applied);
+            }
+        }
+        Item::ArrayOfTables(array) => {
+            for (index, table) in array.iter_mut().enumerate() {
+                for (key, item) in table.iter_mut() {
+                    rewrite_proto_paths(item, &format!("{path}[{index}].{}", key.get()), applied);
+                }
+            }
+        }
+        Item::None => {}
+    }
+}
+
+fn rewrite_value(value: &mut Value, path: &str, applied: &mut Vec<String>) {
+    match value {
+        Value::String(string) => {
+            let old = string.value();
+            let Some(rest) = old
+                .strip_prefix(LEGACY_PROTO_DIR)
+                .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+            else {
+                return;
+            };
+            let new = format!("{PROTO_DIR}{rest}");
+            applied.push(format!("Rewrite {path}: \"{old}\" -> \"{new}\""));
+            let decor = string.decor().clone();
+            *value = Value::from(new);
+            *value.decor_mut() = decor;
+        }
+        Value::Array(array) => {
+            for (index, value) in array.iter_mut().enumerate() {
+                rewrite_value(value, &format!("{path}[{index}]"), applied);
+            }
+        }
+        Value::InlineTable(table) => {
+            for (key, value) in table.iter_mut() {
+                rewrite_value(value, &format!("{path}.{}", key.get()), applied);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether a shell command refers to the `proto/` directory
+fn mentions_legacy_proto_dir(script: &str) -> bool {
+    script
+        .split(|c: char| c.is_whitespace() || c == '=' || c == '"' || c == '\'')
+        .any(|word| {
+            let word = word.strip_prefix("./").unwrap_or(word);
+            word == LEGACY_PROTO_DIR || word.starts_with("proto/")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = r#"exports = ["proto/echo.proto"]
+
+[package]
+name = "echo"
+
+[dependencies]
+user = "acme+UserService"
+
+[scripts]
+gen = "protoc -I proto proto/echo.proto"
+"#;
+
+    #[test]
+    fn test_plan_legacy_project() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("proto")).unwrap();
+        std::fs::write(dir.path().join("Actr.lock"), "").unwrap();
+
+        let migration = plan_migration(dir.path(), LEGACY).unwrap();
+        assert_eq!(migration.from_edition, None);
+        assert_eq!(migration.renames.len(), 2);
+        assert_eq!(migration.manual.len(), 1);
+        assert!(migration.manual[0].contains("Script 'gen'"));
+
+        let config = migration.config.unwrap();
+        assert!(config.contains("edition = 1"));
+        assert!(config.contains(r#"exports = ["protos/echo.proto"]"#));
+        assert!(config.contains(r#"user = { actr_type = "acme+UserService" }"#));
+        // Scripts are reported, never rewritten
+        assert!(config.contains("protoc -I proto proto/echo.proto"));
+
+        let current = plan_migration(dir.path(), &config);
+        assert!(
+            current
+                .unwrap()
+                .applied
+                .iter()
+                .all(|step| !step.contains("edition"))
+        );
+    }
+
+    #[test]
+    fn test_plan_dependency_array_and_newer_edition() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = "edition = 1\n\n[[dependencies]]\nname = \"user\"\nactr_type = \"acme+UserService\"\n\n[[dependencies]]\nactr_type = \"acme+Orphan\"\n";
+
+        let migration = plan_migration(dir.path(), config).unwrap();
+        let rewritten = migration.config.unwrap();
+        assert!(rewritten.contains("[dependencies]"));
+        assert!(rewritten.contains(r#"user = { actr_type = "acme+UserService" }"#));
+        assert_eq!(migration.manual.len(), 1);
+        assert!(migration.renames.is_empty());
+
+        assert!(plan_migration(dir.path(), "edition = 2\n").is_err());
+        assert!(
+            plan_migration(dir.path(), "edition = 1\n")
+                .unwrap()
+                .is_up_to_date()
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! Mock command implementation - stand in for a dependency that is not online yet
+//!
+//! `actr mock <service>` reads the protos `actr install` cached under
+//! `protos/remote/<service>/` and serves every RPC they declare on a local
+//! HTTP/JSON endpoint:
+//!
+//! ```text
+//! POST http://127.0.0.1:50080/<package>.<Service>/<Method>
+//! ```
+//!
+//! The request body is the proto3 JSON form of the input message, or its protobuf
+//! encoding when sent as `application/x-protobuf` (the reply then uses the same
+//! encoding, see [`crate::proto_codec`]). Replies come from the fixture file when
+//! it names the RPC; otherwise an RPC whose request and response types match
+//! echoes the request, and every other RPC returns the response message with each
+//! field at its default value. `GET /` lists the routes.
+//!
+//! ```yaml
+//! # mock.yaml
+//! rpcs:
+//!   GetItem:                      # Method, Service/Method or package.Service/Method
+//!     response: { id: "42", name: "Widget" }
+//!   Charge:
+//!     error: "card declined"
+//!     delay_ms: 250
+//!   Ping:
+//!     echo: true
+//! ```
+//!
+//! The mock does not join the signaling server or speak WebRTC; it lets app code
+//! and scripts exercise the contract before the real service is deployed.
+//! Streaming RPCs answer with a single message.
+
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType};
+use crate::human_println;
+use crate::proto_codec::{ProtoRegistry, RpcMethod};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Largest request body the mock accepts
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Content type of protobuf request and response bodies; anything else is JSON
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// Mock command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Serve a local mock of a dependency from its cached protos",
+    long_about = "Serve every RPC of a dependency's cached protos on a local HTTP/JSON endpoint (POST /<package>.<Service>/<Method>). Replies come from a YAML fixture file, echo the request when the request and response types match, or return the response message with default values.\n\nExamples:\n  actr mock user-service\n  actr mock user-service --fixtures mock.yaml --listen 127.0.0.1:9000"
+)]
+pub struct MockCommand {
+    /// Dependency to mock, as named in Actr.toml
+    pub service: String,
+
+    /// Address to listen on
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:50080")]
+    pub listen: SocketAddr,
+
+    /// YAML file with canned replies per RPC
+    #[arg(long, value_name = "FILE")]
+    pub fixtures: Option<PathBuf>,
+}
+
+/// Contents of a fixture file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockFixtures {
+    #[serde(default)]
+    pub rpcs: HashMap<String, RpcFixture>,
+}
+
+/// Canned reply of one RPC
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcFixture {
+    /// Response message as JSON
+    pub response: Option<Value>,
+    /// Reply with the request
+    #[serde(default)]
+    pub echo: bool,
+    /// Fail the call with this message
+    pub error: Option<String>,
+    /// Wait before replying
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+impl MockFixtures {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse fixtures {}", path.display()))
+    }
+}
+
+/// Fixture keys of an RPC from most to least specific
+fn fixture_keys(rpc: &RpcMethod) -> [String; 3] {
+    [
+        format!("{}/{}", rpc.service, rpc.method),
+        format!("{}/{}", rpc.short_service(), rpc.method),
+        rpc.method.clone(),
+    ]
+}
+
+/// Reply to one call
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockReply {
+    Message(Value),
+    Error(String),
+}
+
+/// RPCs and message definitions of the mocked service
+#[derive(Debug, Clone, Default)]
+pub struct MockService {
+    registry: ProtoRegistry,
+    fixtures: MockFixtures,
+}
+
+impl MockService {
+    pub fn new(registry: ProtoRegistry) -> Self {
+        Self {
+            registry,
+            fixtures: MockFixtures::default(),
+        }
+    }
+
+    /// Use `fixtures` for replies; returns the fixture keys that match no RPC
+    pub fn with_fixtures(mut self, fixtures: MockFixtures) -> (Self, Vec<String>) {
+        let mut unknown: Vec<String> = fixtures
+            .rpcs
+            .keys()
+            .filter(|key| {
+                !self
+                    .rpcs()
+                    .iter()
+                    .any(|rpc| fixture_keys(rpc).contains(*key))
+            })
+            .cloned()
+            .collect();
+        unknown.sort();
+        self.fixtures = fixtures;
+        (self, unknown)
+    }
+
+    pub fn rpcs(&self) -> &[RpcMethod] {
+        self.registry.rpcs()
+    }
+
+    pub fn registry(&self) -> &ProtoRegistry {
+        &self.registry
+    }
+
+    /// RPC served at a request path
+    pub fn route(&self, path: &str) -> Option<&RpcMethod> {
+        self.rpcs().iter().find(|rpc| rpc.path() == path)
+    }
+
+    fn fixture(&self, rpc: &RpcMethod) -> Option<&RpcFixture> {
+        fixture_keys(rpc)
+            .iter()
+            .find_map(|key| self.fixtures.rpcs.get(key))
+    }
+
+    /// Delay configured for an RPC
+    pub fn delay(&self, rpc: &RpcMethod) -> Duration {
+        Duration::from_millis(self.fixture(rpc).map_or(0, |fixture| fixture.delay_ms))
+    }
+
+    /// Reply to a call: fixture, then echo for same-typed RPCs, then defaults
+    pub fn reply(&self, rpc: &RpcMethod, request: Value) -> MockReply {
+        if let Some(fixture) = self.fixture(rpc) {
+            if let Some(error) = &fixture.error {
+                return MockReply::Error(error.clone());
+            }
+            if let Some(response) = &fixture.response {
+                return MockReply::Message(response.clone());
+            }
+            if fixture.echo {
+                return MockReply::Message(request);
+            }
+        }
+        if rpc.input_type == rpc.output_type {
+            return MockReply::Message(request);
+        }
+        MockReply::Message(self.registry.default_json(&rpc.output_type))
+    }
+}
+
+#[async_trait]
+impl Command for MockCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let cache_manager = context.container.get_cache_manager()?;
+        let cached = cache_manager
+            .get_cached_proto(&self.service)
+            .await?
+            .ok_or_else(|| ActrCliError::Cache {
+                message: format!(
+                    "Protos for '{}' are not cached under protos/remote/{}. Run 'actr install' first.",
+                    self.service, self.service
+                ),
+            })?;
+        let registry =
+            ProtoRegistry::from_sources(cached.files.iter().map(|file| file.content.as_str()))
+                .with_context(|| format!("Failed to parse the protos of '{}'", self.service))?;
+        let service = MockService::new(registry);
+        if service.rpcs().is_empty() {
+            return Err(ActrCliError::InvalidProject {
+                message: format!("The protos of '{}' declare no RPCs", self.service),
+            }
+            .into());
+        }
+        let fixtures = match &self.fixtures {
+            Some(path) => MockFixtures::from_file(path)?,
+            None => MockFixtures::default(),
+        };
+        let (service, unknown) = service.with_fixtures(fixtures);
+        for key in unknown {
+            human_println!("⚠️  Fixture '{key}' matches no RPC of {}", self.service);
+        }
+
+        let listener = TcpListener::bind(self.listen)
+            .await
+            .with_context(|| format!("Failed to listen on {}", self.listen))?;
+        let address = listener.local_addr()?;
+        human_println!("🎭 Mocking {} on http://{address}", self.service);
+        for rpc in service.rpcs() {
+            human_println!(
+                "   POST {}{}",
+                rpc.path(),
+                if rpc.streaming {
+                    "  (single reply)"
+                } else {
+                    ""
+                }
+            );
+        }
+        human_println!("Press Ctrl+C to stop");
+
+        tokio::select! {
+            result = serve(listener, Arc::new(service)) => result?,
+            _ = tokio::signal::ctrl_c() => {}
+        }
+        Ok(CommandResult::Success(format!(
+            "Stopped mocking {}",
+            self.service
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::CacheManager]
+    }
+
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn description(&self) -> &str {
+        "Serve a local mock of a dependency from its cached protos"
+    }
+}
+
+/// Answer requests on `listener` until accepting fails
+pub async fn serve(listener: TcpListener, service: Arc<MockService>) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = service.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &service).await {
+                tracing::debug!("Mock connection failed: {e:#}");
+            }
+        });
+    }
+}
+
+/// Response status, content type and body
+type Response = (u16, &'static str, Vec<u8>);
+
+/// Serve one HTTP/1.1 request and close the connection
+async fn handle_connection(stream: TcpStream, service: &MockService) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+    let (mut content_length, mut protobuf) = (0usize, false);
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let (name, value) = (name.trim(), value.trim());
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().unwrap_or(0);
+        } else if name.eq_ignore_ascii_case("content-type") {
+            protobuf = value.starts_with(PROTOBUF_CONTENT_TYPE);
+        }
+    }
+
+    let (status, content_type, body) = if content_length > MAX_BODY_BYTES {
+        json_response(413, &error_body("Request body too large"))
+    } else {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await?;
+        respond(service, method, path, &body, protobuf).await
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    };
+    let mut stream = reader.into_inner();
+    stream
+        .write_all(
+            format!(
+                "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .as_bytes(),
+        )
+        .await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Answer a request; protobuf requests get protobuf replies, errors are always JSON
+async fn respond(
+    service: &MockService,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    protobuf: bool,
+) -> Response {
+    if method == "GET" && path == "/" {
+        let routes: BTreeMap<String, Value> = service
+            .rpcs()
+            .iter()
+            .map(|rpc| {
+                (
+                    rpc.path(),
+                    serde_json::json!({
+                        "request": rpc.input_type,
+                        "response": rpc.output_type,
+                        "streaming": rpc.streaming,
+                    }),
+                )
+            })
+            .collect();
+        return json_response(200, &serde_json::json!({ "rpcs": routes }));
+    }
+    let Some(rpc) = service.route(path) else {
+        return json_response(404, &error_body(&format!("No RPC at {path}")));
+    };
+    if method != "POST" {
+        return json_response(405, &error_body("RPCs are called with POST"));
+    }
+    let request = if protobuf {
+        service.registry().decode(&rpc.input_type, body)
+    } else if body.iter().all(u8::is_ascii_whitespace) {
+        Ok(Value::Object(Map::new()))
+    } else {
+        serde_json::from_slice(body).map_err(Into::into)
+    };
+    let request = match request {
+        Ok(request) => request,
+        Err(e) => return json_response(400, &error_body(&format!("Invalid request: {e:#}"))),
+    };
+
+    tokio::time::sleep(service.delay(rpc)).await;
+    let message = match service.reply(rpc, request) {
+        MockReply::Message(message) => message,
+        MockReply::Error(error) => {
+            human_println!("→ {} 500 {error}", rpc.path());
+            return json_response(500, &error_body(&error));
+        }
+    };
+    if !protobuf {
+        human_println!("→ {} 200", rpc.path());
+        return json_response(200, &message);
+    }
+    match service.registry().encode(&rpc.output_type, &message) {
+        Ok(bytes) => {
+            human_println!("→ {} 200", rpc.path());
+            (200, PROTOBUF_CONTENT_TYPE, bytes)
+        }
+        Err(e) => {
+            let error = format!("Reply does not match {}: {e:#}", rpc.output_type);
+            human_println!("→ {} 500 {error}", rpc.path());
+            json_response(500, &error_body(&error))
+        }
+    }
+}
+
+fn json_response(status: u16, body: &Value) -> Response {
+    let body = serde_json::to_vec_pretty(body).unwrap_or_default();
+    (status, "application/json", body)
+}
+
+fn error_body(message: &str) -> Value {
+    serde_json::json!({ "error": message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTO: &str = r#"
+syntax = "proto3";
+package shop;
+
+enum Status { STATUS_UNKNOWN = 0; STATUS_ACTIVE = 1; }
+
+message Item {
+  string item_id = 1;
+  int64 stock = 2;
+  Status status = 3;
+  repeated string tags = 4;
+  Price price = 5;
+  message Price { double amount = 1; }
+}
+message GetItemRequest { string item_id = 1; }
+message Ping { string nonce = 1; }
+
+service InventoryService {
+  rpc GetItem (GetItemRequest) returns (Item);
+  rpc Ping (Ping) returns (Ping);
+  rpc Reserve (GetItemRequest) returns (Item);
+}
+"#;
+
+    #[test]
+    fn test_defaults_and_echo() {
+        let service = MockService::new(ProtoRegistry::from_sources([PROTO]).unwrap());
+        let get_item = service.route("/shop.InventoryService/GetItem").unwrap();
+        assert_eq!(get_item.output_type, "shop.Item");
+
+        assert_eq!(
+            service.reply(get_item, serde_json::json!({ "itemId": "1" })),
+            MockReply::Message(serde_json::json!({
+                "itemId": "",
+                "stock": "0",
+                "status": "STATUS_UNKNOWN",
+                "tags": [],
+                "price": { "amount": 0.0 },
+            }))
+        );
+
+        let ping = service.route("/shop.InventoryService/Ping").unwrap();
+        let request = serde_json::json!({ "nonce": "abc" });
+        assert_eq!(
+            service.reply(ping, request.clone()),
+            MockReply::Message(request)
+        );
+        assert!(service.route("/shop.InventoryService/Missing").is_none());
+    }
+
+    #[test]
+    fn test_fixtures_take_precedence() {
+        let fixtures: MockFixtures = serde_yaml::from_str(
+            "rpcs:\n  GetItem:\n    response: { itemId: \"42\" }\n  InventoryService/Reserve:\n    error: out of stock\n    delay_ms: 5\n  Unknown: { echo: true }\n",
+        )
+        .unwrap();
+        let (service, unknown) =
+            MockService::new(ProtoRegistry::from_sources([PROTO]).unwrap()).with_fixtures(fixtures);
+        assert_eq!(unknown, vec!["Unknown".to_string()]);
+
+        let get_item = service.route("/shop.InventoryService/GetItem").unwrap();
+        assert_eq!(
+            service.reply(get_item, Value::Null),
+            MockReply::Message(serde_json::json!({ "itemId": "42" }))
+        );
+        let reserve = service.route("/shop.InventoryService/Reserve").unwrap();
+        assert_eq!(
+            service.reply(reserve, Value::Null),
+            MockReply::Error("out of stock".to_string())
+        );
+        assert_eq!(service.delay(reserve), Duration::from_millis(5));
+
+        assert!(
+            serde_yaml::from_str::<MockFixtures>("rpcs:\n  GetItem:\n    reply: {}\n").is_err()
+        );
+    }
+}
@@ -1,16 +1,38 @@
 //! Command implementations for actr-cli
 
+pub mod cache;
+pub mod call;
 pub mod check;
 pub mod codegen;
+pub mod completions;
+pub mod components;
 pub mod config;
+pub mod diff;
 pub mod discovery;
 pub mod doc;
+pub mod doctor;
+pub mod export;
 pub mod fingerprint;
 pub mod generate;
+pub mod graph;
+pub mod history;
+pub mod import;
 pub mod init;
 pub mod initialize;
 pub mod install;
+pub mod lock;
+pub mod metrics;
+pub mod migrate;
+pub mod mock;
+pub mod new;
+pub mod proto;
+pub mod publish;
+pub mod remove;
 pub mod run;
+pub mod search;
+pub mod ui;
+pub mod update;
+pub mod vendor;
 
 use crate::error::Result;
 use async_trait::async_trait;
@@ -29,15 +51,51 @@ pub enum SupportedLanguage {
     Python,
     Swift,
     Kotlin,
+    #[value(alias = "ts")]
+    Typescript,
+}
+
+impl SupportedLanguage {
+    /// Name used on the command line and in Actr.toml
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SupportedLanguage::Rust => "rust",
+            SupportedLanguage::Python => "python",
+            SupportedLanguage::Swift => "swift",
+            SupportedLanguage::Kotlin => "kotlin",
+            SupportedLanguage::Typescript => "typescript",
+        }
+    }
 }
 
 // Re-export new architecture commands
+pub use cache::CacheCommand;
+pub use call::CallCommand;
 pub use check::CheckCommand;
+pub use completions::CompletionsCommand;
+pub use components::ComponentsCommand;
 pub use config::ConfigCommand;
+pub use diff::DiffCommand;
 pub use discovery::DiscoveryCommand;
 pub use doc::DocCommand;
+pub use doctor::DoctorCommand;
+pub use export::ExportCommand;
 pub use fingerprint::FingerprintCommand;
 pub use generate::GenCommand;
+pub use graph::GraphCommand;
+pub use history::HistoryCommand;
+pub use import::ImportCommand;
 pub use init::InitCommand;
 pub use install::InstallCommand;
+pub use lock::LockCommand;
+pub use migrate::MigrateCommand;
+pub use mock::MockCommand;
+pub use new::NewCommand;
+pub use proto::ProtoCommand;
+pub use publish::PublishCommand;
+pub use remove::RemoveCommand;
 pub use run::RunCommand;
+pub use search::SearchCommand;
+pub use ui::UiCommand;
+pub use update::UpdateCommand;
+pub use vendor::VendorCommand;
@@ -0,0 +1,338 @@
+//! `actr new` - add pieces to an existing project
+//!
+//! `actr new actor <Name>` writes `protos/local/<name>.proto` with one service and
+//! the RPCs given by `--rpc Method:Request:Response` (prompted for when omitted),
+//! adds the file to `exports` in Actr.toml and runs `actr gen`. The generation
+//! cache keeps that run to the new proto for generators that work per file.
+
+use crate::commands::init::{is_interactive, run_actr};
+use crate::commands::{Command, SupportedLanguage};
+use crate::error::{ActrCliError, Result};
+use crate::human_println;
+use crate::utils::{to_pascal_case, to_snake_case};
+use async_trait::async_trait;
+use clap::{Args, Subcommand};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use toml_edit::{Array, DocumentMut, Item, Value};
+
+#[derive(Args, Debug, Clone)]
+#[command(about = "Add actors to an existing project")]
+pub struct NewCommand {
+    #[command(subcommand)]
+    pub command: NewSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NewSubcommand {
+    /// Add a service proto to the project and generate its code
+    #[command(
+        long_about = "Create protos/local/<name>.proto with one service, add it to 'exports' in Actr.toml and run 'actr gen'.\n\nExamples:\n  actr new actor Inventory --rpc Get:GetItemRequest:GetItemResponse\n  actr new actor Billing --rpc Charge:ChargeRequest:ChargeResponse --rpc Refund:RefundRequest:RefundResponse\n  actr new actor Chat            # Prompt for the RPCs"
+    )]
+    Actor(NewActorArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct NewActorArgs {
+    /// Actor name, e.g. Inventory (service InventoryService in inventory.proto)
+    pub name: String,
+
+    /// RPC as Method:Request:Response (repeatable); prompted for when omitted
+    #[arg(long = "rpc", value_name = "METHOD:REQ:RESP", value_parser = RpcSpec::parse)]
+    pub rpcs: Vec<RpcSpec>,
+
+    /// Directory of the new proto, relative to Actr.toml
+    #[arg(long, default_value = "protos/local")]
+    pub dir: PathBuf,
+
+    /// Path to Actr.toml config file
+    #[arg(short, long, default_value = "Actr.toml")]
+    pub config: PathBuf,
+
+    /// Languages passed to 'actr gen' (default: the project's generation targets)
+    #[arg(short, long, value_delimiter = ',', value_name = "LANG")]
+    pub language: Vec<SupportedLanguage>,
+
+    /// Only write the proto and update Actr.toml
+    #[arg(long)]
+    pub no_gen: bool,
+
+    /// Offline mode, passed on to 'actr gen' (set by --offline)
+    #[arg(skip)]
+    pub offline: bool,
+}
+
+/// One RPC of the new service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcSpec {
+    pub method: String,
+    pub request: String,
+    pub response: String,
+}
+
+impl RpcSpec {
+    /// Parse `Method:Request:Response`
+    pub fn parse(spec: &str) -> std::result::Result<Self, String> {
+        let parts: Vec<&str> = spec.split(':').map(str::trim).collect();
+        let [method, request, response] = parts.as_slice() else {
+            return Err(format!("expected Method:Request:Response, got '{spec}'"));
+        };
+        for name in [method, request, response] {
+            if !is_proto_identifier(name) {
+                return Err(format!("'{name}' is not a valid proto identifier"));
+            }
+        }
+        Ok(Self {
+            method: method.to_string(),
+            request: request.to_string(),
+            response: response.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Command for NewCommand {
+    async fn execute(&self) -> Result<()> {
+        self.run().await.map(|_| ())
+    }
+}
+
+impl NewCommand {
+    /// Run the sub-generator and return the files it created
+    pub async fn run(&self) -> Result<Vec<PathBuf>> {
+        match &self.command {
+            NewSubcommand::Actor(args) => args.run().await,
+        }
+    }
+}
+
+impl NewActorArgs {
+    pub async fn run(&self) -> Result<Vec<PathBuf>> {
+        if !self.config.exists() {
+            return Err(ActrCliError::InvalidProject(format!(
+                "{} not found. Run 'actr new' inside a project created by 'actr init'.",
+                self.config.display()
+            )));
+        }
+        let service = service_name(&self.name)?;
+        let file_stem = to_snake_case(service.strip_suffix("Service").unwrap_or(&service));
+        let project_root = self
+            .config
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let export = self.dir.join(format!("{file_stem}.proto"));
+        let proto_path = project_root.join(&export);
+        if proto_path.exists() {
+            return Err(ActrCliError::InvalidProject(format!(
+                "{} already exists",
+                proto_path.display()
+            )));
+        }
+
+        let rpcs = if self.rpcs.is_empty() {
+            prompt_rpcs()?
+        } else {
+            self.rpcs.clone()
+        };
+        if rpcs.is_empty() {
+            return Err(ActrCliError::InvalidProject(
+                "No RPCs given; pass --rpc Method:Request:Response".to_string(),
+            ));
+        }
+
+        if let Some(parent) = proto_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&proto_path, render_proto(&file_stem, &service, &rpcs))?;
+        human_println!("📄 Created {}", proto_path.display());
+
+        add_export(&self.config, &export)?;
+        human_println!(
+            "📝 Added {} to exports in {}",
+            export.display(),
+            self.config.display()
+        );
+
+        if !self.no_gen {
+            let languages = self
+                .language
+                .iter()
+                .map(|language| language.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            let config = self.config.to_string_lossy();
+            let mut args = vec!["gen", "--config", config.as_ref()];
+            if !languages.is_empty() {
+                args.extend(["--language", languages.as_str()]);
+            }
+            if self.offline {
+                args.push("--offline");
+            }
+            run_actr(Path::new("."), &args).await?;
+        }
+
+        Ok(vec![proto_path])
+    }
+}
+
+/// `inventory`, `Inventory` and `InventoryService` all become `InventoryService`
+fn service_name(name: &str) -> Result<String> {
+    let pascal = to_pascal_case(name);
+    if !is_proto_identifier(&pascal) {
+        return Err(ActrCliError::InvalidProject(format!(
+            "'{name}' is not a valid actor name"
+        )));
+    }
+    Ok(if pascal.ends_with("Service") {
+        pascal
+    } else {
+        format!("{pascal}Service")
+    })
+}
+
+fn is_proto_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Read `Method:Request:Response` lines until an empty one
+fn prompt_rpcs() -> Result<Vec<RpcSpec>> {
+    if !is_interactive() {
+        return Ok(Vec::new());
+    }
+    human_println!("Enter RPCs as Method:Request:Response, one per line (empty line to finish)");
+    let mut rpcs = Vec::new();
+    loop {
+        print!("  rpc> ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        match RpcSpec::parse(line.trim()) {
+            Ok(rpc) => rpcs.push(rpc),
+            Err(e) => human_println!("  ⚠️  {e}"),
+        }
+    }
+    Ok(rpcs)
+}
+
+/// Proto with one service and an empty message per distinct request/response type
+fn render_proto(package: &str, service: &str, rpcs: &[RpcSpec]) -> String {
+    let mut proto = format!("syntax = \"proto3\";\n\npackage {package};\n\nservice {service} {{\n");
+    for rpc in rpcs {
+        proto.push_str(&format!(
+            "    rpc {} ({}) returns ({});\n",
+            rpc.method, rpc.request, rpc.response
+        ));
+    }
+    proto.push_str("}\n");
+
+    let mut messages: Vec<&str> = Vec::new();
+    for rpc in rpcs {
+        for message in [rpc.request.as_str(), rpc.response.as_str()] {
+            if !messages.contains(&message) {
+                messages.push(message);
+            }
+        }
+    }
+    for message in messages {
+        proto.push_str(&format!("\nmessage {message} {{\n}}\n"));
+    }
+    proto
+}
+
+/// Append `export` to the `exports` array of Actr.toml, keeping the file's formatting
+fn add_export(config_path: &Path, export: &Path) -> Result<()> {
+    let original = std::fs::read_to_string(config_path)?;
+    let mut document = original.parse::<DocumentMut>().map_err(|e| {
+        ActrCliError::config_error(format!("Failed to parse {}: {e}", config_path.display()))
+    })?;
+    let entry = export.to_string_lossy().replace('\\', "/");
+    if !push_export(&mut document, &entry)
+        .map_err(|e| ActrCliError::config_error(format!("{e} in {}", config_path.display())))?
+    {
+        return Ok(());
+    }
+
+    std::fs::write(config_path, document.to_string())?;
+    if let Err(e) = actr_config::ConfigParser::from_file(config_path) {
+        std::fs::write(config_path, original)?;
+        return Err(ActrCliError::config_error(format!(
+            "Adding the export made {} invalid, changes rolled back: {e}",
+            config_path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Add `entry` to `exports` unless it is listed already; returns whether it was added
+fn push_export(document: &mut DocumentMut, entry: &str) -> std::result::Result<bool, String> {
+    let exports = document
+        .entry("exports")
+        .or_insert(Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or_else(|| "'exports' is not an array".to_string())?;
+    if exports.iter().any(|value| value.as_str() == Some(entry)) {
+        return Ok(false);
+    }
+    exports.push(entry);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_spec_and_proto() {
+        let get = RpcSpec::parse("Get:GetItemRequest:Item").unwrap();
+        let list = RpcSpec::parse("List : ListItemsRequest : Item").unwrap();
+        assert!(RpcSpec::parse("Get:GetItemRequest").is_err());
+        assert!(RpcSpec::parse("Get:1Request:Item").is_err());
+        assert_eq!(service_name("inventory").unwrap(), "InventoryService");
+        assert_eq!(
+            service_name("InventoryService").unwrap(),
+            "InventoryService"
+        );
+
+        let proto = render_proto("inventory", "InventoryService", &[get, list]);
+        assert!(proto.contains("package inventory;"));
+        assert!(proto.contains("    rpc Get (GetItemRequest) returns (Item);\n"));
+        assert!(proto.contains("    rpc List (ListItemsRequest) returns (Item);\n"));
+        assert_eq!(proto.matches("message Item {").count(), 1);
+    }
+
+    #[test]
+    fn test_push_export_keeps_formatting() {
+        let mut document: DocumentMut = "# my service\nexports = []\n\n[package]\nname = \"app\"\n"
+            .parse()
+            .unwrap();
+        assert!(push_export(&mut document, "protos/local/inventory.proto").unwrap());
+        assert!(!push_export(&mut document, "protos/local/inventory.proto").unwrap());
+        let content = document.to_string();
+        assert!(
+            content.starts_with("# my service\nexports = [\"protos/local/inventory.proto\"]\n")
+        );
+        assert!(content.contains("[package]\nname = \"app\"\n"));
+
+        let mut document: DocumentMut = "exports = \"x.proto\"\n".parse().unwrap();
+        assert!(push_export(&mut document, "protos/local/inventory.proto").is_err());
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(
+            &path,
+            "# my service\nexports = []\n\n[package]\nname = \"app\"\n",
+        )
+        .unwrap();
+
+        // An Actr.toml that does not validate is restored
+        let export = Path::new("protos/local/inventory.proto");
+        assert!(add_export(&path, export).is_err());
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# my service\nexports = []\n"));
+    }
+}
@@ -0,0 +1,421 @@
+//! Proto command implementation - work with the protos of services
+//!
+//! - `actr proto export <service>` writes a service's protos below
+//!   `<out-dir>/<service>/`, by default in directories following their `package`
+//!   (`acme.user.v1` → `acme/user/v1/user.proto`), and rewrites the imports between
+//!   them to the new paths so the directory can be handed to `protoc -I` as is
+//! - `actr proto lint` checks the project's own protos against the rules in [`lint`]
+//! - `actr proto fmt` normalizes their layout, see [`fmt`]
+
+pub mod fmt;
+pub mod lint;
+
+use crate::core::{
+    Command, CommandContext, CommandResult, ComponentType, ProtoFile, ServiceDiscovery,
+};
+use crate::human_println;
+use crate::proto_parser::ProtoSchema;
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use clap::{Args, Subcommand, ValueEnum};
+use fmt::ProtoFormatter;
+use lint::{LintConfig, Severity};
+use owo_colors::OwoColorize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Directory below the project root exports land in by default
+pub const DEFAULT_EXPORT_DIR: &str = "exports/remote";
+
+/// Proto command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Work with service protos",
+    long_about = "Work with the proto files of services.\n\nExamples:\n  actr proto export user-service                      # exports/remote/user-service/<package dirs>\n  actr proto export user-service --out-dir third_party\n  actr proto export user-service --layout flat\n  actr proto lint                                     # Lint protos/ (without protos/remote/)\n  actr proto lint protos/user.proto\n  actr proto fmt                                      # Format protos/ in place\n  actr proto fmt --check                              # List unformatted files, fail if any"
+)]
+pub struct ProtoCommand {
+    #[command(subcommand)]
+    pub command: ProtoSubcommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProtoSubcommand {
+    /// Write a service's protos to a directory
+    Export(ExportOptions),
+    /// Check the project's protos against the lint rules
+    Lint {
+        /// Proto files or directories (default: protos/, without protos/remote/)
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+    },
+    /// Format the project's protos
+    Fmt {
+        /// Proto files or directories (default: protos/, without protos/remote/)
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+
+        /// Only report files that are not formatted, failing if there are any
+        #[arg(long)]
+        check: bool,
+
+        /// Formatter to use
+        #[arg(long, value_enum, default_value_t = ProtoFormatter::Auto)]
+        formatter: ProtoFormatter,
+    },
+}
+
+/// Where and how exported protos are written
+#[derive(Args, Debug, Clone)]
+pub struct ExportOptions {
+    /// Service to export
+    #[arg(value_name = "SERVICE")]
+    pub service: String,
+
+    /// Directory the service's directory is created in (default: exports/remote)
+    #[arg(long, value_name = "DIR")]
+    pub out_dir: Option<PathBuf>,
+
+    /// Directory layout of the exported files
+    #[arg(long, value_enum, default_value_t = ExportLayout::Package)]
+    pub layout: ExportLayout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ExportLayout {
+    /// One directory per proto package (`acme/user/v1/user.proto`)
+    #[default]
+    Package,
+    /// All files directly in the service directory
+    Flat,
+}
+
+#[async_trait]
+impl Command for ProtoCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        match &self.command {
+            ProtoSubcommand::Export(options) => {
+                let project_root = context
+                    .container
+                    .get_config_manager()?
+                    .get_project_root()
+                    .to_path_buf();
+                let out_dir = options
+                    .out_dir
+                    .clone()
+                    .unwrap_or_else(|| project_root.join(DEFAULT_EXPORT_DIR));
+                let discovery = context.container.get_service_discovery()?;
+                let written = export_service_protos(
+                    &options.service,
+                    discovery.as_ref(),
+                    &out_dir,
+                    options.layout,
+                )
+                .await?;
+                Ok(CommandResult::Success(format!(
+                    "Exported {} proto files to {}",
+                    written.len(),
+                    out_dir.join(&options.service).display()
+                )))
+            }
+            ProtoSubcommand::Lint { paths } => self.lint(context, paths),
+            ProtoSubcommand::Fmt {
+                paths,
+                check,
+                formatter,
+            } => self.fmt(context, paths, *check, *formatter),
+        }
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        match self.command {
+            ProtoSubcommand::Export(_) => {
+                vec![
+                    ComponentType::ConfigManager,
+                    ComponentType::ServiceDiscovery,
+                ]
+            }
+            ProtoSubcommand::Lint { .. } | ProtoSubcommand::Fmt { .. } => {
+                vec![ComponentType::ConfigManager]
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "proto"
+    }
+
+    fn description(&self) -> &str {
+        "Work with service protos"
+    }
+}
+
+impl ProtoCommand {
+    fn lint(&self, context: &CommandContext, paths: &[PathBuf]) -> Result<CommandResult> {
+        let project_root = context
+            .container
+            .get_config_manager()?
+            .get_project_root()
+            .to_path_buf();
+        let config = LintConfig::from_config_file(&project_root.join("Actr.toml"))?;
+        let files = lint::collect_proto_files(&Self::proto_paths(&project_root, paths));
+        let findings = lint::lint_files(&files, &config);
+        let errors = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count();
+        let warnings = findings.len() - errors;
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "files": files.len(),
+                "errors": errors,
+                "warnings": warnings,
+                "findings": findings,
+            });
+            let json = serde_json::to_string_pretty(&report)?;
+            return Ok(if errors > 0 {
+                CommandResult::Error(json)
+            } else {
+                CommandResult::Success(json)
+            });
+        }
+
+        for finding in &findings {
+            let location = format!(
+                "{}:{}",
+                finding
+                    .file
+                    .strip_prefix(&project_root)
+                    .unwrap_or(&finding.file)
+                    .display(),
+                finding.line
+            );
+            let severity = match finding.severity {
+                Severity::Error => finding.severity.to_string().red().to_string(),
+                _ => finding.severity.to_string().yellow().to_string(),
+            };
+            human_println!(
+                "{location}: {severity}[{}]: {}",
+                finding.rule.as_str(),
+                finding.message
+            );
+        }
+
+        let summary = format!(
+            "Linted {} proto files: {errors} errors, {warnings} warnings",
+            files.len()
+        );
+        Ok(if errors > 0 {
+            CommandResult::Error(summary)
+        } else {
+            CommandResult::Success(summary)
+        })
+    }
+}
+
+impl ProtoCommand {
+    fn fmt(
+        &self,
+        context: &CommandContext,
+        paths: &[PathBuf],
+        check: bool,
+        formatter: ProtoFormatter,
+    ) -> Result<CommandResult> {
+        let project_root = context
+            .container
+            .get_config_manager()?
+            .get_project_root()
+            .to_path_buf();
+        let files = lint::collect_proto_files(&Self::proto_paths(&project_root, paths));
+        let formatter = formatter.resolve();
+
+        let mut changed = Vec::new();
+        for file in &files {
+            let current = std::fs::read_to_string(file)?;
+            let formatted = formatter.format_file(file)?;
+            if formatted == current {
+                continue;
+            }
+            let display = file.strip_prefix(&project_root).unwrap_or(file).display();
+            if check {
+                human_println!("❌ Not formatted: {display}");
+            } else {
+                std::fs::write(file, formatted)?;
+                human_println!("✅ Formatted: {display}");
+            }
+            changed.push(file.clone());
+        }
+
+        let summary = format!(
+            "{} of {} proto files {} ({})",
+            changed.len(),
+            files.len(),
+            if check {
+                "need formatting"
+            } else {
+                "reformatted"
+            },
+            formatter.as_str()
+        );
+        Ok(if check && !changed.is_empty() {
+            CommandResult::Error(format!("{summary}. Run 'actr proto fmt' to fix them"))
+        } else {
+            CommandResult::Success(summary)
+        })
+    }
+
+    /// The given paths, or the project's `protos/` directory
+    fn proto_paths(project_root: &Path, paths: &[PathBuf]) -> Vec<PathBuf> {
+        if paths.is_empty() {
+            vec![project_root.join("protos")]
+        } else {
+            paths.to_vec()
+        }
+    }
+}
+
+/// Write a service's protos to `<out_dir>/<service>/`, returning the written paths
+pub(crate) async fn export_service_protos(
+    service_name: &str,
+    service_discovery: &dyn ServiceDiscovery,
+    out_dir: &Path,
+    layout: ExportLayout,
+) -> Result<Vec<PathBuf>> {
+    human_println!("📤 Exporting proto files for {service_name}...");
+
+    let proto_files = service_discovery.get_service_proto(service_name).await?;
+    let service_dir = out_dir.join(service_name);
+
+    let mut written = Vec::new();
+    for (relative, content) in plan_export(&proto_files, layout)? {
+        let file_path = service_dir.join(&relative);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file_path, content)?;
+        human_println!("✅ Exported: {}", file_path.display());
+        written.push(file_path);
+    }
+
+    human_println!("🎉 Export completed, total {} files", written.len());
+    Ok(written)
+}
+
+/// Path of each file below the service directory and its content with imports rewritten
+fn plan_export(files: &[ProtoFile], layout: ExportLayout) -> Result<Vec<(String, String)>> {
+    // Imports are matched by file name, so names must be unique within a service
+    let mut targets: BTreeMap<&str, String> = BTreeMap::new();
+    for file in files {
+        let name = file_name(&file.name);
+        let target = match layout {
+            ExportLayout::Flat => name.to_string(),
+            ExportLayout::Package => match package_dir(&file.content) {
+                Some(dir) => format!("{dir}/{name}"),
+                None => name.to_string(),
+            },
+        };
+        if targets.insert(name, target).is_some() {
+            bail!("Two protos named '{name}'; cannot export them side by side");
+        }
+    }
+
+    Ok(files
+        .iter()
+        .map(|file| {
+            (
+                targets[file_name(&file.name)].clone(),
+                rewrite_imports(&file.content, &targets),
+            )
+        })
+        .collect())
+}
+
+/// `acme/user/v1` for `package acme.user.v1;`
+fn package_dir(content: &str) -> Option<String> {
+    let package = ProtoSchema::parse(content).ok()?.package?;
+    Some(package.replace('.', "/"))
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Point imports of exported files at their exported paths; other imports are kept
+fn rewrite_imports(source: &str, targets: &BTreeMap<&str, String>) -> String {
+    let lines: Vec<String> = source
+        .lines()
+        .map(|line| {
+            if !line.trim_start().starts_with("import ") {
+                return line.to_string();
+            }
+            let Some(start) = line.find('"') else {
+                return line.to_string();
+            };
+            let Some(len) = line[start + 1..].find('"') else {
+                return line.to_string();
+            };
+            let import = &line[start + 1..start + 1 + len];
+            if import.starts_with("google/protobuf/") {
+                return line.to_string();
+            }
+            match targets.get(file_name(import)) {
+                Some(target) => {
+                    format!("{}{target}{}", &line[..start + 1], &line[start + 1 + len..])
+                }
+                None => line.to_string(),
+            }
+        })
+        .collect();
+
+    let mut output = lines.join("\n");
+    if source.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proto(name: &str, content: &str) -> ProtoFile {
+        ProtoFile {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            content: content.to_string(),
+            services: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_package_layout_rewrites_imports() {
+        let files = vec![
+            proto(
+                "user.proto",
+                "syntax = \"proto3\";\npackage acme.user.v1;\nimport \"common.proto\";\nimport \"google/protobuf/empty.proto\";\n",
+            ),
+            proto(
+                "common.proto",
+                "syntax = \"proto3\";\npackage acme.common;\n",
+            ),
+        ];
+
+        let planned = plan_export(&files, ExportLayout::Package).unwrap();
+        assert_eq!(planned[0].0, "acme/user/v1/user.proto");
+        assert_eq!(planned[1].0, "acme/common/common.proto");
+        assert!(
+            planned[0]
+                .1
+                .contains("import \"acme/common/common.proto\";")
+        );
+        assert!(
+            planned[0]
+                .1
+                .contains("import \"google/protobuf/empty.proto\";")
+        );
+
+        let flat = plan_export(&files, ExportLayout::Flat).unwrap();
+        assert_eq!(flat[1].0, "common.proto");
+        assert!(flat[0].1.contains("import \"common.proto\";"));
+    }
+}
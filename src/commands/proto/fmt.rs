@@ -0,0 +1,191 @@
+//! `actr proto fmt`: normalize the layout of the project's own protos
+//!
+//! `buf format` or `clang-format` is used when installed; otherwise the internal
+//! printer re-indents blocks by two spaces, sorts and deduplicates consecutive
+//! top-level `import` and `option` lines, trims trailing whitespace and collapses
+//! blank lines. Every formatter only produces the new text, so `--check` works the
+//! same way for all of them.
+
+use crate::utils::command_exists;
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+const INDENT: &str = "  ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ProtoFormatter {
+    /// buf, then clang-format, then the internal printer
+    #[default]
+    Auto,
+    /// `buf format`
+    Buf,
+    /// `clang-format`
+    ClangFormat,
+    /// The built-in printer
+    Internal,
+}
+
+impl ProtoFormatter {
+    /// The formatter `Auto` stands for on this machine
+    pub fn resolve(self) -> Self {
+        match self {
+            ProtoFormatter::Auto if command_exists("buf") => ProtoFormatter::Buf,
+            ProtoFormatter::Auto if command_exists("clang-format") => ProtoFormatter::ClangFormat,
+            ProtoFormatter::Auto => ProtoFormatter::Internal,
+            other => other,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProtoFormatter::Auto => "auto",
+            ProtoFormatter::Buf => "buf",
+            ProtoFormatter::ClangFormat => "clang-format",
+            ProtoFormatter::Internal => "internal",
+        }
+    }
+
+    /// Formatted content of `path`; the file itself is not touched
+    pub fn format_file(self, path: &Path) -> Result<String> {
+        let external = |program: &str, args: &[&str]| -> Result<String> {
+            let output = StdCommand::new(program)
+                .args(args)
+                .arg(path)
+                .output()
+                .with_context(|| format!("Failed to run {program}"))?;
+            if !output.status.success() {
+                bail!(
+                    "{program} failed on {}: {}",
+                    path.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        };
+        match self.resolve() {
+            ProtoFormatter::Buf => external("buf", &["format"]),
+            ProtoFormatter::ClangFormat => external("clang-format", &["--style=Google"]),
+            _ => {
+                let source = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                Ok(format_source(&source))
+            }
+        }
+    }
+}
+
+/// Internal printer: normalized indentation, sorted imports and options, tidy blank lines
+pub fn format_source(source: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth: usize = 0;
+    let mut in_block_comment = false;
+
+    for raw in source.lines() {
+        let line = raw.trim();
+        if line.is_empty() {
+            // One blank line at most, none at the start of a block or of the file
+            if !in_block_comment
+                && lines
+                    .last()
+                    .is_some_and(|last| !last.is_empty() && !last.ends_with('{'))
+            {
+                lines.push(String::new());
+            }
+            continue;
+        }
+
+        let continues_comment = in_block_comment;
+        let (opens, closes) = count_braces(line, &mut in_block_comment);
+        let leading_close = !continues_comment && line.starts_with('}');
+        let level = depth.saturating_sub(usize::from(leading_close));
+        let indent = INDENT.repeat(level);
+
+        if leading_close && lines.last().is_some_and(|last| last.is_empty()) {
+            lines.pop();
+        }
+        lines.push(if continues_comment && line.starts_with('*') {
+            format!("{indent} {line}")
+        } else {
+            format!("{indent}{line}")
+        });
+        depth = (depth + opens).saturating_sub(closes);
+    }
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+
+    sort_top_level_runs(&mut lines, "import ");
+    sort_top_level_runs(&mut lines, "option ");
+
+    let mut output = lines.join("\n");
+    output.push('\n');
+    output
+}
+
+/// Count `{` and `}` outside strings and comments, tracking `/* */` across lines
+fn count_braces(line: &str, in_block_comment: &mut bool) -> (usize, usize) {
+    let (mut opens, mut closes) = (0, 0);
+    let mut chars = line.chars().peekable();
+    let mut quote: Option<char> = None;
+    while let Some(c) = chars.next() {
+        if *in_block_comment {
+            if c == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                *in_block_comment = false;
+            }
+            continue;
+        }
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '/') if chars.peek() == Some(&'/') => break,
+            (None, '/') if chars.peek() == Some(&'*') => {
+                chars.next();
+                *in_block_comment = true;
+            }
+            (None, '{') => opens += 1,
+            (None, '}') => closes += 1,
+            _ => {}
+        }
+    }
+    (opens, closes)
+}
+
+/// Sort and deduplicate runs of consecutive unindented lines starting with `prefix`
+fn sort_top_level_runs(lines: &mut Vec<String>, prefix: &str) {
+    let mut start = 0;
+    while start < lines.len() {
+        if !lines[start].starts_with(prefix) {
+            start += 1;
+            continue;
+        }
+        let end = (start..lines.len())
+            .find(|&index| !lines[index].starts_with(prefix))
+            .unwrap_or(lines.len());
+        let mut run: Vec<String> = lines.drain(start..end).collect();
+        run.sort();
+        run.dedup();
+        let len = run.len();
+        lines.splice(start..start, run);
+        start += len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_internal_printer() {
+        let source = "\n\nsyntax = \"proto3\";   \npackage acme.user;\n\nimport \"b.proto\";\nimport \"a.proto\";\nimport \"a.proto\";\n\noption java_package = \"com.acme\";\noption go_package = \"acme/user\";\n\n\n\nservice UserService {\n\n      // Fetch one user {\n  rpc GetUser(Req) returns (Res);\n\n}\nmessage Req {\n    string id = 1; // \"}\"\n    /* block\n  * comment */\n  map<string, string> labels = 2;\n  oneof choice {\n  int32 a = 3;\n  }\n}\n";
+        let expected = "syntax = \"proto3\";\npackage acme.user;\n\nimport \"a.proto\";\nimport \"b.proto\";\n\noption go_package = \"acme/user\";\noption java_package = \"com.acme\";\n\nservice UserService {\n  // Fetch one user {\n  rpc GetUser(Req) returns (Res);\n}\nmessage Req {\n  string id = 1; // \"}\"\n  /* block\n   * comment */\n  map<string, string> labels = 2;\n  oneof choice {\n    int32 a = 3;\n  }\n}\n";
+        assert_eq!(format_source(source), expected);
+        assert_eq!(format_source(expected), expected);
+    }
+}
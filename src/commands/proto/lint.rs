@@ -0,0 +1,380 @@
+//! `actr proto lint`: style checks of the project's own protos
+//!
+//! Rules and their default severities:
+//!
+//! | Rule              | Default | Checks                                             |
+//! |-------------------|---------|----------------------------------------------------|
+//! | `package-naming`  | error   | a package is declared, dotted `lower_snake_case`   |
+//! | `service-suffix`  | warning | service names end with `Service`                   |
+//! | `field-naming`    | warning | field names are `lower_snake_case`                 |
+//! | `rpc-comment`     | warning | every RPC has a leading comment                    |
+//! | `enum-zero-value` | error   | the first enum value is numbered 0                 |
+//!
+//! Severities and the suffix are set in Actr.toml:
+//!
+//! ```toml
+//! [lint]
+//! service_suffix = "Api"
+//!
+//! [lint.rules]
+//! rpc-comment = "off"
+//! field-naming = "error"
+//! ```
+
+use crate::core::DefaultProtoProcessor;
+use crate::core::profile;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const DEFAULT_SERVICE_SUFFIX: &str = "Service";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    /// The file does not parse; always an error
+    Syntax,
+    PackageNaming,
+    ServiceSuffix,
+    FieldNaming,
+    RpcComment,
+    EnumZeroValue,
+}
+
+impl LintRule {
+    /// Rules that can be configured in `[lint.rules]`
+    pub const CONFIGURABLE: [LintRule; 5] = [
+        LintRule::PackageNaming,
+        LintRule::ServiceSuffix,
+        LintRule::FieldNaming,
+        LintRule::RpcComment,
+        LintRule::EnumZeroValue,
+    ];
+
+    fn default_severity(self) -> Severity {
+        match self {
+            LintRule::Syntax | LintRule::PackageNaming | LintRule::EnumZeroValue => Severity::Error,
+            LintRule::ServiceSuffix | LintRule::FieldNaming | LintRule::RpcComment => {
+                Severity::Warning
+            }
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintRule::Syntax => "syntax",
+            LintRule::PackageNaming => "package-naming",
+            LintRule::ServiceSuffix => "service-suffix",
+            LintRule::FieldNaming => "field-naming",
+            LintRule::RpcComment => "rpc-comment",
+            LintRule::EnumZeroValue => "enum-zero-value",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Off,
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Off => "off",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// The `[lint]` table of Actr.toml
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    pub service_suffix: Option<String>,
+    /// Severity per rule name, e.g. `rpc-comment = "off"`
+    pub rules: BTreeMap<String, Severity>,
+}
+
+impl LintConfig {
+    pub fn from_config_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let Some(lint) = profile::load_value(path)?.get("lint").cloned() else {
+            return Ok(Self::default());
+        };
+        let config: Self = lint
+            .try_into()
+            .with_context(|| format!("Invalid [lint] table in {}", path.display()))?;
+        for name in config.rules.keys() {
+            if !LintRule::CONFIGURABLE
+                .iter()
+                .any(|rule| rule.as_str() == name)
+            {
+                let known: Vec<&str> = LintRule::CONFIGURABLE.iter().map(|r| r.as_str()).collect();
+                bail!(
+                    "Unknown lint rule '{name}' in [lint.rules]; known rules: {}",
+                    known.join(", ")
+                );
+            }
+        }
+        Ok(config)
+    }
+
+    fn severity(&self, rule: LintRule) -> Severity {
+        if rule == LintRule::Syntax {
+            return Severity::Error;
+        }
+        self.rules
+            .get(rule.as_str())
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+
+    fn service_suffix(&self) -> &str {
+        self.service_suffix
+            .as_deref()
+            .unwrap_or(DEFAULT_SERVICE_SUFFIX)
+    }
+}
+
+/// One rule violation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub file: PathBuf,
+    pub line: usize,
+    pub rule: LintRule,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl std::fmt::Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}",
+            self.file.display(),
+            self.line,
+            self.severity,
+            self.rule.as_str(),
+            self.message
+        )
+    }
+}
+
+/// `.proto` files below `dirs` (files are taken as is), skipping `remote/` dependencies
+pub fn collect_proto_files(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for dir in dirs {
+        if dir.is_file() {
+            files.push(dir.clone());
+            continue;
+        }
+        let entries = WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|entry| entry.depth() != 1 || entry.file_name() != "remote")
+            .flatten();
+        files.extend(
+            entries
+                .filter(|entry| {
+                    entry.file_type().is_file()
+                        && entry.path().extension().is_some_and(|ext| ext == "proto")
+                })
+                .map(|entry| entry.into_path()),
+        );
+    }
+    files.sort();
+    files
+}
+
+/// Lint each file, returning findings ordered by file and line
+pub fn lint_files(files: &[PathBuf], config: &LintConfig) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for file in files {
+        let content = std::fs::read_to_string(file).unwrap_or_default();
+        let mut report = |line: usize, rule: LintRule, message: String| {
+            let severity = config.severity(rule);
+            if severity != Severity::Off {
+                findings.push(Finding {
+                    file: file.clone(),
+                    line,
+                    rule,
+                    severity,
+                    message,
+                });
+            }
+        };
+        match DefaultProtoProcessor::parse_file(file) {
+            Ok(schema) => lint_schema(&schema, &content, config, &mut report),
+            Err(e) => report(1, LintRule::Syntax, format!("{e:#}")),
+        }
+    }
+    findings.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    findings
+}
+
+fn lint_schema(
+    schema: &crate::proto_parser::ProtoSchema,
+    content: &str,
+    config: &LintConfig,
+    report: &mut impl FnMut(usize, LintRule, String),
+) {
+    match &schema.package {
+        None => report(
+            1,
+            LintRule::PackageNaming,
+            "no package declared".to_string(),
+        ),
+        Some(package) if !package.split('.').all(is_lower_snake_case) => report(
+            package_line(content),
+            LintRule::PackageNaming,
+            format!("package '{package}' should be dotted lower_snake_case"),
+        ),
+        Some(_) => {}
+    }
+
+    let suffix = config.service_suffix();
+    for service in &schema.services {
+        if !service.name.ends_with(suffix) {
+            report(
+                service.line,
+                LintRule::ServiceSuffix,
+                format!("service '{}' should end with '{suffix}'", service.name),
+            );
+        }
+        for rpc in &service.methods {
+            if rpc.comment.as_deref().is_none_or(|c| c.trim().is_empty()) {
+                report(
+                    rpc.line,
+                    LintRule::RpcComment,
+                    format!("rpc '{}.{}' has no comment", service.name, rpc.name),
+                );
+            }
+        }
+    }
+
+    for message in &schema.messages {
+        for field in &message.fields {
+            if !is_lower_snake_case(&field.name) {
+                report(
+                    field.line,
+                    LintRule::FieldNaming,
+                    format!(
+                        "field '{}.{}' should be lower_snake_case",
+                        message.name, field.name
+                    ),
+                );
+            }
+        }
+    }
+
+    for enumeration in &schema.enums {
+        match enumeration.values.first() {
+            Some(first) if first.number == 0 => {}
+            Some(first) => report(
+                first.line,
+                LintRule::EnumZeroValue,
+                format!(
+                    "first value of enum '{}' is {} = {}; it must be numbered 0",
+                    enumeration.name, first.name, first.number
+                ),
+            ),
+            None => report(
+                enumeration.line,
+                LintRule::EnumZeroValue,
+                format!("enum '{}' has no zero value", enumeration.name),
+            ),
+        }
+    }
+}
+
+fn is_lower_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && !name.contains("__")
+        && !name.ends_with('_')
+}
+
+fn package_line(content: &str) -> usize {
+    content
+        .lines()
+        .position(|line| line.trim_start().starts_with("package "))
+        .map_or(1, |index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const PROTO: &str = r#"syntax = "proto3";
+package Acme.user;
+
+service UserApi {
+  // Fetch one user
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc ListUsers(GetUserRequest) returns (User);
+}
+
+message GetUserRequest {
+  string userId = 1;
+}
+
+message User {
+  string name = 1;
+}
+
+enum Role {
+  ADMIN = 1;
+  ROLE_UNSPECIFIED = 0;
+}
+"#;
+
+    #[test]
+    fn test_lint_rules_and_severities() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("user.proto");
+        std::fs::write(&file, PROTO).unwrap();
+
+        let findings = lint_files(std::slice::from_ref(&file), &LintConfig::default());
+        let summary: Vec<(usize, LintRule, Severity)> = findings
+            .iter()
+            .map(|f| (f.line, f.rule, f.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, LintRule::PackageNaming, Severity::Error),
+                (4, LintRule::ServiceSuffix, Severity::Warning),
+                (7, LintRule::RpcComment, Severity::Warning),
+                (11, LintRule::FieldNaming, Severity::Warning),
+                (19, LintRule::EnumZeroValue, Severity::Error),
+            ]
+        );
+
+        let config: LintConfig = toml::from_str(
+            "service_suffix = \"Api\"\n[rules]\nrpc-comment = \"off\"\nfield-naming = \"error\"\n",
+        )
+        .unwrap();
+        let rules: Vec<(LintRule, Severity)> = lint_files(&[file], &config)
+            .iter()
+            .map(|f| (f.rule, f.severity))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![
+                (LintRule::PackageNaming, Severity::Error),
+                (LintRule::FieldNaming, Severity::Error),
+                (LintRule::EnumZeroValue, Severity::Error),
+            ]
+        );
+    }
+}
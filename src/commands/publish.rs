@@ -0,0 +1,323 @@
+//! Publish command implementation - register local services with the registry
+//!
+//! Bundles the proto files listed in `exports` of Actr.toml, computes the
+//! semantic service fingerprint and announces the resulting ServiceSpec to the
+//! signaling server, which creates or updates the registry entry. With
+//! `--signing-key` the fingerprint is signed and the signature attached as a tag.
+//! The project's own dependencies are announced as `depends-on:` tags.
+
+use crate::commands::lock::SERVICE_FINGERPRINT_PREFIX;
+use crate::core::trust;
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, DependencyRequirement,
+};
+use crate::human_println;
+use actr_config::LockFile;
+use actr_protocol::{ActrTypeExt, ServiceSpec, service_spec};
+use actr_version::{Fingerprint, ProtoFile};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use owo_colors::OwoColorize;
+use std::path::{Path, PathBuf};
+
+/// Tag applied when no `--tag` is given
+const DEFAULT_TAG: &str = "latest";
+
+/// Publish command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Publish exported protos to the registry",
+    long_about = "Bundle the proto files listed in 'exports' of Actr.toml, compute the service fingerprint and register or update the service in the signaling registry.\n\nExamples:\n  actr publish --dry-run          # Show what would be published\n  actr publish                    # Publish with the 'latest' tag\n  actr publish -t v1.2.0 -t latest"
+)]
+pub struct PublishCommand {
+    /// Show the service spec without contacting the registry
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Tags to attach to the published version (repeatable, defaults to 'latest')
+    #[arg(short, long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
+    /// ed25519 key (PKCS#8 PEM or base64 seed) used to sign the service fingerprint
+    #[arg(long, value_name = "FILE")]
+    pub signing_key: Option<PathBuf>,
+}
+
+#[async_trait]
+impl Command for PublishCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        if context.is_offline() && !self.dry_run {
+            return Err(ActrCliError::InvalidArgument {
+                message: "'actr publish' contacts the registry; run it without --offline or use --dry-run"
+                    .to_string(),
+            }
+            .into());
+        }
+
+        let (config_manager, service_discovery) = (
+            context.container.get_config_manager()?,
+            context.container.get_service_discovery()?,
+        );
+        let project_root = config_manager.get_project_root().to_path_buf();
+        let config = config_manager
+            .load_config(project_root.join("Actr.toml").as_path())
+            .await?;
+
+        if config.exports.is_empty() {
+            return Err(ActrCliError::InvalidProject {
+                message: "No proto files in 'exports' of Actr.toml; nothing to publish".to_string(),
+            }
+            .into());
+        }
+
+        let proto_files: Vec<ProtoFile> = config
+            .exports
+            .iter()
+            .map(|pf| ProtoFile {
+                name: pf.file_name().unwrap_or("unknown.proto").to_string(),
+                content: pf.content.clone(),
+                path: Some(pf.path.to_string_lossy().to_string()),
+            })
+            .collect();
+        let mut tags = if self.tags.is_empty() {
+            vec![DEFAULT_TAG.to_string()]
+        } else {
+            self.tags.clone()
+        };
+        // Declare our own dependencies so consumers can resolve them transitively
+        let lock_file = LockFile::from_file(&project_root.join("Actr.lock.toml")).ok();
+        for dependency in &config.dependencies {
+            let fingerprint = lock_file
+                .as_ref()
+                .and_then(|lock| lock.dependencies.iter().find(|d| d.name == dependency.name))
+                .map(|locked| locked.fingerprint.clone())
+                .or_else(|| dependency.fingerprint.clone());
+            let tag = DependencyRequirement {
+                name: dependency.name.clone(),
+                fingerprint,
+            }
+            .to_tag();
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        let mut spec = build_service_spec(
+            &config.package.name,
+            config.package.description.clone(),
+            &proto_files,
+            tags,
+        )?;
+
+        let public_key = match &self.signing_key {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let key = trust::parse_signing_key(&content)
+                    .with_context(|| format!("Invalid signing key {}", path.display()))?;
+                spec.tags
+                    .push(trust::sign(&key, &spec.name, &spec.fingerprint));
+                Some(trust::encode_public_key(&key.verifying_key()))
+            }
+            None => None,
+        };
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "name": spec.name,
+                "actr_type": config.package.actr_type.to_string_repr(),
+                "fingerprint": spec.fingerprint,
+                "tags": spec.tags,
+                "public_key": public_key,
+                "dry_run": self.dry_run,
+                "protobufs": spec
+                    .protobufs
+                    .iter()
+                    .map(|p| serde_json::json!({"package": p.package, "fingerprint": p.fingerprint}))
+                    .collect::<Vec<_>>(),
+            });
+            if !self.dry_run {
+                service_discovery.publish_service(spec).await?;
+            }
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &report,
+            )?));
+        }
+
+        human_println!(
+            "📦 {} ({})",
+            spec.name.cyan(),
+            config.package.actr_type.to_string_repr()
+        );
+        human_println!("   fingerprint: {}", spec.fingerprint);
+        let display_tags: Vec<&str> = spec
+            .tags
+            .iter()
+            .map(String::as_str)
+            .filter(|tag| !tag.starts_with(trust::SIGNATURE_TAG_PREFIX))
+            .collect();
+        human_println!("   tags: {}", display_tags.join(", "));
+        if let Some(public_key) = &public_key {
+            human_println!("   signed by: {}", public_key);
+        }
+        for protobuf in &spec.protobufs {
+            human_println!("   • {} ({})", protobuf.package, protobuf.fingerprint);
+        }
+
+        if self.dry_run {
+            return Ok(CommandResult::Success(format!(
+                "{} Dry run: {} proto files would be published",
+                "ℹ️".blue(),
+                spec.protobufs.len()
+            )));
+        }
+
+        let name = spec.name.clone();
+        service_discovery
+            .publish_service(spec)
+            .await
+            .with_context(|| format!("Failed to publish '{name}'"))?;
+
+        Ok(CommandResult::Success(format!(
+            "{} Published {}",
+            "✅".green(),
+            name
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![
+            ComponentType::ConfigManager,
+            ComponentType::ServiceDiscovery,
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "publish"
+    }
+
+    fn description(&self) -> &str {
+        "Publish exported protos to the registry"
+    }
+}
+
+/// Build the ServiceSpec announced to the registry
+///
+/// Files are ordered by name so the spec does not depend on the order of `exports`.
+/// Each protobuf carries its own semantic fingerprint; the package is the file stem,
+/// matching how fetched specs are written back as `<package>.proto`.
+pub fn build_service_spec(
+    name: &str,
+    description: Option<String>,
+    proto_files: &[ProtoFile],
+    tags: Vec<String>,
+) -> Result<ServiceSpec> {
+    let mut files: Vec<ProtoFile> = proto_files
+        .iter()
+        .map(|file| ProtoFile {
+            name: file.name.clone(),
+            content: file.content.clone(),
+            path: file.path.clone(),
+        })
+        .collect();
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let protobufs = files
+        .iter()
+        .map(|file| {
+            let fingerprint = Fingerprint::calculate_proto_semantic_fingerprint(&file.content)
+                .with_context(|| format!("Failed to calculate fingerprint of {}", file.name))?;
+            let package = Path::new(&file.name)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| file.name.clone());
+            Ok(service_spec::Protobuf {
+                package,
+                content: file.content.clone(),
+                fingerprint,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let service_fingerprint = Fingerprint::calculate_service_semantic_fingerprint(&files)
+        .context("Failed to calculate service fingerprint")?;
+
+    Ok(ServiceSpec {
+        name: name.to_string(),
+        description,
+        fingerprint: format!("{SERVICE_FINGERPRINT_PREFIX}{service_fingerprint}"),
+        protobufs,
+        published_at: Some(chrono::Utc::now().timestamp()),
+        tags,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_PROTO: &str = r#"syntax = "proto3";
+package user;
+
+message GetUserRequest { string id = 1; }
+message GetUserResponse { string name = 1; }
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (GetUserResponse);
+}
+"#;
+
+    const AUTH_PROTO: &str = r#"syntax = "proto3";
+package auth;
+
+message Token { string value = 1; }
+"#;
+
+    fn proto(name: &str, content: &str) -> ProtoFile {
+        ProtoFile {
+            name: name.to_string(),
+            content: content.to_string(),
+            path: Some(format!("protos/local/{name}")),
+        }
+    }
+
+    #[test]
+    fn test_build_service_spec_fingerprints() {
+        let files = vec![
+            proto("auth.proto", AUTH_PROTO),
+            proto("user.proto", USER_PROTO),
+        ];
+        let spec =
+            build_service_spec("user-service", None, &files, vec!["v1".to_string()]).unwrap();
+
+        let expected = Fingerprint::calculate_service_semantic_fingerprint(&files).unwrap();
+        assert_eq!(
+            spec.fingerprint,
+            format!("{SERVICE_FINGERPRINT_PREFIX}{expected}")
+        );
+        assert_eq!(spec.tags, vec!["v1".to_string()]);
+
+        let packages: Vec<&str> = spec.protobufs.iter().map(|p| p.package.as_str()).collect();
+        assert_eq!(packages, vec!["auth", "user"]);
+        assert_eq!(
+            spec.protobufs[1].fingerprint,
+            Fingerprint::calculate_proto_semantic_fingerprint(USER_PROTO).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_build_service_spec_ignores_export_order() {
+        let forward = vec![
+            proto("user.proto", USER_PROTO),
+            proto("auth.proto", AUTH_PROTO),
+        ];
+        let reverse = vec![
+            proto("auth.proto", AUTH_PROTO),
+            proto("user.proto", USER_PROTO),
+        ];
+        let a = build_service_spec("svc", None, &forward, Vec::new()).unwrap();
+        let b = build_service_spec("svc", None, &reverse, Vec::new()).unwrap();
+        assert_eq!(a.fingerprint, b.fingerprint);
+        assert_eq!(a.protobufs, b.protobufs);
+    }
+}
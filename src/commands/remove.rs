@@ -0,0 +1,237 @@
+//! Remove Command Implementation
+//!
+//! Symmetric counterpart of `actr install`: drops dependencies from Actr.toml,
+//! rewrites Actr.lock.toml and purges cached proto files.
+
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, DependencySpec,
+    UninstallResult,
+};
+use crate::human_println;
+use crate::tr;
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Args;
+
+/// Remove command
+#[derive(Args, Debug)]
+#[command(
+    about = "Remove service dependencies",
+    long_about = "Remove service dependencies from Actr.toml, Actr.lock.toml and the local proto cache.\n\nExamples:\n  actr remove user-service              # Remove by alias or service name\n  actr remove user-service order-service"
+)]
+pub struct RemoveCommand {
+    /// Dependency aliases or service names to remove
+    #[arg(value_name = "PACKAGE", required = true)]
+    pub packages: Vec<String>,
+}
+
+#[async_trait]
+impl Command for RemoveCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        if !std::path::Path::new("Actr.toml").exists() {
+            return Err(ActrCliError::InvalidProject {
+                message: tr!("common.not_a_project"),
+            }
+            .into());
+        }
+
+        human_println!(
+            "{}",
+            tr!("remove.header", packages = self.packages.join(" "))
+        );
+
+        let install_pipeline = context.container.get_install_pipeline()?;
+
+        let config_manager = install_pipeline.config_manager();
+        let config = config_manager
+            .load_config(
+                config_manager
+                    .get_project_root()
+                    .join("Actr.toml")
+                    .as_path(),
+            )
+            .await?;
+
+        let configured: Vec<DependencySpec> = config
+            .dependencies
+            .iter()
+            .map(|d| DependencySpec {
+                alias: d.alias.clone(),
+                name: d.name.clone(),
+                actr_type: d.actr_type.clone(),
+                fingerprint: d.fingerprint.clone(),
+                constraint: Default::default(),
+            })
+            .collect();
+
+        let specs = Self::select_specs(&configured, &self.packages)?;
+
+        human_println!("{}", tr!("remove.removing"));
+        for spec in &specs {
+            if spec.alias == spec.name {
+                human_println!("  ├─ 📋 {}", spec.alias);
+            } else {
+                human_println!(
+                    "  ├─ 📋 {}",
+                    tr!("remove.alias_of", alias = spec.alias, name = spec.name)
+                );
+            }
+        }
+
+        match install_pipeline.uninstall_dependencies(&specs).await {
+            Ok(result) => {
+                human_println!("  └─ {}", tr!("remove.complete"));
+                self.display_remove_success(&result);
+                Ok(CommandResult::Success(result.summary()))
+            }
+            Err(e) => {
+                human_println!("  └─ {}", tr!("remove.failed"));
+                Err(ActrCliError::Dependency {
+                    message: tr!("remove.remove_failed", error = e),
+                }
+                .into())
+            }
+        }
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        // Removal goes through the install pipeline for backup/rollback
+        vec![
+            ComponentType::ConfigManager,
+            ComponentType::DependencyResolver,
+            ComponentType::ServiceDiscovery,
+            ComponentType::NetworkValidator,
+            ComponentType::FingerprintValidator,
+            ComponentType::ProtoProcessor,
+            ComponentType::CacheManager,
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "remove"
+    }
+
+    fn description(&self) -> &str {
+        "Remove service dependencies and their cached protos"
+    }
+}
+
+impl RemoveCommand {
+    pub fn new(packages: Vec<String>) -> Self {
+        Self { packages }
+    }
+
+    // Create from clap Args
+    pub fn from_args(args: &RemoveCommand) -> Self {
+        RemoveCommand {
+            packages: args.packages.clone(),
+        }
+    }
+
+    /// Match requested packages against configured dependencies.
+    ///
+    /// An alias matches a single entry; a service name matches every alias
+    /// that points at that service.
+    fn select_specs(
+        configured: &[DependencySpec],
+        packages: &[String],
+    ) -> Result<Vec<DependencySpec>> {
+        let mut selected: Vec<DependencySpec> = Vec::new();
+
+        for package in packages {
+            let matches: Vec<&DependencySpec> =
+                if let Some(by_alias) = configured.iter().find(|spec| &spec.alias == package) {
+                    vec![by_alias]
+                } else {
+                    configured
+                        .iter()
+                        .filter(|spec| &spec.name == package)
+                        .collect()
+                };
+
+            if matches.is_empty() {
+                return Err(ActrCliError::Dependency {
+                    message: tr!("common.dependency_not_found", name = package),
+                }
+                .into());
+            }
+
+            for spec in matches {
+                if !selected.iter().any(|s| s.alias == spec.alias) {
+                    selected.push(spec.clone());
+                }
+            }
+        }
+
+        Ok(selected)
+    }
+
+    fn display_remove_success(&self, result: &UninstallResult) {
+        human_println!();
+        human_println!("{}", tr!("remove.success"));
+        human_println!(
+            "{}",
+            tr!(
+                "remove.removed_count",
+                count = result.removed_dependencies.len()
+            )
+        );
+        human_println!(
+            "{}",
+            tr!("remove.purged_count", count = result.purged_services.len())
+        );
+
+        if result.updated_lock_file {
+            human_println!("{}", tr!("remove.lock_updated"));
+        }
+
+        if !result.warnings.is_empty() {
+            human_println!();
+            human_println!("{}", tr!("common.warnings"));
+            for warning in &result.warnings {
+                human_println!("   • {warning}");
+            }
+        }
+
+        human_println!();
+        human_println!("{}", tr!("remove.tip_regenerate"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(alias: &str, name: &str) -> DependencySpec {
+        DependencySpec {
+            alias: alias.to_string(),
+            name: name.to_string(),
+            actr_type: None,
+            fingerprint: None,
+            constraint: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_select_specs_by_alias_and_name() {
+        let configured = vec![
+            spec("echo", "echo-service"),
+            spec("echo-backup", "echo-service"),
+            spec("user", "user-service"),
+        ];
+
+        let by_alias = RemoveCommand::select_specs(&configured, &["echo".to_string()]).unwrap();
+        assert_eq!(by_alias.len(), 1);
+        assert_eq!(by_alias[0].alias, "echo");
+
+        let by_name =
+            RemoveCommand::select_specs(&configured, &["echo-service".to_string()]).unwrap();
+        assert_eq!(by_name.len(), 2);
+    }
+
+    #[test]
+    fn test_select_specs_unknown_package() {
+        let configured = vec![spec("user", "user-service")];
+        assert!(RemoveCommand::select_specs(&configured, &["missing".to_string()]).is_err());
+    }
+}
@@ -1,47 +1,109 @@
 //! Run command implementation
+//!
+//! Executes entries from the `[scripts]` section of Actr.toml, like `npm run`.
+//! Scripts run through the platform shell in the project root with project
+//! metadata exported as `ACTR_*` environment variables; their output is streamed
+//! and their exit code becomes the exit code of `actr run`.
+//!
+//! With `--check` the command first verifies, make-style, that Actr.lock.toml
+//! covers every dependency in Actr.toml and that generated code is newer than
+//! the proto files, offering to run `actr install` / `actr gen` when it is not.
 
 use crate::commands::Command;
+use crate::core::profile;
 use crate::error::{ActrCliError, Result};
-use crate::utils::{execute_command_streaming, is_actr_project, warn_if_not_actr_project};
-use actr_config::ConfigParser;
+use crate::utils::{is_actr_project, warn_if_not_actr_project};
+use actr_config::{Config, LockFile};
+use actr_protocol::ActrTypeExt;
 use async_trait::async_trait;
 use clap::Args;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tokio::process::Command as TokioCommand;
 use tracing::info;
+use walkdir::WalkDir;
 
 #[derive(Args)]
+#[command(
+    about = "Run project scripts",
+    long_about = "Run a script from the [scripts] section of Actr.toml through the shell.\n\nThe script sees ACTR_PROJECT_NAME, ACTR_ACTR_TYPE, ACTR_SIGNALING_URL, ACTR_REALM_ID and ACTR_PROJECT_ROOT in its environment. Arguments after the script name are appended to the command.\n\nExamples:\n  actr run                     # Run the \"run\" script\n  actr run test -- --nocapture # Pass extra arguments\n  actr run --list              # Show available scripts"
+)]
 pub struct RunCommand {
     /// Script name to run (defaults to "run")
     pub script_name: Option<String>,
+
+    /// Extra arguments appended to the script command
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub args: Vec<String>,
+
+    /// List the scripts defined in Actr.toml
+    #[arg(long)]
+    pub list: bool,
+
+    /// Verify that the lock file and generated code are up to date before running
+    #[arg(long)]
+    pub check: bool,
+
+    /// Run 'actr install' / 'actr gen' without prompting when stale (implies --check)
+    #[arg(long)]
+    pub auto_fix: bool,
+
+    /// Generated code directory compared against the protos
+    #[arg(long, default_value = "src/generated")]
+    pub generated_dir: PathBuf,
 }
 
 #[async_trait]
 impl Command for RunCommand {
     async fn execute(&self) -> Result<()> {
+        match self.run().await? {
+            0 => Ok(()),
+            code => Err(ActrCliError::command_error(format!(
+                "Script '{}' exited with code {}",
+                self.script_name(),
+                code
+            ))),
+        }
+    }
+}
+
+impl RunCommand {
+    /// Script to run, `run` when none is given
+    pub fn script_name(&self) -> &str {
+        self.script_name.as_deref().unwrap_or("run")
+    }
+
+    /// Run the selected script and return its exit code
+    pub async fn run(&self) -> Result<i32> {
         info!("🚀 Running Actor-RTC project");
 
         // Check that we're in an Actor-RTC project
         warn_if_not_actr_project();
 
-        let _project_root = std::env::current_dir()?;
-
-        // Load configuration if available
-        let config = if is_actr_project() {
-            Some(ConfigParser::from_file("Actr.toml")?)
-        } else {
-            None
-        };
-
-        // Get script command from configuration
-        let script_name = self.script_name.as_deref().unwrap_or("run");
-
-        let Some(ref config) = config else {
+        if !is_actr_project() {
             return Err(ActrCliError::command_error(
                 "No Actr.toml found. Run 'actr init' to create a project.".to_string(),
             ));
-        };
+        }
 
+        let project_root = std::env::current_dir()?;
+        let config = profile::parse_config(Path::new("Actr.toml"))?;
         let available_scripts = config.list_scripts();
 
+        if self.list {
+            if available_scripts.is_empty() {
+                println!("No scripts defined in Actr.toml");
+            }
+            for name in &available_scripts {
+                if let Some(command) = config.get_script(name) {
+                    println!("{name}: {command}");
+                }
+            }
+            return Ok(0);
+        }
+
+        let script_name = self.script_name();
         let Some(command) = config.get_script(script_name) else {
             if available_scripts.is_empty() {
                 return Err(ActrCliError::command_error(
@@ -55,52 +117,385 @@ impl Command for RunCommand {
             )));
         };
 
-        info!("📜 Executing script '{}': {}", script_name, command);
+        if self.check || self.auto_fix {
+            self.ensure_fresh(&project_root, &config).await?;
+        }
+
+        let command_line = script_command_line(command, &self.args);
+        info!("📜 Executing script '{}': {}", script_name, command_line);
+
+        let mut env = script_env(&config);
+        env.push((
+            "ACTR_PROJECT_ROOT".to_string(),
+            project_root.display().to_string(),
+        ));
+
+        let mut child = shell_command(&command_line);
+        child.current_dir(&project_root).envs(env);
+        let status = child.status().await.map_err(|e| {
+            ActrCliError::command_error(format!("Failed to start script '{script_name}': {e}"))
+        })?;
+
+        Ok(exit_code(status))
+    }
+
+    /// Check lock file and generated code freshness, fixing or failing when stale
+    async fn ensure_fresh(&self, project_root: &Path, config: &Config) -> Result<()> {
+        let lock_path = project_root.join("Actr.lock.toml");
+        let lock_file = if lock_path.exists() {
+            Some(LockFile::from_file(&lock_path).map_err(|e| {
+                ActrCliError::command_error(format!("Failed to read Actr.lock.toml: {e}"))
+            })?)
+        } else {
+            None
+        };
+
+        let lock_issues = lock_staleness(config, lock_file.as_ref());
+        if !lock_issues.is_empty() {
+            self.fix("Actr.lock.toml is out of date", &lock_issues, "install")
+                .await?;
+        }
+
+        let generated_dir = project_root.join(&self.generated_dir);
+        if let Some(issue) = generated_staleness(&project_root.join("protos"), &generated_dir) {
+            self.fix("Generated code is out of date", &[issue], "gen")
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Report a stale step and run `actr <subcommand>` if allowed to
+    async fn fix(&self, summary: &str, issues: &[String], subcommand: &str) -> Result<()> {
+        eprintln!("⚠️  {summary}:");
+        for issue in issues {
+            eprintln!("   • {issue}");
+        }
 
-        // Execute the script command
-        self.run_script_command(command).await?;
+        let proceed = self.auto_fix
+            || (std::io::stdin().is_terminal()
+                && dialoguer::Confirm::new()
+                    .with_prompt(format!("Run 'actr {subcommand}' now?"))
+                    .default(true)
+                    .interact()
+                    .unwrap_or(false));
+        if !proceed {
+            return Err(ActrCliError::command_error(format!(
+                "{summary}. Run 'actr {subcommand}' or pass --auto-fix"
+            )));
+        }
 
+        info!("🔧 Running 'actr {}'", subcommand);
+        let status = TokioCommand::new(std::env::current_exe()?)
+            .arg(subcommand)
+            .status()
+            .await?;
+        if !status.success() {
+            return Err(ActrCliError::command_error(format!(
+                "'actr {subcommand}' failed with exit code {}",
+                exit_code(status)
+            )));
+        }
         Ok(())
     }
 }
 
-impl RunCommand {
-    async fn run_script_command(&self, command: &str) -> Result<()> {
-        // Parse command into program and args
-        let parts: Vec<&str> = command.split_whitespace().collect();
-        if parts.is_empty() {
-            return Err(ActrCliError::command_error("Empty command".to_string()));
+/// Dependencies in Actr.toml that the lock file does not cover
+pub fn lock_staleness(config: &Config, lock_file: Option<&LockFile>) -> Vec<String> {
+    if config.dependencies.is_empty() {
+        return Vec::new();
+    }
+    let Some(lock_file) = lock_file else {
+        return vec!["Actr.lock.toml does not exist".to_string()];
+    };
+
+    let mut issues = Vec::new();
+    for dependency in &config.dependencies {
+        let Some(locked) = lock_file.get_dependency(&dependency.name) else {
+            issues.push(format!("'{}' is not locked", dependency.alias));
+            continue;
+        };
+        if let Some(actr_type) = &dependency.actr_type
+            && actr_type.to_string_repr() != locked.actr_type
+        {
+            issues.push(format!(
+                "'{}' is declared as {} but locked as {}",
+                dependency.alias,
+                actr_type.to_string_repr(),
+                locked.actr_type
+            ));
+        }
+        if let Some(fingerprint) = &dependency.fingerprint
+            && fingerprint != &locked.fingerprint
+        {
+            issues.push(format!(
+                "'{}' requires fingerprint {} but {} is locked",
+                dependency.alias, fingerprint, locked.fingerprint
+            ));
         }
+    }
+    issues
+}
+
+/// Compare the newest proto with the newest generated file, like make
+pub fn generated_staleness(proto_dir: &Path, generated_dir: &Path) -> Option<String> {
+    let (proto, proto_time) = newest_file(proto_dir, |path| {
+        path.extension().is_some_and(|ext| ext == "proto")
+    })?;
+    let Some((_, generated_time)) = newest_file(generated_dir, |_| true) else {
+        return Some(format!("{} does not exist", generated_dir.display()));
+    };
+    (proto_time > generated_time).then(|| {
+        format!(
+            "{} is newer than the code in {}",
+            proto.display(),
+            generated_dir.display()
+        )
+    })
+}
+
+fn newest_file(dir: &Path, filter: impl Fn(&Path) -> bool) -> Option<(PathBuf, SystemTime)> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && filter(entry.path()))
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((entry.into_path(), modified))
+        })
+        .max_by_key(|(_, modified)| *modified)
+}
+
+/// Project metadata exported to scripts
+pub fn script_env(config: &Config) -> Vec<(String, String)> {
+    vec![
+        ("ACTR_PROJECT_NAME".to_string(), config.package.name.clone()),
+        (
+            "ACTR_ACTR_TYPE".to_string(),
+            config.package.actr_type.to_string_repr(),
+        ),
+        (
+            "ACTR_SIGNALING_URL".to_string(),
+            config.signaling_url.to_string(),
+        ),
+        (
+            "ACTR_REALM_ID".to_string(),
+            config.realm.realm_id.to_string(),
+        ),
+    ]
+}
+
+/// Append extra arguments to a script, quoting them for the shell
+fn script_command_line(command: &str, args: &[String]) -> String {
+    let mut line = command.to_string();
+    for arg in args {
+        line.push(' ');
+        line.push_str(&shell_quote(arg));
+    }
+    line
+}
+
+#[cfg(unix)]
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
 
-        let program = parts[0];
-        let args = parts[1..].to_vec();
+#[cfg(not(unix))]
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        arg.to_string()
+    } else {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    }
+}
 
-        info!("▶️  Executing: {} {}", program, args.join(" "));
+#[cfg(unix)]
+pub(crate) fn shell_command(command_line: &str) -> TokioCommand {
+    let mut command = TokioCommand::new("sh");
+    command.arg("-c").arg(command_line);
+    command
+}
 
-        // Execute the command
-        execute_command_streaming(program, &args, None).await
+#[cfg(not(unix))]
+pub(crate) fn shell_command(command_line: &str) -> TokioCommand {
+    let mut command = TokioCommand::new("cmd");
+    command.arg("/C").arg(command_line);
+    command
+}
+
+/// Exit code of a finished script; a signal-terminated script maps to 128 + signal
+fn exit_code(status: std::process::ExitStatus) -> i32 {
+    if let Some(code) = status.code() {
+        return code;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
     }
+    1
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use actr_config::ConfigParser;
+    use tempfile::TempDir;
+
+    fn command(script_name: Option<&str>) -> RunCommand {
+        RunCommand {
+            script_name: script_name.map(str::to_string),
+            args: Vec::new(),
+            list: false,
+            check: false,
+            auto_fix: false,
+            generated_dir: PathBuf::from("src/generated"),
+        }
+    }
 
     #[test]
     fn test_script_name_default() {
-        let cmd = RunCommand { script_name: None };
-
-        let script_name = cmd.script_name.as_deref().unwrap_or("run");
-        assert_eq!(script_name, "run");
+        assert_eq!(command(None).script_name(), "run");
     }
 
     #[test]
     fn test_script_name_custom() {
-        let cmd = RunCommand {
-            script_name: Some("test".to_string()),
+        assert_eq!(command(Some("test")).script_name(), "test");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_script_command_line_quotes_args() {
+        let args = vec!["--nocapture".to_string(), "it's here".to_string()];
+        assert_eq!(
+            script_command_line("cargo test", &args),
+            r"cargo test --nocapture 'it'\''s here'"
+        );
+    }
+
+    #[test]
+    fn test_script_env() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Actr.toml");
+        std::fs::write(
+            &path,
+            r#"edition = 1
+exports = []
+
+[package]
+name = "echo-app"
+
+[package.actr_type]
+manufacturer = "acme"
+name = "echo"
+
+[dependencies]
+
+[system.signaling]
+url = "ws://127.0.0.1:8080"
+
+[system.deployment]
+realm_id = 7
+
+[scripts]
+run = "cargo run"
+"#,
+        )
+        .unwrap();
+        let config = ConfigParser::from_file(&path).unwrap();
+
+        let env = script_env(&config);
+        let get = |key: &str| {
+            env.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+                .unwrap()
+        };
+        assert_eq!(get("ACTR_PROJECT_NAME"), "echo-app");
+        assert_eq!(get("ACTR_ACTR_TYPE"), "acme+echo");
+        assert!(get("ACTR_SIGNALING_URL").starts_with("ws://127.0.0.1:8080"));
+        assert_eq!(get("ACTR_REALM_ID"), "7");
+
+        // No dependencies: the lock file is never stale
+        assert!(lock_staleness(&config, None).is_empty());
+    }
+
+    #[test]
+    fn test_lock_staleness() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Actr.toml");
+        std::fs::write(
+            &path,
+            r#"edition = 1
+exports = []
+
+[package]
+name = "echo-app"
+
+[package.actr_type]
+manufacturer = "acme"
+name = "echo"
+
+[dependencies]
+echo = { actr_type = "acme+EchoService" }
+
+[system.signaling]
+url = "ws://127.0.0.1:8080"
+
+[system.deployment]
+realm_id = 7
+"#,
+        )
+        .unwrap();
+        let config = ConfigParser::from_file(&path).unwrap();
+
+        assert_eq!(
+            lock_staleness(&config, None),
+            vec!["Actr.lock.toml does not exist".to_string()]
+        );
+        let issues = lock_staleness(&config, Some(&LockFile::new()));
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].contains("is not locked"));
+    }
+
+    #[test]
+    fn test_generated_staleness() {
+        let temp = TempDir::new().unwrap();
+        let protos = temp.path().join("protos");
+        let generated = temp.path().join("src/generated");
+        std::fs::create_dir_all(&protos).unwrap();
+        let proto = protos.join("echo.proto");
+        std::fs::write(&proto, "syntax = \"proto3\";").unwrap();
+
+        assert!(generated_staleness(&protos, &generated).is_some());
+
+        std::fs::create_dir_all(&generated).unwrap();
+        let output = generated.join("echo.rs");
+        std::fs::write(&output, "// generated").unwrap();
+        let set_mtime = |path: &Path, secs: u64| {
+            std::fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+                .unwrap();
         };
 
-        let script_name = cmd.script_name.as_deref().unwrap_or("run");
-        assert_eq!(script_name, "test");
+        set_mtime(&proto, 1_000);
+        set_mtime(&output, 2_000);
+        assert!(generated_staleness(&protos, &generated).is_none());
+
+        set_mtime(&proto, 3_000);
+        assert!(generated_staleness(&protos, &generated).is_some());
     }
 }
@@ -0,0 +1,411 @@
+//! Search command implementation - non-interactive registry query
+//!
+//! Queries the project's service discovery with name, tag, manufacturer and
+//! publication time filters, then sorts and pages the result. Unlike
+//! `actr discovery` it never prompts, so `--output json` composes with `jq` and
+//! other shell tools.
+
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, NetworkServiceDiscovery,
+    ServiceFilter, ServiceInfo,
+};
+use crate::human_println;
+use actr_protocol::ActrTypeExt;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate};
+use clap::{Args, ValueEnum};
+use owo_colors::OwoColorize;
+
+/// Search command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Search the service registry",
+    long_about = "Search the services known to the project's discovery backends.\n\nA query matches service names and actr_types; without '*' it matches anything containing it, with '*' it is a glob.\n\nExamples:\n  actr search user                      # Names containing 'user'\n  actr search 'user-*' --tag stable\n  actr search --manufacturer acme --published-after 2025-01-01\n  actr search --sort published --reverse --limit 5\n  actr search --output json | jq -r '.services[].name'"
+)]
+pub struct SearchCommand {
+    /// Service name, actr_type or glob pattern (e.g. user, user-*)
+    #[arg(value_name = "QUERY")]
+    pub query: Option<String>,
+
+    /// Only services carrying this tag (repeatable; all must match)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
+    /// Only services of this manufacturer (the part of actr_type before '+')
+    #[arg(long, value_name = "NAME")]
+    pub manufacturer: Option<String>,
+
+    /// Only services published at or after this time (YYYY-MM-DD or RFC 3339)
+    #[arg(long, value_name = "TIME", value_parser = parse_time)]
+    pub published_after: Option<i64>,
+
+    /// Only services published before this time (YYYY-MM-DD or RFC 3339)
+    #[arg(long, value_name = "TIME", value_parser = parse_time)]
+    pub published_before: Option<i64>,
+
+    /// Sort key
+    #[arg(long, value_enum, default_value_t = SearchSort::Name)]
+    pub sort: SearchSort,
+
+    /// Reverse the sort order
+    #[arg(long)]
+    pub reverse: bool,
+
+    /// Results per page
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 20,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub limit: u32,
+
+    /// Page to show, starting at 1
+    #[arg(long, value_name = "N", default_value_t = 1,
+          value_parser = clap::value_parser!(u32).range(1..))]
+    pub page: u32,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = SearchOutputFormat::Table)]
+    pub output: SearchOutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SearchSort {
+    /// Service name, alphabetical
+    #[default]
+    Name,
+    /// Publication time, newest first
+    Published,
+    /// Manufacturer, then name
+    Manufacturer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SearchOutputFormat {
+    /// Human-readable table (default)
+    #[default]
+    Table,
+    /// JSON document with the page of services and the total count
+    Json,
+}
+
+/// One page of matching services
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub services: Vec<ServiceInfo>,
+    /// Matches across all pages
+    pub total: usize,
+    pub page: u32,
+    pub pages: u32,
+}
+
+#[async_trait]
+impl Command for SearchCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let service_discovery = context.container.get_service_discovery()?;
+        let found = service_discovery
+            .discover_services(Some(&self.service_filter()))
+            .await?;
+        let result = self.paginate(self.filter(found));
+
+        if self.output == SearchOutputFormat::Json || context.is_json() {
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &Self::page_to_json(&result),
+            )?));
+        }
+
+        if result.total == 0 {
+            return Ok(CommandResult::Success("No matching services".to_string()));
+        }
+        if result.services.is_empty() {
+            return Ok(CommandResult::Success(format!(
+                "Page {} is past the last page ({})",
+                result.page, result.pages
+            )));
+        }
+
+        Self::display_table(&result.services);
+        Ok(CommandResult::Success(format!(
+            "{} matching services (page {}/{})",
+            result.total, result.page, result.pages
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::ServiceDiscovery]
+    }
+
+    fn name(&self) -> &str {
+        "search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the service registry"
+    }
+}
+
+impl SearchCommand {
+    /// Name glob handed to discovery; plain queries match as substrings
+    fn name_pattern(&self) -> Option<String> {
+        self.query.as_ref().map(|query| {
+            if query.contains('*') {
+                query.clone()
+            } else {
+                format!("*{query}*")
+            }
+        })
+    }
+
+    fn service_filter(&self) -> ServiceFilter {
+        ServiceFilter {
+            name_pattern: self.name_pattern(),
+            version_range: None,
+            tags: (!self.tags.is_empty()).then(|| self.tags.clone()),
+        }
+    }
+
+    /// Apply every filter locally too; backends may only honour part of the filter
+    fn filter(&self, services: Vec<ServiceInfo>) -> Vec<ServiceInfo> {
+        let pattern = self.name_pattern();
+        let mut services: Vec<ServiceInfo> = services
+            .into_iter()
+            .filter(|s| {
+                pattern.as_deref().is_none_or(|p| {
+                    NetworkServiceDiscovery::matches_pattern(&s.name, p)
+                        || NetworkServiceDiscovery::matches_pattern(
+                            &s.actr_type.to_string_repr(),
+                            p,
+                        )
+                })
+            })
+            .filter(|s| self.tags.iter().all(|tag| s.tags.contains(tag)))
+            .filter(|s| {
+                self.manufacturer
+                    .as_ref()
+                    .is_none_or(|m| &s.actr_type.manufacturer == m)
+            })
+            .filter(|s| {
+                self.published_after
+                    .is_none_or(|after| s.published_at.is_some_and(|at| at >= after))
+            })
+            .filter(|s| {
+                self.published_before
+                    .is_none_or(|before| s.published_at.is_some_and(|at| at < before))
+            })
+            .collect();
+
+        match self.sort {
+            SearchSort::Name => services.sort_by(|a, b| a.name.cmp(&b.name)),
+            SearchSort::Published => services.sort_by(|a, b| {
+                b.published_at
+                    .cmp(&a.published_at)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+            SearchSort::Manufacturer => services.sort_by(|a, b| {
+                a.actr_type
+                    .manufacturer
+                    .cmp(&b.actr_type.manufacturer)
+                    .then_with(|| a.name.cmp(&b.name))
+            }),
+        }
+        if self.reverse {
+            services.reverse();
+        }
+        services
+    }
+
+    fn paginate(&self, services: Vec<ServiceInfo>) -> SearchPage {
+        let total = services.len();
+        let limit = self.limit as usize;
+        let pages = total.div_ceil(limit).max(1) as u32;
+        let services = services
+            .into_iter()
+            .skip((self.page as usize - 1) * limit)
+            .take(limit)
+            .collect();
+        SearchPage {
+            services,
+            total,
+            page: self.page,
+            pages,
+        }
+    }
+
+    fn page_to_json(page: &SearchPage) -> serde_json::Value {
+        let services: Vec<serde_json::Value> = page
+            .services
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "name": s.name,
+                    "actr_type": s.actr_type.to_string_repr(),
+                    "fingerprint": s.fingerprint,
+                    "tags": s.tags,
+                    "description": s.description,
+                    "published_at": s.published_at,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "total": page.total,
+            "page": page.page,
+            "pages": page.pages,
+            "services": services,
+        })
+    }
+
+    fn display_table(services: &[ServiceInfo]) {
+        let rows: Vec<[String; 4]> = services
+            .iter()
+            .map(|s| {
+                [
+                    s.name.clone(),
+                    s.actr_type.to_string_repr(),
+                    s.tags.join(", "),
+                    s.published_at
+                        .and_then(|at| DateTime::from_timestamp(at, 0))
+                        .map(|at| at.format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ]
+            })
+            .collect();
+        let headers = ["Service Name", "Type", "Tags", "Published"];
+        let widths: Vec<usize> = (0..headers.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .chain([headers[column].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let header = format!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            headers[0],
+            headers[1],
+            headers[2],
+            headers[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+        human_println!("{}", header.bold());
+        for row in &rows {
+            human_println!(
+                "{}  {:<w1$}  {:<w2$}  {}",
+                format!("{:<w0$}", row[0], w0 = widths[0]).cyan(),
+                row[1],
+                row[2],
+                row[3],
+                w1 = widths[1],
+                w2 = widths[2],
+            );
+        }
+    }
+}
+
+/// Accept a plain date (midnight UTC) or a full RFC 3339 timestamp, as Unix seconds
+fn parse_time(value: &str) -> std::result::Result<i64, ActrCliError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.timestamp());
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc().timestamp())
+        .ok_or_else(|| ActrCliError::InvalidArgument {
+            message: format!("'{value}' is not a date (YYYY-MM-DD) or RFC 3339 timestamp"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_protocol::ActrType;
+
+    fn service(name: &str, actr_type: &str, tags: &[&str], published_at: i64) -> ServiceInfo {
+        ServiceInfo {
+            name: name.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            fingerprint: "service_semantic:abc".to_string(),
+            actr_type: ActrType::from_string_repr(actr_type).unwrap(),
+            published_at: Some(published_at),
+            description: None,
+            methods: Vec::new(),
+        }
+    }
+
+    fn command() -> SearchCommand {
+        SearchCommand {
+            query: None,
+            tags: Vec::new(),
+            manufacturer: None,
+            published_after: None,
+            published_before: None,
+            sort: SearchSort::Name,
+            reverse: false,
+            limit: 20,
+            page: 1,
+            output: SearchOutputFormat::Table,
+        }
+    }
+
+    fn services() -> Vec<ServiceInfo> {
+        vec![
+            service("user-service", "acme+User", &["stable"], 1_735_689_600),
+            service(
+                "auth-service",
+                "acme+Auth",
+                &["stable", "beta"],
+                1_740_000_000,
+            ),
+            service("billing", "globex+Billing", &[], 1_700_000_000),
+        ]
+    }
+
+    fn names(services: &[ServiceInfo]) -> Vec<&str> {
+        services.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_and_sort() {
+        let cmd = SearchCommand {
+            query: Some("service".to_string()),
+            tags: vec!["stable".to_string()],
+            ..command()
+        };
+        assert_eq!(
+            names(&cmd.filter(services())),
+            ["auth-service", "user-service"]
+        );
+
+        let cmd = SearchCommand {
+            manufacturer: Some("acme".to_string()),
+            published_after: Some(parse_time("2025-01-01").unwrap()),
+            sort: SearchSort::Published,
+            ..command()
+        };
+        assert_eq!(
+            names(&cmd.filter(services())),
+            ["auth-service", "user-service"]
+        );
+
+        let cmd = SearchCommand {
+            query: Some("*-service".to_string()),
+            published_before: Some(parse_time("2025-02-01T00:00:00Z").unwrap()),
+            ..command()
+        };
+        assert_eq!(names(&cmd.filter(services())), ["user-service"]);
+    }
+
+    #[test]
+    fn test_paginate() {
+        let cmd = SearchCommand {
+            limit: 2,
+            page: 2,
+            ..command()
+        };
+        let page = cmd.paginate(cmd.filter(services()));
+        assert_eq!((page.total, page.pages), (3, 2));
+        assert_eq!(names(&page.services), ["user-service"]);
+    }
+}
@@ -0,0 +1,735 @@
+//! UI command implementation - terminal dashboard
+//!
+//! `actr ui` shows the configured dependencies with their health and lock state next
+//! to the services discovery knows about. The data comes from the same components
+//! the other commands use: the config manager and lock file for dependencies, the
+//! proto cache for drift, the network validator for health and service discovery
+//! for the service list. Actions leave the dashboard, run through the install
+//! pipeline or the doc generator with their usual output, and return on Enter.
+
+use crate::commands::Command as _;
+use crate::commands::doc::{DocCommand, DocFormat};
+use crate::commands::install::load_dependency_specs;
+use crate::commands::lock::{
+    SERVICE_FINGERPRINT_PREFIX, cached_files, load_lock_file, verify_dependency,
+};
+use crate::commands::proto::{DEFAULT_EXPORT_DIR, ExportLayout, export_service_protos};
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, DependencySpec,
+    NetworkCheckOptions, ServiceInfo,
+};
+use crate::project_lock::ProjectLock;
+use actr_protocol::ActrTypeExt;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Local};
+use clap::Args;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Time to wait for another command holding the project lock before an install
+const LOCK_WAIT: Duration = Duration::from_secs(10);
+
+/// UI command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Open the terminal dashboard",
+    long_about = "Open a terminal dashboard of the project's dependencies (health, lock drift) and the services discovery knows about.\n\nKeys:\n  Tab        Switch between dependencies and services\n  ↑/↓ j/k    Move the selection\n  i          Install (all dependencies, or the selected service)\n  e          Export the selected service's protos to exports/remote/\n  d          Generate the docs and open them\n  r          Reload everything\n  q / Esc    Quit"
+)]
+pub struct UiCommand {
+    /// Seconds between health checks of the dependencies
+    #[arg(long, value_name = "SECONDS", default_value_t = 10,
+          value_parser = clap::value_parser!(u64).range(1..))]
+    pub refresh: u64,
+}
+
+#[async_trait]
+impl Command for UiCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        if context.is_json() || !std::io::stdout().is_terminal() {
+            return Err(ActrCliError::InvalidArgument {
+                message: "actr ui needs an interactive terminal; use 'actr check' or 'actr search --output json' in scripts".to_string(),
+            }
+            .into());
+        }
+
+        let mut dashboard = Dashboard::default();
+        dashboard.reload(context).await?;
+        dashboard.refresh_health(context).await?;
+
+        let mut terminal = ratatui::try_init()?;
+        let result = self.run(&mut terminal, &mut dashboard, context).await;
+        ratatui::restore();
+        result?;
+
+        Ok(CommandResult::Success(String::new()))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        // Install actions reuse the install pipeline
+        vec![
+            ComponentType::ConfigManager,
+            ComponentType::ServiceDiscovery,
+            ComponentType::NetworkValidator,
+            ComponentType::CacheManager,
+            ComponentType::DependencyResolver,
+            ComponentType::FingerprintValidator,
+            ComponentType::ProtoProcessor,
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "ui"
+    }
+
+    fn description(&self) -> &str {
+        "Open the terminal dashboard"
+    }
+}
+
+impl UiCommand {
+    async fn run(
+        &self,
+        terminal: &mut DefaultTerminal,
+        dashboard: &mut Dashboard,
+        context: &CommandContext,
+    ) -> Result<()> {
+        let refresh = Duration::from_secs(self.refresh);
+        let mut last_health = Instant::now();
+        loop {
+            terminal.draw(|frame| dashboard.render(frame))?;
+
+            if last_health.elapsed() >= refresh {
+                dashboard.refresh_health(context).await?;
+                last_health = Instant::now();
+                continue;
+            }
+            if !event::poll(Duration::from_millis(250))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let action = match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                    dashboard.toggle_focus();
+                    continue;
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    dashboard.move_selection(-1);
+                    continue;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    dashboard.move_selection(1);
+                    continue;
+                }
+                KeyCode::Char('r') => {
+                    dashboard.reload(context).await?;
+                    dashboard.refresh_health(context).await?;
+                    last_health = Instant::now();
+                    continue;
+                }
+                KeyCode::Char('i') => Action::Install,
+                KeyCode::Char('e') => Action::Export,
+                KeyCode::Char('d') => Action::Docs,
+                _ => continue,
+            };
+
+            // Leave the dashboard so the action's output and prompts are readable
+            ratatui::restore();
+            let outcome = action.run(dashboard, context).await;
+            match &outcome {
+                Ok(message) => println!("\n✅ {message}"),
+                Err(e) => println!("\n❌ {e:#}"),
+            }
+            println!("Press Enter to return to the dashboard");
+            std::io::stdin().read_line(&mut String::new())?;
+            *terminal = ratatui::try_init()?;
+
+            dashboard.status = match outcome {
+                Ok(message) => message,
+                Err(e) => format!("Failed: {e}"),
+            };
+            dashboard.reload(context).await?;
+            dashboard.refresh_health(context).await?;
+            last_health = Instant::now();
+        }
+        Ok(())
+    }
+}
+
+/// Actions bound to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Install,
+    Export,
+    Docs,
+}
+
+impl Action {
+    async fn run(self, dashboard: &Dashboard, context: &CommandContext) -> Result<String> {
+        let project_root = context
+            .container
+            .get_config_manager()?
+            .get_project_root()
+            .to_path_buf();
+        match self {
+            Action::Install => {
+                let specs = match dashboard.focus {
+                    Pane::Dependencies => dashboard
+                        .dependencies
+                        .iter()
+                        .map(|row| row.spec.clone())
+                        .collect(),
+                    Pane::Services => {
+                        let Some(service) = dashboard.selected_service() else {
+                            return Ok("No service selected".to_string());
+                        };
+                        vec![DependencySpec {
+                            alias: service.name.clone(),
+                            name: service.name.clone(),
+                            actr_type: Some(service.actr_type.clone()),
+                            fingerprint: Some(service.fingerprint.clone()),
+                            constraint: Default::default(),
+                        }]
+                    }
+                };
+                if specs.is_empty() {
+                    return Ok("No dependencies to install".to_string());
+                }
+                let _lock = ProjectLock::acquire(&project_root, "ui", LOCK_WAIT).await?;
+                let result = context
+                    .container
+                    .get_install_pipeline()?
+                    .install_dependencies(&specs)
+                    .await?;
+                Ok(format!("Installed: {}", result.summary()))
+            }
+            Action::Export => {
+                let Some(name) = dashboard.selected_name() else {
+                    return Ok("No service selected".to_string());
+                };
+                let discovery = context.container.get_service_discovery()?;
+                export_service_protos(
+                    &name,
+                    discovery.as_ref(),
+                    &project_root.join(DEFAULT_EXPORT_DIR),
+                    ExportLayout::Package,
+                )
+                .await?;
+                Ok(format!("Exported the protos of {name}"))
+            }
+            Action::Docs => {
+                DocCommand {
+                    output_dir: None,
+                    format: DocFormat::Html,
+                    include_deps: true,
+                    config: None,
+                }
+                .execute()
+                .await?;
+                let index = project_root.join("docs").join("index.html");
+                open_in_browser(&index)?;
+                Ok(format!("Opened {}", index.display()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Pane {
+    #[default]
+    Dependencies,
+    Services,
+}
+
+/// How a configured dependency relates to Actr.lock.toml and the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum LockStatus {
+    /// Locked, cached protos match and discovery serves the locked fingerprint
+    InSync,
+    /// Not in Actr.lock.toml yet
+    Unlocked,
+    /// The cached protos do not match the lock entry
+    CacheMismatch(String),
+    /// Discovery serves a different fingerprint than the locked one
+    Outdated { available: String },
+}
+
+impl LockStatus {
+    fn label(&self) -> (&'static str, Color) {
+        match self {
+            LockStatus::InSync => ("locked", Color::Green),
+            LockStatus::Unlocked => ("unlocked", Color::Yellow),
+            LockStatus::CacheMismatch(_) => ("cache drift", Color::Red),
+            LockStatus::Outdated { .. } => ("outdated", Color::Yellow),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+enum Health {
+    #[default]
+    Unknown,
+    Reachable(Option<u64>),
+    Unreachable(String),
+}
+
+impl Health {
+    fn label(&self) -> (String, Color) {
+        match self {
+            Health::Unknown => ("…".to_string(), Color::DarkGray),
+            Health::Reachable(Some(ms)) => (format!("up {ms}ms"), Color::Green),
+            Health::Reachable(None) => ("up".to_string(), Color::Green),
+            Health::Unreachable(_) => ("down".to_string(), Color::Red),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DependencyRow {
+    spec: DependencySpec,
+    locked_fingerprint: Option<String>,
+    lock: LockStatus,
+    health: Health,
+}
+
+#[derive(Debug, Default)]
+struct Dashboard {
+    dependencies: Vec<DependencyRow>,
+    services: Vec<ServiceInfo>,
+    /// Lock entries of services no longer in Actr.toml
+    stale: Vec<String>,
+    discovery_error: Option<String>,
+    focus: Pane,
+    dependency_state: TableState,
+    service_state: TableState,
+    status: String,
+    refreshed_at: Option<DateTime<Local>>,
+}
+
+impl Dashboard {
+    /// Re-read Actr.toml, the lock file, the cache and discovery, keeping the selection
+    async fn reload(&mut self, context: &CommandContext) -> Result<()> {
+        let config_manager = context.container.get_config_manager()?;
+        let cache_manager = context.container.get_cache_manager()?;
+        let specs = load_dependency_specs(config_manager.as_ref()).await?;
+        let lock_file = load_lock_file(config_manager.get_project_root()).ok();
+
+        (self.services, self.discovery_error) = match context
+            .container
+            .get_service_discovery()?
+            .discover_services(None)
+            .await
+        {
+            Ok(mut services) => {
+                services.sort_by(|a, b| a.name.cmp(&b.name));
+                (services, None)
+            }
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+
+        let previous_health: Vec<(String, Health)> = self
+            .dependencies
+            .drain(..)
+            .map(|row| (row.spec.alias, row.health))
+            .collect();
+        for spec in specs {
+            let locked = lock_file
+                .as_ref()
+                .and_then(|lock| lock.dependencies.iter().find(|dep| dep.name == spec.name));
+            let issues = match locked {
+                Some(locked) => {
+                    let cached = cached_files(cache_manager.as_ref(), &spec.name).await?;
+                    verify_dependency(locked, cached.as_deref())
+                }
+                None => Vec::new(),
+            };
+            let available = self.services.iter().find(|s| s.name == spec.name);
+            let locked_fingerprint = locked.map(|dep| dep.fingerprint.clone());
+            let health = previous_health
+                .iter()
+                .find(|(alias, _)| alias == &spec.alias)
+                .map(|(_, health)| health.clone())
+                .unwrap_or_default();
+            self.dependencies.push(DependencyRow {
+                lock: lock_status(locked_fingerprint.as_deref(), &issues, available),
+                locked_fingerprint,
+                spec,
+                health,
+            });
+        }
+
+        self.stale = lock_file
+            .map(|lock| {
+                lock.dependencies
+                    .into_iter()
+                    .filter(|dep| {
+                        !self
+                            .dependencies
+                            .iter()
+                            .any(|row| row.spec.name == dep.name)
+                    })
+                    .map(|dep| dep.name)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        clamp(&mut self.dependency_state, self.dependencies.len());
+        clamp(&mut self.service_state, self.services.len());
+        self.refreshed_at = Some(Local::now());
+        Ok(())
+    }
+
+    async fn refresh_health(&mut self, context: &CommandContext) -> Result<()> {
+        let network_validator = context.container.get_network_validator()?;
+        let options = NetworkCheckOptions::with_timeout_secs(3);
+        for row in &mut self.dependencies {
+            row.health = match network_validator
+                .check_connectivity(&row.spec.name, &options)
+                .await
+            {
+                Ok(status) if status.is_reachable => Health::Reachable(status.response_time_ms),
+                Ok(status) => {
+                    Health::Unreachable(status.error.unwrap_or_else(|| "unreachable".to_string()))
+                }
+                Err(e) => Health::Unreachable(e.to_string()),
+            };
+        }
+        self.refreshed_at = Some(Local::now());
+        Ok(())
+    }
+
+    fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            Pane::Dependencies => Pane::Services,
+            Pane::Services => Pane::Dependencies,
+        };
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let (state, len) = match self.focus {
+            Pane::Dependencies => (&mut self.dependency_state, self.dependencies.len()),
+            Pane::Services => (&mut self.service_state, self.services.len()),
+        };
+        if len == 0 {
+            return;
+        }
+        let current = state.selected().unwrap_or(0) as isize;
+        state.select(Some((current + delta).rem_euclid(len as isize) as usize));
+    }
+
+    fn selected_dependency(&self) -> Option<&DependencyRow> {
+        self.dependencies.get(self.dependency_state.selected()?)
+    }
+
+    fn selected_service(&self) -> Option<&ServiceInfo> {
+        self.services.get(self.service_state.selected()?)
+    }
+
+    /// Service name of the selection in the focused pane
+    fn selected_name(&self) -> Option<String> {
+        match self.focus {
+            Pane::Dependencies => self.selected_dependency().map(|row| row.spec.name.clone()),
+            Pane::Services => self.selected_service().map(|s| s.name.clone()),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [title, body, details, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(6),
+            Constraint::Length(7),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+
+        let refreshed = self
+            .refreshed_at
+            .map(|time| format!("refreshed {}", time.format("%H:%M:%S")))
+            .unwrap_or_default();
+        frame.render_widget(
+            Line::from(vec![
+                Span::from(" actr ui ").bold().reversed(),
+                Span::from(format!("  {refreshed}  ")).dark_gray(),
+                Span::from(self.status.clone()),
+            ]),
+            title,
+        );
+
+        let focused = |pane: Pane| {
+            if self.focus == pane {
+                Style::new().fg(Color::Cyan)
+            } else {
+                Style::new()
+            }
+        };
+        let highlight = Style::new().add_modifier(Modifier::REVERSED);
+
+        let dependency_rows = self.dependencies.iter().map(|row| {
+            let (health, health_color) = row.health.label();
+            let (lock, lock_color) = row.lock.label();
+            Row::new(vec![
+                Span::from(row.spec.alias.clone()),
+                Span::from(row.spec.name.clone()),
+                Span::from(health).fg(health_color),
+                Span::from(lock).fg(lock_color),
+            ])
+        });
+        let dependencies = Table::new(
+            dependency_rows,
+            [
+                Constraint::Percentage(25),
+                Constraint::Percentage(35),
+                Constraint::Percentage(18),
+                Constraint::Percentage(22),
+            ],
+        )
+        .header(Row::new(["Alias", "Service", "Health", "Lock"]).bold())
+        .block(
+            Block::bordered()
+                .title(format!(" Dependencies ({}) ", self.dependencies.len()))
+                .border_style(focused(Pane::Dependencies)),
+        )
+        .row_highlight_style(highlight);
+        frame.render_stateful_widget(dependencies, left, &mut self.dependency_state);
+
+        let service_rows = self.services.iter().map(|s| {
+            Row::new(vec![
+                s.name.clone(),
+                s.actr_type.to_string_repr(),
+                s.tags.join(", "),
+            ])
+        });
+        let services = Table::new(
+            service_rows,
+            [
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+                Constraint::Percentage(25),
+            ],
+        )
+        .header(Row::new(["Service", "Type", "Tags"]).bold())
+        .block(
+            Block::bordered()
+                .title(format!(" Services ({}) ", self.services.len()))
+                .border_style(focused(Pane::Services)),
+        )
+        .row_highlight_style(highlight);
+        frame.render_stateful_widget(services, right, &mut self.service_state);
+
+        frame.render_widget(
+            Paragraph::new(self.detail_lines()).block(Block::bordered().title(" Details ")),
+            details,
+        );
+        frame.render_widget(
+            Line::from(" Tab switch  ↑↓ move  i install  e export  d docs  r reload  q quit")
+                .dark_gray(),
+            help,
+        );
+    }
+
+    fn detail_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        match self.focus {
+            Pane::Dependencies => {
+                if let Some(row) = self.selected_dependency() {
+                    lines.push(Line::from(format!(
+                        "{} → {}",
+                        row.spec.alias,
+                        row.spec
+                            .actr_type
+                            .as_ref()
+                            .map(|t| t.to_string_repr())
+                            .unwrap_or_else(|| row.spec.name.clone())
+                    )));
+                    lines.push(Line::from(format!(
+                        "Locked:    {}",
+                        row.locked_fingerprint.as_deref().unwrap_or("-")
+                    )));
+                    lines.push(Line::from(match &row.lock {
+                        LockStatus::InSync => "Lock:      in sync with the cache".to_string(),
+                        LockStatus::Unlocked => {
+                            "Lock:      not locked; press i to install".to_string()
+                        }
+                        LockStatus::CacheMismatch(issue) => format!("Lock:      {issue}"),
+                        LockStatus::Outdated { available } => {
+                            format!("Available: {available} (actr update)")
+                        }
+                    }));
+                    if let Health::Unreachable(error) = &row.health {
+                        lines.push(Line::from(format!("Health:    {error}")).red());
+                    }
+                }
+            }
+            Pane::Services => {
+                if let Some(service) = self.selected_service() {
+                    lines.push(Line::from(format!(
+                        "{} ({})",
+                        service.name,
+                        service.actr_type.to_string_repr()
+                    )));
+                    lines.push(Line::from(format!("Fingerprint: {}", service.fingerprint)));
+                    if let Some(description) = &service.description {
+                        lines.push(Line::from(description.clone()));
+                    }
+                    if let Some(published) = service
+                        .published_at
+                        .and_then(|at| DateTime::from_timestamp(at, 0))
+                    {
+                        lines.push(Line::from(format!(
+                            "Published:   {}",
+                            published.format("%Y-%m-%d %H:%M")
+                        )));
+                    }
+                }
+            }
+        }
+        if let Some(error) = &self.discovery_error {
+            lines.push(Line::from(format!("Discovery unavailable: {error}")).red());
+        }
+        if !self.stale.is_empty() {
+            lines.push(
+                Line::from(format!(
+                    "Stale lock entries: {} (actr lock prune)",
+                    self.stale.join(", ")
+                ))
+                .yellow(),
+            );
+        }
+        lines
+    }
+}
+
+/// Lock state of a dependency from its lock entry, cache check and discovered service
+fn lock_status(
+    locked_fingerprint: Option<&str>,
+    cache_issues: &[String],
+    available: Option<&ServiceInfo>,
+) -> LockStatus {
+    let Some(locked) = locked_fingerprint else {
+        return LockStatus::Unlocked;
+    };
+    if let Some(issue) = cache_issues.first() {
+        return LockStatus::CacheMismatch(issue.clone());
+    }
+    let normalize = |fp: &str| {
+        fp.strip_prefix(SERVICE_FINGERPRINT_PREFIX)
+            .unwrap_or(fp)
+            .to_string()
+    };
+    match available {
+        Some(service) if normalize(&service.fingerprint) != normalize(locked) => {
+            LockStatus::Outdated {
+                available: service.fingerprint.clone(),
+            }
+        }
+        _ => LockStatus::InSync,
+    }
+}
+
+/// Keep a selection inside a list of `len` rows
+fn clamp(state: &mut TableState, len: usize) {
+    state.select(match (state.selected(), len) {
+        (_, 0) => None,
+        (Some(index), len) => Some(index.min(len - 1)),
+        (None, _) => Some(0),
+    });
+}
+
+/// Open a file with the platform's default application
+fn open_in_browser(path: &Path) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let status = command.arg(path).status()?;
+    if !status.success() {
+        return Err(ActrCliError::Command {
+            message: format!("Could not open {}", path.display()),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_protocol::ActrType;
+
+    fn service(fingerprint: &str) -> ServiceInfo {
+        ServiceInfo {
+            name: "user-service".to_string(),
+            tags: Vec::new(),
+            fingerprint: fingerprint.to_string(),
+            actr_type: ActrType::from_string_repr("acme+User").unwrap(),
+            published_at: None,
+            description: None,
+            methods: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_lock_status() {
+        let current = service("service_semantic:abc");
+        assert_eq!(lock_status(None, &[], Some(&current)), LockStatus::Unlocked);
+        assert_eq!(
+            lock_status(Some("service_semantic:abc"), &[], Some(&current)),
+            LockStatus::InSync
+        );
+        assert_eq!(lock_status(Some("abc"), &[], None), LockStatus::InSync);
+        assert_eq!(
+            lock_status(Some("service_semantic:old"), &[], Some(&current)),
+            LockStatus::Outdated {
+                available: "service_semantic:abc".to_string()
+            }
+        );
+        let issues = vec!["user.proto is missing from the cache".to_string()];
+        assert!(matches!(
+            lock_status(Some("service_semantic:old"), &issues, Some(&current)),
+            LockStatus::CacheMismatch(_)
+        ));
+    }
+
+    #[test]
+    fn test_selection_wraps_and_clamps() {
+        let mut dashboard = Dashboard {
+            services: vec![service("a"), service("b"), service("c")],
+            focus: Pane::Services,
+            ..Default::default()
+        };
+        clamp(&mut dashboard.service_state, dashboard.services.len());
+        assert_eq!(dashboard.service_state.selected(), Some(0));
+        dashboard.move_selection(-1);
+        assert_eq!(dashboard.service_state.selected(), Some(2));
+
+        dashboard.services.truncate(1);
+        clamp(&mut dashboard.service_state, dashboard.services.len());
+        assert_eq!(dashboard.service_state.selected(), Some(0));
+        clamp(&mut dashboard.dependency_state, 0);
+        assert_eq!(dashboard.dependency_state.selected(), None);
+    }
+}
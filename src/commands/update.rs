@@ -0,0 +1,390 @@
+//! Update Command Implementation
+//!
+//! Re-resolves locked dependencies against the registry and refreshes the lock
+//! file and proto cache only for services whose fingerprint changed.
+
+use crate::core::{
+    ActrCliError, Command, CommandContext, CommandResult, ComponentType, DependencySpec,
+    InstallOptions, ResolvedDependency, TrustPolicy, dependency_constraint,
+};
+use crate::history::HistoryOperation;
+use crate::human_println;
+use crate::tr;
+use actr_config::LockFile;
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Args;
+use std::collections::HashSet;
+
+/// Update command
+#[derive(Args, Debug)]
+#[command(
+    about = "Update locked dependencies",
+    long_about = "Re-resolve dependencies through service discovery and refresh Actr.lock.toml and cached protos for services whose fingerprint changed.\n\nExamples:\n  actr update                           # Update all dependencies\n  actr update --package user-service    # Update a single dependency\n  actr update --dry-run                 # Show changes without writing"
+)]
+pub struct UpdateCommand {
+    /// Only update the given dependency (alias or service name); can be repeated
+    #[arg(long = "package", short = 'p', value_name = "NAME")]
+    pub packages: Vec<String>,
+
+    /// Show what would change without touching the lock file or cache
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Accept services whose publisher signature is missing or does not verify
+    #[arg(long)]
+    pub allow_unsigned: bool,
+}
+
+/// A dependency whose registry state differs from the lock file
+#[derive(Debug, Clone)]
+struct ServiceChange {
+    resolved: ResolvedDependency,
+    locked_fingerprint: Option<String>,
+    added_files: Vec<String>,
+    removed_files: Vec<String>,
+}
+
+#[async_trait]
+impl Command for UpdateCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        if !std::path::Path::new("Actr.toml").exists() {
+            return Err(ActrCliError::InvalidProject {
+                message: tr!("common.not_a_project"),
+            }
+            .into());
+        }
+
+        let install_pipeline = context.container.get_install_pipeline()?;
+        let config_manager = install_pipeline.config_manager();
+        let project_root = config_manager.get_project_root().to_path_buf();
+
+        let config = config_manager
+            .load_config(project_root.join("Actr.toml").as_path())
+            .await?;
+        let mut specs: Vec<DependencySpec> = config
+            .dependencies
+            .iter()
+            .map(|d| DependencySpec {
+                alias: d.alias.clone(),
+                name: d.name.clone(),
+                actr_type: d.actr_type.clone(),
+                fingerprint: d.fingerprint.clone(),
+                constraint: Default::default(),
+            })
+            .collect();
+        dependency_constraint::apply_constraints(&project_root.join("Actr.toml"), &mut specs)?;
+        let specs = self.filter_specs(specs)?;
+
+        if specs.is_empty() {
+            return Ok(CommandResult::Success(tr!("update.no_dependencies")));
+        }
+
+        let lock_file_path = project_root.join("Actr.lock.toml");
+        let lock_file = if lock_file_path.exists() {
+            Some(
+                LockFile::from_file(&lock_file_path)
+                    .map_err(|e| anyhow::anyhow!(tr!("update.lock_read_failed", error = e)))?,
+            )
+        } else {
+            None
+        };
+
+        human_println!("{}", tr!("common.resolving", count = specs.len()));
+
+        let service_discovery = install_pipeline.validation_pipeline().service_discovery();
+        let trust = TrustPolicy::from_config_file(&project_root.join("Actr.toml"))?;
+        let options = InstallOptions {
+            allow_unsigned: self.allow_unsigned,
+            ..Default::default()
+        };
+        let mut changes = Vec::new();
+        let mut warnings = Vec::new();
+        let mut seen = HashSet::new();
+
+        for spec in &specs {
+            // Aliases sharing a service resolve to the same lock entry
+            if !seen.insert(spec.name.clone()) {
+                continue;
+            }
+
+            let details = match service_discovery
+                .find_service(&spec.name, &spec.constraint)
+                .await
+            {
+                Ok(details) => details,
+                Err(e) => {
+                    human_println!(
+                        "  ├─ ❌ {}",
+                        tr!("update.resolve_error", alias = spec.alias, error = e)
+                    );
+                    warnings.push(tr!("update.resolve_failed", name = spec.name, error = e));
+                    continue;
+                }
+            };
+
+            let locked = lock_file
+                .as_ref()
+                .and_then(|lock| lock.get_dependency(&spec.name));
+            let locked_fingerprint = locked.map(|l| l.fingerprint.clone());
+
+            if locked_fingerprint.as_deref() == Some(details.info.fingerprint.as_str()) {
+                human_println!("  ├─ ✅ {}", tr!("update.up_to_date", alias = spec.alias));
+                continue;
+            }
+
+            if let Some(pinned) = &spec.fingerprint
+                && pinned != &details.info.fingerprint
+            {
+                human_println!("  ├─ ⚠️  {}", tr!("update.pinned", alias = spec.alias));
+                warnings.push(tr!(
+                    "update.pinned_warning",
+                    alias = spec.alias,
+                    pinned = pinned,
+                    fingerprint = details.info.fingerprint
+                ));
+                continue;
+            }
+
+            // Changed services are checked against [trust] like a fresh install
+            if let Some(warning) = install_pipeline
+                .verify_publisher(&trust, &details, &options)
+                .await?
+            {
+                warnings.push(warning);
+            }
+
+            let locked_files: HashSet<String> = locked
+                .map(|l| l.files.iter().map(|f| f.path.clone()).collect())
+                .unwrap_or_default();
+            let remote_files: HashSet<String> = details
+                .proto_files
+                .iter()
+                .map(|pf| Self::lock_file_path(&spec.name, &pf.name))
+                .collect();
+
+            let mut added_files: Vec<String> =
+                remote_files.difference(&locked_files).cloned().collect();
+            let mut removed_files: Vec<String> =
+                locked_files.difference(&remote_files).cloned().collect();
+            added_files.sort();
+            removed_files.sort();
+
+            let mut resolved_spec = spec.clone();
+            if resolved_spec.actr_type.is_none() {
+                resolved_spec.actr_type = Some(details.info.actr_type.clone());
+            }
+
+            human_println!("  ├─ 🔄 {}", tr!("update.changed", alias = spec.alias));
+            changes.push(ServiceChange {
+                resolved: ResolvedDependency {
+                    selected_tag: resolved_spec.constraint.selected_tag(&details.info),
+                    spec: resolved_spec,
+                    fingerprint: details.info.fingerprint.clone(),
+                    proto_files: details.proto_files,
+                    dependencies: details.dependencies,
+                },
+                locked_fingerprint,
+                added_files,
+                removed_files,
+            });
+        }
+        human_println!("  └─ {}", tr!("common.resolve_complete"));
+
+        if changes.is_empty() {
+            self.display_warnings(&warnings);
+            return Ok(CommandResult::Success(tr!("update.all_up_to_date")));
+        }
+
+        self.display_changes(&changes);
+
+        if self.dry_run {
+            self.display_warnings(&warnings);
+            return Ok(CommandResult::Success(tr!(
+                "update.dry_run",
+                count = changes.len()
+            )));
+        }
+
+        let resolved: Vec<ResolvedDependency> =
+            changes.into_iter().map(|change| change.resolved).collect();
+        let previous_lock = install_pipeline.load_lock_file();
+        let mut result = install_pipeline.refresh_dependencies(&resolved).await?;
+        let touched: Vec<String> = resolved.iter().map(|dep| dep.spec.name.clone()).collect();
+        install_pipeline.record_history(HistoryOperation::Update, previous_lock.as_ref(), &touched);
+        result.warnings.extend(warnings);
+
+        human_println!();
+        human_println!(
+            "{}",
+            tr!(
+                "update.updated",
+                count = result.installed_dependencies.len()
+            )
+        );
+        human_println!(
+            "{}",
+            tr!("update.cache_updates", count = result.cache_updates)
+        );
+        human_println!("{}", tr!("remove.lock_updated"));
+        self.display_warnings(&result.warnings);
+        human_println!();
+        human_println!("{}", tr!("update.tip_generate"));
+
+        Ok(CommandResult::Success(result.summary()))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        // Update reuses the install pipeline for caching and lock file writes
+        vec![
+            ComponentType::ConfigManager,
+            ComponentType::DependencyResolver,
+            ComponentType::ServiceDiscovery,
+            ComponentType::NetworkValidator,
+            ComponentType::FingerprintValidator,
+            ComponentType::ProtoProcessor,
+            ComponentType::CacheManager,
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "update"
+    }
+
+    fn description(&self) -> &str {
+        "Refresh locked dependencies whose fingerprint changed"
+    }
+}
+
+impl UpdateCommand {
+    pub fn new(packages: Vec<String>, dry_run: bool) -> Self {
+        Self {
+            packages,
+            dry_run,
+            allow_unsigned: false,
+        }
+    }
+
+    // Create from clap Args
+    pub fn from_args(args: &UpdateCommand) -> Self {
+        UpdateCommand {
+            packages: args.packages.clone(),
+            dry_run: args.dry_run,
+            allow_unsigned: args.allow_unsigned,
+        }
+    }
+
+    /// Apply `--package` filters, matching alias or service name
+    fn filter_specs(&self, specs: Vec<DependencySpec>) -> Result<Vec<DependencySpec>> {
+        if self.packages.is_empty() {
+            return Ok(specs);
+        }
+
+        for package in &self.packages {
+            if !specs
+                .iter()
+                .any(|s| &s.alias == package || &s.name == package)
+            {
+                return Err(ActrCliError::Dependency {
+                    message: tr!("common.dependency_not_found", name = package),
+                }
+                .into());
+            }
+        }
+
+        Ok(specs
+            .into_iter()
+            .filter(|s| self.packages.contains(&s.alias) || self.packages.contains(&s.name))
+            .collect())
+    }
+
+    /// Path recorded in the lock file, relative to protos/remote/
+    fn lock_file_path(service_name: &str, proto_name: &str) -> String {
+        if proto_name.ends_with(".proto") {
+            format!("{}/{}", service_name, proto_name)
+        } else {
+            format!("{}/{}.proto", service_name, proto_name)
+        }
+    }
+
+    fn display_changes(&self, changes: &[ServiceChange]) {
+        human_println!();
+        human_println!("{}", tr!("update.changed_services"));
+        let not_locked = tr!("update.not_locked");
+        for change in changes {
+            let old = change.locked_fingerprint.as_deref().unwrap_or(&not_locked);
+            human_println!("  • {}", change.resolved.spec.name);
+            human_println!(
+                "{}",
+                tr!(
+                    "update.fingerprint_change",
+                    old = old,
+                    new = change.resolved.fingerprint
+                )
+            );
+            for file in &change.added_files {
+                human_println!("      + {file}");
+            }
+            for file in &change.removed_files {
+                human_println!("      - {file}");
+            }
+        }
+    }
+
+    fn display_warnings(&self, warnings: &[String]) {
+        if warnings.is_empty() {
+            return;
+        }
+        human_println!();
+        human_println!("{}", tr!("common.warnings"));
+        for warning in warnings {
+            human_println!("   • {warning}");
+        }
+    }
+}
+
+impl Default for UpdateCommand {
+    fn default() -> Self {
+        Self::new(Vec::new(), false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(alias: &str, name: &str) -> DependencySpec {
+        DependencySpec {
+            alias: alias.to_string(),
+            name: name.to_string(),
+            actr_type: None,
+            fingerprint: None,
+            constraint: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_filter_specs() {
+        let specs = vec![spec("user", "user-service"), spec("order", "order-service")];
+
+        let cmd = UpdateCommand::new(vec!["order-service".to_string()], false);
+        let filtered = cmd.filter_specs(specs.clone()).unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].alias, "order");
+
+        let cmd = UpdateCommand::new(vec!["missing".to_string()], false);
+        assert!(cmd.filter_specs(specs).is_err());
+    }
+
+    #[test]
+    fn test_lock_file_path() {
+        assert_eq!(
+            UpdateCommand::lock_file_path("echo-service", "echo.proto"),
+            "echo-service/echo.proto"
+        );
+        assert_eq!(
+            UpdateCommand::lock_file_path("echo-service", "echo"),
+            "echo-service/echo.proto"
+        );
+    }
+}
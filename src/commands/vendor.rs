@@ -0,0 +1,283 @@
+//! Vendor command implementation - commit verified remote protos to the project
+//!
+//! `actr vendor` checks the cached protos of every locked service against
+//! Actr.lock.toml and writes them as standalone, read-only copies to
+//! `protos/remote/<service>/`, the layout the lock file paths are relative to.
+//! Cache entries hard-linked from `~/.actr/cache` are replaced by real files and
+//! files no longer in the lock are removed, so the committed tree alone is enough
+//! for `actr gen` and `actr install --offline`.
+//!
+//! Imports written in lock path form (`<service>/<file>.proto`) are rewritten to
+//! `remote/<service>/<file>.proto`, which protoc resolves from the `protos/` root.
+
+use crate::commands::export::REMOTE_PROTO_DIR;
+use crate::commands::lock::{cached_files, load_lock_file, verify_dependency};
+use crate::core::{ActrCliError, Command, CommandContext, CommandResult, ComponentType};
+use crate::human_println;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Vendor command
+#[derive(Args, Debug, Clone)]
+#[command(
+    about = "Verify cached protos and vendor them into protos/remote/",
+    long_about = "Check the cached protos of every locked dependency against Actr.lock.toml and write them to protos/remote/<service>/ as read-only files that can be committed, so builds no longer need the network or the proto cache.\n\nExamples:\n  actr vendor\n  git add protos/remote Actr.lock.toml"
+)]
+pub struct VendorCommand {}
+
+/// One vendored service
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VendoredService {
+    pub name: String,
+    pub fingerprint: String,
+    pub files: Vec<String>,
+    /// Imports rewritten to the vendored layout
+    pub rewritten_imports: usize,
+}
+
+#[async_trait]
+impl Command for VendorCommand {
+    async fn execute(&self, context: &CommandContext) -> Result<CommandResult> {
+        let (project_root, cache_manager) = (
+            context
+                .container
+                .get_config_manager()?
+                .get_project_root()
+                .to_path_buf(),
+            context.container.get_cache_manager()?,
+        );
+        let lock_file = load_lock_file(&project_root)?;
+
+        // Check everything before touching the project
+        let mut sources = Vec::with_capacity(lock_file.dependencies.len());
+        let mut problems = Vec::new();
+        for locked in &lock_file.dependencies {
+            let files = cached_files(cache_manager.as_ref(), &locked.name).await?;
+            let issues = verify_dependency(locked, files.as_deref());
+            if issues.is_empty() {
+                sources.push((locked, files.unwrap_or_default()));
+            } else {
+                problems.extend(
+                    issues
+                        .into_iter()
+                        .map(|issue| format!("{}: {issue}", locked.name)),
+                );
+            }
+        }
+        if !problems.is_empty() {
+            return Err(ActrCliError::Cache {
+                message: format!(
+                    "Cached protos do not match Actr.lock.toml:\n  {}\nRun 'actr install' to restore the cache first.",
+                    problems.join("\n  ")
+                ),
+            }
+            .into());
+        }
+
+        let layout: BTreeMap<String, BTreeSet<String>> = sources
+            .iter()
+            .map(|(locked, files)| {
+                (
+                    locked.name.clone(),
+                    files.iter().map(|f| f.name.clone()).collect(),
+                )
+            })
+            .collect();
+
+        let remote_dir = project_root.join(REMOTE_PROTO_DIR);
+        let mut vendored = Vec::with_capacity(sources.len());
+        for (locked, files) in &sources {
+            let service_dir = remote_dir.join(&locked.name);
+            let mut rewritten_imports = 0;
+            let mut contents = Vec::with_capacity(files.len());
+            for file in files {
+                let (content, rewritten) = rewrite_imports(&file.content, &layout);
+                rewritten_imports += rewritten;
+                contents.push((file.name.clone(), content));
+            }
+            vendor_service(&service_dir, &contents)?;
+
+            human_println!(
+                "  ├─ 📌 {} ({} files, {})",
+                locked.name,
+                contents.len(),
+                locked.fingerprint
+            );
+            vendored.push(VendoredService {
+                name: locked.name.clone(),
+                fingerprint: locked.fingerprint.clone(),
+                files: contents.into_iter().map(|(name, _)| name).collect(),
+                rewritten_imports,
+            });
+        }
+
+        if context.is_json() {
+            let report = serde_json::json!({
+                "directory": REMOTE_PROTO_DIR,
+                "services": vendored,
+            });
+            return Ok(CommandResult::Success(serde_json::to_string_pretty(
+                &report,
+            )?));
+        }
+
+        let rewritten: usize = vendored.iter().map(|s| s.rewritten_imports).sum();
+        if rewritten > 0 {
+            human_println!("  ├─ ✏️  Rewrote {rewritten} imports to the vendored layout");
+        }
+        human_println!("  └─ Vendoring complete");
+        Ok(CommandResult::Success(format!(
+            "Vendored {} services into {REMOTE_PROTO_DIR}/",
+            vendored.len()
+        )))
+    }
+
+    fn required_components(&self) -> Vec<ComponentType> {
+        vec![ComponentType::ConfigManager, ComponentType::CacheManager]
+    }
+
+    fn name(&self) -> &str {
+        "vendor"
+    }
+
+    fn description(&self) -> &str {
+        "Verify cached protos and vendor them into protos/remote/"
+    }
+}
+
+/// Replace the contents of `service_dir` with read-only copies of `files`
+fn vendor_service(service_dir: &Path, files: &[(String, String)]) -> Result<()> {
+    std::fs::create_dir_all(service_dir)?;
+    let keep: BTreeSet<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    for entry in std::fs::read_dir(service_dir)? {
+        let path = entry?.path();
+        let stale = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| !keep.contains(name));
+        if path.is_file() && stale {
+            remove_file(&path)?;
+        }
+    }
+
+    for (name, content) in files {
+        let path = service_dir.join(name);
+        // Never write through a hard link into the global store
+        if path.exists() {
+            remove_file(&path)?;
+        }
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        let mut permissions = std::fs::metadata(&path)?.permissions();
+        permissions.set_readonly(true);
+        std::fs::set_permissions(&path, permissions)?;
+    }
+    Ok(())
+}
+
+/// Remove a file, clearing the read-only flag Windows refuses to delete through
+fn remove_file(path: &Path) -> Result<()> {
+    if cfg!(windows) {
+        let mut permissions = std::fs::metadata(path)?.permissions();
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        std::fs::set_permissions(path, permissions)?;
+    }
+    std::fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))
+}
+
+/// Rewrite `import "<service>/<file>"` of vendored files to `remote/<service>/<file>`,
+/// returning the new source and the number of rewritten imports
+fn rewrite_imports(source: &str, layout: &BTreeMap<String, BTreeSet<String>>) -> (String, usize) {
+    let mut rewritten = 0;
+    let lines: Vec<String> = source
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if !trimmed.starts_with("import ") {
+                return line.to_string();
+            }
+            let Some(start) = line.find('"') else {
+                return line.to_string();
+            };
+            let Some(len) = line[start + 1..].find('"') else {
+                return line.to_string();
+            };
+            let import = &line[start + 1..start + 1 + len];
+            let vendored = import.split_once('/').is_some_and(|(service, file)| {
+                layout
+                    .get(service)
+                    .is_some_and(|files| files.contains(file))
+            });
+            if !vendored {
+                return line.to_string();
+            }
+            rewritten += 1;
+            format!(
+                "{}remote/{import}{}",
+                &line[..start + 1],
+                &line[start + 1 + len..]
+            )
+        })
+        .collect();
+
+    let mut output = lines.join("\n");
+    if source.ends_with('\n') {
+        output.push('\n');
+    }
+    (output, rewritten)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_imports_of_vendored_files() {
+        let layout: BTreeMap<String, BTreeSet<String>> = [(
+            "common".to_string(),
+            ["types.proto".to_string()].into_iter().collect(),
+        )]
+        .into_iter()
+        .collect();
+        let source = "syntax = \"proto3\";\nimport \"common/types.proto\";\nimport public \"common/other.proto\";\nimport \"google/protobuf/empty.proto\";\nimport \"user.proto\";\n";
+
+        let (output, rewritten) = rewrite_imports(source, &layout);
+        assert_eq!(rewritten, 1);
+        assert!(output.contains("import \"remote/common/types.proto\";"));
+        assert!(output.contains("import public \"common/other.proto\";"));
+        assert!(output.contains("import \"google/protobuf/empty.proto\";"));
+        assert!(output.ends_with("import \"user.proto\";\n"));
+    }
+
+    #[test]
+    fn test_vendor_service_replaces_links_and_stale_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = dir.path().join("store.proto");
+        std::fs::write(&store, "// store").unwrap();
+        let service_dir = dir.path().join("protos/remote/user-service");
+        std::fs::create_dir_all(&service_dir).unwrap();
+        std::fs::hard_link(&store, service_dir.join("user.proto")).unwrap();
+        std::fs::write(service_dir.join("old.proto"), "// old").unwrap();
+
+        let files = vec![("user.proto".to_string(), "// vendored".to_string())];
+        vendor_service(&service_dir, &files).unwrap();
+        // Vendoring again must cope with the read-only copies
+        vendor_service(&service_dir, &files).unwrap();
+
+        let vendored = service_dir.join("user.proto");
+        assert_eq!(std::fs::read_to_string(&vendored).unwrap(), "// vendored");
+        assert!(
+            std::fs::metadata(&vendored)
+                .unwrap()
+                .permissions()
+                .readonly()
+        );
+        assert_eq!(std::fs::read_to_string(&store).unwrap(), "// store");
+        assert!(!service_dir.join("old.proto").exists());
+    }
+}
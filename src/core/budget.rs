@@ -0,0 +1,188 @@
+//! Execution budget: overall timeout and Ctrl+C cancellation
+//!
+//! One budget is created per CLI invocation from the global `--timeout` flag and
+//! shared by the pipelines through the service container. Network calls and
+//! subprocesses run through [`ExecutionBudget::run`], which aborts them once the
+//! deadline passes or the user presses Ctrl+C. The pipelines then return an
+//! error, so their usual rollback paths restore Actr.toml and the proto cache.
+
+use anyhow::Result;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use super::error::ActrCliError;
+
+/// Time a cancelled command gets to roll back before it is abandoned
+pub const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Deadline and cancellation signal shared by every operation of one command
+#[derive(Debug, Clone)]
+pub struct ExecutionBudget {
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl Default for ExecutionBudget {
+    fn default() -> Self {
+        Self::unlimited()
+    }
+}
+
+impl ExecutionBudget {
+    /// Budget without a deadline; only cancelled explicitly
+    pub fn unlimited() -> Self {
+        Self::new(None)
+    }
+
+    /// Budget expiring `timeout` from now
+    pub fn new(timeout: Option<Duration>) -> Self {
+        let (cancelled, _) = watch::channel(false);
+        Self {
+            timeout,
+            deadline: timeout.map(|timeout| Instant::now() + timeout),
+            cancelled: Arc::new(cancelled),
+        }
+    }
+
+    /// Cancel every operation running under this budget
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Time left before the deadline
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Error describing why the budget ended
+    pub fn error(&self) -> ActrCliError {
+        match self.timeout {
+            Some(timeout) if !self.is_cancelled() => ActrCliError::TimedOut {
+                seconds: timeout.as_secs(),
+            },
+            _ => ActrCliError::Interrupted,
+        }
+    }
+
+    /// Fail if the budget is already used up; call between steps
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() || self.is_expired() {
+            return Err(self.error().into());
+        }
+        Ok(())
+    }
+
+    /// Resolves once the budget is cancelled or its deadline passes
+    pub async fn done(&self) {
+        let mut cancelled = self.cancelled.subscribe();
+        let wait_cancelled = async {
+            // The sender lives in `self`, so the channel cannot close while we wait
+            let _ = cancelled.wait_for(|cancelled| *cancelled).await;
+        };
+        match self.deadline {
+            Some(deadline) => {
+                tokio::select! {
+                    _ = wait_cancelled => {}
+                    _ = tokio::time::sleep_until(deadline) => {}
+                }
+            }
+            None => wait_cancelled.await,
+        }
+    }
+
+    /// Run an operation, dropping it when the budget ends
+    ///
+    /// The outer error is the budget error; the inner value is the operation's own
+    /// output, so callers that turn operation failures into report entries still
+    /// propagate cancellation.
+    pub async fn run<F: Future>(&self, operation: F) -> Result<F::Output> {
+        self.check()?;
+        tokio::select! {
+            output = operation => Ok(output),
+            _ = self.done() => Err(self.error().into()),
+        }
+    }
+
+    /// Run a whole command, giving it [`CANCEL_GRACE_PERIOD`] to roll back after the
+    /// budget ends before it is abandoned
+    pub async fn supervise<T>(&self, command: impl Future<Output = Result<T>>) -> Result<T> {
+        tokio::select! {
+            result = command => result,
+            _ = async {
+                self.done().await;
+                tokio::time::sleep(CANCEL_GRACE_PERIOD).await;
+            } => Err(self.error().into()),
+        }
+    }
+
+    /// Cancel on the first Ctrl+C and exit immediately on the second
+    pub fn cancel_on_ctrl_c(&self) {
+        let budget = self.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            eprintln!("\nInterrupted, rolling back (press Ctrl+C again to force quit)");
+            budget.cancel();
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(130);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_completes_within_budget() {
+        let budget = ExecutionBudget::new(Some(Duration::from_secs(5)));
+        let value = budget.run(async { 42 }).await.unwrap();
+        assert_eq!(value, 42);
+        assert!(budget.remaining().unwrap() <= Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_run_times_out() {
+        let budget = ExecutionBudget::new(Some(Duration::from_millis(20)));
+        let err = budget
+            .run(tokio::time::sleep(Duration::from_secs(10)))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ActrCliError>(),
+            Some(ActrCliError::TimedOut { .. })
+        ));
+        assert!(budget.check().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_interrupts_pending_operation() {
+        let budget = ExecutionBudget::unlimited();
+        let pending = budget.run(std::future::pending::<()>());
+        let canceller = budget.clone();
+        tokio::spawn(async move { canceller.cancel() });
+
+        let err = pending.await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ActrCliError>(),
+            Some(ActrCliError::Interrupted)
+        ));
+        assert!(budget.is_cancelled());
+    }
+}
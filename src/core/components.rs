@@ -4,21 +4,40 @@
 
 pub mod cache_manager;
 pub mod config_manager;
+pub mod config_schema;
+pub mod dependency_constraint;
 pub mod dependency_resolver;
 pub mod fingerprint_validator;
+pub mod fixtures;
+pub mod global_cache;
 pub mod network_validator;
+pub mod offline_discovery;
 pub mod proto_processor;
+pub mod registry_backend;
 pub mod service_discovery;
+pub mod source_dependency;
 pub mod user_interface;
 use actr_protocol::{ActrType, discovery_response::TypeEntry};
 pub use cache_manager::DefaultCacheManager;
-pub use config_manager::TomlConfigManager;
+pub use config_manager::{ConfigEditor, TomlConfigManager};
+pub use config_schema::ConfigIssue;
+pub use dependency_constraint::DependencyConstraint;
 pub use dependency_resolver::DefaultDependencyResolver;
 pub use fingerprint_validator::DefaultFingerprintValidator;
-pub use network_validator::DefaultNetworkValidator;
+pub use fixtures::{
+    ComponentOverride, Fixture, FixtureFingerprintValidator, FixtureNetworkValidator,
+    FixtureServiceDiscovery,
+};
+pub use global_cache::GlobalProtoCache;
+pub use network_validator::{DefaultNetworkValidator, OfflineNetworkValidator};
+pub use offline_discovery::OfflineServiceDiscovery;
 pub use proto_processor::DefaultProtoProcessor;
+pub use registry_backend::{
+    DiscoveryBackends, HttpRegistryDiscovery, RegistryBackend, StaticRegistryDiscovery,
+};
 pub use service_discovery::NetworkServiceDiscovery;
-pub use user_interface::ConsoleUI;
+pub use source_dependency::{DependencySource, SourceDependency, SourceDiscovery};
+pub use user_interface::{ConsoleProgressBar, ConsoleUI};
 
 use actr_config::Config;
 use anyhow::Result;
@@ -38,6 +57,9 @@ pub struct DependencySpec {
     pub name: String,
     pub actr_type: Option<ActrType>,
     pub fingerprint: Option<String>,
+    /// Tag, publication time and version constraints from Actr.toml
+    #[serde(default, skip_serializing_if = "DependencyConstraint::is_empty")]
+    pub constraint: DependencyConstraint,
 }
 
 /// 解析后的依赖信息
@@ -46,6 +68,10 @@ pub struct ResolvedDependency {
     pub spec: DependencySpec,
     pub fingerprint: String,
     pub proto_files: Vec<ProtoFile>,
+    /// Names of the services this dependency itself depends on
+    pub dependencies: Vec<String>,
+    /// Tag the service was selected by under the spec's constraint, kept in the lock entry
+    pub selected_tag: Option<String>,
 }
 
 /// Proto文件信息
@@ -70,6 +96,8 @@ pub struct MethodDefinition {
     pub name: String,
     pub input_type: String,
     pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
 }
 
 /// 服务信息
@@ -90,9 +118,57 @@ pub struct ServiceInfo {
 pub struct ServiceDetails {
     pub info: ServiceInfo,
     pub proto_files: Vec<ProtoFile>,
+    /// Names of the services it depends on, from its `depends-on:` tags
     pub dependencies: Vec<String>,
 }
 
+/// Tag declaring a dependency of a published or locked service:
+/// `depends-on:<service>` or `depends-on:<service>@<fingerprint>`
+pub const DEPENDS_ON_TAG_PREFIX: &str = "depends-on:";
+
+/// Dependency requirement carried by a `depends-on:` tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyRequirement {
+    pub name: String,
+    /// Fingerprint the service was built against, if pinned
+    pub fingerprint: Option<String>,
+}
+
+impl DependencyRequirement {
+    /// Requirements declared by a service's tags, in tag order
+    pub fn from_tags(tags: &[String]) -> Vec<Self> {
+        tags.iter()
+            .filter_map(|tag| tag.strip_prefix(DEPENDS_ON_TAG_PREFIX))
+            .map(|requirement| match requirement.split_once('@') {
+                Some((name, fingerprint)) => Self {
+                    name: name.to_string(),
+                    fingerprint: Some(fingerprint.to_string()),
+                },
+                None => Self {
+                    name: requirement.to_string(),
+                    fingerprint: None,
+                },
+            })
+            .filter(|requirement| !requirement.name.is_empty())
+            .collect()
+    }
+
+    /// Service names declared by a service's tags
+    pub fn names_from_tags(tags: &[String]) -> Vec<String> {
+        Self::from_tags(tags)
+            .into_iter()
+            .map(|requirement| requirement.name)
+            .collect()
+    }
+
+    pub fn to_tag(&self) -> String {
+        match &self.fingerprint {
+            Some(fingerprint) => format!("{DEPENDS_ON_TAG_PREFIX}{}@{fingerprint}", self.name),
+            None => format!("{DEPENDS_ON_TAG_PREFIX}{}", self.name),
+        }
+    }
+}
+
 /// 指纹信息
 #[derive(Debug, Clone, PartialEq)]
 pub struct Fingerprint {
@@ -147,6 +223,9 @@ pub struct FingerprintValidation {
 pub struct ConflictReport {
     pub dependency_a: String,
     pub dependency_b: String,
+    /// Service whose fingerprints disagree; `None` when the entries name different
+    /// services, so picking a version cannot settle the conflict
+    pub service: Option<String>,
     pub conflict_type: ConflictType,
     pub description: String,
 }
@@ -188,6 +267,9 @@ pub trait ConfigManager: Send + Sync {
     /// 更新依赖配置
     async fn update_dependency(&self, spec: &DependencySpec) -> Result<()>;
 
+    /// 删除依赖配置，返回是否存在该依赖
+    async fn remove_dependency(&self, alias: &str) -> Result<bool>;
+
     /// 验证配置文件
     async fn validate_config(&self) -> Result<ConfigValidation>;
 
@@ -244,11 +326,14 @@ pub trait DependencyResolver: Send + Sync {
     async fn build_dependency_graph(&self, deps: &[ResolvedDependency]) -> Result<DependencyGraph>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DependencyGraph {
     pub nodes: Vec<String>,
+    /// `(from, to)`: `from` depends on `to`
     pub edges: Vec<(String, String)>,
     pub has_cycles: bool,
+    /// Each cycle as a path that starts and ends at the same service
+    pub cycles: Vec<Vec<String>>,
 }
 
 // ============================================================================
@@ -269,6 +354,47 @@ pub trait ServiceDiscovery: Send + Sync {
 
     /// 获取服务Proto文件
     async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>>;
+
+    /// 发布或更新本地服务的 ServiceSpec
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()>;
+
+    /// Details of the newest candidate for `name` satisfying `constraint`
+    ///
+    /// Registries serve the protos of one current service per name, so the
+    /// candidate list is only consulted to explain a mismatch.
+    async fn find_service(
+        &self,
+        name: &str,
+        constraint: &DependencyConstraint,
+    ) -> Result<ServiceDetails> {
+        let details = self.get_service_details(name).await?;
+        if constraint.is_empty() || constraint.matches(&details.info) {
+            return Ok(details);
+        }
+        let newest = self
+            .discover_services(Some(&constraint.filter(name)))
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|candidate| {
+                candidate.name == details.info.name && constraint.matches(candidate)
+            })
+            .max_by_key(|candidate| candidate.published_at);
+        match newest {
+            Some(candidate) if candidate.fingerprint != details.info.fingerprint => {
+                anyhow::bail!(
+                    "'{name}' satisfying {constraint} is {}, but the registry only serves protos for {}",
+                    candidate.fingerprint,
+                    details.info.fingerprint
+                )
+            }
+            Some(_) => Ok(details),
+            None => anyhow::bail!(
+                "No '{name}' satisfies {constraint} (available tags: {})",
+                details.info.tags.join(", ")
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -397,6 +523,31 @@ pub trait FingerprintValidator: Send + Sync {
 
     /// 生成锁文件指纹
     async fn generate_lock_fingerprint(&self, deps: &[ResolvedDependency]) -> Result<Fingerprint>;
+
+    /// 计算单个 proto 文件的语义指纹（忽略空白与注释，只反映消息/服务结构）
+    async fn compute_proto_fingerprint(&self, file: &ProtoFile) -> Result<Fingerprint>;
+
+    /// 计算一组 proto 文件的服务语义指纹（`service_semantic:<hash>`，与锁文件中的格式一致）
+    async fn compute_files_fingerprint(&self, files: &[ProtoFile]) -> Result<Fingerprint>;
+
+    /// 验证发布者签名：签名须覆盖服务指纹，且指纹须与下载的 proto 一致
+    async fn verify_signature(
+        &self,
+        service: &ServiceInfo,
+        proto_files: &[ProtoFile],
+        publisher_key: &ed25519_dalek::VerifyingKey,
+    ) -> Result<SignatureStatus>;
+}
+
+/// Outcome of publisher signature verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    Valid,
+    /// The service was published without a signature
+    Unsigned,
+    Invalid {
+        reason: String,
+    },
 }
 
 // ============================================================================
@@ -439,6 +590,14 @@ pub trait CacheManager: Send + Sync {
     /// 缓存Proto文件
     async fn cache_proto(&self, service_name: &str, proto: &[ProtoFile]) -> Result<()>;
 
+    /// 暂存Proto文件：写入 `staging` 目录而不是缓存，返回它在提交时替换的缓存目录
+    async fn stage_proto(
+        &self,
+        service_name: &str,
+        proto: &[ProtoFile],
+        staging: &Path,
+    ) -> Result<PathBuf>;
+
     /// 失效缓存
     async fn invalidate_cache(&self, service_name: &str) -> Result<()>;
 
@@ -447,6 +606,9 @@ pub trait CacheManager: Send + Sync {
 
     /// 获取缓存统计
     async fn get_cache_stats(&self) -> Result<CacheStats>;
+
+    /// 列出已缓存的服务
+    async fn list_cached(&self) -> Result<Vec<CacheEntry>>;
 }
 
 #[derive(Debug, Clone)]
@@ -461,10 +623,24 @@ pub struct CachedProto {
 pub struct CacheStats {
     pub total_entries: usize,
     pub total_size_bytes: u64,
+    /// Cache lookups recorded so far (hits + misses)
+    pub lookups: u64,
     pub hit_rate: f64,
     pub miss_rate: f64,
 }
 
+/// 缓存条目
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub service: String,
+    pub files: usize,
+    pub size_bytes: u64,
+    /// Modification time of the newest cached file
+    pub cached_at: Option<std::time::SystemTime>,
+    /// Semantic fingerprint of the cached protos (`service_semantic:<hash>`)
+    pub fingerprint: Option<String>,
+}
+
 // ============================================================================
 // 8. 用户交互组件 (UserInterface)
 // ============================================================================
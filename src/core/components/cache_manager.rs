@@ -1,13 +1,28 @@
 //! Default CacheManager implementation
 //!
-//! Proto files are cached to the project's `protos/remote/` folder (not ~/.actr/cache)
-//! following the documentation spec for dependency management.
+//! Proto files are cached to the project's `protos/remote/` folder following the
+//! documentation spec for dependency management. With a global cache attached, the
+//! files are stored once in `~/.actr/cache` and hard-linked into the project.
 
 use anyhow::Result;
 use async_trait::async_trait;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use super::{CacheManager, CacheStats, CachedProto, Fingerprint, ProtoFile};
+use super::{
+    CacheEntry, CacheManager, CacheStats, CachedProto, Fingerprint, GlobalProtoCache, ProtoFile,
+};
+use crate::commands::lock::SERVICE_FINGERPRINT_PREFIX;
+
+/// Hit/miss counters kept next to the cached protos
+const STATS_FILE: &str = ".cache-stats.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LookupCounters {
+    hits: u64,
+    misses: u64,
+}
 
 /// Default cache manager (file-based, project-local)
 ///
@@ -16,48 +31,73 @@ use super::{CacheManager, CacheStats, CachedProto, Fingerprint, ProtoFile};
 pub struct DefaultCacheManager {
     /// Project root directory (where Actr.toml is located)
     project_root: PathBuf,
+    /// Shared store the project cache is linked from
+    global: Option<GlobalProtoCache>,
 }
 
 impl DefaultCacheManager {
     pub fn new() -> Self {
         Self {
             project_root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            global: None,
         }
     }
 
     pub fn with_project_root(project_root: PathBuf) -> Self {
-        Self { project_root }
+        Self {
+            project_root,
+            global: None,
+        }
+    }
+
+    /// Share cached protos through a user-level content-addressed store
+    pub fn with_global_cache(mut self, global: GlobalProtoCache) -> Self {
+        self.global = Some(global);
+        self
     }
 
     /// Get the proto cache directory for a service
     /// Returns: {project_root}/protos/remote/{service_name}/
     fn get_service_proto_dir(&self, service_name: &str) -> PathBuf {
-        self.project_root
-            .join("protos")
-            .join("remote")
-            .join(service_name)
+        self.remote_dir().join(service_name)
     }
-}
 
-impl Default for DefaultCacheManager {
-    fn default() -> Self {
-        Self::new()
+    /// Root of the remote proto cache: {project_root}/protos/remote/
+    fn remote_dir(&self) -> PathBuf {
+        self.project_root.join("protos").join("remote")
     }
-}
 
-#[async_trait]
-impl CacheManager for DefaultCacheManager {
-    async fn get_cached_proto(&self, service_name: &str) -> Result<Option<CachedProto>> {
-        let cache_path = self.get_service_proto_dir(service_name);
+    fn load_counters(&self) -> LookupCounters {
+        std::fs::read_to_string(self.remote_dir().join(STATS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
 
-        if !cache_path.exists() {
-            return Ok(None);
+    /// Record a lookup; best effort, and only once the cache directory exists
+    fn record_lookup(&self, hit: bool) {
+        let remote_dir = self.remote_dir();
+        if !remote_dir.is_dir() {
+            return;
+        }
+        let mut counters = self.load_counters();
+        if hit {
+            counters.hits += 1;
+        } else {
+            counters.misses += 1;
         }
+        if let Ok(content) = serde_json::to_string(&counters)
+            && let Err(e) = std::fs::write(remote_dir.join(STATS_FILE), content)
+        {
+            tracing::debug!("Failed to record cache lookup: {}", e);
+        }
+    }
 
+    /// Collect the proto files of one cached service, sorted by name
+    fn read_service_protos(dir: &Path) -> Result<Vec<ProtoFile>> {
         let mut files = Vec::new();
-        for entry in std::fs::read_dir(&cache_path)? {
-            let entry = entry?;
-            let path = entry.path();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
             if path.extension().map(|e| e == "proto").unwrap_or(false) {
                 let content = std::fs::read_to_string(&path)?;
                 files.push(ProtoFile {
@@ -68,6 +108,132 @@ impl CacheManager for DefaultCacheManager {
                 });
             }
         }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(files)
+    }
+
+    /// Total size and newest modification time of all files below `dir`
+    fn dir_usage(dir: &Path) -> Result<(u64, Option<SystemTime>)> {
+        let mut size = 0u64;
+        let mut newest: Option<SystemTime> = None;
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                let (sub_size, sub_newest) = Self::dir_usage(&entry.path())?;
+                size += sub_size;
+                newest = newest.max(sub_newest);
+            } else {
+                size += metadata.len();
+                newest = newest.max(metadata.modified().ok());
+            }
+        }
+        Ok((size, newest))
+    }
+
+    /// Write the protos of `service_name` into `cache_path`, linked from the global
+    /// store when one is attached
+    fn write_protos(
+        &self,
+        cache_path: &Path,
+        service_name: &str,
+        files: &[ProtoFile],
+    ) -> Result<()> {
+        std::fs::create_dir_all(cache_path)?;
+
+        // Use the proto file name directly (e.g., echo.v1.proto)
+        let named: Vec<(String, String)> = files
+            .iter()
+            .map(|file| {
+                let file_name = if file.name.ends_with(".proto") {
+                    file.name.clone()
+                } else {
+                    format!("{}.proto", file.name)
+                };
+                (file_name, file.content.clone())
+            })
+            .collect();
+
+        if let Some(global) = &self.global
+            && let Some(fingerprint) = Self::service_fingerprint(
+                named
+                    .iter()
+                    .map(|(name, content)| (name.as_str(), content.as_str())),
+            )
+        {
+            match global.store(&fingerprint, &named) {
+                Ok(entry_dir) => {
+                    GlobalProtoCache::link_into(&entry_dir, cache_path)?;
+                    tracing::info!(
+                        "Linked {} proto files of {} from {} to {}",
+                        named.len(),
+                        service_name,
+                        entry_dir.display(),
+                        cache_path.display()
+                    );
+                    return Ok(());
+                }
+                Err(e) => tracing::warn!("Global proto cache unavailable: {:#}", e),
+            }
+        }
+
+        for (file_name, content) in &named {
+            let file_path = cache_path.join(file_name);
+            // Never write through a hard link into the global store
+            if file_path.exists() {
+                std::fs::remove_file(&file_path)?;
+            }
+            std::fs::write(&file_path, content)?;
+            tracing::debug!("Cached proto file: {}", file_path.display());
+        }
+
+        tracing::info!(
+            "Cached {} proto files of {} to {}",
+            files.len(),
+            service_name,
+            cache_path.display()
+        );
+        Ok(())
+    }
+
+    /// Semantic fingerprint of `(file name, content)` pairs, in the format used by Actr.lock.toml
+    ///
+    /// Paths are left out so linked and copied caches agree.
+    fn service_fingerprint<'a>(
+        files: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Option<String> {
+        let files: Vec<actr_version::ProtoFile> = files
+            .into_iter()
+            .map(|(name, content)| actr_version::ProtoFile {
+                name: name.to_string(),
+                content: content.to_string(),
+                path: None,
+            })
+            .collect();
+        actr_version::Fingerprint::calculate_service_semantic_fingerprint(&files)
+            .ok()
+            .map(|fingerprint| format!("{SERVICE_FINGERPRINT_PREFIX}{fingerprint}"))
+    }
+}
+
+impl Default for DefaultCacheManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CacheManager for DefaultCacheManager {
+    async fn get_cached_proto(&self, service_name: &str) -> Result<Option<CachedProto>> {
+        let cache_path = self.get_service_proto_dir(service_name);
+
+        if !cache_path.exists() {
+            self.record_lookup(false);
+            return Ok(None);
+        }
+
+        let files = Self::read_service_protos(&cache_path)?;
+        self.record_lookup(!files.is_empty());
 
         if files.is_empty() {
             Ok(None)
@@ -86,30 +252,17 @@ impl CacheManager for DefaultCacheManager {
 
     async fn cache_proto(&self, service_name: &str, files: &[ProtoFile]) -> Result<()> {
         let cache_path = self.get_service_proto_dir(service_name);
-        std::fs::create_dir_all(&cache_path)?;
-
-        for file in files {
-            // Use the proto file name directly (e.g., echo.v1.proto)
-            let file_name = if file.name.ends_with(".proto") {
-                file.name.clone()
-            } else {
-                format!("{}.proto", file.name)
-            };
-            let file_path = cache_path.join(&file_name);
-            std::fs::write(&file_path, &file.content)?;
-            tracing::debug!(
-                "Cached proto file: {} -> {}",
-                file.name,
-                file_path.display()
-            );
-        }
+        self.write_protos(&cache_path, service_name, files)
+    }
 
-        tracing::info!(
-            "Cached {} proto files to protos/remote/{}/",
-            files.len(),
-            service_name
-        );
-        Ok(())
+    async fn stage_proto(
+        &self,
+        service_name: &str,
+        files: &[ProtoFile],
+        staging: &Path,
+    ) -> Result<PathBuf> {
+        self.write_protos(staging, service_name, files)?;
+        Ok(self.get_service_proto_dir(service_name))
     }
 
     async fn invalidate_cache(&self, service_name: &str) -> Result<()> {
@@ -121,36 +274,172 @@ impl CacheManager for DefaultCacheManager {
     }
 
     async fn clear_cache(&self) -> Result<()> {
-        let proto_dir = self.project_root.join("protos");
-        if proto_dir.exists() {
-            std::fs::remove_dir_all(&proto_dir)?;
+        // Only the remote cache; protos/local holds the project's own sources
+        let remote_dir = self.remote_dir();
+        if remote_dir.exists() {
+            std::fs::remove_dir_all(&remote_dir)?;
         }
         Ok(())
     }
 
     async fn get_cache_stats(&self) -> Result<CacheStats> {
-        let proto_dir = self.project_root.join("protos");
-        let mut total_size = 0u64;
-        let mut entry_count = 0usize;
-
-        if proto_dir.exists() {
-            for entry in std::fs::read_dir(&proto_dir)? {
-                entry_count += 1;
-                let entry = entry?;
-                if entry.path().is_dir() {
-                    for file in std::fs::read_dir(entry.path())? {
-                        let file = file?;
-                        total_size += file.metadata()?.len();
-                    }
-                }
-            }
-        }
+        let entries = self.list_cached().await?;
+        let counters = self.load_counters();
+        let lookups = counters.hits + counters.misses;
+        let (hit_rate, miss_rate) = if lookups == 0 {
+            (0.0, 0.0)
+        } else {
+            (
+                counters.hits as f64 / lookups as f64,
+                counters.misses as f64 / lookups as f64,
+            )
+        };
 
         Ok(CacheStats {
-            total_entries: entry_count,
-            total_size_bytes: total_size,
-            hit_rate: 0.0,
-            miss_rate: 0.0,
+            total_entries: entries.len(),
+            total_size_bytes: entries.iter().map(|e| e.size_bytes).sum(),
+            lookups,
+            hit_rate,
+            miss_rate,
         })
     }
+
+    async fn list_cached(&self) -> Result<Vec<CacheEntry>> {
+        let remote_dir = self.remote_dir();
+        if !remote_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&remote_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let path = entry.path();
+            let files = Self::read_service_protos(&path)?;
+            let (size_bytes, cached_at) = Self::dir_usage(&path)?;
+            entries.push(CacheEntry {
+                service: entry.file_name().to_string_lossy().to_string(),
+                files: files.len(),
+                size_bytes,
+                cached_at,
+                fingerprint: Self::service_fingerprint(
+                    files
+                        .iter()
+                        .map(|file| (file.name.as_str(), file.content.as_str())),
+                ),
+            });
+        }
+        entries.sort_by(|a, b| a.service.cmp(&b.service));
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ECHO_PROTO: &str =
+        "syntax = \"proto3\";\npackage echo;\nmessage Ping { string text = 1; }\n";
+
+    fn proto(name: &str) -> ProtoFile {
+        ProtoFile {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            content: ECHO_PROTO.to_string(),
+            services: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stats_and_list() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = DefaultCacheManager::with_project_root(dir.path().to_path_buf());
+
+        cache
+            .cache_proto("echo-service", &[proto("echo.proto")])
+            .await
+            .unwrap();
+        assert!(
+            cache
+                .get_cached_proto("echo-service")
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(cache.get_cached_proto("missing").await.unwrap().is_none());
+
+        let entries = cache.list_cached().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].service, "echo-service");
+        assert_eq!(entries[0].files, 1);
+        assert_eq!(entries[0].size_bytes, ECHO_PROTO.len() as u64);
+        assert!(entries[0].cached_at.is_some());
+        assert!(
+            entries[0]
+                .fingerprint
+                .as_deref()
+                .unwrap()
+                .starts_with(SERVICE_FINGERPRINT_PREFIX)
+        );
+
+        let stats = cache.get_cache_stats().await.unwrap();
+        assert_eq!(stats.total_entries, 1);
+        assert_eq!(stats.lookups, 2);
+        assert_eq!(stats.hit_rate, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_global_cache_shared_between_projects() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let global = GlobalProtoCache::new(dir.path().join("global"));
+        let first = DefaultCacheManager::with_project_root(dir.path().join("a"))
+            .with_global_cache(global.clone());
+        let second = DefaultCacheManager::with_project_root(dir.path().join("b"))
+            .with_global_cache(global.clone());
+
+        first
+            .cache_proto("echo", &[proto("echo.proto")])
+            .await
+            .unwrap();
+        second
+            .cache_proto("echo", &[proto("echo.proto")])
+            .await
+            .unwrap();
+
+        let fingerprint = first.list_cached().await.unwrap()[0]
+            .fingerprint
+            .clone()
+            .unwrap();
+        assert!(global.lookup(&fingerprint).is_some());
+        assert_eq!(std::fs::read_dir(global.root()).unwrap().count(), 1);
+        assert!(second.get_cached_proto("echo").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_and_clear_keep_local_protos() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let local = dir.path().join("protos/local");
+        std::fs::create_dir_all(&local).unwrap();
+        std::fs::write(local.join("own.proto"), ECHO_PROTO).unwrap();
+        let cache = DefaultCacheManager::with_project_root(dir.path().to_path_buf());
+
+        cache.cache_proto("a", &[proto("a.proto")]).await.unwrap();
+        cache.cache_proto("b", &[proto("b.proto")]).await.unwrap();
+
+        cache.invalidate_cache("a").await.unwrap();
+        let services: Vec<String> = cache
+            .list_cached()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.service)
+            .collect();
+        assert_eq!(services, vec!["b".to_string()]);
+
+        cache.clear_cache().await.unwrap();
+        assert!(cache.list_cached().await.unwrap().is_empty());
+        assert!(local.join("own.proto").exists());
+    }
 }
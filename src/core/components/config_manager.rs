@@ -1,13 +1,15 @@
-use actr_config::{Config, ConfigParser};
+use actr_config::Config;
 use actr_protocol::ActrTypeExt;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use tokio::fs;
-use toml_edit::{DocumentMut, InlineTable, Item, Table, Value};
+use toml_edit::{DocumentMut, InlineTable, Item, Table, TableLike, Value};
 
-use crate::core::{ConfigBackup, ConfigManager, ConfigValidation, DependencySpec};
+use crate::core::{
+    ConfigBackup, ConfigManager, ConfigValidation, DependencySpec, config_schema, profile,
+};
 
 pub struct TomlConfigManager {
     config_path: PathBuf,
@@ -24,18 +26,6 @@ impl TomlConfigManager {
         }
     }
 
-    async fn read_config_string(&self, path: &Path) -> Result<String> {
-        fs::read_to_string(path)
-            .await
-            .with_context(|| format!("Failed to read config file: {}", path.display()))
-    }
-
-    async fn write_config_string(&self, path: &Path, contents: &str) -> Result<()> {
-        fs::write(path, contents)
-            .await
-            .with_context(|| format!("Failed to write config file: {}", path.display()))
-    }
-
     fn build_backup_path(&self) -> Result<PathBuf> {
         let file_name = self
             .config_path
@@ -59,8 +49,7 @@ impl TomlConfigManager {
 #[async_trait]
 impl ConfigManager for TomlConfigManager {
     async fn load_config(&self, path: &Path) -> Result<Config> {
-        ConfigParser::from_file(path)
-            .with_context(|| format!("Failed to parse config: {}", path.display()))
+        profile::parse_config(path)
     }
 
     async fn save_config(&self, _config: &Config, _path: &Path) -> Result<()> {
@@ -70,28 +59,31 @@ impl ConfigManager for TomlConfigManager {
     }
 
     async fn update_dependency(&self, spec: &DependencySpec) -> Result<()> {
-        let contents = self.read_config_string(&self.config_path).await?;
-        let mut doc = contents
-            .parse::<DocumentMut>()
-            .with_context(|| format!("Failed to parse config: {}", self.config_path.display()))?;
+        let mut editor = ConfigEditor::open(&self.config_path)?;
+        let alias = spec.alias.as_str();
 
-        if !doc.contains_key("dependencies") {
-            doc["dependencies"] = Item::Table(Table::new());
+        // Edit the fields in place so other keys (uri, path, constraints) and the
+        // entry's comments stay as they are
+        if editor
+            .get(&["dependencies", alias])
+            .and_then(Item::as_table_like)
+            .is_none()
+        {
+            editor.set(
+                &["dependencies", alias],
+                Value::InlineTable(InlineTable::new()),
+            )?;
         }
 
-        // Preserve existing dependency entry if it exists
-        let existing_dep = doc["dependencies"]
-            .get(&spec.alias)
-            .and_then(|item| item.as_inline_table());
-
-        let mut dep_table = InlineTable::new();
-
         // Add name attribute if it differs from alias
         if spec.name != spec.alias {
-            dep_table.insert("name", Value::from(spec.name.clone()));
+            editor.set(
+                &["dependencies", alias, "name"],
+                Value::from(spec.name.clone()),
+            )?;
         }
 
-        // Add actr_type attribute - preserve existing if new one is not provided
+        // An actr_type or fingerprint that is not given keeps the existing one
         if let Some(actr_type) = &spec.actr_type {
             let actr_type_repr = actr_type.to_string_repr();
             if actr_type_repr.is_empty() {
@@ -100,38 +92,55 @@ impl ConfigManager for TomlConfigManager {
                     spec.alias
                 ));
             }
-            dep_table.insert("actr_type", Value::from(actr_type_repr));
-        } else if let Some(existing) = existing_dep {
-            // Preserve existing actr_type if new spec doesn't have one
-            if let Some(existing_actr_type) = existing.get("actr_type") {
-                dep_table.insert("actr_type", existing_actr_type.clone());
-            }
+            editor.set(
+                &["dependencies", alias, "actr_type"],
+                Value::from(actr_type_repr),
+            )?;
         }
-
-        // Add fingerprint - preserve existing if new one is not provided
         if let Some(fingerprint) = &spec.fingerprint {
-            dep_table.insert("fingerprint", Value::from(fingerprint.as_str()));
-        } else if let Some(existing) = existing_dep {
-            // Preserve existing fingerprint if new spec doesn't have one
-            if let Some(existing_fp) = existing.get("fingerprint") {
-                dep_table.insert("fingerprint", existing_fp.clone());
-            }
+            editor.set(
+                &["dependencies", alias, "fingerprint"],
+                Value::from(fingerprint.as_str()),
+            )?;
         }
 
-        doc["dependencies"][&spec.alias] = Item::Value(Value::InlineTable(dep_table));
+        if let Some(Item::Value(Value::InlineTable(entry))) =
+            editor.get_mut(&["dependencies", alias])
+        {
+            entry.fmt();
+        }
+        editor.save()
+    }
 
-        self.write_config_string(&self.config_path, &doc.to_string())
-            .await
+    async fn remove_dependency(&self, alias: &str) -> Result<bool> {
+        let mut editor = ConfigEditor::open(&self.config_path)?;
+        let removed = editor.remove(&["dependencies", alias]);
+        if removed {
+            editor.save()?;
+        }
+        Ok(removed)
     }
 
     async fn validate_config(&self) -> Result<ConfigValidation> {
-        let mut errors = Vec::new();
         let warnings = Vec::new();
 
-        let config = match ConfigParser::from_file(&self.config_path) {
+        // Schema problems come with their location; the parser's message is a fallback
+        let mut errors: Vec<String> = config_schema::validate_file(&self.config_path)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        if !errors.is_empty() {
+            return Ok(ConfigValidation {
+                is_valid: false,
+                errors,
+                warnings,
+            });
+        }
+
+        let config = match profile::parse_config(&self.config_path) {
             Ok(config) => config,
             Err(e) => {
-                errors.push(format!("Failed to parse config: {e}"));
+                errors.push(format!("Failed to parse config: {e:#}"));
                 return Ok(ConfigValidation {
                     is_valid: false,
                     errors,
@@ -140,10 +149,6 @@ impl ConfigManager for TomlConfigManager {
             }
         };
 
-        if config.package.name.trim().is_empty() {
-            errors.push("package.name is required".to_string());
-        }
-
         for dependency in &config.dependencies {
             if dependency.alias.trim().is_empty() {
                 errors.push("dependency alias is required".to_string());
@@ -223,6 +228,90 @@ impl ConfigManager for TomlConfigManager {
     }
 }
 
+/// Targeted edits of a TOML file that leave comments, key order and formatting alone
+///
+/// Only the touched keys change; a replaced value keeps the whitespace and trailing
+/// comment around it, and missing tables are created on the way.
+pub struct ConfigEditor {
+    path: PathBuf,
+    document: DocumentMut,
+}
+
+impl ConfigEditor {
+    pub fn open(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let document = contents
+            .parse::<DocumentMut>()
+            .with_context(|| format!("Failed to parse config: {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            document,
+        })
+    }
+
+    pub fn get(&self, key: &[&str]) -> Option<&Item> {
+        let mut item = self.document.as_item();
+        for segment in key {
+            item = item.as_table_like()?.get(segment)?;
+        }
+        Some(item)
+    }
+
+    pub fn get_mut(&mut self, key: &[&str]) -> Option<&mut Item> {
+        let mut item = self.document.as_item_mut();
+        for segment in key {
+            item = item.as_table_like_mut()?.get_mut(segment)?;
+        }
+        Some(item)
+    }
+
+    /// Set `key` (e.g. `["system", "signaling", "url"]`) to `value`
+    pub fn set(&mut self, key: &[&str], mut value: Value) -> Result<()> {
+        let Some((last, parents)) = key.split_last() else {
+            return Err(anyhow::anyhow!("Empty configuration key"));
+        };
+        let mut table: &mut dyn TableLike = self.document.as_table_mut();
+        for (depth, segment) in parents.iter().enumerate() {
+            if !table.contains_key(segment) {
+                let mut child = Table::new();
+                // Only the innermost new table gets a [header] of its own
+                child.set_implicit(depth + 1 < parents.len());
+                table.insert(segment, Item::Table(child));
+            }
+            table = table
+                .get_mut(segment)
+                .and_then(Item::as_table_like_mut)
+                .with_context(|| format!("'{}' is not a table", parents[..=depth].join(".")))?;
+        }
+        if let Some(existing) = table.get(last).and_then(Item::as_value) {
+            *value.decor_mut() = existing.decor().clone();
+        }
+        table.insert(last, Item::Value(value));
+        Ok(())
+    }
+
+    /// Remove `key`, returning whether it existed
+    pub fn remove(&mut self, key: &[&str]) -> bool {
+        let Some((last, parents)) = key.split_last() else {
+            return false;
+        };
+        let mut table: &mut dyn TableLike = self.document.as_table_mut();
+        for segment in parents {
+            match table.get_mut(segment).and_then(Item::as_table_like_mut) {
+                Some(child) => table = child,
+                None => return false,
+            }
+        }
+        table.remove(last).is_some()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        std::fs::write(&self.path, self.document.to_string())
+            .with_context(|| format!("Failed to write config file: {}", self.path.display()))
+    }
+}
+
 fn resolve_project_root(config_path: &Path) -> PathBuf {
     let canonical_path =
         std::fs::canonicalize(config_path).expect("Failed to canonicalize config path");
@@ -231,3 +320,96 @@ fn resolve_project_root(config_path: &Path) -> PathBuf {
         .expect("Config path must have a parent directory")
         .to_path_buf()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_protocol::ActrType;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = r#"# Demo project
+edition = 1
+
+# Services we call
+[dependencies]
+echo = { actr_type = "acme+EchoService" } # echo server
+user = { uri = "https://example.com/user.proto" }
+
+[system.signaling]
+url = "ws://127.0.0.1:8080"   # dev only
+"#;
+
+    #[tokio::test]
+    async fn test_dependency_edits_keep_comments() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+        let manager = TomlConfigManager::new(&path);
+
+        manager
+            .update_dependency(&DependencySpec {
+                alias: "echo".to_string(),
+                name: "echo".to_string(),
+                actr_type: None,
+                fingerprint: Some("sha256:abc".to_string()),
+                constraint: Default::default(),
+            })
+            .await
+            .unwrap();
+        manager
+            .update_dependency(&DependencySpec {
+                alias: "billing".to_string(),
+                name: "billing-service".to_string(),
+                actr_type: Some(ActrType::from_string_repr("acme+BillingService").unwrap()),
+                fingerprint: None,
+                constraint: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert!(manager.remove_dependency("user").await.unwrap());
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            written,
+            r#"# Demo project
+edition = 1
+
+# Services we call
+[dependencies]
+echo = { actr_type = "acme+EchoService", fingerprint = "sha256:abc" } # echo server
+billing = { name = "billing-service", actr_type = "acme+BillingService" }
+
+[system.signaling]
+url = "ws://127.0.0.1:8080"   # dev only
+"#
+        );
+    }
+
+    #[test]
+    fn test_editor_set_and_remove() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+
+        let mut editor = ConfigEditor::open(&path).unwrap();
+        editor
+            .set(
+                &["system", "signaling", "url"],
+                Value::from("wss://signaling.example.com"),
+            )
+            .unwrap();
+        editor
+            .set(
+                &["system", "observability", "filter_level"],
+                Value::from("debug"),
+            )
+            .unwrap();
+        assert!(!editor.remove(&["scripts", "dev"]));
+        editor.save().unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# Demo project\nedition = 1\n\n# Services we call\n"));
+        assert!(written.contains("url = \"wss://signaling.example.com\"   # dev only\n"));
+        assert!(written.ends_with("\n[system.observability]\nfilter_level = \"debug\"\n"));
+    }
+}
@@ -0,0 +1,501 @@
+//! Schema validation of Actr.toml
+//!
+//! `ConfigParser` stops at the first problem with a bare serde message. This pass
+//! reports every problem it finds with the TOML key path, the line and column in
+//! the base file and a suggested fix:
+//!
+//! ```text
+//! Actr.toml:12:7: system.signaling.url: 'http://localhost:8081' has scheme 'http'
+//!     help: use a ws:// or wss:// URL, e.g. "ws://127.0.0.1:8081"
+//! ```
+//!
+//! Values are checked after the active profile is merged and `${VAR}` references
+//! are expanded, so a key supplied only by an overlay is not reported missing; the
+//! location points at the key in the base file, or at its closest parent there.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml_edit::{ImDocument, Item};
+
+use crate::core::profile;
+
+/// One problem in the config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub file: PathBuf,
+    /// Dotted key path, e.g. `dependencies.user.uri` or `exports[0]`
+    pub key: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}: ", self.file.display(), self.line, self.column)?;
+        if !self.key.is_empty() {
+            write!(f, "{}: ", self.key)?;
+        }
+        f.write_str(&self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n    help: {suggestion}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Validate the config at `path`; an empty list means it is valid
+pub fn validate_file(path: &Path) -> Vec<ConfigIssue> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            return vec![Validator::new(path, "").issue(
+                &[],
+                format!("cannot be read: {e}"),
+                Some("run 'actr init' to create a project".to_string()),
+            )];
+        }
+    };
+
+    let document = match ImDocument::parse(source.clone()) {
+        Ok(document) => document,
+        Err(e) => {
+            let offset = e.span().map_or(0, |span| span.start);
+            let (line, column) = position(&source, offset);
+            return vec![ConfigIssue {
+                file: path.to_path_buf(),
+                key: String::new(),
+                line,
+                column,
+                message: format!("invalid TOML: {}", e.message().trim()),
+                suggestion: Some("fix the syntax at this position".to_string()),
+            }];
+        }
+    };
+
+    let mut validator = Validator::new(path, &source);
+    validator.document = Some(document);
+    match profile::load_value(path) {
+        Ok(value) => validator.check(&value),
+        Err(e) => validator.push(&[], format!("{e:#}"), None),
+    }
+    validator.issues
+}
+
+struct Validator<'a> {
+    path: &'a Path,
+    source: &'a str,
+    document: Option<ImDocument<String>>,
+    issues: Vec<ConfigIssue>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(path: &'a Path, source: &'a str) -> Self {
+        Self {
+            path,
+            source,
+            document: None,
+            issues: Vec::new(),
+        }
+    }
+
+    fn check(&mut self, value: &toml::Value) {
+        self.check_package(value);
+        self.check_system(value);
+        self.check_dependencies(value);
+        self.check_exports(value);
+    }
+
+    fn check_package(&mut self, value: &toml::Value) {
+        let Some(package) = value.get("package") else {
+            self.push(
+                &["package"],
+                "required section is missing".to_string(),
+                Some("add a [package] table with name and actr_type".to_string()),
+            );
+            return;
+        };
+        self.require_string(package, &["package", "name"], "name = \"my-service\"");
+        match package.get("actr_type") {
+            Some(actr_type) => {
+                for field in ["manufacturer", "name"] {
+                    self.require_string(
+                        actr_type,
+                        &["package", "actr_type", field],
+                        &format!("{field} = \"...\""),
+                    );
+                }
+            }
+            None => self.push(
+                &["package", "actr_type"],
+                "required section is missing".to_string(),
+                Some(
+                    "add [package.actr_type] with manufacturer = \"acme\" and name = \"my-service\""
+                        .to_string(),
+                ),
+            ),
+        }
+    }
+
+    fn check_system(&mut self, value: &toml::Value) {
+        let system = value.get("system");
+        let section = |name: &str| system.and_then(|system| system.get(name));
+
+        match section("signaling").and_then(|signaling| signaling.get("url")) {
+            Some(url) => self.check_url(
+                url,
+                &["system", "signaling", "url"],
+                &["ws", "wss"],
+                "a ws:// or wss:// URL, e.g. \"ws://127.0.0.1:8081\"",
+            ),
+            None => self.push(
+                &["system", "signaling", "url"],
+                "required key is missing".to_string(),
+                Some("add url = \"ws://127.0.0.1:8081\" under [system.signaling]".to_string()),
+            ),
+        }
+
+        match section("deployment").and_then(|deployment| deployment.get("realm_id")) {
+            Some(toml::Value::Integer(realm)) if (0..=i64::from(u32::MAX)).contains(realm) => {}
+            Some(realm) => self.push(
+                &["system", "deployment", "realm_id"],
+                format!("{realm} is not a valid realm id"),
+                Some(format!("use an integer between 0 and {}", u32::MAX)),
+            ),
+            None => self.push(
+                &["system", "deployment", "realm_id"],
+                "required key is missing".to_string(),
+                Some("add realm_id = 1 under [system.deployment]".to_string()),
+            ),
+        }
+
+        if let Some(endpoint) = section("observability").and_then(|o| o.get("tracing_endpoint")) {
+            self.check_url(
+                endpoint,
+                &["system", "observability", "tracing_endpoint"],
+                &["http", "https"],
+                "an http:// or https:// URL, e.g. \"http://127.0.0.1:4317\"",
+            );
+        }
+
+        let webrtc = section("webrtc");
+        for (key, schemes, example) in [
+            (
+                "stun_urls",
+                ["stun", "stuns"],
+                "\"stun:stun.example.com:3478\"",
+            ),
+            (
+                "turn_urls",
+                ["turn", "turns"],
+                "\"turn:turn.example.com:3478\"",
+            ),
+        ] {
+            let Some(urls) = webrtc.and_then(|webrtc| webrtc.get(key)) else {
+                continue;
+            };
+            let Some(urls) = urls.as_array() else {
+                self.push(
+                    &["system", "webrtc", key],
+                    "must be an array of URLs".to_string(),
+                    Some(format!("use {key} = [{example}]")),
+                );
+                continue;
+            };
+            for (index, url) in urls.iter().enumerate() {
+                let index = index.to_string();
+                self.check_url(
+                    url,
+                    &["system", "webrtc", key, &index],
+                    &schemes,
+                    &format!("a {}: URL, e.g. {example}", schemes[0]),
+                );
+            }
+        }
+    }
+
+    fn check_dependencies(&mut self, value: &toml::Value) {
+        let Some(dependencies) = value.get("dependencies").and_then(|d| d.as_table()) else {
+            return;
+        };
+
+        // Aliases become module and file names, where `user-api` and `user_api` collide
+        let mut normalized: BTreeMap<String, &str> = BTreeMap::new();
+        for (alias, entry) in dependencies {
+            let key = alias.to_lowercase().replace('-', "_");
+            if let Some(first) = normalized.insert(key, alias) {
+                self.push(
+                    &["dependencies", alias],
+                    format!("alias '{alias}' collides with '{first}' in generated code"),
+                    Some(format!("rename one of them, e.g. '{alias}-2'")),
+                );
+            }
+
+            let Some(entry) = entry.as_table() else {
+                self.push(
+                    &["dependencies", alias],
+                    "must be a table".to_string(),
+                    Some(format!("use {alias} = {{ actr_type = \"acme+Service\" }}")),
+                );
+                continue;
+            };
+            if entry.contains_key("uri") && entry.contains_key("path") {
+                self.push(
+                    &["dependencies", alias, "path"],
+                    "uri and path are both set".to_string(),
+                    Some("keep only one proto source".to_string()),
+                );
+            }
+            if let Some(uri) = entry.get("uri") {
+                self.check_url(
+                    uri,
+                    &["dependencies", alias, "uri"],
+                    &["http", "https"],
+                    "an http:// or https:// URL of the proto file",
+                );
+            }
+            if let Some(path) = entry.get("path").and_then(|p| p.as_str())
+                && !self.project_root().join(path).exists()
+            {
+                self.push(
+                    &["dependencies", alias, "path"],
+                    format!("'{path}' does not exist"),
+                    Some("use a path relative to the project root".to_string()),
+                );
+            }
+        }
+    }
+
+    fn check_exports(&mut self, value: &toml::Value) {
+        let Some(exports) = value.get("exports") else {
+            return;
+        };
+        let Some(exports) = exports.as_array() else {
+            self.push(
+                &["exports"],
+                "must be an array of proto paths".to_string(),
+                Some("use exports = [\"protos/service.proto\"]".to_string()),
+            );
+            return;
+        };
+        for (index, export) in exports.iter().enumerate() {
+            let index = index.to_string();
+            let key = ["exports", index.as_str()];
+            let Some(export) = export.as_str() else {
+                self.push(&key, "must be a string".to_string(), None);
+                continue;
+            };
+            if !export.ends_with(".proto") {
+                self.push(
+                    &key,
+                    format!("'{export}' is not a .proto file"),
+                    Some("list proto files, e.g. \"protos/service.proto\"".to_string()),
+                );
+            } else if !self.project_root().join(export).is_file() {
+                self.push(
+                    &key,
+                    format!("'{export}' does not exist"),
+                    Some("use a path relative to the project root, or remove it".to_string()),
+                );
+            }
+        }
+    }
+
+    fn require_string(&mut self, table: &toml::Value, key: &[&str], example: &str) {
+        let field = key[key.len() - 1];
+        match table.get(field) {
+            Some(toml::Value::String(value)) if !value.trim().is_empty() => {}
+            Some(toml::Value::String(_)) => self.push(key, "must not be empty".to_string(), None),
+            Some(_) => self.push(key, "must be a string".to_string(), None),
+            None => {
+                let section = key[..key.len() - 1].join(".");
+                self.push(
+                    key,
+                    "required key is missing".to_string(),
+                    Some(format!("add {example} under [{section}]")),
+                );
+            }
+        }
+    }
+
+    fn check_url(&mut self, value: &toml::Value, key: &[&str], schemes: &[&str], expected: &str) {
+        let Some(text) = value.as_str() else {
+            self.push(key, "must be a string".to_string(), None);
+            return;
+        };
+        let message = match url::Url::parse(text) {
+            Ok(url) if schemes.contains(&url.scheme()) => return,
+            Ok(url) => format!("'{text}' has scheme '{}'", url.scheme()),
+            Err(e) => format!("'{text}' is not a valid URL ({e})"),
+        };
+        self.push(key, message, Some(format!("use {expected}")));
+    }
+
+    fn project_root(&self) -> PathBuf {
+        self.path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf()
+    }
+
+    fn push(&mut self, key: &[&str], message: String, suggestion: Option<String>) {
+        let issue = self.issue(key, message, suggestion);
+        self.issues.push(issue);
+    }
+
+    fn issue(&self, key: &[&str], message: String, suggestion: Option<String>) -> ConfigIssue {
+        let offset = self
+            .document
+            .as_ref()
+            .map_or(0, |document| locate(document.as_item(), key));
+        let (line, column) = position(self.source, offset);
+        ConfigIssue {
+            file: self.path.to_path_buf(),
+            key: key_path(key),
+            line,
+            column,
+            message,
+            suggestion,
+        }
+    }
+}
+
+/// Byte offset of the value at `key`, or of its closest parent that has a position
+///
+/// Tables point at their `[header]`, the last segment at its value and any other
+/// segment at its key.
+fn locate(root: &Item, key: &[&str]) -> usize {
+    let mut offset = 0;
+    let mut item = root;
+    for (depth, segment) in key.iter().enumerate() {
+        if let Item::Value(toml_edit::Value::Array(array)) = item {
+            if let Some(span) = segment
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.get(index))
+                .and_then(|value| value.span())
+            {
+                offset = span.start;
+            }
+            break;
+        }
+        let Some(table) = item.as_table_like() else {
+            break;
+        };
+        let Some(child) = table.get(segment) else {
+            break;
+        };
+        let key_span = table.key(segment).and_then(|key| key.span());
+        let span = match child {
+            Item::Table(child) => child.span().or(key_span),
+            _ if depth + 1 == key.len() => child.span().or(key_span),
+            _ => key_span.or_else(|| child.span()),
+        };
+        if let Some(span) = span {
+            offset = span.start;
+        }
+        item = child;
+    }
+    offset
+}
+
+/// 1-based line and column of a byte offset
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map_or(0, |last| last.chars().count())
+        + 1;
+    (line, column)
+}
+
+fn key_path(key: &[&str]) -> String {
+    let mut path = String::new();
+    for segment in key {
+        if segment.chars().all(|c| c.is_ascii_digit()) {
+            path.push_str(&format!("[{segment}]"));
+        } else {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(segment);
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = r#"edition = 1
+exports = ["protos/missing.proto"]
+
+[package]
+name = "demo"
+
+[package.actr_type]
+manufacturer = "acme"
+
+[dependencies]
+user-api = { actr_type = "acme+UserService" }
+user_api = { uri = "ftp://example.com/user.proto" }
+
+[system.signaling]
+url = "http://127.0.0.1:8081"
+
+[system.deployment]
+realm_id = -1
+
+[system.webrtc]
+stun_urls = ["stun:stun.example.com:3478", "turn:turn.example.com:3478"]
+"#;
+
+    #[test]
+    fn test_issues_with_locations() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(&path, CONFIG).unwrap();
+
+        let issues = validate_file(&path);
+        let located: Vec<(&str, usize)> = issues
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.line))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                ("package.actr_type.name", 7),
+                ("system.signaling.url", 15),
+                ("system.deployment.realm_id", 18),
+                ("system.webrtc.stun_urls[1]", 21),
+                ("dependencies.user_api", 12),
+                ("dependencies.user_api.uri", 12),
+                ("exports[0]", 2),
+            ]
+        );
+        assert_eq!(issues[1].column, 7);
+        assert!(
+            issues[1]
+                .to_string()
+                .contains("help: use a ws:// or wss:// URL")
+        );
+    }
+
+    #[test]
+    fn test_syntax_error_position() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(&path, "[package]\nname = \"demo\"\nname = \"again\"\n").unwrap();
+
+        let issues = validate_file(&path);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 3);
+        assert!(issues[0].message.contains("duplicate key"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Tag, publication time and version constraints of a dependency
+//!
+//! Set on `[dependencies]` entries next to the usual keys:
+//!
+//! ```toml
+//! [dependencies]
+//! user = { actr_type = "acme+UserService", tag = "stable" }
+//! billing = { version = "^1.4", published_after = "2025-01-01" }
+//! ```
+//!
+//! `version` is a semver requirement matched against the service's version tags
+//! (`1.4.2` or `v1.4.2`); `published_after` takes a date, an RFC 3339 time or Unix
+//! seconds. Discovery keeps the newest candidate satisfying every constraint, and
+//! the tag it was selected by is recorded in the lock entry.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{DependencySpec, ServiceFilter, ServiceInfo};
+use crate::core::profile;
+
+/// Set each spec's constraint from its Actr.toml entry, matched by alias
+pub fn apply_constraints(config_path: &Path, specs: &mut [DependencySpec]) -> Result<()> {
+    let mut constraints = DependencyConstraint::from_config_file(config_path)?;
+    for spec in specs {
+        if let Some(constraint) = constraints.remove(&spec.alias) {
+            spec.constraint = constraint;
+        }
+    }
+    Ok(())
+}
+
+/// Constraints a dependency's service must satisfy
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyConstraint {
+    /// Tag the service must carry, e.g. `stable`
+    pub tag: Option<String>,
+    /// Earliest accepted publication time, Unix seconds
+    pub published_after: Option<i64>,
+    /// Semver requirement matched against version tags
+    pub version: Option<String>,
+}
+
+impl DependencyConstraint {
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_none() && self.published_after.is_none() && self.version.is_none()
+    }
+
+    /// Constraints of every `[dependencies]` entry of an Actr.toml, keyed by alias
+    pub fn from_config_file(path: &Path) -> Result<BTreeMap<String, Self>> {
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let config = profile::load_value(path)?;
+        let Some(dependencies) = config.get("dependencies").and_then(|d| d.as_table()) else {
+            return Ok(BTreeMap::new());
+        };
+        let mut constraints = BTreeMap::new();
+        for (alias, entry) in dependencies {
+            let constraint = Self::from_entry(entry)
+                .with_context(|| format!("Invalid constraint on dependency '{alias}'"))?;
+            if !constraint.is_empty() {
+                constraints.insert(alias.clone(), constraint);
+            }
+        }
+        Ok(constraints)
+    }
+
+    /// Read `tag`, `published_after` and `version` from one dependency entry
+    pub fn from_entry(entry: &toml::Value) -> Result<Self> {
+        let string = |key: &str| -> Result<Option<String>> {
+            match entry.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_str()
+                    .map(|s| Some(s.to_string()))
+                    .with_context(|| format!("{key} must be a string")),
+            }
+        };
+        let published_after = match entry.get("published_after") {
+            None => None,
+            Some(toml::Value::Integer(seconds)) => Some(*seconds),
+            Some(toml::Value::String(time)) => Some(parse_time(time)?),
+            Some(toml::Value::Datetime(time)) => Some(parse_time(&time.to_string())?),
+            Some(_) => bail!("published_after must be a date, a time or Unix seconds"),
+        };
+        let version = string("version")?;
+        if let Some(version) = &version {
+            semver::VersionReq::parse(version)
+                .with_context(|| format!("'{version}' is not a semver requirement"))?;
+        }
+        Ok(Self {
+            tag: string("tag")?,
+            published_after,
+            version,
+        })
+    }
+
+    /// Discovery filter narrowing candidates to `name` and the required tag
+    pub fn filter(&self, name: &str) -> ServiceFilter {
+        ServiceFilter {
+            name_pattern: Some(name.to_string()),
+            version_range: None,
+            tags: self.tag.clone().map(|tag| vec![tag]),
+        }
+    }
+
+    pub fn matches(&self, service: &ServiceInfo) -> bool {
+        if let Some(tag) = &self.tag
+            && !service.tags.contains(tag)
+        {
+            return false;
+        }
+        if let Some(after) = self.published_after
+            && service
+                .published_at
+                .is_none_or(|published| published < after)
+        {
+            return false;
+        }
+        self.version.is_none() || self.matching_version(service).is_some()
+    }
+
+    /// Tag `service` was selected by: the highest matching version tag, else `tag`
+    pub fn selected_tag(&self, service: &ServiceInfo) -> Option<String> {
+        self.matching_version(service)
+            .map(|(_, tag)| tag.to_string())
+            .or_else(|| self.tag.clone())
+    }
+
+    fn matching_version<'a>(&self, service: &'a ServiceInfo) -> Option<(semver::Version, &'a str)> {
+        let requirement = semver::VersionReq::parse(self.version.as_deref()?).ok()?;
+        service
+            .tags
+            .iter()
+            .filter_map(|tag| {
+                let version = semver::Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+                requirement
+                    .matches(&version)
+                    .then_some((version, tag.as_str()))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0))
+    }
+}
+
+impl std::fmt::Display for DependencyConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(tag) = &self.tag {
+            parts.push(format!("tag = \"{tag}\""));
+        }
+        if let Some(version) = &self.version {
+            parts.push(format!("version = \"{version}\""));
+        }
+        if let Some(after) = self.published_after {
+            let time = DateTime::from_timestamp(after, 0)
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_else(|| after.to_string());
+            parts.push(format!("published_after = {time}"));
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+fn parse_time(value: &str) -> Result<i64> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.timestamp());
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("'{value}' is not a date (YYYY-MM-DD) or RFC 3339 time"))?;
+    Ok(date
+        .and_hms_opt(0, 0, 0)
+        .map(|time| time.and_utc().timestamp())
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_protocol::{ActrType, ActrTypeExt};
+
+    fn service(tags: &[&str], published_at: Option<i64>) -> ServiceInfo {
+        ServiceInfo {
+            name: "billing".to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            fingerprint: "service_semantic:abc".to_string(),
+            actr_type: ActrType::from_string_repr("acme+Billing").unwrap(),
+            published_at,
+            description: None,
+            methods: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_constraints_match_and_select_tag() {
+        let entry: toml::Value =
+            toml::from_str("version = \"^1.4\"\npublished_after = \"2025-01-01\"\n").unwrap();
+        let constraint = DependencyConstraint::from_entry(&entry).unwrap();
+        assert_eq!(constraint.published_after, Some(1_735_689_600));
+
+        let current = service(
+            &["latest", "v1.4.0", "v1.5.2", "v2.0.0"],
+            Some(1_740_000_000),
+        );
+        assert!(constraint.matches(&current));
+        assert_eq!(constraint.selected_tag(&current).as_deref(), Some("v1.5.2"));
+        assert!(!constraint.matches(&service(&["v1.5.2"], Some(1_700_000_000))));
+        assert!(!constraint.matches(&service(&["v2.0.0"], Some(1_740_000_000))));
+
+        let stable = DependencyConstraint {
+            tag: Some("stable".to_string()),
+            ..Default::default()
+        };
+        assert!(!stable.matches(&current));
+        assert_eq!(
+            stable.selected_tag(&service(&["stable"], None)).as_deref(),
+            Some("stable")
+        );
+    }
+}
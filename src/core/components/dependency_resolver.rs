@@ -1,6 +1,7 @@
 use actr_config::Config;
 use anyhow::Result;
 use async_trait::async_trait;
+use std::collections::HashMap;
 
 use super::{
     ConflictReport, ConflictType, DependencyGraph, DependencyResolver, DependencySpec,
@@ -32,6 +33,7 @@ impl DependencyResolver for DefaultDependencyResolver {
                 name: dependency.name.clone(),
                 actr_type: dependency.actr_type.clone(),
                 fingerprint: dependency.fingerprint.clone(),
+                constraint: Default::default(),
             })
             .collect();
 
@@ -51,18 +53,25 @@ impl DependencyResolver for DefaultDependencyResolver {
                 .iter()
                 .find(|details| details.info.name == spec.name);
 
-            let (fingerprint, proto_files) = match matching_details {
+            let (fingerprint, proto_files, dependencies) = match matching_details {
                 Some(details) => (
                     details.info.fingerprint.clone(),
                     details.proto_files.clone(),
+                    details.dependencies.clone(),
+                ),
+                None => (
+                    spec.fingerprint.clone().unwrap_or_default(),
+                    Vec::new(),
+                    Vec::new(),
                 ),
-                None => (spec.fingerprint.clone().unwrap_or_default(), Vec::new()),
             };
 
             resolved.push(ResolvedDependency {
                 spec: spec.clone(),
                 fingerprint,
                 proto_files,
+                dependencies,
+                selected_tag: None,
             });
         }
 
@@ -83,6 +92,8 @@ impl DependencyResolver for DefaultDependencyResolver {
                         conflicts.push(ConflictReport {
                             dependency_a: deps[i].spec.alias.clone(),
                             dependency_b: deps[j].spec.alias.clone(),
+                            service: (deps[i].spec.name == deps[j].spec.name)
+                                .then(|| deps[i].spec.name.clone()),
                             conflict_type: ConflictType::VersionConflict,
                             description: format!(
                                 "Dependency alias '{}' is duplicated with different targets",
@@ -105,6 +116,7 @@ impl DependencyResolver for DefaultDependencyResolver {
                     conflicts.push(ConflictReport {
                         dependency_a: format!("{} ({})", deps[i].spec.name, deps[i].spec.alias),
                         dependency_b: format!("{} ({})", deps[j].spec.name, deps[j].spec.alias),
+                        service: Some(deps[i].spec.name.clone()),
                         conflict_type: ConflictType::FingerprintMismatch,
                         description: format!(
                             "Dependency {} has conflicting fingerprints",
@@ -119,12 +131,158 @@ impl DependencyResolver for DefaultDependencyResolver {
     }
 
     async fn build_dependency_graph(&self, deps: &[ResolvedDependency]) -> Result<DependencyGraph> {
-        let nodes: Vec<String> = deps.iter().map(|d| d.spec.alias.clone()).collect();
+        // Nodes are service names: several aliases of one service share a node
+        let mut nodes: Vec<String> = Vec::new();
+        let mut edges: Vec<(String, String)> = Vec::new();
+        for dep in deps {
+            if !nodes.contains(&dep.spec.name) {
+                nodes.push(dep.spec.name.clone());
+            }
+        }
+        for dep in deps {
+            for target in &dep.dependencies {
+                if !nodes.contains(target) {
+                    nodes.push(target.clone());
+                }
+                let edge = (dep.spec.name.clone(), target.clone());
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
 
+        let cycles = find_cycles(&nodes, &edges);
         Ok(DependencyGraph {
             nodes,
-            edges: Vec::new(),
-            has_cycles: false,
+            edges,
+            has_cycles: !cycles.is_empty(),
+            cycles,
         })
     }
 }
+
+/// Cycles found by a depth-first walk, each reported once as `[a, b, ..., a]`
+fn find_cycles(nodes: &[String], edges: &[(String, String)]) -> Vec<Vec<String>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        New,
+        OnStack,
+        Done,
+    }
+
+    fn visit<'a>(
+        node: &'a str,
+        edges: &'a [(String, String)],
+        states: &mut HashMap<&'a str, State>,
+        stack: &mut Vec<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        states.insert(node, State::OnStack);
+        stack.push(node);
+        for (_, target) in edges.iter().filter(|(from, _)| from == node) {
+            match states.get(target.as_str()).copied().unwrap_or(State::New) {
+                State::New => visit(target, edges, states, stack, cycles),
+                State::OnStack => {
+                    let start = stack
+                        .iter()
+                        .position(|n| *n == target.as_str())
+                        .unwrap_or(0);
+                    let mut cycle: Vec<String> =
+                        stack[start..].iter().map(|n| n.to_string()).collect();
+                    cycle.push(target.clone());
+                    cycles.push(cycle);
+                }
+                State::Done => {}
+            }
+        }
+        stack.pop();
+        states.insert(node, State::Done);
+    }
+
+    let mut states = HashMap::new();
+    let mut cycles = Vec::new();
+    for node in nodes {
+        if states.get(node.as_str()).copied().unwrap_or(State::New) == State::New {
+            visit(node, edges, &mut states, &mut Vec::new(), &mut cycles);
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::DependencyRequirement;
+
+    fn dep(name: &str, dependencies: &[&str]) -> ResolvedDependency {
+        ResolvedDependency {
+            spec: DependencySpec {
+                alias: name.to_string(),
+                name: name.to_string(),
+                actr_type: None,
+                fingerprint: None,
+                constraint: Default::default(),
+            },
+            fingerprint: String::new(),
+            proto_files: Vec::new(),
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+            selected_tag: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graph_without_cycles() {
+        let graph = DefaultDependencyResolver::new()
+            .build_dependency_graph(&[dep("order", &["user", "payment"]), dep("user", &[])])
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes, vec!["order", "user", "payment"]);
+        assert_eq!(graph.edges.len(), 2);
+        assert!(!graph.has_cycles);
+    }
+
+    #[tokio::test]
+    async fn test_graph_detects_cycles() {
+        let graph = DefaultDependencyResolver::new()
+            .build_dependency_graph(&[
+                dep("a", &["b"]),
+                dep("b", &["c"]),
+                dep("c", &["a"]),
+                dep("d", &["d"]),
+            ])
+            .await
+            .unwrap();
+        assert!(graph.has_cycles);
+        assert_eq!(graph.cycles, vec![vec!["a", "b", "c", "a"], vec!["d", "d"]]);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_requirement_conflicts() {
+        let tags = vec![
+            "latest".to_string(),
+            "depends-on:user@service_semantic:old".to_string(),
+            "depends-on:auth".to_string(),
+        ];
+        let requirements = DependencyRequirement::from_tags(&tags);
+        assert_eq!(requirements.len(), 2);
+        assert_eq!(requirements[0].to_tag(), tags[1]);
+        assert_eq!(requirements[1].fingerprint, None);
+
+        let mut resolved = dep("user", &[]);
+        resolved.fingerprint = "service_semantic:new".to_string();
+        let mut pinned = dep("user", &[]);
+        pinned.spec.alias = "order → user".to_string();
+        pinned.fingerprint = requirements[0].fingerprint.clone().unwrap();
+
+        let conflicts = DefaultDependencyResolver::new()
+            .check_conflicts(&[resolved, pinned])
+            .await
+            .unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert!(matches!(
+            conflicts[0].conflict_type,
+            ConflictType::FingerprintMismatch
+        ));
+        assert_eq!(conflicts[0].service.as_deref(), Some("user"));
+    }
+}
@@ -1,13 +1,20 @@
 //! Default FingerprintValidator implementation
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use sha2::{Digest, Sha256};
 use std::io::Read;
 use std::path::Path;
 use walkdir::WalkDir;
 
-use super::{Fingerprint, FingerprintValidator, ResolvedDependency, ServiceInfo};
+use super::{
+    Fingerprint, FingerprintValidator, ProtoFile, ResolvedDependency, ServiceInfo, SignatureStatus,
+};
+use crate::commands::lock::SERVICE_FINGERPRINT_PREFIX;
+use crate::core::trust;
+
+/// Algorithm name recorded for semantic proto fingerprints
+pub const SEMANTIC_ALGORITHM: &str = "semantic";
 
 /// Default fingerprint validator
 pub struct DefaultFingerprintValidator;
@@ -97,4 +104,152 @@ impl FingerprintValidator for DefaultFingerprintValidator {
             value: hex::encode(hasher.finalize()),
         })
     }
+
+    async fn compute_proto_fingerprint(&self, file: &ProtoFile) -> Result<Fingerprint> {
+        // Same algorithm as `actr fingerprint compute`, so lock entries stay comparable
+        let value = actr_version::Fingerprint::calculate_proto_semantic_fingerprint(&file.content)
+            .with_context(|| format!("Failed to calculate fingerprint of {}", file.name))?;
+        Ok(Fingerprint {
+            algorithm: SEMANTIC_ALGORITHM.to_string(),
+            value,
+        })
+    }
+
+    async fn compute_files_fingerprint(&self, files: &[ProtoFile]) -> Result<Fingerprint> {
+        let files: Vec<actr_version::ProtoFile> = files
+            .iter()
+            .map(|file| actr_version::ProtoFile {
+                name: file.name.clone(),
+                content: file.content.clone(),
+                path: None,
+            })
+            .collect();
+        let value = actr_version::Fingerprint::calculate_service_semantic_fingerprint(&files)
+            .context("Failed to calculate service fingerprint")?;
+        Ok(Fingerprint {
+            algorithm: SEMANTIC_ALGORITHM.to_string(),
+            value: format!("{SERVICE_FINGERPRINT_PREFIX}{value}"),
+        })
+    }
+
+    async fn verify_signature(
+        &self,
+        service: &ServiceInfo,
+        proto_files: &[ProtoFile],
+        publisher_key: &ed25519_dalek::VerifyingKey,
+    ) -> Result<SignatureStatus> {
+        let Some(signature) = trust::signature_from_tags(&service.tags) else {
+            return Ok(SignatureStatus::Unsigned);
+        };
+
+        // The signature covers the fingerprint, so the protos must hash to it
+        let computed = self.compute_files_fingerprint(proto_files).await?.value;
+        if computed != service.fingerprint {
+            return Ok(SignatureStatus::Invalid {
+                reason: format!(
+                    "downloaded protos hash to {computed}, but {} was signed",
+                    service.fingerprint
+                ),
+            });
+        }
+
+        Ok(
+            match trust::verify(
+                publisher_key,
+                &service.name,
+                &service.fingerprint,
+                signature,
+            ) {
+                Ok(()) => SignatureStatus::Valid,
+                Err(e) => SignatureStatus::Invalid {
+                    reason: e.to_string(),
+                },
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_protocol::{ActrType, ActrTypeExt};
+    use std::path::PathBuf;
+
+    fn proto(content: &str) -> ProtoFile {
+        ProtoFile {
+            name: "user.proto".to_string(),
+            path: PathBuf::from("user.proto"),
+            content: content.to_string(),
+            services: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proto_fingerprint_ignores_formatting() {
+        let validator = DefaultFingerprintValidator::new();
+        let compact =
+            proto("syntax = \"proto3\";\npackage user;\nmessage User { string id = 1; }\n");
+        let spaced = proto(
+            "syntax = \"proto3\";\n\n// Users\npackage user;\n\nmessage User {\n  // primary key\n  string id = 1;\n}\n",
+        );
+        let changed =
+            proto("syntax = \"proto3\";\npackage user;\nmessage User { int64 id = 1; }\n");
+
+        let a = validator.compute_proto_fingerprint(&compact).await.unwrap();
+        let b = validator.compute_proto_fingerprint(&spaced).await.unwrap();
+        let c = validator.compute_proto_fingerprint(&changed).await.unwrap();
+
+        assert_eq!(a.algorithm, SEMANTIC_ALGORITHM);
+        assert_eq!(a.value, b.value);
+        assert_ne!(a.value, c.value);
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature() {
+        let validator = DefaultFingerprintValidator::new();
+        let files = vec![proto(
+            "syntax = \"proto3\";\npackage user;\nmessage User { string id = 1; }\n",
+        )];
+        let fingerprint = actr_version::Fingerprint::calculate_service_semantic_fingerprint(&[
+            actr_version::ProtoFile {
+                name: files[0].name.clone(),
+                content: files[0].content.clone(),
+                path: None,
+            },
+        ])
+        .unwrap();
+        let fingerprint = format!("{SERVICE_FINGERPRINT_PREFIX}{fingerprint}");
+
+        let key = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        let mut service = ServiceInfo {
+            name: "user-service".to_string(),
+            tags: vec![trust::sign(&key, "user-service", &fingerprint)],
+            fingerprint,
+            actr_type: ActrType::from_string_repr("acme+UserService").unwrap(),
+            published_at: None,
+            description: None,
+            methods: Vec::new(),
+        };
+        let public = key.verifying_key();
+
+        let status = validator
+            .verify_signature(&service, &files, &public)
+            .await
+            .unwrap();
+        assert_eq!(status, SignatureStatus::Valid);
+
+        let other = ed25519_dalek::SigningKey::from_bytes(&[4u8; 32]).verifying_key();
+        let status = validator
+            .verify_signature(&service, &files, &other)
+            .await
+            .unwrap();
+        assert!(matches!(status, SignatureStatus::Invalid { .. }));
+
+        service.tags.clear();
+        let status = validator
+            .verify_signature(&service, &files, &public)
+            .await
+            .unwrap();
+        assert_eq!(status, SignatureStatus::Unsigned);
+    }
 }
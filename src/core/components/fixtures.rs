@@ -0,0 +1,556 @@
+//! Fixture-backed components for tests and demos
+//!
+//! `--component service-discovery=mock:fixture.json` (likewise `network-validator`
+//! and `fingerprint-validator`) or `ContainerBuilder::component_override` replaces
+//! a component with one that answers from a JSON file instead of the signaling
+//! server, so integration tests and demo runs are deterministic. One file can
+//! drive all three; each component reads its own section:
+//!
+//! ```json
+//! {
+//!   "services": [{
+//!     "name": "user-service",
+//!     "actr_type": "acme+UserService",
+//!     "fingerprint": "service_semantic:1a2b",
+//!     "tags": ["latest"],
+//!     "protos": [{ "path": "protos/user.proto" }, { "name": "types.proto", "content": "syntax = \"proto3\";" }]
+//!   }],
+//!   "network": {
+//!     "user-service": { "reachable": true, "latency_ms": 12 },
+//!     "*": { "reachable": false, "error": "connection refused" }
+//!   },
+//!   "fingerprints": { "user-service": "service_semantic:1a2b" }
+//! }
+//! ```
+//!
+//! Proto paths are relative to the fixture file. Network entries are keyed by
+//! service name or signaling URL, `*` being the fallback; names without an entry
+//! are unreachable. Fingerprints missing from `fingerprints` come from the
+//! service info, as with the default validator.
+
+use actr_protocol::{ActrType, ActrTypeExt};
+use anyhow::{Context, Result, anyhow};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use super::{
+    AvailabilityStatus, ConnectivityStatus, DefaultFingerprintValidator, DependencyRequirement,
+    Fingerprint, FingerprintValidator, HealthStatus, LatencyInfo, MethodDefinition,
+    NetworkCheckOptions, NetworkCheckResult, NetworkServiceDiscovery, NetworkValidator, ProtoFile,
+    ResolvedDependency, ServiceDetails, ServiceDiscovery, ServiceFilter, ServiceInfo,
+    SignatureStatus,
+};
+use crate::core::{ActrCliError, ComponentType};
+use crate::proto_parser::ProtoSchema;
+
+/// Backend prefix of `--component` values
+const MOCK_BACKEND: &str = "mock:";
+
+/// Contents of a fixture file
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    #[serde(default)]
+    pub services: Vec<FixtureService>,
+    #[serde(default)]
+    pub network: HashMap<String, FixtureConnectivity>,
+    #[serde(default)]
+    pub fingerprints: HashMap<String, String>,
+}
+
+/// A service answered by fixture discovery
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureService {
+    pub name: String,
+    pub actr_type: String,
+    #[serde(default)]
+    pub fingerprint: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub published_at: Option<i64>,
+    #[serde(default = "default_available")]
+    pub available: bool,
+    #[serde(default)]
+    pub protos: Vec<FixtureProto>,
+}
+
+fn default_available() -> bool {
+    true
+}
+
+/// A proto given inline or by a path relative to the fixture file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureProto {
+    pub name: Option<String>,
+    pub path: Option<PathBuf>,
+    pub content: Option<String>,
+}
+
+/// Connectivity reported for a service or URL
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureConnectivity {
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Fixture {
+    /// Load a fixture file, reading the protos it references
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+        let mut fixture: Fixture = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid fixture {}", path.display()))?;
+        let base = path.parent().unwrap_or(Path::new("."));
+        for service in &mut fixture.services {
+            for proto in &mut service.protos {
+                if proto.content.is_none() {
+                    let Some(proto_path) = &proto.path else {
+                        anyhow::bail!(
+                            "Proto of '{}' in {} needs a path or content",
+                            service.name,
+                            path.display()
+                        );
+                    };
+                    let proto_path = base.join(proto_path);
+                    proto.content = Some(
+                        std::fs::read_to_string(&proto_path)
+                            .with_context(|| format!("Failed to read {}", proto_path.display()))?,
+                    );
+                }
+            }
+        }
+        Ok(fixture)
+    }
+}
+
+/// One `--component <component>=mock:<fixture.json>` override
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentOverride {
+    pub component: ComponentType,
+    pub fixture: PathBuf,
+}
+
+impl FromStr for ComponentOverride {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let (component, backend) = value
+            .split_once('=')
+            .ok_or_else(|| format!("expected <component>=mock:<fixture.json>, got '{value}'"))?;
+        let component = match component.trim() {
+            "service-discovery" => ComponentType::ServiceDiscovery,
+            "network-validator" => ComponentType::NetworkValidator,
+            "fingerprint-validator" => ComponentType::FingerprintValidator,
+            other => {
+                return Err(format!(
+                    "'{other}' cannot be overridden; use service-discovery, network-validator or fingerprint-validator"
+                ));
+            }
+        };
+        let fixture = backend
+            .trim()
+            .strip_prefix(MOCK_BACKEND)
+            .filter(|path| !path.is_empty())
+            .ok_or_else(|| format!("expected mock:<fixture.json>, got '{backend}'"))?;
+        Ok(Self {
+            component,
+            fixture: PathBuf::from(fixture),
+        })
+    }
+}
+
+/// Service discovery answering from a fixture
+pub struct FixtureServiceDiscovery {
+    services: Vec<FixtureService>,
+}
+
+impl FixtureServiceDiscovery {
+    pub fn new(fixture: &Fixture) -> Self {
+        Self {
+            services: fixture.services.clone(),
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<&FixtureService> {
+        self.services
+            .iter()
+            .find(|service| service.name == name || service.actr_type == name)
+    }
+
+    fn require(&self, name: &str) -> Result<&FixtureService> {
+        self.find(name).ok_or_else(|| {
+            ActrCliError::ServiceNotFound {
+                name: name.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn to_service_info(service: &FixtureService) -> Result<ServiceInfo> {
+        let actr_type = ActrType::from_string_repr(&service.actr_type).map_err(|_| {
+            anyhow!(
+                "Invalid actr_type '{}' for '{}' in fixture",
+                service.actr_type,
+                service.name
+            )
+        })?;
+        let methods = Self::proto_files(service)
+            .iter()
+            .filter_map(|file| ProtoSchema::parse(&file.content).ok())
+            .flat_map(|schema| schema.services)
+            .flat_map(|definition| definition.methods)
+            .map(|rpc| MethodDefinition {
+                name: rpc.name,
+                input_type: rpc.input_type,
+                output_type: rpc.output_type,
+                client_streaming: rpc.client_streaming,
+                server_streaming: rpc.server_streaming,
+            })
+            .collect();
+
+        Ok(ServiceInfo {
+            name: service.name.clone(),
+            tags: service.tags.clone(),
+            fingerprint: service.fingerprint.clone(),
+            actr_type,
+            published_at: service.published_at,
+            description: service.description.clone(),
+            methods,
+        })
+    }
+
+    fn proto_files(service: &FixtureService) -> Vec<ProtoFile> {
+        service
+            .protos
+            .iter()
+            .enumerate()
+            .map(|(index, proto)| {
+                let name = proto
+                    .name
+                    .clone()
+                    .or_else(|| {
+                        proto
+                            .path
+                            .as_ref()
+                            .and_then(|path| path.file_name())
+                            .map(|name| name.to_string_lossy().to_string())
+                    })
+                    .unwrap_or_else(|| format!("{}_{index}.proto", service.name));
+                ProtoFile {
+                    path: PathBuf::from(&name),
+                    name,
+                    content: proto.content.clone().unwrap_or_default(),
+                    services: Vec::new(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for FixtureServiceDiscovery {
+    async fn discover_services(&self, filter: Option<&ServiceFilter>) -> Result<Vec<ServiceInfo>> {
+        let mut services = Vec::new();
+        for service in &self.services {
+            if let Some(pattern) = filter.and_then(|f| f.name_pattern.as_deref())
+                && !NetworkServiceDiscovery::matches_pattern(&service.name, pattern)
+                && !NetworkServiceDiscovery::matches_pattern(&service.actr_type, pattern)
+            {
+                continue;
+            }
+            if let Some(tags) = filter.and_then(|f| f.tags.as_ref())
+                && !tags.iter().all(|tag| service.tags.contains(tag))
+            {
+                continue;
+            }
+            services.push(Self::to_service_info(service)?);
+        }
+        Ok(services)
+    }
+
+    async fn get_service_details(&self, name: &str) -> Result<ServiceDetails> {
+        let service = self.require(name)?;
+        let info = Self::to_service_info(service)?;
+        let dependencies = DependencyRequirement::names_from_tags(&info.tags);
+        Ok(ServiceDetails {
+            info,
+            proto_files: Self::proto_files(service),
+            dependencies,
+        })
+    }
+
+    async fn check_service_availability(&self, name: &str) -> Result<AvailabilityStatus> {
+        let available = self.find(name).is_some_and(|service| service.available);
+        Ok(AvailabilityStatus {
+            is_available: available,
+            last_seen: available.then(std::time::SystemTime::now),
+            health: if available {
+                HealthStatus::Healthy
+            } else {
+                HealthStatus::Unknown
+            },
+        })
+    }
+
+    async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        Ok(Self::proto_files(self.require(name)?))
+    }
+
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()> {
+        tracing::info!("Fixture discovery: not publishing '{}'", spec.name);
+        Ok(())
+    }
+}
+
+/// Network validator answering from a fixture
+pub struct FixtureNetworkValidator {
+    network: HashMap<String, FixtureConnectivity>,
+}
+
+impl FixtureNetworkValidator {
+    pub fn new(fixture: &Fixture) -> Self {
+        Self {
+            network: fixture.network.clone(),
+        }
+    }
+
+    fn status(&self, name: &str) -> ConnectivityStatus {
+        match self.network.get(name).or_else(|| self.network.get("*")) {
+            Some(entry) => ConnectivityStatus {
+                is_reachable: entry.reachable,
+                response_time_ms: entry.latency_ms,
+                error: entry.error.clone().or_else(|| {
+                    (!entry.reachable).then(|| format!("'{name}' is unreachable in the fixture"))
+                }),
+            },
+            None => ConnectivityStatus {
+                is_reachable: false,
+                response_time_ms: None,
+                error: Some(format!("No network fixture for '{name}'")),
+            },
+        }
+    }
+
+    fn health(status: &ConnectivityStatus) -> HealthStatus {
+        if status.is_reachable {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy
+        }
+    }
+}
+
+#[async_trait]
+impl NetworkValidator for FixtureNetworkValidator {
+    async fn check_connectivity(
+        &self,
+        service_name: &str,
+        _options: &NetworkCheckOptions,
+    ) -> Result<ConnectivityStatus> {
+        Ok(self.status(service_name))
+    }
+
+    async fn verify_service_health(
+        &self,
+        service_name: &str,
+        _options: &NetworkCheckOptions,
+    ) -> Result<HealthStatus> {
+        Ok(Self::health(&self.status(service_name)))
+    }
+
+    async fn test_latency(
+        &self,
+        service_name: &str,
+        _options: &NetworkCheckOptions,
+    ) -> Result<LatencyInfo> {
+        let status = self.status(service_name);
+        match status.response_time_ms {
+            Some(latency) if status.is_reachable => Ok(LatencyInfo {
+                min_ms: latency,
+                max_ms: latency,
+                avg_ms: latency,
+                samples: 1,
+            }),
+            _ => anyhow::bail!("No latency for '{service_name}' in the fixture"),
+        }
+    }
+
+    async fn batch_check(
+        &self,
+        service_names: &[String],
+        _options: &NetworkCheckOptions,
+    ) -> Result<Vec<NetworkCheckResult>> {
+        Ok(service_names
+            .iter()
+            .map(|name| {
+                let connectivity = self.status(name);
+                NetworkCheckResult {
+                    health: Self::health(&connectivity),
+                    latency: connectivity.response_time_ms.map(|latency| LatencyInfo {
+                        min_ms: latency,
+                        max_ms: latency,
+                        avg_ms: latency,
+                        samples: 1,
+                    }),
+                    connectivity,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Fingerprint validator with service fingerprints taken from a fixture
+pub struct FixtureFingerprintValidator {
+    fingerprints: HashMap<String, String>,
+    inner: DefaultFingerprintValidator,
+}
+
+impl FixtureFingerprintValidator {
+    pub fn new(fixture: &Fixture) -> Self {
+        Self {
+            fingerprints: fixture.fingerprints.clone(),
+            inner: DefaultFingerprintValidator::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl FingerprintValidator for FixtureFingerprintValidator {
+    async fn compute_service_fingerprint(&self, service: &ServiceInfo) -> Result<Fingerprint> {
+        match self.fingerprints.get(&service.name) {
+            Some(value) => Ok(Fingerprint {
+                algorithm: "sha256".to_string(),
+                value: value.clone(),
+            }),
+            None => self.inner.compute_service_fingerprint(service).await,
+        }
+    }
+
+    async fn verify_fingerprint(
+        &self,
+        expected: &Fingerprint,
+        actual: &Fingerprint,
+    ) -> Result<bool> {
+        self.inner.verify_fingerprint(expected, actual).await
+    }
+
+    async fn compute_project_fingerprint(&self, project_path: &Path) -> Result<Fingerprint> {
+        self.inner.compute_project_fingerprint(project_path).await
+    }
+
+    async fn generate_lock_fingerprint(&self, deps: &[ResolvedDependency]) -> Result<Fingerprint> {
+        self.inner.generate_lock_fingerprint(deps).await
+    }
+
+    async fn compute_proto_fingerprint(&self, file: &ProtoFile) -> Result<Fingerprint> {
+        self.inner.compute_proto_fingerprint(file).await
+    }
+
+    async fn compute_files_fingerprint(&self, files: &[ProtoFile]) -> Result<Fingerprint> {
+        self.inner.compute_files_fingerprint(files).await
+    }
+
+    async fn verify_signature(
+        &self,
+        service: &ServiceInfo,
+        proto_files: &[ProtoFile],
+        publisher_key: &ed25519_dalek::VerifyingKey,
+    ) -> Result<SignatureStatus> {
+        self.inner
+            .verify_signature(service, proto_files, publisher_key)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_component_override() {
+        let parsed: ComponentOverride = "service-discovery=mock:fixtures/registry.json"
+            .parse()
+            .unwrap();
+        assert_eq!(parsed.component, ComponentType::ServiceDiscovery);
+        assert_eq!(parsed.fixture, PathBuf::from("fixtures/registry.json"));
+
+        assert!(
+            "cache-manager=mock:x.json"
+                .parse::<ComponentOverride>()
+                .is_err()
+        );
+        assert!(
+            "network-validator=x.json"
+                .parse::<ComponentOverride>()
+                .is_err()
+        );
+        assert!("network-validator".parse::<ComponentOverride>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fixture_components() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("user.proto"),
+            "syntax = \"proto3\";\npackage user;\nmessage GetUserRequest {}\nmessage User {}\nservice UserService {\n  rpc GetUser (GetUserRequest) returns (User);\n}\n",
+        )
+        .unwrap();
+        let path = dir.path().join("fixture.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "services": [
+                    { "name": "user-service", "actr_type": "acme+UserService", "fingerprint": "fp-1",
+                      "tags": ["depends-on:auth-service"], "protos": [{ "path": "user.proto" }] },
+                    { "name": "auth-service", "actr_type": "acme+AuthService", "available": false }
+                ],
+                "network": { "user-service": { "reachable": true, "latency_ms": 7 } },
+                "fingerprints": { "user-service": "fp-2" }
+            }"#,
+        )
+        .unwrap();
+        let fixture = Fixture::from_file(&path).unwrap();
+
+        let discovery = FixtureServiceDiscovery::new(&fixture);
+        let details = discovery.get_service_details("user-service").await.unwrap();
+        assert_eq!(details.proto_files[0].name, "user.proto");
+        assert_eq!(details.info.methods[0].name, "GetUser");
+        assert_eq!(details.dependencies, vec!["auth-service".to_string()]);
+        assert!(
+            !discovery
+                .check_service_availability("auth-service")
+                .await
+                .unwrap()
+                .is_available
+        );
+        assert!(discovery.get_service_details("missing").await.is_err());
+
+        let options = NetworkCheckOptions::default();
+        let network = FixtureNetworkValidator::new(&fixture);
+        let results = network
+            .batch_check(
+                &["user-service".to_string(), "auth-service".to_string()],
+                &options,
+            )
+            .await
+            .unwrap();
+        assert!(results[0].connectivity.is_reachable);
+        assert_eq!(results[0].connectivity.response_time_ms, Some(7));
+        assert!(!results[1].connectivity.is_reachable);
+
+        let fingerprints = FixtureFingerprintValidator::new(&fixture);
+        let computed = fingerprints
+            .compute_service_fingerprint(&details.info)
+            .await
+            .unwrap();
+        assert_eq!(computed.value, "fp-2");
+    }
+}
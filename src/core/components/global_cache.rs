@@ -0,0 +1,168 @@
+//! User-level content-addressed proto store
+//!
+//! Protos are stored once per service fingerprint under `~/.actr/cache/<hash>/`,
+//! so projects depending on the same service version share a single copy.
+//! Project caches (`protos/remote/<service>/`) are hard-linked from the store,
+//! falling back to plain copies across file systems.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::ProtoFile;
+
+/// Content-addressed proto store shared by all projects of the current user
+#[derive(Debug, Clone)]
+pub struct GlobalProtoCache {
+    root: PathBuf,
+}
+
+impl GlobalProtoCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Store at `~/.actr/cache`, if the home directory is known
+    pub fn user_default() -> Option<Self> {
+        dirs::home_dir().map(|home| Self::new(home.join(".actr").join("cache")))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory name for a fingerprint: the hash part, restricted to safe characters
+    pub fn key_for(fingerprint: &str) -> Option<String> {
+        let hash = fingerprint.rsplit(':').next().unwrap_or(fingerprint);
+        let valid = !hash.is_empty()
+            && hash
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        valid.then(|| hash.to_string())
+    }
+
+    fn entry_dir(&self, fingerprint: &str) -> Option<PathBuf> {
+        Self::key_for(fingerprint).map(|key| self.root.join(key))
+    }
+
+    /// Directory holding the protos of `fingerprint`, if present
+    pub fn lookup(&self, fingerprint: &str) -> Option<PathBuf> {
+        self.entry_dir(fingerprint).filter(|dir| dir.is_dir())
+    }
+
+    /// Store protos under `fingerprint`; an existing entry is left untouched
+    ///
+    /// Files are written to a temporary directory first and renamed into place,
+    /// so concurrent installs never observe a partially written entry.
+    pub fn store(&self, fingerprint: &str, files: &[(String, String)]) -> Result<PathBuf> {
+        let entry_dir = self
+            .entry_dir(fingerprint)
+            .with_context(|| format!("Invalid fingerprint '{fingerprint}'"))?;
+        if entry_dir.is_dir() {
+            return Ok(entry_dir);
+        }
+
+        std::fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create {}", self.root.display()))?;
+        let staging = tempfile::Builder::new()
+            .prefix(".staging-")
+            .tempdir_in(&self.root)?;
+        for (name, content) in files {
+            std::fs::write(staging.path().join(name), content)?;
+        }
+
+        let staging = staging.keep();
+        if let Err(e) = std::fs::rename(&staging, &entry_dir) {
+            let _ = std::fs::remove_dir_all(&staging);
+            // Another install stored the same fingerprint first
+            if !entry_dir.is_dir() {
+                return Err(e).with_context(|| format!("Failed to store {}", entry_dir.display()));
+            }
+        }
+        tracing::debug!("Stored {} in global proto cache", fingerprint);
+        Ok(entry_dir)
+    }
+
+    /// Read the protos stored under `fingerprint`
+    pub fn load(&self, fingerprint: &str) -> Result<Option<Vec<ProtoFile>>> {
+        let Some(entry_dir) = self.lookup(fingerprint) else {
+            return Ok(None);
+        };
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&entry_dir)? {
+            let path = entry?.path();
+            if path.extension().map(|e| e == "proto").unwrap_or(false) {
+                files.push(ProtoFile {
+                    name: path.file_name().unwrap().to_string_lossy().to_string(),
+                    content: std::fs::read_to_string(&path)?,
+                    path,
+                    services: Vec::new(),
+                });
+            }
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok((!files.is_empty()).then_some(files))
+    }
+
+    /// Hard-link (or copy) every file of a store entry into `target_dir`
+    pub fn link_into(entry_dir: &Path, target_dir: &Path) -> Result<usize> {
+        std::fs::create_dir_all(target_dir)?;
+        let mut linked = 0;
+        for entry in std::fs::read_dir(entry_dir)? {
+            let source = entry?.path();
+            if !source.is_file() {
+                continue;
+            }
+            let target = target_dir.join(source.file_name().unwrap());
+            if target.exists() {
+                std::fs::remove_file(&target)?;
+            }
+            if std::fs::hard_link(&source, &target).is_err() {
+                std::fs::copy(&source, &target)?;
+            }
+            linked += 1;
+        }
+        Ok(linked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_for() {
+        assert_eq!(
+            GlobalProtoCache::key_for("service_semantic:abc123").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            GlobalProtoCache::key_for("abc123").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(GlobalProtoCache::key_for("service_semantic:../x"), None);
+        assert_eq!(GlobalProtoCache::key_for("service_semantic:"), None);
+    }
+
+    #[test]
+    fn test_store_load_and_link() {
+        let home = tempfile::TempDir::new().unwrap();
+        let cache = GlobalProtoCache::new(home.path().join("cache"));
+        let files = vec![("echo.proto".to_string(), "syntax = \"proto3\";".to_string())];
+
+        let entry = cache.store("service_semantic:abc", &files).unwrap();
+        // A second store keeps the existing entry
+        assert_eq!(cache.store("service_semantic:abc", &[]).unwrap(), entry);
+
+        let loaded = cache.load("service_semantic:abc").unwrap().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].content, "syntax = \"proto3\";");
+        assert!(cache.load("service_semantic:other").unwrap().is_none());
+
+        let project = home.path().join("project/protos/remote/echo");
+        assert_eq!(GlobalProtoCache::link_into(&entry, &project).unwrap(), 1);
+        assert_eq!(
+            std::fs::read_to_string(project.join("echo.proto")).unwrap(),
+            "syntax = \"proto3\";"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Offline ServiceDiscovery implementation
+//!
+//! Resolves services exclusively from `Actr.lock.toml` and the project-local
+//! proto cache, so validation and install can run without a signaling server.
+//! Protos missing from the project are restored from the global cache by their
+//! locked fingerprint when one is attached.
+
+use actr_config::{LockFile, LockedDependency};
+use actr_protocol::{ActrType, ActrTypeExt};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::{
+    AvailabilityStatus, CacheManager, DependencyRequirement, GlobalProtoCache, HealthStatus,
+    NetworkServiceDiscovery, ProtoFile, ServiceDetails, ServiceDiscovery, ServiceFilter,
+    ServiceInfo,
+};
+use crate::core::ActrCliError;
+
+/// Service discovery backed by the lock file and cached protos
+pub struct OfflineServiceDiscovery {
+    /// Project root directory (where Actr.lock.toml is located)
+    project_root: PathBuf,
+    cache_manager: Arc<dyn CacheManager>,
+    global: Option<GlobalProtoCache>,
+}
+
+impl OfflineServiceDiscovery {
+    pub fn new(project_root: PathBuf, cache_manager: Arc<dyn CacheManager>) -> Self {
+        Self {
+            project_root,
+            cache_manager,
+            global: None,
+        }
+    }
+
+    /// Fall back to the user-level proto store for protos missing from the project
+    pub fn with_global_cache(mut self, global: GlobalProtoCache) -> Self {
+        self.global = Some(global);
+        self
+    }
+
+    fn load_lock_file(&self) -> Result<LockFile> {
+        let lock_file_path = self.project_root.join("Actr.lock.toml");
+        if !lock_file_path.exists() {
+            return Err(ActrCliError::Cache {
+                message:
+                    "Offline mode requires Actr.lock.toml. Run 'actr install' while online first."
+                        .to_string(),
+            }
+            .into());
+        }
+
+        LockFile::from_file(&lock_file_path).map_err(|e| {
+            ActrCliError::Cache {
+                message: format!("Failed to read {}: {}", lock_file_path.display(), e),
+            }
+            .into()
+        })
+    }
+
+    /// Find a locked dependency by service name or actr_type representation
+    fn find_locked<'a>(lock_file: &'a LockFile, name: &str) -> Option<&'a LockedDependency> {
+        lock_file
+            .dependencies
+            .iter()
+            .find(|dep| dep.name == name || dep.actr_type == name)
+    }
+
+    fn to_service_info(dep: &LockedDependency) -> Result<ServiceInfo> {
+        let actr_type = ActrType::from_string_repr(&dep.actr_type)
+            .map_err(|_| anyhow!("Invalid actr_type '{}' in lock file", dep.actr_type))?;
+
+        Ok(ServiceInfo {
+            name: dep.name.clone(),
+            tags: dep.tags.clone(),
+            fingerprint: dep.fingerprint.clone(),
+            actr_type,
+            published_at: dep.published_at,
+            description: dep.description.clone(),
+            methods: Vec::new(),
+        })
+    }
+
+    async fn load_cached_protos(
+        &self,
+        name: &str,
+        fingerprint: Option<&str>,
+    ) -> Result<Vec<ProtoFile>> {
+        if let Some(cached) = self.cache_manager.get_cached_proto(name).await? {
+            return Ok(cached.files);
+        }
+
+        if let Some(global) = &self.global
+            && let Some(fingerprint) = fingerprint
+            && let Some(files) = global.load(fingerprint)?
+        {
+            // Restore the project copy so later commands find it locally
+            self.cache_manager.cache_proto(name, &files).await?;
+            tracing::info!("Restored protos of '{}' from the global cache", name);
+            return Ok(files);
+        }
+
+        Err(ActrCliError::Cache {
+            message: format!(
+                "Protos for '{}' are not cached under protos/remote/{}. Run 'actr install' while online first.",
+                name, name
+            ),
+        }
+        .into())
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for OfflineServiceDiscovery {
+    async fn discover_services(&self, filter: Option<&ServiceFilter>) -> Result<Vec<ServiceInfo>> {
+        let lock_file = self.load_lock_file()?;
+
+        let mut services = Vec::new();
+        for dep in &lock_file.dependencies {
+            if let Some(pattern) = filter.and_then(|f| f.name_pattern.as_deref())
+                && !NetworkServiceDiscovery::matches_pattern(&dep.name, pattern)
+                && !NetworkServiceDiscovery::matches_pattern(&dep.actr_type, pattern)
+            {
+                continue;
+            }
+            if let Some(tags) = filter.and_then(|f| f.tags.as_ref())
+                && !tags.iter().all(|tag| dep.tags.contains(tag))
+            {
+                continue;
+            }
+            services.push(Self::to_service_info(dep)?);
+        }
+
+        Ok(services)
+    }
+
+    async fn get_service_details(&self, name: &str) -> Result<ServiceDetails> {
+        let lock_file = self.load_lock_file()?;
+        let dep = Self::find_locked(&lock_file, name).ok_or_else(|| ActrCliError::Cache {
+            message: format!(
+                "Service '{}' is not in Actr.lock.toml and cannot be resolved offline",
+                name
+            ),
+        })?;
+
+        let info = Self::to_service_info(dep)?;
+        let proto_files = self
+            .load_cached_protos(&dep.name, Some(&dep.fingerprint))
+            .await?;
+
+        // The lock file records the resolution tree as `depends-on:` tags
+        let dependencies = DependencyRequirement::names_from_tags(&info.tags);
+        Ok(ServiceDetails {
+            info,
+            proto_files,
+            dependencies,
+        })
+    }
+
+    async fn check_service_availability(&self, name: &str) -> Result<AvailabilityStatus> {
+        let lock_file = self.load_lock_file()?;
+        let available = match Self::find_locked(&lock_file, name) {
+            Some(dep) => {
+                self.cache_manager
+                    .get_cached_proto(&dep.name)
+                    .await?
+                    .is_some()
+                    || self
+                        .global
+                        .as_ref()
+                        .is_some_and(|global| global.lookup(&dep.fingerprint).is_some())
+            }
+            None => false,
+        };
+
+        Ok(AvailabilityStatus {
+            is_available: available,
+            last_seen: None,
+            health: HealthStatus::Unknown,
+        })
+    }
+
+    async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        // The lock file is optional here; it only supplies the fingerprint for the global cache
+        let locked = self.load_lock_file().ok().and_then(|lock_file| {
+            Self::find_locked(&lock_file, name)
+                .map(|dep| (dep.name.clone(), dep.fingerprint.clone()))
+        });
+        match locked {
+            Some((name, fingerprint)) => self.load_cached_protos(&name, Some(&fingerprint)).await,
+            None => self.load_cached_protos(name, None).await,
+        }
+    }
+
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()> {
+        Err(ActrCliError::InvalidArgument {
+            message: format!(
+                "Cannot publish '{}' in offline mode; run without --offline",
+                spec.name
+            ),
+        }
+        .into())
+    }
+}
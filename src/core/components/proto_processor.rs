@@ -1,10 +1,15 @@
 //! Default ProtoProcessor implementation
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
 use std::path::Path;
 
-use super::{GenerationResult, ProtoFile, ProtoProcessor, ServiceDefinition, ValidationReport};
+use super::{
+    GenerationResult, MethodDefinition, ProtoFile, ProtoProcessor, ServiceDefinition,
+    ValidationReport,
+};
+use crate::commands::codegen::{PROTOC_TOOL, descriptor};
+use crate::proto_parser::ProtoSchema;
 
 /// Default proto processor
 pub struct DefaultProtoProcessor;
@@ -13,6 +18,53 @@ impl DefaultProtoProcessor {
     pub fn new() -> Self {
         Self
     }
+
+    /// Parse one proto file from disk
+    ///
+    /// Uses the lightweight parser, which needs no protoc; the code generators
+    /// use [`Self::compile_file`] instead.
+    pub fn parse_file(path: &Path) -> Result<ProtoSchema> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        ProtoSchema::parse(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Compile one proto file with protoc and return its schema as the compiler
+    /// resolved it
+    ///
+    /// `proto_paths` are the `--proto_path` arguments; the file's own directory is
+    /// added after them. protoc is killed when the future is dropped, e.g. at the
+    /// `--timeout` deadline.
+    pub async fn compile_file(
+        proto_file: &Path,
+        proto_paths: &[String],
+    ) -> crate::error::Result<ProtoSchema> {
+        let out_dir = tempfile::tempdir()?;
+        let descriptor_path = out_dir.path().join("descriptor.pb");
+        let proto_dir = proto_file.parent().unwrap_or_else(|| Path::new("."));
+
+        let output = tokio::process::Command::new(PROTOC_TOOL.name)
+            .args(proto_paths)
+            .arg(format!("--proto_path={}", proto_dir.display()))
+            .arg("--include_source_info")
+            .arg(format!(
+                "--descriptor_set_out={}",
+                descriptor_path.display()
+            ))
+            .arg(proto_file)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| PROTOC_TOOL.spawn_error(e))?;
+        if !output.status.success() {
+            return Err(crate::error::ActrCliError::protoc_failed(
+                proto_file.display(),
+                String::from_utf8_lossy(&output.stderr),
+            ));
+        }
+
+        descriptor::decode(&std::fs::read(&descriptor_path)?, proto_file)
+    }
 }
 
 impl Default for DefaultProtoProcessor {
@@ -43,9 +95,29 @@ impl ProtoProcessor for DefaultProtoProcessor {
         Ok(files)
     }
 
-    async fn parse_proto_services(&self, _files: &[ProtoFile]) -> Result<Vec<ServiceDefinition>> {
-        // Simple stub - in a real implementation, parse the proto files
-        Ok(Vec::new())
+    async fn parse_proto_services(&self, files: &[ProtoFile]) -> Result<Vec<ServiceDefinition>> {
+        let mut services = Vec::new();
+        for file in files {
+            let schema = ProtoSchema::parse(&file.content)
+                .with_context(|| format!("Failed to parse {}", file.path.display()))?;
+            services.extend(schema.services.into_iter().map(|service| {
+                ServiceDefinition {
+                    name: service.name,
+                    methods: service
+                        .methods
+                        .into_iter()
+                        .map(|rpc| MethodDefinition {
+                            name: rpc.name,
+                            input_type: rpc.input_type,
+                            output_type: rpc.output_type,
+                            client_streaming: rpc.client_streaming,
+                            server_streaming: rpc.server_streaming,
+                        })
+                        .collect(),
+                }
+            }));
+        }
+        Ok(services)
     }
 
     async fn generate_code(&self, _input: &Path, output: &Path) -> Result<GenerationResult> {
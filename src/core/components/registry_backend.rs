@@ -0,0 +1,667 @@
+//! Registry backends for service discovery
+//!
+//! The signaling server registry is the default. `[system.discovery.backend]`
+//! selects another registry for the project, and its `dependencies` entries route
+//! single dependencies (by alias or service name) to a different one, so
+//! air-gapped and test setups work without a signaling server:
+//!
+//! ```toml
+//! [system.discovery.backend]
+//! type = "static"                  # signaling (default), static or http
+//! path = "registry.toml"           # relative to Actr.toml
+//!
+//! [system.discovery.backend.dependencies.billing]
+//! type = "http"
+//! url = "https://registry.example.com/api"
+//! token_env = "ACTR_REGISTRY_TOKEN"   # optional bearer token
+//! ```
+//!
+//! A static registry lists the services and where their protos live, as paths
+//! relative to the file or HTTP URLs. Missing fingerprints are computed from the
+//! protos:
+//!
+//! ```toml
+//! [[services]]
+//! name = "echo-service"
+//! actr_type = "acme+EchoService"
+//! tags = ["latest"]
+//! protos = ["protos/echo.proto", "https://example.com/protos/types.proto"]
+//! ```
+//!
+//! Dependencies with a `uri` or `path` are served by their own
+//! [`SourceDiscovery`] ahead of any backend (see [`super::source_dependency`]).
+//!
+//! An HTTP registry answers `GET <url>/services` with a JSON array of
+//! [`HttpService`] (protos may be omitted there), `GET <url>/services/<name>` with
+//! one service including its protos, and accepts `POST <url>/services` to publish.
+
+use actr_config::Config;
+use actr_protocol::{ActrType, ActrTypeExt};
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{
+    AvailabilityStatus, DefaultFingerprintValidator, DependencyRequirement, FingerprintValidator,
+    HealthStatus, MethodDefinition, NetworkServiceDiscovery, ProtoFile, ServiceDetails,
+    ServiceDiscovery, ServiceFilter, ServiceInfo, SourceDependency, SourceDiscovery,
+};
+use crate::core::{ActrCliError, NetworkSettings, RetryPolicy, profile};
+use crate::proto_parser::ProtoSchema;
+
+/// Where services are looked up
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RegistryBackend {
+    /// The signaling server's registry
+    #[default]
+    Signaling,
+    /// A `registry.toml` listing services and proto locations
+    Static { path: PathBuf },
+    /// A JSON registry API
+    Http {
+        url: String,
+        /// Environment variable holding a bearer token
+        token_env: Option<String>,
+    },
+}
+
+/// `[system.discovery.backend]` of an Actr.toml
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryBackends {
+    pub default: RegistryBackend,
+    /// Per-dependency backends keyed by alias or service name
+    pub dependencies: BTreeMap<String, RegistryBackend>,
+}
+
+impl DiscoveryBackends {
+    /// Load the backend table from an Actr.toml (with the active profile applied)
+    pub fn from_config_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let value = profile::load_value(path)?;
+        let table = value
+            .get("system")
+            .and_then(|system| system.get("discovery"))
+            .and_then(|discovery| discovery.get("backend"));
+        Self::from_table(table)
+    }
+
+    pub fn from_table(table: Option<&toml::Value>) -> Result<Self> {
+        let Some(table) = table else {
+            return Ok(Self::default());
+        };
+        let mut table = table
+            .as_table()
+            .context("system.discovery.backend must be a table")?
+            .clone();
+        let dependencies = match table.remove("dependencies") {
+            Some(entries) => entries
+                .try_into::<BTreeMap<String, RegistryBackend>>()
+                .context("Invalid system.discovery.backend.dependencies")?,
+            None => BTreeMap::new(),
+        };
+        let default = if table.is_empty() {
+            RegistryBackend::Signaling
+        } else {
+            toml::Value::Table(table)
+                .try_into()
+                .context("Invalid system.discovery.backend")?
+        };
+        Ok(Self {
+            default,
+            dependencies,
+        })
+    }
+}
+
+/// Service discovery for a project, honouring its registry backends
+pub fn project_discovery(
+    config_path: &Path,
+    config: Config,
+    retry: RetryPolicy,
+    network: NetworkSettings,
+) -> Result<Arc<dyn ServiceDiscovery>> {
+    let backends = DiscoveryBackends::from_config_file(config_path)?;
+    let base_dir = config_path.parent().unwrap_or(Path::new("."));
+    let build = |backend: &RegistryBackend| -> Result<Arc<dyn ServiceDiscovery>> {
+        Ok(match backend {
+            RegistryBackend::Signaling => Arc::new(
+                NetworkServiceDiscovery::new(config.clone())
+                    .with_retry_policy(retry)
+                    .with_network_settings(network.clone()),
+            ),
+            RegistryBackend::Static { path } => Arc::new(StaticRegistryDiscovery::from_file(
+                &base_dir.join(path),
+                &network,
+            )?),
+            RegistryBackend::Http { url, token_env } => {
+                let token =
+                    match token_env {
+                        Some(name) => Some(std::env::var(name).with_context(|| {
+                            format!("Registry token variable {name} is not set")
+                        })?),
+                        None => None,
+                    };
+                Arc::new(
+                    HttpRegistryDiscovery::new(url, token, &network)?
+                        .with_actr_type(config.package.actr_type.to_string_repr()),
+                )
+            }
+        })
+    };
+
+    let default = build(&backends.default)?;
+    let sources = SourceDependency::from_config_file(config_path)?;
+    if backends.dependencies.is_empty() && sources.is_empty() {
+        return Ok(default);
+    }
+    let mut routes: BTreeMap<String, Arc<dyn ServiceDiscovery>> = BTreeMap::new();
+    for (key, backend) in &backends.dependencies {
+        let name = config
+            .dependencies
+            .iter()
+            .find(|dep| dep.alias == *key)
+            .map(|dep| dep.name.clone())
+            .unwrap_or_else(|| key.clone());
+        routes.insert(name, build(backend)?);
+    }
+    // Protos pinned to a URL or path never go through a registry
+    for source in sources {
+        let name = source.name.clone();
+        routes.insert(
+            name,
+            Arc::new(SourceDiscovery::new(source, base_dir, &network)?),
+        );
+    }
+    Ok(Arc::new(RoutedServiceDiscovery { default, routes }))
+}
+
+/// Sends each service to its own backend, everything else to the default one
+pub struct RoutedServiceDiscovery {
+    default: Arc<dyn ServiceDiscovery>,
+    routes: BTreeMap<String, Arc<dyn ServiceDiscovery>>,
+}
+
+impl RoutedServiceDiscovery {
+    fn backend(&self, name: &str) -> &Arc<dyn ServiceDiscovery> {
+        self.routes.get(name).unwrap_or(&self.default)
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for RoutedServiceDiscovery {
+    async fn discover_services(&self, filter: Option<&ServiceFilter>) -> Result<Vec<ServiceInfo>> {
+        let mut services: Vec<ServiceInfo> = self
+            .default
+            .discover_services(filter)
+            .await?
+            .into_iter()
+            .filter(|service| !self.routes.contains_key(&service.name))
+            .collect();
+        for (name, backend) in &self.routes {
+            match backend.discover_services(filter).await {
+                Ok(found) => services.extend(found.into_iter().filter(|s| s.name == *name)),
+                Err(e) => tracing::warn!("Registry for '{name}' failed: {e}"),
+            }
+        }
+        Ok(services)
+    }
+
+    async fn get_service_details(&self, name: &str) -> Result<ServiceDetails> {
+        self.backend(name).get_service_details(name).await
+    }
+
+    async fn check_service_availability(&self, name: &str) -> Result<AvailabilityStatus> {
+        self.backend(name).check_service_availability(name).await
+    }
+
+    async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        self.backend(name).get_service_proto(name).await
+    }
+
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()> {
+        self.default.publish_service(spec).await
+    }
+}
+
+/// A service listed in a static `registry.toml`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StaticService {
+    pub name: String,
+    pub actr_type: String,
+    pub fingerprint: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    pub published_at: Option<i64>,
+    /// Proto paths relative to the registry file, or HTTP URLs
+    #[serde(default)]
+    pub protos: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StaticRegistryFile {
+    #[serde(default)]
+    services: Vec<StaticService>,
+}
+
+/// Discovery answering from a static registry file
+pub struct StaticRegistryDiscovery {
+    path: PathBuf,
+    services: Vec<StaticService>,
+    client: reqwest::Client,
+}
+
+impl StaticRegistryDiscovery {
+    pub fn from_file(path: &Path, network: &NetworkSettings) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read registry {}", path.display()))?;
+        let file: StaticRegistryFile = toml::from_str(&content)
+            .with_context(|| format!("Invalid registry {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            services: file.services,
+            client: network.http_client()?,
+        })
+    }
+
+    fn require(&self, name: &str) -> Result<&StaticService> {
+        self.services
+            .iter()
+            .find(|service| service.name == name || service.actr_type == name)
+            .ok_or_else(|| {
+                ActrCliError::ServiceNotFound {
+                    name: name.to_string(),
+                }
+                .into()
+            })
+    }
+
+    async fn proto_files(&self, service: &StaticService) -> Result<Vec<ProtoFile>> {
+        let base = self.path.parent().unwrap_or(Path::new("."));
+        let mut files = Vec::with_capacity(service.protos.len());
+        for location in &service.protos {
+            let content = if location.starts_with("http://") || location.starts_with("https://") {
+                self.client
+                    .get(location)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .with_context(|| format!("Failed to download {location}"))?
+                    .text()
+                    .await?
+            } else {
+                let path = base.join(location);
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?
+            };
+            let name = location
+                .rsplit(['/', '\\'])
+                .next()
+                .unwrap_or(location)
+                .to_string();
+            files.push(ProtoFile {
+                path: PathBuf::from(&name),
+                name,
+                content,
+                services: Vec::new(),
+            });
+        }
+        Ok(files)
+    }
+
+    async fn service_info(
+        &self,
+        service: &StaticService,
+        files: &[ProtoFile],
+    ) -> Result<ServiceInfo> {
+        let fingerprint = match &service.fingerprint {
+            Some(fingerprint) => fingerprint.clone(),
+            None => {
+                DefaultFingerprintValidator::new()
+                    .compute_files_fingerprint(files)
+                    .await?
+                    .value
+            }
+        };
+        Ok(ServiceInfo {
+            name: service.name.clone(),
+            tags: service.tags.clone(),
+            fingerprint,
+            actr_type: parse_actr_type(&service.actr_type, &service.name)?,
+            published_at: service.published_at,
+            description: service.description.clone(),
+            methods: methods_of(files),
+        })
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for StaticRegistryDiscovery {
+    async fn discover_services(&self, filter: Option<&ServiceFilter>) -> Result<Vec<ServiceInfo>> {
+        let mut services = Vec::new();
+        for service in &self.services {
+            if !matches(&service.name, &service.actr_type, &service.tags, filter) {
+                continue;
+            }
+            let files = self.proto_files(service).await?;
+            services.push(self.service_info(service, &files).await?);
+        }
+        Ok(services)
+    }
+
+    async fn get_service_details(&self, name: &str) -> Result<ServiceDetails> {
+        let service = self.require(name)?;
+        let proto_files = self.proto_files(service).await?;
+        let info = self.service_info(service, &proto_files).await?;
+        let dependencies = DependencyRequirement::names_from_tags(&info.tags);
+        Ok(ServiceDetails {
+            info,
+            proto_files,
+            dependencies,
+        })
+    }
+
+    async fn check_service_availability(&self, name: &str) -> Result<AvailabilityStatus> {
+        // Listed services are always installable; there is no liveness to report
+        let listed = self.require(name).is_ok();
+        Ok(AvailabilityStatus {
+            is_available: listed,
+            last_seen: None,
+            health: HealthStatus::Unknown,
+        })
+    }
+
+    async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        self.proto_files(self.require(name)?).await
+    }
+
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()> {
+        bail!(
+            "Cannot publish '{}': the static registry {} is read-only; add the service to it instead",
+            spec.name,
+            self.path.display()
+        )
+    }
+}
+
+/// A service as exchanged with an HTTP registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpService {
+    pub name: String,
+    pub actr_type: String,
+    #[serde(default)]
+    pub fingerprint: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<i64>,
+    #[serde(default)]
+    pub protos: Vec<HttpProto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpProto {
+    pub name: String,
+    pub content: String,
+}
+
+/// Discovery against an HTTP registry API
+pub struct HttpRegistryDiscovery {
+    url: String,
+    token: Option<String>,
+    /// actr_type sent along with published services
+    actr_type: Option<String>,
+    client: reqwest::Client,
+}
+
+impl HttpRegistryDiscovery {
+    pub fn new(url: &str, token: Option<String>, network: &NetworkSettings) -> Result<Self> {
+        Ok(Self {
+            url: url.trim_end_matches('/').to_string(),
+            token,
+            actr_type: None,
+            client: network.http_client()?,
+        })
+    }
+
+    pub fn with_actr_type(mut self, actr_type: String) -> Self {
+        self.actr_type = Some(actr_type);
+        self
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, format!("{}{path}", self.url));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        let response = self
+            .request(reqwest::Method::GET, path)
+            .send()
+            .await
+            .with_context(|| format!("Registry request to {}{path} failed", self.url))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let body = response
+            .error_for_status()
+            .with_context(|| format!("Registry request to {}{path} failed", self.url))?
+            .text()
+            .await?;
+        serde_json::from_str(&body)
+            .map(Some)
+            .with_context(|| format!("Invalid registry response from {}{path}", self.url))
+    }
+
+    async fn service(&self, name: &str) -> Result<HttpService> {
+        let encoded: String = url::form_urlencoded::byte_serialize(name.as_bytes()).collect();
+        let path = format!("/services/{encoded}");
+        self.get(&path).await?.ok_or_else(|| {
+            ActrCliError::ServiceNotFound {
+                name: name.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn proto_files(service: &HttpService) -> Vec<ProtoFile> {
+        service
+            .protos
+            .iter()
+            .map(|proto| ProtoFile {
+                name: proto.name.clone(),
+                path: PathBuf::from(&proto.name),
+                content: proto.content.clone(),
+                services: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn service_info(service: &HttpService) -> Result<ServiceInfo> {
+        Ok(ServiceInfo {
+            name: service.name.clone(),
+            tags: service.tags.clone(),
+            fingerprint: service.fingerprint.clone(),
+            actr_type: parse_actr_type(&service.actr_type, &service.name)?,
+            published_at: service.published_at,
+            description: service.description.clone(),
+            methods: methods_of(&Self::proto_files(service)),
+        })
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for HttpRegistryDiscovery {
+    async fn discover_services(&self, filter: Option<&ServiceFilter>) -> Result<Vec<ServiceInfo>> {
+        let services: Vec<HttpService> = self.get("/services").await?.unwrap_or_default();
+        services
+            .iter()
+            .filter(|s| matches(&s.name, &s.actr_type, &s.tags, filter))
+            .map(Self::service_info)
+            .collect()
+    }
+
+    async fn get_service_details(&self, name: &str) -> Result<ServiceDetails> {
+        let service = self.service(name).await?;
+        let info = Self::service_info(&service)?;
+        let dependencies = DependencyRequirement::names_from_tags(&info.tags);
+        Ok(ServiceDetails {
+            info,
+            proto_files: Self::proto_files(&service),
+            dependencies,
+        })
+    }
+
+    async fn check_service_availability(&self, name: &str) -> Result<AvailabilityStatus> {
+        let available = self.service(name).await.is_ok();
+        Ok(AvailabilityStatus {
+            is_available: available,
+            last_seen: available.then(std::time::SystemTime::now),
+            health: if available {
+                HealthStatus::Healthy
+            } else {
+                HealthStatus::Unknown
+            },
+        })
+    }
+
+    async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        Ok(Self::proto_files(&self.service(name).await?))
+    }
+
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()> {
+        let service = HttpService {
+            actr_type: self.actr_type.clone().unwrap_or_default(),
+            fingerprint: spec.fingerprint.clone(),
+            tags: spec.tags.clone(),
+            description: spec.description.clone(),
+            published_at: spec.published_at,
+            protos: spec
+                .protobufs
+                .iter()
+                .map(|proto| HttpProto {
+                    name: format!("{}.proto", proto.package),
+                    content: proto.content.clone(),
+                })
+                .collect(),
+            name: spec.name.clone(),
+        };
+        self.request(reqwest::Method::POST, "/services")
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_string(&service)?)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to publish '{}' to {}", spec.name, self.url))?;
+        tracing::info!(
+            "Published {} ({}) to {}",
+            spec.name,
+            spec.fingerprint,
+            self.url
+        );
+        Ok(())
+    }
+}
+
+fn parse_actr_type(actr_type: &str, name: &str) -> Result<ActrType> {
+    ActrType::from_string_repr(actr_type)
+        .map_err(|_| anyhow!("Invalid actr_type '{actr_type}' for '{name}' in registry"))
+}
+
+fn matches(name: &str, actr_type: &str, tags: &[String], filter: Option<&ServiceFilter>) -> bool {
+    if let Some(pattern) = filter.and_then(|f| f.name_pattern.as_deref())
+        && !NetworkServiceDiscovery::matches_pattern(name, pattern)
+        && !NetworkServiceDiscovery::matches_pattern(actr_type, pattern)
+    {
+        return false;
+    }
+    filter
+        .and_then(|f| f.tags.as_ref())
+        .is_none_or(|wanted| wanted.iter().all(|tag| tags.contains(tag)))
+}
+
+pub(crate) fn methods_of(files: &[ProtoFile]) -> Vec<MethodDefinition> {
+    files
+        .iter()
+        .filter_map(|file| ProtoSchema::parse(&file.content).ok())
+        .flat_map(|schema| schema.services)
+        .flat_map(|definition| definition.methods)
+        .map(|rpc| MethodDefinition {
+            name: rpc.name,
+            input_type: rpc.input_type,
+            output_type: rpc.output_type,
+            client_streaming: rpc.client_streaming,
+            server_streaming: rpc.server_streaming,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backend_table_with_dependency_routes() {
+        let table: toml::Value = toml::from_str(
+            "type = \"static\"\npath = \"registry.toml\"\n\n[dependencies.billing]\ntype = \"http\"\nurl = \"https://registry.example.com\"\n",
+        )
+        .unwrap();
+        let backends = DiscoveryBackends::from_table(Some(&table)).unwrap();
+        assert_eq!(
+            backends.default,
+            RegistryBackend::Static {
+                path: PathBuf::from("registry.toml")
+            }
+        );
+        assert_eq!(
+            backends.dependencies["billing"],
+            RegistryBackend::Http {
+                url: "https://registry.example.com".to_string(),
+                token_env: None
+            }
+        );
+        assert_eq!(
+            DiscoveryBackends::from_table(None).unwrap().default,
+            RegistryBackend::Signaling
+        );
+    }
+
+    #[tokio::test]
+    async fn test_static_registry_computes_missing_fingerprint() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("protos")).unwrap();
+        std::fs::write(
+            dir.path().join("protos/echo.proto"),
+            "syntax = \"proto3\";\npackage echo;\nservice Echo { rpc Say(Msg) returns (Msg); }\nmessage Msg { string text = 1; }\n",
+        )
+        .unwrap();
+        let registry = dir.path().join("registry.toml");
+        std::fs::write(
+            &registry,
+            "[[services]]\nname = \"echo-service\"\nactr_type = \"acme+EchoService\"\ntags = [\"latest\"]\nprotos = [\"protos/echo.proto\"]\n",
+        )
+        .unwrap();
+
+        let discovery =
+            StaticRegistryDiscovery::from_file(&registry, &NetworkSettings::default()).unwrap();
+        let details = discovery.get_service_details("echo-service").await.unwrap();
+        assert!(details.info.fingerprint.starts_with("service_semantic:"));
+        assert_eq!(details.proto_files[0].name, "echo.proto");
+        assert_eq!(details.info.methods[0].name, "Say");
+        assert!(discovery.publish_service(Default::default()).await.is_err());
+    }
+}
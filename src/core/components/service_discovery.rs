@@ -1,14 +1,14 @@
 use crate::core::{
-    AvailabilityStatus, HealthStatus, ProtoFile, ServiceDetails, ServiceDiscovery, ServiceFilter,
-    ServiceInfo,
+    AvailabilityStatus, DependencyRequirement, HealthStatus, NetworkSettings, ProtoFile,
+    RetryPolicy, ServiceDetails, ServiceDiscovery, ServiceFilter, ServiceInfo,
 };
 use actr_config::Config;
 use actr_protocol::ActrTypeExt;
 use actr_protocol::{
     AIdCredential, ActrId, ActrToSignaling, ActrType, DiscoveryRequest, ErrorResponse,
-    GetServiceSpecRequest, PeerToSignaling, RegisterRequest, SignalingEnvelope, actr_to_signaling,
-    discovery_response, get_service_spec_response, peer_to_signaling, register_response,
-    signaling_envelope, signaling_to_actr,
+    GetServiceSpecRequest, PeerToSignaling, RegisterRequest, ServiceSpec, SignalingEnvelope,
+    actr_to_signaling, discovery_response, get_service_spec_response, peer_to_signaling,
+    register_response, signaling_envelope, signaling_to_actr,
 };
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
@@ -17,7 +17,7 @@ use prost::Message;
 use std::path::PathBuf;
 use std::time::SystemTime;
 use tokio::sync::Mutex;
-use tokio_tungstenite::{connect_async, tungstenite::Message as WsMessage};
+use tokio_tungstenite::tungstenite::Message as WsMessage;
 
 type SignalingSocket =
     tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
@@ -31,6 +31,8 @@ struct SignalingState {
 pub struct NetworkServiceDiscovery {
     config: Config,
     state: Mutex<Option<SignalingState>>,
+    retry: RetryPolicy,
+    network: NetworkSettings,
 }
 
 impl NetworkServiceDiscovery {
@@ -38,9 +40,23 @@ impl NetworkServiceDiscovery {
         Self {
             config,
             state: Mutex::new(None),
+            retry: RetryPolicy::default(),
+            network: NetworkSettings::default(),
         }
     }
 
+    /// Retry policy for signaling requests
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Proxy and TLS settings for the signaling connection
+    pub fn with_network_settings(mut self, network: NetworkSettings) -> Self {
+        self.network = network;
+        self
+    }
+
     fn format_actr_type(actr_type: &ActrType) -> String {
         actr_type.to_string_repr()
     }
@@ -56,8 +72,18 @@ impl NetworkServiceDiscovery {
         Ok(())
     }
 
-    // TODO: add filter support
     async fn discover_entries(
+        &self,
+        filter: Option<&ServiceFilter>,
+    ) -> Result<Vec<discovery_response::TypeEntry>> {
+        // A failed attempt drops the signaling state, so each retry reconnects
+        self.retry
+            .run("signaling discovery", || self.discover_entries_once(filter))
+            .await
+    }
+
+    // TODO: add filter support
+    async fn discover_entries_once(
         &self,
         _filter: Option<&ServiceFilter>,
     ) -> Result<Vec<discovery_response::TypeEntry>> {
@@ -107,6 +133,75 @@ impl NetworkServiceDiscovery {
         result
     }
 
+    async fn get_service_proto_once(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        self.ensure_connected().await?;
+        let mut state_guard = self.state.lock().await;
+        let state = state_guard
+            .as_mut()
+            .context("Signaling state not initialized")?;
+
+        let request = GetServiceSpecRequest {
+            name: name.to_string(),
+        };
+        let payload = actr_to_signaling::Payload::GetServiceSpecRequest(request);
+        let envelope =
+            Self::build_envelope(signaling_envelope::Flow::ActrToServer(ActrToSignaling {
+                source: state.actr_id.clone(),
+                credential: state.credential.clone(),
+                payload: Some(payload),
+            }))?;
+
+        let result = match Self::send_envelope(&mut state.socket, envelope).await {
+            Ok(()) => loop {
+                let envelope = Self::read_envelope(&mut state.socket).await?;
+                match envelope.flow {
+                    Some(signaling_envelope::Flow::ServerToActr(server)) => match server.payload {
+                        Some(signaling_to_actr::Payload::GetServiceSpecResponse(response)) => {
+                            let proto_files = match response.result {
+                                Some(get_service_spec_response::Result::Success(success)) => {
+                                    success
+                                        .protobufs
+                                        .into_iter()
+                                        .map(|p| ProtoFile {
+                                            name: format!("{}.proto", p.package),
+                                            path: PathBuf::new(),
+                                            content: p.content,
+                                            services: Vec::new(),
+                                        })
+                                        .collect()
+                                }
+                                Some(get_service_spec_response::Result::Error(error)) => {
+                                    break Err(Self::as_error("Get service spec failed", &error));
+                                }
+                                None => {
+                                    break Err(anyhow!(
+                                        "Get service spec response is missing result"
+                                    ));
+                                }
+                            };
+                            break Ok(proto_files);
+                        }
+                        Some(signaling_to_actr::Payload::Error(error)) => {
+                            break Err(Self::as_error("Get service spec failed", &error));
+                        }
+                        _ => {}
+                    },
+                    Some(signaling_envelope::Flow::EnvelopeError(error)) => {
+                        break Err(Self::as_error("Get service spec failed", &error));
+                    }
+                    _ => {}
+                }
+            },
+            Err(err) => Err(err),
+        };
+
+        if result.is_err() {
+            *state_guard = None;
+        }
+
+        result
+    }
+
     fn handle_discovery_response(
         response: actr_protocol::DiscoveryResponse,
     ) -> Result<Vec<discovery_response::TypeEntry>> {
@@ -120,15 +215,22 @@ impl NetworkServiceDiscovery {
     }
 
     async fn connect_and_register(&self) -> Result<SignalingState> {
+        self.register(None).await
+    }
+
+    /// Register with signaling, optionally announcing a ServiceSpec
+    async fn register(&self, service_spec: Option<ServiceSpec>) -> Result<SignalingState> {
         let signaling_url = self.config.signaling_url.as_str();
-        let (mut socket, _) = connect_async(signaling_url)
+        let mut socket = self
+            .network
+            .connect_websocket(signaling_url)
             .await
             .with_context(|| format!("Failed to connect to signaling: {signaling_url}"))?;
 
         let register_request = RegisterRequest {
             actr_type: self.config.package.actr_type.clone(),
             realm: self.config.realm,
-            service_spec: None,
+            service_spec,
             acl: None,
         };
 
@@ -267,7 +369,7 @@ impl NetworkServiceDiscovery {
         true
     }
 
-    fn matches_pattern(value: &str, pattern: &str) -> bool {
+    pub(crate) fn matches_pattern(value: &str, pattern: &str) -> bool {
         if pattern == "*" {
             return true;
         }
@@ -355,10 +457,11 @@ impl ServiceDiscovery for NetworkServiceDiscovery {
             }
         };
 
+        let dependencies = DependencyRequirement::names_from_tags(&info.tags);
         Ok(ServiceDetails {
             info,
             proto_files,
-            dependencies: Vec::new(),
+            dependencies,
         })
     }
 
@@ -379,71 +482,23 @@ impl ServiceDiscovery for NetworkServiceDiscovery {
     }
 
     async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
-        self.ensure_connected().await?;
-        let mut state_guard = self.state.lock().await;
-        let state = state_guard
-            .as_mut()
-            .context("Signaling state not initialized")?;
-
-        let request = GetServiceSpecRequest {
-            name: name.to_string(),
-        };
-        let payload = actr_to_signaling::Payload::GetServiceSpecRequest(request);
-        let envelope =
-            Self::build_envelope(signaling_envelope::Flow::ActrToServer(ActrToSignaling {
-                source: state.actr_id.clone(),
-                credential: state.credential.clone(),
-                payload: Some(payload),
-            }))?;
-
-        let result = match Self::send_envelope(&mut state.socket, envelope).await {
-            Ok(()) => loop {
-                let envelope = Self::read_envelope(&mut state.socket).await?;
-                match envelope.flow {
-                    Some(signaling_envelope::Flow::ServerToActr(server)) => match server.payload {
-                        Some(signaling_to_actr::Payload::GetServiceSpecResponse(response)) => {
-                            let proto_files = match response.result {
-                                Some(get_service_spec_response::Result::Success(success)) => {
-                                    success
-                                        .protobufs
-                                        .into_iter()
-                                        .map(|p| ProtoFile {
-                                            name: format!("{}.proto", p.package),
-                                            path: PathBuf::new(),
-                                            content: p.content,
-                                            services: Vec::new(),
-                                        })
-                                        .collect()
-                                }
-                                Some(get_service_spec_response::Result::Error(error)) => {
-                                    break Err(Self::as_error("Get service spec failed", &error));
-                                }
-                                None => {
-                                    break Err(anyhow!(
-                                        "Get service spec response is missing result"
-                                    ));
-                                }
-                            };
-                            break Ok(proto_files);
-                        }
-                        Some(signaling_to_actr::Payload::Error(error)) => {
-                            break Err(Self::as_error("Get service spec failed", &error));
-                        }
-                        _ => {}
-                    },
-                    Some(signaling_envelope::Flow::EnvelopeError(error)) => {
-                        break Err(Self::as_error("Get service spec failed", &error));
-                    }
-                    _ => {}
-                }
-            },
-            Err(err) => Err(err),
-        };
-
-        if result.is_err() {
-            *state_guard = None;
-        }
+        self.retry
+            .run(&format!("signaling service spec {name}"), || {
+                self.get_service_proto_once(name)
+            })
+            .await
+    }
 
-        result
+    async fn publish_service(&self, spec: ServiceSpec) -> Result<()> {
+        // Publishing uses its own registration; the cached discovery session is left alone
+        let mut state = self
+            .retry
+            .run(&format!("signaling publish {}", spec.name), || {
+                self.register(Some(spec.clone()))
+            })
+            .await?;
+        let _ = state.socket.close(None).await;
+        tracing::info!("Published {} ({})", spec.name, spec.fingerprint);
+        Ok(())
     }
 }
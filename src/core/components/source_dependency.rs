@@ -0,0 +1,347 @@
+//! Dependencies whose protos come from a URL or a local path
+//!
+//! A `[dependencies]` entry with `uri` or `path` skips the registry:
+//!
+//! ```toml
+//! [dependencies]
+//! user = { uri = "https://example.com/user.proto" }
+//! shared = { path = "../shared-protos", actr_type = "acme+SharedTypes" }
+//! ```
+//!
+//! A `path` is a `.proto` file or a directory searched recursively, relative to
+//! Actr.toml. Each entry is served by a [`SourceDiscovery`] routed in front of the
+//! registry backends, so resolution, caching, fingerprinting and locking go
+//! through the regular install pipeline. The fingerprint is computed from the
+//! protos, and the service info carries a `source:<uri|path>` tag recording where
+//! they came from. `actr_type` defaults to `external+<name>`.
+
+use actr_protocol::{ActrType, ActrTypeExt};
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::{
+    AvailabilityStatus, DefaultFingerprintValidator, FingerprintValidator, HealthStatus, ProtoFile,
+    ServiceDetails, ServiceDiscovery, ServiceFilter, ServiceInfo, registry_backend,
+};
+use crate::core::{ActrCliError, NetworkSettings, profile};
+
+/// Tag prefix recording where a source dependency's protos came from
+pub const SOURCE_TAG_PREFIX: &str = "source:";
+
+/// Manufacturer used when a source dependency declares no `actr_type`
+const DEFAULT_MANUFACTURER: &str = "external";
+
+/// Where the protos of a dependency are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySource {
+    /// An `http(s)://` URL of one proto file
+    Uri(String),
+    /// A proto file or directory, relative to Actr.toml
+    Path(PathBuf),
+}
+
+impl std::fmt::Display for DependencySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DependencySource::Uri(uri) => f.write_str(uri),
+            DependencySource::Path(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A `[dependencies]` entry with a `uri` or `path`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceDependency {
+    pub alias: String,
+    pub name: String,
+    pub actr_type: Option<String>,
+    pub source: DependencySource,
+}
+
+impl SourceDependency {
+    /// Source dependencies declared in an Actr.toml (with the active profile applied)
+    pub fn from_config_file(path: &Path) -> Result<Vec<Self>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Self::from_value(&profile::load_value(path)?)
+    }
+
+    pub fn from_value(config: &toml::Value) -> Result<Vec<Self>> {
+        let Some(dependencies) = config.get("dependencies").and_then(|d| d.as_table()) else {
+            return Ok(Vec::new());
+        };
+        let mut sources = Vec::new();
+        for (alias, entry) in dependencies {
+            let get = |key: &str| -> Result<Option<String>> {
+                match entry.get(key) {
+                    None => Ok(None),
+                    Some(value) => value
+                        .as_str()
+                        .map(|s| Some(s.to_string()))
+                        .with_context(|| format!("dependencies.{alias}.{key} must be a string")),
+                }
+            };
+            let source = match (get("uri")?, get("path")?) {
+                (None, None) => continue,
+                (Some(_), Some(_)) => {
+                    bail!("Dependency '{alias}' sets both uri and path; use one of them")
+                }
+                (Some(uri), None) => {
+                    if !uri.starts_with("http://") && !uri.starts_with("https://") {
+                        bail!("Dependency '{alias}': uri must be an http(s) URL, got '{uri}'");
+                    }
+                    DependencySource::Uri(uri)
+                }
+                (None, Some(path)) => DependencySource::Path(PathBuf::from(path)),
+            };
+            sources.push(Self {
+                alias: alias.clone(),
+                name: get("name")?.unwrap_or_else(|| alias.clone()),
+                actr_type: get("actr_type")?,
+                source,
+            });
+        }
+        Ok(sources)
+    }
+
+    fn actr_type(&self) -> Result<ActrType> {
+        let repr = self
+            .actr_type
+            .clone()
+            .unwrap_or_else(|| format!("{DEFAULT_MANUFACTURER}+{}", self.name));
+        ActrType::from_string_repr(&repr)
+            .map_err(|_| anyhow!("Invalid actr_type '{repr}' for dependency '{}'", self.alias))
+    }
+}
+
+/// Discovery serving exactly one source dependency
+pub struct SourceDiscovery {
+    dependency: SourceDependency,
+    /// Directory relative paths are resolved against
+    base_dir: PathBuf,
+    client: reqwest::Client,
+}
+
+impl SourceDiscovery {
+    pub fn new(
+        dependency: SourceDependency,
+        base_dir: &Path,
+        network: &NetworkSettings,
+    ) -> Result<Self> {
+        Ok(Self {
+            dependency,
+            base_dir: base_dir.to_path_buf(),
+            client: network.http_client()?,
+        })
+    }
+
+    fn require(&self, name: &str) -> Result<()> {
+        if name == self.dependency.name || name == self.dependency.alias {
+            return Ok(());
+        }
+        Err(ActrCliError::ServiceNotFound {
+            name: name.to_string(),
+        }
+        .into())
+    }
+
+    async fn proto_files(&self) -> Result<Vec<ProtoFile>> {
+        let files = match &self.dependency.source {
+            DependencySource::Uri(uri) => {
+                let content = self
+                    .client
+                    .get(uri)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .with_context(|| format!("Failed to download {uri}"))?
+                    .text()
+                    .await?;
+                let name = uri
+                    .split(['?', '#'])
+                    .next()
+                    .and_then(|path| path.rsplit('/').next())
+                    .filter(|name| name.ends_with(".proto"))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("{}.proto", self.dependency.name));
+                vec![proto_file(name, content)]
+            }
+            DependencySource::Path(path) => read_proto_path(&self.base_dir.join(path))?,
+        };
+        if files.is_empty() {
+            bail!(
+                "No .proto files found for dependency '{}' at {}",
+                self.dependency.alias,
+                self.dependency.source
+            );
+        }
+        Ok(files)
+    }
+
+    async fn service_info(&self, files: &[ProtoFile]) -> Result<ServiceInfo> {
+        let fingerprint = DefaultFingerprintValidator::new()
+            .compute_files_fingerprint(files)
+            .await?
+            .value;
+        Ok(ServiceInfo {
+            name: self.dependency.name.clone(),
+            tags: vec![format!("{SOURCE_TAG_PREFIX}{}", self.dependency.source)],
+            fingerprint,
+            actr_type: self.dependency.actr_type()?,
+            published_at: None,
+            description: None,
+            methods: registry_backend::methods_of(files),
+        })
+    }
+}
+
+#[async_trait]
+impl ServiceDiscovery for SourceDiscovery {
+    async fn discover_services(&self, _filter: Option<&ServiceFilter>) -> Result<Vec<ServiceInfo>> {
+        let files = self.proto_files().await?;
+        Ok(vec![self.service_info(&files).await?])
+    }
+
+    async fn get_service_details(&self, name: &str) -> Result<ServiceDetails> {
+        self.require(name)?;
+        let proto_files = self.proto_files().await?;
+        Ok(ServiceDetails {
+            info: self.service_info(&proto_files).await?,
+            proto_files,
+            dependencies: Vec::new(),
+        })
+    }
+
+    async fn check_service_availability(&self, name: &str) -> Result<AvailabilityStatus> {
+        let available = self.require(name).is_ok() && self.proto_files().await.is_ok();
+        Ok(AvailabilityStatus {
+            is_available: available,
+            last_seen: None,
+            health: HealthStatus::Unknown,
+        })
+    }
+
+    async fn get_service_proto(&self, name: &str) -> Result<Vec<ProtoFile>> {
+        self.require(name)?;
+        self.proto_files().await
+    }
+
+    async fn publish_service(&self, spec: actr_protocol::ServiceSpec) -> Result<()> {
+        bail!(
+            "Cannot publish '{}' through the source dependency '{}'",
+            spec.name,
+            self.dependency.alias
+        )
+    }
+}
+
+/// A proto file, or every proto below a directory
+///
+/// Protos are cached flat per dependency, so file names must be unique.
+fn read_proto_path(path: &Path) -> Result<Vec<ProtoFile>> {
+    if path.is_file() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        return Ok(vec![proto_file(name, content)]);
+    }
+    if !path.is_dir() {
+        bail!("Proto path {} does not exist", path.display());
+    }
+
+    let mut names = BTreeSet::new();
+    let mut files = Vec::new();
+    let mut entries: Vec<_> = WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.file_type().is_file() && entry.path().extension().is_some_and(|e| e == "proto")
+        })
+        .collect();
+    entries.sort_by(|a, b| a.path().cmp(b.path()));
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !names.insert(name.clone()) {
+            bail!(
+                "Two protos named '{name}' below {}; file names must be unique",
+                path.display()
+            );
+        }
+        let content = std::fs::read_to_string(entry.path())
+            .with_context(|| format!("Failed to read {}", entry.path().display()))?;
+        files.push(proto_file(name, content));
+    }
+    Ok(files)
+}
+
+fn proto_file(name: String, content: String) -> ProtoFile {
+    ProtoFile {
+        path: PathBuf::from(&name),
+        name,
+        content,
+        services: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_source_entries_from_config() {
+        let config: toml::Value = toml::from_str(
+            "[dependencies]\nuser = { uri = \"https://example.com/user.proto\" }\nshared = { path = \"../shared\", name = \"shared-types\" }\necho = {}\n",
+        )
+        .unwrap();
+        let sources = SourceDependency::from_value(&config).unwrap();
+        assert_eq!(sources.len(), 2);
+        let find = |alias: &str| sources.iter().find(|s| s.alias == alias).unwrap();
+        assert_eq!(find("shared").name, "shared-types");
+        assert_eq!(
+            find("user").source,
+            DependencySource::Uri("https://example.com/user.proto".to_string())
+        );
+
+        let both: toml::Value =
+            toml::from_str("[dependencies]\nx = { uri = \"https://a/x.proto\", path = \"x\" }\n")
+                .unwrap();
+        assert!(SourceDependency::from_value(&both).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_path_dependency_details() {
+        let dir = TempDir::new().unwrap();
+        let protos = dir.path().join("shared/v1");
+        std::fs::create_dir_all(&protos).unwrap();
+        std::fs::write(
+            protos.join("types.proto"),
+            "syntax = \"proto3\";\npackage shared;\nmessage Id { string value = 1; }\n",
+        )
+        .unwrap();
+
+        let discovery = SourceDiscovery::new(
+            SourceDependency {
+                alias: "shared".to_string(),
+                name: "shared".to_string(),
+                actr_type: None,
+                source: DependencySource::Path(PathBuf::from("shared")),
+            },
+            dir.path(),
+            &NetworkSettings::default(),
+        )
+        .unwrap();
+        let details = discovery.get_service_details("shared").await.unwrap();
+        assert_eq!(details.proto_files[0].name, "types.proto");
+        assert!(details.info.fingerprint.starts_with("service_semantic:"));
+        assert_eq!(details.info.tags, vec!["source:shared"]);
+        assert!(discovery.get_service_details("other").await.is_err());
+    }
+}
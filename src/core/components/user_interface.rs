@@ -2,10 +2,13 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use dialoguer::{Confirm, Select, theme::ColorfulTheme};
 use std::io::{self, Write};
+use std::time::Duration;
 
 use crate::core::{ActrCliError, ProgressBar, ServiceInfo, UserInterface};
 
-pub struct ConsoleUI;
+pub struct ConsoleUI {
+    quiet: bool,
+}
 
 impl Default for ConsoleUI {
     fn default() -> Self {
@@ -15,7 +18,13 @@ impl Default for ConsoleUI {
 
 impl ConsoleUI {
     pub fn new() -> Self {
-        Self
+        Self { quiet: false }
+    }
+
+    /// Suppress progress bars (--quiet)
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
     }
 
     fn read_line(&self) -> Result<String> {
@@ -71,19 +80,57 @@ impl UserInterface for ConsoleUI {
     }
 
     async fn show_progress(&self, message: &str) -> Result<Box<dyn ProgressBar>> {
-        println!("⏳ {message}...");
-        Ok(Box::new(ConsoleProgressBar))
+        Ok(Box::new(ConsoleProgressBar::new(message, self.quiet)))
     }
 }
 
-pub struct ConsoleProgressBar;
+/// indicatif-backed progress bar drawn on stderr
+///
+/// Progress is a fraction in `0.0..=1.0`. The bar is hidden when `quiet` is
+/// set or stderr is not a terminal, and cleared once finished or dropped.
+pub struct ConsoleProgressBar {
+    bar: indicatif::ProgressBar,
+}
+
+impl ConsoleProgressBar {
+    const LENGTH: u64 = 1000;
+
+    pub fn new(message: &str, quiet: bool) -> Self {
+        let bar = if quiet {
+            indicatif::ProgressBar::hidden()
+        } else {
+            indicatif::ProgressBar::new(Self::LENGTH)
+        };
+        if let Ok(style) =
+            indicatif::ProgressStyle::with_template("{spinner} {wide_msg} [{bar:30}] {percent:>3}%")
+        {
+            bar.set_style(style.progress_chars("=> "));
+        }
+        bar.set_message(message.to_string());
+        bar.enable_steady_tick(Duration::from_millis(120));
+        Self { bar }
+    }
+}
 
 impl ProgressBar for ConsoleProgressBar {
-    fn update(&self, _progress: f64) {}
+    fn update(&self, progress: f64) {
+        let position = (progress.clamp(0.0, 1.0) * Self::LENGTH as f64).round() as u64;
+        self.bar.set_position(position);
+    }
 
     fn set_message(&self, message: &str) {
-        println!("⏳ {message}...");
+        self.bar.set_message(message.to_string());
     }
 
-    fn finish(&self) {}
+    fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+impl Drop for ConsoleProgressBar {
+    fn drop(&mut self) {
+        if !self.bar.is_finished() {
+            self.bar.finish_and_clear();
+        }
+    }
 }
@@ -1,13 +1,23 @@
 //! 依赖注入容器
 //!
 //! 管理所有组件的生命周期和依赖关系
+//!
+//! Components and pipelines are created on first use and kept in once-cells, so
+//! every getter takes `&self` and commands share the container through an `Arc`
+//! without locking it around async calls.
 
 use anyhow::Result;
-use std::collections::HashMap;
-use std::sync::Arc;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
+use super::budget::ExecutionBudget;
 use super::components::*;
+use super::network::NetworkSettings;
 use super::pipelines::*;
+use super::profile;
+use super::retry::RetryPolicy;
 
 /// 组件类型枚举
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -22,6 +32,46 @@ pub enum ComponentType {
     UserInterface,
 }
 
+impl ComponentType {
+    pub const ALL: [ComponentType; 8] = [
+        ComponentType::ConfigManager,
+        ComponentType::DependencyResolver,
+        ComponentType::ServiceDiscovery,
+        ComponentType::NetworkValidator,
+        ComponentType::FingerprintValidator,
+        ComponentType::ProtoProcessor,
+        ComponentType::CacheManager,
+        ComponentType::UserInterface,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentType::ConfigManager => "ConfigManager",
+            ComponentType::DependencyResolver => "DependencyResolver",
+            ComponentType::ServiceDiscovery => "ServiceDiscovery",
+            ComponentType::NetworkValidator => "NetworkValidator",
+            ComponentType::FingerprintValidator => "FingerprintValidator",
+            ComponentType::ProtoProcessor => "ProtoProcessor",
+            ComponentType::CacheManager => "CacheManager",
+            ComponentType::UserInterface => "UserInterface",
+        }
+    }
+}
+
+/// How the container provides a component (`actr components`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ComponentStatus {
+    pub component: &'static str,
+    pub available: bool,
+    /// `registered` (explicitly or through `--component`), `default` or `missing`
+    pub source: &'static str,
+    /// Backing implementation; unknown for explicitly registered components
+    pub implementation: Option<String>,
+    /// Key settings such as the config file, cache directory or registry URL, or
+    /// why the component is missing
+    pub details: BTreeMap<String, String>,
+}
+
 /// 服务容器
 pub struct ServiceContainer {
     config_manager: Option<Arc<dyn ConfigManager>>,
@@ -32,11 +82,17 @@ pub struct ServiceContainer {
     proto_processor: Option<Arc<dyn ProtoProcessor>>,
     cache_manager: Option<Arc<dyn CacheManager>>,
     user_interface: Option<Arc<dyn UserInterface>>,
+    budget: ExecutionBudget,
+    /// Implementations known for registered components (fixture overrides)
+    implementations: HashMap<ComponentType, String>,
+
+    // 未显式注册时按需创建的默认实现
+    defaults: Option<Arc<DefaultComponents>>,
 
     // 缓存的管道实例
-    validation_pipeline: Option<Arc<ValidationPipeline>>,
-    install_pipeline: Option<Arc<InstallPipeline>>,
-    generation_pipeline: Option<Arc<GenerationPipeline>>,
+    validation_pipeline: OnceLock<Arc<ValidationPipeline>>,
+    install_pipeline: OnceLock<Arc<InstallPipeline>>,
+    generation_pipeline: OnceLock<Arc<GenerationPipeline>>,
 }
 
 impl ServiceContainer {
@@ -51,9 +107,12 @@ impl ServiceContainer {
             proto_processor: None,
             cache_manager: None,
             user_interface: None,
-            validation_pipeline: None,
-            install_pipeline: None,
-            generation_pipeline: None,
+            budget: ExecutionBudget::unlimited(),
+            implementations: HashMap::new(),
+            defaults: None,
+            validation_pipeline: OnceLock::new(),
+            install_pipeline: OnceLock::new(),
+            generation_pipeline: OnceLock::new(),
         }
     }
 
@@ -101,161 +160,196 @@ impl ServiceContainer {
         self
     }
 
+    /// Timeout and cancellation shared by the pipelines
+    pub fn with_budget(mut self, budget: ExecutionBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
     /// 获取组件
     pub fn get_config_manager(&self) -> Result<Arc<dyn ConfigManager>> {
-        self.config_manager
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("ConfigManager not registered"))
+        match (&self.config_manager, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.config_manager(),
+            (None, None) => Err(anyhow::anyhow!("ConfigManager not registered")),
+        }
     }
 
     pub fn get_dependency_resolver(&self) -> Result<Arc<dyn DependencyResolver>> {
-        self.dependency_resolver
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("DependencyResolver not registered"))
+        match (&self.dependency_resolver, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.dependency_resolver(),
+            (None, None) => Err(anyhow::anyhow!("DependencyResolver not registered")),
+        }
     }
 
     pub fn get_service_discovery(&self) -> Result<Arc<dyn ServiceDiscovery>> {
-        self.service_discovery
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("ServiceDiscovery not registered"))
+        match (&self.service_discovery, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.service_discovery(),
+            (None, None) => Err(anyhow::anyhow!("ServiceDiscovery not registered")),
+        }
     }
 
     pub fn get_network_validator(&self) -> Result<Arc<dyn NetworkValidator>> {
-        self.network_validator
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("NetworkValidator not registered"))
+        match (&self.network_validator, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.network_validator(),
+            (None, None) => Err(anyhow::anyhow!("NetworkValidator not registered")),
+        }
     }
 
     pub fn get_fingerprint_validator(&self) -> Result<Arc<dyn FingerprintValidator>> {
-        self.fingerprint_validator
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("FingerprintValidator not registered"))
+        match (&self.fingerprint_validator, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.fingerprint_validator(),
+            (None, None) => Err(anyhow::anyhow!("FingerprintValidator not registered")),
+        }
     }
 
     pub fn get_proto_processor(&self) -> Result<Arc<dyn ProtoProcessor>> {
-        self.proto_processor
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("ProtoProcessor not registered"))
+        match (&self.proto_processor, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.proto_processor(),
+            (None, None) => Err(anyhow::anyhow!("ProtoProcessor not registered")),
+        }
     }
 
     pub fn get_cache_manager(&self) -> Result<Arc<dyn CacheManager>> {
-        self.cache_manager
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("CacheManager not registered"))
+        match (&self.cache_manager, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.cache_manager(),
+            (None, None) => Err(anyhow::anyhow!("CacheManager not registered")),
+        }
     }
 
     pub fn get_user_interface(&self) -> Result<Arc<dyn UserInterface>> {
-        self.user_interface
-            .clone()
-            .ok_or_else(|| anyhow::anyhow!("UserInterface not registered"))
+        match (&self.user_interface, &self.defaults) {
+            (Some(component), _) => Ok(component.clone()),
+            (None, Some(defaults)) => defaults.user_interface(),
+            (None, None) => Err(anyhow::anyhow!("UserInterface not registered")),
+        }
+    }
+
+    pub fn get_budget(&self) -> ExecutionBudget {
+        self.budget.clone()
     }
 
     /// 获取验证管道 (延迟创建)
-    pub fn get_validation_pipeline(&mut self) -> Result<Arc<ValidationPipeline>> {
-        if self.validation_pipeline.is_none() {
+    pub fn get_validation_pipeline(&self) -> Result<Arc<ValidationPipeline>> {
+        lazy(&self.validation_pipeline, || {
             let pipeline = ValidationPipeline::new(
                 self.get_config_manager()?,
                 self.get_dependency_resolver()?,
                 self.get_service_discovery()?,
                 self.get_network_validator()?,
                 self.get_fingerprint_validator()?,
-            );
-            self.validation_pipeline = Some(Arc::new(pipeline));
-        }
-
-        Ok(self.validation_pipeline.clone().unwrap())
+            )
+            .with_budget(self.budget.clone());
+            Ok(Arc::new(pipeline))
+        })
     }
 
     /// 获取安装管道 (延迟创建)
-    pub fn get_install_pipeline(&mut self) -> Result<Arc<InstallPipeline>> {
-        if self.install_pipeline.is_none() {
+    pub fn get_install_pipeline(&self) -> Result<Arc<InstallPipeline>> {
+        lazy(&self.install_pipeline, || {
             let validation_pipeline = (*self.get_validation_pipeline()?).clone();
-            let pipeline = InstallPipeline::new(
+            let mut pipeline = InstallPipeline::new(
                 validation_pipeline,
                 self.get_config_manager()?,
                 self.get_cache_manager()?,
                 self.get_proto_processor()?,
             );
-            self.install_pipeline = Some(Arc::new(pipeline));
-        }
-
-        Ok(self.install_pipeline.clone().unwrap())
+            if let Ok(ui) = self.get_user_interface() {
+                pipeline = pipeline.with_user_interface(ui);
+            }
+            Ok(Arc::new(pipeline))
+        })
     }
 
     /// 获取生成管道 (延迟创建)
-    pub fn get_generation_pipeline(&mut self) -> Result<Arc<GenerationPipeline>> {
-        if self.generation_pipeline.is_none() {
-            let pipeline = GenerationPipeline::new(
+    pub fn get_generation_pipeline(&self) -> Result<Arc<GenerationPipeline>> {
+        lazy(&self.generation_pipeline, || {
+            let mut pipeline = GenerationPipeline::new(
                 self.get_config_manager()?,
                 self.get_proto_processor()?,
                 self.get_cache_manager()?,
-            );
-            self.generation_pipeline = Some(Arc::new(pipeline));
+            )
+            .with_budget(self.budget.clone());
+            if let Ok(ui) = self.get_user_interface() {
+                pipeline = pipeline.with_user_interface(ui);
+            }
+            Ok(Arc::new(pipeline))
+        })
+    }
+
+    /// Whether a component is registered or can be created from the defaults
+    fn provides(&self, component_type: &ComponentType) -> bool {
+        self.registered(component_type)
+            || self
+                .defaults
+                .as_ref()
+                .is_some_and(|defaults| defaults.provides(component_type))
+    }
+
+    fn registered(&self, component_type: &ComponentType) -> bool {
+        match component_type {
+            ComponentType::ConfigManager => self.config_manager.is_some(),
+            ComponentType::DependencyResolver => self.dependency_resolver.is_some(),
+            ComponentType::ServiceDiscovery => self.service_discovery.is_some(),
+            ComponentType::NetworkValidator => self.network_validator.is_some(),
+            ComponentType::FingerprintValidator => self.fingerprint_validator.is_some(),
+            ComponentType::ProtoProcessor => self.proto_processor.is_some(),
+            ComponentType::CacheManager => self.cache_manager.is_some(),
+            ComponentType::UserInterface => self.user_interface.is_some(),
         }
+    }
 
-        Ok(self.generation_pipeline.clone().unwrap())
+    /// Every component type with where it comes from and its key settings
+    pub fn component_status(&self) -> Vec<ComponentStatus> {
+        ComponentType::ALL
+            .iter()
+            .map(|component_type| {
+                let (source, implementation, details) = if self.registered(component_type) {
+                    (
+                        "registered",
+                        self.implementations.get(component_type).cloned(),
+                        BTreeMap::new(),
+                    )
+                } else if let Some(defaults) = &self.defaults {
+                    let (implementation, details) = defaults.describe(component_type);
+                    let source = if implementation.is_some() {
+                        "default"
+                    } else {
+                        "missing"
+                    };
+                    (source, implementation, details)
+                } else {
+                    let details = BTreeMap::from([(
+                        "reason".to_string(),
+                        "not registered and no defaults configured".to_string(),
+                    )]);
+                    ("missing", None, details)
+                };
+                ComponentStatus {
+                    component: component_type.as_str(),
+                    available: source != "missing",
+                    source,
+                    implementation,
+                    details,
+                }
+            })
+            .collect()
     }
 
     /// 验证所有必需的组件是否已注册
     pub fn validate(&self, required_components: &[ComponentType]) -> Result<()> {
         for component_type in required_components {
-            match component_type {
-                ComponentType::ConfigManager => {
-                    if self.config_manager.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "ConfigManager is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::DependencyResolver => {
-                    if self.dependency_resolver.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "DependencyResolver is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::ServiceDiscovery => {
-                    if self.service_discovery.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "ServiceDiscovery is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::NetworkValidator => {
-                    if self.network_validator.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "NetworkValidator is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::FingerprintValidator => {
-                    if self.fingerprint_validator.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "FingerprintValidator is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::ProtoProcessor => {
-                    if self.proto_processor.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "ProtoProcessor is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::CacheManager => {
-                    if self.cache_manager.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "CacheManager is required but not registered"
-                        ));
-                    }
-                }
-                ComponentType::UserInterface => {
-                    if self.user_interface.is_none() {
-                        return Err(anyhow::anyhow!(
-                            "UserInterface is required but not registered"
-                        ));
-                    }
-                }
+            if !self.provides(component_type) {
+                return Err(anyhow::anyhow!(
+                    "{} is required but not registered (run 'actr components' for details)",
+                    component_type.as_str()
+                ));
             }
         }
         Ok(())
@@ -268,10 +362,23 @@ impl Default for ServiceContainer {
     }
 }
 
+/// Default Actr.toml when no config path is given
+pub const DEFAULT_CONFIG_FILE: &str = "Actr.toml";
+
 /// 容器构建器
+///
+/// The built container creates the default implementation of each component on
+/// first use, seeded from one Actr.toml. Components registered explicitly, through
+/// the builder methods or `component_override`, take precedence, also over the
+/// offline implementations.
 pub struct ContainerBuilder {
     container: ServiceContainer,
-    config_path: Option<std::path::PathBuf>,
+    config_path: Option<PathBuf>,
+    offline: bool,
+    quiet: bool,
+    global_cache: Option<GlobalProtoCache>,
+    retry_overrides: (Option<u32>, Option<u64>, Option<u64>),
+    component_overrides: Vec<ComponentOverride>,
 }
 
 impl ContainerBuilder {
@@ -280,21 +387,397 @@ impl ContainerBuilder {
         Self {
             container: ServiceContainer::new(),
             config_path: None,
+            offline: false,
+            quiet: false,
+            global_cache: None,
+            retry_overrides: (None, None, None),
+            component_overrides: Vec::new(),
         }
     }
 
-    /// 设置配置文件路径
-    pub fn config_path<P: Into<std::path::PathBuf>>(mut self, path: P) -> Self {
+    /// 设置配置文件路径 (the file must exist; defaults to ./Actr.toml if present)
+    pub fn config_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
         self.config_path = Some(path.into());
         self
     }
 
+    /// Use the lock-file/cache-only implementations of the network components
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Suppress progress bars in the console UI
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
+    /// Share cached protos through a user-level store
+    pub fn global_cache(mut self, global_cache: Option<GlobalProtoCache>) -> Self {
+        self.global_cache = global_cache;
+        self
+    }
+
+    /// Command-line overrides for the `[system.discovery]` retry policy
+    pub fn retry_overrides(
+        mut self,
+        attempts: Option<u32>,
+        base_delay_ms: Option<u64>,
+        max_delay_ms: Option<u64>,
+    ) -> Self {
+        self.retry_overrides = (attempts, base_delay_ms, max_delay_ms);
+        self
+    }
+
+    /// Use `discovery` instead of the signaling or offline implementation
+    pub fn service_discovery(mut self, discovery: Arc<dyn ServiceDiscovery>) -> Self {
+        self.container = self.container.register_service_discovery(discovery);
+        self
+    }
+
+    /// Use `validator` instead of the default or offline network validator
+    pub fn network_validator(mut self, validator: Arc<dyn NetworkValidator>) -> Self {
+        self.container = self.container.register_network_validator(validator);
+        self
+    }
+
+    /// Use `validator` instead of the default fingerprint validator
+    pub fn fingerprint_validator(mut self, validator: Arc<dyn FingerprintValidator>) -> Self {
+        self.container = self.container.register_fingerprint_validator(validator);
+        self
+    }
+
+    /// Replace a component with its fixture-backed mock (`--component`); the
+    /// fixture is read by `build`
+    pub fn component_override(mut self, component_override: ComponentOverride) -> Self {
+        self.component_overrides.push(component_override);
+        self
+    }
+
     /// 构建容器
     pub fn build(self) -> Result<ServiceContainer> {
-        // TODO: 这里应该根据配置创建默认的组件实现
-        // 现在先返回空容器，实际实现时需要创建具体的组件实例
+        let config_path = match self.config_path {
+            Some(path) if !path.is_file() => {
+                anyhow::bail!("Config file not found: {}", path.display())
+            }
+            Some(path) => path,
+            None => PathBuf::from(DEFAULT_CONFIG_FILE),
+        };
+
+        let (attempts, base_delay_ms, max_delay_ms) = self.retry_overrides;
+        let retry = RetryPolicy::from_config_file(&config_path)?.with_overrides(
+            attempts,
+            base_delay_ms,
+            max_delay_ms,
+        );
+        let network = NetworkSettings::from_config_file(&config_path)?;
+
+        let mut container = self.container;
+        let mut fixtures: HashMap<PathBuf, Fixture> = HashMap::new();
+        for component_override in self.component_overrides {
+            let path = component_override.fixture;
+            if !fixtures.contains_key(&path) {
+                fixtures.insert(path.clone(), Fixture::from_file(&path)?);
+            }
+            let fixture = &fixtures[&path];
+            let component = component_override.component.clone();
+            container = match component_override.component {
+                ComponentType::ServiceDiscovery => container
+                    .register_service_discovery(Arc::new(FixtureServiceDiscovery::new(fixture))),
+                ComponentType::NetworkValidator => container
+                    .register_network_validator(Arc::new(FixtureNetworkValidator::new(fixture))),
+                ComponentType::FingerprintValidator => container.register_fingerprint_validator(
+                    Arc::new(FixtureFingerprintValidator::new(fixture)),
+                ),
+                other => anyhow::bail!("{other:?} has no fixture-backed implementation"),
+            };
+            container
+                .implementations
+                .insert(component, format!("fixture {}", path.display()));
+        }
+        container.defaults = Some(Arc::new(DefaultComponents {
+            config_path,
+            offline: self.offline,
+            quiet: self.quiet,
+            global_cache: self.global_cache,
+            retry,
+            network,
+            config_manager: OnceLock::new(),
+            dependency_resolver: OnceLock::new(),
+            service_discovery: OnceLock::new(),
+            network_validator: OnceLock::new(),
+            fingerprint_validator: OnceLock::new(),
+            proto_processor: OnceLock::new(),
+            cache_manager: OnceLock::new(),
+            user_interface: OnceLock::new(),
+        }));
+        Ok(container)
+    }
+}
+
+/// Default component implementations, each constructed on first use
+///
+/// A command that never touches service discovery therefore does not fail when
+/// the signaling section of Actr.toml cannot be parsed.
+struct DefaultComponents {
+    config_path: PathBuf,
+    offline: bool,
+    quiet: bool,
+    global_cache: Option<GlobalProtoCache>,
+    retry: RetryPolicy,
+    network: NetworkSettings,
+
+    config_manager: OnceLock<Arc<dyn ConfigManager>>,
+    dependency_resolver: OnceLock<Arc<dyn DependencyResolver>>,
+    service_discovery: OnceLock<Arc<dyn ServiceDiscovery>>,
+    network_validator: OnceLock<Arc<dyn NetworkValidator>>,
+    fingerprint_validator: OnceLock<Arc<dyn FingerprintValidator>>,
+    proto_processor: OnceLock<Arc<dyn ProtoProcessor>>,
+    cache_manager: OnceLock<Arc<dyn CacheManager>>,
+    user_interface: OnceLock<Arc<dyn UserInterface>>,
+}
 
-        Ok(self.container)
+/// Get a component from its cell, constructing it if needed
+///
+/// Concurrent first calls may both construct it; the first stored value wins.
+fn lazy<T: ?Sized>(
+    cell: &OnceLock<Arc<T>>,
+    init: impl FnOnce() -> Result<Arc<T>>,
+) -> Result<Arc<T>> {
+    if let Some(component) = cell.get() {
+        return Ok(component.clone());
+    }
+    let component = init()?;
+    Ok(cell.get_or_init(|| component).clone())
+}
+
+impl DefaultComponents {
+    fn has_config(&self) -> bool {
+        self.config_path.is_file()
+    }
+
+    /// Components that read Actr.toml are only available inside a project
+    fn provides(&self, component_type: &ComponentType) -> bool {
+        match component_type {
+            ComponentType::ConfigManager | ComponentType::ServiceDiscovery => self.has_config(),
+            _ => true,
+        }
+    }
+
+    /// Directory of the seeding Actr.toml, or the working directory outside a project
+    fn project_root(&self) -> PathBuf {
+        std::fs::canonicalize(&self.config_path)
+            .ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    fn require_config(&self, component: &str) -> Result<()> {
+        if !self.has_config() {
+            anyhow::bail!(
+                "{component} not registered: {} not found",
+                self.config_path.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Implementation a component would get and its key settings, or `None` with
+    /// the reason when it cannot be created
+    fn describe(
+        &self,
+        component_type: &ComponentType,
+    ) -> (Option<String>, BTreeMap<String, String>) {
+        let mut details = BTreeMap::new();
+        if !self.provides(component_type) {
+            details.insert(
+                "reason".to_string(),
+                format!("{} not found", self.config_path.display()),
+            );
+            return (None, details);
+        }
+        let implementation = match component_type {
+            ComponentType::ConfigManager => {
+                details.insert("config".to_string(), self.config_path.display().to_string());
+                "TomlConfigManager"
+            }
+            ComponentType::DependencyResolver => "DefaultDependencyResolver",
+            ComponentType::ServiceDiscovery if self.offline => {
+                details.insert(
+                    "mode".to_string(),
+                    "offline (lock file and cache)".to_string(),
+                );
+                "OfflineServiceDiscovery"
+            }
+            ComponentType::ServiceDiscovery => return self.describe_discovery(),
+            ComponentType::NetworkValidator if self.offline => "OfflineNetworkValidator",
+            ComponentType::NetworkValidator => {
+                details.insert(
+                    "retry".to_string(),
+                    format!("{} attempts", self.retry.attempts),
+                );
+                "DefaultNetworkValidator"
+            }
+            ComponentType::FingerprintValidator => "DefaultFingerprintValidator",
+            ComponentType::ProtoProcessor => "DefaultProtoProcessor",
+            ComponentType::CacheManager => {
+                details.insert(
+                    "cache_dir".to_string(),
+                    self.project_root()
+                        .join("protos")
+                        .join("remote")
+                        .display()
+                        .to_string(),
+                );
+                if let Some(global) = &self.global_cache {
+                    details.insert(
+                        "global_cache".to_string(),
+                        global.root().display().to_string(),
+                    );
+                }
+                "DefaultCacheManager"
+            }
+            ComponentType::UserInterface => {
+                details.insert("quiet".to_string(), self.quiet.to_string());
+                "ConsoleUI"
+            }
+        };
+        (Some(implementation.to_string()), details)
+    }
+
+    /// Registry backend of the project and the dependencies routed elsewhere
+    fn describe_discovery(&self) -> (Option<String>, BTreeMap<String, String>) {
+        let mut details = BTreeMap::new();
+        let backends = match DiscoveryBackends::from_config_file(&self.config_path) {
+            Ok(backends) => backends,
+            Err(e) => {
+                details.insert("error".to_string(), format!("{e:#}"));
+                return (Some("NetworkServiceDiscovery".to_string()), details);
+            }
+        };
+        let implementation = match &backends.default {
+            RegistryBackend::Signaling => {
+                let url = profile::load_value(&self.config_path)
+                    .ok()
+                    .and_then(|value| {
+                        value
+                            .get("system")?
+                            .get("signaling")?
+                            .get("url")?
+                            .as_str()
+                            .map(str::to_string)
+                    });
+                if let Some(url) = url {
+                    details.insert("signaling_url".to_string(), url);
+                }
+                "NetworkServiceDiscovery"
+            }
+            RegistryBackend::Static { path } => {
+                details.insert("registry".to_string(), path.display().to_string());
+                "StaticRegistryDiscovery"
+            }
+            RegistryBackend::Http { url, .. } => {
+                details.insert("registry_url".to_string(), url.clone());
+                "HttpRegistryDiscovery"
+            }
+        };
+
+        let mut routed: Vec<String> = backends.dependencies.keys().cloned().collect();
+        routed.extend(
+            SourceDependency::from_config_file(&self.config_path)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|source| source.alias),
+        );
+        if routed.is_empty() {
+            return (Some(implementation.to_string()), details);
+        }
+        details.insert("routed".to_string(), routed.join(", "));
+        (
+            Some(format!(
+                "RoutedServiceDiscovery (default: {implementation})"
+            )),
+            details,
+        )
+    }
+
+    fn config_manager(&self) -> Result<Arc<dyn ConfigManager>> {
+        lazy(&self.config_manager, || {
+            self.require_config("ConfigManager")?;
+            Ok(Arc::new(TomlConfigManager::new(&self.config_path)))
+        })
+    }
+
+    fn dependency_resolver(&self) -> Result<Arc<dyn DependencyResolver>> {
+        lazy(&self.dependency_resolver, || {
+            Ok(Arc::new(DefaultDependencyResolver::new()))
+        })
+    }
+
+    /// Discovery through the project's registry backends, or lock file and cache
+    /// lookups in offline mode
+    fn service_discovery(&self) -> Result<Arc<dyn ServiceDiscovery>> {
+        lazy(&self.service_discovery, || {
+            self.require_config("ServiceDiscovery")?;
+            if self.offline {
+                let mut discovery =
+                    OfflineServiceDiscovery::new(self.project_root(), self.cache_manager()?);
+                if let Some(global) = &self.global_cache {
+                    discovery = discovery.with_global_cache(global.clone());
+                }
+                return Ok(Arc::new(discovery));
+            }
+            let config = profile::parse_config(&self.config_path)?;
+            registry_backend::project_discovery(
+                &self.config_path,
+                config,
+                self.retry,
+                self.network.clone(),
+            )
+        })
+    }
+
+    fn network_validator(&self) -> Result<Arc<dyn NetworkValidator>> {
+        lazy(&self.network_validator, || {
+            if self.offline {
+                return Ok(Arc::new(OfflineNetworkValidator::new()));
+            }
+            Ok(Arc::new(
+                DefaultNetworkValidator::new()
+                    .with_retry_policy(self.retry)
+                    .with_network_settings(self.network.clone()),
+            ))
+        })
+    }
+
+    fn fingerprint_validator(&self) -> Result<Arc<dyn FingerprintValidator>> {
+        lazy(&self.fingerprint_validator, || {
+            Ok(Arc::new(DefaultFingerprintValidator::new()))
+        })
+    }
+
+    fn proto_processor(&self) -> Result<Arc<dyn ProtoProcessor>> {
+        lazy(&self.proto_processor, || {
+            Ok(Arc::new(DefaultProtoProcessor::new()))
+        })
+    }
+
+    fn cache_manager(&self) -> Result<Arc<dyn CacheManager>> {
+        lazy(&self.cache_manager, || {
+            let manager = DefaultCacheManager::with_project_root(self.project_root());
+            Ok(Arc::new(match &self.global_cache {
+                Some(global) => manager.with_global_cache(global.clone()),
+                None => manager,
+            }))
+        })
+    }
+
+    fn user_interface(&self) -> Result<Arc<dyn UserInterface>> {
+        lazy(&self.user_interface, || {
+            Ok(Arc::new(ConsoleUI::new().with_quiet(self.quiet)))
+        })
     }
 }
 
@@ -306,11 +789,37 @@ impl Default for ContainerBuilder {
 
 /// 命令执行上下文
 pub struct CommandContext {
-    pub container: Arc<std::sync::Mutex<ServiceContainer>>,
+    pub container: Arc<ServiceContainer>,
     pub args: CommandArgs,
     pub working_dir: std::path::PathBuf,
 }
 
+impl CommandContext {
+    /// Whether dependencies must be resolved from the lock file and proto cache only
+    pub fn is_offline(&self) -> bool {
+        self.args
+            .flags
+            .get("offline")
+            .is_some_and(|value| value == "true")
+    }
+
+    /// Whether progress bars should be suppressed (--quiet)
+    pub fn is_quiet(&self) -> bool {
+        self.args
+            .flags
+            .get("quiet")
+            .is_some_and(|value| value == "true")
+    }
+
+    /// Whether results should be emitted as JSON (--json)
+    pub fn is_json(&self) -> bool {
+        self.args
+            .flags
+            .get("json")
+            .is_some_and(|value| value == "true")
+    }
+}
+
 /// 命令参数
 #[derive(Debug, Clone)]
 pub struct CommandArgs {
@@ -345,3 +854,144 @@ pub trait Command: Send + Sync {
     /// 命令描述
     fn description(&self) -> &str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_outside_project() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let container = ContainerBuilder::new()
+            .config_path(dir.path().join("missing.toml"))
+            .build();
+        assert!(container.is_err());
+
+        let container = ContainerBuilder::new().offline(true).build().unwrap();
+        assert!(container.get_user_interface().is_ok());
+        assert!(container.get_fingerprint_validator().is_ok());
+        assert!(
+            container
+                .validate(&[ComponentType::ProtoProcessor, ComponentType::CacheManager])
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_component_override_replaces_defaults() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let fixture = dir.path().join("fixture.json");
+        std::fs::write(
+            &fixture,
+            r#"{ "services": [{ "name": "echo", "actr_type": "acme+Echo" }] }"#,
+        )
+        .unwrap();
+
+        // Registered components need no Actr.toml and win over offline mode
+        let container = ContainerBuilder::new()
+            .offline(true)
+            .component_override(
+                format!("service-discovery=mock:{}", fixture.display())
+                    .parse()
+                    .unwrap(),
+            )
+            .network_validator(Arc::new(FixtureNetworkValidator::new(&Fixture::default())))
+            .build()
+            .unwrap();
+        assert!(
+            container
+                .validate(&[ComponentType::ServiceDiscovery])
+                .is_ok()
+        );
+        let statuses = container.component_status();
+        let status_of = |name: &str| statuses.iter().find(|s| s.component == name).unwrap();
+        assert_eq!(status_of("ServiceDiscovery").source, "registered");
+        assert_eq!(
+            status_of("ServiceDiscovery").implementation,
+            Some(format!("fixture {}", fixture.display()))
+        );
+        assert_eq!(status_of("NetworkValidator").implementation, None);
+        assert!(!status_of("ConfigManager").available);
+        assert_eq!(
+            status_of("CacheManager").implementation.as_deref(),
+            Some("DefaultCacheManager")
+        );
+        let services = container
+            .get_service_discovery()
+            .unwrap()
+            .discover_services(None)
+            .await
+            .unwrap();
+        assert_eq!(services[0].name, "echo");
+        let status = container
+            .get_network_validator()
+            .unwrap()
+            .check_connectivity("echo", &NetworkCheckOptions::default())
+            .await
+            .unwrap();
+        assert!(!status.is_reachable);
+
+        let missing = ContainerBuilder::new()
+            .component_override("network-validator=mock:missing.json".parse().unwrap())
+            .build();
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_components_constructed_lazily() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join("Actr.toml");
+        // Not a valid actr config: only components that parse it may fail
+        std::fs::write(&config_path, "[package]\nname = \"broken\"\n").unwrap();
+
+        let container = ContainerBuilder::new()
+            .config_path(&config_path)
+            .build()
+            .unwrap();
+        assert!(
+            container
+                .validate(&[
+                    ComponentType::ConfigManager,
+                    ComponentType::ServiceDiscovery
+                ])
+                .is_ok()
+        );
+        let manager = container.get_config_manager().unwrap();
+        assert_eq!(
+            manager.get_project_root(),
+            std::fs::canonicalize(dir.path()).unwrap()
+        );
+        assert!(container.get_service_discovery().is_err());
+
+        // Explicit registrations take precedence over the defaults
+        let resolver: Arc<dyn DependencyResolver> = Arc::new(DefaultDependencyResolver::new());
+        let container = container.register_dependency_resolver(resolver.clone());
+        assert!(Arc::ptr_eq(
+            &container.get_dependency_resolver().unwrap(),
+            &resolver
+        ));
+    }
+
+    #[test]
+    fn test_shared_container_builds_pipelines_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config_path = dir.path().join("Actr.toml");
+        std::fs::write(&config_path, "[package]\nname = \"broken\"\n").unwrap();
+        let container = Arc::new(
+            ContainerBuilder::new()
+                .config_path(&config_path)
+                .offline(true)
+                .build()
+                .unwrap(),
+        );
+
+        let pipelines: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| container.get_generation_pipeline().unwrap()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        let first = container.get_generation_pipeline().unwrap();
+        assert!(pipelines.iter().all(|p| Arc::ptr_eq(p, &first)));
+    }
+}
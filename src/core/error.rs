@@ -1,7 +1,24 @@
 //! Unified Error Handling
 //!
 //! Defines unified error types and handling strategies for the CLI tool
+//!
+//! Every error has a stable code (`ACTR-Ennnn`) that is printed with it and emitted
+//! in `--json` error documents. The leading digit is the area:
+//!
+//! | Codes   | Area                                          |
+//! |---------|-----------------------------------------------|
+//! | `E1xxx` | Actr.toml, project layout and arguments       |
+//! | `E2xxx` | dependencies, fingerprints and signatures     |
+//! | `E3xxx` | network, signaling and service discovery      |
+//! | `E4xxx` | code generation and its toolchain             |
+//! | `E5xxx` | command execution, locking and cancellation   |
+//! | `E9xxx` | I/O and internal errors                       |
+//!
+//! Codes are never reused. The errors of [`crate::error::ActrCliError`], returned by
+//! `actr gen`, share the same table.
 
+use super::i18n;
+use crate::tr;
 use thiserror::Error;
 
 /// CLI Unified Error Type
@@ -37,6 +54,9 @@ pub enum ActrCliError {
     #[error("Fingerprint mismatch: expected {expected}, got {actual}")]
     FingerprintMismatch { expected: String, actual: String },
 
+    #[error("Signature verification failed for {service}: {reason}")]
+    SignatureVerification { service: String, reason: String },
+
     #[error("Compatibility conflict: {message}")]
     CompatibilityConflict { message: String },
 
@@ -64,6 +84,19 @@ pub enum ActrCliError {
     #[error("Operation cancelled")]
     OperationCancelled,
 
+    #[error("Interrupted")]
+    Interrupted,
+
+    #[error("Timed out after {seconds}s")]
+    TimedOut { seconds: u64 },
+
+    #[error("Project is locked by {holder} ({path})")]
+    ProjectLocked { holder: String, path: String },
+
+    /// A script of `actr run` finished with a non-zero exit code, which `actr` exits with
+    #[error("Script '{script}' exited with code {code}")]
+    ScriptFailed { script: String, code: i32 },
+
     #[error("IO error")]
     Io(#[from] std::io::Error),
 
@@ -131,35 +164,58 @@ pub enum ValidationError {
     InsufficientPermissions { resource: String },
 }
 
+/// Code of errors that carry none, e.g. plain `anyhow` errors
+pub const UNKNOWN_ERROR_CODE: &str = "ACTR-E9999";
+
 /// User-friendly Error Display
 impl ActrCliError {
+    /// Stable error code for scripts and wrapping tools
+    pub fn code(&self) -> &'static str {
+        match self {
+            ActrCliError::Serialization(_) => "ACTR-E1001",
+            ActrCliError::Config { .. } => "ACTR-E1002",
+            ActrCliError::InvalidProject { .. } => "ACTR-E1003",
+            ActrCliError::InvalidArgument { .. } => "ACTR-E1004",
+            ActrCliError::Dependency { .. } => "ACTR-E2001",
+            ActrCliError::DependencyConflict { .. } => "ACTR-E2002",
+            ActrCliError::FingerprintMismatch { .. } => "ACTR-E2003",
+            ActrCliError::FingerprintValidation { .. } => "ACTR-E2004",
+            ActrCliError::SignatureVerification { .. } => "ACTR-E2005",
+            ActrCliError::CompatibilityConflict { .. } => "ACTR-E2006",
+            ActrCliError::InstallFailed { .. } => "ACTR-E2007",
+            ActrCliError::ValidationFailed { .. } => "ACTR-E2008",
+            ActrCliError::Network { .. } => "ACTR-E3001",
+            ActrCliError::Http(_) => "ACTR-E3002",
+            ActrCliError::ServiceNotFound { .. } => "ACTR-E3003",
+            ActrCliError::ServiceDiscovery { .. } => "ACTR-E3004",
+            ActrCliError::TimedOut { .. } => "ACTR-E3005",
+            ActrCliError::CodeGeneration { .. } => "ACTR-E4001",
+            ActrCliError::Command { .. } => "ACTR-E5001",
+            ActrCliError::Cache { .. } => "ACTR-E5002",
+            ActrCliError::UserInterface { .. } => "ACTR-E5003",
+            ActrCliError::ComponentNotRegistered { .. } => "ACTR-E5004",
+            ActrCliError::ProjectLocked { .. } => "ACTR-E5005",
+            ActrCliError::OperationCancelled => "ACTR-E5006",
+            ActrCliError::Interrupted => "ACTR-E5007",
+            ActrCliError::ScriptFailed { .. } => "ACTR-E5008",
+            ActrCliError::Io(_) => "ACTR-E9001",
+            ActrCliError::Other(_) => UNKNOWN_ERROR_CODE,
+        }
+    }
+
     /// Get user-friendly error message
     pub fn user_message(&self) -> String {
         match self {
-            ActrCliError::Config { message } => {
-                format!(
-                    "⚠️  Config file error: {message}\n💡 Hint: Check Actr.toml syntax and content"
-                )
-            }
-            ActrCliError::Network { message } => {
-                format!(
-                    "🌐 Network connection error: {message}\n💡 Hint: Check network connection and service address"
-                )
-            }
+            ActrCliError::Config { message } => tr!("error.message.config", message = message),
+            ActrCliError::Network { message } => tr!("error.message.network", message = message),
             ActrCliError::Dependency { message } => {
-                format!(
-                    "📦 Dependency error: {message}\n💡 Hint: Run 'actr check' to check dependencies"
-                )
+                tr!("error.message.dependency", message = message)
             }
             ActrCliError::ValidationFailed { details } => {
-                format!(
-                    "❌ Validation failed: {details}\n💡 Hint: Fix the issues above and try again"
-                )
+                tr!("error.message.validation_failed", details = details)
             }
             ActrCliError::InstallFailed { reason } => {
-                format!(
-                    "📥 Install failed: {reason}\n💡 Hint: Run 'actr check' to check environment"
-                )
+                tr!("error.message.install_failed", reason = reason)
             }
             _ => self.to_string(),
         }
@@ -167,36 +223,18 @@ impl ActrCliError {
 
     /// Get possible solutions
     pub fn suggested_actions(&self) -> Vec<String> {
-        match self {
-            ActrCliError::Config { .. } => vec![
-                "Check Actr.toml file syntax".to_string(),
-                "Run 'actr config test' to validate config".to_string(),
-                "Refer to config examples in documentation".to_string(),
-            ],
-            ActrCliError::Network { .. } => vec![
-                "Check network connection".to_string(),
-                "Verify service address is correct".to_string(),
-                "Check firewall settings".to_string(),
-                "Run 'actr check --verbose' for details".to_string(),
-            ],
-            ActrCliError::Dependency { .. } => vec![
-                "Run 'actr check' to check dependency status".to_string(),
-                "Run 'actr install' to install missing dependencies".to_string(),
-                "Run 'actr discovery' to find available services".to_string(),
-            ],
-            ActrCliError::ValidationFailed { .. } => vec![
-                "Check and fix reported issues".to_string(),
-                "Run 'actr check --verbose' for detailed diagnostics".to_string(),
-                "Ensure all dependency services are available".to_string(),
-            ],
-            ActrCliError::InstallFailed { .. } => vec![
-                "Check disk space".to_string(),
-                "Check network connection".to_string(),
-                "Run 'actr check' to validate environment".to_string(),
-                "Try clearing cache and retry".to_string(),
-            ],
-            _ => vec!["View detailed error information".to_string()],
-        }
+        let key = match self {
+            ActrCliError::Config { .. } => "error.actions.config",
+            ActrCliError::Network { .. } => "error.actions.network",
+            ActrCliError::Dependency { .. } => "error.actions.dependency",
+            ActrCliError::ValidationFailed { .. } => "error.actions.validation_failed",
+            ActrCliError::SignatureVerification { .. } => "error.actions.signature_verification",
+            ActrCliError::TimedOut { .. } => "error.actions.timed_out",
+            ActrCliError::ProjectLocked { .. } => "error.actions.project_locked",
+            ActrCliError::InstallFailed { .. } => "error.actions.install_failed",
+            _ => "error.actions.default",
+        };
+        i18n::messages(key)
     }
 
     /// Get related documentation links
@@ -287,13 +325,13 @@ impl ErrorReporter {
         let mut output = Vec::new();
 
         // Main error message
-        output.push(error.user_message());
+        output.push(format!("[{}] {}", error.code(), error.user_message()));
         output.push(String::new());
 
         // Suggested solutions
         let actions = error.suggested_actions();
         if !actions.is_empty() {
-            output.push("🔧 Suggested solutions:".to_string());
+            output.push(tr!("error.suggested_solutions"));
             for (i, action) in actions.iter().enumerate() {
                 output.push(format!("   {}. {}", i + 1, action));
             }
@@ -303,7 +341,7 @@ impl ErrorReporter {
         // Documentation links
         let docs = error.documentation_links();
         if !docs.is_empty() {
-            output.push("📚 Related documentation:".to_string());
+            output.push(tr!("error.related_docs"));
             for (title, url) in docs {
                 output.push(format!("   • {title}: {url}"));
             }
@@ -316,17 +354,17 @@ impl ErrorReporter {
     /// Format validation report
     pub fn format_validation_report(report: &super::components::ValidationReport) -> String {
         let mut output = vec![
-            "🔍 Dependency Validation Report".to_string(),
+            tr!("validation.title"),
             "=".repeat(50),
             String::new(),
-            "📋 Config file validation:".to_string(),
+            tr!("validation.config"),
         ];
 
         // Config validation
         if report.config_validation.is_valid {
-            output.push("   ✅ Passed".to_string());
+            output.push(format!("   ✅ {}", tr!("validation.passed")));
         } else {
-            output.push("   ❌ Failed".to_string());
+            output.push(format!("   ❌ {}", tr!("validation.failed")));
             for error in &report.config_validation.errors {
                 output.push(format!("      • {error}"));
             }
@@ -334,33 +372,42 @@ impl ErrorReporter {
         output.push(String::new());
 
         // Dependency validation
-        output.push("📦 Dependency availability:".to_string());
+        output.push(tr!("validation.dependencies"));
         for dep in &report.dependency_validation {
             if dep.is_available {
-                output.push(format!("   ✅ {} - available", dep.dependency));
+                output.push(format!(
+                    "   ✅ {} - {}",
+                    dep.dependency,
+                    tr!("validation.available")
+                ));
             } else {
                 output.push(format!(
                     "   ❌ {} - {}",
                     dep.dependency,
-                    dep.error.as_deref().unwrap_or("unavailable")
+                    dep.error
+                        .clone()
+                        .unwrap_or_else(|| tr!("validation.unavailable"))
                 ));
             }
         }
         output.push(String::new());
 
         // Network validation
-        output.push("🌐 Network connectivity:".to_string());
+        output.push(tr!("validation.network"));
         for net in &report.network_validation {
             if net.is_reachable {
                 let latency = net
                     .latency_ms
                     .map(|ms| format!(" ({ms}ms)"))
                     .unwrap_or_default();
-                output.push(format!("   ✅ Connected{}", latency));
+                output.push(format!("   ✅ {}{}", tr!("validation.connected"), latency));
             } else {
                 output.push(format!(
-                    "   ❌ Connection failed - {}",
-                    net.error.as_deref().unwrap_or("unreachable")
+                    "   ❌ {} - {}",
+                    tr!("validation.connection_failed"),
+                    net.error
+                        .clone()
+                        .unwrap_or_else(|| tr!("validation.unreachable"))
                 ));
             }
         }
@@ -368,15 +415,21 @@ impl ErrorReporter {
 
         // Fingerprint validation
         if !report.fingerprint_validation.is_empty() {
-            output.push("🔐 Fingerprint validation:".to_string());
+            output.push(tr!("validation.fingerprints"));
             for fp in &report.fingerprint_validation {
                 if fp.is_valid {
-                    output.push(format!("   ✅ {} - passed", fp.dependency));
+                    output.push(format!(
+                        "   ✅ {} - {}",
+                        fp.dependency,
+                        tr!("validation.fingerprint_passed")
+                    ));
                 } else {
                     output.push(format!(
                         "   ❌ {} - {}",
                         fp.dependency,
-                        fp.error.as_deref().unwrap_or("validation failed")
+                        fp.error
+                            .clone()
+                            .unwrap_or_else(|| tr!("validation.fingerprint_failed"))
                     ));
                 }
             }
@@ -385,7 +438,7 @@ impl ErrorReporter {
 
         // Conflict report
         if !report.conflicts.is_empty() {
-            output.push("⚠️ Dependency conflicts:".to_string());
+            output.push(tr!("validation.conflicts"));
             for conflict in &report.conflicts {
                 output.push(format!(
                     "   • {} vs {}: {}",
@@ -397,9 +450,9 @@ impl ErrorReporter {
 
         // Summary
         if report.is_success() {
-            output.push("✨ Overall: All validations passed".to_string());
+            output.push(tr!("validation.overall_passed"));
         } else {
-            output.push("❌ Overall: Issues need to be resolved".to_string());
+            output.push(tr!("validation.overall_failed"));
         }
 
         output.join("\n")
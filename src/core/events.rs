@@ -0,0 +1,165 @@
+//! Machine-readable progress events (`--event-stream`)
+//!
+//! Each event is one JSON object per line, written to stdout or appended to a
+//! file or named pipe, so editors can follow init, install, gen and check while
+//! they run. Every object carries the schema `version`, an RFC 3339 `timestamp`
+//! and its `event` kind; within a version fields are only ever added.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Schema version written into every event
+pub const EVENT_STREAM_VERSION: u32 = 1;
+
+/// `--event-stream` target meaning stdout
+pub const STDOUT_TARGET: &str = "-";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ON_STDOUT: AtomicBool = AtomicBool::new(false);
+static SINK: Mutex<Option<Box<dyn Write + Send>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    CommandStarted {
+        command: String,
+    },
+    /// A pipeline stage, as listed by `--timings`
+    StageStarted {
+        stage: String,
+    },
+    StageFinished {
+        stage: String,
+        elapsed_ms: u64,
+    },
+    FileGenerated {
+        path: String,
+    },
+    /// One dependency checked by `actr check`
+    ServiceChecked {
+        service: String,
+        passed: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A compiler or protoc problem, also printed as `file:line:col: severity: message`
+    Diagnostic {
+        file: String,
+        line: u32,
+        column: u32,
+        severity: String,
+        message: String,
+    },
+    /// `error` is the `{ code, message, hints, docs }` object of `--json`
+    Error {
+        command: String,
+        error: Value,
+    },
+    /// Last event; `result` is the document `--json` prints
+    CommandFinished {
+        command: String,
+        result: Value,
+    },
+}
+
+/// Send events to `target`: [`STDOUT_TARGET`], or a file or named pipe to append to
+pub fn open_event_stream(target: &Path) -> std::io::Result<()> {
+    let sink: Box<dyn Write + Send> = if target == Path::new(STDOUT_TARGET) {
+        ON_STDOUT.store(true, Ordering::Relaxed);
+        Box::new(std::io::stdout())
+    } else {
+        Box::new(
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(target)?,
+        )
+    };
+    *SINK.lock().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+    ENABLED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Whether `--event-stream` is active
+pub fn event_stream_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether events go to stdout, which then carries nothing else
+pub fn event_stream_on_stdout() -> bool {
+    ON_STDOUT.load(Ordering::Relaxed)
+}
+
+/// Write `event` to the stream, if any
+///
+/// Write errors are ignored: a reader going away must not fail the command.
+pub fn emit(event: Event) {
+    if !event_stream_enabled() {
+        return;
+    }
+    let line = render(&event, &chrono::Utc::now().to_rfc3339());
+    if let Some(sink) = SINK.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        let _ = writeln!(sink, "{line}").and_then(|()| sink.flush());
+    }
+}
+
+/// A `file_generated` event for each of `files`
+pub fn emit_files_generated(files: &[PathBuf]) {
+    if !event_stream_enabled() {
+        return;
+    }
+    for file in files {
+        emit(Event::FileGenerated {
+            path: file.display().to_string(),
+        });
+    }
+}
+
+/// One line of the stream
+fn render(event: &Event, timestamp: &str) -> String {
+    let mut value = serde_json::to_value(event).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), EVENT_STREAM_VERSION.into());
+        object.insert("timestamp".to_string(), timestamp.into());
+    }
+    value.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_event() {
+        let line = render(
+            &Event::StageFinished {
+                stage: "protoc".to_string(),
+                elapsed_ms: 12,
+            },
+            "2026-01-01T00:00:00+00:00",
+        );
+        assert!(!line.contains('\n'));
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["event"], "stage_finished");
+        assert_eq!(value["stage"], "protoc");
+        assert_eq!(value["elapsed_ms"], 12);
+        assert_eq!(value["version"], EVENT_STREAM_VERSION);
+        assert_eq!(value["timestamp"], "2026-01-01T00:00:00+00:00");
+
+        let line = render(
+            &Event::ServiceChecked {
+                service: "echo".to_string(),
+                passed: true,
+                error: None,
+            },
+            "2026-01-01T00:00:00+00:00",
+        );
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["event"], "service_checked");
+        assert!(value.get("error").is_none());
+    }
+}
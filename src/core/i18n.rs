@@ -0,0 +1,198 @@
+//! Localized user-facing messages
+//!
+//! Messages live in per-language TOML catalogs (`locales/en.toml`, `locales/zh-CN.toml`)
+//! and are looked up by dotted key with the [`tr!`](crate::tr) macro:
+//!
+//! ```ignore
+//! human_println!("{}", tr!("gen.view_code", path = output.display()));
+//! ```
+//!
+//! `{name}` placeholders are replaced by the named arguments. The language comes from
+//! the global `--lang` flag, else `ACTR_LANG`, else the system locale (`LC_ALL`,
+//! `LC_MESSAGES`, `LANG`), and defaults to English. Keys missing from a catalog fall
+//! back to English, so strings can move into the catalogs one command at a time.
+
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{OnceLock, RwLock};
+
+/// Environment variable selecting the language when `--lang` is not given
+pub const LANG_ENV_VAR: &str = "ACTR_LANG";
+
+const EN_CATALOG: &str = include_str!("locales/en.toml");
+const ZH_CN_CATALOG: &str = include_str!("locales/zh-CN.toml");
+
+static ACTIVE_LANGUAGE: RwLock<Option<Language>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Language {
+    #[value(name = "en")]
+    En,
+    #[value(name = "zh-CN", alias = "zh")]
+    ZhCn,
+}
+
+impl Language {
+    /// Language of a locale or `--lang` value: `zh_CN.UTF-8`, `zh-CN`, `en_US`, `C`, ...
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let locale = locale.trim().to_ascii_lowercase();
+        if locale.starts_with("zh") {
+            Some(Language::ZhCn)
+        } else if locale.starts_with("en") || locale == "c" || locale == "posix" {
+            Some(Language::En)
+        } else {
+            None
+        }
+    }
+
+    /// `ACTR_LANG`, then the system locale
+    pub fn detect() -> Self {
+        std::env::var(LANG_ENV_VAR)
+            .ok()
+            .into_iter()
+            .chain(
+                ["LC_ALL", "LC_MESSAGES", "LANG"]
+                    .iter()
+                    .filter_map(|var| std::env::var(var).ok()),
+            )
+            .filter(|value| !value.trim().is_empty())
+            .find_map(|value| Self::from_locale(&value))
+            .unwrap_or(Language::En)
+    }
+
+    fn catalog(self) -> &'static HashMap<String, String> {
+        static EN: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static ZH_CN: OnceLock<HashMap<String, String>> = OnceLock::new();
+        match self {
+            Language::En => EN.get_or_init(|| parse_catalog(EN_CATALOG)),
+            Language::ZhCn => ZH_CN.get_or_init(|| parse_catalog(ZH_CN_CATALOG)),
+        }
+    }
+}
+
+/// Select the language for the whole process (`--lang`); `None` detects it
+pub fn set_language(language: Option<Language>) {
+    *ACTIVE_LANGUAGE.write().unwrap() = language;
+}
+
+/// Language selected by `--lang`, `ACTR_LANG` or the system locale
+pub fn language() -> Language {
+    ACTIVE_LANGUAGE
+        .read()
+        .unwrap()
+        .unwrap_or_else(Language::detect)
+}
+
+/// Message `key` in the active language with `{name}` placeholders filled in
+pub fn message(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    message_in(language(), key, args)
+}
+
+/// Message `key` in `language`, falling back to English and then to the key itself
+pub fn message_in(language: Language, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = language
+        .catalog()
+        .get(key)
+        .or_else(|| Language::En.catalog().get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+}
+
+/// List message `key` (a TOML array in the catalogs) in the active language
+pub fn messages(key: &str) -> Vec<String> {
+    message(key, &[]).lines().map(str::to_string).collect()
+}
+
+/// Flatten `[section] key = "text"` tables into `section.key` entries
+fn parse_catalog(source: &str) -> HashMap<String, String> {
+    fn flatten(prefix: &str, table: &toml::Table, entries: &mut HashMap<String, String>) {
+        for (key, value) in table {
+            let key = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{prefix}.{key}")
+            };
+            match value {
+                toml::Value::Table(table) => flatten(&key, table, entries),
+                toml::Value::String(text) => {
+                    entries.insert(key, text.clone());
+                }
+                // Lists such as suggested actions, one item per line
+                toml::Value::Array(items) => {
+                    let items: Vec<&str> = items.iter().filter_map(toml::Value::as_str).collect();
+                    entries.insert(key, items.join("\n"));
+                }
+                other => {
+                    entries.insert(key, other.to_string());
+                }
+            }
+        }
+    }
+
+    let table: toml::Table = source.parse().expect("message catalogs are valid TOML");
+    let mut entries = HashMap::new();
+    flatten("", &table, &mut entries);
+    entries
+}
+
+/// Localized message: `tr!("key")` or `tr!("key", name = value, ...)`
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::core::i18n::message($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::core::i18n::message(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_have_the_same_keys() {
+        let en = Language::En.catalog();
+        let zh_cn = Language::ZhCn.catalog();
+        let mut missing: Vec<&String> = en.keys().filter(|key| !zh_cn.contains_key(*key)).collect();
+        missing.extend(zh_cn.keys().filter(|key| !en.contains_key(*key)));
+        missing.sort();
+        assert!(
+            missing.is_empty(),
+            "keys missing from a catalog: {missing:?}"
+        );
+    }
+
+    #[test]
+    fn test_message_lookup() {
+        assert_eq!(Language::from_locale("zh_CN.UTF-8"), Some(Language::ZhCn));
+        assert_eq!(Language::from_locale("en_US.UTF-8"), Some(Language::En));
+        assert_eq!(Language::from_locale("C"), Some(Language::En));
+        assert_eq!(Language::from_locale("fr_FR"), None);
+
+        assert_eq!(
+            message_in(Language::En, "gen.view_code", &[("path", &"src/generated")]),
+            "📖 View generated code: src/generated"
+        );
+        assert_eq!(
+            message_in(
+                Language::ZhCn,
+                "gen.view_code",
+                &[("path", &"src/generated")]
+            ),
+            "📖 查看生成的代码：src/generated"
+        );
+        assert_eq!(
+            message_in(Language::ZhCn, "no.such.key", &[]),
+            "no.such.key"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Environment variable interpolation in Actr.toml
+//!
+//! String values may reference `${VAR}` or `${VAR:-fallback}`; `$${` writes a
+//! literal `${`. References are expanded after profile overlays are merged, so
+//! every command that reads the config sees the same values:
+//!
+//! ```toml
+//! [system.signaling]
+//! url = "wss://signaling.example.com/?token=${SIGNALING_TOKEN}"
+//! ```
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use super::error::ActrCliError;
+
+/// Expand every string value in `value` with variables from the process environment
+///
+/// Returns whether anything was expanded. All undefined variables are reported in
+/// one error.
+pub fn expand_value(value: &mut toml::Value, source: &Path) -> Result<bool> {
+    expand_value_with(value, source, &|name| std::env::var(name).ok())
+}
+
+/// Expand every string value in `value` with variables from `lookup`
+pub fn expand_value_with(
+    value: &mut toml::Value,
+    source: &Path,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<bool> {
+    let mut missing = BTreeSet::new();
+    let changed = expand_in(value, lookup, &mut missing)?;
+    if !missing.is_empty() {
+        return Err(ActrCliError::Config {
+            message: format!(
+                "{} references undefined environment variables: {}",
+                source.display(),
+                missing.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+        }
+        .into());
+    }
+    Ok(changed)
+}
+
+fn expand_in(
+    value: &mut toml::Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    missing: &mut BTreeSet<String>,
+) -> Result<bool> {
+    let mut changed = false;
+    match value {
+        toml::Value::String(s) => {
+            if s.contains('$') {
+                let expanded = expand_str(s, lookup, missing)?;
+                if expanded != *s {
+                    *s = expanded;
+                    changed = true;
+                }
+            }
+        }
+        toml::Value::Array(items) => {
+            for item in items {
+                changed |= expand_in(item, lookup, missing)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                changed |= expand_in(item, lookup, missing)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(changed)
+}
+
+/// Expand the references in one string, collecting undefined variables
+pub fn expand_str(
+    input: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    missing: &mut BTreeSet<String>,
+) -> Result<String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = after;
+            continue;
+        }
+        let Some(body) = tail.strip_prefix("${") else {
+            out.push('$');
+            rest = &tail[1..];
+            continue;
+        };
+        let Some(end) = body.find('}') else {
+            return Err(ActrCliError::Config {
+                message: format!("Unterminated '${{' in \"{input}\""),
+            }
+            .into());
+        };
+        let reference = &body[..end];
+        let (name, fallback) = match reference.split_once(":-") {
+            Some((name, fallback)) => (name, Some(fallback)),
+            None => (reference, None),
+        };
+        if !is_valid_name(name) {
+            return Err(ActrCliError::Config {
+                message: format!("Invalid variable reference '${{{reference}}}' in \"{input}\""),
+            }
+            .into());
+        }
+        // Like the shell, `:-` also applies to variables that are set but empty
+        match (
+            lookup(name).filter(|v| !v.is_empty() || fallback.is_none()),
+            fallback,
+        ) {
+            (Some(value), _) => out.push_str(&value),
+            (None, Some(fallback)) => out.push_str(fallback),
+            (None, None) => {
+                missing.insert(name.to_string());
+            }
+        }
+        rest = &body[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "TOKEN" => Some("s3cret".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_str() {
+        let mut missing = BTreeSet::new();
+        let expand = |s: &str, missing: &mut BTreeSet<String>| expand_str(s, &lookup, missing);
+
+        assert_eq!(
+            expand("wss://host/?token=${TOKEN}", &mut missing).unwrap(),
+            "wss://host/?token=s3cret"
+        );
+        assert_eq!(
+            expand("${HOST:-localhost}:8080", &mut missing).unwrap(),
+            "localhost:8080"
+        );
+        assert_eq!(expand("${EMPTY:-dev}", &mut missing).unwrap(), "dev");
+        assert_eq!(
+            expand("$${TOKEN} costs $5", &mut missing).unwrap(),
+            "${TOKEN} costs $5"
+        );
+        assert!(missing.is_empty());
+        assert!(expand("${TOKEN", &mut missing).is_err());
+        assert!(expand("${1BAD}", &mut missing).is_err());
+    }
+
+    #[test]
+    fn test_expand_value_reports_all_missing() {
+        let mut value: toml::Value = toml::from_str(
+            "[system.signaling]\nurl = \"wss://${HOST}/?token=${TOKEN}\"\n\n[package]\nname = \"${NAME}\"\ntags = [\"${TOKEN}\"]\n",
+        )
+        .unwrap();
+        let err = expand_value_with(&mut value, Path::new("Actr.toml"), &lookup).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Actr.toml references undefined environment variables: HOST, NAME")
+        );
+
+        let mut value: toml::Value = toml::from_str("tags = [\"${TOKEN}\"]\n").unwrap();
+        assert!(expand_value_with(&mut value, Path::new("Actr.toml"), &lookup).unwrap());
+        assert_eq!(value["tags"][0].as_str(), Some("s3cret"));
+    }
+}
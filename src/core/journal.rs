@@ -0,0 +1,358 @@
+//! Install transactions
+//!
+//! An install writes to three places: Actr.toml, the proto cache and
+//! Actr.lock.toml. A [`Transaction`] keeps them consistent across crashes:
+//!
+//! 1. **Prepare**: cached protos and the new lock file are written to
+//!    `.actr/transaction/staged/`, and Actr.toml is edited in place after its
+//!    backup is recorded. The live cache and lock file are untouched.
+//! 2. **Commit**: `journal.json` is switched to `committing`, then each staged
+//!    entry is renamed over its target; replaced targets are moved to
+//!    `.actr/transaction/backup/` first. Finally the directory is removed.
+//!
+//! A journal found on the next run is recovered before anything else happens:
+//! a prepared transaction is rolled back (Actr.toml restored, staging dropped),
+//! a committing one is rolled forward, since its commit point was reached.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::components::ConfigBackup;
+use crate::history::HISTORY_DIR;
+
+/// Directory of the journal and staged files, below the project root
+pub const TRANSACTION_DIR: &str = "transaction";
+const JOURNAL_FILE: &str = "journal.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JournalState {
+    Prepared,
+    Committing,
+}
+
+/// A file or directory to rename over `target` on commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StagedEntry {
+    target: PathBuf,
+    staged: PathBuf,
+    backup: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Journal {
+    state: JournalState,
+    /// Actr.toml and its copy from before the transaction
+    config_backup: Option<(PathBuf, PathBuf)>,
+    staged: Vec<StagedEntry>,
+}
+
+/// How an interrupted transaction was recovered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    /// Interrupted before its commit point; the project is as it was before
+    RolledBack,
+    /// Interrupted while committing; the remaining entries were moved into place
+    Completed,
+}
+
+/// Staged writes of one install, committed or rolled back as a whole
+pub struct Transaction {
+    dir: PathBuf,
+    journal: Journal,
+}
+
+impl Transaction {
+    /// Start a transaction in `project_root`
+    ///
+    /// Fails if an earlier transaction was not recovered.
+    pub fn begin(project_root: &Path) -> Result<Self> {
+        let dir = transaction_dir(project_root);
+        if dir.join(JOURNAL_FILE).exists() {
+            anyhow::bail!(
+                "An interrupted install left {}; it must be recovered first",
+                dir.display()
+            );
+        }
+        // Leftovers of a transaction that crashed before writing its journal
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        std::fs::create_dir_all(dir.join("staged"))
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let transaction = Self {
+            dir,
+            journal: Journal {
+                state: JournalState::Prepared,
+                config_backup: None,
+                staged: Vec::new(),
+            },
+        };
+        transaction.persist()?;
+        Ok(transaction)
+    }
+
+    /// Recover the transaction an interrupted run left in `project_root`, if any
+    pub fn recover(project_root: &Path) -> Result<Option<Recovery>> {
+        let dir = transaction_dir(project_root);
+        let path = dir.join(JOURNAL_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let journal: Journal = serde_json::from_str(&content)
+            .with_context(|| format!("Corrupt install journal {}", path.display()))?;
+        let transaction = Self { dir, journal };
+        match transaction.journal.state {
+            JournalState::Prepared => {
+                transaction.rollback()?;
+                Ok(Some(Recovery::RolledBack))
+            }
+            JournalState::Committing => {
+                transaction.apply()?;
+                transaction.finish()?;
+                Ok(Some(Recovery::Completed))
+            }
+        }
+    }
+
+    /// Record the Actr.toml backup, before Actr.toml is edited
+    pub fn record_config_backup(&mut self, backup: &ConfigBackup) -> Result<()> {
+        self.journal.config_backup =
+            Some((backup.original_path.clone(), backup.backup_path.clone()));
+        self.persist()
+    }
+
+    /// Fresh staging path for `name`; write the content there, then call [`Self::stage`]
+    pub fn staging_path(&self, name: &str) -> PathBuf {
+        self.dir
+            .join("staged")
+            .join(format!("{}-{}", self.journal.staged.len(), sanitize(name)))
+    }
+
+    /// Rename `staged` over `target` on commit
+    pub fn stage(&mut self, target: PathBuf, staged: PathBuf) -> Result<()> {
+        let backup = self
+            .dir
+            .join("backup")
+            .join(staged.file_name().unwrap_or_default());
+        self.journal.staged.push(StagedEntry {
+            target,
+            staged,
+            backup,
+        });
+        self.persist()
+    }
+
+    /// Move every staged entry into place
+    ///
+    /// If a rename fails, the entries already moved are put back and Actr.toml
+    /// is restored.
+    pub fn commit(mut self) -> Result<()> {
+        self.journal.state = JournalState::Committing;
+        self.persist()?;
+        if let Err(e) = self.apply() {
+            if let Err(undo) = self.undo_applied().and_then(|()| self.rollback()) {
+                tracing::error!("Failed to roll back the install: {undo:#}");
+            }
+            return Err(e.context("Failed to commit the install"));
+        }
+        self.finish()
+    }
+
+    /// Discard staged writes and restore Actr.toml
+    pub fn rollback(&self) -> Result<()> {
+        if let Some((original, backup)) = &self.journal.config_backup
+            && backup.exists()
+        {
+            std::fs::copy(backup, original).with_context(|| {
+                format!(
+                    "Failed to restore {} from {}",
+                    original.display(),
+                    backup.display()
+                )
+            })?;
+            std::fs::remove_file(backup)?;
+        }
+        std::fs::remove_dir_all(&self.dir)
+            .with_context(|| format!("Failed to remove {}", self.dir.display()))
+    }
+
+    /// Rename staged entries over their targets; entries already moved are skipped
+    fn apply(&self) -> Result<()> {
+        std::fs::create_dir_all(self.dir.join("backup"))?;
+        for entry in &self.journal.staged {
+            if !entry.staged.exists() {
+                continue;
+            }
+            if entry.target.exists() && !entry.backup.exists() {
+                std::fs::rename(&entry.target, &entry.backup)
+                    .with_context(|| format!("Failed to move {} aside", entry.target.display()))?;
+            }
+            if let Some(parent) = entry.target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::rename(&entry.staged, &entry.target)
+                .with_context(|| format!("Failed to move {} into place", entry.target.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Put back the targets `apply` replaced
+    fn undo_applied(&self) -> Result<()> {
+        for entry in self.journal.staged.iter().rev() {
+            if entry.staged.exists() {
+                if entry.backup.exists() {
+                    // Moved aside, but the staged entry never made it
+                    std::fs::rename(&entry.backup, &entry.target)?;
+                }
+                continue;
+            }
+            remove_path(&entry.target)?;
+            if entry.backup.exists() {
+                std::fs::rename(&entry.backup, &entry.target)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<()> {
+        if let Some((_, backup)) = &self.journal.config_backup
+            && backup.exists()
+        {
+            std::fs::remove_file(backup)?;
+        }
+        std::fs::remove_dir_all(&self.dir)
+            .with_context(|| format!("Failed to remove {}", self.dir.display()))
+    }
+
+    /// Write the journal atomically
+    fn persist(&self) -> Result<()> {
+        let path = self.dir.join(JOURNAL_FILE);
+        let tmp_path = self.dir.join(format!("{JOURNAL_FILE}.tmp"));
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(&self.journal)?)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, &path)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+fn transaction_dir(project_root: &Path) -> PathBuf {
+    project_root.join(HISTORY_DIR).join(TRANSACTION_DIR)
+}
+
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (tempfile::TempDir, PathBuf, PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let lock = dir.path().join("Actr.lock.toml");
+        let cache = dir.path().join("protos/remote/echo");
+        std::fs::write(&lock, "old lock").unwrap();
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(cache.join("echo.proto"), "old proto").unwrap();
+        (dir, lock, cache)
+    }
+
+    fn stage(transaction: &mut Transaction, lock: &Path, cache: &Path) {
+        let staged_cache = transaction.staging_path("echo");
+        std::fs::create_dir_all(&staged_cache).unwrap();
+        std::fs::write(staged_cache.join("echo.proto"), "new proto").unwrap();
+        transaction
+            .stage(cache.to_path_buf(), staged_cache)
+            .unwrap();
+        let staged_lock = transaction.staging_path("Actr.lock.toml");
+        std::fs::write(&staged_lock, "new lock").unwrap();
+        transaction.stage(lock.to_path_buf(), staged_lock).unwrap();
+    }
+
+    #[test]
+    fn test_commit_and_recover_prepared() {
+        let (dir, lock, cache) = setup();
+        let config = dir.path().join("Actr.toml");
+        let config_backup = dir.path().join("Actr.toml.bak");
+        std::fs::write(&config, "edited").unwrap();
+        std::fs::write(&config_backup, "original").unwrap();
+
+        // Crash before the commit point: nothing staged reaches the project
+        let mut transaction = Transaction::begin(dir.path()).unwrap();
+        transaction
+            .record_config_backup(&ConfigBackup {
+                original_path: config.clone(),
+                backup_path: config_backup.clone(),
+                timestamp: std::time::SystemTime::now(),
+            })
+            .unwrap();
+        stage(&mut transaction, &lock, &cache);
+        drop(transaction);
+        assert_eq!(
+            Transaction::recover(dir.path()).unwrap(),
+            Some(Recovery::RolledBack)
+        );
+        assert_eq!(std::fs::read_to_string(&config).unwrap(), "original");
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), "old lock");
+        assert!(!config_backup.exists());
+        assert_eq!(Transaction::recover(dir.path()).unwrap(), None);
+
+        let mut transaction = Transaction::begin(dir.path()).unwrap();
+        stage(&mut transaction, &lock, &cache);
+        transaction.commit().unwrap();
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), "new lock");
+        assert_eq!(
+            std::fs::read_to_string(cache.join("echo.proto")).unwrap(),
+            "new proto"
+        );
+        assert!(!transaction_dir(dir.path()).exists());
+    }
+
+    #[test]
+    fn test_recover_committing_rolls_forward() {
+        let (dir, lock, cache) = setup();
+        let mut transaction = Transaction::begin(dir.path()).unwrap();
+        stage(&mut transaction, &lock, &cache);
+
+        // Crash after the cache entry was moved, before the lock file was
+        transaction.journal.state = JournalState::Committing;
+        transaction.persist().unwrap();
+        let first = &transaction.journal.staged[0];
+        std::fs::create_dir_all(first.backup.parent().unwrap()).unwrap();
+        std::fs::rename(&first.target, &first.backup).unwrap();
+        std::fs::rename(&first.staged, &first.target).unwrap();
+        drop(transaction);
+
+        assert_eq!(
+            Transaction::recover(dir.path()).unwrap(),
+            Some(Recovery::Completed)
+        );
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), "new lock");
+        assert_eq!(
+            std::fs::read_to_string(cache.join("echo.proto")).unwrap(),
+            "new proto"
+        );
+        assert!(!transaction_dir(dir.path()).exists());
+    }
+}
@@ -3,13 +3,30 @@
 //! 实现统一的CLI复用架构，通过8个核心组件和3个操作管道
 //! 提供一致的用户体验和高代码复用率。
 
+pub mod budget;
 pub mod components;
 pub mod container;
 pub mod error;
+pub mod events;
+pub mod i18n;
+pub mod interpolate;
+pub mod journal;
+pub mod network;
+pub mod output;
 pub mod pipelines;
+pub mod profile;
+pub mod retry;
+pub mod timings;
+pub mod trust;
 
 // Re-export core types
+pub use budget::ExecutionBudget;
 pub use components::*;
 pub use container::*;
 pub use error::*;
+pub use journal::{Recovery, Transaction};
+pub use network::NetworkSettings;
+pub use output::*;
 pub use pipelines::*;
+pub use retry::RetryPolicy;
+pub use trust::TrustPolicy;
@@ -0,0 +1,277 @@
+//! 机器可读输出 (--json)
+//!
+//! `OutputFormatter` turns any `CommandResult` (or error) into a single JSON
+//! document on stdout. While JSON output is enabled, human-oriented progress
+//! lines printed with `human_println!` are redirected to stderr.
+
+use actr_protocol::ActrTypeExt;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::{
+    ActrCliError, CommandResult, GenerationResult, InstallResult, UNKNOWN_ERROR_CODE,
+    ValidationReport,
+};
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable JSON output for the whole process
+pub fn set_json_output(enabled: bool) {
+    JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--json` is active
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Print a human-oriented line: stdout normally, stderr when `--json` is active
+#[macro_export]
+macro_rules! human_println {
+    () => {
+        if $crate::core::json_output() {
+            eprintln!();
+        } else {
+            println!();
+        }
+    };
+    ($($arg:tt)*) => {
+        if $crate::core::json_output() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Serializes command results and errors into JSON documents
+pub struct OutputFormatter;
+
+impl OutputFormatter {
+    /// JSON document for a finished command
+    pub fn format_result(command: &str, result: &CommandResult) -> Value {
+        match result {
+            CommandResult::Success(message) => match embedded_json(message) {
+                Some(data) => json!({ "status": "success", "command": command, "data": data }),
+                None => json!({ "status": "success", "command": command, "message": message }),
+            },
+            CommandResult::Install(install) => json!({
+                "status": "success",
+                "command": command,
+                "install": Self::install_json(install),
+            }),
+            CommandResult::Validation(report) => json!({
+                "status": if report.is_success() { "success" } else { "error" },
+                "command": command,
+                "validation": Self::validation_json(report),
+            }),
+            CommandResult::Generation(generation) => json!({
+                "status": if generation.errors.is_empty() { "success" } else { "error" },
+                "command": command,
+                "generation": Self::generation_json(generation),
+            }),
+            CommandResult::Error(message) => match embedded_json(message) {
+                Some(data) => json!({
+                    "status": "error",
+                    "command": command,
+                    "error": data.get("error").cloned().unwrap_or_else(|| json!("command failed")),
+                    "data": data,
+                }),
+                None => json!({ "status": "error", "command": command, "error": message }),
+            },
+        }
+    }
+
+    /// JSON document for a command that returned an error
+    ///
+    /// `error` is `{ code, message, hints, docs }` so wrapping tools can branch on the code.
+    pub fn format_error(command: &str, error: &anyhow::Error) -> Value {
+        json!({ "status": "error", "command": command, "error": Self::error_json(error) })
+    }
+
+    /// `{ code, message, hints, docs }` object describing `error`
+    pub fn error_json(error: &anyhow::Error) -> Value {
+        if let Some(cli_error) = error.downcast_ref::<ActrCliError>() {
+            let docs: Vec<Value> = cli_error
+                .documentation_links()
+                .into_iter()
+                .map(|(title, url)| json!({ "title": title, "url": url }))
+                .collect();
+            return json!({
+                "code": cli_error.code(),
+                "message": cli_error.to_string(),
+                "hints": cli_error.suggested_actions(),
+                "docs": docs,
+            });
+        }
+        if let Some(gen_error) = error.downcast_ref::<crate::error::ActrCliError>() {
+            return json!({
+                "code": gen_error.code(),
+                "message": gen_error.to_string(),
+                "hints": gen_error.suggested_actions(),
+                "docs": [],
+            });
+        }
+        json!({
+            "code": UNKNOWN_ERROR_CODE,
+            "message": format!("{error:#}"),
+            "hints": [],
+            "docs": [],
+        })
+    }
+
+    fn install_json(result: &InstallResult) -> Value {
+        json!({
+            "installed_dependencies": result
+                .installed_dependencies
+                .iter()
+                .map(|dep| json!({
+                    "alias": dep.spec.alias,
+                    "name": dep.spec.name,
+                    "actr_type": dep.spec.actr_type.as_ref().map(|t| t.to_string_repr()),
+                    "fingerprint": dep.fingerprint,
+                    "proto_files": dep.proto_files.iter().map(|p| p.name.clone()).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+            "updated_config": result.updated_config,
+            "updated_lock_file": result.updated_lock_file,
+            "cache_updates": result.cache_updates,
+            "warnings": result.warnings,
+        })
+    }
+
+    fn validation_json(report: &ValidationReport) -> Value {
+        json!({
+            "is_valid": report.is_success(),
+            "config": {
+                "is_valid": report.config_validation.is_valid,
+                "errors": report.config_validation.errors,
+                "warnings": report.config_validation.warnings,
+            },
+            "dependencies": report
+                .dependency_validation
+                .iter()
+                .map(|d| json!({ "dependency": d.dependency, "available": d.is_available, "error": d.error }))
+                .collect::<Vec<_>>(),
+            "network": report
+                .network_validation
+                .iter()
+                .filter(|n| n.is_applicable)
+                .map(|n| json!({
+                    "reachable": n.is_reachable,
+                    "health": format!("{:?}", n.health),
+                    "latency_ms": n.latency_ms,
+                    "error": n.error,
+                }))
+                .collect::<Vec<_>>(),
+            "fingerprints": report
+                .fingerprint_validation
+                .iter()
+                .map(|f| json!({ "dependency": f.dependency, "valid": f.is_valid, "error": f.error }))
+                .collect::<Vec<_>>(),
+            "conflicts": report
+                .conflicts
+                .iter()
+                .map(|c| json!({
+                    "dependency_a": c.dependency_a,
+                    "dependency_b": c.dependency_b,
+                    "service": c.service,
+                    "type": format!("{:?}", c.conflict_type),
+                    "description": c.description,
+                }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    fn generation_json(result: &GenerationResult) -> Value {
+        json!({
+            "files": result
+                .generated_files
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>(),
+            "warnings": result.warnings,
+            "errors": result.errors,
+        })
+    }
+}
+
+/// Commands such as `check --format json` already return a JSON report as their message
+fn embedded_json(message: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(message)
+        .ok()
+        .filter(|value| value.is_object() || value.is_array())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_success_message_and_embedded_report() {
+        let plain = OutputFormatter::format_result(
+            "doc",
+            &CommandResult::Success("Documentation generated".to_string()),
+        );
+        assert_eq!(plain["status"], "success");
+        assert_eq!(plain["message"], "Documentation generated");
+
+        let embedded = OutputFormatter::format_result(
+            "check",
+            &CommandResult::Success(r#"{"passed":true}"#.to_string()),
+        );
+        assert_eq!(embedded["data"]["passed"], true);
+    }
+
+    #[test]
+    fn test_generation_lists_files() {
+        let value = OutputFormatter::format_result(
+            "gen",
+            &CommandResult::Generation(GenerationResult {
+                generated_files: vec![PathBuf::from("src/generated/mod.rs")],
+                warnings: vec![],
+                errors: vec![],
+            }),
+        );
+        assert_eq!(value["command"], "gen");
+        assert_eq!(value["generation"]["files"][0], "src/generated/mod.rs");
+    }
+
+    #[test]
+    fn test_error_result() {
+        let value =
+            OutputFormatter::format_result("install", &CommandResult::Error("boom".to_string()));
+        assert_eq!(value["status"], "error");
+        assert_eq!(value["error"], "boom");
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let error = anyhow::Error::new(ActrCliError::FingerprintMismatch {
+            expected: "sha256:aaa".to_string(),
+            actual: "sha256:bbb".to_string(),
+        });
+        let value = OutputFormatter::format_error("install", &error);
+        assert_eq!(value["status"], "error");
+        assert_eq!(value["error"]["code"], "ACTR-E2003");
+        assert_eq!(
+            value["error"]["message"],
+            "Fingerprint mismatch: expected sha256:aaa, got sha256:bbb"
+        );
+        assert!(value["error"]["hints"].is_array());
+        assert_eq!(value["error"]["docs"][0]["title"], "User Guide");
+
+        let gen_error = anyhow::Error::new(crate::error::ActrCliError::tool_missing(
+            "protoc",
+            "brew install protobuf",
+        ));
+        let value = OutputFormatter::format_error("gen", &gen_error);
+        assert_eq!(value["error"]["code"], "ACTR-E4002");
+        assert_eq!(value["error"]["hints"][0], "brew install protobuf");
+
+        let value = OutputFormatter::format_error("gen", &anyhow::anyhow!("boom"));
+        assert_eq!(value["error"]["code"], UNKNOWN_ERROR_CODE);
+        assert_eq!(value["error"]["message"], "boom");
+    }
+}
@@ -7,12 +7,44 @@ use actr_protocol::ActrTypeExt;
 use anyhow::Result;
 use std::sync::Arc;
 
+use super::budget::ExecutionBudget;
 use super::components::*;
+use super::error::ActrCliError;
+use super::journal::{Recovery, Transaction};
+use super::timings::timed;
+use super::trust::TrustPolicy;
+use crate::history::{History, HistoryOperation};
+
+/// Lock file next to Actr.toml
+const LOCK_FILE_NAME: &str = "Actr.lock.toml";
 
 // ============================================================================
 // 管道结果类型
 // ============================================================================
 
+/// 安装选项
+#[derive(Debug, Clone, Default)]
+pub struct InstallOptions {
+    /// Install services whose publisher signature is missing or invalid (with a warning)
+    pub allow_unsigned: bool,
+    /// How fingerprint conflicts between dependencies are settled
+    pub resolution: ConflictResolution,
+}
+
+/// 依赖冲突处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ConflictResolution {
+    /// Fail on any conflict
+    #[default]
+    Strict,
+    /// Keep the locked version of a conflicting service when its protos are cached
+    PreferLocked,
+    /// Install the version the registry serves now
+    PreferLatest,
+    /// Ask which version to keep for each conflicting service
+    Interactive,
+}
+
 /// 安装结果
 #[derive(Debug, Clone)]
 pub struct InstallResult {
@@ -43,13 +75,105 @@ impl InstallResult {
     }
 }
 
-/// 安装计划
+/// 卸载结果
 #[derive(Debug, Clone)]
+pub struct UninstallResult {
+    pub removed_dependencies: Vec<DependencySpec>,
+    pub purged_services: Vec<String>,
+    pub updated_lock_file: bool,
+    pub warnings: Vec<String>,
+}
+
+impl UninstallResult {
+    pub fn summary(&self) -> String {
+        format!(
+            "Removed {} dependencies, purged {} cache entries",
+            self.removed_dependencies.len(),
+            self.purged_services.len()
+        )
+    }
+}
+
+/// 安装计划
+///
+/// Computed by `InstallPipeline::plan_install` without writing anything, and
+/// applied as is by `install_dependencies_with`.
+#[derive(Debug, Clone, Default)]
 pub struct InstallPlan {
+    /// Direct dependencies as they will be written to Actr.toml
     pub dependencies_to_install: Vec<DependencySpec>,
+    /// Every service to lock, transitive ones included, with its resolved fingerprint
     pub resolved_dependencies: Vec<ResolvedDependency>,
+    /// Bytes of proto files that will be cached
     pub estimated_cache_size: u64,
+    /// Project paths the install writes to
     pub required_permissions: Vec<String>,
+    /// Effect on each Actr.lock.toml entry, in `resolved_dependencies` order
+    pub lock_changes: Vec<LockChange>,
+    /// Services kept at their locked version by the conflict strategy
+    pub kept_locked: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Effect of an install on one lock file entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockChange {
+    Added {
+        name: String,
+        fingerprint: String,
+    },
+    Updated {
+        name: String,
+        from: String,
+        to: String,
+    },
+    Unchanged {
+        name: String,
+        fingerprint: String,
+    },
+}
+
+impl LockChange {
+    fn between(previous: Option<&LockFile>, dep: &ResolvedDependency) -> Self {
+        let name = dep.spec.name.clone();
+        let fingerprint = dep.fingerprint.clone();
+        match previous.and_then(|lock| lock.dependencies.iter().find(|locked| locked.name == name))
+        {
+            None => Self::Added { name, fingerprint },
+            Some(locked) if locked.fingerprint == fingerprint => {
+                Self::Unchanged { name, fingerprint }
+            }
+            Some(locked) => Self::Updated {
+                name,
+                from: locked.fingerprint.clone(),
+                to: fingerprint,
+            },
+        }
+    }
+}
+
+/// 传递依赖解析结果
+#[derive(Debug, Clone, Default)]
+pub struct TransitiveResolution {
+    /// Direct dependencies first, then the services they pull in
+    pub dependencies: Vec<ResolvedDependency>,
+    /// Fingerprints pinned by `depends-on:` tags, aliased `<dependent> → <service>`
+    pub requirements: Vec<ResolvedDependency>,
+    /// Fetched service details by service name
+    pub details: std::collections::HashMap<String, ServiceDetails>,
+    /// Services that could not be fetched, with the error
+    pub unresolved: Vec<(String, String)>,
+}
+
+impl TransitiveResolution {
+    /// Resolved services plus pinned requirements, for `check_conflicts`
+    pub fn conflict_candidates(&self) -> Vec<ResolvedDependency> {
+        self.dependencies
+            .iter()
+            .chain(&self.requirements)
+            .cloned()
+            .collect()
+    }
 }
 
 /// 生成选项
@@ -75,6 +199,7 @@ pub struct ValidationPipeline {
     service_discovery: Arc<dyn ServiceDiscovery>,
     network_validator: Arc<dyn NetworkValidator>,
     fingerprint_validator: Arc<dyn FingerprintValidator>,
+    budget: ExecutionBudget,
 }
 
 impl ValidationPipeline {
@@ -91,14 +216,31 @@ impl ValidationPipeline {
             service_discovery,
             network_validator,
             fingerprint_validator,
+            budget: ExecutionBudget::unlimited(),
         }
     }
 
+    /// Abort network calls when the command times out or is interrupted
+    pub fn with_budget(mut self, budget: ExecutionBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Get execution budget
+    pub fn budget(&self) -> &ExecutionBudget {
+        &self.budget
+    }
+
     /// Get service discovery component
     pub fn service_discovery(&self) -> &Arc<dyn ServiceDiscovery> {
         &self.service_discovery
     }
 
+    /// Get dependency resolver component
+    pub fn dependency_resolver(&self) -> &Arc<dyn DependencyResolver> {
+        &self.dependency_resolver
+    }
+
     /// Get network validator component
     pub fn network_validator(&self) -> &Arc<dyn NetworkValidator> {
         &self.network_validator
@@ -109,6 +251,11 @@ impl ValidationPipeline {
         &self.config_manager
     }
 
+    /// Get fingerprint validator component
+    pub fn fingerprint_validator(&self) -> &Arc<dyn FingerprintValidator> {
+        &self.fingerprint_validator
+    }
+
     /// 完整的项目验证流程
     pub async fn validate_project(&self) -> Result<ValidationReport> {
         // 1. 配置文件验证
@@ -140,7 +287,15 @@ impl ValidationPipeline {
 
         let mut service_details = Vec::new();
         for spec in &dependency_specs {
-            match self.service_discovery.get_service_details(&spec.name).await {
+            match self
+                .budget
+                .run(timed(
+                    "discovery",
+                    self.service_discovery
+                        .find_service(&spec.name, &spec.constraint),
+                ))
+                .await?
+            {
                 Ok(details) => service_details.push(details),
                 Err(_) => {
                     // Service might not be available, continue without details
@@ -202,9 +357,13 @@ impl ValidationPipeline {
             } else {
                 // Perform validation
                 let (available, err) = match self
-                    .service_discovery
-                    .check_service_availability(&spec.name)
-                    .await
+                    .budget
+                    .run(timed(
+                        "discovery",
+                        self.service_discovery
+                            .check_service_availability(&spec.name),
+                    ))
+                    .await?
                 {
                     Ok(status) => {
                         if status.is_available {
@@ -235,6 +394,101 @@ impl ValidationPipeline {
         Ok(results)
     }
 
+    /// Resolve the given specs and, breadth first, every service they depend on
+    ///
+    /// Each service is fetched once, so a service reached through several paths
+    /// resolves to a single fingerprint. Transitive dependencies use the service
+    /// name as their alias; services that cannot be fetched are kept without protos.
+    /// Fingerprints pinned by `depends-on:` tags are collected in `requirements`.
+    pub async fn resolve_transitive(
+        &self,
+        specs: &[DependencySpec],
+    ) -> Result<TransitiveResolution> {
+        use std::collections::{HashSet, VecDeque};
+
+        let mut resolution = TransitiveResolution::default();
+        let mut queue: VecDeque<DependencySpec> = specs.iter().cloned().collect();
+        let mut queued: HashSet<String> = specs.iter().map(|s| s.name.clone()).collect();
+        let mut failed: HashSet<String> = HashSet::new();
+
+        while let Some(spec) = queue.pop_front() {
+            if !resolution.details.contains_key(&spec.name) && !failed.contains(&spec.name) {
+                match self
+                    .budget
+                    .run(timed(
+                        "discovery",
+                        self.service_discovery
+                            .find_service(&spec.name, &spec.constraint),
+                    ))
+                    .await?
+                {
+                    Ok(details) => {
+                        for requirement in DependencyRequirement::from_tags(&details.info.tags) {
+                            if let Some(fingerprint) = requirement.fingerprint {
+                                resolution.requirements.push(ResolvedDependency {
+                                    spec: DependencySpec {
+                                        alias: format!(
+                                            "{} → {}",
+                                            details.info.name, requirement.name
+                                        ),
+                                        name: requirement.name,
+                                        actr_type: None,
+                                        fingerprint: Some(fingerprint.clone()),
+                                        constraint: Default::default(),
+                                    },
+                                    fingerprint,
+                                    proto_files: Vec::new(),
+                                    dependencies: Vec::new(),
+                                    selected_tag: None,
+                                });
+                            }
+                        }
+                        resolution.details.insert(spec.name.clone(), details);
+                    }
+                    Err(e) => {
+                        resolution
+                            .unresolved
+                            .push((spec.name.clone(), e.to_string()));
+                        failed.insert(spec.name.clone());
+                    }
+                }
+            }
+
+            let resolved = match resolution.details.get(&spec.name) {
+                Some(details) => {
+                    for name in &details.dependencies {
+                        if queued.insert(name.clone()) {
+                            queue.push_back(DependencySpec {
+                                alias: name.clone(),
+                                name: name.clone(),
+                                actr_type: None,
+                                fingerprint: None,
+                                constraint: Default::default(),
+                            });
+                        }
+                    }
+                    ResolvedDependency {
+                        fingerprint: details.info.fingerprint.clone(),
+                        proto_files: details.proto_files.clone(),
+                        dependencies: details.dependencies.clone(),
+                        selected_tag: spec.constraint.selected_tag(&details.info),
+                        spec,
+                    }
+                }
+                None => ResolvedDependency {
+                    fingerprint: spec.fingerprint.clone().unwrap_or_default(),
+                    proto_files: Vec::new(),
+                    dependencies: Vec::new(),
+                    spec,
+                    selected_tag: None,
+                },
+            };
+            resolution.dependencies.push(resolved);
+        }
+
+        Ok(resolution)
+    }
+
     /// 网络连通性验证
     pub async fn validate_network_connectivity(
         &self,
@@ -242,7 +496,13 @@ impl ValidationPipeline {
         options: &NetworkCheckOptions,
     ) -> Result<Vec<NetworkValidation>> {
         let names = deps.iter().map(|d| d.spec.name.clone()).collect::<Vec<_>>();
-        let network_results = self.network_validator.batch_check(&names, options).await?;
+        let network_results = self
+            .budget
+            .run(timed(
+                "network checks",
+                self.network_validator.batch_check(&names, options),
+            ))
+            .await??;
 
         Ok(network_results
             .into_iter()
@@ -260,6 +520,8 @@ impl ValidationPipeline {
                         Some("Network check skipped: no endpoint address available".to_string());
                     health = HealthStatus::Unknown;
                     latency_ms = None;
+                } else if error.as_deref() == Some(OfflineNetworkValidator::SKIP_MESSAGE) {
+                    is_applicable = false;
                 }
 
                 NetworkValidation {
@@ -290,15 +552,21 @@ impl ValidationPipeline {
             // 计算实际指纹（如果 resolved_dependencies 中没有指纹，从远程获取）
             let actual_fp = if dep.fingerprint.is_empty() {
                 match self
-                    .service_discovery
-                    .get_service_details(&dep.spec.name)
-                    .await
+                    .budget
+                    .run(timed(
+                        "discovery",
+                        self.service_discovery
+                            .find_service(&dep.spec.name, &dep.spec.constraint),
+                    ))
+                    .await?
                 {
                     Ok(details) => {
-                        let computed = self
-                            .fingerprint_validator
-                            .compute_service_fingerprint(&details.info)
-                            .await?;
+                        let computed = timed(
+                            "fingerprints",
+                            self.fingerprint_validator
+                                .compute_service_fingerprint(&details.info),
+                        )
+                        .await?;
                         Some(computed)
                     }
                     Err(e) => {
@@ -353,6 +621,7 @@ pub struct InstallPipeline {
     cache_manager: Arc<dyn CacheManager>,
     #[allow(dead_code)]
     proto_processor: Arc<dyn ProtoProcessor>,
+    user_interface: Option<Arc<dyn UserInterface>>,
 }
 
 impl InstallPipeline {
@@ -367,9 +636,16 @@ impl InstallPipeline {
             config_manager,
             cache_manager,
             proto_processor,
+            user_interface: None,
         }
     }
 
+    /// Report per-dependency progress through the given UI
+    pub fn with_user_interface(mut self, user_interface: Arc<dyn UserInterface>) -> Self {
+        self.user_interface = Some(user_interface);
+        self
+    }
+
     /// Get validation pipeline reference
     pub fn validation_pipeline(&self) -> &ValidationPipeline {
         &self.validation_pipeline
@@ -382,7 +658,79 @@ impl InstallPipeline {
 
     /// Check-First 安装流程
     pub async fn install_dependencies(&self, specs: &[DependencySpec]) -> Result<InstallResult> {
-        // 🔍 阶段1: 完整验证 (复用ValidationPipeline)
+        self.install_dependencies_with(specs, &InstallOptions::default())
+            .await
+    }
+
+    /// Install flow with explicit options (e.g. `--allow-unsigned`)
+    pub async fn install_dependencies_with(
+        &self,
+        specs: &[DependencySpec],
+        options: &InstallOptions,
+    ) -> Result<InstallResult> {
+        self.recover_interrupted_install()?;
+
+        // 🔍 阶段1: 完整验证与解析（不写入任何文件）
+        let plan = self.plan_install(specs, options).await?;
+
+        // 📝 阶段2: 暂存写入，然后一次性提交
+        let previous_lock = self.load_lock_file();
+        let mut transaction = Transaction::begin(self.config_manager.get_project_root())?;
+        let prepared = async {
+            let backup = self.config_manager.backup_config().await?;
+            transaction.record_config_backup(&backup)?;
+            self.execute_atomic_install(plan, &mut transaction).await
+        }
+        .await;
+
+        match prepared {
+            Ok(result) => {
+                transaction.commit()?;
+                let touched: Vec<String> = result
+                    .installed_dependencies
+                    .iter()
+                    .map(|dep| dep.spec.name.clone())
+                    .collect();
+                self.record_history(HistoryOperation::Install, previous_lock.as_ref(), &touched);
+                Ok(result)
+            }
+            Err(e) => {
+                // 安装失败：恢复 Actr.toml，丢弃暂存的缓存与锁文件
+                if let Err(rollback) = transaction.rollback() {
+                    tracing::error!("Failed to roll back the install: {rollback:#}");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Finish or undo an install an earlier run was interrupted in
+    pub fn recover_interrupted_install(&self) -> Result<()> {
+        match Transaction::recover(self.config_manager.get_project_root())? {
+            Some(Recovery::RolledBack) => tracing::warn!(
+                "Rolled back an interrupted install; Actr.toml, the proto cache and Actr.lock.toml are as before it"
+            ),
+            Some(Recovery::Completed) => {
+                tracing::warn!("Completed the commit of an interrupted install")
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Validate and resolve an install without touching Actr.toml, the cache or
+    /// the lock file (`actr install --dry-run`)
+    ///
+    /// Fails wherever the install itself would fail before writing: unavailable
+    /// or unresolvable services, unsettled conflicts and rejected signatures.
+    pub async fn plan_install(
+        &self,
+        specs: &[DependencySpec],
+        options: &InstallOptions,
+    ) -> Result<InstallPlan> {
+        use std::collections::HashSet;
+
+        // 复用ValidationPipeline
         let validation_report = self
             .validation_pipeline
             .validate_dependencies(specs)
@@ -409,35 +757,51 @@ impl InstallPipeline {
             ));
         }
 
-        // 📝 阶段2: 原子性安装
-        let backup = self.config_manager.backup_config().await?;
-
-        match self.execute_atomic_install(specs).await {
-            Ok(result) => {
-                // 安装成功，清理备份
-                self.config_manager.remove_backup(backup).await?;
-                Ok(result)
-            }
-            Err(e) => {
-                // 安装失败，恢复备份
-                self.config_manager.restore_backup(backup).await?;
-                Err(e)
+        let trust = TrustPolicy::from_config_file(
+            &self.config_manager.get_project_root().join("Actr.toml"),
+        )?;
+
+        // 1. 解析传递依赖并检查冲突
+        let mut resolution = self.validation_pipeline.resolve_transitive(specs).await?;
+        if !resolution.unresolved.is_empty() {
+            return Err(ActrCliError::Dependency {
+                message: format!(
+                    "Failed to resolve dependencies: {}",
+                    resolution
+                        .unresolved
+                        .iter()
+                        .map(|(name, error)| format!("{name}: {error}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
             }
+            .into());
         }
-    }
+        let conflicts = self
+            .validation_pipeline
+            .dependency_resolver()
+            .check_conflicts(&resolution.conflict_candidates())
+            .await?;
+        let mut plan = InstallPlan::default();
+        let mut kept_locked = self
+            .settle_conflicts(
+                &conflicts,
+                &resolution,
+                options.resolution,
+                &mut plan.warnings,
+            )
+            .await?;
+        let previous = self.load_lock_file();
 
-    /// 原子性安装执行
-    /// Note: Multiple aliases pointing to the same service will be deduplicated -
-    /// only one entry per unique service name will be installed and recorded in lock file
-    async fn execute_atomic_install(&self, specs: &[DependencySpec]) -> Result<InstallResult> {
-        use std::collections::HashSet;
+        let direct: HashSet<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+        let mut planned_services: HashSet<String> = HashSet::new();
+        let mut cached_services = Vec::new();
 
-        let mut result = InstallResult::success();
-        let mut installed_services: HashSet<String> = HashSet::new();
+        for dep in &resolution.dependencies {
+            let spec = &dep.spec;
 
-        for spec in specs {
-            // Skip if we already installed this service (by name)
-            if installed_services.contains(&spec.name) {
+            // Skip if we already planned this service (by name)
+            if planned_services.contains(&spec.name) {
                 tracing::debug!(
                     "Skipping duplicate service '{}' (alias: '{}')",
                     spec.name,
@@ -446,58 +810,588 @@ impl InstallPipeline {
                 continue;
             }
 
-            // 1. 获取服务详情（在更新配置之前，确保我们有完整的 actr_type）
-            let service_details = self
-                .validation_pipeline
-                .service_discovery
-                .get_service_details(&spec.name)
-                .await?;
+            // 2. 服务详情已在解析阶段获取（包含完整的 actr_type）
+            let service_details = resolution
+                .details
+                .remove(&spec.name)
+                .ok_or_else(|| anyhow::anyhow!("Service '{}' was not resolved", spec.name))?;
+            let kept = kept_locked.remove(&spec.name);
+
+            // 2b. 校验发布者签名（配置了 [trust] 时；保留的锁定版本已在安装时校验）
+            if kept.is_none()
+                && let Some(warning) = timed(
+                    "signature checks",
+                    self.verify_publisher(&trust, &service_details, options),
+                )
+                .await?
+            {
+                plan.warnings.push(warning);
+            }
 
-            // 2. 构建 resolved_spec，确保包含 actr_type
+            // 3. 构建 resolved_spec，确保包含 actr_type
             let mut resolved_spec = spec.clone();
             // 如果 spec 中没有 actr_type，使用从服务详情中获取的
             if resolved_spec.actr_type.is_none() {
                 resolved_spec.actr_type = Some(service_details.info.actr_type.clone());
             }
 
-            // 3. 更新配置文件（使用包含 actr_type 的 resolved_spec）
-            self.config_manager
-                .update_dependency(&resolved_spec)
+            // 4. 仅直接依赖写入 Actr.toml
+            if direct.contains(spec.name.as_str()) {
+                plan.dependencies_to_install.push(resolved_spec.clone());
+            }
+
+            // 5. 保留锁定版本时沿用已缓存的文件
+            let resolved_dep = match kept {
+                Some(kept) => {
+                    plan.kept_locked.push(spec.name.clone());
+                    ResolvedDependency {
+                        spec: resolved_spec,
+                        ..kept
+                    }
+                }
+                None => {
+                    plan.estimated_cache_size += service_details
+                        .proto_files
+                        .iter()
+                        .map(|file| file.content.len() as u64)
+                        .sum::<u64>();
+                    cached_services.push(format!("protos/remote/{}/", spec.name));
+                    ResolvedDependency {
+                        selected_tag: resolved_spec.constraint.selected_tag(&service_details.info),
+                        spec: resolved_spec,
+                        fingerprint: service_details.info.fingerprint,
+                        proto_files: service_details.proto_files,
+                        dependencies: service_details.dependencies,
+                    }
+                }
+            };
+
+            plan.lock_changes
+                .push(LockChange::between(previous.as_ref(), &resolved_dep));
+            plan.resolved_dependencies.push(resolved_dep);
+            planned_services.insert(spec.name.clone());
+        }
+
+        if !plan.dependencies_to_install.is_empty() {
+            plan.required_permissions.push("Actr.toml".to_string());
+        }
+        plan.required_permissions.extend(cached_services);
+        plan.required_permissions.push("Actr.lock.toml".to_string());
+        Ok(plan)
+    }
+
+    /// 原子性安装执行（准备阶段）
+    /// Note: Multiple aliases pointing to the same service were deduplicated by the
+    /// plan - only one entry per unique service name is installed and recorded in
+    /// lock file. Transitive dependencies are cached and locked but not added to
+    /// Actr.toml. Protos and the lock file are only staged in `transaction`.
+    async fn execute_atomic_install(
+        &self,
+        plan: InstallPlan,
+        transaction: &mut Transaction,
+    ) -> Result<InstallResult> {
+        use std::collections::HashSet;
+
+        let mut result = InstallResult::success();
+        result.warnings = plan.warnings;
+        let direct: HashSet<String> = plan
+            .dependencies_to_install
+            .iter()
+            .map(|spec| spec.name.clone())
+            .collect();
+        let progress = start_progress(&self.user_interface, "Installing dependencies").await;
+        let total = plan.resolved_dependencies.len();
+
+        for (index, dep) in plan.resolved_dependencies.into_iter().enumerate() {
+            self.validation_pipeline.budget.check()?;
+            if let Some(progress) = &progress {
+                progress.set_message(&format!("Installing {}", dep.spec.alias));
+                progress.update(index as f64 / total as f64);
+            }
+
+            // 1. 更新配置文件（仅直接依赖，使用包含 actr_type 的 resolved_spec）
+            if direct.contains(&dep.spec.name) {
+                timed(
+                    "config update",
+                    self.config_manager.update_dependency(&dep.spec),
+                )
                 .await?;
-            result.updated_config = true;
+                result.updated_config = true;
+            } else {
+                tracing::info!("Installing transitive dependency '{}'", dep.spec.name);
+            }
 
-            // 4. 缓存Proto文件
-            self.cache_manager
-                .cache_proto(&spec.name, &service_details.proto_files)
+            // 2. 暂存Proto文件（保留锁定版本时沿用已缓存的文件）
+            if !plan.kept_locked.contains(&dep.spec.name) {
+                let staging = transaction.staging_path(&dep.spec.name);
+                let target = timed(
+                    "proto cache",
+                    self.cache_manager
+                        .stage_proto(&dep.spec.name, &dep.proto_files, &staging),
+                )
                 .await?;
+                transaction.stage(target, staging)?;
+                result.cache_updates += 1;
+            }
 
-            result.cache_updates += 1;
+            // 3. 记录已安装的依赖
+            result.installed_dependencies.push(dep);
+        }
 
-            // 5. 记录已安装的依赖
+        // 4. 更新锁文件 (lock file also deduplicates by name)
+        if let Some(progress) = &progress {
+            progress.set_message("Updating Actr.lock.toml");
+            progress.update(1.0);
+        }
+        let lock_file = timed(
+            "lock file",
+            self.build_lock_file(&result.installed_dependencies),
+        )
+        .await?;
+        let staging = transaction.staging_path(LOCK_FILE_NAME);
+        lock_file.save_to_file(&staging)?;
+        transaction.stage(
+            self.config_manager.get_project_root().join(LOCK_FILE_NAME),
+            staging,
+        )?;
+        result.updated_lock_file = true;
+        if let Some(progress) = progress {
+            progress.finish();
+        }
+
+        Ok(result)
+    }
 
-            let resolved_dep = ResolvedDependency {
-                spec: resolved_spec,
-                fingerprint: service_details.info.fingerprint,
-                proto_files: service_details.proto_files,
+    /// Apply the conflict strategy to the reports of `check_conflicts`
+    ///
+    /// Returns the services to keep at their locked version, keyed by name; every
+    /// other conflicting service is installed as the registry serves it. Each
+    /// outcome is recorded as a warning.
+    async fn settle_conflicts(
+        &self,
+        conflicts: &[ConflictReport],
+        resolution: &TransitiveResolution,
+        strategy: ConflictResolution,
+        warnings: &mut Vec<String>,
+    ) -> Result<std::collections::HashMap<String, ResolvedDependency>> {
+        let mut kept = std::collections::HashMap::new();
+        if conflicts.is_empty() {
+            return Ok(kept);
+        }
+
+        let unsettled: Vec<&ConflictReport> = if strategy == ConflictResolution::Strict {
+            conflicts.iter().collect()
+        } else {
+            conflicts.iter().filter(|c| c.service.is_none()).collect()
+        };
+        if !unsettled.is_empty() {
+            let hint = if strategy == ConflictResolution::Strict {
+                " (use --resolution to choose a version)"
+            } else {
+                ""
             };
-            result.installed_dependencies.push(resolved_dep);
+            return Err(ActrCliError::Dependency {
+                message: format!(
+                    "Dependency conflicts: {}{hint}",
+                    unsettled
+                        .iter()
+                        .map(|c| format!(
+                            "{} ({} vs {})",
+                            c.description, c.dependency_a, c.dependency_b
+                        ))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ),
+            }
+            .into());
+        }
 
-            // Mark this service as installed
-            installed_services.insert(spec.name.clone());
+        let mut services: Vec<&str> = Vec::new();
+        for service in conflicts.iter().filter_map(|c| c.service.as_deref()) {
+            if !services.contains(&service) {
+                services.push(service);
+            }
         }
 
-        // 4. 更新锁文件 (lock file also deduplicates by name)
-        self.update_lock_file(&result.installed_dependencies)
+        let previous = self.load_lock_file();
+        for service in services {
+            let served = resolution
+                .details
+                .get(service)
+                .map(|details| details.info.fingerprint.clone())
+                .unwrap_or_default();
+            let locked = self.locked_version(service, previous.as_ref()).await?;
+
+            let keep_locked = match strategy {
+                ConflictResolution::Strict | ConflictResolution::PreferLatest => false,
+                ConflictResolution::PreferLocked => locked.is_some(),
+                ConflictResolution::Interactive => {
+                    let user_interface = self.user_interface.as_ref().ok_or_else(|| {
+                        ActrCliError::InvalidArgument {
+                            message: "--resolution interactive needs a terminal".to_string(),
+                        }
+                    })?;
+                    let reasons: Vec<String> = conflicts
+                        .iter()
+                        .filter(|c| c.service.as_deref() == Some(service))
+                        .map(|c| format!("{} vs {}", c.dependency_a, c.dependency_b))
+                        .collect();
+                    let mut choices = vec![format!("Install {served} from the registry")];
+                    if let Some(locked) = &locked {
+                        choices.push(format!("Keep locked {}", locked.fingerprint));
+                    }
+                    choices.push("Abort install".to_string());
+
+                    let prompt = format!(
+                        "Conflicting fingerprints for '{service}': {}",
+                        reasons.join(", ")
+                    );
+                    let choice = user_interface.select_from_list(&choices, &prompt).await?;
+                    if choice == choices.len() - 1 {
+                        return Err(ActrCliError::OperationCancelled.into());
+                    }
+                    choice == 1
+                }
+            };
+
+            match locked {
+                Some(locked) if keep_locked => {
+                    warnings.push(format!(
+                        "Conflict on '{service}': kept locked {}",
+                        locked.fingerprint
+                    ));
+                    kept.insert(service.to_string(), locked);
+                }
+                _ => warnings.push(format!(
+                    "Conflict on '{service}': installed {served} from the registry"
+                )),
+            }
+        }
+
+        Ok(kept)
+    }
+
+    /// Locked version of a service, if it is in the lock file and its protos are cached
+    async fn locked_version(
+        &self,
+        service: &str,
+        previous: Option<&LockFile>,
+    ) -> Result<Option<ResolvedDependency>> {
+        let Some(locked) =
+            previous.and_then(|lock| lock.dependencies.iter().find(|dep| dep.name == service))
+        else {
+            return Ok(None);
+        };
+        let Some(cached) = self.cache_manager.get_cached_proto(service).await? else {
+            return Ok(None);
+        };
+        Ok(Some(ResolvedDependency {
+            spec: DependencySpec {
+                alias: service.to_string(),
+                name: service.to_string(),
+                actr_type: None,
+                fingerprint: Some(locked.fingerprint.clone()),
+                constraint: Default::default(),
+            },
+            fingerprint: locked.fingerprint.clone(),
+            proto_files: cached.files,
+            dependencies: DependencyRequirement::names_from_tags(&locked.tags),
+            selected_tag: locked
+                .tags
+                .iter()
+                .find(|tag| !tag.starts_with(DEPENDS_ON_TAG_PREFIX))
+                .cloned(),
+        }))
+    }
+
+    /// 刷新已锁定的依赖：只更新缓存和锁文件，不修改 Actr.toml
+    /// The lock file and the cached protos of `deps` are restored if any step fails.
+    pub async fn refresh_dependencies(&self, deps: &[ResolvedDependency]) -> Result<InstallResult> {
+        self.recover_interrupted_install()?;
+        let lock_file_path = self
+            .config_manager
+            .get_project_root()
+            .join("Actr.lock.toml");
+        let lock_snapshot = if lock_file_path.exists() {
+            Some(std::fs::read_to_string(&lock_file_path)?)
+        } else {
+            None
+        };
+
+        let mut result = InstallResult::success();
+        // Cache entries replaced so far, with the protos they held before
+        let mut cache_snapshot: Vec<(String, Option<Vec<ProtoFile>>)> = Vec::new();
+        let refresh = async {
+            for dep in deps {
+                self.validation_pipeline.budget.check()?;
+                let previous = self.cache_manager.get_cached_proto(&dep.spec.name).await?;
+                cache_snapshot.push((dep.spec.name.clone(), previous.map(|cached| cached.files)));
+                // Drop stale files before writing the new protos
+                self.cache_manager.invalidate_cache(&dep.spec.name).await?;
+                self.cache_manager
+                    .cache_proto(&dep.spec.name, &dep.proto_files)
+                    .await?;
+                result.cache_updates += 1;
+            }
+            self.update_lock_file(deps).await
+        };
+
+        match refresh.await {
+            Ok(()) => {
+                result.updated_lock_file = true;
+                result.installed_dependencies = deps.to_vec();
+                Ok(result)
+            }
+            Err(e) => {
+                match lock_snapshot {
+                    Some(contents) => std::fs::write(&lock_file_path, contents)?,
+                    None if lock_file_path.exists() => std::fs::remove_file(&lock_file_path)?,
+                    None => {}
+                }
+                self.restore_cache(cache_snapshot).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Put back the cached protos replaced by a failed refresh
+    ///
+    /// Failures are only logged so the refresh error is the one reported.
+    async fn restore_cache(&self, snapshot: Vec<(String, Option<Vec<ProtoFile>>)>) {
+        for (service_name, files) in snapshot.into_iter().rev() {
+            let restored = async {
+                self.cache_manager.invalidate_cache(&service_name).await?;
+                if let Some(files) = files {
+                    self.cache_manager
+                        .cache_proto(&service_name, &files)
+                        .await?;
+                }
+                Ok::<_, anyhow::Error>(())
+            };
+            if let Err(e) = restored.await {
+                tracing::warn!("Failed to restore cached protos for '{service_name}': {e:#}");
+            }
+        }
+    }
+
+    /// 卸载流程：配置与锁文件原子更新，失败时回滚
+    ///
+    /// Cached protos are purged only after both files are committed, since
+    /// deleted cache directories cannot be restored on rollback.
+    pub async fn uninstall_dependencies(
+        &self,
+        specs: &[DependencySpec],
+    ) -> Result<UninstallResult> {
+        self.recover_interrupted_install()?;
+        let lock_file_path = self
+            .config_manager
+            .get_project_root()
+            .join("Actr.lock.toml");
+        let lock_snapshot = if lock_file_path.exists() {
+            Some(std::fs::read_to_string(&lock_file_path)?)
+        } else {
+            None
+        };
+        let backup = self.config_manager.backup_config().await?;
+        let previous_lock = self.load_lock_file();
+
+        let mut result = match self.execute_atomic_uninstall(specs).await {
+            Ok(result) => {
+                self.config_manager.remove_backup(backup).await?;
+                let touched: Vec<String> = result
+                    .removed_dependencies
+                    .iter()
+                    .map(|spec| spec.name.clone())
+                    .collect();
+                self.record_history(HistoryOperation::Remove, previous_lock.as_ref(), &touched);
+                result
+            }
+            Err(e) => {
+                self.config_manager.restore_backup(backup).await?;
+                if let Some(contents) = lock_snapshot {
+                    std::fs::write(&lock_file_path, contents)?;
+                }
+                return Err(e);
+            }
+        };
+
+        for service_name in &result.purged_services {
+            if let Err(e) = self.cache_manager.invalidate_cache(service_name).await {
+                result.warnings.push(format!(
+                    "Failed to purge cached protos for '{}': {}",
+                    service_name, e
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// 原子性卸载执行
+    /// Note: a service stays in the lock file and cache while another alias still references it
+    async fn execute_atomic_uninstall(&self, specs: &[DependencySpec]) -> Result<UninstallResult> {
+        use std::collections::HashSet;
+
+        let project_root = self.config_manager.get_project_root();
+        let config = self
+            .config_manager
+            .load_config(project_root.join("Actr.toml").as_path())
             .await?;
-        result.updated_lock_file = true;
 
+        let removed_aliases: HashSet<&str> = specs.iter().map(|s| s.alias.as_str()).collect();
+        let still_referenced: HashSet<&str> = config
+            .dependencies
+            .iter()
+            .filter(|d| !removed_aliases.contains(d.alias.as_str()))
+            .map(|d| d.name.as_str())
+            .collect();
+
+        let mut result = UninstallResult {
+            removed_dependencies: Vec::new(),
+            purged_services: Vec::new(),
+            updated_lock_file: false,
+            warnings: Vec::new(),
+        };
+
+        // 1. 更新配置文件
+        for spec in specs {
+            if !self.config_manager.remove_dependency(&spec.alias).await? {
+                return Err(anyhow::anyhow!(
+                    "Dependency '{}' not found in Actr.toml",
+                    spec.alias
+                ));
+            }
+            result.removed_dependencies.push(spec.clone());
+
+            if !still_referenced.contains(spec.name.as_str())
+                && !result.purged_services.contains(&spec.name)
+            {
+                result.purged_services.push(spec.name.clone());
+            }
+        }
+
+        // 2. 更新锁文件（写入临时文件后重命名）
+        let lock_file_path = project_root.join("Actr.lock.toml");
+        if lock_file_path.exists() {
+            let mut lock_file = LockFile::from_file(&lock_file_path)?;
+
+            // Transitive dependencies only the removed services pulled in go too
+            let kept = locked_closure(&lock_file, still_referenced.iter().copied());
+            let removed = locked_closure(
+                &lock_file,
+                result.purged_services.iter().map(String::as_str),
+            );
+            for name in removed {
+                if !kept.contains(&name) && !result.purged_services.contains(&name) {
+                    result.purged_services.push(name);
+                }
+            }
+
+            lock_file
+                .dependencies
+                .retain(|d| !result.purged_services.contains(&d.name));
+            lock_file.update_timestamp();
+
+            let tmp_path = lock_file_path.with_extension("toml.tmp");
+            lock_file.save_to_file(&tmp_path)?;
+            std::fs::rename(&tmp_path, &lock_file_path)?;
+            result.updated_lock_file = true;
+        }
+
+        tracing::info!(
+            "Removed {} dependencies from config",
+            result.removed_dependencies.len()
+        );
         Ok(result)
     }
 
+    /// Check the publisher signature of a fetched service against `[trust]`
+    ///
+    /// Returns a warning when `--allow-unsigned` let an unverified service through.
+    /// Used by install while planning and by `actr update` before refreshing.
+    pub async fn verify_publisher(
+        &self,
+        trust: &TrustPolicy,
+        details: &ServiceDetails,
+        options: &InstallOptions,
+    ) -> Result<Option<String>> {
+        let name = &details.info.name;
+        let publisher = &details.info.actr_type.manufacturer;
+        let problem = match trust.key_for(publisher) {
+            Some(key) => match self
+                .validation_pipeline
+                .fingerprint_validator()
+                .verify_signature(&details.info, &details.proto_files, key)
+                .await?
+            {
+                SignatureStatus::Valid => {
+                    tracing::info!("Verified signature of '{}' by '{}'", name, publisher);
+                    return Ok(None);
+                }
+                SignatureStatus::Unsigned => format!("not signed, but '{publisher}' is trusted"),
+                SignatureStatus::Invalid { reason } => reason,
+            },
+            None if trust.require_signatures => {
+                format!("no trusted key for publisher '{publisher}' in [trust.publishers]")
+            }
+            None => return Ok(None),
+        };
+
+        if options.allow_unsigned {
+            Ok(Some(format!(
+                "Installed '{name}' without a valid signature: {problem}"
+            )))
+        } else {
+            Err(ActrCliError::SignatureVerification {
+                service: name.clone(),
+                reason: problem,
+            }
+            .into())
+        }
+    }
+
+    /// Current Actr.lock.toml, if present and readable
+    pub fn load_lock_file(&self) -> Option<LockFile> {
+        let lock_file_path = self
+            .config_manager
+            .get_project_root()
+            .join("Actr.lock.toml");
+        lock_file_path
+            .exists()
+            .then(|| LockFile::from_file(&lock_file_path).ok())
+            .flatten()
+    }
+
+    /// Append the lock file transition to `.actr/history.jsonl`
+    ///
+    /// Best effort: the operation already succeeded, so a failed write is only logged.
+    pub fn record_history(
+        &self,
+        operation: HistoryOperation,
+        previous: Option<&LockFile>,
+        touched: &[String],
+    ) {
+        let history = History::for_project(self.config_manager.get_project_root());
+        let current = self.load_lock_file();
+        if let Err(e) = history.record(operation, previous, current.as_ref(), touched) {
+            tracing::warn!("Failed to write {}: {:#}", history.path().display(), e);
+        }
+    }
+
     /// Update lock file with new format (no embedded proto content)
     async fn update_lock_file(&self, dependencies: &[ResolvedDependency]) -> Result<()> {
+        let lock_file_path = self.config_manager.get_project_root().join(LOCK_FILE_NAME);
+        self.build_lock_file(dependencies)
+            .await?
+            .save_to_file(&lock_file_path)?;
+        tracing::info!("Updated lock file: {} dependencies", dependencies.len());
+        Ok(())
+    }
+
+    /// Current lock file with `dependencies` added or replaced
+    async fn build_lock_file(&self, dependencies: &[ResolvedDependency]) -> Result<LockFile> {
         let project_root = self.config_manager.get_project_root();
-        let lock_file_path = project_root.join("Actr.lock.toml");
+        let lock_file_path = project_root.join(LOCK_FILE_NAME);
 
         // Load existing lock file or create new one
         let mut lock_file = if lock_file_path.exists() {
@@ -510,25 +1404,55 @@ impl InstallPipeline {
         for dep in dependencies {
             let service_name = dep.spec.name.clone();
 
-            // Create protobuf entries with relative path (no content)
-            let protobufs: Vec<ProtoFileMeta> = dep
-                .proto_files
+            // Create protobuf entries with relative path and semantic fingerprint (no content)
+            let mut protobufs: Vec<ProtoFileMeta> = Vec::with_capacity(dep.proto_files.len());
+            for pf in &dep.proto_files {
+                let file_name = if pf.name.ends_with(".proto") {
+                    pf.name.clone()
+                } else {
+                    format!("{}.proto", pf.name)
+                };
+                // Path relative to proto/remote/ (e.g., "service_name/file.proto")
+                let path = format!("{}/{}", service_name, file_name);
+                let fingerprint = self
+                    .validation_pipeline
+                    .fingerprint_validator()
+                    .compute_proto_fingerprint(pf)
+                    .await?;
+
+                protobufs.push(ProtoFileMeta {
+                    path,
+                    fingerprint: fingerprint.value,
+                });
+            }
+
+            // Record the resolution tree: one `depends-on:` tag per edge, pinned to
+            // the fingerprint locked for the target
+            let mut tags: Vec<String> = dep
+                .dependencies
                 .iter()
-                .map(|pf| {
-                    let file_name = if pf.name.ends_with(".proto") {
-                        pf.name.clone()
-                    } else {
-                        format!("{}.proto", pf.name)
-                    };
-                    // Path relative to proto/remote/ (e.g., "service_name/file.proto")
-                    let path = format!("{}/{}", service_name, file_name);
-
-                    ProtoFileMeta {
-                        path,
-                        fingerprint: String::new(), // TODO: compute semantic fingerprint
+                .map(|name| {
+                    let fingerprint = dependencies
+                        .iter()
+                        .find(|d| &d.spec.name == name)
+                        .map(|d| d.fingerprint.clone())
+                        .or_else(|| {
+                            lock_file
+                                .dependencies
+                                .iter()
+                                .find(|locked| &locked.name == name)
+                                .map(|locked| locked.fingerprint.clone())
+                        })
+                        .filter(|fingerprint| !fingerprint.is_empty());
+                    DependencyRequirement {
+                        name: name.clone(),
+                        fingerprint,
                     }
+                    .to_tag()
                 })
                 .collect();
+            // The tag the service was selected by under a `tag`/`version` constraint
+            tags.extend(dep.selected_tag.clone());
 
             // Create service spec metadata
             let spec = ServiceSpecMeta {
@@ -537,7 +1461,7 @@ impl InstallPipeline {
                 fingerprint: dep.fingerprint.clone(),
                 protobufs,
                 published_at: None,
-                tags: Vec::new(),
+                tags,
             };
 
             // Create locked dependency
@@ -548,13 +1472,27 @@ impl InstallPipeline {
             lock_file.add_dependency(locked_dep);
         }
 
-        // Update timestamp and save
         lock_file.update_timestamp();
-        lock_file.save_to_file(&lock_file_path)?;
+        Ok(lock_file)
+    }
+}
 
-        tracing::info!("Updated lock file: {} dependencies", dependencies.len());
-        Ok(())
+/// Services reachable from `roots` through the `depends-on:` tags of the lock file
+fn locked_closure<'a>(
+    lock_file: &LockFile,
+    roots: impl IntoIterator<Item = &'a str>,
+) -> std::collections::HashSet<String> {
+    let mut reached: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut pending: Vec<String> = roots.into_iter().map(str::to_string).collect();
+    while let Some(name) = pending.pop() {
+        if !reached.insert(name.clone()) {
+            continue;
+        }
+        if let Some(locked) = lock_file.dependencies.iter().find(|dep| dep.name == name) {
+            pending.extend(DependencyRequirement::names_from_tags(&locked.tags));
+        }
     }
+    reached
 }
 
 // ============================================================================
@@ -568,6 +1506,8 @@ pub struct GenerationPipeline {
     proto_processor: Arc<dyn ProtoProcessor>,
     #[allow(dead_code)]
     cache_manager: Arc<dyn CacheManager>,
+    user_interface: Option<Arc<dyn UserInterface>>,
+    budget: ExecutionBudget,
 }
 
 impl GenerationPipeline {
@@ -580,9 +1520,23 @@ impl GenerationPipeline {
             config_manager,
             proto_processor,
             cache_manager,
+            user_interface: None,
+            budget: ExecutionBudget::unlimited(),
         }
     }
 
+    /// Report per-proto progress through the given UI
+    pub fn with_user_interface(mut self, user_interface: Arc<dyn UserInterface>) -> Self {
+        self.user_interface = Some(user_interface);
+        self
+    }
+
+    /// Abort protoc and formatters when the command times out or is interrupted
+    pub fn with_budget(mut self, budget: ExecutionBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
     /// 执行代码生成
     pub async fn generate_code(&self, options: &GenerationOptions) -> Result<GenerationResult> {
         // 1. 清理输出目录（如果需要）
@@ -591,41 +1545,61 @@ impl GenerationPipeline {
         }
 
         // 2. 发现本地Proto文件
-        let local_protos = self
-            .proto_processor
-            .discover_proto_files(&options.input_path)
-            .await?;
+        let local_protos = timed(
+            "proto discovery",
+            self.proto_processor
+                .discover_proto_files(&options.input_path),
+        )
+        .await?;
 
         // 3. 加载依赖的Proto文件
         let dependency_protos = self.load_dependency_protos().await?;
 
         // 4. 验证Proto语法
         let all_protos = [local_protos, dependency_protos].concat();
-        let validation = self
-            .proto_processor
-            .validate_proto_syntax(&all_protos)
-            .await?;
+        let validation = timed(
+            "proto validation",
+            self.proto_processor.validate_proto_syntax(&all_protos),
+        )
+        .await?;
 
         if !validation.is_valid {
             return Err(anyhow::anyhow!("Proto file syntax validation failed"));
         }
 
         // 5. 执行代码生成
+        let progress = start_progress(
+            &self.user_interface,
+            &format!("Generating code for {} proto files", all_protos.len()),
+        )
+        .await;
         let mut generation_result = self
-            .proto_processor
-            .generate_code(&options.input_path, &options.output_path)
-            .await?;
+            .budget
+            .run(timed(
+                "protoc",
+                self.proto_processor
+                    .generate_code(&options.input_path, &options.output_path),
+            ))
+            .await??;
 
         // 6. 后处理：格式化和检查
         if options.format_code {
-            self.format_generated_code(&generation_result.generated_files)
-                .await?;
+            timed(
+                "format",
+                self.format_generated_code(&generation_result.generated_files, progress.as_deref()),
+            )
+            .await?;
+        }
+        if let Some(progress) = progress {
+            progress.finish();
         }
 
         if options.run_checks {
-            let check_result = self
-                .run_code_checks(&generation_result.generated_files)
-                .await?;
+            let check_result = timed(
+                "code checks",
+                self.run_code_checks(&generation_result.generated_files),
+            )
+            .await?;
             generation_result.warnings.extend(check_result.warnings);
             generation_result.errors.extend(check_result.errors);
         }
@@ -649,11 +1623,27 @@ impl GenerationPipeline {
     }
 
     /// 格式化生成的代码
-    async fn format_generated_code(&self, files: &[std::path::PathBuf]) -> Result<()> {
-        for file in files {
+    async fn format_generated_code(
+        &self,
+        files: &[std::path::PathBuf],
+        progress: Option<&dyn ProgressBar>,
+    ) -> Result<()> {
+        for (index, file) in files.iter().enumerate() {
+            if let Some(progress) = progress {
+                progress.set_message(&format!("Formatting {}", file.display()));
+                progress.update(index as f64 / files.len() as f64);
+            }
             if file.extension().and_then(|s| s.to_str()) == Some("rs") {
-                // 运行 rustfmt
-                let output = std::process::Command::new("rustfmt").arg(file).output()?;
+                // 运行 rustfmt (killed if the budget runs out)
+                let output = self
+                    .budget
+                    .run(
+                        tokio::process::Command::new("rustfmt")
+                            .arg(file)
+                            .kill_on_drop(true)
+                            .output(),
+                    )
+                    .await??;
 
                 if !output.status.success() {
                     eprintln!(
@@ -676,3 +1666,14 @@ impl GenerationPipeline {
         })
     }
 }
+
+/// Start a progress bar if a UI is attached; progress is best-effort and never fails the operation
+async fn start_progress(
+    user_interface: &Option<Arc<dyn UserInterface>>,
+    message: &str,
+) -> Option<Box<dyn ProgressBar>> {
+    match user_interface {
+        Some(ui) => ui.show_progress(message).await.ok(),
+        None => None,
+    }
+}
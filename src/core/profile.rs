@@ -0,0 +1,194 @@
+//! Environment profiles: `Actr.<profile>.toml` overlays
+//!
+//! A profile is selected with the global `--env <profile>` flag or the `ACTR_ENV`
+//! environment variable. Its overlay lives next to the base config and is merged
+//! over it table by table, so an overlay only needs the keys that differ:
+//!
+//! ```toml
+//! # Actr.prod.toml
+//! [system.signaling]
+//! url = "wss://signaling.example.com"
+//!
+//! [system.deployment]
+//! realm_id = 3001
+//! ```
+//!
+//! Scalars and arrays in the overlay replace the base value. `${VAR}` references
+//! are expanded afterwards (see [`super::interpolate`]). Commands that edit the
+//! config (`install`, `remove`, `config set`) still write the base file.
+
+use actr_config::{Config, ConfigParser};
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use super::interpolate;
+
+/// Environment variable selecting a profile when `--env` is not given
+pub const PROFILE_ENV_VAR: &str = "ACTR_ENV";
+
+static ACTIVE_PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Select the profile for the whole process (`--env`); `None` falls back to `ACTR_ENV`
+pub fn set_active_profile(profile: Option<String>) {
+    *ACTIVE_PROFILE.write().unwrap() = profile;
+}
+
+/// Profile selected by `--env` or `ACTR_ENV`
+pub fn active_profile() -> Option<String> {
+    ACTIVE_PROFILE
+        .read()
+        .unwrap()
+        .clone()
+        .or_else(|| std::env::var(PROFILE_ENV_VAR).ok())
+        .filter(|profile| !profile.trim().is_empty())
+}
+
+/// Overlay file of a profile: `Actr.toml` → `Actr.<profile>.toml`
+pub fn overlay_path(config_path: &Path, profile: &str) -> PathBuf {
+    let stem = config_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Actr".to_string());
+    config_path.with_file_name(format!("{stem}.{profile}.toml"))
+}
+
+/// Parse a config with the active profile merged over it
+pub fn parse_config(path: &Path) -> Result<Config> {
+    parse_config_with(path, active_profile().as_deref())
+}
+
+/// Parse a config with the overlay of `profile` merged over it
+pub fn parse_config_with(path: &Path, profile: Option<&str>) -> Result<Config> {
+    let (value, rewritten) = load(path, profile)?;
+    if !rewritten {
+        return ConfigParser::from_file(path)
+            .with_context(|| format!("Failed to parse config: {}", path.display()));
+    }
+
+    let merged = toml::to_string(&value)?;
+    // Parse from the config's directory so relative paths resolve as in the base file
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let mut merged_file = tempfile::Builder::new()
+        .prefix(".actr-profile-")
+        .suffix(".toml")
+        .tempfile_in(dir)
+        .with_context(|| format!("Failed to create a temporary config in {}", dir.display()))?;
+    std::io::Write::write_all(&mut merged_file, merged.as_bytes())?;
+    ConfigParser::from_file(merged_file.path()).with_context(|| match profile {
+        Some(profile) => format!(
+            "Failed to parse config: {} with profile '{profile}'",
+            path.display()
+        ),
+        None => format!("Failed to parse config: {}", path.display()),
+    })
+}
+
+/// Raw TOML of a config with the active profile merged over it
+///
+/// Used by the modules that read their own tables (`[system.network]`, `[trust]`, ...).
+pub fn load_value(path: &Path) -> Result<toml::Value> {
+    load_value_with(path, active_profile().as_deref())
+}
+
+/// Raw TOML of a config with the overlay of `profile` merged over it
+pub fn load_value_with(path: &Path, profile: Option<&str>) -> Result<toml::Value> {
+    load(path, profile).map(|(value, _)| value)
+}
+
+/// Merged and interpolated TOML, and whether it differs from the base file
+fn load(path: &Path, profile: Option<&str>) -> Result<(toml::Value, bool)> {
+    let mut value = read_toml(path)?;
+    if let Some(profile) = profile {
+        let overlay = overlay_path(path, profile);
+        if !overlay.exists() {
+            bail!(
+                "Profile '{profile}' is selected but {} does not exist",
+                overlay.display()
+            );
+        }
+        merge(&mut value, read_toml(&overlay)?);
+    }
+    let expanded = interpolate::expand_value(&mut value, path)?;
+    Ok((value, profile.is_some() || expanded))
+}
+
+fn read_toml(path: &Path) -> Result<toml::Value> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Merge `overlay` into `base`: tables recursively, everything else replaced
+pub fn merge(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_overlay() {
+        let mut base: toml::Value = toml::from_str(
+            "[package]\nname = \"app\"\n\n[system.signaling]\nurl = \"ws://localhost:8081\"\n\n[system.deployment]\nrealm_id = 1\n",
+        )
+        .unwrap();
+        let overlay: toml::Value =
+            toml::from_str("[system.signaling]\nurl = \"wss://prod.example.com\"\n").unwrap();
+        merge(&mut base, overlay);
+
+        assert_eq!(base["package"]["name"].as_str(), Some("app"));
+        assert_eq!(
+            base["system"]["signaling"]["url"].as_str(),
+            Some("wss://prod.example.com")
+        );
+        assert_eq!(
+            base["system"]["deployment"]["realm_id"].as_integer(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_load_value_with_profile() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        std::fs::write(&path, "[system.deployment]\nrealm_id = 1\n").unwrap();
+        std::fs::write(
+            dir.path().join("Actr.staging.toml"),
+            "[system.deployment]\nrealm_id = 2\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            overlay_path(&path, "staging"),
+            dir.path().join("Actr.staging.toml")
+        );
+        let value = load_value_with(&path, None).unwrap();
+        assert_eq!(
+            value["system"]["deployment"]["realm_id"].as_integer(),
+            Some(1)
+        );
+        let value = load_value_with(&path, Some("staging")).unwrap();
+        assert_eq!(
+            value["system"]["deployment"]["realm_id"].as_integer(),
+            Some(2)
+        );
+        assert!(load_value_with(&path, Some("prod")).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Retry policy for network calls
+//!
+//! Signaling connections and connectivity probes are retried with exponential
+//! backoff and jitter. The policy is read from the `[system.discovery]` table of
+//! Actr.toml and can be overridden by the global `--retries`,
+//! `--retry-base-delay` and `--retry-max-delay` flags.
+
+use anyhow::{Context, Result};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use std::time::Duration;
+
+use super::profile;
+
+/// Exponential backoff policy with jitter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub attempts: u32,
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound for any single delay
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that runs the operation exactly once
+    pub fn none() -> Self {
+        Self {
+            attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Load `[system.discovery]` from an Actr.toml, falling back to the defaults
+    ///
+    /// ```toml
+    /// [system.discovery]
+    /// attempts = 5
+    /// base_delay_ms = 250
+    /// max_delay_ms = 10000
+    /// ```
+    pub fn from_config_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let value = profile::load_value(path)?;
+
+        let mut policy = Self::default();
+        let Some(table) = value
+            .get("system")
+            .and_then(|system| system.get("discovery"))
+        else {
+            return Ok(policy);
+        };
+
+        let read = |key: &str| -> Result<Option<u64>> {
+            match table.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .as_integer()
+                    .and_then(|n| u64::try_from(n).ok())
+                    .map(Some)
+                    .with_context(|| {
+                        format!("system.discovery.{key} must be a non-negative integer")
+                    }),
+            }
+        };
+
+        if let Some(attempts) = read("attempts")? {
+            policy.attempts = u32::try_from(attempts).unwrap_or(u32::MAX);
+        }
+        if let Some(ms) = read("base_delay_ms")? {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        if let Some(ms) = read("max_delay_ms")? {
+            policy.max_delay = Duration::from_millis(ms);
+        }
+        Ok(policy.normalized())
+    }
+
+    /// Apply CLI overrides on top of the configured policy
+    pub fn with_overrides(
+        mut self,
+        attempts: Option<u32>,
+        base_delay_ms: Option<u64>,
+        max_delay_ms: Option<u64>,
+    ) -> Self {
+        if let Some(attempts) = attempts {
+            self.attempts = attempts;
+        }
+        if let Some(ms) = base_delay_ms {
+            self.base_delay = Duration::from_millis(ms);
+        }
+        if let Some(ms) = max_delay_ms {
+            self.max_delay = Duration::from_millis(ms);
+        }
+        self.normalized()
+    }
+
+    fn normalized(mut self) -> Self {
+        self.attempts = self.attempts.max(1);
+        self.max_delay = self.max_delay.max(self.base_delay);
+        self
+    }
+
+    /// Backoff ceiling before retry number `retry` (1-based): `base * 2^(retry-1)`, capped
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Delay before retry number `retry`, with "equal jitter" (half fixed, half random)
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let ceiling = self.backoff(retry);
+        let half = ceiling / 2;
+        let spread = (ceiling - half).as_millis() as u64;
+        let jitter = if spread == 0 {
+            0
+        } else {
+            random_u64() % (spread + 1)
+        };
+        half + Duration::from_millis(jitter)
+    }
+
+    /// Run `operation` until it succeeds or the attempts are exhausted
+    ///
+    /// Every failed attempt is traced; the error of the last attempt is returned.
+    pub async fn run<T, F, Fut>(&self, operation: &str, mut f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            tracing::debug!("{operation}: attempt {attempt}/{}", self.attempts);
+            match f().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt >= self.attempts => {
+                    tracing::warn!(
+                        "{operation}: attempt {attempt}/{} failed, giving up: {e}",
+                        self.attempts
+                    );
+                    return Err(e);
+                }
+                Err(e) => {
+                    let delay = self.delay_for(attempt);
+                    tracing::warn!(
+                        "{operation}: attempt {attempt}/{} failed: {e}; retrying in {}ms",
+                        self.attempts,
+                        delay.as_millis()
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Cheap per-call randomness for jitter (std's `RandomState` is randomly seeded)
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(64), Duration::from_millis(1000));
+
+        for retry in 1..8 {
+            let delay = policy.delay_for(retry);
+            assert!(delay >= policy.backoff(retry) / 2);
+            assert!(delay <= policy.backoff(retry));
+        }
+    }
+
+    #[test]
+    fn test_from_config_file_and_overrides() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Actr.toml");
+        std::fs::write(
+            &path,
+            "[system.discovery]\nattempts = 5\nbase_delay_ms = 50\nmax_delay_ms = 400\n",
+        )
+        .unwrap();
+
+        let policy = RetryPolicy::from_config_file(&path).unwrap();
+        assert_eq!(policy.attempts, 5);
+        assert_eq!(policy.base_delay, Duration::from_millis(50));
+        assert_eq!(policy.max_delay, Duration::from_millis(400));
+
+        let policy = policy.with_overrides(Some(0), None, Some(10));
+        assert_eq!(policy.attempts, 1);
+        assert_eq!(policy.max_delay, Duration::from_millis(50));
+
+        std::fs::write(&path, "[system.discovery]\nattempts = -1\n").unwrap();
+        assert!(RetryPolicy::from_config_file(&path).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_until_success() {
+        let policy = RetryPolicy {
+            attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        };
+        let calls = AtomicU32::new(0);
+        let result = policy
+            .run("test", || async {
+                if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                    anyhow::bail!("transient")
+                }
+                Ok(42)
+            })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,161 @@
+//! Stage timings for `--timings`
+//!
+//! Pipeline stages run inside a `stage` tracing span (visible with
+//! `--log-level`), are reported on the `--event-stream` and, when timings are enabled, add their wall time to a
+//! process-wide table. Concurrent stages, such as the per-service network
+//! checks, each count in full, so a total can exceed the command's run time.
+
+use super::events::{self, Event};
+use comfy_table::{Cell, CellAlignment, Table};
+use std::future::Future;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STAGES: Mutex<Vec<StageTiming>> = Mutex::new(Vec::new());
+
+/// Accumulated time of one stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageTiming {
+    pub stage: &'static str,
+    pub calls: u32,
+    pub total: Duration,
+    pub slowest: Duration,
+}
+
+/// Enable or disable timing collection for the whole process
+pub fn set_timings(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether `--timings` is active
+pub fn timings_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Run `future` as `stage`
+pub async fn timed<F: Future>(stage: &'static str, future: F) -> F::Output {
+    emit_started(stage);
+    let started = Instant::now();
+    let output = future
+        .instrument(tracing::info_span!("stage", name = stage))
+        .await;
+    record(stage, started.elapsed());
+    output
+}
+
+/// Run the blocking `f` as `stage`
+pub fn timed_blocking<T>(stage: &'static str, f: impl FnOnce() -> T) -> T {
+    emit_started(stage);
+    let started = Instant::now();
+    let output = tracing::info_span!("stage", name = stage).in_scope(f);
+    record(stage, started.elapsed());
+    output
+}
+
+fn emit_started(stage: &'static str) {
+    events::emit(Event::StageStarted {
+        stage: stage.to_string(),
+    });
+}
+
+fn record(stage: &'static str, elapsed: Duration) {
+    tracing::debug!(
+        stage,
+        elapsed_ms = elapsed.as_millis() as u64,
+        "Stage finished"
+    );
+    events::emit(Event::StageFinished {
+        stage: stage.to_string(),
+        elapsed_ms: elapsed.as_millis() as u64,
+    });
+    if !timings_enabled() {
+        return;
+    }
+    let mut stages = STAGES.lock().unwrap_or_else(|e| e.into_inner());
+    match stages.iter_mut().find(|timing| timing.stage == stage) {
+        Some(timing) => {
+            timing.calls += 1;
+            timing.total += elapsed;
+            timing.slowest = timing.slowest.max(elapsed);
+        }
+        None => stages.push(StageTiming {
+            stage,
+            calls: 1,
+            total: elapsed,
+            slowest: elapsed,
+        }),
+    }
+}
+
+/// Recorded stages in the order they first ran
+pub fn stage_timings() -> Vec<StageTiming> {
+    STAGES.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Breakdown table of the recorded stages, `None` when nothing was timed
+pub fn render_timings(stages: &[StageTiming]) -> Option<Table> {
+    if stages.is_empty() {
+        return None;
+    }
+    let mut table = Table::new();
+    table.set_header(vec!["Stage", "Calls", "Total", "Slowest"]);
+    for timing in stages {
+        table.add_row(vec![
+            Cell::new(timing.stage),
+            Cell::new(timing.calls).set_alignment(CellAlignment::Right),
+            Cell::new(format_duration(timing.total)).set_alignment(CellAlignment::Right),
+            Cell::new(format_duration(timing.slowest)).set_alignment(CellAlignment::Right),
+        ]);
+    }
+    Some(table)
+}
+
+/// Print the breakdown on stderr when `--timings` is active
+pub fn print_timings() {
+    if !timings_enabled() {
+        return;
+    }
+    match render_timings(&stage_timings()) {
+        Some(table) => eprintln!("\n⏱️  Timings\n{table}"),
+        None => eprintln!("\n⏱️  No pipeline stages ran"),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_timed_stages_accumulate() {
+        set_timings(true);
+        let value = timed("test stage", async { 7 }).await;
+        assert_eq!(value, 7);
+        timed_blocking("test stage", || {
+            std::thread::sleep(Duration::from_millis(2))
+        });
+
+        let stages = stage_timings();
+        let timing = stages
+            .iter()
+            .find(|timing| timing.stage == "test stage")
+            .unwrap();
+        assert_eq!(timing.calls, 2);
+        assert!(timing.slowest >= Duration::from_millis(2));
+        assert!(timing.total >= timing.slowest);
+
+        let table = render_timings(&stages).unwrap().to_string();
+        assert!(table.contains("test stage"));
+        assert!(render_timings(&[]).is_none());
+    }
+}
@@ -0,0 +1,213 @@
+//! Publisher trust for signed proto bundles
+//!
+//! Publishers may sign the semantic fingerprint of a service with an ed25519 key
+//! (`actr publish --signing-key`). The signature travels as a service tag
+//! `signature:ed25519:<base64>`, and the public keys a project trusts are listed
+//! per manufacturer in the `[trust]` table of Actr.toml:
+//!
+//! ```toml
+//! [trust]
+//! require_signatures = false
+//!
+//! [trust.publishers]
+//! acme = "ed25519:M2D7kG1J7p2...="
+//! ```
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::pkcs8::DecodePrivateKey;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::profile;
+
+/// Tag prefix carrying the publisher signature
+pub const SIGNATURE_TAG_PREFIX: &str = "signature:ed25519:";
+
+/// Prefix of public keys in `[trust.publishers]`
+const KEY_PREFIX: &str = "ed25519:";
+
+/// Domain separator so a signature cannot be replayed for another purpose
+const PAYLOAD_CONTEXT: &str = "actr-service-signature/v1";
+
+/// Trusted publisher keys loaded from Actr.toml
+#[derive(Debug, Clone, Default)]
+pub struct TrustPolicy {
+    publishers: BTreeMap<String, VerifyingKey>,
+    /// Refuse dependencies whose publisher has no trusted key
+    pub require_signatures: bool,
+}
+
+impl TrustPolicy {
+    /// Load `[trust]` from an Actr.toml; a missing file or table trusts nobody
+    pub fn from_config_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let value = profile::load_value(path)?;
+        let Some(table) = value.get("trust") else {
+            return Ok(Self::default());
+        };
+
+        let require_signatures = match table.get("require_signatures") {
+            None => false,
+            Some(value) => value
+                .as_bool()
+                .context("trust.require_signatures must be a boolean")?,
+        };
+
+        let mut publishers = BTreeMap::new();
+        if let Some(keys) = table.get("publishers") {
+            let keys = keys
+                .as_table()
+                .context("trust.publishers must be a table of manufacturer = \"ed25519:<key>\"")?;
+            for (publisher, key) in keys {
+                let key = key
+                    .as_str()
+                    .with_context(|| format!("trust.publishers.{publisher} must be a string"))?;
+                let key = parse_public_key(key)
+                    .with_context(|| format!("Invalid key for trust.publishers.{publisher}"))?;
+                publishers.insert(publisher.clone(), key);
+            }
+        }
+
+        Ok(Self {
+            publishers,
+            require_signatures,
+        })
+    }
+
+    /// Key trusted for a manufacturer
+    pub fn key_for(&self, publisher: &str) -> Option<&VerifyingKey> {
+        self.publishers.get(publisher)
+    }
+}
+
+/// Bytes covered by the signature
+pub fn signed_payload(service_name: &str, fingerprint: &str) -> Vec<u8> {
+    format!("{PAYLOAD_CONTEXT}\n{service_name}\n{fingerprint}").into_bytes()
+}
+
+/// Signature tag of a service, if it was published signed
+pub fn signature_from_tags(tags: &[String]) -> Option<&str> {
+    tags.iter()
+        .find_map(|tag| tag.strip_prefix(SIGNATURE_TAG_PREFIX))
+}
+
+/// Parse `ed25519:<base64>` (the prefix is optional)
+pub fn parse_public_key(value: &str) -> Result<VerifyingKey> {
+    let encoded = value.strip_prefix(KEY_PREFIX).unwrap_or(value);
+    let bytes: [u8; 32] = STANDARD
+        .decode(encoded.trim())
+        .context("public key is not valid base64")?
+        .try_into()
+        .map_err(|_| anyhow!("ed25519 public keys are 32 bytes"))?;
+    VerifyingKey::from_bytes(&bytes).context("invalid ed25519 public key")
+}
+
+/// Public key in the format used by `[trust.publishers]`
+pub fn encode_public_key(key: &VerifyingKey) -> String {
+    format!("{KEY_PREFIX}{}", STANDARD.encode(key.as_bytes()))
+}
+
+/// Parse a signing key: a PKCS#8 PEM file (`openssl genpkey -algorithm ed25519`)
+/// or the base64 encoded 32-byte seed
+pub fn parse_signing_key(content: &str) -> Result<SigningKey> {
+    let content = content.trim();
+    if content.starts_with("-----BEGIN") {
+        return SigningKey::from_pkcs8_pem(content)
+            .map_err(|e| anyhow!("invalid PKCS#8 ed25519 key: {e}"));
+    }
+    let seed: [u8; 32] = STANDARD
+        .decode(content)
+        .context("signing key is neither PEM nor base64")?
+        .try_into()
+        .map_err(|_| anyhow!("ed25519 seeds are 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Signature tag for a service fingerprint
+pub fn sign(key: &SigningKey, service_name: &str, fingerprint: &str) -> String {
+    let signature = key.sign(&signed_payload(service_name, fingerprint));
+    format!(
+        "{SIGNATURE_TAG_PREFIX}{}",
+        STANDARD.encode(signature.to_bytes())
+    )
+}
+
+/// Check an encoded signature (without the tag prefix) against a trusted key
+pub fn verify(
+    key: &VerifyingKey,
+    service_name: &str,
+    fingerprint: &str,
+    encoded_signature: &str,
+) -> Result<()> {
+    let bytes = STANDARD
+        .decode(encoded_signature)
+        .context("signature is not valid base64")?;
+    let signature = Signature::from_slice(&bytes).context("malformed ed25519 signature")?;
+    if key
+        .verify(&signed_payload(service_name, fingerprint), &signature)
+        .is_err()
+    {
+        bail!("signature does not match the trusted publisher key");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let key = signing_key();
+        let tag = sign(&key, "user-service", "service_semantic:abc");
+        let tags = vec!["latest".to_string(), tag];
+        let signature = signature_from_tags(&tags).unwrap();
+
+        let public = key.verifying_key();
+        assert!(verify(&public, "user-service", "service_semantic:abc", signature).is_ok());
+        assert!(verify(&public, "user-service", "service_semantic:def", signature).is_err());
+        assert!(verify(&public, "other-service", "service_semantic:abc", signature).is_err());
+    }
+
+    #[test]
+    fn test_trust_policy_from_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("Actr.toml");
+        let public = encode_public_key(&signing_key().verifying_key());
+        std::fs::write(
+            &path,
+            format!(
+                "[trust]\nrequire_signatures = true\n\n[trust.publishers]\nacme = \"{public}\"\n"
+            ),
+        )
+        .unwrap();
+
+        let policy = TrustPolicy::from_config_file(&path).unwrap();
+        assert!(policy.require_signatures);
+        assert_eq!(
+            policy.key_for("acme").map(encode_public_key).as_deref(),
+            Some(public.as_str())
+        );
+        assert!(policy.key_for("other").is_none());
+
+        std::fs::write(&path, "[trust.publishers]\nacme = \"ed25519:short\"\n").unwrap();
+        assert!(TrustPolicy::from_config_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_parse_signing_key_seed() {
+        let seed = STANDARD.encode([7u8; 32]);
+        let key = parse_signing_key(&format!("{seed}\n")).unwrap();
+        assert_eq!(key.verifying_key(), signing_key().verifying_key());
+        assert!(parse_signing_key("not a key").is_err());
+    }
+}
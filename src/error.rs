@@ -6,6 +6,8 @@
 //! 3. 层次分明：区分系统错误vs业务错误
 //! 4. 易于调试：提供足够的上下文信息
 
+use crate::core::i18n;
+use crate::tr;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -43,6 +45,23 @@ pub enum ActrCliError {
     #[error("Code generation failed: {0}")]
     CodeGeneration(String),
 
+    // === 代码生成工具链错误 ===
+    #[error("Required tool not found: {tool}")]
+    ToolMissing { tool: String, install_hint: String },
+
+    #[error("protoc failed on {file}:\n{stderr}")]
+    ProtocFailed { file: String, stderr: String },
+
+    #[error("Formatting generated code failed: {0}")]
+    FormatFailed(String),
+
+    #[error("Failed to write {path}: {source}")]
+    OutputWriteFailed {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
     // === 模板和初始化错误 ===
     #[error("Template rendering failed: {0}")]
     Template(#[from] handlebars::RenderError),
@@ -88,6 +107,65 @@ impl ActrCliError {
         Self::Command(msg.into())
     }
 
+    /// 外部工具未安装
+    pub fn tool_missing(tool: impl Into<String>, install_hint: impl Into<String>) -> Self {
+        Self::ToolMissing {
+            tool: tool.into(),
+            install_hint: install_hint.into(),
+        }
+    }
+
+    /// 启动外部工具失败：未安装时为 ToolMissing，否则为命令执行错误
+    pub fn spawn_error(tool: &str, install_hint: &str, error: std::io::Error) -> Self {
+        if error.kind() == std::io::ErrorKind::NotFound {
+            Self::tool_missing(tool, install_hint)
+        } else {
+            Self::command_error(format!("Failed to execute {tool}: {error}"))
+        }
+    }
+
+    /// protoc 处理某个 proto 文件失败
+    pub fn protoc_failed(file: impl std::fmt::Display, stderr: impl AsRef<str>) -> Self {
+        Self::ProtocFailed {
+            file: file.to_string(),
+            stderr: stderr.as_ref().trim_end().to_string(),
+        }
+    }
+
+    /// 写入（或创建、删除）生成输出失败
+    pub fn output_write_failed(path: impl AsRef<std::path::Path>, source: std::io::Error) -> Self {
+        Self::OutputWriteFailed {
+            path: path.as_ref().display().to_string(),
+            source,
+        }
+    }
+
+    /// 稳定的错误码，与 `crate::core::ActrCliError::code` 共用同一张编码表
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ConfigParsing(_) => "ACTR-E1001",
+            Self::Configuration(_) => "ACTR-E1002",
+            Self::InvalidProject(_) => "ACTR-E1003",
+            Self::ProjectExists(_) => "ACTR-E1005",
+            Self::Dependency(_) => "ACTR-E2001",
+            Self::Git(_) => "ACTR-E2009",
+            Self::Network(_) => "ACTR-E3002",
+            Self::CodeGeneration(_) => "ACTR-E4001",
+            Self::ToolMissing { .. } => "ACTR-E4002",
+            Self::ProtocFailed { .. } => "ACTR-E4003",
+            Self::FormatFailed(_) => "ACTR-E4004",
+            Self::OutputWriteFailed { .. } => "ACTR-E4005",
+            Self::Build(_) => "ACTR-E4006",
+            Self::Template(_) => "ACTR-E4007",
+            Self::Command(_) => "ACTR-E5001",
+            Self::Unsupported(_) => "ACTR-E5008",
+            Self::Actor(_) => "ACTR-E5009",
+            Self::Io(_) => "ACTR-E9001",
+            Self::Serialization(_) => "ACTR-E9002",
+            Self::Internal(_) => "ACTR-E9999",
+        }
+    }
+
     /// 检查是否为配置相关错误
     pub fn is_config_error(&self) -> bool {
         matches!(
@@ -111,9 +189,38 @@ impl ActrCliError {
             Self::Build(_) => Some("💡 Check proto files and dependencies"),
             Self::Network(_) => Some("💡 Check your network connection and proxy settings"),
             Self::Unsupported(_) => Some("💡 This feature is not implemented yet"),
+            Self::ToolMissing { .. } => Some("💡 Install the missing tool and rerun the command"),
+            Self::ProtocFailed { .. } => Some("💡 Fix the proto file reported by protoc"),
+            Self::FormatFailed(_) => Some("💡 Rerun with --no-format to skip formatting"),
+            Self::OutputWriteFailed { .. } => Some("💡 Check permissions of the output directory"),
             _ => None,
         }
     }
+
+    /// 针对错误类型给出的具体操作建议
+    pub fn suggested_actions(&self) -> Vec<String> {
+        match self {
+            Self::ToolMissing { tool, install_hint } => vec![
+                install_hint.clone(),
+                tr!("error.actions.tool_missing.path", tool = tool),
+                tr!("error.actions.tool_missing.doctor"),
+            ],
+            Self::ProtocFailed { file, .. } => {
+                let mut actions = vec![tr!("error.actions.protoc_failed.fix", file = file)];
+                actions.extend(i18n::messages("error.actions.protoc_failed.more"));
+                actions
+            }
+            Self::FormatFailed(_) => i18n::messages("error.actions.format_failed"),
+            Self::OutputWriteFailed { .. } => i18n::messages("error.actions.output_write_failed"),
+            Self::Configuration(_) | Self::ConfigParsing(_) => {
+                i18n::messages("error.actions.configuration")
+            }
+            _ => self
+                .user_hint()
+                .map(|hint| vec![hint.trim_start_matches("💡 ").to_string()])
+                .unwrap_or_default(),
+        }
+    }
 }
 
 /// CLI特定的Result类型
@@ -121,3 +228,37 @@ pub type Result<T> = std::result::Result<T, ActrCliError>;
 
 // === 错误兼容性转换 ===
 // 保证现有代码的兼容性，同时引导向新错误类型迁移
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn test_codegen_error_variants() {
+        let missing = ActrCliError::spawn_error(
+            "protoc",
+            "brew install protobuf",
+            ErrorKind::NotFound.into(),
+        );
+        assert!(matches!(missing, ActrCliError::ToolMissing { .. }));
+        assert!(!missing.is_config_error());
+        assert_eq!(missing.suggested_actions()[0], "brew install protobuf");
+
+        let denied: Error = ErrorKind::PermissionDenied.into();
+        assert!(matches!(
+            ActrCliError::spawn_error("protoc", "brew install protobuf", denied),
+            ActrCliError::Command(_)
+        ));
+
+        let failed =
+            ActrCliError::protoc_failed("protos/echo.proto", "echo.proto:3:1: Expected \";\".\n");
+        assert_eq!(
+            failed.to_string(),
+            "protoc failed on protos/echo.proto:\necho.proto:3:1: Expected \";\"."
+        );
+        assert!(failed.suggested_actions()[0].contains("protos/echo.proto"));
+        assert_eq!(failed.code(), "ACTR-E4003");
+        assert_eq!(missing.code(), "ACTR-E4002");
+    }
+}
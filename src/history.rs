@@ -0,0 +1,295 @@
+//! Dependency audit log
+//!
+//! Every install, remove, update and lock regeneration appends one JSON line per
+//! affected service to `.actr/history.jsonl`:
+//!
+//! ```json
+//! {"timestamp":"2025-01-10T08:00:00Z","user":"alice","operation":"update","service":"user-service","previous_fingerprint":"service_semantic:ab12","new_fingerprint":"service_semantic:cd34"}
+//! ```
+//!
+//! The file is only ever appended to, so it can be committed and reviewed like any
+//! other project file.
+
+use actr_config::LockFile;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub const HISTORY_DIR: &str = ".actr";
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// Dependency operation recorded in the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryOperation {
+    Install,
+    Remove,
+    Update,
+    Lock,
+}
+
+impl std::fmt::Display for HistoryOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Install => "install",
+            Self::Remove => "remove",
+            Self::Update => "update",
+            Self::Lock => "lock",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One line of `.actr/history.jsonl`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub user: String,
+    pub operation: HistoryOperation,
+    pub service: String,
+    pub previous_fingerprint: Option<String>,
+    pub new_fingerprint: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn fingerprint_changed(&self) -> bool {
+        self.previous_fingerprint != self.new_fingerprint
+    }
+}
+
+/// Project audit log
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn for_project(project_root: &Path) -> Self {
+        Self {
+            path: project_root.join(HISTORY_DIR).join(HISTORY_FILE),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append entries, creating `.actr/` on first use
+    pub fn append(&self, entries: &[HistoryEntry]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        // One write per operation keeps entries of concurrent commands on separate lines
+        file.write_all(lines.as_bytes())?;
+        Ok(())
+    }
+
+    /// Read all entries in the order they were recorded; malformed lines are skipped
+    pub fn load(&self) -> Result<Vec<HistoryEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(index, line)| match serde_json::from_str(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    tracing::warn!("Skipping malformed history line {}: {}", index + 1, e);
+                    None
+                }
+            })
+            .collect())
+    }
+
+    /// Record the lock file transition of one operation
+    pub fn record(
+        &self,
+        operation: HistoryOperation,
+        previous: Option<&LockFile>,
+        current: Option<&LockFile>,
+        touched: &[String],
+    ) -> Result<()> {
+        self.append(&entries_for(
+            operation,
+            previous,
+            current,
+            touched,
+            Utc::now(),
+            &current_user(),
+        ))
+    }
+}
+
+/// Entries for every service whose locked fingerprint changed, plus the services the
+/// operation explicitly touched (a reinstall at the same fingerprint is still recorded)
+pub fn entries_for(
+    operation: HistoryOperation,
+    previous: Option<&LockFile>,
+    current: Option<&LockFile>,
+    touched: &[String],
+    timestamp: DateTime<Utc>,
+    user: &str,
+) -> Vec<HistoryEntry> {
+    let fingerprints = |lock: Option<&LockFile>| -> BTreeMap<String, String> {
+        lock.map(|lock| {
+            lock.dependencies
+                .iter()
+                .map(|dep| (dep.name.clone(), dep.fingerprint.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+    let before = fingerprints(previous);
+    let after = fingerprints(current);
+
+    let mut services: Vec<&String> = before.keys().chain(after.keys()).collect();
+    services.extend(touched);
+    services.sort();
+    services.dedup();
+
+    services
+        .into_iter()
+        .filter_map(|service| {
+            let previous_fingerprint = before.get(service).cloned();
+            let new_fingerprint = after.get(service).cloned();
+            let changed = previous_fingerprint != new_fingerprint;
+            (changed || touched.contains(service)).then(|| HistoryEntry {
+                timestamp,
+                user: user.to_string(),
+                operation,
+                service: service.clone(),
+                previous_fingerprint,
+                new_fingerprint,
+            })
+        })
+        .collect()
+}
+
+/// Name of the user running the command
+pub fn current_user() -> String {
+    ["ACTR_USER", "USER", "USERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actr_config::{LockedDependency, ServiceSpecMeta};
+
+    fn lock(deps: &[(&str, &str)]) -> LockFile {
+        let mut lock = LockFile::new();
+        for (name, fingerprint) in deps {
+            lock.add_dependency(LockedDependency::new(
+                format!("acme+{name}"),
+                ServiceSpecMeta {
+                    name: name.to_string(),
+                    description: None,
+                    fingerprint: fingerprint.to_string(),
+                    protobufs: Vec::new(),
+                    published_at: None,
+                    tags: Vec::new(),
+                },
+            ));
+        }
+        lock
+    }
+
+    #[test]
+    fn test_entries_for_lock_transition() {
+        let previous = lock(&[("auth", "fp-a"), ("user", "fp-1"), ("gone", "fp-x")]);
+        let current = lock(&[("auth", "fp-a"), ("user", "fp-2"), ("new", "fp-n")]);
+        let entries = entries_for(
+            HistoryOperation::Lock,
+            Some(&previous),
+            Some(&current),
+            &[],
+            Utc::now(),
+            "alice",
+        );
+
+        let summary: Vec<(&str, Option<&str>, Option<&str>)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.service.as_str(),
+                    e.previous_fingerprint.as_deref(),
+                    e.new_fingerprint.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("gone", Some("fp-x"), None),
+                ("new", None, Some("fp-n")),
+                ("user", Some("fp-1"), Some("fp-2")),
+            ]
+        );
+        assert!(entries.iter().all(|e| e.user == "alice"));
+    }
+
+    #[test]
+    fn test_touched_services_recorded_without_change() {
+        let both = lock(&[("auth", "fp-a")]);
+        let entries = entries_for(
+            HistoryOperation::Install,
+            Some(&both),
+            Some(&both),
+            &["auth".to_string()],
+            Utc::now(),
+            "bob",
+        );
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0].fingerprint_changed());
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let history = History::for_project(dir.path());
+        history
+            .record(
+                HistoryOperation::Install,
+                None,
+                Some(&lock(&[("user", "fp-1")])),
+                &["user".to_string()],
+            )
+            .unwrap();
+        history
+            .record(
+                HistoryOperation::Remove,
+                Some(&lock(&[("user", "fp-1")])),
+                None,
+                &["user".to_string()],
+            )
+            .unwrap();
+
+        let entries = history.load().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation, HistoryOperation::Install);
+        assert_eq!(entries[1].new_fingerprint, None);
+    }
+}
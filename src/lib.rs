@@ -2,14 +2,23 @@
 //!
 //! 提供 Actor-RTC CLI 工具的核心功能模块
 
+pub mod api;
 pub mod assets;
+pub mod buf_config;
 pub mod commands;
 pub mod core;
 pub mod error;
+pub mod history;
+pub mod logging;
 pub mod plugin_config;
+pub mod plugin_manager;
+pub mod project_lock;
+pub mod proto_codec;
+pub mod proto_parser;
 pub mod templates;
 pub use templates as template;
 pub mod utils;
+pub mod workspace;
 
 // Re-export commonly used types
 pub use core::*;
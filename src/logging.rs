@@ -0,0 +1,128 @@
+//! Diagnostic logging (`--log-level`, `--log-format`, `--log-file`)
+//!
+//! Logs go to stderr, or to the file named by `--log-file`, so they never mix
+//! with command output on stdout. The level takes any `tracing` filter
+//! directive (`debug`, `actr_cli=trace,reqwest=info`); without one, `RUST_LOG`
+//! applies and logging stays off unless a log file was requested, which logs at
+//! `info`. Each flag can also be set through its `ACTR_LOG_*` variable.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fs::OpenOptions;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Environment variable overriding `--log-level`
+pub const LOG_LEVEL_ENV: &str = "ACTR_LOG_LEVEL";
+/// Environment variable overriding `--log-format`
+pub const LOG_FORMAT_ENV: &str = "ACTR_LOG_FORMAT";
+/// Environment variable overriding `--log-file`
+pub const LOG_FILE_ENV: &str = "ACTR_LOG_FILE";
+
+/// Layout of log lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// One line per event with its target, source file and line
+    #[default]
+    Pretty,
+    /// Shorter lines without source locations
+    Compact,
+    /// One JSON object per event, for log collectors
+    Json,
+}
+
+/// Logging settings from the global flags
+#[derive(Debug, Clone, Default)]
+pub struct LogOptions {
+    pub level: Option<String>,
+    pub format: LogFormat,
+    pub file: Option<PathBuf>,
+}
+
+/// Install the global subscriber; fails on an invalid level or unwritable file
+pub fn init(options: &LogOptions) -> Result<()> {
+    let filter = filter(options)?;
+    let (writer, ansi) = match &options.file {
+        Some(path) => (BoxMakeWriter::new(Mutex::new(open_log_file(path)?)), false),
+        None => (
+            BoxMakeWriter::new(std::io::stderr),
+            std::io::stderr().is_terminal(),
+        ),
+    };
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi)
+        .with_target(true)
+        .with_level(true);
+    let layer = match options.format {
+        LogFormat::Pretty => layer
+            .with_file(true)
+            .with_line_number(true)
+            .with_filter(filter)
+            .boxed(),
+        LogFormat::Compact => layer.compact().with_filter(filter).boxed(),
+        LogFormat::Json => layer
+            .json()
+            .flatten_event(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_filter(filter)
+            .boxed(),
+    };
+    // A subscriber may already be installed (e.g. by a test harness)
+    let _ = tracing_subscriber::registry().with(layer).try_init();
+    Ok(())
+}
+
+fn filter(options: &LogOptions) -> Result<EnvFilter> {
+    if let Some(level) = &options.level {
+        return EnvFilter::try_new(level).with_context(|| format!("Invalid log level '{level}'"));
+    }
+    Ok(EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(if options.file.is_some() {
+            "info"
+        } else {
+            "off"
+        })
+    }))
+}
+
+fn open_log_file(path: &Path) -> Result<std::fs::File> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_from_options() {
+        let options = LogOptions {
+            level: Some("actr_cli=debug,reqwest=info".to_string()),
+            ..Default::default()
+        };
+        let directives = filter(&options).unwrap().to_string();
+        assert!(directives.contains("actr_cli=debug"));
+        assert!(directives.contains("reqwest=info"));
+
+        let options = LogOptions {
+            level: Some("actr_cli=loud".to_string()),
+            ..Default::default()
+        };
+        assert!(filter(&options).is_err());
+    }
+}
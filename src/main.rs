@@ -7,22 +7,37 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use owo_colors::OwoColorize;
 use std::sync::Arc;
-use tracing_subscriber::layer::SubscriberExt;
-use tracing_subscriber::util::SubscriberInitExt;
 
 // 导入核心复用组件
+use actr_cli::core::events::{self, Event, event_stream_on_stdout};
+use actr_cli::core::i18n::Language;
+use actr_cli::core::timings::print_timings;
 use actr_cli::core::{
-    ActrCliError, Command, CommandContext, ConfigManager, ConsoleUI, ContainerBuilder,
-    DefaultCacheManager, DefaultDependencyResolver, DefaultFingerprintValidator,
-    DefaultNetworkValidator, DefaultProtoProcessor, ErrorReporter, NetworkServiceDiscovery,
-    ServiceContainer, TomlConfigManager,
+    ActrCliError, Command, CommandContext, CommandResult, ComponentOverride, ContainerBuilder,
+    ErrorReporter, ExecutionBudget, GenerationResult, GlobalProtoCache, OutputFormatter,
+    ServiceContainer, set_json_output,
 };
+use actr_cli::logging::{LogFormat, LogOptions};
+use actr_cli::project_lock::ProjectLock;
+use actr_cli::{human_println, tr};
 
 // 导入命令实现
+use actr_cli::commands::cache::CacheSubcommand;
+use actr_cli::commands::check::CheckOutputFormat;
+use actr_cli::commands::config::ConfigSubcommand;
+use actr_cli::commands::discovery::{DiscoveryAction, DiscoveryOutputFormat};
+use actr_cli::commands::fingerprint::FingerprintFormat;
+use actr_cli::commands::graph::GraphFormat;
+use actr_cli::commands::new::NewSubcommand;
+use actr_cli::commands::proto::ProtoSubcommand;
 use actr_cli::commands::{
-    CheckCommand, Command as LegacyCommand, ConfigCommand, DiscoveryCommand, DocCommand,
-    FingerprintCommand, GenCommand, InitCommand, InstallCommand, RunCommand,
+    CacheCommand, CallCommand, CheckCommand, CompletionsCommand, ComponentsCommand, ConfigCommand,
+    DiffCommand, DiscoveryCommand, DocCommand, DoctorCommand, ExportCommand, FingerprintCommand,
+    GenCommand, GraphCommand, HistoryCommand, ImportCommand, InitCommand, InstallCommand,
+    LockCommand, MigrateCommand, MockCommand, NewCommand, ProtoCommand, PublishCommand,
+    RemoveCommand, RunCommand, SearchCommand, UiCommand, UpdateCommand, VendorCommand,
 };
+use actr_cli::workspace::{WORKSPACE_FILE_NAME, Workspace};
 
 /// ACTR-CLI - Actor-RTC Command Line Tool
 #[derive(Parser)]
@@ -37,6 +52,95 @@ struct Cli {
     #[arg(short, action = clap::ArgAction::Count)]
     verbose: u8,
 
+    /// Resolve dependencies only from Actr.lock.toml and cached protos (also ACTR_OFFLINE=1)
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Suppress progress bars
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Print the command result as a single JSON document on stdout
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Cache protos only in the project, bypassing the shared store in ~/.actr/cache
+    #[arg(long, global = true)]
+    no_global_cache: bool,
+
+    /// Attempts for signaling and connectivity calls (overrides [system.discovery] attempts)
+    #[arg(long, global = true, value_name = "N")]
+    retries: Option<u32>,
+
+    /// Initial retry delay in milliseconds (overrides [system.discovery] base_delay_ms)
+    #[arg(long, global = true, value_name = "MS")]
+    retry_base_delay: Option<u64>,
+
+    /// Maximum retry delay in milliseconds (overrides [system.discovery] max_delay_ms)
+    #[arg(long, global = true, value_name = "MS")]
+    retry_max_delay: Option<u64>,
+
+    /// Actr.toml that seeds the command's components (defaults to ./Actr.toml)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
+
+    /// Merge Actr.<PROFILE>.toml over Actr.toml (also ACTR_ENV)
+    #[arg(long = "env", global = true, value_name = "PROFILE")]
+    env: Option<String>,
+
+    /// Abort the command after this many seconds, rolling back partial changes
+    #[arg(long, global = true, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Do not take the project lock (.actr/lock) before modifying the project
+    #[arg(long, global = true)]
+    no_lock: bool,
+
+    /// Seconds to wait for another actr process to release the project lock
+    #[arg(long, global = true, value_name = "SECS", default_value_t = 30)]
+    lock_timeout: u64,
+
+    /// Log filter, e.g. debug or actr_cli=trace (default: RUST_LOG, else off)
+    #[arg(long, global = true, value_name = "FILTER", env = actr_cli::logging::LOG_LEVEL_ENV)]
+    log_level: Option<String>,
+
+    /// Layout of log lines
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Pretty, env = actr_cli::logging::LOG_FORMAT_ENV)]
+    log_format: LogFormat,
+
+    /// Append logs to this file instead of stderr (logs at info unless --log-level is set)
+    #[arg(long, global = true, value_name = "PATH", env = actr_cli::logging::LOG_FILE_ENV)]
+    log_file: Option<std::path::PathBuf>,
+
+    /// Print how long each pipeline stage took (on stderr) when the command ends
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Language of user-facing messages (default: ACTR_LANG, else the system locale)
+    #[arg(long, global = true, value_enum, value_name = "LANG")]
+    lang: Option<Language>,
+
+    /// Write newline-delimited JSON progress events to stdout, or with =PATH to a file or named pipe
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = events::STDOUT_TARGET
+    )]
+    event_stream: Option<std::path::PathBuf>,
+
+    /// Replace a component with a fixture-backed mock, e.g. service-discovery=mock:registry.json (repeatable)
+    #[arg(
+        long = "component",
+        global = true,
+        value_name = "KIND=mock:PATH",
+        env = "ACTR_COMPONENTS",
+        value_delimiter = ','
+    )]
+    components: Vec<ComponentOverride>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -46,12 +150,66 @@ enum Commands {
     /// Initialize a new Actor project
     Init(InitCommand),
 
+    /// Add actors to an existing project
+    New(NewCommand),
+
     /// Install service dependencies
     Install(InstallCommand),
 
+    /// Remove service dependencies
+    Remove(RemoveCommand),
+
+    /// Update locked dependencies
+    Update(UpdateCommand),
+
+    /// Verify and maintain Actr.lock.toml
+    Lock(LockCommand),
+
+    /// Show proto changes between installed and remote services
+    Diff(DiffCommand),
+
+    /// Publish exported protos to the registry
+    Publish(PublishCommand),
+
+    /// Inspect and maintain the proto cache
+    Cache(CacheCommand),
+
+    /// Show the dependency audit log
+    History(HistoryCommand),
+
+    /// Show the service dependency graph
+    Graph(GraphCommand),
+
+    /// Bundle the project and its cached protos for offline use
+    Export(ExportCommand),
+
+    /// Restore a bundle written by 'actr export'
+    Import(ImportCommand),
+
+    /// Verify cached protos and vendor them into protos/remote/
+    Vendor(VendorCommand),
+
+    /// Upgrade Actr.toml and the project layout to the current edition
+    Migrate(MigrateCommand),
+
+    /// Serve a local mock of a dependency from its cached protos
+    Mock(MockCommand),
+
+    /// Invoke an RPC of a dependency with a JSON request
+    Call(CallCommand),
+
     /// Discover network services
     Discovery(DiscoveryCommand),
 
+    /// Search the service registry
+    Search(SearchCommand),
+
+    /// Work with service protos
+    Proto(ProtoCommand),
+
+    /// Open the terminal dashboard
+    Ui(UiCommand),
+
     /// Generate project documentation
     Doc(DocCommand),
 
@@ -69,25 +227,29 @@ enum Commands {
 
     /// Run project scripts
     Run(RunCommand),
+
+    /// Generate shell completion scripts
+    Completions(CompletionsCommand),
+
+    /// Diagnose the development environment
+    Doctor(DoctorCommand),
+
+    /// Show how each component is provided
+    Components(ComponentsCommand),
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 初始化日志
-    let layer = tracing_subscriber::fmt::layer()
-        .with_target(true)
-        .with_level(true)
-        .with_line_number(true)
-        .with_file(true);
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("off"));
-    let _ = tracing_subscriber::registry()
-        .with(filter)
-        .with(layer)
-        .try_init();
-
     // 使用 clap 解析命令行参数
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    apply_deprecated_check_timeout(&mut cli, &std::env::args().collect::<Vec<_>>());
+
+    // 初始化日志
+    actr_cli::logging::init(&LogOptions {
+        level: cli.log_level.clone(),
+        format: cli.log_format,
+        file: cli.log_file.clone(),
+    })?;
 
     // Handle -vv for version and commit info
     if cli.verbose >= 2 {
@@ -100,27 +262,102 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    // 构建服务容器并注册组件
-    let container = build_container().await?;
+    if let Some(target) = &cli.event_stream {
+        events::open_event_stream(target)
+            .map_err(|e| anyhow::anyhow!("Cannot open event stream {}: {e}", target.display()))?;
+    }
 
-    // 创建命令执行上下文
-    let context = CommandContext {
-        container: Arc::new(std::sync::Mutex::new(container)),
-        args: actr_cli::core::CommandArgs {
-            command: String::new(),
-            subcommand: None,
-            flags: std::collections::HashMap::new(),
-            positional: Vec::new(),
-        },
-        working_dir: std::env::current_dir()?,
+    let options = GlobalOptions {
+        offline: cli.offline || actr_cli::utils::offline_from_env(),
+        quiet: cli.quiet,
+        // Human output moves to stderr while stdout carries the event stream
+        json: cli.json || event_stream_on_stdout(),
+        no_global_cache: cli.no_global_cache,
+        retries: cli.retries,
+        retry_base_delay: cli.retry_base_delay,
+        retry_max_delay: cli.retry_max_delay,
+        config: cli.config.clone(),
+        no_lock: cli.no_lock,
+        lock_timeout: std::time::Duration::from_secs(cli.lock_timeout),
+        // Absolute, as workspace members run in their own directory
+        components: cli
+            .components
+            .iter()
+            .map(|component| {
+                Ok(ComponentOverride {
+                    component: component.component.clone(),
+                    fixture: std::path::absolute(&component.fixture)?,
+                })
+            })
+            .collect::<std::io::Result<_>>()?,
     };
+    set_json_output(options.json);
+    actr_cli::core::timings::set_timings(cli.timings);
+    actr_cli::core::profile::set_active_profile(cli.env.clone());
+    actr_cli::core::i18n::set_language(cli.lang);
+
+    // One budget for the whole invocation, workspace members included
+    let budget = ExecutionBudget::new(cli.timeout.map(std::time::Duration::from_secs));
+    if !cli.command.as_ref().is_some_and(is_watch_command) {
+        budget.cancel_on_ctrl_c();
+    }
+
+    // Completion scripts must work outside projects and with a broken Actr.toml
+    if let Some(Commands::Completions(cmd)) = &cli.command {
+        use clap::CommandFactory;
+        return cmd.write_to(Cli::command(), &mut std::io::stdout());
+    }
 
-    // 根据命令分发执行
     if let Some(cmd) = &cli.command {
-        match execute_command(cmd, &context).await {
+        events::emit(Event::CommandStarted {
+            command: command_name(cmd).to_string(),
+        });
+    }
+
+    if let Some(cmd) = &cli.command
+        && is_workspace_command(cmd)
+    {
+        return run_workspace(cmd, &options, &budget).await;
+    }
+
+    // 构建服务容器并注册组件，并创建命令执行上下文
+    let context = build_context(&options, &budget).await?;
+
+    // 根据命令分发执行
+    if let Some(cmd) = &cli.command
+        && options.json
+    {
+        let result = budget
+            .supervise(execute_locked(cmd, &options, &context))
+            .await;
+        print_timings();
+        let (document, failed) = match &result {
+            Ok(result) => (
+                OutputFormatter::format_result(command_name(cmd), result),
+                is_failure(result),
+            ),
+            Err(e) => (OutputFormatter::format_error(command_name(cmd), e), true),
+        };
+        emit_finished(command_name(cmd), &result);
+        if !event_stream_on_stdout() {
+            println!("{}", serde_json::to_string_pretty(&document)?);
+        }
+        if let Err(e) = &result {
+            std::process::exit(exit_code(e));
+        }
+        if failed {
+            std::process::exit(1);
+        }
+    } else if let Some(cmd) = &cli.command {
+        let result = budget
+            .supervise(execute_locked(cmd, &options, &context))
+            .await;
+        print_timings();
+        emit_finished(command_name(cmd), &result);
+        match result {
             Ok(result) => match result {
                 actr_cli::core::CommandResult::Success(msg) => {
-                    if msg != "Help displayed" {
+                    if !msg.is_empty() && msg != "Help displayed" {
                         println!("{msg}");
                     }
                 }
@@ -146,11 +383,25 @@ async fn main() -> Result<()> {
                         // Exit silently
                         std::process::exit(0);
                     }
+                    if matches!(cli_error, ActrCliError::ScriptFailed { .. }) {
+                        // The script has reported its own failure
+                        std::process::exit(exit_code(&e));
+                    }
                     eprintln!("{}", ErrorReporter::format_error(cli_error));
+                } else if let Some(gen_error) = e.downcast_ref::<actr_cli::error::ActrCliError>() {
+                    // Errors of `actr gen` and the code generators
+                    eprintln!("{} [{}] {gen_error}", "Error:".red(), gen_error.code());
+                    let actions = gen_error.suggested_actions();
+                    if !actions.is_empty() {
+                        eprintln!("\n{}", tr!("error.suggested_solutions"));
+                        for (i, action) in actions.iter().enumerate() {
+                            eprintln!("   {}. {action}", i + 1);
+                        }
+                    }
                 } else {
                     eprintln!("{} {e:?}", "Error:".red());
                 }
-                std::process::exit(1);
+                std::process::exit(exit_code(&e));
             }
         }
     } else {
@@ -161,132 +412,736 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// 构建服务容器
-async fn build_container() -> Result<ServiceContainer> {
-    let config_path = std::path::Path::new("Actr.toml");
-    let mut builder = ContainerBuilder::new();
-    let mut config_manager = None;
+/// Options shared by every command
+#[derive(Debug, Clone, Default)]
+struct GlobalOptions {
+    offline: bool,
+    quiet: bool,
+    json: bool,
+    no_global_cache: bool,
+    retries: Option<u32>,
+    retry_base_delay: Option<u64>,
+    retry_max_delay: Option<u64>,
+    config: Option<std::path::PathBuf>,
+    no_lock: bool,
+    lock_timeout: std::time::Duration,
+    components: Vec<ComponentOverride>,
+}
 
-    if config_path.exists() {
-        builder = builder.config_path(config_path);
+/// Subcommand name used in JSON output
+fn command_name(command: &Commands) -> &'static str {
+    match command {
+        Commands::Init(_) => "init",
+        Commands::New(_) => "new",
+        Commands::Install(_) => "install",
+        Commands::Remove(_) => "remove",
+        Commands::Update(_) => "update",
+        Commands::Lock(_) => "lock",
+        Commands::Diff(_) => "diff",
+        Commands::Publish(_) => "publish",
+        Commands::Cache(_) => "cache",
+        Commands::History(_) => "history",
+        Commands::Graph(_) => "graph",
+        Commands::Export(_) => "export",
+        Commands::Import(_) => "import",
+        Commands::Vendor(_) => "vendor",
+        Commands::Migrate(_) => "migrate",
+        Commands::Mock(_) => "mock",
+        Commands::Call(_) => "call",
+        Commands::Discovery(_) => "discovery",
+        Commands::Search(_) => "search",
+        Commands::Proto(_) => "proto",
+        Commands::Ui(_) => "ui",
+        Commands::Doc(_) => "doc",
+        Commands::Gen(_) => "gen",
+        Commands::Check(_) => "check",
+        Commands::Fingerprint(_) => "fingerprint",
+        Commands::Config(_) => "config",
+        Commands::Run(_) => "run",
+        Commands::Completions(_) => "completions",
+        Commands::Doctor(_) => "doctor",
+        Commands::Components(_) => "components",
     }
+}
 
-    let mut container = builder.build()?;
+/// Exit code for a failed command: 130 after Ctrl+C, 1 otherwise
+fn exit_code(error: &anyhow::Error) -> i32 {
+    match error.downcast_ref::<ActrCliError>() {
+        Some(ActrCliError::Interrupted) => 130,
+        Some(ActrCliError::ScriptFailed { code, .. }) => *code,
+        _ => 1,
+    }
+}
 
-    // Register UI component (always available)
-    container = container.register_user_interface(Arc::new(ConsoleUI::new()));
+/// `actr check --timeout N` set the per-check timeout before the global `--timeout`
+/// existed. Given after `check` without `--check-timeout`, it keeps that meaning
+/// with a deprecation warning instead of silently becoming the command budget.
+fn apply_deprecated_check_timeout(cli: &mut Cli, args: &[String]) {
+    if let Some(Commands::Check(cmd)) = &mut cli.command
+        && let Some(secs) = cli.timeout
+        && check_timeout_alias_used(args)
+    {
+        eprintln!(
+            "⚠️  'actr check --timeout' is deprecated: use --check-timeout {secs} for the per-check \
+             timeout, or put --timeout before 'check' to limit the whole command"
+        );
+        cmd.timeout = secs;
+        cli.timeout = None;
+    }
+}
 
-    if config_path.exists() {
-        let manager = Arc::new(TomlConfigManager::new(config_path));
-        container = container.register_config_manager(manager.clone());
-        config_manager = Some(manager);
+/// Whether `--timeout` follows the `check` subcommand and `--check-timeout` is absent
+fn check_timeout_alias_used(args: &[String]) -> bool {
+    let args: Vec<&str> = args
+        .iter()
+        .skip(1)
+        .map(String::as_str)
+        .take_while(|arg| *arg != "--")
+        .collect();
+    let Some(check) = args.iter().position(|arg| *arg == "check") else {
+        return false;
+    };
+    let after = &args[check + 1..];
+    let is_flag = |arg: &&str, flag: &str| {
+        *arg == flag
+            || arg
+                .strip_prefix(flag)
+                .is_some_and(|rest| rest.starts_with('='))
+    };
+    after.iter().any(|arg| is_flag(arg, "--timeout"))
+        && !after.iter().any(|arg| is_flag(arg, "--check-timeout"))
+}
+
+/// Commands that handle Ctrl+C themselves to stop watching, serving or the dashboard
+fn is_watch_command(command: &Commands) -> bool {
+    match command {
+        Commands::Gen(cmd) => cmd.watch,
+        Commands::Check(cmd) => cmd.watch,
+        Commands::Mock(_) | Commands::Ui(_) => true,
+        _ => false,
+    }
+}
+
+/// Commands that write Actr.toml, Actr.lock.toml, the proto cache or generated code
+fn is_mutating_command(command: &Commands) -> bool {
+    match command {
+        Commands::Install(cmd) => !cmd.dry_run,
+        Commands::Update(cmd) => !cmd.dry_run,
+        Commands::Migrate(cmd) => !cmd.dry_run,
+        Commands::Remove(_)
+        | Commands::Import(_)
+        | Commands::Vendor(_)
+        | Commands::Lock(_)
+        | Commands::New(_) => true,
+        Commands::Gen(cmd) => !cmd.watch && !cmd.check,
+        // Interactive runs may export or add the selected service
+        Commands::Discovery(cmd) => {
+            cmd.output != DiscoveryOutputFormat::Json
+                && !cmd.list_only
+                && cmd.action != Some(DiscoveryAction::Details)
+        }
+        Commands::Proto(cmd) => matches!(
+            cmd.command,
+            ProtoSubcommand::Fmt { check: false, .. } | ProtoSubcommand::Export(_)
+        ),
+        Commands::Cache(cmd) => matches!(
+            cmd.command,
+            CacheSubcommand::Clear { .. } | CacheSubcommand::Invalidate { .. }
+        ),
+        Commands::Config(cmd) => matches!(
+            cmd.command,
+            ConfigSubcommand::Set { .. } | ConfigSubcommand::Unset { .. }
+        ),
+        _ => false,
+    }
+}
+
+/// Execute a command, holding the project lock while it modifies the project
+async fn execute_locked(
+    command: &Commands,
+    options: &GlobalOptions,
+    context: &CommandContext,
+) -> Result<CommandResult> {
+    let _lock = lock_project(command, options).await?;
+    execute_command(command, options, context).await
+}
+
+/// Actr.toml a command works on: `gen -c`, else --config, else the current directory's
+fn config_path(command: &Commands, options: &GlobalOptions) -> std::path::PathBuf {
+    match command {
+        Commands::Gen(cmd) => cmd.config.clone(),
+        _ => options
+            .config
+            .clone()
+            .unwrap_or_else(|| std::path::PathBuf::from("Actr.toml")),
+    }
+}
+
+/// Take `.actr/lock` in the project of the Actr.toml the command works on
+///
+/// Nothing is locked outside projects, e.g. for `actr init` in an empty directory.
+async fn lock_project(command: &Commands, options: &GlobalOptions) -> Result<Option<ProjectLock>> {
+    if options.no_lock || !is_mutating_command(command) {
+        return Ok(None);
+    }
+    let config = config_path(command, options);
+    if !config.exists() {
+        return Ok(None);
+    }
+    let root = match config.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => std::env::current_dir()?,
+    };
+    let lock = ProjectLock::acquire(&root, command_name(command), options.lock_timeout).await?;
+    Ok(Some(lock))
+}
+
+/// Whether a command result should produce a non-zero exit code
+fn is_failure(result: &CommandResult) -> bool {
+    match result {
+        CommandResult::Error(_) => true,
+        CommandResult::Validation(report) => !report.is_success(),
+        CommandResult::Generation(generation) => !generation.errors.is_empty(),
+        CommandResult::Success(_) | CommandResult::Install(_) => false,
+    }
+}
+
+/// 创建命令执行上下文（基于当前工作目录）
+async fn build_context(
+    options: &GlobalOptions,
+    budget: &ExecutionBudget,
+) -> Result<CommandContext> {
+    let container = build_container(options)?.with_budget(budget.clone());
+
+    let mut flags = std::collections::HashMap::new();
+    if options.offline {
+        flags.insert("offline".to_string(), "true".to_string());
+    }
+    if options.quiet {
+        flags.insert("quiet".to_string(), "true".to_string());
+    }
+    if options.json {
+        flags.insert("json".to_string(), "true".to_string());
+    }
+
+    Ok(CommandContext {
+        container: Arc::new(container),
+        args: actr_cli::core::CommandArgs {
+            command: String::new(),
+            subcommand: None,
+            flags,
+            positional: Vec::new(),
+        },
+        working_dir: std::env::current_dir()?,
+    })
+}
+
+/// Send the outcome of a command to the event stream: an `error` event when it
+/// failed, then `command_finished` with the document `--json` prints
+fn emit_finished(command: &str, result: &Result<CommandResult>) {
+    if !events::event_stream_enabled() {
+        return;
+    }
+    let document = match result {
+        Ok(result) => OutputFormatter::format_result(command, result),
+        Err(e) => {
+            events::emit(Event::Error {
+                command: command.to_string(),
+                error: OutputFormatter::error_json(e),
+            });
+            OutputFormatter::format_error(command, e)
+        }
+    };
+    events::emit(Event::CommandFinished {
+        command: command.to_string(),
+        result: document,
+    });
+}
+
+/// Whether the command was invoked with --workspace
+fn is_workspace_command(command: &Commands) -> bool {
+    match command {
+        Commands::Check(cmd) => cmd.workspace,
+        Commands::Install(cmd) => cmd.workspace,
+        Commands::Gen(cmd) => cmd.workspace,
+        _ => false,
+    }
+}
+
+/// Run a command in every workspace member and print an aggregated summary
+///
+/// Each member runs with its own directory as the working directory and a
+/// freshly built container. The shared Actr.lock.toml seeds the member lock
+/// files before install and receives them back afterwards; other commands only
+/// check that member lock files agree with it.
+async fn run_workspace(
+    command: &Commands,
+    options: &GlobalOptions,
+    budget: &ExecutionBudget,
+) -> Result<()> {
+    if options.config.is_some() {
+        return Err(anyhow::anyhow!(
+            "--config cannot be combined with --workspace; each member uses its own Actr.toml"
+        ));
+    }
+    if let Commands::Gen(cmd) = command
+        && cmd.watch
+    {
+        return Err(anyhow::anyhow!(
+            "--watch cannot be combined with --workspace; run 'actr gen --watch' inside a member"
+        ));
+    }
+
+    let original_dir = std::env::current_dir()?;
+    let workspace = Workspace::discover(&original_dir)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No {WORKSPACE_FILE_NAME} found in {} or any parent directory",
+            original_dir.display()
+        )
+    })?;
+
+    human_println!(
+        "📦 Workspace: {} ({} members)",
+        workspace.root().display(),
+        workspace.members().len()
+    );
+
+    let installs = matches!(command, Commands::Install(cmd) if !cmd.dry_run);
+    let mut outcomes: Vec<(String, Result<CommandResult>)> = Vec::new();
+    for member in workspace.members() {
+        let name = workspace.member_name(member);
+        human_println!();
+        human_println!("▶ {name}");
+
+        let prepared = if installs {
+            workspace.sync_member_lock(member)
+        } else {
+            workspace.verify_member_lock(member)
+        };
+        let outcome = match prepared {
+            Ok(()) => {
+                std::env::set_current_dir(member)?;
+                let result = run_member(command, options, budget).await;
+                std::env::set_current_dir(&original_dir)?;
+                result
+            }
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = &outcome {
+            events::emit(Event::Error {
+                command: command_name(command).to_string(),
+                error: OutputFormatter::error_json(e),
+            });
+        }
+        outcomes.push((name, outcome));
     }
+    print_timings();
 
-    let mut container =
-        container.register_dependency_resolver(Arc::new(DefaultDependencyResolver::new()));
+    let mut lock_conflicts = Vec::new();
+    if installs {
+        lock_conflicts = workspace.merge_member_locks()?;
+    }
+
+    let failed = outcomes
+        .iter()
+        .filter(|(_, outcome)| outcome.as_ref().is_none_or(is_failure))
+        .count();
+
+    let members: Vec<serde_json::Value> = outcomes
+        .iter()
+        .map(|(name, outcome)| {
+            let mut document = match outcome {
+                Ok(result) => OutputFormatter::format_result(command_name(command), result),
+                Err(e) => OutputFormatter::format_error(command_name(command), e),
+            };
+            document["member"] = serde_json::Value::String(name.clone());
+            document
+        })
+        .collect();
+    let document = serde_json::json!({
+        "status": if failed == 0 && lock_conflicts.is_empty() { "success" } else { "error" },
+        "command": command_name(command),
+        "workspace": workspace.root().display().to_string(),
+        "members": members,
+        "lock_conflicts": lock_conflicts.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+    });
+    events::emit(Event::CommandFinished {
+        command: command_name(command).to_string(),
+        result: document.clone(),
+    });
+
+    if options.json {
+        if !event_stream_on_stdout() {
+            println!("{}", serde_json::to_string_pretty(&document)?);
+        }
+    } else {
+        println!();
+        println!("📊 Workspace summary:");
+        for (index, (name, outcome)) in outcomes.iter().enumerate() {
+            let glyph = if index + 1 == outcomes.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            match member_summary(outcome) {
+                Ok(msg) => println!("  {glyph} ✅ {name}: {msg}"),
+                Err(err) => println!("  {glyph} ❌ {name}: {err}"),
+            }
+        }
+
+        if !lock_conflicts.is_empty() {
+            println!();
+            println!("⚠️  Shared lock file conflicts (Actr.lock.toml left unchanged):");
+            for conflict in &lock_conflicts {
+                println!("   • {conflict}");
+            }
+        }
+    }
+
+    if failed > 0 || !lock_conflicts.is_empty() {
+        if !options.json {
+            eprintln!(
+                "{} {failed} of {} workspace members failed",
+                "❌".red(),
+                outcomes.len()
+            );
+        }
+        std::process::exit(1);
+    }
 
-    // Register network validator (stub implementation)
-    container = container.register_network_validator(Arc::new(DefaultNetworkValidator::new()));
+    Ok(())
+}
 
-    // Register fingerprint validator
-    container =
-        container.register_fingerprint_validator(Arc::new(DefaultFingerprintValidator::new()));
+/// Execute a command for a single workspace member (working directory already set)
+async fn run_member(
+    command: &Commands,
+    options: &GlobalOptions,
+    budget: &ExecutionBudget,
+) -> Result<CommandResult> {
+    budget.check()?;
+    let context = build_context(options, budget).await?;
+    let result = budget
+        .supervise(execute_locked(command, options, &context))
+        .await?;
+    if let CommandResult::Validation(report) = &result
+        && !options.json
+    {
+        println!("{}", ErrorReporter::format_validation_report(report));
+    }
+    Ok(result)
+}
 
-    // Register proto processor
-    container = container.register_proto_processor(Arc::new(DefaultProtoProcessor::new()));
+/// One-line summary of a member outcome for the workspace report
+fn member_summary(outcome: &Result<CommandResult>) -> std::result::Result<String, String> {
+    match outcome {
+        Ok(CommandResult::Success(msg)) => Ok(msg.clone()),
+        Ok(CommandResult::Install(install_result)) => Ok(install_result.summary()),
+        Ok(CommandResult::Validation(report)) if report.is_success() => {
+            Ok("Validation passed".to_string())
+        }
+        Ok(CommandResult::Validation(_)) => Err("Validation failed".to_string()),
+        Ok(CommandResult::Generation(gen_result)) => Ok(format!(
+            "Generated {} files",
+            gen_result.generated_files.len()
+        )),
+        Ok(CommandResult::Error(error)) => Err(error.clone()),
+        Err(e) => Err(match e.downcast_ref::<ActrCliError>() {
+            Some(cli_error) => cli_error.to_string(),
+            None => e.to_string(),
+        }),
+    }
+}
 
-    // Register cache manager
-    container = container.register_cache_manager(Arc::new(DefaultCacheManager::new()));
+/// 构建服务容器
+///
+/// Components are created on first use from the selected Actr.toml. In offline
+/// mode the network-facing components are replaced by implementations that only
+/// read Actr.lock.toml and the proto cache.
+fn build_container(options: &GlobalOptions) -> Result<ServiceContainer> {
+    let mut builder = ContainerBuilder::new()
+        .offline(options.offline)
+        .quiet(options.quiet)
+        .global_cache(if options.no_global_cache {
+            None
+        } else {
+            GlobalProtoCache::user_default()
+        })
+        .retry_overrides(
+            options.retries,
+            options.retry_base_delay,
+            options.retry_max_delay,
+        );
+    if let Some(config) = &options.config {
+        builder = builder.config_path(config);
+    }
+    for component in &options.components {
+        builder = builder.component_override(component.clone());
+    }
+    builder.build()
+}
 
-    if let Some(manager) = config_manager {
-        let config = manager.load_config(config_path).await?;
-        container =
-            container.register_service_discovery(Arc::new(NetworkServiceDiscovery::new(config)));
+/// Fail with a hint to run `actr init` when the Actr.toml a command needs is missing
+fn require_config(config: &std::path::Path) -> Result<()> {
+    if !config.exists() {
+        return Err(anyhow::anyhow!(
+            "No {} found.\n💡 Hint: Run 'actr init' to initialize a new project first.",
+            config.display()
+        ));
     }
-    Ok(container)
+    Ok(())
 }
 
 /// 执行命令
 async fn execute_command(
     command: &Commands,
+    options: &GlobalOptions,
     context: &CommandContext,
 ) -> Result<actr_cli::core::CommandResult> {
     match command {
         Commands::Init(cmd) => {
             // InitCommand 使用旧的 Command trait，直接执行
-            match cmd.execute().await {
-                Ok(_) => Ok(actr_cli::core::CommandResult::Success(
-                    "Project initialized".to_string(),
-                )),
-                Err(e) => Err(e.into()),
-            }
+            let summary = cmd.run_with_discovery(!context.is_offline()).await?;
+            let warnings = summary
+                .hooks
+                .iter()
+                .filter(|hook| !hook.success())
+                .map(|hook| {
+                    format!(
+                        "post_init hook '{}' failed: {}",
+                        hook.command,
+                        hook.output.trim()
+                    )
+                })
+                .collect();
+            Ok(CommandResult::Generation(GenerationResult {
+                generated_files: summary.created,
+                warnings,
+                errors: vec![],
+            }))
+        }
+        Commands::New(cmd) => {
+            let mut cmd = cmd.clone();
+            let NewSubcommand::Actor(args) = &mut cmd.command;
+            args.offline = context.is_offline();
+            let generated_files = cmd.run().await?;
+            Ok(CommandResult::Generation(GenerationResult {
+                generated_files,
+                warnings: vec![],
+                errors: vec![],
+            }))
         }
         Commands::Install(cmd) => {
             let command = InstallCommand::from_args(cmd);
 
             // 验证所需组件
-            context
-                .container
-                .lock()
-                .unwrap()
-                .validate(&command.required_components())?;
+            context.container.validate(&command.required_components())?;
+
+            // 执行命令
+            command.execute(context).await
+        }
+        Commands::Remove(cmd) => {
+            let command = RemoveCommand::from_args(cmd);
+
+            // 验证所需组件
+            context.container.validate(&command.required_components())?;
 
             // 执行命令
             command.execute(context).await
         }
+        Commands::Update(cmd) => {
+            let command = UpdateCommand::from_args(cmd);
+
+            // 验证所需组件
+            context.container.validate(&command.required_components())?;
+
+            // 执行命令
+            command.execute(context).await
+        }
+        Commands::Lock(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Diff(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Publish(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::History(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Graph(cmd) => {
+            let mut cmd = cmd.clone();
+            if context.is_json() {
+                cmd.format = GraphFormat::Json;
+            }
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Export(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Import(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Vendor(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Migrate(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Mock(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Call(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Cache(cmd) => {
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
         Commands::Discovery(cmd) => {
-            let command = DiscoveryCommand::from_args(cmd);
+            let mut command = DiscoveryCommand::from_args(cmd);
+            if context.is_json() {
+                command.output = DiscoveryOutputFormat::Json;
+            }
 
             // TODO: (Option B) In the future, if a default public signaling server is available,
             // we should allow discovery to run without a local Actr.toml by using a default config.
             // For now (Option A), we require a project context to get the signaling URL.
-            if !std::path::Path::new("Actr.toml").exists() {
-                return Err(anyhow::anyhow!(
-                    "No Actr.toml found in current directory.\n💡 Hint: Run 'actr init' to initialize a new project first."
-                ));
-            }
+            require_config(&config_path(command, options))?;
 
             // 验证所需组件
-            {
-                let container = context.container.lock().unwrap();
-                container.validate(&command.required_components())?;
-            }
+            context.container.validate(&command.required_components())?;
 
             // 执行命令
             command.execute(context).await
         }
-        Commands::Doc(cmd) => match cmd.execute().await {
-            Ok(_) => Ok(actr_cli::core::CommandResult::Success(
-                "Documentation generated".to_string(),
-            )),
-            Err(e) => Err(e.into()),
-        },
+        Commands::Search(cmd) => {
+            require_config(&config_path(command, options))?;
+
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Proto(cmd) => {
+            require_config(&config_path(command, options))?;
+
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Ui(cmd) => {
+            require_config(&config_path(command, options))?;
+
+            // 验证所需组件
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Doc(cmd) => {
+            let cmd = DocCommand {
+                config: Some(config_path(command, options)),
+                ..cmd.clone()
+            };
+            match cmd.execute().await {
+                Ok(_) => Ok(actr_cli::core::CommandResult::Success(
+                    "Documentation generated".to_string(),
+                )),
+                Err(e) => Err(e.into()),
+            }
+        }
         Commands::Check(cmd) => {
+            let mut cmd = cmd.clone();
+            if context.is_json() {
+                cmd.format = CheckOutputFormat::Json;
+            }
             if cmd.config_file.is_none() {
-                let container = context.container.lock().unwrap();
-                container.validate(&cmd.required_components())?;
+                context.container.validate(&cmd.required_components())?;
             }
 
             cmd.execute(context).await
         }
-        Commands::Fingerprint(cmd) => cmd.execute(context).await,
-        Commands::Gen(cmd) => match cmd.execute().await {
-            Ok(_) => Ok(actr_cli::core::CommandResult::Success(
-                "Generation completed".to_string(),
-            )),
-            Err(e) => Err(e.into()),
-        },
+        Commands::Fingerprint(cmd) => {
+            let mut cmd = cmd.clone();
+            if context.is_json() {
+                cmd.format = FingerprintFormat::Json;
+            }
+            context.container.validate(&cmd.required_components())?;
+
+            cmd.execute(context).await
+        }
+        Commands::Doctor(cmd) => cmd.execute(context).await,
+        Commands::Components(cmd) => cmd.execute(context).await,
+        Commands::Gen(cmd) => {
+            let mut cmd = cmd.clone();
+            cmd.offline = context.is_offline();
+            cmd.quiet = context.is_quiet();
+            // Dropping the run at the deadline or on Ctrl+C kills protoc, plugins and formatters
+            let generated_files = context.container.get_budget().run(cmd.run()).await??;
+            Ok(CommandResult::Generation(GenerationResult {
+                generated_files,
+                warnings: vec![],
+                errors: vec![],
+            }))
+        }
         Commands::Config(cmd) => {
             // Config command uses the new Command trait from core
             use actr_cli::core::Command;
             cmd.execute(context).await
         }
-        Commands::Run(cmd) => match cmd.execute().await {
-            Ok(_) => Ok(actr_cli::core::CommandResult::Success(
-                "Script executed".to_string(),
-            )),
-            Err(e) => Err(e.into()),
-        },
+        Commands::Completions(cmd) => {
+            use clap::CommandFactory;
+            cmd.write_to(Cli::command(), &mut std::io::stdout())?;
+            Ok(CommandResult::Success("Help displayed".to_string()))
+        }
+        Commands::Run(cmd) => {
+            // Propagated as the exit code once the result and events are emitted,
+            // instead of collapsing failures to 1
+            let code = cmd.run().await?;
+            if code != 0 {
+                return Err(ActrCliError::ScriptFailed {
+                    script: cmd.script_name().to_string(),
+                    code,
+                }
+                .into());
+            }
+            Ok(actr_cli::core::CommandResult::Success(String::new()))
+        }
     }
 }
 
@@ -304,9 +1159,179 @@ mod tests {
         // TODO: 实现参数解析测试
     }
 
-    #[tokio::test]
-    async fn test_build_container() {
-        let container = build_container().await;
+    #[test]
+    fn test_cli_definition() {
+        use clap::CommandFactory;
+        // Catches clashes between global flags and subcommand flags (e.g. --timeout)
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_check_command() {
+        let cli = Cli::try_parse_from([
+            "actr",
+            "check",
+            "user-service",
+            "order-service",
+            "-f",
+            "Actr.prod.toml",
+            "--lock",
+            "--verbose",
+            "--check-timeout",
+            "3",
+            "--timeout",
+            "60",
+        ])
+        .unwrap();
+        assert_eq!(cli.timeout, Some(60));
+        let Some(Commands::Check(cmd)) = cli.command else {
+            panic!("expected the check command");
+        };
+        assert_eq!(cmd.packages, vec!["user-service", "order-service"]);
+        assert_eq!(cmd.config_file.as_deref(), Some("Actr.prod.toml"));
+        assert!(cmd.lock);
+        assert!(cmd.verbose);
+        assert_eq!(cmd.timeout, 3);
+    }
+
+    #[test]
+    fn test_deprecated_check_timeout_alias() {
+        let args: Vec<String> = ["actr", "check", "--timeout", "3"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let mut cli = Cli::try_parse_from(&args).unwrap();
+        apply_deprecated_check_timeout(&mut cli, &args);
+        assert_eq!(cli.timeout, None);
+        let Some(Commands::Check(cmd)) = &cli.command else {
+            panic!("expected the check command");
+        };
+        assert_eq!(cmd.timeout, 3);
+
+        let strings = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(!check_timeout_alias_used(&strings(&[
+            "actr",
+            "--timeout",
+            "60",
+            "check"
+        ])));
+        assert!(!check_timeout_alias_used(&strings(&[
+            "actr",
+            "check",
+            "--check-timeout",
+            "3",
+            "--timeout=60"
+        ])));
+        assert!(check_timeout_alias_used(&strings(&[
+            "actr",
+            "check",
+            "--timeout=3"
+        ])));
+    }
+
+    #[test]
+    fn test_parse_install_resolution() {
+        let cli =
+            Cli::try_parse_from(["actr", "install", "--resolution", "prefer-locked"]).unwrap();
+        let Some(Commands::Install(cmd)) = cli.command else {
+            panic!("expected the install command");
+        };
+        assert_eq!(
+            cmd.resolution,
+            actr_cli::core::ConflictResolution::PreferLocked
+        );
+        assert!(Cli::try_parse_from(["actr", "install", "--resolution", "newest"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_global_env() {
+        let cli = Cli::try_parse_from(["actr", "check", "--env", "prod"]).unwrap();
+        assert_eq!(cli.env.as_deref(), Some("prod"));
+    }
+
+    #[test]
+    fn test_mutating_commands_take_project_lock() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).unwrap().command.unwrap();
+        assert!(is_mutating_command(&parse(&["actr", "install"])));
+        assert!(is_mutating_command(&parse(&[
+            "actr", "config", "set", "a", "b"
+        ])));
+        assert!(!is_mutating_command(&parse(&[
+            "actr",
+            "install",
+            "--dry-run"
+        ])));
+        assert!(!is_mutating_command(&parse(&["actr", "cache", "stats"])));
+        assert!(is_mutating_command(&parse(&[
+            "actr", "new", "actor", "Chat"
+        ])));
+        assert!(is_mutating_command(&parse(&["actr", "proto", "fmt"])));
+        assert!(!is_mutating_command(&parse(&[
+            "actr", "proto", "fmt", "--check"
+        ])));
+        assert!(is_mutating_command(&parse(&[
+            "actr",
+            "discovery",
+            "--select",
+            "echo",
+            "--action",
+            "add"
+        ])));
+        assert!(!is_mutating_command(&parse(&[
+            "actr",
+            "discovery",
+            "--list-only"
+        ])));
+        assert!(!is_mutating_command(&parse(&[
+            "actr",
+            "discovery",
+            "--output",
+            "json",
+            "--select",
+            "echo"
+        ])));
+
+        let options = GlobalOptions {
+            config: Some("Actr.prod.toml".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config_path(&parse(&["actr", "gen", "-c", "other/Actr.toml"]), &options),
+            std::path::PathBuf::from("other/Actr.toml")
+        );
+        assert_eq!(
+            config_path(&parse(&["actr", "install"]), &options),
+            std::path::PathBuf::from("Actr.prod.toml")
+        );
+
+        let cli =
+            Cli::try_parse_from(["actr", "install", "--no-lock", "--lock-timeout", "5"]).unwrap();
+        assert!(cli.no_lock);
+        assert_eq!(cli.lock_timeout, 5);
+    }
+
+    #[test]
+    fn test_require_config_names_configured_path() {
+        let err = require_config(std::path::Path::new("missing/Actr.prod.toml")).unwrap_err();
+        assert!(err.to_string().contains("No missing/Actr.prod.toml found"));
+    }
+
+    #[test]
+    fn test_build_container() {
+        let container = build_container(&GlobalOptions::default());
+        assert!(container.is_ok());
+
+        let container = build_container(&GlobalOptions {
+            offline: true,
+            quiet: true,
+            ..GlobalOptions::default()
+        });
         assert!(container.is_ok());
+
+        let container = build_container(&GlobalOptions {
+            config: Some("does-not-exist/Actr.toml".into()),
+            ..GlobalOptions::default()
+        });
+        assert!(container.is_err());
     }
 }
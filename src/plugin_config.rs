@@ -1,3 +1,9 @@
+//! `.protoc-plugin.toml` - protoc plugin version requirements
+//!
+//! Each `[plugins]` entry is either a bare version (`"0.1.10"`, meaning at least
+//! that version) or a semver requirement such as `">=0.1.10, <0.2"` or `"~0.1.10"`.
+
+use semver::{Comparator, Op, Version, VersionReq};
 use serde::Deserialize;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -11,22 +17,88 @@ const CONFIG_FILE_NAME: &str = ".protoc-plugin.toml";
 struct ProtocPluginFile {
     version: Option<u32>,
     plugins: Option<HashMap<String, String>>,
+    release_url: Option<String>,
+}
+
+/// Version requirement for one plugin
+#[derive(Debug, Clone)]
+pub struct PluginRequirement {
+    raw: String,
+    req: VersionReq,
+    minimum: Option<String>,
+}
+
+impl PluginRequirement {
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        // A bare version keeps its historical meaning of "at least this version"
+        let req = if is_valid_version_string(value) {
+            VersionReq {
+                comparators: vec![Comparator {
+                    op: Op::GreaterEq,
+                    ..comparator_for(value)
+                }],
+            }
+        } else {
+            VersionReq::parse(value)
+                .map_err(|e| ActrCliError::config_error(format!("'{value}': {e}")))?
+        };
+        let minimum = req.comparators.iter().find_map(lower_bound);
+        Ok(Self {
+            raw: value.to_string(),
+            req,
+            minimum,
+        })
+    }
+
+    /// Whether an installed plugin version satisfies the requirement
+    pub fn matches(&self, installed: &str) -> bool {
+        parse_lenient(installed).is_some_and(|version| self.req.matches(&version))
+    }
+
+    /// Lowest version allowed by the requirement, used when installing the plugin
+    pub fn minimum(&self) -> Option<&str> {
+        self.minimum.as_deref()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl std::fmt::Display for PluginRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ProtocPluginConfig {
     path: PathBuf,
-    plugins: HashMap<String, String>,
+    plugins: HashMap<String, PluginRequirement>,
+    release_url: Option<String>,
 }
 
 impl ProtocPluginConfig {
+    /// Version requirement configured for `plugin`
+    pub fn requirement(&self, plugin: &str) -> Option<&PluginRequirement> {
+        self.plugins.get(plugin)
+    }
+
+    /// Lowest version allowed for `plugin`
     pub fn min_version(&self, plugin: &str) -> Option<&str> {
-        self.plugins.get(plugin).map(String::as_str)
+        self.requirement(plugin)
+            .and_then(PluginRequirement::minimum)
     }
 
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    /// Base URL of prebuilt plugin release artifacts, if overridden
+    pub fn release_url(&self) -> Option<&str> {
+        self.release_url.as_deref()
+    }
 }
 
 pub fn load_protoc_plugin_config(config_path: &Path) -> Result<Option<ProtocPluginConfig>> {
@@ -52,23 +124,26 @@ pub fn load_protoc_plugin_config(config_path: &Path) -> Result<Option<ProtocPlug
         )));
     }
 
-    let plugins = parsed.plugins.unwrap_or_default();
-    for (name, min_version) in &plugins {
-        if min_version.trim().is_empty() {
-            return Err(ActrCliError::config_error(format!(
-                "Minimum version for plugin '{name}' cannot be empty"
-            )));
-        }
-        if !is_valid_version_string(min_version) {
+    let mut plugins = HashMap::new();
+    for (name, requirement) in parsed.plugins.unwrap_or_default() {
+        if requirement.trim().is_empty() {
             return Err(ActrCliError::config_error(format!(
-                "Invalid minimum version '{min_version}' for plugin '{name}'"
+                "Version requirement for plugin '{name}' cannot be empty"
             )));
         }
+        let requirement = PluginRequirement::parse(&requirement).map_err(|e| {
+            ActrCliError::config_error(format!(
+                "Invalid version requirement for plugin '{name}' in {}: {e}",
+                plugin_path.display()
+            ))
+        })?;
+        plugins.insert(name, requirement);
     }
 
     Ok(Some(ProtocPluginConfig {
         path: plugin_path,
         plugins,
+        release_url: parsed.release_url,
     }))
 }
 
@@ -103,3 +178,113 @@ fn is_valid_version_string(value: &str) -> bool {
     value.chars().all(|c| c.is_ascii_digit() || c == '.')
         && value.chars().any(|c| c.is_ascii_digit())
 }
+
+/// Extract the first dotted version number from `--version` output
+/// (e.g. "libprotoc 25.1" or "protoc-gen-actrframework 0.1.10")
+pub fn parse_version_output(output: &str) -> Option<String> {
+    output.split_whitespace().find_map(|token| {
+        let token = token
+            .trim_start_matches('v')
+            .trim_end_matches(|c: char| !c.is_ascii_digit());
+        let is_version = token.contains('.')
+            && token.chars().all(|c| c.is_ascii_digit() || c == '.')
+            && token.starts_with(|c: char| c.is_ascii_digit());
+        is_version.then(|| token.to_string())
+    })
+}
+
+/// Version such as "25.1" or "v0.1.10", missing components default to 0
+fn parse_lenient(value: &str) -> Option<Version> {
+    let value = value.trim().trim_start_matches('v');
+    if let Ok(version) = Version::parse(value) {
+        return Some(version);
+    }
+    if !is_valid_version_string(value) {
+        return None;
+    }
+    let mut parts = value.split('.').map(|p| p.parse::<u64>().unwrap_or(0));
+    Some(Version::new(
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    ))
+}
+
+fn comparator_for(value: &str) -> Comparator {
+    let version = parse_lenient(value).unwrap_or_else(|| Version::new(0, 0, 0));
+    Comparator {
+        op: Op::Exact,
+        major: version.major,
+        minor: Some(version.minor),
+        patch: Some(version.patch),
+        pre: version.pre,
+    }
+}
+
+fn lower_bound(comparator: &Comparator) -> Option<String> {
+    match comparator.op {
+        Op::Exact | Op::GreaterEq | Op::Caret | Op::Tilde | Op::Wildcard => Some(format!(
+            "{}.{}.{}",
+            comparator.major,
+            comparator.minor.unwrap_or(0),
+            comparator.patch.unwrap_or(0)
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bare_version_means_at_least() {
+        let requirement = PluginRequirement::parse("0.1.10").unwrap();
+        assert!(requirement.matches("0.1.10"));
+        assert!(requirement.matches("0.2.0"));
+        assert!(!requirement.matches("0.1.9"));
+        assert_eq!(requirement.minimum(), Some("0.1.10"));
+    }
+
+    #[test]
+    fn test_semver_ranges() {
+        let requirement = PluginRequirement::parse(">=0.1.10, <0.2").unwrap();
+        assert!(requirement.matches("0.1.12"));
+        assert!(!requirement.matches("0.2.0"));
+        assert_eq!(requirement.minimum(), Some("0.1.10"));
+
+        let tilde = PluginRequirement::parse("~0.1.10").unwrap();
+        assert!(tilde.matches("v0.1.11"));
+        assert!(!tilde.matches("0.2.0"));
+
+        assert!(PluginRequirement::parse("not-a-version").is_err());
+    }
+
+    #[test]
+    fn test_parse_version_output() {
+        assert_eq!(
+            parse_version_output("libprotoc 25.1\n"),
+            Some("25.1".to_string())
+        );
+        assert_eq!(
+            parse_version_output("protoc-gen-actrframework 0.1.10"),
+            Some("0.1.10".to_string())
+        );
+        assert_eq!(
+            parse_version_output("ktlint v1.2.1"),
+            Some("1.2.1".to_string())
+        );
+        assert_eq!(parse_version_output("no version here"), None);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_requirement() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(CONFIG_FILE_NAME),
+            "version = 1\n\n[plugins]\nprotoc-gen-actrframework = \">=banana\"\n",
+        )
+        .unwrap();
+        assert!(load_protoc_plugin_config(&temp.path().join("Actr.toml")).is_err());
+    }
+}
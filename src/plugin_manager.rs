@@ -0,0 +1,231 @@
+//! Prebuilt protoc plugin installer
+//!
+//! Downloads `protoc-gen-actrframework[-swift|-kotlin]` release binaries for the
+//! host OS/arch, verifies them against the published `.sha256` checksum and
+//! installs them into `~/.actr/bin`. The release base URL defaults to the actr
+//! GitHub releases and can be overridden with `ACTR_PLUGIN_RELEASE_URL` or the
+//! `release_url` key in `.protoc-plugin.toml`.
+
+use crate::error::{ActrCliError, Result};
+use crate::plugin_config::load_protoc_plugin_config;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::Command as StdCommand;
+use tracing::{debug, info, warn};
+
+/// Environment variable overriding the release base URL
+pub const RELEASE_URL_ENV: &str = "ACTR_PLUGIN_RELEASE_URL";
+
+/// Default location of prebuilt plugin artifacts
+pub const DEFAULT_RELEASE_URL: &str = "https://github.com/actor-rtc/actr/releases/download";
+
+/// Directory prebuilt plugins are installed into (`~/.actr/bin`)
+pub fn plugin_bin_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir()
+        .ok_or_else(|| ActrCliError::config_error("Cannot determine home directory"))?;
+    Ok(home.join(".actr").join("bin"))
+}
+
+/// Locate a plugin, preferring `~/.actr/bin` over PATH
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    if let Ok(dir) = plugin_bin_dir() {
+        let installed = dir.join(format!("{name}{}", std::env::consts::EXE_SUFFIX));
+        if installed.is_file() {
+            return Some(installed);
+        }
+    }
+
+    let output = StdCommand::new("which").arg(name).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!path.is_empty()).then(|| PathBuf::from(path))
+}
+
+/// Locate a plugin, installing a prebuilt release when it is missing
+///
+/// Lookup order: the path in `env_var` (when given and set), `~/.actr/bin`, PATH,
+/// then a download of the minimum version `.protoc-plugin.toml` requires (the CLI
+/// version without an entry). Offline mode never downloads.
+pub async fn locate_or_install(
+    name: &str,
+    config_path: &Path,
+    env_var: Option<&str>,
+    offline: bool,
+) -> Result<PathBuf> {
+    if let Some(var) = env_var
+        && let Ok(value) = std::env::var(var)
+        && !value.trim().is_empty()
+    {
+        let path = PathBuf::from(value);
+        if path.is_file() {
+            debug!("Using {name} from {var}: {}", path.display());
+            return Ok(path);
+        }
+        warn!("{var} points to {}, which does not exist", path.display());
+    }
+
+    if let Some(path) = find_plugin(name) {
+        return Ok(path);
+    }
+
+    if offline {
+        return Err(ActrCliError::command_error(format!(
+            "Offline mode: {name} is not installed in ~/.actr/bin or on PATH and cannot be downloaded"
+        )));
+    }
+    let version = load_protoc_plugin_config(config_path)?
+        .and_then(|config| config.min_version(name).map(str::to_string))
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string());
+    info!("📦 {name} not found, installing v{version}...");
+    PluginInstaller::from_config(config_path)?
+        .install(name, &version)
+        .await
+}
+
+/// Release target triple of the running host
+pub fn host_target() -> Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Ok("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") => Ok("aarch64-unknown-linux-gnu"),
+        ("macos", "x86_64") => Ok("x86_64-apple-darwin"),
+        ("macos", "aarch64") => Ok("aarch64-apple-darwin"),
+        ("windows", "x86_64") => Ok("x86_64-pc-windows-msvc"),
+        (os, arch) => Err(ActrCliError::Unsupported(format!(
+            "No prebuilt protoc plugins for {arch}-{os}"
+        ))),
+    }
+}
+
+/// Downloads and installs prebuilt plugin binaries
+#[derive(Debug, Clone)]
+pub struct PluginInstaller {
+    release_url: String,
+}
+
+impl PluginInstaller {
+    pub fn new(release_url: impl Into<String>) -> Self {
+        Self {
+            release_url: release_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Resolve the release URL from the environment, `.protoc-plugin.toml` or the default
+    pub fn from_config(config_path: &Path) -> Result<Self> {
+        if let Ok(url) = std::env::var(RELEASE_URL_ENV)
+            && !url.trim().is_empty()
+        {
+            return Ok(Self::new(url));
+        }
+        let configured = load_protoc_plugin_config(config_path)?
+            .and_then(|config| config.release_url().map(str::to_string));
+        Ok(Self::new(
+            configured.unwrap_or_else(|| DEFAULT_RELEASE_URL.to_string()),
+        ))
+    }
+
+    /// URL of the plugin binary for a version and target
+    pub fn artifact_url(&self, plugin: &str, version: &str, target: &str) -> String {
+        format!(
+            "{}/v{version}/{plugin}-{version}-{target}{}",
+            self.release_url,
+            std::env::consts::EXE_SUFFIX
+        )
+    }
+
+    /// Download, verify and install `plugin` at `version`, returning the installed path
+    pub async fn install(&self, plugin: &str, version: &str) -> Result<PathBuf> {
+        let target = host_target()?;
+        let url = self.artifact_url(plugin, version, target);
+        info!("📥 Downloading {plugin} v{version} ({target})...");
+        debug!("Plugin artifact URL: {url}");
+
+        let binary = fetch(&url).await?;
+        let checksum = fetch(&format!("{url}.sha256")).await?;
+        verify_checksum(&binary, &String::from_utf8_lossy(&checksum))
+            .map_err(|e| ActrCliError::command_error(format!("{url}: {e}")))?;
+
+        let bin_dir = plugin_bin_dir()?;
+        std::fs::create_dir_all(&bin_dir)?;
+        let destination = bin_dir.join(format!("{plugin}{}", std::env::consts::EXE_SUFFIX));
+
+        // Write next to the destination and rename so a failed download never leaves
+        // a truncated plugin behind
+        let staging = tempfile::NamedTempFile::new_in(&bin_dir)?;
+        std::fs::write(staging.path(), &binary)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(staging.path(), std::fs::Permissions::from_mode(0o755))?;
+        }
+        staging
+            .persist(&destination)
+            .map_err(|e| ActrCliError::Io(e.error))?;
+
+        info!(
+            "✅ Installed {plugin} v{version} to {}",
+            destination.display()
+        );
+        Ok(destination)
+    }
+}
+
+async fn fetch(url: &str) -> Result<Vec<u8>> {
+    let client = crate::core::NetworkSettings::from_env()?.http_client()?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(ActrCliError::command_error(format!(
+            "Failed to download {url}: HTTP {}",
+            response.status()
+        )));
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Check `bytes` against a `sha256sum`-style checksum file ("<hex>  <file>")
+fn verify_checksum(bytes: &[u8], checksum_file: &str) -> std::result::Result<(), String> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| "empty checksum file".to_string())?
+        .to_ascii_lowercase();
+    let actual = hex::encode(Sha256::digest(bytes));
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "checksum mismatch (expected {expected}, got {actual})"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_url() {
+        let installer = PluginInstaller::new("https://example.com/releases/");
+        let url = installer.artifact_url(
+            "protoc-gen-actrframework",
+            "0.1.10",
+            "x86_64-unknown-linux-gnu",
+        );
+        assert_eq!(
+            url,
+            format!(
+                "https://example.com/releases/v0.1.10/protoc-gen-actrframework-0.1.10-x86_64-unknown-linux-gnu{}",
+                std::env::consts::EXE_SUFFIX
+            )
+        );
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let digest = hex::encode(Sha256::digest(b"plugin"));
+        assert!(verify_checksum(b"plugin", &format!("{digest}  protoc-gen-actrframework")).is_ok());
+        assert!(verify_checksum(b"tampered", &digest).is_err());
+        assert!(verify_checksum(b"plugin", "").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Advisory project lock
+//!
+//! Commands that write Actr.toml, Actr.lock.toml or the proto cache hold an exclusive
+//! lock on `.actr/lock` for their whole run, so two `actr install` invocations in the
+//! same project cannot interleave their writes. The lock is an OS file lock (released
+//! when the process exits, even on a crash); the file itself only carries the holder
+//! description shown to waiting processes:
+//!
+//! ```json
+//! {"pid":4242,"command":"install","started_at":"2025-01-10T08:00:00Z"}
+//! ```
+
+use crate::core::ActrCliError;
+use crate::history::HISTORY_DIR;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const LOCK_FILE: &str = "lock";
+
+/// Delay between two attempts while another process holds the lock
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The process currently holding the project lock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+}
+
+impl std::fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pid {} ('actr {}', running since {})",
+            self.pid,
+            self.command,
+            self.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+        )
+    }
+}
+
+/// Exclusive lock on a project, released on drop
+#[derive(Debug)]
+pub struct ProjectLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl ProjectLock {
+    /// Path of the lock file of a project
+    pub fn path_for(project_root: &Path) -> PathBuf {
+        project_root.join(HISTORY_DIR).join(LOCK_FILE)
+    }
+
+    /// Acquire the lock, waiting up to `wait` for another actr process to release it
+    pub async fn acquire(project_root: &Path, command: &str, wait: Duration) -> Result<Self> {
+        let path = Self::path_for(project_root);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // Not truncated on open: the current holder's description must survive
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        let deadline = Instant::now() + wait;
+        let mut announced = false;
+        loop {
+            match file.try_lock_exclusive() {
+                Ok(()) => break,
+                Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+                    let holder = describe_holder(&mut file);
+                    if Instant::now() >= deadline {
+                        return Err(ActrCliError::ProjectLocked {
+                            holder,
+                            path: path.display().to_string(),
+                        }
+                        .into());
+                    }
+                    if !announced {
+                        eprintln!(
+                            "⏳ Waiting for {holder} to release {} (up to {}s)",
+                            path.display(),
+                            wait.as_secs()
+                        );
+                        announced = true;
+                    }
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+                Err(e) => {
+                    return Err(
+                        anyhow::Error::new(e).context(format!("Failed to lock {}", path.display()))
+                    );
+                }
+            }
+        }
+
+        let holder = LockHolder {
+            pid: std::process::id(),
+            command: command.to_string(),
+            started_at: Utc::now(),
+        };
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serde_json::to_string(&holder)?.as_bytes())?;
+        file.flush()?;
+
+        Ok(Self { file, path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        // Clear the description first so a stale holder is never reported
+        let _ = self.file.set_len(0);
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Read the holder description; the file may be empty or unreadable on some platforms
+fn describe_holder(file: &mut File) -> String {
+    let mut content = String::new();
+    let holder = file
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| file.read_to_string(&mut content))
+        .ok()
+        .and_then(|_| serde_json::from_str::<LockHolder>(&content).ok());
+    match holder {
+        Some(holder) => holder.to_string(),
+        None => "another actr process".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_second_acquire_times_out_naming_holder() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = ProjectLock::acquire(dir.path(), "install", Duration::ZERO)
+            .await
+            .unwrap();
+
+        let err = ProjectLock::acquire(dir.path(), "remove", Duration::from_millis(250))
+            .await
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains(&format!("pid {}", std::process::id())));
+        assert!(message.contains("actr install"));
+
+        drop(lock);
+        let content = std::fs::read_to_string(ProjectLock::path_for(dir.path())).unwrap();
+        assert!(content.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_succeeds_once_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = ProjectLock::acquire(dir.path(), "install", Duration::ZERO)
+            .await
+            .unwrap();
+
+        let root = dir.path().to_path_buf();
+        let waiter = tokio::spawn(async move {
+            ProjectLock::acquire(&root, "update", Duration::from_secs(5)).await
+        });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        drop(lock);
+
+        let lock = waiter.await.unwrap().unwrap();
+        let content = std::fs::read_to_string(lock.path()).unwrap();
+        let holder: LockHolder = serde_json::from_str(&content).unwrap();
+        assert_eq!(holder.command, "update");
+    }
+}
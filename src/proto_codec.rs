@@ -0,0 +1,761 @@
+//! JSON ↔ protobuf conversion driven by parsed proto sources
+//!
+//! [`ProtoRegistry`] collects the messages, enums and RPCs of `.proto` sources
+//! (see [`crate::proto_parser`]) and converts messages between the proto3 JSON
+//! mapping and the protobuf wire format without protoc or generated types. It
+//! backs the tools that only have a service's protos at hand (`actr call`,
+//! `actr mock`).
+//!
+//! JSON field names may be lowerCamelCase or as declared. 64-bit integers are
+//! written as strings, bytes as base64 and enums by value name. Decoded messages
+//! list every field, with the defaults filled in. Well-known types get no special
+//! JSON form; they are only known when their protos are part of the sources.
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use prost::encoding::{WireType, decode_key, decode_varint, encode_key, encode_varint};
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+use crate::proto_parser::{EnumValueDef, FieldDef, FieldLabel, MessageDef, ProtoSchema};
+
+/// Nesting depth after which default messages are left empty (recursive types)
+const MAX_DEFAULT_DEPTH: usize = 8;
+
+const SCALARS: [&str; 15] = [
+    "double", "float", "int64", "uint64", "int32", "fixed64", "fixed32", "bool", "string", "bytes",
+    "uint32", "sfixed32", "sfixed64", "sint32", "sint64",
+];
+
+/// One RPC declared by the sources
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcMethod {
+    /// `package.Service`
+    pub service: String,
+    pub method: String,
+    /// Fully qualified message names
+    pub input_type: String,
+    pub output_type: String,
+    pub streaming: bool,
+}
+
+impl RpcMethod {
+    /// Route of the RPC, `/package.Service/Method`
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.service, self.method)
+    }
+
+    /// Service name without the package
+    pub fn short_service(&self) -> &str {
+        self.service.rsplit('.').next().unwrap_or(&self.service)
+    }
+}
+
+/// Messages, enums and RPCs of a set of proto sources
+#[derive(Debug, Clone, Default)]
+pub struct ProtoRegistry {
+    /// Fully qualified message name → definition
+    messages: HashMap<String, MessageDef>,
+    /// Fully qualified enum name → values in declaration order
+    enums: HashMap<String, Vec<EnumValueDef>>,
+    rpcs: Vec<RpcMethod>,
+}
+
+/// What a field type refers to
+enum Kind<'a> {
+    Scalar(&'static str),
+    Enum(&'a [EnumValueDef]),
+    Message(String),
+}
+
+/// A field value as read from the wire
+#[derive(Clone, Copy)]
+enum Raw<'a> {
+    Varint(u64),
+    Fixed32(u32),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+}
+
+impl ProtoRegistry {
+    /// Parse proto sources; RPC types are resolved across all of them
+    pub fn from_sources<'a>(sources: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut registry = Self::default();
+        let mut methods = Vec::new();
+        for source in sources {
+            let schema = ProtoSchema::parse(source)?;
+            for message in &schema.messages {
+                registry
+                    .messages
+                    .insert(schema.qualified(&message.name), message.clone());
+            }
+            for definition in &schema.enums {
+                registry.enums.insert(
+                    schema.qualified(&definition.name),
+                    definition.values.clone(),
+                );
+            }
+            let package = schema.package.clone().unwrap_or_default();
+            for service in &schema.services {
+                for method in &service.methods {
+                    methods.push((
+                        package.clone(),
+                        schema.qualified(&service.name),
+                        method.clone(),
+                    ));
+                }
+            }
+        }
+
+        for (package, service, method) in methods {
+            let message_type = |type_name: &str| match registry.resolve(type_name, &package) {
+                Some(Kind::Message(name)) => name,
+                _ => type_name.trim_start_matches('.').to_string(),
+            };
+            let rpc = RpcMethod {
+                service,
+                method: method.name.clone(),
+                input_type: message_type(&method.input_type),
+                output_type: message_type(&method.output_type),
+                streaming: method.client_streaming || method.server_streaming,
+            };
+            registry.rpcs.push(rpc);
+        }
+        Ok(registry)
+    }
+
+    pub fn rpcs(&self) -> &[RpcMethod] {
+        &self.rpcs
+    }
+
+    /// RPC named `Method`, `Service/Method` or `package.Service/Method`
+    pub fn find_rpc(&self, name: &str) -> Result<&RpcMethod> {
+        let name = name.trim_start_matches('/');
+        let matches: Vec<&RpcMethod> = self
+            .rpcs
+            .iter()
+            .filter(|rpc| match name.rsplit_once('/') {
+                Some((service, method)) => {
+                    rpc.method == method
+                        && (rpc.service == service || rpc.short_service() == service)
+                }
+                None => rpc.method == name,
+            })
+            .collect();
+        match matches.as_slice() {
+            [rpc] => Ok(rpc),
+            [] => {
+                let known: Vec<String> = self.rpcs.iter().map(RpcMethod::path).collect();
+                bail!("No RPC '{name}'; available: {}", known.join(", "))
+            }
+            _ => {
+                let candidates: Vec<String> = matches.iter().map(|rpc| rpc.path()).collect();
+                bail!(
+                    "RPC '{name}' is ambiguous, use Service/Method: {}",
+                    candidates.join(", ")
+                )
+            }
+        }
+    }
+
+    /// Proto3 JSON of a message with every field at its default value
+    pub fn default_json(&self, type_name: &str) -> Value {
+        Value::Object(self.default_message(type_name, 0))
+    }
+
+    /// Encode the JSON form of a message
+    pub fn encode(&self, type_name: &str, json: &Value) -> Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        self.encode_message(type_name, json, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Decode a message into its JSON form
+    pub fn decode(&self, type_name: &str, bytes: &[u8]) -> Result<Value> {
+        self.decode_message(type_name, bytes)
+    }
+
+    /// Resolve a type used in the fully qualified `scope` the way protoc does:
+    /// innermost scope first, then outwards to the root
+    fn resolve(&self, type_name: &str, scope: &str) -> Option<Kind<'_>> {
+        let lookup = |name: &str| {
+            if self.messages.contains_key(name) {
+                Some(Kind::Message(name.to_string()))
+            } else {
+                self.enums
+                    .get(name)
+                    .map(|values| Kind::Enum(values.as_slice()))
+            }
+        };
+        if let Some(absolute) = type_name.strip_prefix('.') {
+            return lookup(absolute);
+        }
+
+        let mut candidates: Vec<String> = Vec::new();
+        let mut current = scope;
+        while !current.is_empty() {
+            candidates.push(format!("{current}.{type_name}"));
+            current = current.rsplit_once('.').map_or("", |(parent, _)| parent);
+        }
+        candidates.push(type_name.to_string());
+        candidates.iter().find_map(|candidate| lookup(candidate))
+    }
+
+    fn kind(&self, type_name: &str, scope: &str) -> Result<Kind<'_>> {
+        if let Some(scalar) = SCALARS.iter().find(|scalar| **scalar == type_name) {
+            return Ok(Kind::Scalar(*scalar));
+        }
+        self.resolve(type_name, scope)
+            .ok_or_else(|| anyhow!("Type '{type_name}' is not defined in the protos"))
+    }
+
+    fn message(&self, type_name: &str) -> Result<&MessageDef> {
+        self.messages
+            .get(type_name)
+            .ok_or_else(|| anyhow!("Message '{type_name}' is not defined in the protos"))
+    }
+
+    fn default_message(&self, type_name: &str, depth: usize) -> Map<String, Value> {
+        let mut object = Map::new();
+        let Some(message) = self.messages.get(type_name) else {
+            return object;
+        };
+        if depth >= MAX_DEFAULT_DEPTH {
+            return object;
+        }
+        for field in &message.fields {
+            // Only the first member of a oneof is set
+            if let Some(oneof) = &field.oneof
+                && message
+                    .fields
+                    .iter()
+                    .find(|other| other.oneof.as_ref() == Some(oneof))
+                    .is_some_and(|first| first.name != field.name)
+            {
+                continue;
+            }
+            let value = if field.label == FieldLabel::Repeated {
+                Value::Array(Vec::new())
+            } else if map_types(&field.type_name).is_some() {
+                Value::Object(Map::new())
+            } else {
+                match self.kind(&field.type_name, type_name) {
+                    Ok(kind) => self.default_value(&kind, depth),
+                    Err(_) => Value::Object(Map::new()),
+                }
+            };
+            object.insert(json_name(&field.name), value);
+        }
+        object
+    }
+
+    fn default_value(&self, kind: &Kind, depth: usize) -> Value {
+        match kind {
+            Kind::Scalar("string" | "bytes") => Value::String(String::new()),
+            Kind::Scalar("bool") => Value::Bool(false),
+            // 64-bit integers are strings in proto3 JSON
+            Kind::Scalar("int64" | "uint64" | "sint64" | "fixed64" | "sfixed64") => {
+                Value::String("0".to_string())
+            }
+            Kind::Scalar("float" | "double") => Value::from(0.0),
+            Kind::Scalar(_) => Value::from(0),
+            Kind::Enum(values) => values
+                .first()
+                .map_or(Value::from(0), |value| Value::String(value.name.clone())),
+            Kind::Message(name) => Value::Object(self.default_message(name, depth + 1)),
+        }
+    }
+
+    fn encode_message(&self, type_name: &str, json: &Value, buf: &mut Vec<u8>) -> Result<()> {
+        let message = self.message(type_name)?;
+        let object = json
+            .as_object()
+            .ok_or_else(|| anyhow!("{type_name}: expected a JSON object, got {json}"))?;
+        for (key, value) in object {
+            let field = message
+                .fields
+                .iter()
+                .find(|field| field.name == *key || json_name(&field.name) == *key)
+                .ok_or_else(|| anyhow!("{type_name}: unknown field '{key}'"))?;
+            if value.is_null() {
+                continue;
+            }
+            self.encode_field(field, value, type_name, buf)
+                .with_context(|| format!("Invalid value for {type_name}.{}", field.name))?;
+        }
+        Ok(())
+    }
+
+    fn encode_field(
+        &self,
+        field: &FieldDef,
+        value: &Value,
+        scope: &str,
+        buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        let number = u32::try_from(field.number)?;
+        if let Some((key_type, value_type)) = map_types(&field.type_name) {
+            let entries = value
+                .as_object()
+                .ok_or_else(|| anyhow!("expected a JSON object, got {value}"))?;
+            let (key_kind, value_kind) =
+                (self.kind(key_type, scope)?, self.kind(value_type, scope)?);
+            for (key, entry_value) in entries {
+                let mut entry = Vec::new();
+                self.encode_value(1, &key_kind, &Value::String(key.clone()), &mut entry)?;
+                self.encode_value(2, &value_kind, entry_value, &mut entry)?;
+                length_delimited(number, &entry, buf);
+            }
+            return Ok(());
+        }
+
+        let kind = self.kind(&field.type_name, scope)?;
+        if field.label == FieldLabel::Repeated {
+            let items = value
+                .as_array()
+                .ok_or_else(|| anyhow!("expected a JSON array, got {value}"))?;
+            for item in items {
+                self.encode_value(number, &kind, item, buf)?;
+            }
+            return Ok(());
+        }
+        self.encode_value(number, &kind, value, buf)
+    }
+
+    fn encode_value(
+        &self,
+        number: u32,
+        kind: &Kind,
+        value: &Value,
+        buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        let scalar = match kind {
+            Kind::Message(name) => {
+                let mut nested = Vec::new();
+                self.encode_message(name, value, &mut nested)?;
+                length_delimited(number, &nested, buf);
+                return Ok(());
+            }
+            Kind::Enum(values) => {
+                let number_value = match value {
+                    Value::String(name) => values
+                        .iter()
+                        .find(|candidate| candidate.name == *name)
+                        .map(|candidate| candidate.number)
+                        .ok_or_else(|| anyhow!("unknown enum value '{name}'"))?,
+                    other => json_int(other)?,
+                };
+                encode_key(number, WireType::Varint, buf);
+                encode_varint(number_value as u64, buf);
+                return Ok(());
+            }
+            Kind::Scalar(scalar) => *scalar,
+        };
+
+        match scalar {
+            "int32" | "int64" | "uint32" | "uint64" | "sint32" | "sint64" | "bool" => {
+                let varint = match scalar {
+                    "int32" => i64::from(i32::try_from(json_int(value)?)?) as u64,
+                    "int64" => json_int(value)? as u64,
+                    "uint32" => u64::from(u32::try_from(json_uint(value)?)?),
+                    "uint64" => json_uint(value)?,
+                    "sint32" => zigzag_encode(i64::from(i32::try_from(json_int(value)?)?)),
+                    "sint64" => zigzag_encode(json_int(value)?),
+                    _ => u64::from(json_bool(value)?),
+                };
+                encode_key(number, WireType::Varint, buf);
+                encode_varint(varint, buf);
+            }
+            "fixed32" | "sfixed32" | "float" => {
+                let bytes = match scalar {
+                    "fixed32" => u32::try_from(json_uint(value)?)?.to_le_bytes(),
+                    "sfixed32" => i32::try_from(json_int(value)?)?.to_le_bytes(),
+                    _ => (json_float(value)? as f32).to_le_bytes(),
+                };
+                encode_key(number, WireType::ThirtyTwoBit, buf);
+                buf.extend_from_slice(&bytes);
+            }
+            "fixed64" | "sfixed64" | "double" => {
+                let bytes = match scalar {
+                    "fixed64" => json_uint(value)?.to_le_bytes(),
+                    "sfixed64" => json_int(value)?.to_le_bytes(),
+                    _ => json_float(value)?.to_le_bytes(),
+                };
+                encode_key(number, WireType::SixtyFourBit, buf);
+                buf.extend_from_slice(&bytes);
+            }
+            "string" => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("expected a string, got {value}"))?;
+                length_delimited(number, text.as_bytes(), buf);
+            }
+            _ => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("expected a base64 string, got {value}"))?;
+                let bytes = BASE64.decode(text).context("expected base64")?;
+                length_delimited(number, &bytes, buf);
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_message(&self, type_name: &str, mut buf: &[u8]) -> Result<Value> {
+        let message = self.message(type_name)?;
+        let mut object = self.default_message(type_name, 0);
+        while !buf.is_empty() {
+            let (number, wire_type) = decode_key(&mut buf)?;
+            let raw = read_raw(wire_type, &mut buf)?;
+            // Unknown fields are skipped, as by every protobuf runtime
+            let Some(field) = message
+                .fields
+                .iter()
+                .find(|field| field.number == i64::from(number))
+            else {
+                continue;
+            };
+            let name = json_name(&field.name);
+            let decoded = self
+                .decode_field(field, raw, type_name)
+                .with_context(|| format!("Failed to decode {type_name}.{}", field.name))?;
+            match decoded {
+                Decoded::Entry(key, value) => {
+                    if let Some(Value::Object(map)) = object.get_mut(&name) {
+                        map.insert(key, value);
+                    }
+                }
+                Decoded::Items(values) => {
+                    if let Some(Value::Array(items)) = object.get_mut(&name) {
+                        items.extend(values);
+                    }
+                }
+                Decoded::Value(value) => {
+                    if let Some(oneof) = &field.oneof {
+                        for member in message
+                            .fields
+                            .iter()
+                            .filter(|member| member.oneof.as_ref() == Some(oneof))
+                        {
+                            object.remove(&json_name(&member.name));
+                        }
+                    }
+                    object.insert(name, value);
+                }
+            }
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn decode_field(&self, field: &FieldDef, raw: Raw, scope: &str) -> Result<Decoded> {
+        if let Some((key_type, value_type)) = map_types(&field.type_name) {
+            let Raw::Bytes(mut entry) = raw else {
+                bail!("map entry is not length-delimited");
+            };
+            let (key_kind, value_kind) =
+                (self.kind(key_type, scope)?, self.kind(value_type, scope)?);
+            let mut key = self.default_value(&key_kind, 0);
+            let mut value = self.default_value(&value_kind, 0);
+            while !entry.is_empty() {
+                let (number, wire_type) = decode_key(&mut entry)?;
+                let raw = read_raw(wire_type, &mut entry)?;
+                match number {
+                    1 => key = self.decode_value(&key_kind, raw)?,
+                    2 => value = self.decode_value(&value_kind, raw)?,
+                    _ => {}
+                }
+            }
+            let key = match key {
+                Value::String(key) => key,
+                other => other.to_string(),
+            };
+            return Ok(Decoded::Entry(key, value));
+        }
+
+        let kind = self.kind(&field.type_name, scope)?;
+        if field.label != FieldLabel::Repeated {
+            return Ok(Decoded::Value(self.decode_value(&kind, raw)?));
+        }
+        // Numeric repeated fields may arrive packed into one length-delimited record
+        match (raw, packed_wire_type(&kind)) {
+            (Raw::Bytes(mut packed), Some(wire_type)) => {
+                let mut items = Vec::new();
+                while !packed.is_empty() {
+                    items.push(self.decode_value(&kind, read_raw(wire_type, &mut packed)?)?);
+                }
+                Ok(Decoded::Items(items))
+            }
+            (raw, _) => Ok(Decoded::Items(vec![self.decode_value(&kind, raw)?])),
+        }
+    }
+
+    fn decode_value(&self, kind: &Kind, raw: Raw) -> Result<Value> {
+        Ok(match (kind, raw) {
+            (Kind::Message(name), Raw::Bytes(bytes)) => self.decode_message(name, bytes)?,
+            (Kind::Enum(values), Raw::Varint(varint)) => {
+                let number = i64::from(varint as i32);
+                values
+                    .iter()
+                    .find(|value| value.number == number)
+                    .map_or(Value::from(number), |value| {
+                        Value::String(value.name.clone())
+                    })
+            }
+            (Kind::Scalar(scalar), raw) => match (*scalar, raw) {
+                ("int32", Raw::Varint(varint)) => Value::from(varint as i32),
+                ("int64", Raw::Varint(varint)) => Value::String((varint as i64).to_string()),
+                ("uint32", Raw::Varint(varint)) => Value::from(varint as u32),
+                ("uint64", Raw::Varint(varint)) => Value::String(varint.to_string()),
+                ("sint32", Raw::Varint(varint)) => Value::from(zigzag_decode(varint) as i32),
+                ("sint64", Raw::Varint(varint)) => Value::String(zigzag_decode(varint).to_string()),
+                ("bool", Raw::Varint(varint)) => Value::Bool(varint != 0),
+                ("fixed32", Raw::Fixed32(bits)) => Value::from(bits),
+                ("sfixed32", Raw::Fixed32(bits)) => Value::from(bits as i32),
+                // Through the shortest decimal form so 0.1f32 stays 0.1
+                ("float", Raw::Fixed32(bits)) => {
+                    float_json(f32::from_bits(bits).to_string().parse().unwrap_or(f64::NAN))
+                }
+                ("fixed64", Raw::Fixed64(bits)) => Value::String(bits.to_string()),
+                ("sfixed64", Raw::Fixed64(bits)) => Value::String((bits as i64).to_string()),
+                ("double", Raw::Fixed64(bits)) => float_json(f64::from_bits(bits)),
+                ("string", Raw::Bytes(bytes)) => Value::String(String::from_utf8(bytes.to_vec())?),
+                ("bytes", Raw::Bytes(bytes)) => Value::String(BASE64.encode(bytes)),
+                (scalar, _) => bail!("unexpected wire type for {scalar}"),
+            },
+            _ => bail!("unexpected wire type"),
+        })
+    }
+}
+
+/// Field value decoded from one record
+enum Decoded {
+    Value(Value),
+    Items(Vec<Value>),
+    Entry(String, Value),
+}
+
+/// `map<K, V>` → `(K, V)`
+fn map_types(type_name: &str) -> Option<(&str, &str)> {
+    let inner = type_name.strip_prefix("map<")?.strip_suffix('>')?;
+    let (key, value) = inner.split_once(',')?;
+    Some((key.trim(), value.trim()))
+}
+
+/// proto3 JSON name of a field: `user_id` → `userId`
+fn json_name(name: &str) -> String {
+    heck::AsLowerCamelCase(name).to_string()
+}
+
+fn packed_wire_type(kind: &Kind) -> Option<WireType> {
+    match kind {
+        Kind::Enum(_) => Some(WireType::Varint),
+        Kind::Scalar("string" | "bytes") | Kind::Message(_) => None,
+        Kind::Scalar("fixed32" | "sfixed32" | "float") => Some(WireType::ThirtyTwoBit),
+        Kind::Scalar("fixed64" | "sfixed64" | "double") => Some(WireType::SixtyFourBit),
+        Kind::Scalar(_) => Some(WireType::Varint),
+    }
+}
+
+fn read_raw<'a>(wire_type: WireType, buf: &mut &'a [u8]) -> Result<Raw<'a>> {
+    Ok(match wire_type {
+        WireType::Varint => Raw::Varint(decode_varint(buf)?),
+        WireType::ThirtyTwoBit => Raw::Fixed32(u32::from_le_bytes(take(buf, 4)?.try_into()?)),
+        WireType::SixtyFourBit => Raw::Fixed64(u64::from_le_bytes(take(buf, 8)?.try_into()?)),
+        WireType::LengthDelimited => {
+            let len = usize::try_from(decode_varint(buf)?)?;
+            Raw::Bytes(take(buf, len)?)
+        }
+        WireType::StartGroup | WireType::EndGroup => bail!("Groups are not supported"),
+    })
+}
+
+fn take<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if buf.len() < len {
+        bail!("Message is truncated");
+    }
+    let (head, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(head)
+}
+
+fn length_delimited(number: u32, bytes: &[u8], buf: &mut Vec<u8>) {
+    encode_key(number, WireType::LengthDelimited, buf);
+    encode_varint(bytes.len() as u64, buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn float_json(value: f64) -> Value {
+    Number::from_f64(value).map_or_else(
+        || {
+            Value::String(
+                if value.is_nan() {
+                    "NaN"
+                } else if value > 0.0 {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                }
+                .to_string(),
+            )
+        },
+        Value::Number,
+    )
+}
+
+fn json_int(value: &Value) -> Result<i64> {
+    match value {
+        Value::Number(number) => number.as_i64().or_else(|| {
+            number
+                .as_f64()
+                .filter(|float| float.fract() == 0.0 && float.abs() < 9.2e18)
+                .map(|float| float as i64)
+        }),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("expected an integer, got {value}"))
+}
+
+fn json_uint(value: &Value) -> Result<u64> {
+    match value {
+        Value::Number(number) => number.as_u64().or_else(|| {
+            number
+                .as_f64()
+                .filter(|float| float.fract() == 0.0 && (0.0..1.8e19).contains(float))
+                .map(|float| float as u64)
+        }),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("expected an unsigned integer, got {value}"))
+}
+
+fn json_float(value: &Value) -> Result<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => match text.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            other => other.trim().parse().ok(),
+        },
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("expected a number, got {value}"))
+}
+
+fn json_bool(value: &Value) -> Result<bool> {
+    match value {
+        Value::Bool(flag) => Some(*flag),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| anyhow!("expected a boolean, got {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROTO: &str = r#"
+syntax = "proto3";
+package shop;
+
+enum Status { STATUS_UNKNOWN = 0; STATUS_ACTIVE = 1; }
+
+message Item {
+  string item_id = 1;
+  int64 stock = 2;
+  Status status = 3;
+  repeated int32 sizes = 4;
+  Price price = 5;
+  map<string, sint32> counts = 6;
+  bytes blob = 7;
+  oneof owner {
+    string user = 8;
+    string team = 9;
+  }
+  message Price { double amount = 1; float discount = 2; }
+}
+message GetItemRequest { string item_id = 1; }
+
+service InventoryService {
+  rpc GetItem (GetItemRequest) returns (Item);
+}
+"#;
+
+    #[test]
+    fn test_round_trip_and_defaults() {
+        let registry = ProtoRegistry::from_sources([PROTO]).unwrap();
+        let rpc = registry.find_rpc("InventoryService/GetItem").unwrap();
+        assert_eq!(rpc.input_type, "shop.GetItemRequest");
+        assert_eq!(rpc.output_type, "shop.Item");
+        assert!(registry.find_rpc("Missing").is_err());
+
+        let item = serde_json::json!({
+            "item_id": "a-1",
+            "stock": "9000000000",
+            "status": "STATUS_ACTIVE",
+            "sizes": [1, -2, 3],
+            "price": { "amount": 9.5, "discount": 0.1 },
+            "counts": { "red": -4 },
+            "blob": "AQI=",
+            "team": "ops",
+        });
+        let bytes = registry.encode("shop.Item", &item).unwrap();
+        assert_eq!(
+            registry.decode("shop.Item", &bytes).unwrap(),
+            serde_json::json!({
+                "itemId": "a-1",
+                "stock": "9000000000",
+                "status": "STATUS_ACTIVE",
+                "sizes": [1, -2, 3],
+                "price": { "amount": 9.5, "discount": 0.1 },
+                "counts": { "red": -4 },
+                "blob": "AQI=",
+                "team": "ops",
+            })
+        );
+
+        assert_eq!(
+            registry.default_json("shop.Item.Price"),
+            serde_json::json!({ "amount": 0.0, "discount": 0.0 })
+        );
+        let defaults = registry.default_json("shop.Item");
+        assert_eq!(defaults["user"], "");
+        assert!(defaults.get("team").is_none());
+    }
+
+    #[test]
+    fn test_encode_errors_and_packed_decoding() {
+        let registry = ProtoRegistry::from_sources([PROTO]).unwrap();
+        let err = registry
+            .encode("shop.Item", &serde_json::json!({ "colour": "red" }))
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown field 'colour'"));
+        let err = registry
+            .encode("shop.Item", &serde_json::json!({ "sizes": [1, "x"] }))
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("Invalid value for shop.Item.sizes"));
+        assert!(
+            registry
+                .encode("shop.Item", &serde_json::json!({ "status": "STATUS_GONE" }))
+                .is_err()
+        );
+
+        // sizes = [1, 2] packed, as protobuf runtimes write repeated scalars
+        let decoded = registry
+            .decode("shop.Item", &[0x22, 0x02, 0x01, 0x02])
+            .unwrap();
+        assert_eq!(decoded["sizes"], serde_json::json!([1, 2]));
+    }
+}
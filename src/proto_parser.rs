@@ -0,0 +1,736 @@
+//! Lightweight `.proto` parser
+//!
+//! Extracts the package, imports, messages, enums and services (including
+//! streaming flags, line numbers and leading comments) from proto sources
+//! without invoking protoc. Options are skipped, so the result suits diffing
+//! and linting; `actr gen` builds the same [`ProtoSchema`] from protoc
+//! descriptor sets instead. Nested messages and enums are flattened with dotted
+//! names (`Outer.Inner`).
+
+use anyhow::{Result, anyhow, bail};
+use serde::Serialize;
+
+/// Parsed contents of one proto file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProtoSchema {
+    pub syntax: Option<String>,
+    pub package: Option<String>,
+    pub imports: Vec<String>,
+    pub messages: Vec<MessageDef>,
+    pub enums: Vec<EnumDef>,
+    pub services: Vec<ServiceDef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MessageDef {
+    /// Name relative to the package; nested messages are joined with `.`
+    pub name: String,
+    pub fields: Vec<FieldDef>,
+    pub line: usize,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldLabel {
+    Singular,
+    Optional,
+    Repeated,
+    Required,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldDef {
+    pub name: String,
+    pub number: i64,
+    /// Type as written, e.g. `string`, `.acme.User` or `map<string, int32>`
+    pub type_name: String,
+    pub label: FieldLabel,
+    pub oneof: Option<String>,
+    pub line: usize,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnumDef {
+    pub name: String,
+    pub values: Vec<EnumValueDef>,
+    pub line: usize,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnumValueDef {
+    pub name: String,
+    pub number: i64,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServiceDef {
+    pub name: String,
+    pub methods: Vec<RpcDef>,
+    pub line: usize,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcDef {
+    pub name: String,
+    pub input_type: String,
+    pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    pub line: usize,
+    pub comment: Option<String>,
+}
+
+impl ProtoSchema {
+    /// Parse proto source text
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        Parser { tokens, pos: 0 }.parse_file()
+    }
+
+    /// Prefix `name` with the package (`acme.user.User`)
+    pub fn qualified(&self, name: &str) -> String {
+        match &self.package {
+            Some(package) => format!("{package}.{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    /// Normalize a type reference for comparison: drop the leading `.` and this
+    /// file's package prefix so `User`, `acme.User` and `.acme.User` compare equal
+    pub fn normalize_type<'a>(&self, type_name: &'a str) -> &'a str {
+        let trimmed = type_name.trim_start_matches('.');
+        match &self.package {
+            Some(package) => trimmed
+                .strip_prefix(package.as_str())
+                .and_then(|rest| rest.strip_prefix('.'))
+                .unwrap_or(trimmed),
+            None => trimmed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Int(String),
+    Str(String),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    /// Comment block directly preceding the token
+    comment: Option<String>,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pending_comment: Vec<String> = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                let start = i + 2;
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                push_comment(&mut pending_comment, &tokens, &text, line);
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                let start_line = line;
+                let start = i + 2;
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    bail!("line {start_line}: unterminated block comment");
+                }
+                let text: String = chars[start..i].iter().collect();
+                i += 2;
+                push_comment(&mut pending_comment, &tokens, &text, start_line);
+            }
+            '"' | '\'' => {
+                let quote = c;
+                let start_line = line;
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None | Some('\n') => bail!("line {start_line}: unterminated string"),
+                        Some('\\') => {
+                            if let Some(next) = chars.get(i + 1) {
+                                value.push(*next);
+                            }
+                            i += 2;
+                        }
+                        Some(ch) if *ch == quote => {
+                            i += 1;
+                            break;
+                        }
+                        Some(ch) => {
+                            value.push(*ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Str(value),
+                    line: start_line,
+                    comment: take_comment(&mut pending_comment),
+                });
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Int(chars[start..i].iter().collect()),
+                    line,
+                    comment: take_comment(&mut pending_comment),
+                });
+            }
+            c if c.is_alphabetic() || c == '_' || c == '.' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Ident(chars[start..i].iter().collect()),
+                    line,
+                    comment: take_comment(&mut pending_comment),
+                });
+            }
+            _ => {
+                tokens.push(Token {
+                    kind: TokenKind::Symbol(c),
+                    line,
+                    comment: take_comment(&mut pending_comment),
+                });
+                i += 1;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn push_comment(pending: &mut Vec<String>, tokens: &[Token], text: &str, line: usize) {
+    // Trailing comments on the same line as a token are not doc comments
+    if tokens.last().is_some_and(|t| t.line == line) {
+        return;
+    }
+    for l in text.lines() {
+        let cleaned = l.trim().trim_start_matches('*').trim();
+        if !cleaned.is_empty() {
+            pending.push(cleaned.to_string());
+        }
+    }
+}
+
+fn take_comment(pending: &mut Vec<String>) -> Option<String> {
+    if pending.is_empty() {
+        None
+    } else {
+        Some(std::mem::take(pending).join("\n"))
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse_file(mut self) -> Result<ProtoSchema> {
+        let mut schema = ProtoSchema::default();
+
+        while let Some(token) = self.next() {
+            match &token.kind {
+                TokenKind::Ident(word) => match word.as_str() {
+                    "syntax" | "edition" => {
+                        self.expect_symbol('=')?;
+                        schema.syntax = Some(self.string()?);
+                        self.expect_symbol(';')?;
+                    }
+                    "package" => {
+                        schema.package = Some(self.ident()?);
+                        self.expect_symbol(';')?;
+                    }
+                    "import" => {
+                        if self.peek_ident_is("public") || self.peek_ident_is("weak") {
+                            self.next();
+                        }
+                        schema.imports.push(self.string()?);
+                        self.expect_symbol(';')?;
+                    }
+                    "option" => self.skip_statement()?,
+                    "message" => self.parse_message("", &token, &mut schema)?,
+                    "enum" => self.parse_enum("", &token, &mut schema)?,
+                    "service" => self.parse_service(&token, &mut schema)?,
+                    "extend" => {
+                        self.ident()?;
+                        self.expect_symbol('{')?;
+                        self.skip_block()?;
+                    }
+                    other => bail!("line {}: unexpected '{}'", token.line, other),
+                },
+                TokenKind::Symbol(';') => {}
+                other => bail!("line {}: unexpected {:?}", token.line, other),
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn parse_message(
+        &mut self,
+        prefix: &str,
+        start: &Token,
+        schema: &mut ProtoSchema,
+    ) -> Result<()> {
+        let name = format!("{prefix}{}", self.ident()?);
+        self.expect_symbol('{')?;
+        let mut fields = Vec::new();
+
+        loop {
+            let token = self.next_or_eof("message body")?;
+            match &token.kind {
+                TokenKind::Symbol('}') => break,
+                TokenKind::Symbol(';') => {}
+                TokenKind::Ident(word) => match word.as_str() {
+                    "message" => self.parse_message(&format!("{name}."), &token, schema)?,
+                    "enum" => self.parse_enum(&format!("{name}."), &token, schema)?,
+                    "option" | "reserved" | "extensions" => self.skip_statement()?,
+                    "extend" => {
+                        self.ident()?;
+                        self.expect_symbol('{')?;
+                        self.skip_block()?;
+                    }
+                    "oneof" => {
+                        let oneof = self.ident()?;
+                        self.expect_symbol('{')?;
+                        loop {
+                            let inner = self.next_or_eof("oneof body")?;
+                            match &inner.kind {
+                                TokenKind::Symbol('}') => break,
+                                TokenKind::Symbol(';') => {}
+                                TokenKind::Ident(w) if w == "option" => self.skip_statement()?,
+                                _ => fields.push(self.parse_field(&inner, Some(&oneof))?),
+                            }
+                        }
+                    }
+                    _ => fields.push(self.parse_field(&token, None)?),
+                },
+                other => bail!(
+                    "line {}: unexpected {:?} in message {}",
+                    token.line,
+                    other,
+                    name
+                ),
+            }
+        }
+
+        schema.messages.push(MessageDef {
+            name,
+            fields,
+            line: start.line,
+            comment: start.comment.clone(),
+        });
+        Ok(())
+    }
+
+    /// Parse a field whose first token (label, type or `map`) has been consumed
+    fn parse_field(&mut self, first: &Token, oneof: Option<&str>) -> Result<FieldDef> {
+        let TokenKind::Ident(word) = &first.kind else {
+            bail!(
+                "line {}: expected field, found {:?}",
+                first.line,
+                first.kind
+            );
+        };
+
+        let (label, type_name) = match word.as_str() {
+            "optional" => (FieldLabel::Optional, self.field_type()?),
+            "repeated" => (FieldLabel::Repeated, self.field_type()?),
+            "required" => (FieldLabel::Required, self.field_type()?),
+            "map" if self.peek_symbol_is('<') => (FieldLabel::Singular, self.map_type()?),
+            other => (FieldLabel::Singular, other.to_string()),
+        };
+
+        let name = self.ident()?;
+        self.expect_symbol('=')?;
+        let number = self.int()?;
+        if self.peek_symbol_is('[') {
+            self.next();
+            self.skip_until(']')?;
+        }
+        self.expect_symbol(';')?;
+
+        Ok(FieldDef {
+            name,
+            number,
+            type_name,
+            label,
+            oneof: oneof.map(str::to_string),
+            line: first.line,
+            comment: first.comment.clone(),
+        })
+    }
+
+    fn field_type(&mut self) -> Result<String> {
+        if self.peek_ident_is("map") {
+            self.next();
+            if self.peek_symbol_is('<') {
+                return self.map_type();
+            }
+            return Ok("map".to_string());
+        }
+        self.ident()
+    }
+
+    fn map_type(&mut self) -> Result<String> {
+        self.expect_symbol('<')?;
+        let key = self.ident()?;
+        self.expect_symbol(',')?;
+        let value = self.ident()?;
+        self.expect_symbol('>')?;
+        Ok(format!("map<{key}, {value}>"))
+    }
+
+    fn parse_enum(&mut self, prefix: &str, start: &Token, schema: &mut ProtoSchema) -> Result<()> {
+        let name = format!("{prefix}{}", self.ident()?);
+        self.expect_symbol('{')?;
+        let mut values = Vec::new();
+
+        loop {
+            let token = self.next_or_eof("enum body")?;
+            match &token.kind {
+                TokenKind::Symbol('}') => break,
+                TokenKind::Symbol(';') => {}
+                TokenKind::Ident(word) if word == "option" || word == "reserved" => {
+                    self.skip_statement()?
+                }
+                TokenKind::Ident(value) => {
+                    self.expect_symbol('=')?;
+                    let number = self.int()?;
+                    if self.peek_symbol_is('[') {
+                        self.next();
+                        self.skip_until(']')?;
+                    }
+                    self.expect_symbol(';')?;
+                    values.push(EnumValueDef {
+                        name: value.clone(),
+                        number,
+                        line: token.line,
+                    });
+                }
+                other => bail!(
+                    "line {}: unexpected {:?} in enum {}",
+                    token.line,
+                    other,
+                    name
+                ),
+            }
+        }
+
+        schema.enums.push(EnumDef {
+            name,
+            values,
+            line: start.line,
+            comment: start.comment.clone(),
+        });
+        Ok(())
+    }
+
+    fn parse_service(&mut self, start: &Token, schema: &mut ProtoSchema) -> Result<()> {
+        let name = self.ident()?;
+        self.expect_symbol('{')?;
+        let mut methods = Vec::new();
+
+        loop {
+            let token = self.next_or_eof("service body")?;
+            match &token.kind {
+                TokenKind::Symbol('}') => break,
+                TokenKind::Symbol(';') => {}
+                TokenKind::Ident(word) if word == "option" => self.skip_statement()?,
+                TokenKind::Ident(word) if word == "rpc" => methods.push(self.parse_rpc(&token)?),
+                other => bail!(
+                    "line {}: unexpected {:?} in service {}",
+                    token.line,
+                    other,
+                    name
+                ),
+            }
+        }
+
+        schema.services.push(ServiceDef {
+            name,
+            methods,
+            line: start.line,
+            comment: start.comment.clone(),
+        });
+        Ok(())
+    }
+
+    fn parse_rpc(&mut self, start: &Token) -> Result<RpcDef> {
+        let name = self.ident()?;
+        let (client_streaming, input_type) = self.rpc_type()?;
+        let returns = self.ident()?;
+        if returns != "returns" {
+            bail!("line {}: expected 'returns' in rpc {}", start.line, name);
+        }
+        let (server_streaming, output_type) = self.rpc_type()?;
+
+        if self.peek_symbol_is('{') {
+            self.next();
+            self.skip_block()?;
+        } else {
+            self.expect_symbol(';')?;
+        }
+
+        Ok(RpcDef {
+            name,
+            input_type,
+            output_type,
+            client_streaming,
+            server_streaming,
+            line: start.line,
+            comment: start.comment.clone(),
+        })
+    }
+
+    /// `( [stream] Type )`
+    fn rpc_type(&mut self) -> Result<(bool, String)> {
+        self.expect_symbol('(')?;
+        let mut type_name = self.ident()?;
+        let mut streaming = false;
+        if type_name == "stream" && !self.peek_symbol_is(')') {
+            streaming = true;
+            type_name = self.ident()?;
+        }
+        self.expect_symbol(')')?;
+        Ok((streaming, type_name))
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn next_or_eof(&mut self, context: &str) -> Result<Token> {
+        self.next()
+            .ok_or_else(|| anyhow!("unexpected end of file in {context}"))
+    }
+
+    fn peek_symbol_is(&self, symbol: char) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token { kind: TokenKind::Symbol(c), .. }) if *c == symbol)
+    }
+
+    fn peek_ident_is(&self, word: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token { kind: TokenKind::Ident(w), .. }) if w == word)
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<()> {
+        let token = self.next_or_eof(&format!("'{symbol}'"))?;
+        match token.kind {
+            TokenKind::Symbol(c) if c == symbol => Ok(()),
+            other => bail!(
+                "line {}: expected '{}', found {:?}",
+                token.line,
+                symbol,
+                other
+            ),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        let token = self.next_or_eof("identifier")?;
+        match token.kind {
+            TokenKind::Ident(word) => Ok(word),
+            other => bail!(
+                "line {}: expected identifier, found {:?}",
+                token.line,
+                other
+            ),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let token = self.next_or_eof("string")?;
+        match token.kind {
+            TokenKind::Str(value) => Ok(value),
+            other => bail!("line {}: expected string, found {:?}", token.line, other),
+        }
+    }
+
+    fn int(&mut self) -> Result<i64> {
+        let token = self.next_or_eof("number")?;
+        let TokenKind::Int(text) = &token.kind else {
+            bail!(
+                "line {}: expected number, found {:?}",
+                token.line,
+                token.kind
+            );
+        };
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.as_str()),
+        };
+        let value = if let Some(hex) = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+        {
+            i64::from_str_radix(hex, 16)
+        } else if digits.len() > 1 && digits.starts_with('0') {
+            i64::from_str_radix(&digits[1..], 8)
+        } else {
+            digits.parse()
+        }
+        .map_err(|_| anyhow!("line {}: invalid number '{}'", token.line, text))?;
+        Ok(if negative { -value } else { value })
+    }
+
+    /// Skip to the end of a statement, including aggregate option values in braces
+    fn skip_statement(&mut self) -> Result<()> {
+        loop {
+            let token = self.next_or_eof("statement")?;
+            match token.kind {
+                TokenKind::Symbol(';') => return Ok(()),
+                TokenKind::Symbol('{') => self.skip_block()?,
+                _ => {}
+            }
+        }
+    }
+
+    /// Skip to the `}` matching an already consumed `{`
+    fn skip_block(&mut self) -> Result<()> {
+        self.skip_until('}')
+    }
+
+    fn skip_until(&mut self, close: char) -> Result<()> {
+        let open = match close {
+            '}' => '{',
+            ']' => '[',
+            _ => '(',
+        };
+        let mut depth = 1;
+        while depth > 0 {
+            let token = self.next_or_eof(&format!("'{close}'"))?;
+            match token.kind {
+                TokenKind::Symbol(c) if c == open => depth += 1,
+                TokenKind::Symbol(c) if c == close => depth -= 1,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+syntax = "proto3";
+package acme.user;
+
+import "google/protobuf/timestamp.proto";
+option java_package = "com.acme.user";
+
+// A registered user
+message User {
+  string id = 1; // trailing comment
+  repeated string tags = 2 [deprecated = true];
+  map<string, int32> scores = 3;
+  oneof contact {
+    string email = 4;
+    string phone = 5;
+  }
+  message Address {
+    string city = 1;
+  }
+  enum Role {
+    ROLE_UNSPECIFIED = 0;
+    ROLE_ADMIN = 1;
+  }
+  reserved 10 to 12;
+}
+
+/* User lookups */
+service UserService {
+  option (acme.audited) = { enabled: true };
+  // Fetch a single user
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc Watch(stream WatchRequest) returns (stream .acme.user.User) {
+    option deprecated = true;
+  }
+}
+"#;
+
+    #[test]
+    fn test_parse_schema() {
+        let schema = ProtoSchema::parse(SOURCE).unwrap();
+        assert_eq!(schema.syntax.as_deref(), Some("proto3"));
+        assert_eq!(schema.package.as_deref(), Some("acme.user"));
+        assert_eq!(schema.imports, vec!["google/protobuf/timestamp.proto"]);
+
+        let names: Vec<&str> = schema.messages.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["User.Address", "User"]);
+        let user = schema.messages.iter().find(|m| m.name == "User").unwrap();
+        assert_eq!(user.comment.as_deref(), Some("A registered user"));
+        assert_eq!(user.fields.len(), 5);
+        assert_eq!(user.fields[0].comment, None);
+        assert_eq!(user.fields[1].label, FieldLabel::Repeated);
+        assert_eq!(user.fields[2].type_name, "map<string, int32>");
+        assert_eq!(user.fields[3].oneof.as_deref(), Some("contact"));
+        assert_eq!(schema.enums[0].name, "User.Role");
+        assert_eq!(schema.enums[0].values[1].number, 1);
+
+        let service = &schema.services[0];
+        assert_eq!(service.comment.as_deref(), Some("User lookups"));
+        assert_eq!(service.methods.len(), 2);
+        assert_eq!(
+            service.methods[0].comment.as_deref(),
+            Some("Fetch a single user")
+        );
+        let watch = &service.methods[1];
+        assert!(watch.client_streaming && watch.server_streaming);
+        assert_eq!(schema.normalize_type(&watch.output_type), "User");
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err = ProtoSchema::parse("syntax = \"proto3\";\nmessage User {\n  string id 1;\n}\n")
+            .unwrap_err();
+        assert!(err.to_string().starts_with("line 3:"), "{err}");
+        assert!(ProtoSchema::parse("message User {").is_err());
+    }
+}
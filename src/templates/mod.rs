@@ -2,20 +2,23 @@
 
 pub mod kotlin;
 pub mod python;
+pub mod registry;
 pub mod rust;
 pub mod swift;
+pub mod typescript;
 
 use self::kotlin::KotlinTemplate;
 use self::python::PythonTemplate;
 use self::rust::RustTemplate;
 use self::swift::SwiftTemplate;
+use self::typescript::TypescriptTemplate;
 use crate::assets::FixtureAssets;
 use crate::error::{ActrCliError, Result};
 use crate::utils::{to_pascal_case, to_snake_case};
 use clap::ValueEnum;
-use handlebars::Handlebars;
+use handlebars::{Handlebars, handlebars_helper};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::ErrorKind;
 use std::path::Path;
 
@@ -81,6 +84,29 @@ pub struct TemplateContext {
     pub stun_urls: String,
     #[serde(rename = "TURN_URLS")]
     pub turn_urls: String,
+    /// User-defined values from `--var` and a template's `[template.vars]`
+    #[serde(flatten)]
+    pub vars: BTreeMap<String, String>,
+}
+
+handlebars_helper!(snake: |value: str| to_snake_case(value));
+handlebars_helper!(pascal: |value: str| to_pascal_case(value));
+handlebars_helper!(kebab: |value: str| heck::AsKebabCase(value).to_string());
+handlebars_helper!(camel: |value: str| heck::AsLowerCamelCase(value).to_string());
+handlebars_helper!(upper: |value: str| value.to_uppercase());
+handlebars_helper!(lower: |value: str| value.to_lowercase());
+
+/// Handlebars instance used for project templates, with the case helpers
+/// `snake`, `pascal`, `kebab`, `camel`, `upper` and `lower` (`{{kebab ORG}}`)
+pub fn handlebars() -> Handlebars<'static> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("snake", Box::new(snake));
+    handlebars.register_helper("pascal", Box::new(pascal));
+    handlebars.register_helper("kebab", Box::new(kebab));
+    handlebars.register_helper("camel", Box::new(camel));
+    handlebars.register_helper("upper", Box::new(upper));
+    handlebars.register_helper("lower", Box::new(lower));
+    handlebars
 }
 
 impl TemplateContext {
@@ -100,9 +126,23 @@ impl TemplateContext {
             realm_id: 2368266035,
             stun_urls: r#"["stun:actrix1.develenv.com:3478"]"#.to_string(),
             turn_urls: r#"["turn:actrix1.develenv.com:3478"]"#.to_string(),
+            vars: BTreeMap::new(),
         }
     }
 
+    /// Add user-defined variables; they cannot replace the built-in ones
+    pub fn with_vars(mut self, vars: &BTreeMap<String, String>) -> Self {
+        let builtin = serde_json::to_value(&self).ok();
+        for (key, value) in vars {
+            if builtin.as_ref().is_some_and(|b| b.get(key).is_some()) {
+                tracing::warn!("Ignoring --var {key}: it is a built-in template variable");
+                continue;
+            }
+            self.vars.insert(key.clone(), value.clone());
+        }
+        self
+    }
+
     pub async fn new_with_versions(
         project_name: &str,
         signaling_url: &str,
@@ -141,10 +181,13 @@ pub struct ProjectTemplate {
 impl ProjectTemplate {
     pub fn new(template_name: ProjectTemplateName, language: SupportedLanguage) -> Self {
         let lang_template: Box<dyn LangTemplate> = match language {
-            SupportedLanguage::Swift => Box::new(SwiftTemplate),
+            SupportedLanguage::Swift => Box::new(SwiftTemplate {
+                swift_package: false,
+            }),
             SupportedLanguage::Kotlin => Box::new(KotlinTemplate),
             SupportedLanguage::Python => Box::new(PythonTemplate),
             SupportedLanguage::Rust => Box::new(RustTemplate),
+            SupportedLanguage::Typescript => Box::new(TypescriptTemplate),
         };
 
         Self {
@@ -153,6 +196,16 @@ impl ProjectTemplate {
         }
     }
 
+    /// Swift template laid out as a Swift package (Package.swift, no project.yml)
+    pub fn swift_package(template_name: ProjectTemplateName) -> Self {
+        Self {
+            name: template_name,
+            lang_template: Box::new(SwiftTemplate {
+                swift_package: true,
+            }),
+        }
+    }
+
     pub fn load_file(
         fixture_path: &Path,
         files: &mut HashMap<String, String>,
@@ -194,7 +247,7 @@ impl ProjectTemplate {
 
     pub fn generate(&self, project_path: &Path, context: &TemplateContext) -> Result<()> {
         let files = self.lang_template.load_files(self.name)?;
-        let handlebars = Handlebars::new();
+        let handlebars = handlebars();
 
         for (file_path, content) in &files {
             let rendered_path = handlebars.render_template(file_path, context)?;
@@ -231,6 +284,28 @@ mod tests {
         assert_eq!(ctx.actr_protocols_version, DEFAULT_ACTR_PROTOCOLS_VERSION);
     }
 
+    #[test]
+    fn test_template_vars_and_helpers() {
+        let vars = BTreeMap::from([
+            ("ORG".to_string(), "Acme Robotics".to_string()),
+            ("PROJECT_NAME".to_string(), "other".to_string()),
+        ]);
+        let ctx =
+            TemplateContext::new("my-app", "ws://localhost:8080", "echo-service").with_vars(&vars);
+        assert_eq!(ctx.vars.len(), 1);
+
+        let rendered = handlebars()
+            .render_template(
+                "{{PROJECT_NAME}} {{kebab ORG}} {{snake ORG}} {{pascal PROJECT_NAME}} {{upper ORG}}",
+                &ctx,
+            )
+            .unwrap();
+        assert_eq!(
+            rendered,
+            "my-app acme-robotics acme_robotics MyApp ACME ROBOTICS"
+        );
+    }
+
     #[test]
     fn test_project_template_new() {
         let template = ProjectTemplate::new(ProjectTemplateName::Echo, SupportedLanguage::Swift);
@@ -264,6 +339,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_swift_package_template_generation() {
+        let temp_dir = TempDir::new().unwrap();
+        let template = ProjectTemplate::swift_package(ProjectTemplateName::Echo);
+        let context = TemplateContext::new("test-app", "ws://localhost:8080", "echo-service");
+
+        template
+            .generate(temp_dir.path(), &context)
+            .expect("Failed to generate");
+
+        assert!(!temp_dir.path().join("project.yml").exists());
+        let manifest = std::fs::read_to_string(temp_dir.path().join("Package.swift")).unwrap();
+        assert!(manifest.contains("name: \"TestApp\""));
+        assert!(manifest.contains("path: \"TestApp\""));
+        assert!(temp_dir.path().join("TestApp/ActrService.swift").exists());
+    }
+
+    #[test]
+    fn test_typescript_template_generation() {
+        let temp_dir = TempDir::new().unwrap();
+        let template =
+            ProjectTemplate::new(ProjectTemplateName::Echo, SupportedLanguage::Typescript);
+        let context = TemplateContext::new("web-app", "ws://localhost:8080", "echo-service");
+
+        template
+            .generate(temp_dir.path(), &context)
+            .expect("Failed to generate");
+
+        for file in ["package.json", "tsconfig.json", "Actr.toml", "src/main.ts"] {
+            assert!(temp_dir.path().join(file).exists(), "{file} missing");
+        }
+        let package_json = std::fs::read_to_string(temp_dir.path().join("package.json")).unwrap();
+        assert!(package_json.contains("\"name\": \"web-app\""));
+    }
+
+    #[test]
+    fn test_python_template_generation() {
+        let temp_dir = TempDir::new().unwrap();
+        let template = ProjectTemplate::new(ProjectTemplateName::Echo, SupportedLanguage::Python);
+        let context = TemplateContext::new("py-echo", "ws://localhost:8080", "echo-service");
+
+        template
+            .generate(temp_dir.path(), &context)
+            .expect("Failed to generate");
+
+        for role in ["server", "client"] {
+            let dir = temp_dir.path().join(role);
+            assert!(dir.join("Actr.toml").exists());
+            assert!(dir.join("pyproject.toml").exists());
+            assert!(dir.join(format!("{role}.py")).exists());
+        }
+        assert!(
+            temp_dir
+                .path()
+                .join("server/protos/local/py_echo.proto")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_data_stream_template_generation() {
+        for language in [SupportedLanguage::Rust, SupportedLanguage::Swift] {
+            let temp_dir = TempDir::new().unwrap();
+            let template = ProjectTemplate::new(ProjectTemplateName::DataStream, language);
+            let context =
+                TemplateContext::new("stream-app", "ws://localhost:8080", "LocalFileService");
+
+            template
+                .generate(temp_dir.path(), &context)
+                .expect("Failed to generate");
+
+            let actr_toml = std::fs::read_to_string(temp_dir.path().join("Actr.toml")).unwrap();
+            assert!(actr_toml.contains("acme+StreamEchoServer"));
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let template =
+            ProjectTemplate::new(ProjectTemplateName::DataStream, SupportedLanguage::Rust);
+        let context = TemplateContext::new("stream-app", "ws://localhost:8080", "LocalFileService");
+        template.generate(temp_dir.path(), &context).unwrap();
+        let lib_rs = std::fs::read_to_string(temp_dir.path().join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub mod stream_app"));
+        assert!(lib_rs.contains("impl ILocalFileService for StreamAppActor"));
+    }
+
     #[test]
     fn test_project_template_load_files() {
         let template = ProjectTemplate::new(ProjectTemplateName::Echo, SupportedLanguage::Swift);
@@ -18,6 +18,16 @@ pub fn load(files: &mut HashMap<String, String>) -> Result<()> {
         files,
         "client/Actr.toml",
     )?;
+    ProjectTemplate::load_file(
+        &python_fixtures.join("pyproject.server.toml.jinja2"),
+        files,
+        "server/pyproject.toml",
+    )?;
+    ProjectTemplate::load_file(
+        &python_fixtures.join("pyproject.client.toml.jinja2"),
+        files,
+        "client/pyproject.toml",
+    )?;
     ProjectTemplate::load_file(
         &python_fixtures.join("server.py.jinja2"),
         files,
@@ -0,0 +1,596 @@
+//! Custom project templates
+//!
+//! Besides the built-in templates, `actr init --template-path` accepts a local
+//! directory, a template registered under `~/.actr/templates/<name>`, a git
+//! repository (`https://host/repo.git#v1.0`) or a `.tar.gz` archive. Each
+//! template carries a `template.toml` manifest describing its files and the
+//! handlebars variables it expects:
+//!
+//! ```toml
+//! name = "acme-service"
+//! description = "ACME backend service"
+//! languages = ["rust"]
+//!
+//! [[files]]
+//! path = "Actr.toml.hbs"
+//! target = "Actr.toml"
+//!
+//! [[files]]
+//! path = "assets/logo.png"
+//! render = false
+//!
+//! [variables.TEAM]
+//! description = "Owning team"
+//! default = "platform"
+//!
+//! [template.vars]
+//! LICENSE = "Apache-2.0"
+//!
+//! [hooks]
+//! post_init = ["npm install", "npm run build"]
+//! ```
+//!
+//! Without `[[files]]`, every file next to the manifest is rendered and a
+//! trailing `.hbs` is dropped from its name. `[variables]` are prompted for (or
+//! taken from `--var`), `[template.vars]` are fixed values that `--var` may
+//! override; both render next to the built-in context and the case helpers of
+//! [`crate::templates::handlebars`].
+//!
+//! `post_init` hooks are shell commands run in the new project after generation,
+//! only once the user confirms them or passes `--allow-hooks`.
+
+use crate::commands::SupportedLanguage;
+use crate::error::{ActrCliError, Result};
+use crate::templates::TemplateContext;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use tempfile::TempDir;
+use walkdir::WalkDir;
+
+/// Manifest file at the root of every custom template
+pub const MANIFEST_FILE: &str = "template.toml";
+
+/// Where a custom template comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    /// Template directory on disk
+    Local(PathBuf),
+    /// Git repository, optionally pinned to a branch, tag or commit
+    Git {
+        url: String,
+        reference: Option<String>,
+    },
+    /// `.tar.gz` archive, local or downloaded over HTTP(S)
+    Archive(String),
+}
+
+impl TemplateSource {
+    /// Interpret a `--template-path` value
+    ///
+    /// Bare names resolve against the registry directory (`~/.actr/templates`)
+    /// when no directory of that name exists locally.
+    pub fn parse(spec: &str) -> Self {
+        if spec.ends_with(".tar.gz") || spec.ends_with(".tgz") {
+            return Self::Archive(spec.to_string());
+        }
+
+        let is_remote = spec.starts_with("https://")
+            || spec.starts_with("http://")
+            || spec.starts_with("ssh://")
+            || spec.starts_with("git@");
+        if is_remote || spec.ends_with(".git") || spec.contains(".git#") {
+            let (url, reference) = match spec.rsplit_once('#') {
+                Some((url, reference)) if !reference.is_empty() => {
+                    (url.to_string(), Some(reference.to_string()))
+                }
+                _ => (spec.trim_end_matches('#').to_string(), None),
+            };
+            return Self::Git { url, reference };
+        }
+
+        let path = PathBuf::from(spec);
+        if !path.exists()
+            && !spec.contains(['/', '\\'])
+            && let Some(registered) = registry_dir().map(|dir| dir.join(spec))
+            && registered.join(MANIFEST_FILE).exists()
+        {
+            return Self::Local(registered);
+        }
+        Self::Local(path)
+    }
+}
+
+/// Directory holding registered templates (`~/.actr/templates`)
+pub fn registry_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".actr").join("templates"))
+}
+
+/// Parsed `template.toml`
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Languages the template targets; empty means any
+    #[serde(default)]
+    pub languages: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<TemplateFile>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, TemplateVariable>,
+    #[serde(default)]
+    pub template: TemplateSettings,
+    #[serde(default)]
+    pub hooks: TemplateHooks,
+}
+
+/// `[hooks]` section of the manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateHooks {
+    /// Shell commands run in the project directory after generation, in order
+    #[serde(default)]
+    pub post_init: Vec<String>,
+}
+
+/// Outcome of one hook command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookRun {
+    pub command: String,
+    /// Exit code; `None` when the command could not be started or was killed
+    pub code: Option<i32>,
+    /// Combined stdout and stderr
+    pub output: String,
+}
+
+impl HookRun {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// Run hook commands in `project_dir`, stopping at the first failure
+pub async fn run_hooks(project_dir: &Path, commands: &[String]) -> Vec<HookRun> {
+    let mut runs = Vec::with_capacity(commands.len());
+    for command in commands {
+        tracing::info!("🪝 Running hook '{command}'");
+        let run = match crate::commands::run::shell_command(command)
+            .current_dir(project_dir)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+        {
+            Ok(output) => HookRun {
+                command: command.clone(),
+                code: output.status.code(),
+                output: format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                ),
+            },
+            Err(e) => HookRun {
+                command: command.clone(),
+                code: None,
+                output: format!("failed to start: {e}"),
+            },
+        };
+        let failed = !run.success();
+        runs.push(run);
+        if failed {
+            break;
+        }
+    }
+    runs
+}
+
+/// `[template]` section of the manifest
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateSettings {
+    /// Fixed values exposed to handlebars; `--var` overrides them
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+}
+
+/// One file copied into the new project
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateFile {
+    /// Path relative to the template root
+    pub path: String,
+    /// Destination relative to the project (handlebars allowed); defaults to `path` without `.hbs`
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Render the content with handlebars (disable for binary files)
+    #[serde(default = "default_render")]
+    pub render: bool,
+}
+
+fn default_render() -> bool {
+    true
+}
+
+/// A handlebars variable the template expects
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TemplateVariable {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// A template ready to be rendered
+pub struct CustomTemplate {
+    root: PathBuf,
+    manifest: TemplateManifest,
+    /// Keeps fetched git/archive checkouts alive until rendering is done
+    _checkout: Option<TempDir>,
+}
+
+impl CustomTemplate {
+    /// Fetch (if remote) and load a template
+    pub async fn fetch(source: &TemplateSource) -> Result<Self> {
+        match source {
+            TemplateSource::Local(path) => Self::load(path),
+            TemplateSource::Git { url, reference } => {
+                let checkout = TempDir::new()?;
+                let url = url.clone();
+                let reference = reference.clone();
+                let dest = checkout.path().to_path_buf();
+                tokio::task::spawn_blocking(move || {
+                    clone_repository(&url, reference.as_deref(), &dest)
+                })
+                .await
+                .map_err(|e| {
+                    ActrCliError::command_error(format!("Git clone task failed: {e}"))
+                })??;
+                let mut template = Self::load(checkout.path())?;
+                template._checkout = Some(checkout);
+                Ok(template)
+            }
+            TemplateSource::Archive(location) => {
+                let bytes = if location.starts_with("http://") || location.starts_with("https://") {
+                    let client = crate::core::NetworkSettings::from_env()?.http_client()?;
+                    let response = client.get(location).send().await?;
+                    if !response.status().is_success() {
+                        return Err(ActrCliError::command_error(format!(
+                            "Failed to download template {location}: HTTP {}",
+                            response.status()
+                        )));
+                    }
+                    response.bytes().await?.to_vec()
+                } else {
+                    std::fs::read(location)?
+                };
+                let checkout = TempDir::new()?;
+                tar::Archive::new(flate2::read::GzDecoder::new(bytes.as_slice()))
+                    .unpack(checkout.path())?;
+                let root = archive_root(checkout.path())?;
+                let mut template = Self::load(&root)?;
+                template._checkout = Some(checkout);
+                Ok(template)
+            }
+        }
+    }
+
+    /// Load a template directory containing `template.toml`
+    pub fn load(root: &Path) -> Result<Self> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        if !manifest_path.exists() {
+            return Err(ActrCliError::InvalidProject(format!(
+                "Template manifest not found: {}",
+                manifest_path.display()
+            )));
+        }
+        let content = std::fs::read_to_string(&manifest_path)?;
+        let manifest: TemplateManifest = toml::from_str(&content).map_err(|e| {
+            ActrCliError::config_error(format!("Invalid {}: {e}", manifest_path.display()))
+        })?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            manifest,
+            _checkout: None,
+        })
+    }
+
+    pub fn manifest(&self) -> &TemplateManifest {
+        &self.manifest
+    }
+
+    /// Fail when the template does not target `language`
+    pub fn check_language(&self, language: SupportedLanguage) -> Result<()> {
+        let languages = &self.manifest.languages;
+        let name = language
+            .to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default();
+        if languages.is_empty() || languages.iter().any(|l| l.eq_ignore_ascii_case(&name)) {
+            Ok(())
+        } else {
+            Err(ActrCliError::InvalidProject(format!(
+                "Template '{}' supports {} but --language is {name}",
+                self.manifest.name,
+                languages.join(", ")
+            )))
+        }
+    }
+
+    /// Resolve manifest variables from `--var` values, defaults and (optionally) prompts,
+    /// together with `[template.vars]` and any other `--var` values
+    pub fn resolve_variables(
+        &self,
+        overrides: &BTreeMap<String, String>,
+        interactive: bool,
+    ) -> Result<BTreeMap<String, String>> {
+        let mut values = self.manifest.template.vars.clone();
+        for (key, variable) in &self.manifest.variables {
+            let value = match (overrides.get(key), &variable.default) {
+                (Some(value), _) => value.clone(),
+                (None, _) if interactive => {
+                    let prompt = variable.description.as_deref().unwrap_or(key.as_str());
+                    let mut input = dialoguer::Input::<String>::new().with_prompt(prompt);
+                    if let Some(default) = &variable.default {
+                        input = input.default(default.clone());
+                    }
+                    input.interact_text().map_err(|e| {
+                        ActrCliError::command_error(format!("Failed to read {key}: {e}"))
+                    })?
+                }
+                (None, Some(default)) => default.clone(),
+                (None, None) => {
+                    return Err(ActrCliError::InvalidProject(format!(
+                        "Template variable '{key}' is required; pass --var {key}=<value>"
+                    )));
+                }
+            };
+            values.insert(key.clone(), value);
+        }
+        for (key, value) in overrides {
+            if self.manifest.variables.contains_key(key) {
+                continue;
+            }
+            if !self.manifest.template.vars.contains_key(key) {
+                tracing::warn!(
+                    "Template '{}' does not declare variable '{key}'",
+                    self.manifest.name
+                );
+            }
+            values.insert(key.clone(), value.clone());
+        }
+        Ok(values)
+    }
+
+    /// Render the template into `project_dir`, returning the written files
+    pub fn generate(
+        &self,
+        project_dir: &Path,
+        context: &TemplateContext,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<Vec<PathBuf>> {
+        let mut data = serde_json::to_value(context)
+            .map_err(|e| ActrCliError::command_error(format!("Invalid template context: {e}")))?;
+        if let Some(map) = data.as_object_mut() {
+            for (key, value) in variables {
+                map.insert(key.clone(), serde_json::Value::String(value.clone()));
+            }
+        }
+
+        let handlebars = crate::templates::handlebars();
+        let mut written = Vec::new();
+        for file in self.files() {
+            let source = self.root.join(&file.path);
+            let target = match &file.target {
+                Some(target) => target.clone(),
+                None => file
+                    .path
+                    .strip_suffix(".hbs")
+                    .unwrap_or(&file.path)
+                    .to_string(),
+            };
+            let target = handlebars.render_template(&target, &data)?;
+            let relative = safe_relative_path(&target)?;
+            let destination = project_dir.join(relative);
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            if file.render {
+                let content = std::fs::read_to_string(&source).map_err(|e| {
+                    ActrCliError::InvalidProject(format!(
+                        "Cannot render {} ({e}); set render = false for binary files",
+                        file.path
+                    ))
+                })?;
+                std::fs::write(&destination, handlebars.render_template(&content, &data)?)?;
+            } else {
+                std::fs::copy(&source, &destination)?;
+            }
+            written.push(destination);
+        }
+        Ok(written)
+    }
+
+    /// Files listed in the manifest, or every file next to it
+    fn files(&self) -> Vec<TemplateFile> {
+        if !self.manifest.files.is_empty() {
+            return self.manifest.files.clone();
+        }
+        let mut files: Vec<TemplateFile> = WalkDir::new(&self.root)
+            .into_iter()
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(&self.root).ok()?;
+                let path = relative.to_string_lossy().replace('\\', "/");
+                (path != MANIFEST_FILE).then_some(TemplateFile {
+                    path,
+                    target: None,
+                    render: true,
+                })
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+}
+
+/// Reject absolute targets and `..` so templates cannot write outside the project
+fn safe_relative_path(target: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(target);
+    let escapes = path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if escapes || target.trim().is_empty() {
+        return Err(ActrCliError::InvalidProject(format!(
+            "Template target '{target}' must be a relative path inside the project"
+        )));
+    }
+    Ok(path)
+}
+
+/// Archives often wrap the template in a single top-level directory
+fn archive_root(dir: &Path) -> Result<PathBuf> {
+    if dir.join(MANIFEST_FILE).exists() {
+        return Ok(dir.to_path_buf());
+    }
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    match entries.as_slice() {
+        [single] if single.join(MANIFEST_FILE).exists() => Ok(single.clone()),
+        _ => Err(ActrCliError::InvalidProject(format!(
+            "Archive does not contain {MANIFEST_FILE}"
+        ))),
+    }
+}
+
+fn clone_repository(url: &str, reference: Option<&str>, dest: &Path) -> Result<()> {
+    tracing::info!("📥 Cloning template {url}");
+    let repo = git2::Repository::clone(url, dest)?;
+    if let Some(reference) = reference {
+        let object = repo
+            .revparse_single(reference)
+            .or_else(|_| repo.revparse_single(&format!("origin/{reference}")))?;
+        repo.checkout_tree(&object, None)?;
+        repo.set_head_detached(object.peel_to_commit()?.id())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_template_source() {
+        assert_eq!(
+            TemplateSource::parse("https://github.com/acme/templates.git#v1.2"),
+            TemplateSource::Git {
+                url: "https://github.com/acme/templates.git".to_string(),
+                reference: Some("v1.2".to_string()),
+            }
+        );
+        assert_eq!(
+            TemplateSource::parse("git@github.com:acme/templates.git"),
+            TemplateSource::Git {
+                url: "git@github.com:acme/templates.git".to_string(),
+                reference: None,
+            }
+        );
+        assert_eq!(
+            TemplateSource::parse("https://example.com/acme.tar.gz"),
+            TemplateSource::Archive("https://example.com/acme.tar.gz".to_string())
+        );
+        assert_eq!(
+            TemplateSource::parse("./templates/acme"),
+            TemplateSource::Local(PathBuf::from("./templates/acme"))
+        );
+    }
+
+    #[test]
+    fn test_generate_local_template() {
+        let template_dir = TempDir::new().unwrap();
+        let root = template_dir.path();
+        std::fs::write(
+            root.join(MANIFEST_FILE),
+            "name = \"acme\"\nlanguages = [\"rust\"]\n\n[variables.TEAM]\ndefault = \"platform\"\n\n[template.vars]\nLICENSE = \"MIT\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Actr.toml.hbs"),
+            "# {{PROJECT_NAME}} owned by {{upper TEAM}} ({{LICENSE}})\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("src/{{PROJECT_NAME_SNAKE}}.rs"),
+            "// {{PROJECT_NAME_PASCAL}}\n",
+        )
+        .unwrap();
+
+        let template = CustomTemplate::load(root).unwrap();
+        template.check_language(SupportedLanguage::Rust).unwrap();
+        assert!(template.check_language(SupportedLanguage::Swift).is_err());
+
+        let variables = template.resolve_variables(&BTreeMap::new(), false).unwrap();
+        assert_eq!(variables["TEAM"], "platform");
+        assert_eq!(variables["LICENSE"], "MIT");
+
+        let project = TempDir::new().unwrap();
+        let context = TemplateContext::new("my-app", "ws://localhost:8080", "echo-service");
+        let written = template
+            .generate(project.path(), &context, &variables)
+            .unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(project.path().join("Actr.toml")).unwrap(),
+            "# my-app owned by PLATFORM (MIT)\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(project.path().join("src/my_app.rs")).unwrap(),
+            "// MyApp\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hooks_stop_at_first_failure() {
+        let project = TempDir::new().unwrap();
+        let commands = vec![
+            "echo ready".to_string(),
+            "exit 3".to_string(),
+            "echo never".to_string(),
+        ];
+        let runs = run_hooks(project.path(), &commands).await;
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].success());
+        assert_eq!(runs[0].output.trim(), "ready");
+        assert_eq!(runs[1].code, Some(3));
+    }
+
+    #[test]
+    fn test_required_variable_and_unsafe_target() {
+        let template_dir = TempDir::new().unwrap();
+        let root = template_dir.path();
+        std::fs::write(
+            root.join(MANIFEST_FILE),
+            "name = \"acme\"\n\n[[files]]\npath = \"a.txt\"\ntarget = \"../escape.txt\"\n\n[variables.TEAM]\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("a.txt"), "x").unwrap();
+
+        let template = CustomTemplate::load(root).unwrap();
+        assert!(template.resolve_variables(&BTreeMap::new(), false).is_err());
+
+        let overrides = BTreeMap::from([("TEAM".to_string(), "core".to_string())]);
+        let variables = template.resolve_variables(&overrides, false).unwrap();
+        let project = TempDir::new().unwrap();
+        let context = TemplateContext::new("my-app", "ws://localhost:8080", "echo-service");
+        assert!(
+            template
+                .generate(project.path(), &context, &variables)
+                .is_err()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod data_stream;
 pub mod echo;
 
 pub use echo::load;
@@ -17,9 +18,7 @@ impl LangTemplate for RustTemplate {
                 echo::load(&mut files)?;
             }
             ProjectTemplateName::DataStream => {
-                return Err(crate::error::ActrCliError::Unsupported(
-                    "DataStream template is not supported for Rust yet".to_string(),
-                ));
+                data_stream::load(&mut files)?;
             }
         }
 
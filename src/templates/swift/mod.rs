@@ -1,11 +1,15 @@
-use super::{LangTemplate, ProjectTemplateName};
+use super::{LangTemplate, ProjectTemplate, ProjectTemplateName};
 use crate::error::Result;
 use std::collections::HashMap;
+use std::path::Path;
 
 pub mod data_stream;
 pub mod echo;
 
-pub struct SwiftTemplate;
+pub struct SwiftTemplate {
+    /// Lay the project out around Package.swift instead of an xcodegen project.yml
+    pub swift_package: bool,
+}
 
 impl LangTemplate for SwiftTemplate {
     fn load_files(&self, template_name: ProjectTemplateName) -> Result<HashMap<String, String>> {
@@ -20,6 +24,16 @@ impl LangTemplate for SwiftTemplate {
             }
         }
 
+        if self.swift_package {
+            files.remove("project.yml");
+            let fixtures_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+            ProjectTemplate::load_file(
+                &fixtures_root.join("swift/Package.swift.hbs"),
+                &mut files,
+                "Package.swift",
+            )?;
+        }
+
         Ok(files)
     }
 }
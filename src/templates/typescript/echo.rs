@@ -0,0 +1,32 @@
+use crate::error::Result;
+use crate::templates::ProjectTemplate;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub fn load(files: &mut HashMap<String, String>) -> Result<()> {
+    let fixtures_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    let ts_fixtures = fixtures_root.join("typescript");
+
+    // package.json / tsconfig.json
+    ProjectTemplate::load_file(&ts_fixtures.join("package.json.hbs"), files, "package.json")?;
+    ProjectTemplate::load_file(
+        &ts_fixtures.join("tsconfig.json.hbs"),
+        files,
+        "tsconfig.json",
+    )?;
+
+    // Actr.toml
+    ProjectTemplate::load_file(&ts_fixtures.join("echo/Actr.toml.hbs"), files, "Actr.toml")?;
+
+    // Web entry point using the ActorClient
+    ProjectTemplate::load_file(&ts_fixtures.join("index.html.hbs"), files, "index.html")?;
+    ProjectTemplate::load_file(&ts_fixtures.join("echo/main.ts.hbs"), files, "src/main.ts")?;
+
+    // README.md
+    ProjectTemplate::load_file(&ts_fixtures.join("echo/README.md.hbs"), files, "README.md")?;
+
+    // .gitignore
+    ProjectTemplate::load_file(&ts_fixtures.join("gitignore.hbs"), files, ".gitignore")?;
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+pub mod echo;
+
+use super::{LangTemplate, ProjectTemplateName};
+use crate::error::Result;
+use std::collections::HashMap;
+
+pub struct TypescriptTemplate;
+
+impl LangTemplate for TypescriptTemplate {
+    fn load_files(&self, template_name: ProjectTemplateName) -> Result<HashMap<String, String>> {
+        let mut files = HashMap::new();
+
+        match template_name {
+            ProjectTemplateName::Echo => {
+                echo::load(&mut files)?;
+            }
+            ProjectTemplateName::DataStream => {
+                return Err(crate::error::ActrCliError::Unsupported(
+                    "DataStream template is not supported for TypeScript yet".to_string(),
+                ));
+            }
+        }
+
+        Ok(files)
+    }
+}
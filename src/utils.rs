@@ -11,6 +11,9 @@ use tracing::{debug, info, warn};
 
 pub const GIT_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Environment variable that enables offline mode (same as `--offline`)
+pub const OFFLINE_ENV: &str = "ACTR_OFFLINE";
+
 /// Execute a command and return the output
 #[allow(dead_code)]
 pub async fn execute_command(cmd: &str, args: &[&str], cwd: Option<&Path>) -> Result<Output> {
@@ -263,6 +266,18 @@ pub fn warn_if_not_actr_project() {
     }
 }
 
+/// Check whether offline mode is requested through `ACTR_OFFLINE`
+pub fn offline_from_env() -> bool {
+    std::env::var(OFFLINE_ENV).is_ok_and(|value| is_truthy(&value))
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -288,6 +303,15 @@ mod tests {
         ensure_dir_exists(&test_path).unwrap();
     }
 
+    #[test]
+    fn test_is_truthy() {
+        assert!(is_truthy("1"));
+        assert!(is_truthy("TRUE"));
+        assert!(is_truthy(" yes "));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy(""));
+    }
+
     #[tokio::test]
     async fn test_execute_command() {
         // Test a simple command that should succeed
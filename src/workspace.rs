@@ -0,0 +1,491 @@
+//! Workspace support
+//!
+//! A workspace groups several Actor-RTC projects under one root directory that
+//! contains an `Actr.workspace.toml`:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["gateway", "services/*"]
+//! exclude = ["services/legacy"]
+//! ```
+//!
+//! The root `Actr.lock.toml` is the single source of truth for every member.
+//! `install` seeds each member's lock file from it and merges the member lock
+//! files back afterwards, refusing to write the root when members disagree.
+//! Commands that only read locks (`check`, `gen`) never write member lock files;
+//! they fail when a member's lock has drifted from the root.
+
+use actr_config::LockFile;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ActrCliError, Result};
+
+pub const WORKSPACE_FILE_NAME: &str = "Actr.workspace.toml";
+const CONFIG_FILE_NAME: &str = "Actr.toml";
+const LOCK_FILE_NAME: &str = "Actr.lock.toml";
+
+#[derive(Debug, Deserialize)]
+struct WorkspaceFile {
+    workspace: WorkspaceSection,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkspaceSection {
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+/// A loaded workspace with its member project directories
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    root: PathBuf,
+    members: Vec<PathBuf>,
+}
+
+/// Two members locked the same service at different fingerprints
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockConflict {
+    pub service: String,
+    pub member: String,
+    pub shared_fingerprint: String,
+    pub member_fingerprint: String,
+}
+
+impl std::fmt::Display for LockConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is locked at {} in the workspace but {} in '{}'",
+            self.service, self.shared_fingerprint, self.member_fingerprint, self.member
+        )
+    }
+}
+
+impl Workspace {
+    /// Find the nearest `Actr.workspace.toml` in `start` or any of its parents
+    pub fn discover(start: &Path) -> Result<Option<Self>> {
+        for dir in start.ancestors() {
+            if dir.join(WORKSPACE_FILE_NAME).exists() {
+                return Self::load(dir).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Load the workspace rooted at `root`
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(WORKSPACE_FILE_NAME);
+        let contents = std::fs::read_to_string(&path)?;
+        let parsed: WorkspaceFile = toml::from_str(&contents).map_err(|e| {
+            ActrCliError::config_error(format!("Failed to parse {}: {e}", path.display()))
+        })?;
+
+        let excluded: HashSet<PathBuf> = parsed
+            .workspace
+            .exclude
+            .iter()
+            .flat_map(|pattern| expand_member_pattern(root, pattern))
+            .collect();
+
+        let mut members = Vec::new();
+        for pattern in &parsed.workspace.members {
+            let matches = expand_member_pattern(root, pattern);
+            if matches.is_empty() && !pattern.contains('*') {
+                return Err(ActrCliError::config_error(format!(
+                    "Workspace member '{pattern}' does not exist"
+                )));
+            }
+            for dir in matches {
+                if excluded.contains(&dir) || members.contains(&dir) {
+                    continue;
+                }
+                if !dir.join(CONFIG_FILE_NAME).exists() {
+                    if pattern.contains('*') {
+                        continue;
+                    }
+                    return Err(ActrCliError::config_error(format!(
+                        "Workspace member '{pattern}' has no {CONFIG_FILE_NAME}"
+                    )));
+                }
+                members.push(dir);
+            }
+        }
+
+        if members.is_empty() {
+            return Err(ActrCliError::config_error(format!(
+                "{} does not list any member projects",
+                path.display()
+            )));
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            members,
+        })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn members(&self) -> &[PathBuf] {
+        &self.members
+    }
+
+    /// Path of the shared lock file
+    pub fn lock_file_path(&self) -> PathBuf {
+        self.root.join(LOCK_FILE_NAME)
+    }
+
+    /// Member path relative to the workspace root, for display
+    pub fn member_name(&self, member: &Path) -> String {
+        member
+            .strip_prefix(&self.root)
+            .unwrap_or(member)
+            .display()
+            .to_string()
+    }
+
+    /// Copy the shared lock entries for the member's dependencies into its lock
+    /// file, before `install` runs in it
+    pub fn sync_member_lock(&self, member: &Path) -> Result<()> {
+        let shared_path = self.lock_file_path();
+        if !shared_path.exists() {
+            return Ok(());
+        }
+        let shared = read_lock(&shared_path)?;
+
+        let member_path = member.join(LOCK_FILE_NAME);
+        let mut lock_file = if member_path.exists() {
+            read_lock(&member_path)?
+        } else {
+            LockFile::new()
+        };
+
+        for name in self.member_dependencies(member)? {
+            if let Some(locked) = shared.get_dependency(&name) {
+                lock_file.add_dependency(locked.clone());
+            }
+        }
+
+        lock_file.update_timestamp();
+        save_lock(&lock_file, &member_path)
+    }
+
+    /// Check that the member's lock file agrees with the shared one, before a
+    /// command that only reads locks runs in it
+    ///
+    /// Every dependency locked in the shared lock file must be locked at the same
+    /// fingerprint in the member; nothing is written.
+    pub fn verify_member_lock(&self, member: &Path) -> Result<()> {
+        let shared_path = self.lock_file_path();
+        if !shared_path.exists() {
+            return Ok(());
+        }
+        let shared = read_lock(&shared_path)?;
+        let member_path = member.join(LOCK_FILE_NAME);
+        let member_lock = if member_path.exists() {
+            Some(read_lock(&member_path)?)
+        } else {
+            None
+        };
+
+        let mut drifted = Vec::new();
+        for name in self.member_dependencies(member)? {
+            let Some(locked) = shared.get_dependency(&name) else {
+                continue;
+            };
+            let member_fingerprint = member_lock
+                .as_ref()
+                .and_then(|lock| lock.get_dependency(&name))
+                .map(|entry| entry.fingerprint.clone());
+            if member_fingerprint.as_ref() != Some(&locked.fingerprint) {
+                drifted.push(LockConflict {
+                    service: name,
+                    member: self.member_name(member),
+                    shared_fingerprint: locked.fingerprint.clone(),
+                    member_fingerprint: member_fingerprint.unwrap_or_else(|| "nothing".to_string()),
+                });
+            }
+        }
+
+        if drifted.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = drifted.iter().map(ToString::to_string).collect();
+        Err(ActrCliError::config_error(format!(
+            "{} of '{}' is out of sync with the workspace lock file:\n  {}\nRun 'actr install --workspace' to update it",
+            LOCK_FILE_NAME,
+            self.member_name(member),
+            details.join("\n  ")
+        )))
+    }
+
+    /// Merge every member lock file into the shared lock file, after `install`
+    ///
+    /// Members must agree on the fingerprint of every service they lock. When they
+    /// do not, the conflicts are returned and the shared lock file is left as it was.
+    pub fn merge_member_locks(&self) -> Result<Vec<LockConflict>> {
+        let shared_path = self.lock_file_path();
+        let mut shared = if shared_path.exists() {
+            read_lock(&shared_path)?
+        } else {
+            LockFile::new()
+        };
+
+        let mut conflicts = Vec::new();
+        let mut merged: HashSet<String> = HashSet::new();
+        for member in &self.members {
+            let member_path = member.join(LOCK_FILE_NAME);
+            if !member_path.exists() {
+                continue;
+            }
+            let member_lock = read_lock(&member_path)?;
+            for locked in &member_lock.dependencies {
+                match shared.get_dependency(&locked.name) {
+                    Some(existing)
+                        if merged.contains(&locked.name)
+                            && existing.fingerprint != locked.fingerprint =>
+                    {
+                        conflicts.push(LockConflict {
+                            service: locked.name.clone(),
+                            member: self.member_name(member),
+                            shared_fingerprint: existing.fingerprint.clone(),
+                            member_fingerprint: locked.fingerprint.clone(),
+                        });
+                    }
+                    _ => {
+                        shared.add_dependency(locked.clone());
+                        merged.insert(locked.name.clone());
+                    }
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            shared.update_timestamp();
+            save_lock(&shared, &shared_path)?;
+        }
+        Ok(conflicts)
+    }
+
+    /// Dependency names of a member, read with the active `--env` profile and
+    /// `${VAR}` expansion
+    fn member_dependencies(&self, member: &Path) -> Result<Vec<String>> {
+        let config =
+            crate::core::profile::parse_config(&member.join(CONFIG_FILE_NAME)).map_err(|e| {
+                ActrCliError::config_error(format!(
+                    "Failed to parse {} in '{}': {e:#}",
+                    CONFIG_FILE_NAME,
+                    self.member_name(member)
+                ))
+            })?;
+        Ok(config
+            .dependencies
+            .into_iter()
+            .map(|dependency| dependency.name)
+            .collect())
+    }
+}
+
+/// Expand a member pattern; `*` matches any single directory name
+fn expand_member_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut current = vec![root.to_path_buf()];
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        let mut next = Vec::new();
+        for dir in &current {
+            if segment == "*" {
+                let Ok(entries) = std::fs::read_dir(dir) else {
+                    continue;
+                };
+                let mut children: Vec<PathBuf> = entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.is_dir())
+                    .filter(|path| {
+                        !path
+                            .file_name()
+                            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+                    })
+                    .collect();
+                children.sort();
+                next.extend(children);
+            } else {
+                let candidate = dir.join(segment);
+                if candidate.is_dir() {
+                    next.push(candidate);
+                }
+            }
+        }
+        current = next;
+    }
+    current
+}
+
+fn read_lock(path: &Path) -> Result<LockFile> {
+    LockFile::from_file(path)
+        .map_err(|e| ActrCliError::config_error(format!("Failed to parse {}: {e}", path.display())))
+}
+
+fn save_lock(lock_file: &LockFile, path: &Path) -> Result<()> {
+    lock_file
+        .save_to_file(path)
+        .map_err(|e| ActrCliError::config_error(format!("Failed to write {}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_member(root: &Path, rel: &str) {
+        let dir = root.join(rel);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(CONFIG_FILE_NAME), "").unwrap();
+    }
+
+    #[test]
+    fn test_load_expands_globs_and_excludes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write_member(root, "gateway");
+        write_member(root, "services/echo");
+        write_member(root, "services/legacy");
+        std::fs::create_dir_all(root.join("services/not-a-project")).unwrap();
+        std::fs::write(
+            root.join(WORKSPACE_FILE_NAME),
+            "[workspace]\nmembers = [\"gateway\", \"services/*\"]\nexclude = [\"services/legacy\"]\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::load(root).unwrap();
+        let names: Vec<String> = workspace
+            .members()
+            .iter()
+            .map(|m| workspace.member_name(m))
+            .collect();
+        assert_eq!(names, vec!["gateway", "services/echo"]);
+    }
+
+    #[test]
+    fn test_load_rejects_missing_member() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(WORKSPACE_FILE_NAME),
+            "[workspace]\nmembers = [\"missing\"]\n",
+        )
+        .unwrap();
+
+        assert!(Workspace::load(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_discover_walks_up() {
+        let temp = TempDir::new().unwrap();
+        write_member(temp.path(), "app");
+        std::fs::write(
+            temp.path().join(WORKSPACE_FILE_NAME),
+            "[workspace]\nmembers = [\"app\"]\n",
+        )
+        .unwrap();
+
+        let workspace = Workspace::discover(&temp.path().join("app"))
+            .unwrap()
+            .expect("workspace should be found");
+        assert_eq!(workspace.root(), temp.path());
+        assert_eq!(workspace.lock_file_path(), temp.path().join(LOCK_FILE_NAME));
+    }
+
+    fn locked(name: &str, fingerprint: &str) -> actr_config::LockedDependency {
+        actr_config::LockedDependency::new(
+            format!("acme+{name}"),
+            actr_config::ServiceSpecMeta {
+                name: name.to_string(),
+                description: None,
+                fingerprint: fingerprint.to_string(),
+                protobufs: Vec::new(),
+                published_at: None,
+                tags: Vec::new(),
+            },
+        )
+    }
+
+    fn write_lock(path: &Path, deps: &[(&str, &str)]) {
+        let mut lock = LockFile::new();
+        for (name, fingerprint) in deps {
+            lock.add_dependency(locked(name, fingerprint));
+        }
+        save_lock(&lock, path).unwrap();
+    }
+
+    /// Workspace with members `a` and `b`, both depending on `echo`
+    fn echo_workspace(root: &Path) -> Workspace {
+        for member in ["a", "b"] {
+            let dir = root.join(member);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(
+                dir.join(CONFIG_FILE_NAME),
+                "edition = 1\nexports = []\n\n[package]\nname = \"app\"\ndescription = \"Member\"\n[package.actr_type]\nmanufacturer = \"acme\"\nname = \"App\"\n\n[dependencies]\necho = { actr_type = \"acme+EchoService\" }\n\n[system.signaling]\nurl = \"ws://127.0.0.1:1/\"\n\n[system.deployment]\nrealm_id = 1\n\n[system.discovery]\nvisible = true\n",
+            )
+            .unwrap();
+        }
+        std::fs::write(
+            root.join(WORKSPACE_FILE_NAME),
+            "[workspace]\nmembers = [\"a\", \"b\"]\n",
+        )
+        .unwrap();
+        Workspace::load(root).unwrap()
+    }
+
+    #[test]
+    fn test_verify_member_lock_does_not_write() {
+        let temp = TempDir::new().unwrap();
+        let workspace = echo_workspace(temp.path());
+        write_lock(&workspace.lock_file_path(), &[("echo", "fp-1")]);
+
+        let member = temp.path().join("a");
+        assert!(workspace.verify_member_lock(&member).is_err());
+        assert!(!member.join(LOCK_FILE_NAME).exists());
+
+        write_lock(&member.join(LOCK_FILE_NAME), &[("echo", "fp-0")]);
+        let err = workspace.verify_member_lock(&member).unwrap_err();
+        assert!(err.to_string().contains("echo is locked at fp-1"), "{err}");
+
+        workspace.sync_member_lock(&member).unwrap();
+        workspace.verify_member_lock(&member).unwrap();
+    }
+
+    #[test]
+    fn test_merge_conflict_leaves_shared_lock_alone() {
+        let temp = TempDir::new().unwrap();
+        let workspace = echo_workspace(temp.path());
+        write_lock(&workspace.lock_file_path(), &[("echo", "fp-0")]);
+        write_lock(
+            &temp.path().join("a").join(LOCK_FILE_NAME),
+            &[("echo", "fp-1")],
+        );
+        write_lock(
+            &temp.path().join("b").join(LOCK_FILE_NAME),
+            &[("echo", "fp-2")],
+        );
+        let before = std::fs::read_to_string(workspace.lock_file_path()).unwrap();
+
+        let conflicts = workspace.merge_member_locks().unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].member, "b");
+        assert_eq!(
+            std::fs::read_to_string(workspace.lock_file_path()).unwrap(),
+            before
+        );
+
+        write_lock(
+            &temp.path().join("b").join(LOCK_FILE_NAME),
+            &[("echo", "fp-1")],
+        );
+        assert!(workspace.merge_member_locks().unwrap().is_empty());
+        let shared = read_lock(&workspace.lock_file_path()).unwrap();
+        assert_eq!(shared.get_dependency("echo").unwrap().fingerprint, "fp-1");
+    }
+}
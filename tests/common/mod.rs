@@ -0,0 +1,146 @@
+//! Shared setup for the CLI integration tests
+//!
+//! Each test runs the `actr` binary in a temporary project whose service
+//! discovery, network and fingerprint components answer from a fixture file
+//! (`--component ...=mock:fixture.json`), with `HOME` pointed into the project so
+//! the global proto cache and config stay out of the user's home.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// Echo service answered by [`FIXTURE`]
+pub const ECHO_PROTO: &str = r#"syntax = "proto3";
+package echo;
+
+message EchoRequest {
+  string message = 1;
+}
+
+message EchoResponse {
+  string reply = 1;
+}
+
+service EchoService {
+  rpc Echo (EchoRequest) returns (EchoResponse);
+}
+"#;
+
+/// Registry with one unsigned service, `echo-service` published by `acme`
+pub const FIXTURE: &str = r#"{
+  "services": [{
+    "name": "echo-service",
+    "actr_type": "acme+EchoService",
+    "fingerprint": "service_semantic:echo1",
+    "tags": ["latest"],
+    "protos": [{ "path": "fixture/echo.proto" }]
+  }],
+  "network": { "*": { "reachable": true, "latency_ms": 1 } }
+}"#;
+
+/// Actr.toml of a project depending on `echo-service`; `extra` is appended verbatim
+pub fn config(extra: &str) -> String {
+    format!(
+        r#"edition = 1
+exports = []
+
+[package]
+name = "test-app"
+description = "Integration test project"
+[package.actr_type]
+manufacturer = "test-company"
+name = "TestApp"
+
+[dependencies]
+echo-service = {{ actr_type = "acme+EchoService" }}
+
+[system.signaling]
+url = "ws://127.0.0.1:1/"
+
+[system.deployment]
+realm_id = 1001
+
+[system.discovery]
+visible = true
+{extra}"#
+    )
+}
+
+/// A temporary project with the echo fixture
+pub struct Project {
+    dir: TempDir,
+}
+
+impl Project {
+    /// Project with `config` as its Actr.toml
+    pub fn new(config: &str) -> Self {
+        let dir = TempDir::new().unwrap();
+        let project = Self { dir };
+        project.write("Actr.toml", config);
+        project.write("fixture/echo.proto", ECHO_PROTO);
+        project.write("fixture/fixture.json", FIXTURE);
+        project
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn file(&self, relative: &str) -> PathBuf {
+        self.dir.path().join(relative)
+    }
+
+    pub fn write(&self, relative: &str, contents: &str) {
+        let path = self.file(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    pub fn read(&self, relative: &str) -> String {
+        fs::read_to_string(self.file(relative)).unwrap()
+    }
+
+    /// `actr` with the fixture components, run in the project directory
+    pub fn command(&self) -> Command {
+        let fixture = self.file("fixture/fixture.json");
+        let mut command = Command::new(env!("CARGO_BIN_EXE_actr"));
+        command
+            .current_dir(self.path())
+            .env("HOME", self.file("home"))
+            .env_remove("ACTR_COMPONENTS")
+            .env("NO_COLOR", "1")
+            .arg(format!(
+                "--component=service-discovery=mock:{0},network-validator=mock:{0},fingerprint-validator=mock:{0}",
+                fixture.display()
+            ));
+        command
+    }
+
+    /// Run `actr <args>` and return its output
+    pub fn actr(&self, args: &[&str]) -> Output {
+        self.command().args(args).output().unwrap()
+    }
+}
+
+/// Panic with both streams unless `output` succeeded
+pub fn assert_success(output: &Output) {
+    assert!(
+        output.status.success(),
+        "actr failed with {}\nstdout:\n{}\nstderr:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Both streams of `output`, for asserting on messages wherever they went
+pub fn all_output(output: &Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
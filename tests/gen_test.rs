@@ -0,0 +1,166 @@
+//! `actr gen` end to end
+
+mod common;
+
+use common::{Project, all_output, assert_success, config};
+use std::time::{Duration, Instant};
+
+/// Install `protoc` as the project's protoc and an empty protoc-gen-es, returning the
+/// PATH that puts the fake protoc first
+#[cfg(unix)]
+fn fake_toolchain(project: &Project, protoc: &str) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    project.write("bin/protoc", protoc);
+    project.write("node_modules/.bin/protoc-gen-es", "#!/bin/sh\n");
+    for script in ["bin/protoc", "node_modules/.bin/protoc-gen-es"] {
+        std::fs::set_permissions(project.file(script), std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+    }
+    format!(
+        "{}:{}",
+        project.file("bin").display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+/// protoc answering descriptor requests with `$DESCRIPTOR` and writing one message
+/// module per `--es_out`, so TypeScript generation runs without a real toolchain
+#[cfg(unix)]
+const STUB_PROTOC: &str = r#"#!/bin/sh
+for arg in "$@"; do
+  case "$arg" in
+    --descriptor_set_out=*) cp "$DESCRIPTOR" "${arg#--descriptor_set_out=}" ;;
+    --es_out=*) mkdir -p "${arg#--es_out=}" && echo "// $ES_MESSAGES" > "${arg#--es_out=}/echo_pb.ts" ;;
+  esac
+done
+"#;
+
+/// Project with the echo service installed and [`STUB_PROTOC`] set up, and the
+/// environment `actr gen` needs to use it
+#[cfg(unix)]
+fn stub_project() -> (Project, Vec<(&'static str, String)>) {
+    use prost::Message;
+
+    let project = Project::new(&config(""));
+    assert_success(&project.actr(&["install"]));
+
+    let descriptor = prost_types::FileDescriptorSet {
+        file: vec![prost_types::FileDescriptorProto {
+            name: Some("echo.proto".to_string()),
+            package: Some("echo".to_string()),
+            syntax: Some("proto3".to_string()),
+            ..Default::default()
+        }],
+    };
+    std::fs::write(project.file("fixture/echo.pb"), descriptor.encode_to_vec()).unwrap();
+
+    let path = fake_toolchain(&project, STUB_PROTOC);
+    let env = vec![
+        ("PATH", path),
+        (
+            "DESCRIPTOR",
+            project.file("fixture/echo.pb").display().to_string(),
+        ),
+        ("ES_MESSAGES", "echo messages v1".to_string()),
+    ];
+    (project, env)
+}
+
+/// `actr gen -l typescript` into `generated/` with `args` appended
+#[cfg(unix)]
+fn gen_typescript(
+    project: &Project,
+    env: &[(&'static str, String)],
+    args: &[&str],
+) -> std::process::Output {
+    project
+        .command()
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .args(["gen", "-l", "typescript", "--no-format", "-o", "generated"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_passes_on_fresh_output() {
+    let (project, env) = stub_project();
+    assert_success(&gen_typescript(&project, &env, &[]));
+    let index = project.read("generated/index.ts");
+
+    let output = gen_typescript(&project, &env, &["--check"]);
+    assert_success(&output);
+    assert!(
+        all_output(&output).contains("is up to date with the protos"),
+        "{}",
+        all_output(&output)
+    );
+    // The check never touches the committed output
+    assert_eq!(project.read("generated/index.ts"), index);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_check_fails_and_lists_drifted_files() {
+    let (project, mut env) = stub_project();
+    assert_success(&gen_typescript(&project, &env, &[]));
+    std::fs::remove_file(project.file("generated/actr-config.ts")).unwrap();
+    env.retain(|(key, _)| *key != "ES_MESSAGES");
+    env.push(("ES_MESSAGES", "echo messages v2".to_string()));
+
+    let output = gen_typescript(&project, &env, &["--check"]);
+    assert!(!output.status.success());
+    let all = all_output(&output);
+    assert!(all.contains("is out of date"), "{all}");
+    assert!(all.contains("actr-config.ts"), "{all}");
+    assert!(all.contains("echo_pb.ts"), "{all}");
+    assert!(project.read("generated/echo_pb.ts").contains("v1"));
+    assert!(!project.file("generated/actr-config.ts").exists());
+}
+
+/// Whether process `pid` still runs (zombies waiting to be reaped count as gone)
+#[cfg(target_os = "linux")]
+fn is_running(pid: &str) -> bool {
+    std::fs::read_to_string(format!("/proc/{pid}/status"))
+        .is_ok_and(|status| !status.lines().any(|line| line.starts_with("State:\tZ")))
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_hanging_protoc_is_killed_at_timeout() {
+    let project = Project::new(&config(""));
+    assert_success(&project.actr(&["install"]));
+
+    // protoc that never returns
+    let pid_file = project.file("protoc.pid");
+    let path = fake_toolchain(
+        &project,
+        "#!/bin/sh\necho $$ > \"$PID_FILE\"\nexec sleep 60\n",
+    );
+
+    let started = Instant::now();
+    let output = project
+        .command()
+        .env("PATH", path)
+        .env("PID_FILE", &pid_file)
+        .args(["--timeout", "2", "gen", "-l", "typescript", "--no-format"])
+        .output()
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(30));
+    assert!(!output.status.success());
+    assert!(
+        all_output(&output).contains("Timed out after 2s"),
+        "{}",
+        all_output(&output)
+    );
+
+    let pid = std::fs::read_to_string(&pid_file).unwrap();
+    let pid = pid.trim();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while is_running(pid) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    assert!(!is_running(pid), "protoc {pid} outlived the deadline");
+}
@@ -0,0 +1,46 @@
+//! `--json` keeps stdout to the one result document
+
+mod common;
+
+use common::{Project, assert_success, config};
+use std::process::Output;
+
+/// The single JSON document on stdout; progress output must have gone to stderr
+fn document(output: &Output) -> serde_json::Value {
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "stdout is not one JSON document ({e}):\n{}",
+            String::from_utf8_lossy(&output.stdout)
+        )
+    })
+}
+
+#[test]
+fn test_install_update_remove_print_only_json_on_stdout() {
+    let project = Project::new(&config(""));
+
+    for (args, command) in [
+        (&["--json", "install"][..], "install"),
+        (&["--json", "update"][..], "update"),
+        (&["--json", "remove", "echo-service"][..], "remove"),
+    ] {
+        let output = project.actr(args);
+        assert_success(&output);
+        let document = document(&output);
+        assert_eq!(document["status"], "success", "{document}");
+        assert_eq!(document["command"], command);
+    }
+    assert!(!project.read("Actr.toml").contains("echo-service"));
+}
+
+#[test]
+fn test_failed_install_prints_error_document() {
+    let project = Project::new(&config(""));
+
+    let output = project.actr(&["--json", "install", "missing-service"]);
+    assert!(!output.status.success());
+    let document = document(&output);
+    assert_eq!(document["status"], "error", "{document}");
+    assert_eq!(document["command"], "install");
+    assert!(document["error"]["code"].is_string());
+}
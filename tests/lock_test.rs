@@ -0,0 +1,41 @@
+//! The project lock (`.actr/lock`) held by mutating commands
+
+mod common;
+
+use common::{Project, all_output, assert_success, config};
+use fs2::FileExt;
+
+#[test]
+fn test_install_fails_while_another_process_holds_the_lock() {
+    let project = Project::new(&config(""));
+    std::fs::create_dir_all(project.file(".actr")).unwrap();
+    let lock = std::fs::File::create(project.file(".actr/lock")).unwrap();
+    lock.try_lock_exclusive().unwrap();
+
+    let output = project.actr(&["--lock-timeout", "0", "install"]);
+    assert!(!output.status.success());
+    assert!(
+        all_output(&output).contains("ACTR-E5005"),
+        "{}",
+        all_output(&output)
+    );
+    assert!(!project.file("Actr.lock.toml").exists());
+
+    // Read-only commands do not wait for the lock
+    assert_success(&project.actr(&["--lock-timeout", "0", "install", "--dry-run"]));
+
+    FileExt::unlock(&lock).unwrap();
+    assert_success(&project.actr(&["--lock-timeout", "0", "install"]));
+    assert!(project.read("Actr.lock.toml").contains("echo-service"));
+}
+
+#[test]
+fn test_no_lock_skips_the_project_lock() {
+    let project = Project::new(&config(""));
+    std::fs::create_dir_all(project.file(".actr")).unwrap();
+    let lock = std::fs::File::create(project.file(".actr/lock")).unwrap();
+    lock.try_lock_exclusive().unwrap();
+
+    assert_success(&project.actr(&["--no-lock", "install"]));
+    assert!(project.read("Actr.lock.toml").contains("echo-service"));
+}
@@ -0,0 +1,32 @@
+//! Exit codes and `--json` documents of `actr run`
+
+mod common;
+
+use common::{Project, config};
+
+const FAILING_SCRIPT: &str = "\n[scripts]\nfail = \"exit 3\"\n";
+
+#[test]
+fn test_json_run_prints_document_before_exiting_with_script_code() {
+    let project = Project::new(&config(FAILING_SCRIPT));
+
+    let output = project.actr(&["--json", "run", "fail"]);
+    assert_eq!(output.status.code(), Some(3));
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "stdout is not a JSON document ({e}):\n{}",
+            String::from_utf8_lossy(&output.stdout)
+        )
+    });
+    assert_eq!(document["status"], "error");
+    assert_eq!(document["command"], "run");
+    assert_eq!(document["error"]["code"], "ACTR-E5008");
+}
+
+#[test]
+fn test_run_exits_with_script_code() {
+    let project = Project::new(&config(FAILING_SCRIPT));
+
+    let output = project.actr(&["run", "fail"]);
+    assert_eq!(output.status.code(), Some(3));
+}
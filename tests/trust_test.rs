@@ -0,0 +1,44 @@
+//! `[trust]` enforcement of `actr install` and `actr update`
+
+mod common;
+
+use common::{Project, all_output, assert_success, config};
+
+const REQUIRE_SIGNATURES: &str = "\n[trust]\nrequire_signatures = true\n";
+
+#[test]
+fn test_update_rejects_unsigned_service() {
+    let project = Project::new(&config(REQUIRE_SIGNATURES));
+
+    let output = project.actr(&["update"]);
+    assert!(!output.status.success());
+    assert!(
+        all_output(&output).contains("Signature verification failed for echo-service"),
+        "{}",
+        all_output(&output)
+    );
+    assert!(!project.file("Actr.lock.toml").exists());
+
+    let output = project.actr(&["update", "--allow-unsigned"]);
+    assert_success(&output);
+    assert!(project.read("Actr.lock.toml").contains("echo-service"));
+}
+
+#[test]
+fn test_install_rejects_unsigned_service() {
+    let project = Project::new(&config(REQUIRE_SIGNATURES));
+
+    let output = project.actr(&["install"]);
+    assert!(!output.status.success());
+    assert!(
+        all_output(&output).contains("Signature verification failed for echo-service"),
+        "{}",
+        all_output(&output)
+    );
+    assert!(!project.file("Actr.lock.toml").exists());
+    assert!(!project.file("protos/remote").exists());
+
+    let output = project.actr(&["install", "--allow-unsigned"]);
+    assert_success(&output);
+    assert!(project.read("Actr.lock.toml").contains("echo-service"));
+}